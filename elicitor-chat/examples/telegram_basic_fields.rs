@@ -0,0 +1,15 @@
+//! Basic field types through a Telegram bot
+//!
+//! Create a bot with @BotFather, then send it `/start` once the example runs.
+//!
+//! Run with: TELEGRAM_BOT_TOKEN=... cargo run -p elicitor-chat --features telegram --example telegram_basic_fields
+
+use elicitor_chat::{ChatBackend, TelegramTransport};
+use example_surveys::BasicFields;
+
+fn main() -> anyhow::Result<()> {
+    let transport = TelegramTransport::first_chat(std::env::var("TELEGRAM_BOT_TOKEN")?)?;
+    let result = BasicFields::builder().run(ChatBackend::new(transport))?;
+    println!("{result:#?}");
+    Ok(())
+}
@@ -0,0 +1,475 @@
+//! Chat backend implementation for SurveyBackend trait.
+
+use std::sync::Mutex;
+
+use elicitor::{
+    Conversation, FieldHandling, Messages, NoObserver, Notice, PrivacyReport, QuestionKind,
+    QuestionStep, ResponsePath, ResponseValue, Responses, SensitiveField, SensitiveSink,
+    SurveyBackend, SurveyDefinition, SurveyError, SurveyObserver, converse,
+};
+use thiserror::Error;
+
+use crate::reply::{CANCEL_COMMAND, DONE_REPLY, choose, options, parse, parse_list, shown};
+use crate::transport::{ChatMessage, ChatTransport};
+
+/// Error type for the chat backend.
+#[derive(Debug, Error)]
+pub enum ChatError<E> {
+    /// The respondent sent `/cancel`, left the conversation or stopped replying.
+    ///
+    /// Holds the answers given before that.
+    #[error("Survey cancelled by user")]
+    Cancelled { partial: Box<Responses> },
+
+    /// The survey has a masked question, whose answer would stay in the chat history.
+    ///
+    /// Allow such questions with [`ChatBackend::with_masked_answers`].
+    #[error("{0} is masked, but chat replies stay in the conversation")]
+    MaskedQuestion(ResponsePath),
+
+    /// Sending or receiving a message failed.
+    #[error("Messenger error: {0}")]
+    Transport(#[source] E),
+}
+
+impl<E> ChatError<E> {
+    /// The answers given before the respondent cancelled, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            Self::MaskedQuestion(_) | Self::Transport(_) => None,
+        }
+    }
+
+    /// A cancellation whose partial answers are filled in by `collect`.
+    fn cancelled() -> Self {
+        Self::Cancelled {
+            partial: Box::default(),
+        }
+    }
+}
+
+impl<E: std::error::Error + Send + Sync + 'static> From<ChatError<E>> for SurveyError {
+    fn from(err: ChatError<E>) -> Self {
+        match err {
+            ChatError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
+        }
+    }
+}
+
+/// Chat backend, asking one question per message through a [`ChatTransport`].
+///
+/// Choices come with a keyboard of their options, defaults are offered as a button,
+/// and lists and multi-selects are collected over several replies until the
+/// respondent sends `Done`. Rejected answers are explained and asked again. Sending
+/// `/cancel` cancels the survey.
+///
+/// Replies stay in the conversation, so surveys with masked questions such as
+/// passwords fail with [`ChatError::MaskedQuestion`] unless they are allowed with
+/// [`with_masked_answers`](Self::with_masked_answers).
+pub struct ChatBackend<T> {
+    transport: Mutex<T>,
+    messages: Messages,
+    masked_answers: bool,
+}
+
+impl<T> std::fmt::Debug for ChatBackend<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatBackend").finish_non_exhaustive()
+    }
+}
+
+impl<T: ChatTransport> ChatBackend<T> {
+    /// Ask surveys through `transport`.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport: Mutex::new(transport),
+            messages: Messages::default(),
+            masked_answers: false,
+        }
+    }
+
+    /// Explain rejected replies in another language, e.g. `Messages::translated(&german)`.
+    pub fn with_messages(mut self, messages: Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Ask masked questions too, although their answers stay in the chat history.
+    pub fn with_masked_answers(mut self) -> Self {
+        self.masked_answers = true;
+        self
+    }
+
+    /// The transport, e.g. to ask the same respondent something else afterwards.
+    pub fn into_transport(self) -> T {
+        self.transport
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: ChatTransport> SurveyBackend for ChatBackend<T> {
+    type Error = ChatError<T::Error>;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        if !self.masked_answers
+            && let Some(field) = PrivacyReport::new(definition).fields().first()
+        {
+            return Err(ChatError::MaskedQuestion(field.path.clone()));
+        }
+
+        let mut transport = self.transport.lock().unwrap_or_else(|e| e.into_inner());
+        let mut chat = Chat {
+            transport: &mut *transport,
+            messages: &self.messages,
+        };
+        converse(&mut chat, definition, validate, observer)
+    }
+}
+
+/// Answers to masked questions are sent as chat replies, if they are asked at all.
+impl<T> SensitiveSink for ChatBackend<T> {
+    fn sink_name(&self) -> String {
+        "chat history".to_string()
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        if self.masked_answers {
+            FieldHandling::Plaintext
+        } else {
+            FieldHandling::Omitted
+        }
+    }
+}
+
+/// The conversation, with the transport's errors wrapped.
+struct Chat<'a, T> {
+    transport: &'a mut T,
+    messages: &'a Messages,
+}
+
+impl<T: ChatTransport> Chat<'_, T> {
+    fn send(&mut self, message: ChatMessage) -> Result<(), ChatError<T::Error>> {
+        self.transport.send(&message).map_err(ChatError::Transport)
+    }
+
+    /// The next reply. `/cancel` and the end of the conversation cancel the survey.
+    fn receive(&mut self) -> Result<String, ChatError<T::Error>> {
+        match self.transport.receive().map_err(ChatError::Transport)? {
+            Some(reply) if !reply.trim().eq_ignore_ascii_case(CANCEL_COMMAND) => {
+                Ok(reply.trim().to_string())
+            }
+            _ => Err(ChatError::cancelled()),
+        }
+    }
+}
+
+impl<T: ChatTransport> Conversation for Chat<'_, T> {
+    type Error = ChatError<T::Error>;
+
+    fn show(&mut self, text: &str, _notice: Notice) -> Result<(), Self::Error> {
+        self.send(ChatMessage::text(text))
+    }
+
+    fn ask(
+        &mut self,
+        step: &QuestionStep,
+        responses: &Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<ResponseValue, Self::Error> {
+        ask(self, step, responses, validate)
+    }
+
+    fn interrupted(&mut self, error: Self::Error, partial: Responses) -> Self::Error {
+        match error {
+            ChatError::Cancelled { .. } => ChatError::Cancelled {
+                partial: Box::new(partial),
+            },
+            error => error,
+        }
+    }
+}
+
+/// Ask `step` until the answer passes validation.
+fn ask<T: ChatTransport>(
+    chat: &mut Chat<'_, T>,
+    step: &QuestionStep,
+    responses: &Responses,
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+) -> Result<ResponseValue, ChatError<T::Error>> {
+    let default = step.suggestion.clone().or_else(|| kind_default(&step.kind));
+
+    loop {
+        let parsed = match &step.kind {
+            QuestionKind::List(list_q) => {
+                let prompt = format!("{}\n(one item per message, then {DONE_REPLY})", step.prompt);
+                chat.send(ChatMessage::with_options(prompt, options(&step.kind, None)))?;
+                let items = read_items(chat)?;
+                parse_list(list_q, &items).and_then(|value| {
+                    let count = items.len();
+                    match (list_q.min_items, list_q.max_items) {
+                        (Some(min), _) if count < min => Err(format!("Send at least {min} items")),
+                        (_, Some(max)) if count > max => Err(format!("Send at most {max} items")),
+                        _ => Ok(value),
+                    }
+                })
+            }
+            QuestionKind::AnyOf(any_q) => {
+                let names: Vec<&str> = any_q.variants.iter().map(|v| v.name.as_str()).collect();
+                let mut chosen = match &default {
+                    Some(ResponseValue::ChosenVariants(indices)) => indices.clone(),
+                    _ => Vec::new(),
+                };
+                let keyboard = options(&step.kind, None);
+                let prompt = format!("{}\n(tap to select, then {DONE_REPLY})", step.prompt);
+                chat.send(ChatMessage::with_options(prompt, keyboard.clone()))?;
+                loop {
+                    let reply = chat.receive()?;
+                    if reply.eq_ignore_ascii_case(DONE_REPLY) {
+                        break;
+                    }
+                    let toggled = reply
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|part| !part.is_empty())
+                        .map(|part| choose(part, &names))
+                        .collect::<Result<Vec<_>, _>>();
+                    let text = match toggled {
+                        Ok(toggled) => {
+                            for idx in toggled {
+                                match chosen.iter().position(|&c| c == idx) {
+                                    Some(pos) => {
+                                        chosen.remove(pos);
+                                    }
+                                    None => chosen.push(idx),
+                                }
+                            }
+                            chosen.sort_unstable();
+                            selection(&chosen, &names)
+                        }
+                        Err(msg) => format!("Error: {msg}"),
+                    };
+                    chat.send(ChatMessage::with_options(text, keyboard.clone()))?;
+                }
+                match any_q.max_selected {
+                    Some(max) if chosen.len() > max => Err(format!("Select at most {max} options")),
+                    _ => Ok(ResponseValue::ChosenVariants(chosen)),
+                }
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                let mut columns = Vec::with_capacity(matrix_q.rows.len());
+                let mut parsed = Ok(());
+                for row in &matrix_q.rows {
+                    chat.send(ChatMessage::with_options(
+                        format!("{}\n{row}", step.prompt),
+                        options(&step.kind, None),
+                    ))?;
+                    match choose(&chat.receive()?, &matrix_q.columns) {
+                        Ok(column) => columns.push(column),
+                        Err(msg) => {
+                            parsed = Err(msg);
+                            break;
+                        }
+                    }
+                }
+                parsed.map(|()| ResponseValue::ChosenVariants(columns))
+            }
+            kind => {
+                chat.send(ChatMessage::with_options(
+                    step.prompt.clone(),
+                    options(kind, default.as_ref()),
+                ))?;
+                let reply = chat.receive()?;
+                parse(kind, &reply, chat.messages)
+            }
+        };
+
+        // Multi-selects are validated at the question, like in the other backends
+        let checked = parsed.and_then(|value| match step.kind {
+            QuestionKind::OneOf(_) => Ok(value),
+            QuestionKind::AnyOf(_) => {
+                validate(&value, responses, &step.path.parent()).map(|()| value)
+            }
+            _ => validate(&value, responses, &step.path).map(|()| value),
+        });
+        match checked {
+            Ok(value) => return Ok(value),
+            Err(msg) => chat.send(ChatMessage::text(format!("Error: {msg}")))?,
+        }
+    }
+}
+
+/// Replies up to the next `Done`, one item each.
+fn read_items<T: ChatTransport>(
+    chat: &mut Chat<'_, T>,
+) -> Result<Vec<String>, ChatError<T::Error>> {
+    let mut items = Vec::new();
+    loop {
+        let reply = chat.receive()?;
+        if reply.eq_ignore_ascii_case(DONE_REPLY) {
+            return Ok(items);
+        }
+        items.push(reply);
+    }
+}
+
+/// The current selection of a multi-select, as confirmed to the respondent.
+fn selection(chosen: &[usize], names: &[&str]) -> String {
+    if chosen.is_empty() {
+        "Nothing selected".to_string()
+    } else {
+        let selected: Vec<&str> = chosen.iter().map(|&idx| names[idx]).collect();
+        format!("Selected: {}", selected.join(", "))
+    }
+}
+
+/// The default configured on the question kind, offered when no suggestion is given.
+fn kind_default(kind: &QuestionKind) -> Option<ResponseValue> {
+    let default = match kind {
+        QuestionKind::Input(q) => q.default.clone().map(ResponseValue::String),
+        QuestionKind::Multiline(q) => q.default.clone().map(ResponseValue::String),
+        QuestionKind::Path(q) => q.default.clone().map(ResponseValue::String),
+        QuestionKind::Int(q) => q.default.map(ResponseValue::Int),
+        QuestionKind::Currency(q) => q.default.map(ResponseValue::Int),
+        QuestionKind::Float(q) => q.default.map(ResponseValue::Float),
+        QuestionKind::Color(q) => q.default.map(|color| ResponseValue::String(color.to_hex())),
+        _ => None,
+    };
+    // Only values that can be sent back as a reply are worth offering
+    default.filter(|value| shown(kind, value).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{AnyOfQuestion, InputQuestion, IntQuestion, MaskedQuestion, Question, Variant};
+    use std::collections::VecDeque;
+
+    /// A respondent replying from a script, recording what the backend sent.
+    #[derive(Default)]
+    struct Scripted {
+        replies: VecDeque<&'static str>,
+        sent: Vec<ChatMessage>,
+    }
+
+    impl ChatTransport for Scripted {
+        type Error = std::io::Error;
+
+        fn send(&mut self, message: &ChatMessage) -> Result<(), Self::Error> {
+            self.sent.push(message.clone());
+            Ok(())
+        }
+
+        fn receive(&mut self) -> Result<Option<String>, Self::Error> {
+            Ok(self.replies.pop_front().map(str::to_string))
+        }
+    }
+
+    fn scripted(replies: &[&'static str]) -> ChatBackend<Scripted> {
+        ChatBackend::new(Scripted {
+            replies: replies.iter().copied().collect(),
+            sent: Vec::new(),
+        })
+    }
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new(
+                "age",
+                "Age?",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new(
+                "extras",
+                "Extras?",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![
+                    Variant::unit("Cheese"),
+                    Variant::unit("Ham"),
+                    Variant::unit("Olives"),
+                ])),
+            ),
+        ])
+    }
+
+    #[test]
+    fn respondent_answers_message_by_message() {
+        // The age is out of bounds at first, Ham is tapped twice and deselected again
+        let backend = scripted(&["200", "36", "Cheese", "ham", "olives", "Ham", "Done"]);
+        let responses = backend.collect(&definition(), &|_, _, _| Ok(())).unwrap();
+        assert_eq!(
+            responses.get(&ResponsePath::new("age")),
+            Some(&ResponseValue::Int(36))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("extras.selected_variants")),
+            Some(&ResponseValue::ChosenVariants(vec![0, 2]))
+        );
+
+        let sent = backend.into_transport().sent;
+        assert_eq!(sent[1].text, "Error: Value must be at most 150");
+        assert_eq!(sent[3].options, ["Cheese", "Ham", "Olives", "Done"]);
+        assert_eq!(sent[5].text, "Selected: Cheese, Ham");
+    }
+
+    #[test]
+    fn cancel_command_keeps_partial_answers() {
+        let backend = scripted(&["36", "/cancel"]);
+        let err = backend
+            .collect(&definition(), &|_, _, _| Ok(()))
+            .unwrap_err();
+        assert_eq!(
+            err.partial().unwrap().get(&ResponsePath::new("age")),
+            Some(&ResponseValue::Int(36))
+        );
+    }
+
+    #[test]
+    fn defaults_can_be_taken_with_a_tap() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "name",
+            "Name?",
+            QuestionKind::Input(InputQuestion::with_default("Ada")),
+        )]);
+        let backend = scripted(&["Ada"]);
+        backend.collect(&definition, &|_, _, _| Ok(())).unwrap();
+        assert_eq!(backend.into_transport().sent[0].options, ["Ada"]);
+    }
+
+    #[test]
+    fn masked_questions_need_to_be_allowed() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "pin",
+            "PIN?",
+            QuestionKind::Masked(MaskedQuestion::new()),
+        )]);
+        let backend = scripted(&["1234"]);
+        let err = backend.collect(&definition, &|_, _, _| Ok(())).unwrap_err();
+        assert!(matches!(err, ChatError::MaskedQuestion(path) if path == ResponsePath::new("pin")));
+        assert!(backend.into_transport().sent.is_empty());
+
+        let backend = scripted(&["1234"]).with_masked_answers();
+        let responses = backend.collect(&definition, &|_, _, _| Ok(())).unwrap();
+        assert!(responses.get(&ResponsePath::new("pin")).is_some());
+
+        let report = PrivacyReport::new(&definition).with_sink(&backend);
+        assert_eq!(
+            report.handling(&ResponsePath::new("pin"), "chat history"),
+            Some(FieldHandling::Plaintext)
+        );
+    }
+}
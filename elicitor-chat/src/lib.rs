@@ -0,0 +1,41 @@
+//! # elicitor-chat
+//!
+//! Chat backend for elicitor.
+//!
+//! The backend asks a survey as a conversation: every question is a message, the
+//! respondent's reply is the answer. It fits data collection through messengers,
+//! where people are used to answering a bot one message at a time.
+//!
+//! ## Features
+//!
+//! - Choices, yes/no questions and rating scales come with a keyboard of their options
+//! - Defaults are offered as a button, so they can be taken with one tap
+//! - Multi-selects are toggled one tap at a time, lists are sent one item per message,
+//!   both until the respondent sends `Done`
+//! - Rejected answers are explained and asked again, `/cancel` cancels the survey
+//! - Masked questions are refused unless allowed, as their answers stay in the chat
+//! - Any messenger through the [`ChatTransport`] trait, Telegram with the `telegram`
+//!   feature
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use elicitor_chat::{ChatBackend, TelegramTransport};
+//!
+//! // Waits for someone to send /start to the bot, then asks them
+//! let transport = TelegramTransport::first_chat(std::env::var("TELEGRAM_BOT_TOKEN")?)?
+//!     .with_reply_timeout(std::time::Duration::from_secs(600));
+//! let config = Config::builder().run(ChatBackend::new(transport))?;
+//! ```
+
+mod backend;
+mod reply;
+#[cfg(feature = "telegram")]
+mod telegram;
+mod transport;
+
+pub use backend::{ChatBackend, ChatError};
+pub use reply::{CANCEL_COMMAND, DONE_REPLY};
+#[cfg(feature = "telegram")]
+pub use telegram::{TelegramError, TelegramTransport};
+pub use transport::{ChatMessage, ChatTransport};
@@ -0,0 +1,229 @@
+//! Keyboards offered with a question and parsing of the respondent's replies.
+
+use elicitor::{ListElementKind, ListQuestion, Messages, QuestionKind, ResponseValue};
+
+/// Reply that ends a list or a multi-select.
+pub const DONE_REPLY: &str = "Done";
+
+/// Reply that cancels the survey.
+pub const CANCEL_COMMAND: &str = "/cancel";
+
+/// How many completions of an autocomplete question are offered as buttons.
+const MAX_COMPLETIONS: usize = 8;
+
+/// The replies offered as buttons for a question of `kind`.
+///
+/// `default` is offered as a button of its own for questions without fixed choices,
+/// so the respondent can take it with one tap.
+pub(crate) fn options(kind: &QuestionKind, default: Option<&ResponseValue>) -> Vec<String> {
+    match kind {
+        QuestionKind::Confirm(_) => vec!["Yes".to_string(), "No".to_string()],
+        QuestionKind::Select(q) => q.options.iter().map(|o| o.label.clone()).collect(),
+        QuestionKind::Rating(q) => q.points().map(|point| point.to_string()).collect(),
+        QuestionKind::OneOf(q) => q.variants.iter().map(|v| v.name.clone()).collect(),
+        QuestionKind::AnyOf(q) => q
+            .variants
+            .iter()
+            .map(|v| v.name.clone())
+            .chain([DONE_REPLY.to_string()])
+            .collect(),
+        QuestionKind::Matrix(q) => q.columns.clone(),
+        QuestionKind::List(_) => vec![DONE_REPLY.to_string()],
+        QuestionKind::Autocomplete(q) => q
+            .completions("")
+            .into_iter()
+            .take(MAX_COMPLETIONS)
+            .collect(),
+        QuestionKind::Masked(_) => Vec::new(),
+        kind => default
+            .and_then(|value| shown(kind, value))
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// How a default is shown, so that sending it back gives the same answer.
+pub(crate) fn shown(kind: &QuestionKind, value: &ResponseValue) -> Option<String> {
+    match (kind, value) {
+        (QuestionKind::Currency(q), ResponseValue::Int(minor)) => Some(q.format(*minor)),
+        (QuestionKind::Select(q), ResponseValue::String(v)) => {
+            q.position(v).map(|idx| q.options[idx].label.clone())
+        }
+        (QuestionKind::Confirm(_), ResponseValue::Bool(b)) => {
+            Some(if *b { "Yes" } else { "No" }.to_string())
+        }
+        (_, ResponseValue::String(s)) if !s.is_empty() => Some(s.clone()),
+        (_, ResponseValue::Int(n)) => Some(n.to_string()),
+        (_, ResponseValue::Float(f)) => Some(f.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a reply to a question of `kind`, checked against its constraints.
+///
+/// Lists, multi-selects and matrices are collected over several replies and are not
+/// parsed here. Replies to ratings and yes/no questions are explained in the language
+/// of `messages`.
+pub(crate) fn parse(
+    kind: &QuestionKind,
+    reply: &str,
+    messages: &Messages,
+) -> Result<ResponseValue, String> {
+    let value = match kind {
+        QuestionKind::Int(q) => {
+            let n = q.parse(reply)?;
+            q.check_bounds(n)?;
+            ResponseValue::Int(n)
+        }
+        QuestionKind::Float(q) => {
+            let f = q.parse(reply)?;
+            q.check_bounds(f)?;
+            ResponseValue::Float(f)
+        }
+        QuestionKind::Currency(q) => {
+            let minor = q.parse(reply)?;
+            q.check_bounds(minor)?;
+            ResponseValue::Int(minor)
+        }
+        QuestionKind::Rating(q) => {
+            let n = reply.parse().map_err(|_| messages.pick_point())?;
+            if !q.contains(n) {
+                return Err(messages.not_a_point(&n.to_string()));
+            }
+            ResponseValue::Int(n)
+        }
+        QuestionKind::Color(q) => ResponseValue::String(q.parse(reply)?.to_hex()),
+        QuestionKind::Path(q) => {
+            q.check(reply)?;
+            ResponseValue::String(reply.to_string())
+        }
+        QuestionKind::Confirm(_) => match reply.to_lowercase().as_str() {
+            "y" | "yes" | "true" => ResponseValue::Bool(true),
+            "n" | "no" | "false" => ResponseValue::Bool(false),
+            _ => return Err(messages.yes_or_no()),
+        },
+        QuestionKind::Select(q) => {
+            let labels: Vec<&str> = q.options.iter().map(|o| o.label.as_str()).collect();
+            let idx = q
+                .position(reply)
+                .map_or_else(|| choose(reply, &labels), Ok)?;
+            ResponseValue::String(q.options[idx].value.clone())
+        }
+        QuestionKind::OneOf(q) => {
+            let names: Vec<&str> = q.variants.iter().map(|v| v.name.as_str()).collect();
+            ResponseValue::ChosenVariant(choose(reply, &names)?)
+        }
+        _ => ResponseValue::String(reply.to_string()),
+    };
+    Ok(value)
+}
+
+/// Parse the items of a list question, one per reply.
+pub(crate) fn parse_list(list_q: &ListQuestion, items: &[String]) -> Result<ResponseValue, String> {
+    let messages = &list_q.messages;
+    match &list_q.element_kind {
+        ListElementKind::String => Ok(ResponseValue::StringList(items.to_vec())),
+        ListElementKind::Int { min, max } => items
+            .iter()
+            .map(|item| {
+                let n: i64 = item
+                    .parse()
+                    .map_err(|_| messages.item_not_integer(&format!("{item:?}")))?;
+                bounds(
+                    messages,
+                    n as f64,
+                    min.map(|m| m as f64),
+                    max.map(|m| m as f64),
+                )
+                .map(|()| n)
+            })
+            .collect::<Result<_, _>>()
+            .map(ResponseValue::IntList),
+        ListElementKind::Float { min, max } => items
+            .iter()
+            .map(|item| {
+                let f: f64 = item
+                    .parse()
+                    .map_err(|_| messages.item_not_number(&format!("{item:?}")))?;
+                bounds(messages, f, *min, *max).map(|()| f)
+            })
+            .collect::<Result<_, _>>()
+            .map(ResponseValue::FloatList),
+    }
+}
+
+/// The index of the choice named by `reply`, either its number or its name.
+pub(crate) fn choose(reply: &str, choices: &[impl AsRef<str>]) -> Result<usize, String> {
+    if let Ok(number) = reply.parse::<usize>() {
+        return number
+            .checked_sub(1)
+            .filter(|&idx| idx < choices.len())
+            .ok_or_else(|| format!("Send a number from 1 to {}", choices.len()));
+    }
+    choices
+        .iter()
+        .position(|choice| choice.as_ref().eq_ignore_ascii_case(reply))
+        .ok_or_else(|| format!("{reply:?} is not one of the choices"))
+}
+
+/// Check a list item against the bounds of the list's elements.
+fn bounds(
+    messages: &Messages,
+    value: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> Result<(), String> {
+    if let Some(min) = min.filter(|&min| value < min) {
+        Err(messages.at_least(&min.to_string()))
+    } else if let Some(max) = max.filter(|&max| value > max) {
+        Err(messages.at_most(&max.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{ConfirmQuestion, IntQuestion, RatingQuestion};
+
+    #[test]
+    fn replies_are_parsed_by_question_kind() {
+        let english = Messages::default();
+        let age = QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150)));
+        assert_eq!(parse(&age, "42", &english), Ok(ResponseValue::Int(42)));
+        assert!(parse(&age, "200", &english).is_err());
+        assert!(parse(&age, "old", &english).is_err());
+
+        let ok = QuestionKind::Confirm(ConfirmQuestion::with_default(true));
+        assert_eq!(parse(&ok, "Yes", &english), Ok(ResponseValue::Bool(true)));
+        assert_eq!(parse(&ok, "n", &english), Ok(ResponseValue::Bool(false)));
+        assert_eq!(
+            parse(&ok, "maybe", &english),
+            Err("Please answer yes or no".to_string())
+        );
+    }
+
+    #[test]
+    fn explanations_are_translated() {
+        let german = std::collections::HashMap::from([(
+            "elicitor.not-a-point".to_string(),
+            "{point} ist kein Punkt der Skala".to_string(),
+        )]);
+        let rating = QuestionKind::Rating(RatingQuestion::new(1, 5));
+        assert_eq!(
+            parse(&rating, "9", &Messages::translated(&german)),
+            Err("9 ist kein Punkt der Skala".to_string())
+        );
+    }
+
+    #[test]
+    fn defaults_are_offered_as_buttons() {
+        let age = QuestionKind::Int(IntQuestion::new());
+        assert_eq!(options(&age, Some(&ResponseValue::Int(30))), ["30"]);
+        assert!(options(&age, None).is_empty());
+
+        let rating = QuestionKind::Rating(RatingQuestion::new(1, 3));
+        assert_eq!(options(&rating, None), ["1", "2", "3"]);
+    }
+}
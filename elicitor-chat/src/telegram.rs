@@ -0,0 +1,182 @@
+//! Telegram transport, talking to the Bot API by long polling.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::transport::{ChatMessage, ChatTransport};
+
+/// How long one `getUpdates` call waits for new messages, in seconds.
+const POLL_SECONDS: u64 = 30;
+
+/// Error type for the Telegram transport.
+#[derive(Debug, Error)]
+pub enum TelegramError {
+    /// The request to the Bot API failed.
+    #[error("HTTP error: {0}")]
+    Http(#[from] ureq::Error),
+
+    /// The Bot API rejected the request, e.g. because of an invalid token.
+    #[error("Telegram API error: {0}")]
+    Api(String),
+}
+
+/// A conversation with one Telegram chat, through a bot.
+///
+/// Questions are sent as messages with a reply keyboard of their options, and the
+/// replies are fetched with long polling, so the bot needs no public address. Only
+/// one transport at a time may poll the updates of a bot.
+pub struct TelegramTransport {
+    agent: ureq::Agent,
+    token: String,
+    chat_id: i64,
+    offset: i64,
+    replies: VecDeque<String>,
+    reply_timeout: Option<Duration>,
+}
+
+impl std::fmt::Debug for TelegramTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The token grants control over the bot
+        f.debug_struct("TelegramTransport")
+            .field("chat_id", &self.chat_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TelegramTransport {
+    /// Talk to `chat_id` through the bot with `token`.
+    pub fn new(token: impl Into<String>, chat_id: i64) -> Self {
+        Self {
+            agent: ureq::Agent::new_with_defaults(),
+            token: token.into(),
+            chat_id,
+            offset: 0,
+            replies: VecDeque::new(),
+            reply_timeout: None,
+        }
+    }
+
+    /// Wait for the first message to the bot with `token`, e.g. `/start`, and talk to
+    /// the chat it came from.
+    pub fn first_chat(token: impl Into<String>) -> Result<Self, TelegramError> {
+        let mut transport = Self::new(token, 0);
+        loop {
+            if let Some((chat_id, _)) = transport.poll()?.into_iter().next() {
+                transport.chat_id = chat_id;
+                return Ok(transport);
+            }
+        }
+    }
+
+    /// Cancel the survey when the respondent does not reply within `timeout`.
+    pub fn with_reply_timeout(mut self, timeout: Duration) -> Self {
+        self.reply_timeout = Some(timeout);
+        self
+    }
+
+    /// The chat this transport talks to.
+    pub fn chat_id(&self) -> i64 {
+        self.chat_id
+    }
+
+    /// Call the Bot API method `method` and return its result.
+    fn call(&self, method: &str, params: Value) -> Result<Value, TelegramError> {
+        let url = format!("https://api.telegram.org/bot{}/{method}", self.token);
+        let response: Value = self
+            .agent
+            .post(&url)
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .send_json(params)?
+            .body_mut()
+            .read_json()?;
+        if response["ok"].as_bool() == Some(true) {
+            Ok(response["result"].clone())
+        } else {
+            Err(TelegramError::Api(
+                response["description"]
+                    .as_str()
+                    .unwrap_or("unknown error")
+                    .to_string(),
+            ))
+        }
+    }
+
+    /// The chats and texts of the messages that arrived since the last poll.
+    fn poll(&mut self) -> Result<Vec<(i64, String)>, TelegramError> {
+        let updates = self.call(
+            "getUpdates",
+            json!({
+                "offset": self.offset,
+                "timeout": POLL_SECONDS,
+                "allowed_updates": ["message"],
+            }),
+        )?;
+        let mut messages = Vec::new();
+        for update in updates.as_array().into_iter().flatten() {
+            if let Some(id) = update["update_id"].as_i64() {
+                self.offset = self.offset.max(id + 1);
+            }
+            let message = &update["message"];
+            if let (Some(chat_id), Some(text)) =
+                (message["chat"]["id"].as_i64(), message["text"].as_str())
+            {
+                messages.push((chat_id, text.to_string()));
+            }
+        }
+        Ok(messages)
+    }
+}
+
+impl ChatTransport for TelegramTransport {
+    type Error = TelegramError;
+
+    fn send(&mut self, message: &ChatMessage) -> Result<(), Self::Error> {
+        let reply_markup = if message.options.is_empty() {
+            json!({ "remove_keyboard": true })
+        } else {
+            let rows: Vec<Value> = message
+                .options
+                .iter()
+                .map(|option| json!([{ "text": option }]))
+                .collect();
+            json!({ "keyboard": rows, "one_time_keyboard": true, "resize_keyboard": true })
+        };
+        self.call(
+            "sendMessage",
+            json!({
+                "chat_id": self.chat_id,
+                "text": message.text,
+                "reply_markup": reply_markup,
+            }),
+        )?;
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Option<String>, Self::Error> {
+        let deadline = self.reply_timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            if let Some(reply) = self.replies.pop_front() {
+                return Ok(Some(reply));
+            }
+            // Messages from other chats are not part of this conversation
+            let chat_id = self.chat_id;
+            let messages = self.poll()?;
+            self.replies.extend(
+                messages
+                    .into_iter()
+                    .filter(|(id, _)| *id == chat_id)
+                    .map(|(_, text)| text),
+            );
+            if self.replies.is_empty()
+                && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                return Ok(None);
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! The messenger a survey is asked through.
+
+/// A message to the respondent, with the replies offered as buttons.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChatMessage {
+    /// The text of the message.
+    pub text: String,
+
+    /// Replies offered as a keyboard, e.g. the choices of a question. The
+    /// respondent may type a reply of their own instead.
+    pub options: Vec<String>,
+}
+
+impl ChatMessage {
+    /// A message without buttons.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            options: Vec::new(),
+        }
+    }
+
+    /// A message offering `options` as buttons.
+    pub fn with_options(text: impl Into<String>, options: Vec<String>) -> Self {
+        Self {
+            text: text.into(),
+            options,
+        }
+    }
+}
+
+/// A conversation with one respondent in a messenger.
+///
+/// Implement this to ask surveys through a chat service of your own: sending shows
+/// a message with its buttons, receiving waits for the respondent's next reply.
+pub trait ChatTransport: Send {
+    /// Error type of the messenger.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Send `message` to the respondent.
+    fn send(&mut self, message: &ChatMessage) -> Result<(), Self::Error>;
+
+    /// Wait for the next reply, a typed text or the label of a button.
+    ///
+    /// Returns `None` once the conversation is over, e.g. when the respondent left or
+    /// did not reply in time, which cancels the survey.
+    fn receive(&mut self) -> Result<Option<String>, Self::Error>;
+}
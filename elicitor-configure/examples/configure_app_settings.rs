@@ -0,0 +1,23 @@
+//! Configure subcommand example - write `AppSettings` to a config file.
+//!
+//! Run with: cargo run -p elicitor-configure --example configure_app_settings -- configure
+//!
+//! Try `-- configure --config settings.json` or, after a first run,
+//! `-- configure --non-interactive` to rewrite the config without prompting.
+//! After adding a field to `AppSettings`, `-- configure --upgrade` only asks for
+//! the new field.
+
+use example_surveys::AppSettings;
+
+fn main() -> anyhow::Result<()> {
+    if let Some(settings) =
+        elicitor_configure::configure_subcommand::<AppSettings>("app_settings.toml")
+    {
+        println!("{:#?}", settings?);
+        return Ok(());
+    }
+
+    let settings: AppSettings = elicitor_configure::load("app_settings.toml")?;
+    println!("Loaded settings: {settings:#?}");
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! `.env` example - configure a service and write its settings as environment variables.
+//!
+//! Run with: cargo run -p elicitor-configure --example configure_env_file
+//!
+//! Running it again merges into the existing `service.env`, keeping any
+//! variables that were added by hand.
+
+use elicitor::Survey;
+use elicitor_configure::{Configure, EnvFile};
+use serde::Serialize;
+
+#[derive(Survey, Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+#[derive(Survey, Serialize, Debug)]
+struct Database {
+    #[ask("Database URL:")]
+    url: String,
+
+    #[ask("Connection pool size:")]
+    #[min(1)]
+    #[max(64)]
+    pool_size: u32,
+}
+
+#[derive(Survey, Serialize, Debug)]
+struct ServiceConfig {
+    #[ask("Port to listen on:")]
+    #[min(1)]
+    port: u16,
+
+    #[ask("Log level:")]
+    log_level: LogLevel,
+
+    #[ask("Database:")]
+    database: Database,
+}
+
+fn main() -> anyhow::Result<()> {
+    let config: ServiceConfig = Configure::new("service.toml").run()?;
+
+    let env = EnvFile::new("service.env").with_prefix("BILLING_");
+    env.write(&config)?;
+    println!("Wrote {}:\n{}", env.path().display(), env.render(&config)?);
+    Ok(())
+}
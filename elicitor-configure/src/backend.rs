@@ -0,0 +1,187 @@
+//! Backend selection for the configure subcommand.
+
+use std::io::IsTerminal;
+
+use elicitor::{
+    AnyOfQuestion, DefaultValue, Messages, OneOfQuestion, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, Survey, SurveyBackend,
+    SurveyDefinition,
+};
+
+use crate::ConfigureError;
+use crate::document::{Visit, walk};
+
+/// A backend that answers every question with its suggested or assumed value.
+///
+/// Used for `--non-interactive` runs, where all answers come from an answers
+/// file or an existing config. Answers are validated like in interactive
+/// backends, and any question without a value is an error.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultsBackend;
+
+impl DefaultsBackend {
+    /// Create a new defaults backend.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl SurveyBackend for DefaultsBackend {
+    type Error = ConfigureError;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        let mut collector = DefaultsCollector {
+            validate,
+            responses: Responses::new(),
+        };
+        walk(
+            &definition.questions,
+            &ResponsePath::empty(),
+            &mut collector,
+        )?;
+        Ok(collector.responses)
+    }
+}
+
+struct DefaultsCollector<'a> {
+    validate: &'a dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    responses: Responses,
+}
+
+impl Visit for DefaultsCollector<'_> {
+    fn leaf(&mut self, question: &Question, path: &ResponsePath) -> Result<(), ConfigureError> {
+        let value = match question.default() {
+            DefaultValue::Suggested(value) | DefaultValue::Assumed(value) => value,
+            DefaultValue::None => {
+                return Err(ConfigureError::MissingAnswer(path.as_str().to_string()));
+            }
+        };
+
+        // Backends do not validate confirmations
+        if !matches!(question.kind(), QuestionKind::Confirm(_)) {
+            check_bounds(question.kind(), value)
+                .and_then(|()| (self.validate)(value, &self.responses, path))
+                .map_err(|message| ConfigureError::ValidationFailed {
+                    path: path.as_str().to_string(),
+                    message,
+                })?;
+        }
+        self.responses.insert(path.clone(), value.clone());
+        Ok(())
+    }
+
+    fn variant(
+        &mut self,
+        one_of: &OneOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Option<usize>, ConfigureError> {
+        let key = path.child(SELECTED_VARIANT_KEY);
+        let idx = one_of
+            .default
+            .ok_or_else(|| ConfigureError::MissingAnswer(key.as_str().to_string()))?;
+        self.responses
+            .insert(key, ResponseValue::ChosenVariant(idx));
+        Ok(Some(idx))
+    }
+
+    fn variants(
+        &mut self,
+        any_of: &AnyOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Vec<usize>, ConfigureError> {
+        self.responses.insert(
+            path.child(SELECTED_VARIANTS_KEY),
+            ResponseValue::ChosenVariants(any_of.defaults.clone()),
+        );
+        Ok(any_of.defaults.clone())
+    }
+}
+
+/// Check the `#[min]`/`#[max]` and rating scale bounds that interactive backends enforce while prompting.
+fn check_bounds(kind: &QuestionKind, value: &ResponseValue) -> Result<(), String> {
+    match (kind, value) {
+        (QuestionKind::Currency(currency_q), ResponseValue::Int(value)) => {
+            currency_q.check_bounds(*value)
+        }
+        (QuestionKind::Int(int_q), ResponseValue::Int(value)) => int_q.check_bounds(*value),
+        (QuestionKind::Float(float_q), ResponseValue::Float(value)) => float_q.check_bounds(*value),
+        (QuestionKind::Rating(rating_q), ResponseValue::Int(value)) => {
+            // A scale has no translated messages, its points are picked rather than typed
+            let messages = Messages::default();
+            if *value < rating_q.min {
+                Err(messages.at_least(&rating_q.min.to_string()))
+            } else if *value > rating_q.max {
+                Err(messages.at_most(&rating_q.max.to_string()))
+            } else {
+                Ok(())
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether both stdin and stdout are attached to a terminal.
+pub(crate) fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Collect responses with the first interactive backend enabled by a feature.
+///
+/// Backends are preferred in the order requestty, dialoguer, ratatui.
+#[allow(
+    unreachable_code,
+    unused_variables,
+    clippy::extra_unused_type_parameters
+)]
+pub(crate) fn collect_interactive<T: Survey>(
+    definition: &SurveyDefinition,
+    known: &Responses,
+) -> Result<Responses, ConfigureError> {
+    #[cfg(feature = "requestty")]
+    return collect::<T>(
+        elicitor_wizard_requestty::RequesttyBackend::new(),
+        definition,
+        known,
+    );
+
+    #[cfg(feature = "dialoguer")]
+    return collect::<T>(
+        elicitor_wizard_dialoguer::DialoguerBackend::new(),
+        definition,
+        known,
+    );
+
+    #[cfg(feature = "ratatui")]
+    return collect::<T>(
+        elicitor_wizard_ratatui::RatatuiBackend::new(),
+        definition,
+        known,
+    );
+
+    Err(ConfigureError::NoInteractiveBackend)
+}
+
+/// Collect responses for `T` with the given backend.
+///
+/// `known` holds answers that are not asked again. Validators see them as if
+/// they had been collected, so cross-field checks still work.
+pub(crate) fn collect<T: Survey>(
+    backend: impl SurveyBackend,
+    definition: &SurveyDefinition,
+    known: &Responses,
+) -> Result<Responses, ConfigureError> {
+    backend
+        .collect(definition, &|value, responses, path| {
+            if known.is_empty() {
+                return T::validate_field(value, responses, path);
+            }
+            let mut all = known.clone();
+            all.extend(responses.clone());
+            T::validate_field(value, &all, path)
+        })
+        .map_err(|err| ConfigureError::Backend(err.into()))
+}
@@ -0,0 +1,316 @@
+//! The `configure` command.
+
+use std::path::{Path, PathBuf};
+
+use elicitor::{
+    FieldHandling, ResponsePath, Responses, SensitiveField, SensitiveSink, Survey, SurveyBackend,
+    SurveyDefinition,
+};
+
+use crate::backend::{self, DefaultsBackend};
+use crate::document::{
+    assume_answers, read_answers, retain_unanswered, suggest_answers, unanswered, write_answers,
+};
+use crate::{ConfigFormat, ConfigureError};
+
+/// Runs a survey and writes the answers to a config file.
+///
+/// Answers from an existing config file and from an answers file are offered as
+/// suggestions, so re-running `configure` only asks the user to confirm them.
+/// In non-interactive mode they are used as-is and every question needs one.
+///
+/// After the config type gained new fields, an upgrade run
+/// ([`with_upgrade`](Self::with_upgrade)) keeps the existing answers and only
+/// asks for what the config file does not answer yet.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let config: AppConfig = Configure::new("app.toml")
+///     .with_answers("defaults.json")
+///     .run()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Configure {
+    path: PathBuf,
+    format: Option<ConfigFormat>,
+    answers: Option<PathBuf>,
+    non_interactive: bool,
+    upgrade: bool,
+}
+
+impl Configure {
+    /// Configure into the config file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            format: None,
+            answers: None,
+            non_interactive: false,
+            upgrade: false,
+        }
+    }
+
+    /// Parse the arguments following the `configure` subcommand.
+    ///
+    /// Accepts `--config <path>`, `--format toml|json`, `--answers <path>`,
+    /// `--non-interactive` and `--upgrade`. Without `--config`, `default_path` is used.
+    pub fn from_args<I, S>(
+        default_path: impl Into<PathBuf>,
+        args: I,
+    ) -> Result<Self, ConfigureError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut configure = Self::new(default_path);
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ConfigureError::Usage(format!("missing value for `{flag}`")))
+            };
+
+            match flag.as_str() {
+                "--config" | "-c" => configure.path = PathBuf::from(value()?),
+                "--answers" | "-a" => configure.answers = Some(PathBuf::from(value()?)),
+                "--format" | "-f" => {
+                    let name = value()?;
+                    let format = ConfigFormat::from_name(&name)
+                        .ok_or_else(|| ConfigureError::Usage(format!("unknown format `{name}`")))?;
+                    configure.format = Some(format);
+                }
+                "--non-interactive" => configure.non_interactive = true,
+                "--upgrade" => configure.upgrade = true,
+                other => {
+                    return Err(ConfigureError::Usage(format!(
+                        "unexpected argument `{other}`"
+                    )));
+                }
+            }
+        }
+
+        Ok(configure)
+    }
+
+    /// Write the config file in the given format instead of guessing it from the extension.
+    pub fn with_format(mut self, format: ConfigFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Read answers from the given TOML or JSON file.
+    pub fn with_answers(mut self, path: impl Into<PathBuf>) -> Self {
+        self.answers = Some(path.into());
+        self
+    }
+
+    /// Never prompt, take all answers from the answers file and the existing config.
+    pub fn with_non_interactive(mut self, non_interactive: bool) -> Self {
+        self.non_interactive = non_interactive;
+        self
+    }
+
+    /// Only ask for answers missing from the existing config, keep all others.
+    ///
+    /// Enums whose selected variant gained fields are asked again, with the
+    /// saved variant pre-selected and its known fields skipped.
+    pub fn with_upgrade(mut self, upgrade: bool) -> Self {
+        self.upgrade = upgrade;
+        self
+    }
+
+    /// The config file that will be written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The answers file, if any.
+    pub fn answers(&self) -> Option<&Path> {
+        self.answers.as_deref()
+    }
+
+    /// Whether prompting is disabled.
+    pub fn is_non_interactive(&self) -> bool {
+        self.non_interactive
+    }
+
+    /// Whether only missing answers are asked for.
+    pub fn is_upgrade(&self) -> bool {
+        self.upgrade
+    }
+
+    /// The format the config file will be written in.
+    pub fn format(&self) -> ConfigFormat {
+        self.format
+            .unwrap_or_else(|| ConfigFormat::from_path(&self.path))
+    }
+
+    /// Run the survey and write the config file.
+    ///
+    /// Prompts with the first enabled wizard backend if stdin and stdout are a
+    /// terminal, and falls back to non-interactive mode otherwise.
+    pub fn run<T: Survey>(&self) -> Result<T, ConfigureError> {
+        let (definition, known) = self.prepare::<T>()?;
+
+        let responses = if definition.questions.is_empty() {
+            Responses::new()
+        } else if self.non_interactive || !backend::is_interactive() {
+            collect_defaults::<T>(&definition, &known)?
+        } else {
+            backend::collect_interactive::<T>(&definition, &known)?
+        };
+        self.finish(known, responses)
+    }
+
+    /// Run the survey with an explicitly chosen backend and write the config file.
+    pub fn run_with<T: Survey, B: SurveyBackend>(&self, backend: B) -> Result<T, ConfigureError> {
+        let (definition, known) = self.prepare::<T>()?;
+
+        let responses = if definition.questions.is_empty() {
+            Responses::new()
+        } else if self.non_interactive {
+            collect_defaults::<T>(&definition, &known)?
+        } else {
+            backend::collect::<T>(backend, &definition, &known)?
+        };
+        self.finish(known, responses)
+    }
+
+    /// Build the survey to run, and the answers that are kept without asking.
+    ///
+    /// Answers from the existing config and the answers file are offered as
+    /// suggestions. In upgrade mode, the existing config's answers are kept
+    /// instead and only the remaining questions are asked.
+    fn prepare<T: Survey>(&self) -> Result<(SurveyDefinition, Responses), ConfigureError> {
+        let mut definition = T::survey();
+        let root = ResponsePath::empty();
+
+        let saved = if self.path.exists() {
+            read_file(&self.path, self.format(), &definition)?
+        } else {
+            Responses::new()
+        };
+        let answers = match &self.answers {
+            Some(path) => read_file(path, ConfigFormat::from_path(path), &definition)?,
+            None => Responses::new(),
+        };
+
+        if !self.upgrade {
+            let mut suggestions = saved;
+            suggestions.extend(answers);
+            suggest_answers(&mut definition.questions, &root, &suggestions);
+            return Ok((definition, Responses::new()));
+        }
+
+        retain_unanswered(&mut definition.questions, &root, &saved);
+        suggest_answers(&mut definition.questions, &root, &answers);
+        assume_answers(&mut definition.questions, &root, &saved);
+        Ok((definition, saved))
+    }
+
+    fn finish<T: Survey>(
+        &self,
+        known: Responses,
+        collected: Responses,
+    ) -> Result<T, ConfigureError> {
+        let mut responses = known;
+        responses.extend(collected);
+
+        let definition = T::survey();
+        let document = write_answers(&definition.questions, &responses)?;
+
+        let io_error = |source| ConfigureError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        // Rewriting an existing config keeps its comments and unknown keys
+        let text = if self.path.exists() {
+            let existing = std::fs::read_to_string(&self.path).map_err(io_error)?;
+            self.format()
+                .render_over(&existing, &document, &self.path)?
+        } else {
+            self.format().render(&document)?
+        };
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        std::fs::write(&self.path, text).map_err(io_error)?;
+
+        Ok(T::from_responses(&responses))
+    }
+}
+
+/// Config files hold every answer as given, masked ones included.
+impl SensitiveSink for Configure {
+    fn sink_name(&self) -> String {
+        format!("config file {}", self.path.display())
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        FieldHandling::Plaintext
+    }
+}
+
+/// Load a config file written by [`Configure`].
+///
+/// The file is checked like a non-interactive run: every question needs an
+/// answer and all validators must pass.
+pub fn load<T: Survey>(path: impl AsRef<Path>) -> Result<T, ConfigureError> {
+    let path = path.as_ref();
+    let mut definition = T::survey();
+    let answers = read_file(path, ConfigFormat::from_path(path), &definition)?;
+    suggest_answers(&mut definition.questions, &ResponsePath::empty(), &answers);
+
+    let responses = collect_defaults::<T>(&definition, &Responses::new())?;
+    Ok(T::from_responses(&responses))
+}
+
+/// Fields of `T` that the config file at `path` does not answer yet.
+///
+/// After fields were added to the config type, these are the questions an
+/// upgrade run ([`Configure::with_upgrade`]) asks. Enum fields without a
+/// selected variant are listed by their `selected_variant` path.
+pub fn new_fields<T: Survey>(path: impl AsRef<Path>) -> Result<Vec<ResponsePath>, ConfigureError> {
+    let path = path.as_ref();
+    let definition = T::survey();
+    let saved = read_file(path, ConfigFormat::from_path(path), &definition)?;
+    Ok(unanswered(
+        &definition.questions,
+        &ResponsePath::empty(),
+        &saved,
+    ))
+}
+
+fn read_file(
+    path: &Path,
+    format: ConfigFormat,
+    definition: &SurveyDefinition,
+) -> Result<Responses, ConfigureError> {
+    let text = std::fs::read_to_string(path).map_err(|source| ConfigureError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let document = format.parse(&text, path)?;
+    read_answers(&definition.questions, &document)
+}
+
+fn collect_defaults<T: Survey>(
+    definition: &SurveyDefinition,
+    known: &Responses,
+) -> Result<Responses, ConfigureError> {
+    DefaultsBackend::new().collect(definition, &|value, responses, path| {
+        let mut all = known.clone();
+        all.extend(responses.clone());
+        T::validate_field(value, &all, path)
+    })
+}
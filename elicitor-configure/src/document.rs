@@ -0,0 +1,621 @@
+//! Conversion between survey responses and config documents.
+//!
+//! Config files are plain nested tables: every response path becomes a key path
+//! (`server.port = 8080`), enum selections are stored by variant name under
+//! `selected_variant`/`selected_variants`. The survey definition provides the
+//! types, so the derived type itself does not need to be serializable.
+
+use elicitor::{
+    AnyOfQuestion, ListElementKind, OneOfQuestion, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, Variant,
+};
+use serde_json::{Map, Value};
+
+use crate::ConfigureError;
+
+/// Callbacks for walking the questions of a survey.
+pub(crate) trait Visit {
+    /// Called for every question that produces a single response value.
+    fn leaf(&mut self, question: &Question, path: &ResponsePath) -> Result<(), ConfigureError>;
+
+    /// Returns the selected variant of an enum, if any.
+    fn variant(
+        &mut self,
+        one_of: &OneOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Option<usize>, ConfigureError>;
+
+    /// Returns the selected variants of a multi-select.
+    fn variants(
+        &mut self,
+        any_of: &AnyOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Vec<usize>, ConfigureError>;
+}
+
+/// Walk `questions` in order, descending only into selected enum variants.
+pub(crate) fn walk(
+    questions: &[Question],
+    prefix: &ResponsePath,
+    visit: &mut impl Visit,
+) -> Result<(), ConfigureError> {
+    for question in questions {
+        walk_question(question, &prefix.child(question.path().as_str()), visit)?;
+    }
+    Ok(())
+}
+
+fn walk_question(
+    question: &Question,
+    path: &ResponsePath,
+    visit: &mut impl Visit,
+) -> Result<(), ConfigureError> {
+    match question.kind() {
+        QuestionKind::Unit => Ok(()),
+        QuestionKind::AllOf(all_of) => walk(all_of.questions(), path, visit),
+        QuestionKind::OneOf(one_of) => match visit.variant(one_of, path)? {
+            Some(idx) => walk_variant(one_of.variants.get(idx), path, visit),
+            None => Ok(()),
+        },
+        QuestionKind::AnyOf(any_of) => {
+            for idx in visit.variants(any_of, path)? {
+                let variant_path = path.child(&idx.to_string());
+                walk_variant(any_of.variants.get(idx), &variant_path, visit)?;
+            }
+            Ok(())
+        }
+        _ => visit.leaf(question, path),
+    }
+}
+
+fn walk_variant(
+    variant: Option<&Variant>,
+    path: &ResponsePath,
+    visit: &mut impl Visit,
+) -> Result<(), ConfigureError> {
+    match variant.map(|variant| &variant.kind) {
+        Some(QuestionKind::AllOf(all_of)) => walk(all_of.questions(), path, visit),
+        Some(QuestionKind::Unit) | None => Ok(()),
+        // Newtype variants store their value under `0`
+        Some(other) => {
+            let question = Question::new(ResponsePath::new("0"), "", other.clone());
+            walk_question(&question, &path.child("0"), visit)
+        }
+    }
+}
+
+/// Read all answers present in `document`, typed by the survey questions.
+///
+/// Missing answers are skipped, answers of the wrong type are an error.
+pub(crate) fn read_answers(
+    questions: &[Question],
+    document: &Value,
+) -> Result<Responses, ConfigureError> {
+    let mut reader = Reader {
+        document,
+        responses: Responses::new(),
+    };
+    walk(questions, &ResponsePath::empty(), &mut reader)?;
+    Ok(reader.responses)
+}
+
+/// Build a config document from collected responses.
+pub(crate) fn write_answers(
+    questions: &[Question],
+    responses: &Responses,
+) -> Result<Value, ConfigureError> {
+    let mut writer = Writer {
+        responses,
+        document: Value::Object(Map::new()),
+    };
+    walk(questions, &ResponsePath::empty(), &mut writer)?;
+    Ok(writer.document)
+}
+
+/// Paths of all questions that `answers` leaves open.
+///
+/// Enum fields count as open if no variant is selected; fields of unselected
+/// variants are not listed.
+pub(crate) fn unanswered(
+    questions: &[Question],
+    prefix: &ResponsePath,
+    answers: &Responses,
+) -> Vec<ResponsePath> {
+    let mut diff = Diff {
+        answers,
+        open: Vec::new(),
+    };
+    walk(questions, prefix, &mut diff).expect("finding open questions cannot fail");
+    diff.open
+}
+
+/// Drop every question that `answers` fully covers.
+///
+/// Nested structs keep only their open fields. Enums with an open field inside
+/// the selected variant are kept as a whole, since backends always ask for the
+/// variant before its fields.
+pub(crate) fn retain_unanswered(
+    questions: &mut Vec<Question>,
+    prefix: &ResponsePath,
+    answers: &Responses,
+) {
+    questions.retain_mut(|question| {
+        if unanswered(std::slice::from_ref(question), prefix, answers).is_empty() {
+            return false;
+        }
+        let path = prefix.child(question.path().as_str());
+        if let QuestionKind::AllOf(all_of) = question.kind_mut() {
+            retain_unanswered(all_of.questions_mut(), &path, answers);
+        }
+        true
+    });
+}
+
+/// Pre-fill questions with known answers, so backends offer them as suggestions.
+pub(crate) fn suggest_answers(
+    questions: &mut [Question],
+    prefix: &ResponsePath,
+    answers: &Responses,
+) {
+    apply_answers(questions, prefix, answers, false);
+}
+
+/// Pre-fill questions with known answers as assumptions, so backends skip them.
+///
+/// Enum selections cannot be skipped and are pre-selected instead.
+pub(crate) fn assume_answers(
+    questions: &mut [Question],
+    prefix: &ResponsePath,
+    answers: &Responses,
+) {
+    apply_answers(questions, prefix, answers, true);
+}
+
+fn apply_answers(
+    questions: &mut [Question],
+    prefix: &ResponsePath,
+    answers: &Responses,
+    assume: bool,
+) {
+    for question in questions {
+        let path = prefix.child(question.path().as_str());
+        apply_answer(question, &path, answers, assume);
+    }
+}
+
+fn apply_answer(question: &mut Question, path: &ResponsePath, answers: &Responses, assume: bool) {
+    if let Some(value) = answers.get(path) {
+        if assume {
+            question.set_assumption(value.clone());
+        } else {
+            question.set_suggestion(value.clone());
+        }
+    }
+
+    match question.kind_mut() {
+        QuestionKind::AllOf(all_of) => apply_answers(all_of.questions_mut(), path, answers, assume),
+        QuestionKind::OneOf(one_of) => {
+            let selected = answers
+                .get(&path.child(SELECTED_VARIANT_KEY))
+                .and_then(ResponseValue::as_chosen_variant);
+            if selected.is_some() {
+                one_of.default = selected;
+            }
+            for variant in &mut one_of.variants {
+                apply_variant(&mut variant.kind, path, answers, assume);
+            }
+        }
+        QuestionKind::AnyOf(any_of) => {
+            if let Some(selected) = answers
+                .get(&path.child(SELECTED_VARIANTS_KEY))
+                .and_then(ResponseValue::as_chosen_variants)
+            {
+                any_of.defaults = selected.to_vec();
+            }
+            for (idx, variant) in any_of.variants.iter_mut().enumerate() {
+                let variant_path = path.child(&idx.to_string());
+                apply_variant(&mut variant.kind, &variant_path, answers, assume);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn apply_variant(kind: &mut QuestionKind, path: &ResponsePath, answers: &Responses, assume: bool) {
+    match kind {
+        QuestionKind::AllOf(all_of) => apply_answers(all_of.questions_mut(), path, answers, assume),
+        QuestionKind::Unit => {}
+        other => {
+            let mut question = Question::new(
+                ResponsePath::new("0"),
+                "",
+                std::mem::replace(other, QuestionKind::Unit),
+            );
+            apply_answer(&mut question, &path.child("0"), answers, assume);
+            *other = std::mem::replace(question.kind_mut(), QuestionKind::Unit);
+        }
+    }
+}
+
+/// Reads typed answers out of a document.
+struct Reader<'a> {
+    document: &'a Value,
+    responses: Responses,
+}
+
+impl Visit for Reader<'_> {
+    fn leaf(&mut self, question: &Question, path: &ResponsePath) -> Result<(), ConfigureError> {
+        if let Some(value) = lookup(self.document, path) {
+            let answer = answer_from_value(question.kind(), value, path)?;
+            self.responses.insert(path.clone(), answer);
+        }
+        Ok(())
+    }
+
+    fn variant(
+        &mut self,
+        one_of: &OneOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Option<usize>, ConfigureError> {
+        let key = path.child(SELECTED_VARIANT_KEY);
+        let Some(value) = lookup(self.document, &key) else {
+            return Ok(None);
+        };
+        let idx = variant_index(&one_of.variants, value, &key)?;
+        self.responses
+            .insert(key, ResponseValue::ChosenVariant(idx));
+        Ok(Some(idx))
+    }
+
+    fn variants(
+        &mut self,
+        any_of: &AnyOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Vec<usize>, ConfigureError> {
+        let key = path.child(SELECTED_VARIANTS_KEY);
+        let Some(value) = lookup(self.document, &key) else {
+            return Ok(Vec::new());
+        };
+        let items = value
+            .as_array()
+            .ok_or_else(|| invalid_answer(&key, "a list of variants", value))?;
+        let indices = items
+            .iter()
+            .map(|item| variant_index(&any_of.variants, item, &key))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.responses
+            .insert(key, ResponseValue::ChosenVariants(indices.clone()));
+        Ok(indices)
+    }
+}
+
+/// Collects the paths of questions without an answer.
+struct Diff<'a> {
+    answers: &'a Responses,
+    open: Vec<ResponsePath>,
+}
+
+impl Visit for Diff<'_> {
+    fn leaf(&mut self, _question: &Question, path: &ResponsePath) -> Result<(), ConfigureError> {
+        if !self.answers.contains(path) {
+            self.open.push(path.clone());
+        }
+        Ok(())
+    }
+
+    fn variant(
+        &mut self,
+        _one_of: &OneOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Option<usize>, ConfigureError> {
+        let key = path.child(SELECTED_VARIANT_KEY);
+        let selected = self
+            .answers
+            .get(&key)
+            .and_then(ResponseValue::as_chosen_variant);
+        if selected.is_none() {
+            self.open.push(key);
+        }
+        Ok(selected)
+    }
+
+    fn variants(
+        &mut self,
+        _any_of: &AnyOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Vec<usize>, ConfigureError> {
+        let key = path.child(SELECTED_VARIANTS_KEY);
+        match self
+            .answers
+            .get(&key)
+            .and_then(ResponseValue::as_chosen_variants)
+        {
+            Some(selected) => Ok(selected.to_vec()),
+            None => {
+                self.open.push(key);
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+/// Writes collected responses into a document.
+struct Writer<'a> {
+    responses: &'a Responses,
+    document: Value,
+}
+
+impl Visit for Writer<'_> {
+    fn leaf(&mut self, question: &Question, path: &ResponsePath) -> Result<(), ConfigureError> {
+        if let Some(value) = self.responses.get(path) {
+            insert(
+                &mut self.document,
+                path,
+                value_from_answer(question.kind(), value),
+            );
+        }
+        Ok(())
+    }
+
+    fn variant(
+        &mut self,
+        one_of: &OneOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Option<usize>, ConfigureError> {
+        let key = path.child(SELECTED_VARIANT_KEY);
+        let selected = self
+            .responses
+            .get(&key)
+            .and_then(ResponseValue::as_chosen_variant);
+        if let Some(variant) = selected.and_then(|idx| one_of.variants.get(idx)) {
+            insert(&mut self.document, &key, Value::from(variant.name.clone()));
+        }
+        Ok(selected)
+    }
+
+    fn variants(
+        &mut self,
+        any_of: &AnyOfQuestion,
+        path: &ResponsePath,
+    ) -> Result<Vec<usize>, ConfigureError> {
+        let key = path.child(SELECTED_VARIANTS_KEY);
+        let selected = self
+            .responses
+            .get(&key)
+            .and_then(ResponseValue::as_chosen_variants)
+            .map(<[usize]>::to_vec)
+            .unwrap_or_default();
+        let names = selected
+            .iter()
+            .filter_map(|&idx| any_of.variants.get(idx))
+            .map(|variant| Value::from(variant.name.clone()))
+            .collect();
+        insert(&mut self.document, &key, Value::Array(names));
+        Ok(selected)
+    }
+}
+
+fn lookup<'a>(document: &'a Value, path: &ResponsePath) -> Option<&'a Value> {
+    path.segments()
+        .try_fold(document, |value, segment| value.get(segment))
+}
+
+fn insert(document: &mut Value, path: &ResponsePath, value: Value) {
+    let segments: Vec<&str> = path.segments().collect();
+    let Some((last, parents)) = segments.split_last() else {
+        return;
+    };
+
+    let mut current = document;
+    for segment in parents {
+        let Value::Object(map) = current else {
+            return;
+        };
+        current = map
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    if let Value::Object(map) = current {
+        map.insert(last.to_string(), value);
+    }
+}
+
+fn invalid_answer(path: &ResponsePath, expected: &str, found: &Value) -> ConfigureError {
+    ConfigureError::InvalidAnswer {
+        path: path.as_str().to_string(),
+        message: format!("expected {expected}, found {found}"),
+    }
+}
+
+/// Accepts a variant by name or by index.
+fn variant_index(
+    variants: &[Variant],
+    value: &Value,
+    path: &ResponsePath,
+) -> Result<usize, ConfigureError> {
+    let idx = match value {
+        Value::String(name) => variants.iter().position(|variant| &variant.name == name),
+        Value::Number(number) => number
+            .as_u64()
+            .map(|idx| idx as usize)
+            .filter(|&idx| idx < variants.len()),
+        _ => None,
+    };
+    idx.ok_or_else(|| {
+        let names: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+        invalid_answer(path, &format!("one of {names:?}"), value)
+    })
+}
+
+fn answer_from_value(
+    kind: &QuestionKind,
+    value: &Value,
+    path: &ResponsePath,
+) -> Result<ResponseValue, ConfigureError> {
+    let string = || {
+        value
+            .as_str()
+            .map(|s| ResponseValue::String(s.to_string()))
+            .ok_or_else(|| invalid_answer(path, "a string", value))
+    };
+
+    match kind {
+        QuestionKind::Input(_)
+        | QuestionKind::Autocomplete(_)
+        | QuestionKind::Multiline(_)
+        | QuestionKind::Masked(_) => string(),
+        QuestionKind::Select(select) => {
+            let answer = string()?;
+            match answer.as_str() {
+                Some(choice) if select.position(choice).is_some() => Ok(answer),
+                _ => Err(ConfigureError::InvalidAnswer {
+                    path: path.as_str().to_string(),
+                    message: format!("{value} is not one of the available choices"),
+                }),
+            }
+        }
+        QuestionKind::Int(_) => value
+            .as_i64()
+            .map(ResponseValue::Int)
+            .ok_or_else(|| invalid_answer(path, "an integer", value)),
+        QuestionKind::Currency(currency) => match value {
+            // Numbers are minor units like the field itself, strings are typed amounts
+            Value::String(amount) => {
+                currency
+                    .parse(amount)
+                    .map(ResponseValue::Int)
+                    .map_err(|message| ConfigureError::InvalidAnswer {
+                        path: path.as_str().to_string(),
+                        message,
+                    })
+            }
+            _ => value
+                .as_i64()
+                .map(ResponseValue::Int)
+                .ok_or_else(|| invalid_answer(path, "an amount", value)),
+        },
+        QuestionKind::Color(color) => {
+            let hex = value
+                .as_str()
+                .ok_or_else(|| invalid_answer(path, "a hex color", value))?;
+            color
+                .parse(hex)
+                .map(|color| ResponseValue::String(color.to_hex()))
+                .map_err(|message| ConfigureError::InvalidAnswer {
+                    path: path.as_str().to_string(),
+                    message,
+                })
+        }
+        QuestionKind::Path(path_q) => {
+            let answer = string()?;
+            path_q
+                .check(answer.as_str().unwrap_or_default())
+                .map(|()| answer)
+                .map_err(|message| ConfigureError::InvalidAnswer {
+                    path: path.as_str().to_string(),
+                    message,
+                })
+        }
+        QuestionKind::Rating(rating) => value
+            .as_i64()
+            .filter(|&v| rating.contains(v))
+            .map(ResponseValue::Int)
+            .ok_or_else(|| {
+                let expected = format!("a rating from {} to {}", rating.min, rating.max);
+                invalid_answer(path, &expected, value)
+            }),
+        QuestionKind::Matrix(matrix) => {
+            // A table of row name to column name
+            let expected = format!(
+                "a table of {:?} to one of {:?}",
+                matrix.rows, matrix.columns
+            );
+            let table = value
+                .as_object()
+                .ok_or_else(|| invalid_answer(path, &expected, value))?;
+            matrix
+                .rows
+                .iter()
+                .map(|row| {
+                    let column = table
+                        .get(row)
+                        .and_then(Value::as_str)
+                        .and_then(|name| matrix.columns.iter().position(|c| c == name));
+                    column.ok_or_else(|| invalid_answer(path, &expected, value))
+                })
+                .collect::<Result<_, _>>()
+                .map(ResponseValue::ChosenVariants)
+        }
+        QuestionKind::Float(_) => value
+            .as_f64()
+            .map(ResponseValue::Float)
+            .ok_or_else(|| invalid_answer(path, "a number", value)),
+        QuestionKind::Confirm(_) => value
+            .as_bool()
+            .map(ResponseValue::Bool)
+            .ok_or_else(|| invalid_answer(path, "true or false", value)),
+        QuestionKind::List(list) => {
+            let items = value
+                .as_array()
+                .ok_or_else(|| invalid_answer(path, "a list", value))?;
+            match list.element_kind {
+                ListElementKind::String => items
+                    .iter()
+                    .map(|item| item.as_str().map(str::to_string))
+                    .collect::<Option<_>>()
+                    .map(ResponseValue::StringList)
+                    .ok_or_else(|| invalid_answer(path, "a list of strings", value)),
+                ListElementKind::Int { .. } => items
+                    .iter()
+                    .map(Value::as_i64)
+                    .collect::<Option<_>>()
+                    .map(ResponseValue::IntList)
+                    .ok_or_else(|| invalid_answer(path, "a list of integers", value)),
+                ListElementKind::Float { .. } => items
+                    .iter()
+                    .map(Value::as_f64)
+                    .collect::<Option<_>>()
+                    .map(ResponseValue::FloatList)
+                    .ok_or_else(|| invalid_answer(path, "a list of numbers", value)),
+            }
+        }
+        QuestionKind::Unit
+        | QuestionKind::AllOf(_)
+        | QuestionKind::OneOf(_)
+        | QuestionKind::AnyOf(_) => unreachable!("nested questions are walked, not read"),
+    }
+}
+
+fn value_from_answer(kind: &QuestionKind, value: &ResponseValue) -> Value {
+    // Amounts are stored as typed, e.g. "$1,250.50"
+    if let (QuestionKind::Currency(currency), ResponseValue::Int(minor)) = (kind, value) {
+        return Value::from(currency.format(*minor));
+    }
+
+    // Matrices are stored by name, like enum selections
+    if let (QuestionKind::Matrix(matrix), ResponseValue::ChosenVariants(columns)) = (kind, value) {
+        return Value::Object(
+            matrix
+                .rows
+                .iter()
+                .zip(columns)
+                .filter_map(|(row, &column)| {
+                    let name = matrix.columns.get(column)?;
+                    Some((row.clone(), Value::from(name.clone())))
+                })
+                .collect(),
+        );
+    }
+
+    match value {
+        ResponseValue::String(s) => Value::from(s.clone()),
+        ResponseValue::Int(i) => Value::from(*i),
+        ResponseValue::Float(f) => Value::from(*f),
+        ResponseValue::Bool(b) => Value::from(*b),
+        ResponseValue::ChosenVariant(idx) => Value::from(*idx),
+        ResponseValue::ChosenVariants(indices) => Value::from(indices.clone()),
+        ResponseValue::StringList(items) => Value::from(items.clone()),
+        ResponseValue::IntList(items) => Value::from(items.clone()),
+        ResponseValue::FloatList(items) => Value::from(items.clone()),
+    }
+}
@@ -0,0 +1,323 @@
+//! `.env` file output.
+//!
+//! Unlike config files, `.env` files are written from the finished config value
+//! through serde, so `#[serde(rename_all = "...")]` and `#[serde(rename = "...")]`
+//! decide the variable names. Nested fields are joined with a separator
+//! (`DATABASE__URL`), lists of plain values are comma-separated.
+
+use std::path::{Path, PathBuf};
+
+use elicitor::{FieldHandling, SensitiveField, SensitiveSink};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::ConfigureError;
+
+/// Writes a config value as environment variable assignments to a `.env` file.
+///
+/// Existing files are merged: assignments for known variables are replaced in
+/// place, comments and unrelated variables are kept, and new variables are
+/// appended at the end.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let config: ServiceConfig = Configure::new("service.toml").run()?;
+/// EnvFile::new(".env").with_prefix("SERVICE_").write(&config)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnvFile {
+    path: PathBuf,
+    prefix: String,
+    separator: String,
+    uppercase: bool,
+}
+
+impl EnvFile {
+    /// Write to the `.env` file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            prefix: String::new(),
+            separator: "__".to_string(),
+            uppercase: true,
+        }
+    }
+
+    /// Prepend `prefix` to every variable name, e.g. `APP_`.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Join nested field names with `separator` instead of `__`.
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Whether field names are upper-cased (the default) or used as serialized.
+    pub fn with_uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// The `.env` file that will be written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The variable assignments for `config`, in field order.
+    ///
+    /// `config` must serialize to a struct or map. `None` values become empty
+    /// assignments, so merging clears a previously set variable.
+    pub fn assignments<T: Serialize>(
+        &self,
+        config: &T,
+    ) -> Result<Vec<(String, String)>, ConfigureError> {
+        let value =
+            serde_json::to_value(config).map_err(|e| ConfigureError::Render(e.to_string()))?;
+        if !value.is_object() {
+            return Err(ConfigureError::Render(
+                "an env file needs a struct or map at the top level".to_string(),
+            ));
+        }
+
+        let mut assignments = Vec::new();
+        self.flatten(&value, &mut Vec::new(), &mut assignments);
+        Ok(assignments)
+    }
+
+    /// Render `config` as the contents of a fresh `.env` file.
+    pub fn render<T: Serialize>(&self, config: &T) -> Result<String, ConfigureError> {
+        Ok(self
+            .assignments(config)?
+            .iter()
+            .map(|(key, value)| format!("{key}={}\n", quote(value)))
+            .collect())
+    }
+
+    /// Write `config` to the `.env` file, merging with its current contents.
+    pub fn write<T: Serialize>(&self, config: &T) -> Result<(), ConfigureError> {
+        let io_error = |source| ConfigureError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        let existing = if self.path.exists() {
+            std::fs::read_to_string(&self.path).map_err(io_error)?
+        } else {
+            String::new()
+        };
+
+        let text = merge(&existing, self.assignments(config)?);
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        std::fs::write(&self.path, text).map_err(io_error)
+    }
+
+    fn flatten(&self, value: &Value, keys: &mut Vec<String>, out: &mut Vec<(String, String)>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    keys.push(key.clone());
+                    self.flatten(value, keys, out);
+                    keys.pop();
+                }
+            }
+            // Lists of tables get one group of variables per item
+            Value::Array(items) if items.iter().any(|item| item.is_object() || item.is_array()) => {
+                for (idx, item) in items.iter().enumerate() {
+                    keys.push(idx.to_string());
+                    self.flatten(item, keys, out);
+                    keys.pop();
+                }
+            }
+            Value::Array(items) => {
+                let joined = items.iter().map(scalar).collect::<Vec<_>>().join(",");
+                out.push((self.key(keys), joined));
+            }
+            scalar_value => out.push((self.key(keys), scalar(scalar_value))),
+        }
+    }
+
+    fn key(&self, keys: &[String]) -> String {
+        let key = format!("{}{}", self.prefix, keys.join(&self.separator));
+        if self.uppercase {
+            key.to_uppercase()
+        } else {
+            key
+        }
+    }
+}
+
+/// `.env` files hold every serialized field as given.
+impl SensitiveSink for EnvFile {
+    fn sink_name(&self) -> String {
+        format!("env file {}", self.path.display())
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        FieldHandling::Plaintext
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Quote a value if a shell or dotenv parser would otherwise misread it.
+fn quote(value: &str) -> String {
+    let plain = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "_-.,:/@+%".contains(c));
+    if plain {
+        return value.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' | '\\' | '$' | '`' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Replace known assignments in `existing`, keep everything else, append the rest.
+fn merge(existing: &str, assignments: Vec<(String, String)>) -> String {
+    let mut pending = assignments;
+    let mut lines = Vec::new();
+
+    for line in existing.lines() {
+        let trimmed = line.trim_start();
+        let (export, rest) = match trimmed.strip_prefix("export ") {
+            Some(rest) => ("export ", rest.trim_start()),
+            None => ("", trimmed),
+        };
+        let key = rest.split_once('=').map(|(key, _)| key.trim());
+
+        match key.and_then(|key| pending.iter().position(|(k, _)| k == key)) {
+            Some(idx) if !trimmed.starts_with('#') => {
+                let (key, value) = pending.remove(idx);
+                lines.push(format!("{export}{key}={}", quote(&value)));
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    for (key, value) in pending {
+        lines.push(format!("{key}={}", quote(&value)));
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Database {
+        url: String,
+        pool_size: u32,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ServiceConfig {
+        service_name: String,
+        debug: bool,
+        allowed_origins: Vec<String>,
+        database: Database,
+        api_token: Option<String>,
+    }
+
+    fn config() -> ServiceConfig {
+        ServiceConfig {
+            service_name: "billing api".to_string(),
+            debug: false,
+            allowed_origins: vec![
+                "https://a.example".to_string(),
+                "https://b.example".to_string(),
+            ],
+            database: Database {
+                url: "postgres://localhost/billing".to_string(),
+                pool_size: 8,
+            },
+            api_token: None,
+        }
+    }
+
+    #[test]
+    fn assignments_follow_serde_names() {
+        let env = EnvFile::new(".env").with_prefix("app_");
+
+        assert_eq!(
+            env.render(&config()).unwrap(),
+            "APP_SERVICENAME=\"billing api\"\n\
+             APP_DEBUG=false\n\
+             APP_ALLOWEDORIGINS=https://a.example,https://b.example\n\
+             APP_DATABASE__URL=postgres://localhost/billing\n\
+             APP_DATABASE__POOL_SIZE=8\n\
+             APP_APITOKEN=\n"
+        );
+
+        let verbatim = EnvFile::new(".env")
+            .with_separator("_")
+            .with_uppercase(false);
+        let keys: Vec<String> = verbatim
+            .assignments(&config())
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(keys[0], "serviceName");
+        assert_eq!(keys[3], "database_url");
+    }
+
+    #[test]
+    fn special_characters_are_quoted() {
+        assert_eq!(quote("plain-value_1.0"), "plain-value_1.0");
+        assert_eq!(quote("a \"b\" $HOME"), "\"a \\\"b\\\" \\$HOME\"");
+        assert_eq!(quote("two\nlines"), "\"two\\nlines\"");
+    }
+
+    #[test]
+    fn existing_files_are_merged() {
+        let existing = "# Service settings\nexport DEBUG=true\nOTHER=kept\n";
+        let merged = merge(
+            existing,
+            vec![
+                ("DEBUG".to_string(), "false".to_string()),
+                ("PORT".to_string(), "8080".to_string()),
+            ],
+        );
+
+        assert_eq!(
+            merged,
+            "# Service settings\nexport DEBUG=false\nOTHER=kept\nPORT=8080\n"
+        );
+    }
+
+    #[test]
+    fn non_struct_values_are_rejected() {
+        assert!(matches!(
+            EnvFile::new(".env").render(&42),
+            Err(ConfigureError::Render(_))
+        ));
+    }
+}
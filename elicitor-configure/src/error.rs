@@ -0,0 +1,57 @@
+//! Error type for the configure subcommand.
+
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// Usage line shown for invalid command-line arguments.
+pub const USAGE: &str = "Usage: configure [--config <path>] [--format toml|json] [--answers <path>] [--non-interactive] [--upgrade]";
+
+/// Errors that can occur while configuring.
+#[derive(Debug, Error)]
+pub enum ConfigureError {
+    /// Invalid command-line arguments.
+    #[error("{0}\n\n{USAGE}")]
+    Usage(String),
+
+    /// Reading or writing a file failed.
+    #[error("Could not access '{}': {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// A config or answers file could not be parsed.
+    #[error("Could not parse '{}': {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+
+    /// The configuration could not be rendered.
+    #[error("Could not render configuration: {0}")]
+    Render(String),
+
+    /// A profiles file has no profile of the requested name.
+    #[error("No profile '{name}' in '{}'", path.display())]
+    UnknownProfile { path: PathBuf, name: String },
+
+    /// An answer does not fit the question it belongs to.
+    #[error("Invalid answer for '{path}': {message}")]
+    InvalidAnswer { path: String, message: String },
+
+    /// A question has no answer in non-interactive mode.
+    #[error("Missing answer for '{0}'")]
+    MissingAnswer(String),
+
+    /// An answer was rejected by the survey's validators.
+    #[error("Validation failed for '{path}': {message}")]
+    ValidationFailed { path: String, message: String },
+
+    /// Interactive mode was requested but no interactive backend is compiled in.
+    #[error(
+        "No interactive backend available, enable one of the backend features or pass --non-interactive"
+    )]
+    NoInteractiveBackend,
+
+    /// The backend failed or the user cancelled.
+    #[error(transparent)]
+    Backend(anyhow::Error),
+}
@@ -0,0 +1,126 @@
+//! Config file formats.
+
+use std::path::Path;
+
+use serde_json::Value;
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::ConfigureError;
+
+/// The file format a configuration is written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfigFormat {
+    /// TOML, with nested fields as tables.
+    #[default]
+    Toml,
+    /// Pretty-printed JSON, with nested fields as objects.
+    Json,
+}
+
+impl ConfigFormat {
+    /// Pick the format from a file extension (`.json` is JSON, everything else is TOML).
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Toml,
+        }
+    }
+
+    /// Parse a format name as given on the command line.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn parse(self, text: &str, path: &Path) -> Result<Value, ConfigureError> {
+        let parsed = match self {
+            Self::Toml => toml::from_str(text).map_err(|e| e.to_string()),
+            Self::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+        };
+        parsed.map_err(|message| ConfigureError::Parse {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    pub(crate) fn render(self, document: &Value) -> Result<String, ConfigureError> {
+        let rendered = match self {
+            Self::Toml => toml::to_string_pretty(document).map_err(|e| e.to_string()),
+            Self::Json => serde_json::to_string_pretty(document)
+                .map(|json| json + "\n")
+                .map_err(|e| e.to_string()),
+        };
+        rendered.map_err(ConfigureError::Render)
+    }
+
+    /// Render `document` over the `existing` config file.
+    ///
+    /// Answers replace the values at their keys, while keys the survey does not
+    /// know about are kept. TOML comments and layout are kept as well.
+    pub(crate) fn render_over(
+        self,
+        existing: &str,
+        document: &Value,
+        path: &Path,
+    ) -> Result<String, ConfigureError> {
+        let rendered = self.render(document)?;
+        let parse_error = |message: String| ConfigureError::Parse {
+            path: path.to_path_buf(),
+            message,
+        };
+        match self {
+            Self::Toml => {
+                let mut merged: DocumentMut = existing
+                    .parse()
+                    .map_err(|e: toml_edit::TomlError| parse_error(e.to_string()))?;
+                let update: DocumentMut = rendered
+                    .parse()
+                    .map_err(|e: toml_edit::TomlError| ConfigureError::Render(e.to_string()))?;
+                merge_table(merged.as_table_mut(), update.as_table());
+                Ok(merged.to_string())
+            }
+            Self::Json => {
+                let mut merged = self.parse(existing, path)?;
+                merge_value(&mut merged, document.clone());
+                self.render(&merged)
+            }
+        }
+    }
+}
+
+/// Write the entries of `update` into `table`, keeping the decor of replaced values.
+fn merge_table(table: &mut Table, update: &Table) {
+    for (key, item) in update {
+        match (table.get_mut(key), item) {
+            (Some(Item::Table(existing)), Item::Table(update)) => merge_table(existing, update),
+            (Some(Item::Value(existing)), Item::Value(value)) => {
+                let decor = existing.decor().clone();
+                *existing = value.clone();
+                *existing.decor_mut() = decor;
+            }
+            _ => {
+                table.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+/// Write the entries of `update` into `value`, descending into objects present in both.
+fn merge_value(value: &mut Value, update: Value) {
+    match (value, update) {
+        (Value::Object(existing), Value::Object(update)) => {
+            for (key, update) in update {
+                match existing.get_mut(&key) {
+                    Some(value) => merge_value(value, update),
+                    None => {
+                        existing.insert(key, update);
+                    }
+                }
+            }
+        }
+        (value, update) => *value = update,
+    }
+}
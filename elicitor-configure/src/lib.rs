@@ -0,0 +1,588 @@
+//! # elicitor-configure
+//!
+//! A ready-made `configure` subcommand for applications with a `#[derive(Survey)]`
+//! config type. `mybin configure` runs the survey and writes the answers to a TOML
+//! or JSON config file, which the application reads back with [`load`].
+//!
+//! ```text
+//! mybin configure [--config <path>] [--format toml|json] [--answers <path>] [--non-interactive] [--upgrade]
+//! ```
+//!
+//! - `--config` chooses the config file (defaults to the path given by the application)
+//! - `--format` overrides the format guessed from the file extension
+//! - `--answers` reads answers from a TOML or JSON file and offers them as suggestions
+//! - `--non-interactive` never prompts, every question must be answered by the answers
+//!   file or the existing config
+//! - `--upgrade` keeps the answers in the existing config and only asks for fields
+//!   it does not have yet, e.g. after a new version added fields (see [`new_fields`])
+//!
+//! The wizard backend is picked by feature (`requestty` by default, `dialoguer` or
+//! `ratatui`). Without a terminal, `configure` runs non-interactively.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use elicitor::Survey;
+//!
+//! #[derive(Survey, Debug)]
+//! struct AppConfig {
+//!     #[ask("Server host:")]
+//!     host: String,
+//!
+//!     #[ask("Server port:")]
+//!     port: u16,
+//! }
+//!
+//! fn main() -> anyhow::Result<()> {
+//!     if let Some(config) = elicitor_configure::configure_subcommand::<AppConfig>("app.toml") {
+//!         println!("Configured: {:?}", config?);
+//!         return Ok(());
+//!     }
+//!
+//!     let config: AppConfig = elicitor_configure::load("app.toml")?;
+//!     println!("Running with {config:?}");
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ## File layout
+//!
+//! Nested fields become tables, enum selections are stored by variant name.
+//! Optional text fields are left empty with `""`. Re-running `configure` over an
+//! existing file only replaces the answers, comments and other keys stay as they are.
+//!
+//! ```toml
+//! host = "localhost"
+//! port = 8080
+//!
+//! [database]
+//! selected_variant = "Postgres"
+//! url = "postgres://localhost/app"
+//! ```
+//!
+//! ## Profiles
+//!
+//! [`load_profile`] reads a named preset of suggested and assumed answers, e.g. for
+//! "production" or "staging", from a file laid out like a config file. The builder
+//! of any derived survey applies it with `with_profile`:
+//!
+//! ```rust,ignore
+//! let production = elicitor_configure::load_profile::<AppConfig>("profiles.toml", "production")?;
+//! let config = AppConfig::builder().with_profile(&production).run(backend)?;
+//! ```
+//!
+//! ## `.env` files
+//!
+//! [`EnvFile`] writes a finished config value as environment variable
+//! assignments, for services that read their settings from the environment.
+//! It goes through serde, so the config type needs `#[derive(Serialize)]`:
+//!
+//! ```rust,ignore
+//! let config: ServiceConfig = Configure::new("service.toml").run()?;
+//! EnvFile::new(".env").with_prefix("SERVICE_").write(&config)?;
+//! ```
+
+mod backend;
+mod configure;
+mod document;
+mod env;
+mod error;
+mod format;
+mod profile;
+
+pub use backend::DefaultsBackend;
+pub use configure::{Configure, load, new_fields};
+pub use env::EnvFile;
+pub use error::{ConfigureError, USAGE};
+pub use format::ConfigFormat;
+pub use profile::{load_profile, load_profiles};
+
+use std::path::PathBuf;
+
+use elicitor::Survey;
+
+/// Run the `configure` subcommand if it was given on the command line.
+///
+/// Returns `None` if the first argument is not `configure`, so the application
+/// can continue normally. Otherwise the remaining arguments are parsed with
+/// [`Configure::from_args`] and the survey is run.
+pub fn configure_subcommand<T: Survey>(
+    default_path: impl Into<PathBuf>,
+) -> Option<Result<T, ConfigureError>> {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() != Some("configure") {
+        return None;
+    }
+
+    Some(
+        Configure::from_args(default_path, args).and_then(|configure| {
+            let config = configure.run()?;
+            eprintln!("Configuration written to {}", configure.path().display());
+            Ok(config)
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[derive(Survey, Debug, PartialEq)]
+    enum Database {
+        Sqlite {
+            #[ask("Database file:")]
+            file: String,
+        },
+        Postgres {
+            #[ask("Connection URL:")]
+            url: String,
+        },
+    }
+
+    #[derive(Survey, Debug, PartialEq)]
+    struct Server {
+        #[ask("Host:")]
+        host: String,
+
+        #[ask("Port:")]
+        #[min(1)]
+        port: u16,
+    }
+
+    #[derive(Survey, Debug, PartialEq)]
+    struct AppConfig {
+        #[ask("Application name:")]
+        name: String,
+
+        #[ask("Server:")]
+        server: Server,
+
+        #[ask("Database:")]
+        database: Database,
+
+        #[ask("Enable debug logging?")]
+        debug: bool,
+
+        #[ask("Allowed origins:")]
+        origins: Vec<String>,
+    }
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("elicitor-configure-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    const ANSWERS: &str = r#"
+        name = "demo"
+        debug = true
+        origins = ["https://example.com"]
+
+        [server]
+        host = "localhost"
+        port = 8080
+
+        [database]
+        selected_variant = "Postgres"
+        url = "postgres://localhost/demo"
+    "#;
+
+    #[test]
+    fn profiles_are_typed_by_the_survey() {
+        let profiles = temp_file(
+            "profiles.toml",
+            r#"
+            [production.assume]
+            name = "shop"
+            debug = false
+            origins = ["https://shop.example.com"]
+
+            [production.assume.server]
+            host = "0.0.0.0"
+            port = 443
+
+            [production.assume.database]
+            selected_variant = "Postgres"
+            url = "postgres://db.internal/shop"
+
+            [staging.suggest]
+            debug = true
+            "#,
+        );
+
+        let staging = load_profile::<AppConfig>(&profiles, "staging").unwrap();
+        assert_eq!(
+            staging.suggestions.get("debug"),
+            Some(&elicitor::ResponseValue::Bool(true))
+        );
+        assert!(staging.assumptions.is_empty());
+
+        // Every answer is assumed, nothing is asked
+        let production = load_profile::<AppConfig>(&profiles, "production").unwrap();
+        let config = AppConfig::builder()
+            .with_profile(&production)
+            .run(elicitor::TestBackend::new())
+            .unwrap();
+        assert_eq!(config.server.port, 443);
+        assert_eq!(
+            config.database,
+            Database::Postgres {
+                url: "postgres://db.internal/shop".to_string()
+            }
+        );
+
+        assert!(matches!(
+            load_profile::<AppConfig>(&profiles, "qa"),
+            Err(ConfigureError::UnknownProfile { .. })
+        ));
+    }
+
+    #[test]
+    fn args_are_parsed() {
+        let configure = Configure::from_args(
+            "app.toml",
+            [
+                "--answers=answers.json",
+                "--format",
+                "json",
+                "--non-interactive",
+                "--upgrade",
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(configure.path(), PathBuf::from("app.toml"));
+        assert_eq!(configure.format(), ConfigFormat::Json);
+        assert!(configure.is_non_interactive());
+        assert!(configure.is_upgrade());
+        assert_eq!(configure.answers(), Some(Path::new("answers.json")));
+
+        assert!(matches!(
+            Configure::from_args("app.toml", ["--verbose"]),
+            Err(ConfigureError::Usage(_))
+        ));
+        assert!(matches!(
+            Configure::from_args("app.toml", ["--config"]),
+            Err(ConfigureError::Usage(_))
+        ));
+    }
+
+    #[test]
+    fn non_interactive_run_writes_config() {
+        let answers = temp_file("answers.toml", ANSWERS);
+        let output = answers.with_file_name("written.json");
+
+        let config: AppConfig = Configure::new(&output)
+            .with_answers(&answers)
+            .with_non_interactive(true)
+            .run()
+            .unwrap();
+
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(
+            config.database,
+            Database::Postgres {
+                url: "postgres://localhost/demo".to_string()
+            }
+        );
+
+        let written = std::fs::read_to_string(&output).unwrap();
+        assert!(written.contains(r#""selected_variant": "Postgres""#));
+
+        // The written config loads back into the same value
+        let loaded: AppConfig = load(&output).unwrap();
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        let answers = temp_file("round_trip.toml", ANSWERS);
+        let output = answers.with_file_name("written.toml");
+
+        let config: AppConfig = Configure::new(&output)
+            .with_answers(&answers)
+            .with_non_interactive(true)
+            .run()
+            .unwrap();
+
+        assert_eq!(load::<AppConfig>(&output).unwrap(), config);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let answers = temp_file("round_trip.json", &json_answers());
+        let output = answers.with_file_name("round_trip-out.json");
+
+        let config: AppConfig = Configure::new(&output)
+            .with_answers(&answers)
+            .with_non_interactive(true)
+            .run()
+            .unwrap();
+
+        assert_eq!(config.origins, ["https://example.com"]);
+        assert_eq!(load::<AppConfig>(&output).unwrap(), config);
+
+        // Written again as TOML, the answers stay the same
+        let toml = output.with_file_name("round_trip-out.toml");
+        Configure::new(&toml)
+            .with_answers(&output)
+            .with_non_interactive(true)
+            .run::<AppConfig>()
+            .unwrap();
+        assert_eq!(load::<AppConfig>(&toml).unwrap(), config);
+    }
+
+    /// [`ANSWERS`] as JSON.
+    fn json_answers() -> String {
+        let value: toml::Value = toml::from_str(ANSWERS).unwrap();
+        serde_json::to_string_pretty(&value).unwrap()
+    }
+
+    #[test]
+    fn answers_are_offered_as_suggestions() {
+        let answers = temp_file("prefill.toml", ANSWERS);
+        let output = answers.with_file_name("prefill-out.toml");
+
+        // `DefaultsBackend` answers every question with its suggestion
+        let configure = Configure::from_args(
+            &output,
+            ["--answers".to_string(), answers.display().to_string()],
+        )
+        .unwrap();
+        let config: AppConfig = configure.run_with(DefaultsBackend::new()).unwrap();
+        assert_eq!(config.name, "demo");
+        assert_eq!(config.server.host, "localhost");
+
+        // The existing config suggests what the answers file leaves open,
+        // the answers file wins where both have an answer
+        let rename = temp_file("prefill-rename.toml", "name = \"renamed\"\n");
+        let config: AppConfig = Configure::new(&output)
+            .with_answers(&rename)
+            .run_with(DefaultsBackend::new())
+            .unwrap();
+        assert_eq!(config.name, "renamed");
+        assert_eq!(config.server.port, 8080);
+    }
+
+    #[test]
+    fn non_interactive_needs_every_answer() {
+        // Without an answers file, nothing answers the questions
+        let output = temp_file("empty.toml", "").with_file_name("unanswered.toml");
+        assert!(matches!(
+            Configure::new(&output)
+                .with_non_interactive(true)
+                .run::<AppConfig>(),
+            Err(ConfigureError::MissingAnswer(path)) if path == "name"
+        ));
+        assert!(!output.exists());
+
+        // A variant's fields need answers once the variant is selected
+        let answers = temp_file(
+            "no_variant_fields.toml",
+            &ANSWERS.replace("url = \"postgres://localhost/demo\"", ""),
+        );
+        assert!(matches!(
+            Configure::new(&output)
+                .with_answers(&answers)
+                .with_non_interactive(true)
+                .run::<AppConfig>(),
+            Err(ConfigureError::MissingAnswer(path)) if path == "database.url"
+        ));
+    }
+
+    #[test]
+    fn rewriting_keeps_comments_and_unknown_keys() {
+        let saved = temp_file(
+            "commented.toml",
+            r#"# Settings of the demo app
+name = "old" # shown in the title bar
+debug = true
+origins = ["https://example.com"]
+log_format = "json"
+
+[server]
+# Public interface
+host = "localhost"
+port = 8080
+
+[database]
+selected_variant = "Postgres"
+url = "postgres://localhost/demo"
+
+[telemetry]
+endpoint = "https://otel.example.com"
+"#,
+        );
+        let answers = temp_file("rename.toml", "name = \"new\"\n\n[server]\nport = 9090\n");
+
+        Configure::new(&saved)
+            .with_answers(&answers)
+            .with_non_interactive(true)
+            .run::<AppConfig>()
+            .unwrap();
+
+        let written = std::fs::read_to_string(&saved).unwrap();
+        assert!(
+            written.starts_with(
+                "# Settings of the demo app\nname = \"new\" # shown in the title bar\n"
+            )
+        );
+        assert!(written.contains("# Public interface\nhost = \"localhost\"\nport = 9090\n"));
+        assert!(written.contains("log_format = \"json\""));
+        assert!(written.contains("[telemetry]\nendpoint = \"https://otel.example.com\""));
+        assert_eq!(load::<AppConfig>(&saved).unwrap().server.port, 9090);
+
+        let saved = temp_file(
+            "extra.json",
+            &json_answers().replacen('{', "{\n  \"log_format\": \"json\",", 1),
+        );
+        Configure::new(&saved)
+            .with_answers(&answers)
+            .with_non_interactive(true)
+            .run::<AppConfig>()
+            .unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&saved).unwrap()).unwrap();
+        assert_eq!(written["log_format"], "json");
+        assert_eq!(written["name"], "new");
+        assert_eq!(written["server"]["port"], 9090);
+    }
+
+    #[test]
+    fn missing_answers_are_reported() {
+        let answers = temp_file("partial.toml", "name = \"demo\"\n");
+        let output = answers.with_file_name("partial-out.toml");
+
+        let result = Configure::new(&output)
+            .with_answers(&answers)
+            .with_non_interactive(true)
+            .run::<AppConfig>();
+
+        assert!(matches!(
+            result,
+            Err(ConfigureError::MissingAnswer(path)) if path == "server.host"
+        ));
+    }
+
+    /// `Server` after a release that added a field.
+    #[derive(Survey, Debug, PartialEq)]
+    struct ServerV2 {
+        #[ask("Host:")]
+        host: String,
+
+        #[ask("Port:")]
+        #[min(1)]
+        port: u16,
+
+        #[ask("Request timeout (s):")]
+        timeout: u32,
+    }
+
+    /// `AppConfig` after a release that added fields.
+    #[derive(Survey, Debug, PartialEq)]
+    struct AppConfigV2 {
+        #[ask("Application name:")]
+        name: String,
+
+        #[ask("Server:")]
+        server: ServerV2,
+
+        #[ask("Database:")]
+        database: Database,
+
+        #[ask("Enable debug logging?")]
+        debug: bool,
+
+        #[ask("Allowed origins:")]
+        origins: Vec<String>,
+
+        #[ask("Deployment region:")]
+        region: String,
+    }
+
+    #[test]
+    fn upgrade_asks_only_new_fields() {
+        let answers = temp_file("upgrade.toml", ANSWERS);
+        let saved = answers.with_file_name("upgrade-config.toml");
+        Configure::new(&saved)
+            .with_answers(&answers)
+            .with_non_interactive(true)
+            .run::<AppConfig>()
+            .unwrap();
+
+        let new: Vec<String> = new_fields::<AppConfigV2>(&saved)
+            .unwrap()
+            .iter()
+            .map(|path| path.as_str().to_string())
+            .collect();
+        assert_eq!(new, ["server.timeout", "region"]);
+
+        // The backend is only asked for the new fields
+        let config: AppConfigV2 = Configure::new(&saved)
+            .with_upgrade(true)
+            .run_with(
+                elicitor::TestBackend::new()
+                    .with_int("server.timeout", 30)
+                    .with_string("region", "eu-central"),
+            )
+            .unwrap();
+
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(config.server.timeout, 30);
+        assert_eq!(config.region, "eu-central");
+        assert_eq!(config.origins, ["https://example.com"]);
+        assert!(new_fields::<AppConfigV2>(&saved).unwrap().is_empty());
+    }
+
+    #[test]
+    fn non_interactive_upgrade_needs_new_answers() {
+        let answers = temp_file("upgrade-ni.toml", ANSWERS);
+        let saved = answers.with_file_name("upgrade-ni-config.toml");
+        Configure::new(&saved)
+            .with_answers(&answers)
+            .with_non_interactive(true)
+            .run::<AppConfig>()
+            .unwrap();
+
+        let upgrade = Configure::new(&saved)
+            .with_upgrade(true)
+            .with_non_interactive(true);
+        assert!(matches!(
+            upgrade.run::<AppConfigV2>(),
+            Err(ConfigureError::MissingAnswer(path)) if path == "server.timeout"
+        ));
+
+        let new_answers = temp_file(
+            "upgrade-new.toml",
+            "region = \"us-east\"\n\n[server]\ntimeout = 10\n",
+        );
+        let config: AppConfigV2 = upgrade.with_answers(new_answers).run().unwrap();
+        assert_eq!(config.name, "demo");
+        assert_eq!(config.region, "us-east");
+    }
+
+    #[test]
+    fn invalid_answers_are_rejected() {
+        let wrong_type = temp_file("wrong_type.toml", &ANSWERS.replace("8080", "\"8080\""));
+        assert!(matches!(
+            load::<AppConfig>(&wrong_type),
+            Err(ConfigureError::InvalidAnswer { path, .. }) if path == "server.port"
+        ));
+
+        let unknown_variant = temp_file("variant.toml", &ANSWERS.replace("Postgres", "Mysql"));
+        assert!(matches!(
+            load::<AppConfig>(&unknown_variant),
+            Err(ConfigureError::InvalidAnswer { path, .. }) if path == "database.selected_variant"
+        ));
+
+        let out_of_range = temp_file("range.toml", &ANSWERS.replace("8080", "0"));
+        assert!(matches!(
+            load::<AppConfig>(&out_of_range),
+            Err(ConfigureError::ValidationFailed { path, .. }) if path == "server.port"
+        ));
+    }
+}
@@ -0,0 +1,88 @@
+//! Loading profiles of suggested and assumed answers from a file.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use elicitor::{Profile, ResponseValue, Responses, Survey};
+use serde_json::Value;
+
+use crate::document::read_answers;
+use crate::{ConfigFormat, ConfigureError};
+
+/// Load every profile in a TOML or JSON file, typed by the questions of `T`.
+///
+/// Each top-level table is a profile named by its key. Its `suggest` table holds the
+/// answers to suggest, its `assume` table the answers to assume, laid out like a config
+/// file:
+///
+/// ```toml
+/// [production.assume]
+/// name = "shop"
+/// debug = false
+///
+/// [production.assume.database]
+/// selected_variant = "Postgres"
+/// url = "postgres://db.internal/shop"
+///
+/// [staging.suggest]
+/// debug = true
+/// ```
+pub fn load_profiles<T: Survey>(path: impl AsRef<Path>) -> Result<Vec<Profile>, ConfigureError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|source| ConfigureError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let document = ConfigFormat::from_path(path).parse(&text, path)?;
+    let Value::Object(profiles) = document else {
+        return Err(ConfigureError::Parse {
+            path: path.to_path_buf(),
+            message: "expected a table of profiles".to_string(),
+        });
+    };
+
+    let definition = T::survey();
+    let read = |table: Option<&Value>| match table {
+        Some(table) => read_answers(&definition.questions, table).map(|answers| keyed(&answers)),
+        None => Ok(Default::default()),
+    };
+    profiles
+        .iter()
+        .map(|(name, profile)| {
+            if !profile.is_object() {
+                return Err(ConfigureError::Parse {
+                    path: path.to_path_buf(),
+                    message: format!("profile '{name}' is not a table"),
+                });
+            }
+            Ok(Profile {
+                name: name.clone(),
+                suggestions: read(profile.get("suggest"))?,
+                assumptions: read(profile.get("assume"))?,
+            })
+        })
+        .collect()
+}
+
+/// Load the profile called `name` from a TOML or JSON file, see [`load_profiles`].
+pub fn load_profile<T: Survey>(
+    path: impl AsRef<Path>,
+    name: &str,
+) -> Result<Profile, ConfigureError> {
+    let path = path.as_ref();
+    load_profiles::<T>(path)?
+        .into_iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| ConfigureError::UnknownProfile {
+            path: path.to_path_buf(),
+            name: name.to_string(),
+        })
+}
+
+/// The answers keyed by their path, as profiles hold them.
+fn keyed(answers: &Responses) -> BTreeMap<String, ResponseValue> {
+    answers
+        .iter()
+        .map(|(path, value)| (path.as_str().to_string(), value.clone()))
+        .collect()
+}
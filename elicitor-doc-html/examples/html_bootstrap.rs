@@ -0,0 +1,26 @@
+//! Bootstrap example - generate a form that fits into a Bootstrap site.
+//!
+//! The default styles are replaced by Bootstrap classes, the submit button gets a
+//! custom class, and the colors follow the browser's dark mode setting.
+//!
+//! Run with: cargo run -p elicitor-doc-html --example html_bootstrap
+
+use elicitor_doc_html::{ColorScheme, CssFramework, HtmlOptions, to_html_with_options};
+use example_surveys::SandwichOrder;
+
+fn main() {
+    let options = HtmlOptions::new()
+        .with_title("Sandwich Order")
+        .with_styles(false)
+        .with_framework(CssFramework::Bootstrap)
+        .with_stylesheet("https://cdn.jsdelivr.net/npm/bootstrap@5.3.3/dist/css/bootstrap.min.css")
+        .with_classes("form", "container my-4")
+        .with_classes("submit", "btn-lg")
+        .with_css(".survey-form { max-width: 40rem; }")
+        .with_color_scheme(ColorScheme::Auto);
+    let html = to_html_with_options::<SandwichOrder>(options);
+
+    std::fs::write("bootstrap.html", &html).expect("Failed to write HTML file");
+
+    println!("Generated bootstrap.html");
+}
@@ -0,0 +1,14 @@
+//! Color question example - generate an HTML form with color pickers.
+//!
+//! Run with: cargo run -p elicitor-doc-html --example html_color
+
+use elicitor_doc_html::to_html;
+use example_surveys::EditorTheme;
+
+fn main() {
+    let html = to_html::<EditorTheme>(Some("Editor Theme"));
+
+    std::fs::write("color.html", &html).expect("Failed to write HTML file");
+
+    println!("Generated color.html");
+}
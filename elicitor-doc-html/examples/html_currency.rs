@@ -0,0 +1,14 @@
+//! Currency question example - generate an HTML form with currency amount fields.
+//!
+//! Run with: cargo run -p elicitor-doc-html --example html_currency
+
+use elicitor_doc_html::to_html;
+use example_surveys::MortgageApplication;
+
+fn main() {
+    let html = to_html::<MortgageApplication>(Some("Mortgage Application"));
+
+    std::fs::write("currency.html", &html).expect("Failed to write HTML file");
+
+    println!("Generated currency.html");
+}
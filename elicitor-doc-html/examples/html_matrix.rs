@@ -0,0 +1,14 @@
+//! Matrix question example - generate an HTML form with a table of radio buttons.
+//!
+//! Run with: cargo run -p elicitor-doc-html --example html_matrix
+
+use elicitor_doc_html::to_html;
+use example_surveys::CourseEvaluation;
+
+fn main() {
+    let html = to_html::<CourseEvaluation>(Some("Course Evaluation"));
+
+    std::fs::write("matrix.html", &html).expect("Failed to write HTML file");
+
+    println!("Generated matrix.html");
+}
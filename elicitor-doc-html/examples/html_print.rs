@@ -0,0 +1,18 @@
+//! Print example - generate a paper form to fill in by hand.
+//!
+//! Run with: cargo run -p elicitor-doc-html --example html_print
+
+use elicitor_doc_html::{HtmlOptions, to_html_with_options};
+use example_surveys::JobApplication;
+
+fn main() {
+    let options = HtmlOptions::new()
+        .with_title("Acme Corp - Job Application")
+        .with_print_mode(true);
+
+    let html = to_html_with_options::<JobApplication>(options);
+
+    std::fs::write("job_application_print.html", &html).expect("Failed to write HTML file");
+
+    println!("Generated job_application_print.html");
+}
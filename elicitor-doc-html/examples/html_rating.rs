@@ -0,0 +1,14 @@
+//! Rating scale example - generate an HTML form with star, numeric and Likert scales.
+//!
+//! Run with: cargo run -p elicitor-doc-html --example html_rating
+
+use elicitor_doc_html::to_html;
+use example_surveys::CourseFeedback;
+
+fn main() {
+    let html = to_html::<CourseFeedback>(Some("Course Feedback"));
+
+    std::fs::write("rating.html", &html).expect("Failed to write HTML file");
+
+    println!("Generated rating.html");
+}
@@ -1,12 +1,32 @@
 //! Validation example - generate an HTML form with validated fields.
 //!
+//! The form checks the age bounds in the browser, and the field rules mirror the
+//! `#[validate]` functions so mistakes show up before the form is submitted.
+//!
 //! Run with: cargo run -p derive-html-document --example validation
 
-use elicitor_doc_html::to_html;
+use elicitor_doc_html::{FieldRules, HtmlOptions, to_html_with_options};
 use example_surveys::AccountCreation;
 
 fn main() {
-    let html = to_html::<AccountCreation>(Some("Account Creation"));
+    let options = HtmlOptions::new()
+        .with_title("Account Creation")
+        .with_client_validation(true)
+        .with_field_rules(
+            "username",
+            FieldRules::new().with_length(Some(3), None).with_pattern(
+                "[A-Za-z0-9_]*",
+                "Username can only contain letters, numbers, and underscores",
+            ),
+        )
+        .with_field_rules(
+            "email",
+            FieldRules::new().with_script(
+                r#"if (!value.includes("@")) return "Email must contain '@' symbol";
+                   if (!value.includes(".")) return "Email must contain a domain";"#,
+            ),
+        );
+    let html = to_html_with_options::<AccountCreation>(options);
 
     std::fs::write("validation.html", &html).expect("Failed to write HTML file");
 
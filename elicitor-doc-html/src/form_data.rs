@@ -0,0 +1,458 @@
+//! Parsing submitted HTML forms back into responses.
+
+use std::collections::HashMap;
+
+use elicitor::{
+    COMMENT_KEY, DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, Survey,
+    SurveyDefinition, apply_assumed,
+};
+
+/// Error type for parsing form submissions.
+#[derive(Debug, thiserror::Error)]
+pub enum FormDataError {
+    #[error("No answer for {0}")]
+    MissingValue(ResponsePath),
+
+    #[error("Invalid answer for {path}: {message}")]
+    InvalidValue { path: ResponsePath, message: String },
+}
+
+/// Parse a submission of a form generated by [`to_html`](crate::to_html) into `T`.
+///
+/// `fields` are the decoded name/value pairs of the submission, as web frameworks
+/// provide them. Answers are checked with the survey's validators, like backends do.
+pub fn from_form_data<T: Survey>(fields: &[(String, String)]) -> Result<T, FormDataError> {
+    let responses = form_data_to_responses(&T::survey(), fields)?;
+    for (path, value) in responses.iter() {
+        T::validate_field(value, &responses, path).map_err(|message| {
+            FormDataError::InvalidValue {
+                path: path.clone(),
+                message,
+            }
+        })?;
+    }
+    Ok(T::from_responses(&responses))
+}
+
+/// Parse an `application/x-www-form-urlencoded` request body into `T`.
+pub fn from_urlencoded<T: Survey>(body: &str) -> Result<T, FormDataError> {
+    from_form_data(&parse_urlencoded(body))
+}
+
+/// Map a form submission onto the questions of `definition`.
+///
+/// Values are converted to the question types and checked against their bounds and
+/// choices. Assumed answers, which forms leave out, are filled in.
+pub fn form_data_to_responses(
+    definition: &SurveyDefinition,
+    fields: &[(String, String)],
+) -> Result<Responses, FormDataError> {
+    let mut form = FormFields::default();
+    for (name, value) in fields {
+        form.values.entry(name).or_default().push(value);
+    }
+
+    let mut responses = Responses::new();
+    for question in definition.questions() {
+        form.import_question(question, "", &ResponsePath::empty(), &mut responses)?;
+    }
+    apply_assumed(definition, &mut responses);
+    Ok(responses)
+}
+
+/// Decode an `application/x-www-form-urlencoded` body into name/value pairs.
+pub fn parse_urlencoded(body: &str) -> Vec<(String, String)> {
+    body.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = (bytes[idx] == b'%')
+            .then(|| bytes.get(idx + 1..idx + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[idx], escaped) {
+            (_, Some(b)) => {
+                decoded.push(b);
+                idx += 3;
+            }
+            (b'+', None) => {
+                decoded.push(b' ');
+                idx += 1;
+            }
+            (b, None) => {
+                decoded.push(b);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Submitted values by field name. Checkbox groups submit a name several times.
+#[derive(Default)]
+struct FormFields<'a> {
+    values: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl FormFields<'_> {
+    fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name)?.first().copied()
+    }
+
+    fn all(&self, name: &str) -> &[&str] {
+        self.values.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Import `question`, whose inputs are named below `field_prefix` and whose answers
+    /// go below `prefix`. The two differ for enum follow-ups, which forms group below
+    /// the variant, and for multi-select items, which forms number by variant and
+    /// responses by position.
+    fn import_question(
+        &self,
+        question: &Question,
+        field_prefix: &str,
+        prefix: &ResponsePath,
+        responses: &mut Responses,
+    ) -> Result<(), FormDataError> {
+        let segment = question.path().as_str();
+        let (field, path) = if segment.is_empty() {
+            (field_prefix.to_string(), prefix.clone())
+        } else {
+            (join(field_prefix, segment), prefix.child(segment))
+        };
+        if let DefaultValue::Assumed(_) = question.default() {
+            return Ok(());
+        }
+
+        if let Some(value) = self.convert(question.kind(), &field, &path, responses)? {
+            responses.insert(path.clone(), value);
+        }
+        if question.allows_comment()
+            && let Some(comment) = self
+                .get(&join(&field, COMMENT_KEY))
+                .filter(|comment| !comment.trim().is_empty())
+        {
+            responses.insert(path.child(COMMENT_KEY), comment.to_string());
+        }
+        Ok(())
+    }
+
+    /// The answer to a question of `kind`. Enums and groups store their answers
+    /// themselves and return `None`.
+    fn convert(
+        &self,
+        kind: &QuestionKind,
+        field: &str,
+        path: &ResponsePath,
+        responses: &mut Responses,
+    ) -> Result<Option<ResponseValue>, FormDataError> {
+        let text = self.get(field).map(str::trim);
+        let required = || {
+            text.filter(|text| !text.is_empty())
+                .ok_or_else(|| FormDataError::MissingValue(path.clone()))
+        };
+        let value = match kind {
+            QuestionKind::Unit => return Ok(None),
+
+            QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
+            | QuestionKind::Multiline(_)
+            | QuestionKind::Masked(_)
+            | QuestionKind::Path(_) => {
+                // Keep the whitespace of text answers
+                ResponseValue::String(self.get(field).unwrap_or_default().to_string())
+            }
+
+            QuestionKind::Int(int_q) => {
+                let value = parse::<i64>(required()?, path)?;
+                check_bounds(value, int_q.min, int_q.max, path)?;
+                ResponseValue::Int(value)
+            }
+
+            QuestionKind::Float(float_q) => {
+                let value = parse::<f64>(required()?, path)?;
+                check_bounds(value, float_q.min, float_q.max, path)?;
+                ResponseValue::Float(value)
+            }
+
+            QuestionKind::Currency(currency_q) => {
+                let minor = currency_q
+                    .parse(required()?)
+                    .and_then(|minor| currency_q.check_bounds(minor).map(|()| minor))
+                    .map_err(|message| invalid(path, message))?;
+                ResponseValue::Int(minor)
+            }
+
+            QuestionKind::Color(color_q) => {
+                let color = color_q
+                    .parse(required()?)
+                    .map_err(|message| invalid(path, message))?;
+                ResponseValue::String(color.to_hex())
+            }
+
+            QuestionKind::List(list_q) => {
+                let items = text
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty());
+                match list_q.element_kind {
+                    ListElementKind::String => {
+                        ResponseValue::StringList(items.map(str::to_string).collect())
+                    }
+                    ListElementKind::Int { min, max } => ResponseValue::IntList(
+                        items
+                            .map(|item| {
+                                let value = parse::<i64>(item, path)?;
+                                check_bounds(value, min, max, path).map(|()| value)
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    ListElementKind::Float { min, max } => ResponseValue::FloatList(
+                        items
+                            .map(|item| {
+                                let value = parse::<f64>(item, path)?;
+                                check_bounds(value, min, max, path).map(|()| value)
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                }
+            }
+
+            // Unchecked checkboxes are not submitted at all
+            QuestionKind::Confirm(_) => ResponseValue::Bool(text.is_some_and(is_checked)),
+
+            QuestionKind::Select(select_q) => {
+                let value = required()?;
+                if select_q.position(value).is_none() {
+                    return Err(invalid(
+                        path,
+                        format!("{value:?} is not one of the choices"),
+                    ));
+                }
+                ResponseValue::String(value.to_string())
+            }
+
+            QuestionKind::Rating(rating_q) => {
+                let value = parse::<i64>(required()?, path)?;
+                if !rating_q.contains(value) {
+                    return Err(invalid(path, format!("{value} is not on the scale")));
+                }
+                ResponseValue::Int(value)
+            }
+
+            QuestionKind::Matrix(matrix_q) => {
+                // One radio group per row, named by row and valued by column label
+                let columns = matrix_q
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        let row_path = path.child(row);
+                        let column = self
+                            .get(&join(field, row))
+                            .ok_or_else(|| FormDataError::MissingValue(row_path.clone()))?;
+                        matrix_q
+                            .columns
+                            .iter()
+                            .position(|label| label == column)
+                            .ok_or_else(|| {
+                                invalid(&row_path, format!("{column:?} is not one of the columns"))
+                            })
+                    })
+                    .collect::<Result<_, _>>()?;
+                ResponseValue::ChosenVariants(columns)
+            }
+
+            QuestionKind::OneOf(one_of) => {
+                let choice_path = path.child(SELECTED_VARIANT_KEY);
+                let text = text
+                    .filter(|text| !text.is_empty())
+                    .ok_or_else(|| FormDataError::MissingValue(choice_path.clone()))?;
+                let idx = parse_index(text, one_of.variants.len(), &choice_path)?;
+                responses.insert(choice_path, ResponseValue::ChosenVariant(idx));
+
+                let variant = &one_of.variants[idx];
+                match &variant.kind {
+                    QuestionKind::Unit => {}
+                    QuestionKind::AllOf(all_of) => {
+                        let variant_field = join(field, &variant.name);
+                        for nested in all_of.questions() {
+                            self.import_question(nested, &variant_field, path, responses)?;
+                        }
+                    }
+                    kind => {
+                        let nested = Question::new(variant.name.as_str(), "", kind.clone());
+                        self.import_question(&nested, field, path, responses)?;
+                    }
+                }
+                return Ok(None);
+            }
+
+            QuestionKind::AnyOf(any_of) => {
+                let choices_path = path.child(SELECTED_VARIANTS_KEY);
+                let mut chosen = self
+                    .all(&format!("{field}[]"))
+                    .iter()
+                    .map(|text| parse_index(text.trim(), any_of.variants.len(), &choices_path))
+                    .collect::<Result<Vec<_>, _>>()?;
+                chosen.sort_unstable();
+                chosen.dedup();
+
+                for (item, &idx) in chosen.iter().enumerate() {
+                    let item_path = path.child(&item.to_string());
+                    responses.insert(
+                        item_path.child(SELECTED_VARIANT_KEY),
+                        ResponseValue::ChosenVariant(idx),
+                    );
+                    if let QuestionKind::AllOf(all_of) = &any_of.variants[idx].kind {
+                        let item_field = join(field, &idx.to_string());
+                        for nested in all_of.questions() {
+                            self.import_question(nested, &item_field, &item_path, responses)?;
+                        }
+                    }
+                }
+                responses.insert(choices_path, ResponseValue::ChosenVariants(chosen));
+                return Ok(None);
+            }
+
+            QuestionKind::AllOf(all_of) => {
+                for nested in all_of.questions() {
+                    self.import_question(nested, field, path, responses)?;
+                }
+                return Ok(None);
+            }
+        };
+        Ok(Some(value))
+    }
+}
+
+fn join(prefix: &str, segment: &str) -> String {
+    if prefix.is_empty() {
+        segment.to_string()
+    } else {
+        format!("{prefix}.{segment}")
+    }
+}
+
+/// Whether a checkbox value means checked. Browsers submit `on` unless a value is set.
+fn is_checked(value: &str) -> bool {
+    !matches!(
+        value.to_ascii_lowercase().as_str(),
+        "" | "off" | "false" | "no" | "0"
+    )
+}
+
+fn parse<N: std::str::FromStr>(text: &str, path: &ResponsePath) -> Result<N, FormDataError> {
+    text.parse()
+        .map_err(|_| invalid(path, format!("{text:?} is not a number")))
+}
+
+fn parse_index(text: &str, len: usize, path: &ResponsePath) -> Result<usize, FormDataError> {
+    text.parse()
+        .ok()
+        .filter(|idx| *idx < len)
+        .ok_or_else(|| invalid(path, format!("{text:?} is not one of the choices")))
+}
+
+fn check_bounds<N: PartialOrd + std::fmt::Display>(
+    value: N,
+    min: Option<N>,
+    max: Option<N>,
+    path: &ResponsePath,
+) -> Result<(), FormDataError> {
+    match (min, max) {
+        (Some(min), _) if value < min => Err(invalid(path, format!("{value} is below {min}"))),
+        (_, Some(max)) if value > max => Err(invalid(path, format!("{value} is above {max}"))),
+        _ => Ok(()),
+    }
+}
+
+fn invalid(path: &ResponsePath, message: String) -> FormDataError {
+    FormDataError::InvalidValue {
+        path: path.clone(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use example_surveys::{Checkout, PaymentMethod, ShippingMethod};
+
+    fn pairs(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn urlencoded_bodies_are_decoded() {
+        assert_eq!(
+            parse_urlencoded("name=Ada+Lovelace&tags%5B%5D=1&tags%5B%5D=3&note="),
+            pairs(&[
+                ("name", "Ada Lovelace"),
+                ("tags[]", "1"),
+                ("tags[]", "3"),
+                ("note", ""),
+            ])
+        );
+    }
+
+    #[test]
+    fn enum_follow_ups_are_read_below_the_variant() {
+        let checkout: Checkout = from_form_data(&pairs(&[
+            ("shipping", "1"),
+            ("payment", "0"),
+            ("payment.Credit Card.card_number", "4111"),
+            ("payment.Credit Card.cvv", "123"),
+        ]))
+        .unwrap();
+        assert!(matches!(checkout.shipping, ShippingMethod::Express));
+        assert!(matches!(
+            checkout.payment,
+            PaymentMethod::CreditCard { card_number, cvv } if card_number == "4111" && cvv == "123"
+        ));
+
+        let err = from_urlencoded::<Checkout>("payment=3&payment.Other.0=IOU").unwrap_err();
+        assert!(
+            matches!(err, FormDataError::MissingValue(path) if path.as_str() == "shipping.selected_variant")
+        );
+    }
+
+    #[test]
+    fn checkbox_groups_become_multi_selections() {
+        use example_surveys::{DeveloperProfile, Hobby, ProgrammingLanguage};
+
+        let body = "name=Ada&languages%5B%5D=0&hobbies%5B%5D=4&hobbies%5B%5D=1\
+            &hobbies.1.favorite_game=Go&hobbies.1.hours_per_week=5\
+            &hobbies.4.plays_instrument=on&hobbies.4.favorite_genre=Jazz";
+        let profile: DeveloperProfile = from_urlencoded(body).unwrap();
+
+        assert!(matches!(profile.languages[..], [ProgrammingLanguage::Rust]));
+        assert!(matches!(
+            &profile.hobbies[..],
+            [
+                Hobby::Gaming {
+                    hours_per_week: 5,
+                    ..
+                },
+                Hobby::Music {
+                    plays_instrument: true,
+                    ..
+                }
+            ]
+        ));
+    }
+}
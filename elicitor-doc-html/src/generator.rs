@@ -1,6 +1,16 @@
 //! HTML form generator implementation.
 
-use elicitor::{DefaultValue, ListElementKind, Question, QuestionKind, Survey, SurveyDefinition};
+use std::collections::BTreeMap;
+
+use elicitor::{
+    COMMENT_KEY, DefaultValue, FieldHandling, ListElementKind, MatrixQuestion, Messages, PathMode,
+    PathQuestion, Question, QuestionKind, RatingQuestion, ResponseValue, SensitiveField,
+    SensitiveSink, Survey, SurveyDefinition, Variant,
+};
+
+use crate::print;
+use crate::theme::{self, ColorScheme, CssFramework};
+use crate::validation::{self, FieldRules};
 
 /// Options for HTML generation.
 #[derive(Debug, Clone, Default)]
@@ -13,6 +23,30 @@ pub struct HtmlOptions {
     pub full_document: bool,
     /// Custom CSS class prefix for all generated elements.
     pub class_prefix: String,
+    /// URL the form posts its answers to. Without it the browser submits to the page URL
+    /// with a GET request.
+    pub action: Option<String>,
+    /// Whether to emit a script that checks the answers before the form is submitted.
+    pub client_validation: bool,
+    /// Extra client-side checks, by response path.
+    pub field_rules: BTreeMap<String, FieldRules>,
+    /// Messages of the client-side checks of lengths, formats and selections. Bounds
+    /// of numbers use the messages of their question.
+    pub messages: Messages,
+    /// CSS framework whose classes are added to the generated elements.
+    pub framework: CssFramework,
+    /// Extra classes, by element name (e.g. `input` for `{prefix}-input`).
+    pub classes: BTreeMap<String, String>,
+    /// Extra classes for the wrapper of questions, by question kind (e.g. `int`).
+    pub kind_classes: BTreeMap<String, String>,
+    /// Stylesheets linked from the document.
+    pub stylesheets: Vec<String>,
+    /// CSS added in a `<style>` block after the default styles.
+    pub custom_css: Option<String>,
+    /// Light, dark or browser-selected colors.
+    pub color_scheme: ColorScheme,
+    /// Whether to generate a paper form to fill in by hand instead of inputs.
+    pub print_mode: bool,
 }
 
 impl HtmlOptions {
@@ -23,6 +57,17 @@ impl HtmlOptions {
             include_styles: true,
             full_document: true,
             class_prefix: "survey".to_string(),
+            action: None,
+            client_validation: false,
+            field_rules: BTreeMap::new(),
+            messages: Messages::default(),
+            framework: CssFramework::None,
+            classes: BTreeMap::new(),
+            kind_classes: BTreeMap::new(),
+            stylesheets: Vec::new(),
+            custom_css: None,
+            color_scheme: ColorScheme::Light,
+            print_mode: false,
         }
     }
 
@@ -49,6 +94,100 @@ impl HtmlOptions {
         self.class_prefix = prefix.into();
         self
     }
+
+    /// Post the answers to `url`, e.g. a handler that parses them with
+    /// [`from_urlencoded`](crate::from_urlencoded).
+    pub fn with_action(mut self, url: impl Into<String>) -> Self {
+        self.action = Some(url.into());
+        self
+    }
+
+    /// Check bounds, required selections and field rules in the browser before submitting.
+    pub fn with_client_validation(mut self, enabled: bool) -> Self {
+        self.client_validation = enabled;
+        self
+    }
+
+    /// Add client-side checks for the field at `path`, enabling client validation.
+    pub fn with_field_rules(mut self, path: impl Into<String>, rules: FieldRules) -> Self {
+        self.field_rules.insert(path.into(), rules);
+        self.client_validation = true;
+        self
+    }
+
+    /// Show the messages of client-side checks in another language, e.g.
+    /// `Messages::translated(&german)`.
+    pub fn with_messages(mut self, messages: Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Add the classes of a CSS framework next to the prefixed ones.
+    ///
+    /// The default styles are meant for standalone forms, so they are usually switched off
+    /// with [`with_styles(false)`](Self::with_styles) when a framework provides the look.
+    pub fn with_framework(mut self, framework: CssFramework) -> Self {
+        self.framework = framework;
+        self
+    }
+
+    /// Add `classes` to every element with the class `{prefix}-{element}`.
+    pub fn with_classes(mut self, element: impl Into<String>, classes: impl Into<String>) -> Self {
+        self.classes.insert(element.into(), classes.into());
+        self
+    }
+
+    /// Add `classes` to the wrapper of every question of a kind.
+    ///
+    /// Kinds are named like the [`QuestionKind`] variants in snake case, e.g. `int`,
+    /// `multiline` or `one_of`.
+    pub fn with_kind_classes(
+        mut self,
+        kind: impl Into<String>,
+        classes: impl Into<String>,
+    ) -> Self {
+        self.kind_classes.insert(kind.into(), classes.into());
+        self
+    }
+
+    /// Link an external stylesheet.
+    pub fn with_stylesheet(mut self, href: impl Into<String>) -> Self {
+        self.stylesheets.push(href.into());
+        self
+    }
+
+    /// Add CSS in a `<style>` block, after the default styles so it can override them.
+    pub fn with_css(mut self, css: impl Into<String>) -> Self {
+        self.custom_css = Some(css.into());
+        self
+    }
+
+    /// Use light or dark colors, or follow the browser.
+    pub fn with_color_scheme(mut self, scheme: ColorScheme) -> Self {
+        self.color_scheme = scheme;
+        self
+    }
+
+    /// Generate a form to print and fill in by hand.
+    ///
+    /// Inputs become ruled lines sized by the expected answer, checkboxes empty squares and
+    /// choices circles to tick. The fields of every enum variant are shown, and the form
+    /// has no submit button or validation script.
+    pub fn with_print_mode(mut self, enabled: bool) -> Self {
+        self.print_mode = enabled;
+        self
+    }
+}
+
+/// Generated forms never pre-fill masked fields.
+impl SensitiveSink for HtmlOptions {
+    fn sink_name(&self) -> String {
+        "html form".to_string()
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        FieldHandling::Omitted
+    }
 }
 
 /// Generate an HTML form from a survey type.
@@ -64,38 +203,29 @@ pub fn to_html<T: Survey>(title: Option<&str>) -> String {
 
 /// Generate an HTML form with custom options.
 pub fn to_html_with_options<T: Survey>(options: HtmlOptions) -> String {
-    let definition = T::survey();
-    generate_html(&definition, &options)
+    definition_to_html(&T::survey(), &options)
 }
 
-/// Generate HTML from a survey definition.
-fn generate_html(definition: &SurveyDefinition, options: &HtmlOptions) -> String {
+/// Generate an HTML form from a survey definition, e.g. one translated with
+/// `SurveyDefinition::translate`.
+pub fn definition_to_html(definition: &SurveyDefinition, options: &HtmlOptions) -> String {
     let mut html = String::new();
     let prefix = &options.class_prefix;
 
-    if options.full_document {
-        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
-        html.push_str("  <meta charset=\"UTF-8\">\n");
-        html.push_str(
-            "  <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n",
-        );
-
-        if let Some(title) = &options.title {
-            html.push_str(&format!("  <title>{}</title>\n", escape_html(title)));
-        }
-
-        if options.include_styles {
-            html.push_str(&generate_styles(prefix));
-        }
-
-        html.push_str("</head>\n<body>\n");
-    }
-
-    html.push_str(&format!("<form class=\"{prefix}-form\">\n"));
+    let action = options
+        .action
+        .as_deref()
+        .filter(|_| !options.print_mode)
+        .map(|url| format!(" method=\"post\" action=\"{}\"", escape_html(url)))
+        .unwrap_or_default();
+    let mut form = format!(
+        "<form class=\"{prefix}-form\"{action}{}>\n",
+        theme::form_attributes(options)
+    );
 
     // Prelude
     if let Some(prelude) = &definition.prelude {
-        html.push_str(&format!(
+        form.push_str(&format!(
             "  <div class=\"{prefix}-prelude\">{}</div>\n",
             escape_html(prelude)
         ));
@@ -103,33 +233,88 @@ fn generate_html(definition: &SurveyDefinition, options: &HtmlOptions) -> String
 
     // Title
     if let Some(title) = &options.title {
-        html.push_str(&format!(
+        form.push_str(&format!(
             "  <h1 class=\"{prefix}-title\">{}</h1>\n",
             escape_html(title)
         ));
     }
 
     // Questions
-    html.push_str(&format!("  <div class=\"{prefix}-questions\">\n"));
-    for question in definition.questions() {
-        html.push_str(&generate_question(question, prefix, 2, None));
+    form.push_str(&format!("  <div class=\"{prefix}-questions\">\n"));
+    if options.print_mode {
+        form.push_str(&print::generate_questions(definition, options));
+    } else {
+        for question in definition.questions() {
+            form.push_str(&generate_question(question, options, 2, None));
+        }
     }
-    html.push_str("  </div>\n");
+    form.push_str("  </div>\n");
 
     // Epilogue
     if let Some(epilogue) = &definition.epilogue {
-        html.push_str(&format!(
+        form.push_str(&format!(
             "  <div class=\"{prefix}-epilogue\">{}</div>\n",
             escape_html(epilogue)
         ));
     }
 
     // Submit button
-    html.push_str(&format!(
-        "  <button type=\"submit\" class=\"{prefix}-submit\">Submit</button>\n"
-    ));
+    if !options.print_mode {
+        form.push_str(&format!(
+            "  <button type=\"submit\" class=\"{prefix}-submit\">Submit</button>\n"
+        ));
+    }
 
-    html.push_str("</form>\n");
+    form.push_str("</form>\n");
+    let has_variant_fields = form.contains(&format!("class=\"{prefix}-nested\""));
+
+    if options.full_document {
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        html.push_str("  <meta charset=\"UTF-8\">\n");
+        html.push_str(
+            "  <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n",
+        );
+
+        if let Some(title) = &options.title {
+            html.push_str(&format!("  <title>{}</title>\n", escape_html(title)));
+        }
+
+        if options.color_scheme != ColorScheme::Light {
+            html.push_str(&format!(
+                "  <meta name=\"color-scheme\" content=\"{}\">\n",
+                options.color_scheme.meta_content()
+            ));
+        }
+
+        if options.include_styles {
+            html.push_str(&generate_styles(prefix, options.color_scheme));
+            if options.print_mode {
+                html.push_str(&print::generate_styles(prefix));
+            }
+        }
+        html.push_str(&theme::generate_links(options, "  "));
+
+        // Without the toggle script every variant's fields stay visible
+        if has_variant_fields {
+            html.push_str(&format!(
+                "  <noscript><style>.{prefix}-nested[hidden] {{ display: block; }}</style></noscript>\n"
+            ));
+        }
+
+        html.push_str("</head>\n<body>\n");
+    } else {
+        html.push_str(&theme::generate_links(options, ""));
+    }
+
+    html.push_str(&theme::apply_classes(&form, options));
+
+    if options.client_validation && !options.print_mode {
+        html.push_str(&validation::generate_script(definition, options));
+    }
+    if has_variant_fields {
+        html.push_str(&generate_toggle_script(prefix));
+    }
+    html.push_str(&theme::generate_script(options));
 
     if options.full_document {
         html.push_str("</body>\n</html>\n");
@@ -141,10 +326,11 @@ fn generate_html(definition: &SurveyDefinition, options: &HtmlOptions) -> String
 /// Generate HTML for a single question.
 fn generate_question(
     question: &Question,
-    prefix: &str,
+    options: &HtmlOptions,
     indent: usize,
     parent_path: Option<&str>,
 ) -> String {
+    let prefix = options.class_prefix.as_str();
     let ind = "  ".repeat(indent);
 
     // Build the full path
@@ -185,7 +371,7 @@ fn generate_question(
 
             html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
             html.push_str(&format!(
-                "{ind}  <label for=\"{field_id}\">{}</label>\n",
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
             html.push_str(&format!(
@@ -194,15 +380,44 @@ fn generate_question(
             html.push_str(&format!("{ind}</div>\n"));
         }
 
+        QuestionKind::Autocomplete(autocomplete_q) => {
+            let value_attr = default_value
+                .and_then(|v| v.as_str())
+                .or(autocomplete_q.default.as_deref())
+                .map(|s| format!(" value=\"{}\"", escape_html(s)))
+                .unwrap_or_default();
+
+            html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
+            html.push_str(&format!(
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
+                escape_html(&label)
+            ));
+            html.push_str(&format!(
+                "{ind}  <input type=\"text\" id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-input\" list=\"{field_id}-suggestions\"{value_attr}>\n"
+            ));
+            // Callback sources are evaluated once with empty input
+            html.push_str(&format!(
+                "{ind}  <datalist id=\"{field_id}-suggestions\">\n"
+            ));
+            for suggestion in autocomplete_q.completions("") {
+                html.push_str(&format!(
+                    "{ind}    <option value=\"{}\">\n",
+                    escape_html(&suggestion)
+                ));
+            }
+            html.push_str(&format!("{ind}  </datalist>\n"));
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+
         QuestionKind::Multiline(_) => {
             let content = default_value
                 .and_then(|v| v.as_str())
-                .map(|s| escape_html(s))
+                .map(escape_html)
                 .unwrap_or_default();
 
             html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
             html.push_str(&format!(
-                "{ind}  <label for=\"{field_id}\">{}</label>\n",
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
             html.push_str(&format!(
@@ -215,7 +430,7 @@ fn generate_question(
             // Don't pre-fill password fields for security
             html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
             html.push_str(&format!(
-                "{ind}  <label for=\"{field_id}\">{}</label>\n",
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
             html.push_str(&format!(
@@ -232,7 +447,7 @@ fn generate_question(
 
             html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
             html.push_str(&format!(
-                "{ind}  <label for=\"{field_id}\">{}</label>\n",
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
 
@@ -258,7 +473,7 @@ fn generate_question(
 
             html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
             html.push_str(&format!(
-                "{ind}  <label for=\"{field_id}\">{}</label>\n",
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
 
@@ -276,6 +491,102 @@ fn generate_question(
             html.push_str(&format!("{ind}</div>\n"));
         }
 
+        QuestionKind::Currency(currency_q) => {
+            let value_attr = default_value
+                .and_then(|v| v.as_int())
+                .or(currency_q.default)
+                .map(|minor| format!(" value=\"{}\"", escape_html(&currency_q.format(minor))))
+                .unwrap_or_default();
+
+            html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
+            html.push_str(&format!(
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{} ({})</label>\n",
+                escape_html(&label),
+                escape_html(currency_q.symbol())
+            ));
+            // Amounts are typed with grouping and decimal separators, so use a text input
+            html.push_str(&format!(
+                "{ind}  <input type=\"text\" inputmode=\"decimal\" id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-input\" data-currency=\"{}\"{value_attr}>\n",
+                currency_q.currency
+            ));
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+
+        QuestionKind::Color(color_q) => {
+            // <input type=color> always has a value, so fall back to black like browsers do
+            let value = default_value
+                .and_then(|v| v.as_str())
+                .and_then(|hex| hex.parse::<elicitor::Color>().ok())
+                .or(color_q.default)
+                .unwrap_or_default();
+
+            html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
+            html.push_str(&format!(
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
+                escape_html(&label)
+            ));
+            html.push_str(&format!(
+                "{ind}  <input type=\"color\" id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-color\" value=\"{value}\">\n"
+            ));
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+
+        QuestionKind::Path(path_q) => {
+            html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
+            html.push_str(&format!(
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
+                escape_html(&label)
+            ));
+            html.push_str(&format!(
+                "{ind}  <input {}>\n",
+                path_input_attrs(path_q, &field_id, &path, prefix)
+            ));
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+
+        QuestionKind::Rating(rating_q) => {
+            let default_selected = default_value.and_then(|v| v.as_int()).or(rating_q.default);
+
+            html.push_str(&format!(
+                "{ind}<fieldset class=\"{prefix}-fieldset {prefix}-rating\">\n"
+            ));
+            html.push_str(&format!(
+                "{ind}  <legend>{}</legend>\n",
+                escape_html(&label)
+            ));
+            html.push_str(&generate_rating_scale(
+                rating_q,
+                &path,
+                default_selected,
+                prefix,
+                indent + 1,
+            ));
+            html.push_str(&format!("{ind}</fieldset>\n"));
+        }
+
+        QuestionKind::Matrix(matrix_q) => {
+            let default_selected = match default_value {
+                Some(ResponseValue::ChosenVariants(columns)) => columns.as_slice(),
+                _ => &[],
+            };
+
+            html.push_str(&format!(
+                "{ind}<fieldset class=\"{prefix}-fieldset {prefix}-matrix\">\n"
+            ));
+            html.push_str(&format!(
+                "{ind}  <legend>{}</legend>\n",
+                escape_html(&label)
+            ));
+            html.push_str(&generate_matrix_table(
+                matrix_q,
+                &path,
+                default_selected,
+                prefix,
+                indent + 1,
+            ));
+            html.push_str(&format!("{ind}</fieldset>\n"));
+        }
+
         QuestionKind::Confirm(confirm_q) => {
             // Use suggested value if provided, otherwise fall back to confirm_q.default
             let is_checked = default_value
@@ -287,15 +598,45 @@ fn generate_question(
                 "{ind}<div class=\"{prefix}-field {prefix}-checkbox\">\n"
             ));
             html.push_str(&format!(
-                "{ind}  <input type=\"checkbox\" id=\"{field_id}\" name=\"{path}\"{checked}>\n"
+                "{ind}  <input type=\"checkbox\" class=\"{prefix}-check\" id=\"{field_id}\" name=\"{path}\"{checked}>\n"
             ));
             html.push_str(&format!(
-                "{ind}  <label for=\"{field_id}\">{}</label>\n",
+                "{ind}  <label class=\"{prefix}-check-label\" for=\"{field_id}\">{}</label>\n",
                 escape_html(&label)
             ));
             html.push_str(&format!("{ind}</div>\n"));
         }
 
+        QuestionKind::Select(select_q) => {
+            let default_selected = default_value
+                .and_then(|v| v.as_str())
+                .and_then(|v| select_q.position(v))
+                .or(select_q.default);
+
+            html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
+            html.push_str(&format!(
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
+                escape_html(&label)
+            ));
+            html.push_str(&format!(
+                "{ind}  <select id=\"{field_id}\" name=\"{path}\" class=\"{prefix}-select\">\n"
+            ));
+            for (idx, option) in select_q.options.iter().enumerate() {
+                let selected = if default_selected == Some(idx) {
+                    " selected"
+                } else {
+                    ""
+                };
+                html.push_str(&format!(
+                    "{ind}    <option value=\"{}\"{selected}>{}</option>\n",
+                    escape_html(&option.value),
+                    escape_html(&option.label)
+                ));
+            }
+            html.push_str(&format!("{ind}  </select>\n"));
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+
         QuestionKind::List(list_q) => {
             let type_hint = match &list_q.element_kind {
                 ListElementKind::String => "comma-separated text",
@@ -305,7 +646,7 @@ fn generate_question(
 
             html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
             html.push_str(&format!(
-                "{ind}  <label for=\"{field_id}\">{} ({})</label>\n",
+                "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{} ({})</label>\n",
                 escape_html(&label),
                 type_hint
             ));
@@ -356,18 +697,25 @@ fn generate_question(
                 };
 
                 html.push_str(&format!("{ind}  <div class=\"{prefix}-radio-option\">\n"));
+                let described = variant_described_by(variant, &variant_id);
                 html.push_str(&format!(
-                    "{ind}    <input type=\"radio\" id=\"{variant_id}\" name=\"{path}\" value=\"{idx}\"{checked}>\n"
+                    "{ind}    <input type=\"radio\" class=\"{prefix}-check\" id=\"{variant_id}\" name=\"{path}\" value=\"{idx}\"{described}{checked}>\n"
                 ));
                 html.push_str(&format!(
-                    "{ind}    <label for=\"{variant_id}\">{}</label>\n",
+                    "{ind}    <label class=\"{prefix}-check-label\" for=\"{variant_id}\">{}</label>\n",
                     escape_html(&variant_label)
                 ));
+                html.push_str(&variant_help(variant, &variant_id, prefix, indent + 2));
 
-                // Nested fields for this variant
+                // Nested fields for this variant, shown while it is selected
                 if !matches!(variant.kind, QuestionKind::Unit) {
+                    let hidden = if default_selected == Some(idx) {
+                        ""
+                    } else {
+                        " hidden"
+                    };
                     html.push_str(&format!(
-                        "{ind}    <div class=\"{prefix}-nested\" data-variant=\"{idx}\">\n"
+                        "{ind}    <fieldset class=\"{prefix}-nested\" data-variant=\"{idx}\"{hidden}>\n"
                     ));
                     html.push_str(&generate_variant_fields(
                         &variant.kind,
@@ -375,7 +723,7 @@ fn generate_question(
                         prefix,
                         indent + 3,
                     ));
-                    html.push_str(&format!("{ind}    </div>\n"));
+                    html.push_str(&format!("{ind}    </fieldset>\n"));
                 }
 
                 html.push_str(&format!("{ind}  </div>\n"));
@@ -427,18 +775,21 @@ fn generate_question(
                 html.push_str(&format!(
                     "{ind}  <div class=\"{prefix}-checkbox-option\">\n"
                 ));
+                let described = variant_described_by(variant, &variant_id);
                 html.push_str(&format!(
-                    "{ind}    <input type=\"checkbox\" id=\"{variant_id}\" name=\"{path}[]\" value=\"{idx}\"{checked}>\n"
+                    "{ind}    <input type=\"checkbox\" class=\"{prefix}-check\" id=\"{variant_id}\" name=\"{path}[]\" value=\"{idx}\"{described}{checked}>\n"
                 ));
                 html.push_str(&format!(
-                    "{ind}    <label for=\"{variant_id}\">{}</label>\n",
+                    "{ind}    <label class=\"{prefix}-check-label\" for=\"{variant_id}\">{}</label>\n",
                     escape_html(&variant_label)
                 ));
+                html.push_str(&variant_help(variant, &variant_id, prefix, indent + 2));
 
-                // Nested fields for this variant
+                // Nested fields for this variant, shown while it is checked
                 if !matches!(variant.kind, QuestionKind::Unit) {
+                    let hidden = if checked.is_empty() { " hidden" } else { "" };
                     html.push_str(&format!(
-                        "{ind}    <div class=\"{prefix}-nested\" data-variant=\"{idx}\">\n"
+                        "{ind}    <fieldset class=\"{prefix}-nested\" data-variant=\"{idx}\"{hidden}>\n"
                     ));
                     html.push_str(&generate_variant_fields(
                         &variant.kind,
@@ -446,7 +797,7 @@ fn generate_question(
                         prefix,
                         indent + 3,
                     ));
-                    html.push_str(&format!("{ind}    </div>\n"));
+                    html.push_str(&format!("{ind}    </fieldset>\n"));
                 }
 
                 html.push_str(&format!("{ind}  </div>\n"));
@@ -467,7 +818,7 @@ fn generate_question(
             for nested_q in all_of.questions() {
                 html.push_str(&generate_question(
                     nested_q,
-                    prefix,
+                    options,
                     indent + 1,
                     Some(&path),
                 ));
@@ -477,6 +828,17 @@ fn generate_question(
         }
     }
 
+    theme::add_kind_classes(&mut html, question.kind(), options);
+
+    if question.allows_comment() {
+        html.push_str(&format!("{ind}<details class=\"{prefix}-comment\">\n"));
+        html.push_str(&format!("{ind}  <summary>Add comment</summary>\n"));
+        html.push_str(&format!(
+            "{ind}  <textarea id=\"{field_id}-comment\" name=\"{path}.{COMMENT_KEY}\" rows=\"2\" class=\"{prefix}-textarea\"></textarea>\n"
+        ));
+        html.push_str(&format!("{ind}</details>\n"));
+    }
+
     html
 }
 
@@ -510,6 +872,38 @@ fn generate_variant_fields(
             }
             html.push_str(&format!("{ind}<input {attrs}>\n"));
         }
+        QuestionKind::Color(color_q) => {
+            let field_id = base_path.replace('.', "-");
+            let value = color_q.default.unwrap_or_default();
+            html.push_str(&format!(
+                "{ind}<input type=\"color\" id=\"{field_id}\" name=\"{base_path}\" class=\"{prefix}-color\" value=\"{value}\">\n"
+            ));
+        }
+        QuestionKind::Path(path_q) => {
+            let field_id = base_path.replace('.', "-");
+            html.push_str(&format!(
+                "{ind}<input {}>\n",
+                path_input_attrs(path_q, &field_id, base_path, prefix)
+            ));
+        }
+        QuestionKind::Rating(rating_q) => {
+            html.push_str(&generate_rating_scale(
+                rating_q,
+                base_path,
+                rating_q.default,
+                prefix,
+                indent,
+            ));
+        }
+        QuestionKind::Matrix(matrix_q) => {
+            html.push_str(&generate_matrix_table(
+                matrix_q,
+                base_path,
+                &[],
+                prefix,
+                indent,
+            ));
+        }
         QuestionKind::Float(float_q) => {
             let field_id = base_path.replace('.', "-");
             let mut attrs = format!(
@@ -531,7 +925,7 @@ fn generate_variant_fields(
 
                 html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
                 html.push_str(&format!(
-                    "{ind}  <label for=\"{field_id}\">{}</label>\n",
+                    "{ind}  <label class=\"{prefix}-label\" for=\"{field_id}\">{}</label>\n",
                     escape_html(&label)
                 ));
 
@@ -581,12 +975,178 @@ fn generate_variant_fields(
     html
 }
 
+/// Attributes of the input for a path question: a file picker limited to the
+/// accepted extensions, a directory picker, or plain text for any path.
+fn path_input_attrs(path_q: &PathQuestion, field_id: &str, name: &str, prefix: &str) -> String {
+    match &path_q.mode {
+        PathMode::Any => {
+            let value_attr = path_q
+                .default
+                .as_ref()
+                .map(|s| format!(" value=\"{}\"", escape_html(s)))
+                .unwrap_or_default();
+            format!(
+                "type=\"text\" id=\"{field_id}\" name=\"{name}\" class=\"{prefix}-input\"{value_attr}"
+            )
+        }
+        PathMode::File { extensions } => {
+            let accept_attr = if extensions.is_empty() {
+                String::new()
+            } else {
+                let accept = extensions
+                    .iter()
+                    .map(|ext| format!(".{}", escape_html(ext)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!(" accept=\"{accept}\"")
+            };
+            format!(
+                "type=\"file\" id=\"{field_id}\" name=\"{name}\" class=\"{prefix}-file\"{accept_attr}"
+            )
+        }
+        PathMode::Directory => format!(
+            "type=\"file\" id=\"{field_id}\" name=\"{name}\" class=\"{prefix}-file\" webkitdirectory"
+        ),
+    }
+}
+
+/// The `aria-describedby` attribute pointing a variant's input at its help, with a
+/// leading space.
+fn variant_described_by(variant: &Variant, variant_id: &str) -> String {
+    match variant.help {
+        Some(_) => format!(" aria-describedby=\"{variant_id}-help\""),
+        None => String::new(),
+    }
+}
+
+/// What choosing a variant means, below its label.
+fn variant_help(variant: &Variant, variant_id: &str, prefix: &str, indent: usize) -> String {
+    let Some(help) = &variant.help else {
+        return String::new();
+    };
+    format!(
+        "{}<small class=\"{prefix}-variant-help\" id=\"{variant_id}-help\">{}</small>\n",
+        "  ".repeat(indent),
+        escape_html(help)
+    )
+}
+
+/// A row of radio buttons, one per point on the scale, with the anchors at the ends.
+fn generate_rating_scale(
+    rating_q: &RatingQuestion,
+    path: &str,
+    default_selected: Option<i64>,
+    prefix: &str,
+    indent: usize,
+) -> String {
+    let ind = "  ".repeat(indent);
+    let field_id = path.replace('.', "-");
+    let mut html = format!("{ind}<div class=\"{prefix}-rating-scale\">\n");
+
+    if let Some((low, _)) = &rating_q.anchors {
+        html.push_str(&format!(
+            "{ind}  <span class=\"{prefix}-rating-anchor\">{}</span>\n",
+            escape_html(low)
+        ));
+    }
+    for point in rating_q.points() {
+        let checked = if default_selected == Some(point) {
+            " checked"
+        } else {
+            ""
+        };
+        html.push_str(&format!(
+            "{ind}  <label class=\"{prefix}-check-label\" for=\"{field_id}-{point}\" title=\"{}\"><input type=\"radio\" class=\"{prefix}-check\" id=\"{field_id}-{point}\" name=\"{path}\" value=\"{point}\"{checked}> {point}</label>\n",
+            escape_html(&rating_q.label(point))
+        ));
+    }
+    if let Some((_, high)) = &rating_q.anchors {
+        html.push_str(&format!(
+            "{ind}  <span class=\"{prefix}-rating-anchor\">{}</span>\n",
+            escape_html(high)
+        ));
+    }
+
+    html.push_str(&format!("{ind}</div>\n"));
+    html
+}
+
+/// Generate a table with one radio group per matrix row, named `path.Row`.
+fn generate_matrix_table(
+    matrix_q: &MatrixQuestion,
+    path: &str,
+    default_selected: &[usize],
+    prefix: &str,
+    indent: usize,
+) -> String {
+    let ind = "  ".repeat(indent);
+    let mut html = format!("{ind}<table class=\"{prefix}-matrix-table\">\n");
+
+    html.push_str(&format!("{ind}  <tr>\n{ind}    <th></th>\n"));
+    for column in &matrix_q.columns {
+        html.push_str(&format!("{ind}    <th>{}</th>\n", escape_html(column)));
+    }
+    html.push_str(&format!("{ind}  </tr>\n"));
+
+    for (row_idx, row) in matrix_q.rows.iter().enumerate() {
+        let name = format!("{path}.{row}");
+        let field_id = name.replace('.', "-");
+        html.push_str(&format!(
+            "{ind}  <tr>\n{ind}    <th scope=\"row\">{}</th>\n",
+            escape_html(row)
+        ));
+        for (column_idx, column) in matrix_q.columns.iter().enumerate() {
+            let checked = if default_selected.get(row_idx) == Some(&column_idx) {
+                " checked"
+            } else {
+                ""
+            };
+            html.push_str(&format!(
+                "{ind}    <td><input type=\"radio\" class=\"{prefix}-check\" id=\"{field_id}-{column_idx}\" name=\"{name}\" value=\"{}\" aria-label=\"{}: {}\"{checked}></td>\n",
+                escape_html(column),
+                escape_html(row),
+                escape_html(column)
+            ));
+        }
+        html.push_str(&format!("{ind}  </tr>\n"));
+    }
+
+    html.push_str(&format!("{ind}</table>\n"));
+    html
+}
+
+/// Script that shows the follow-up fields of the chosen variants and disables the others,
+/// so they are neither validated nor submitted.
+fn generate_toggle_script(prefix: &str) -> String {
+    format!(
+        r#"<script>
+  (function () {{
+    var form = document.currentScript.previousElementSibling;
+    while (form && form.tagName !== "FORM") {{
+      form = form.previousElementSibling;
+    }}
+    var sections = form.querySelectorAll(".{prefix}-nested");
+    function update() {{
+      Array.prototype.forEach.call(sections, function (section) {{
+        var toggle = section.parentElement.querySelector("input");
+        section.hidden = !toggle.checked;
+        section.disabled = !toggle.checked;
+      }});
+    }}
+    form.addEventListener("change", update);
+    update();
+  }})();
+</script>
+"#
+    )
+}
+
 /// Format a prompt as a label.
-fn format_label(ask: &str, path: &str) -> String {
+pub(crate) fn format_label(ask: &str, path: &str) -> String {
     if ask.is_empty() {
         // Create a readable label from the path
         path.split('.')
-            .last()
+            .next_back()
             .unwrap_or("")
             .split('_')
             .map(|word| {
@@ -604,7 +1164,7 @@ fn format_label(ask: &str, path: &str) -> String {
 }
 
 /// Escape HTML special characters.
-fn escape_html(s: &str) -> String {
+pub(crate) fn escape_html(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
         .replace('>', "&gt;")
@@ -613,7 +1173,15 @@ fn escape_html(s: &str) -> String {
 }
 
 /// Generate default CSS styles.
-fn generate_styles(prefix: &str) -> String {
+fn generate_styles(prefix: &str, scheme: ColorScheme) -> String {
+    let dark = match scheme {
+        ColorScheme::Light => String::new(),
+        ColorScheme::Dark => dark_styles(prefix, "    "),
+        ColorScheme::Auto => format!(
+            "    @media (prefers-color-scheme: dark) {{\n{}    }}\n",
+            dark_styles(prefix, "      ")
+        ),
+    };
     format!(
         r#"  <style>
     .{prefix}-form {{
@@ -635,11 +1203,15 @@ fn generate_styles(prefix: &str) -> String {
       display: block;
       margin-bottom: 0.25rem;
     }}
-    .{prefix}-input, .{prefix}-textarea {{
+    .{prefix}-input, .{prefix}-textarea, .{prefix}-select {{
       width: 100%;
       padding: 0.5rem;
       box-sizing: border-box;
     }}
+    .{prefix}-color {{
+      width: 4rem;
+      height: 2rem;
+    }}
     .{prefix}-checkbox {{
       display: flex;
       align-items: center;
@@ -655,20 +1227,64 @@ fn generate_styles(prefix: &str) -> String {
     .{prefix}-radio-option, .{prefix}-checkbox-option {{
       margin: 0.25rem 0;
     }}
-    .{prefix}-nested {{
+    .{prefix}-variant-help {{
+      display: block;
       margin-left: 1.5rem;
-      padding-left: 0.5rem;
+      font-size: 0.9rem;
+      color: #666;
+    }}
+    .{prefix}-rating-scale {{
+      display: flex;
+      align-items: center;
+      gap: 0.75rem;
+    }}
+    .{prefix}-rating-scale label {{
+      display: inline;
+    }}
+    .{prefix}-rating-anchor {{
+      font-style: italic;
+      color: #666;
+    }}
+    .{prefix}-matrix-table th, .{prefix}-matrix-table td {{
+      padding: 0.25rem 0.75rem;
+      text-align: center;
+    }}
+    .{prefix}-matrix-table th[scope="row"] {{
+      text-align: left;
+      font-weight: normal;
+    }}
+    .{prefix}-comment {{
+      margin: 0.25rem 0 0.75rem;
+      font-size: 0.9rem;
+    }}
+    .{prefix}-nested {{
+      margin: 0 0 0 1.5rem;
+      padding: 0 0 0 0.5rem;
+      border: 0;
       border-left: 2px solid #ccc;
     }}
     .{prefix}-submit {{
       margin-top: 1rem;
       padding: 0.5rem 1rem;
     }}
-  </style>
+{dark}  </style>
 "#
     )
 }
 
+/// Dark overrides of the default styles, indented by `ind`.
+fn dark_styles(prefix: &str, ind: &str) -> String {
+    [
+        format!("body, .{prefix}-form {{ background: #1e1e1e; color: #e6e6e6; }}"),
+        format!(".{prefix}-prelude, .{prefix}-epilogue {{ background: #2b2b2b; }}"),
+        format!(".{prefix}-rating-anchor, .{prefix}-variant-help {{ color: #aaa; }}"),
+        format!(".{prefix}-nested {{ border-left-color: #555; }}"),
+    ]
+    .iter()
+    .map(|rule| format!("{ind}{rule}\n"))
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -696,4 +1312,97 @@ mod tests {
         assert!(options.full_document);
         assert_eq!(options.class_prefix, "my-form");
     }
+
+    #[test]
+    fn variant_fields_are_shown_for_the_selected_variant() {
+        use elicitor::{AllOfQuestion, InputQuestion, OneOfQuestion, Variant};
+
+        let card = QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+            "number",
+            "Card number:",
+            QuestionKind::Input(InputQuestion::new()),
+        )]));
+        let paypal = QuestionKind::Input(InputQuestion::new());
+        let payment = |default| {
+            let variants = vec![
+                Variant::new("Card", card.clone()),
+                Variant::new("PayPal", paypal.clone()),
+                Variant::unit("Cash"),
+            ];
+            let one_of = match default {
+                Some(idx) => OneOfQuestion::with_default(variants, idx),
+                None => OneOfQuestion::new(variants),
+            };
+            SurveyDefinition::new(vec![Question::new(
+                "payment",
+                "Payment:",
+                QuestionKind::OneOf(one_of),
+            )])
+        };
+
+        let html = definition_to_html(&payment(None), &HtmlOptions::new());
+        assert!(html.contains("<fieldset class=\"survey-nested\" data-variant=\"0\" hidden>"));
+        assert!(html.contains("<fieldset class=\"survey-nested\" data-variant=\"1\" hidden>"));
+        assert!(!html.contains("data-variant=\"2\""));
+        assert!(html.contains("section.disabled = !toggle.checked;"));
+        assert!(html.contains("<noscript>"));
+
+        let html = definition_to_html(&payment(Some(1)), &HtmlOptions::new());
+        assert!(html.contains("<fieldset class=\"survey-nested\" data-variant=\"1\">"));
+
+        let plain = SurveyDefinition::new(vec![Question::new(
+            "name",
+            "Name:",
+            QuestionKind::Input(InputQuestion::new()),
+        )]);
+        let html = definition_to_html(&plain, &HtmlOptions::new());
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("<noscript>"));
+    }
+
+    #[test]
+    fn variants_are_described_below_their_labels() {
+        use elicitor::{AnyOfQuestion, OneOfQuestion, Variant};
+
+        let variants = vec![
+            Variant::unit("Card").with_help("Visa & Mastercard"),
+            Variant::unit("Cash"),
+        ];
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                "payment",
+                "Payment:",
+                QuestionKind::OneOf(OneOfQuestion::new(variants.clone())),
+            ),
+            Question::new(
+                "accepted",
+                "Accepted:",
+                QuestionKind::AnyOf(AnyOfQuestion::new(variants)),
+            ),
+        ]);
+
+        let html = definition_to_html(&definition, &HtmlOptions::new());
+        assert!(html.contains(
+            "id=\"payment-Card\" name=\"payment\" value=\"0\" aria-describedby=\"payment-Card-help\""
+        ));
+        assert!(html.contains(
+            "<small class=\"survey-variant-help\" id=\"payment-Card-help\">Visa &amp; Mastercard</small>"
+        ));
+        assert!(html.contains("id=\"accepted-0-help\">Visa &amp; Mastercard</small>"));
+        assert!(!html.contains("payment-Cash-help"));
+
+        let print = definition_to_html(&definition, &HtmlOptions::new().with_print_mode(true));
+        assert!(
+            print
+                .contains("Card</span> <span class=\"survey-hint\">(Visa &amp; Mastercard)</span>")
+        );
+    }
+
+    #[test]
+    fn dark_styles_follow_the_color_scheme() {
+        assert!(!generate_styles("survey", ColorScheme::Light).contains("#1e1e1e"));
+        assert!(generate_styles("survey", ColorScheme::Dark).contains("#1e1e1e"));
+        let auto = generate_styles("survey", ColorScheme::Auto);
+        assert!(auto.contains("@media (prefers-color-scheme: dark) {"));
+    }
 }
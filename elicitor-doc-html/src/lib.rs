@@ -6,6 +6,19 @@
 //! It does NOT collect responses — use it to generate static HTML forms
 //! that can be served, printed, or processed by other tools.
 //!
+//! With [`HtmlOptions::with_client_validation`] the form checks number bounds, required
+//! selections and [`FieldRules`] in the browser before it is submitted.
+//!
+//! [`HtmlOptions::with_framework`] adds Bootstrap or Tailwind classes so the form fits into
+//! an existing site, together with [`HtmlOptions::with_classes`], custom CSS and a
+//! [`ColorScheme`].
+//!
+//! [`HtmlOptions::with_print_mode`] generates a paper form instead, with ruled lines,
+//! boxes and circles to fill in by hand.
+//!
+//! Submissions of a generated form are parsed back with [`from_form_data`] or
+//! [`from_urlencoded`].
+//!
 //! ## Usage
 //!
 //! ```rust,ignore
@@ -27,8 +40,22 @@
 //!     let html = to_html::<UserProfile>(Some("User Profile"));
 //!     std::fs::write("form.html", html).unwrap();
 //! }
+//!
+//! // In the handler the form posts to
+//! fn submit(body: &str) -> Result<UserProfile, elicitor_doc_html::FormDataError> {
+//!     elicitor_doc_html::from_urlencoded(body)
+//! }
 //! ```
 
+mod form_data;
 mod generator;
+mod print;
+mod theme;
+mod validation;
 
-pub use generator::{HtmlOptions, to_html, to_html_with_options};
+pub use form_data::{
+    FormDataError, form_data_to_responses, from_form_data, from_urlencoded, parse_urlencoded,
+};
+pub use generator::{HtmlOptions, definition_to_html, to_html, to_html_with_options};
+pub use theme::{ColorScheme, CssFramework};
+pub use validation::FieldRules;
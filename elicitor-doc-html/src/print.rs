@@ -0,0 +1,495 @@
+//! Paper forms to print and fill in by hand.
+//!
+//! With [`HtmlOptions::with_print_mode`] the form has no inputs: text fields become ruled
+//! lines sized by the expected answer, checkboxes empty squares, and every choice is
+//! listed with a circle to tick. The fields of enum variants are always shown, headed by
+//! the variant they belong to.
+
+use elicitor::{DefaultValue, ListElementKind, Question, QuestionKind, SurveyDefinition, Variant};
+
+use crate::HtmlOptions;
+use crate::generator::{escape_html, format_label};
+use crate::theme;
+
+/// Number of ruled lines for a multiline answer.
+const MULTILINE_LINES: usize = 4;
+
+/// Number of ruled lines for a list, raised to its minimum and lowered to its maximum
+/// length.
+const LIST_LINES: usize = 3;
+
+/// Width of a number line without both bounds, in characters.
+const NUMBER_WIDTH: usize = 12;
+
+/// Generate the questions of a paper form.
+pub(crate) fn generate_questions(definition: &SurveyDefinition, options: &HtmlOptions) -> String {
+    let mut html = String::new();
+    for question in definition.questions() {
+        html.push_str(&generate_question(question, options, 2));
+    }
+    html
+}
+
+fn generate_question(question: &Question, options: &HtmlOptions, indent: usize) -> String {
+    let mut html = String::new();
+    // Assumed fields are not part of the form
+    if matches!(question.default(), DefaultValue::Assumed(_)) {
+        return html;
+    }
+
+    let prefix = options.class_prefix.as_str();
+    let ind = "  ".repeat(indent);
+    let label = escape_html(&format_label(question.ask(), question.path().as_str()));
+
+    match question.kind() {
+        QuestionKind::Unit => {}
+
+        // The enum inside its wrapper has no label of its own
+        kind if label.is_empty() => html.push_str(&generate_answer(kind, options, indent)),
+
+        QuestionKind::Confirm(_) => {
+            html.push_str(&format!(
+                "{ind}<div class=\"{prefix}-field {prefix}-checkbox\">\n"
+            ));
+            html.push_str(&format!(
+                "{ind}  <span class=\"{prefix}-box\"></span> <span class=\"{prefix}-check-label\">{label}</span>\n"
+            ));
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+
+        QuestionKind::AllOf(all_of) => {
+            html.push_str(&format!(
+                "{ind}<fieldset class=\"{prefix}-fieldset {prefix}-group\">\n"
+            ));
+            html.push_str(&format!("{ind}  <legend>{label}</legend>\n"));
+            for nested in all_of.questions() {
+                html.push_str(&generate_question(nested, options, indent + 1));
+            }
+            html.push_str(&format!("{ind}</fieldset>\n"));
+        }
+
+        kind @ (QuestionKind::OneOf(_)
+        | QuestionKind::AnyOf(_)
+        | QuestionKind::Rating(_)
+        | QuestionKind::Matrix(_)) => {
+            html.push_str(&format!("{ind}<fieldset class=\"{prefix}-fieldset\">\n"));
+            html.push_str(&format!("{ind}  <legend>{label}</legend>\n"));
+            html.push_str(&generate_answer(kind, options, indent + 1));
+            html.push_str(&format!("{ind}</fieldset>\n"));
+        }
+
+        kind => {
+            html.push_str(&format!("{ind}<div class=\"{prefix}-field\">\n"));
+            html.push_str(&format!(
+                "{ind}  <div class=\"{prefix}-label\">{label}</div>\n"
+            ));
+            html.push_str(&generate_answer(kind, options, indent + 1));
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+    }
+
+    theme::add_kind_classes(&mut html, question.kind(), options);
+
+    if question.allows_comment() {
+        html.push_str(&format!(
+            "{ind}<div class=\"{prefix}-comment\">Comment: {}</div>\n",
+            line(prefix, None)
+        ));
+    }
+
+    html
+}
+
+/// The space for the answer to a question of `kind`, without its label.
+fn generate_answer(kind: &QuestionKind, options: &HtmlOptions, indent: usize) -> String {
+    let prefix = options.class_prefix.as_str();
+    let ind = "  ".repeat(indent);
+    let mut html = String::new();
+
+    match kind {
+        QuestionKind::Unit => {}
+
+        QuestionKind::Input(_) => html.push_str(&format!("{ind}{}\n", line(prefix, None))),
+
+        QuestionKind::Autocomplete(autocomplete_q) => {
+            let suggestions: Vec<String> =
+                autocomplete_q.completions("").into_iter().take(5).collect();
+            html.push_str(&format!("{ind}{}\n", line(prefix, None)));
+            if !suggestions.is_empty() {
+                html.push_str(&format!(
+                    "{ind}{}\n",
+                    hint(prefix, &format!("e.g. {}", suggestions.join(", ")))
+                ));
+            }
+        }
+
+        QuestionKind::Multiline(_) => {
+            for _ in 0..MULTILINE_LINES {
+                html.push_str(&format!("{ind}{}\n", line(prefix, None)));
+            }
+        }
+
+        QuestionKind::Masked(_) => html.push_str(&format!("{ind}{}\n", line(prefix, Some(24)))),
+
+        QuestionKind::Int(int_q) => {
+            let min = int_q.min.map(|min| int_q.format(min));
+            let max = int_q.max.map(|max| int_q.format(max));
+            let width = number_width(min.as_ref(), max.as_ref());
+            html.push_str(&format!("{ind}{}", line(prefix, Some(width))));
+            html.push_str(&bounds_hint(prefix, "whole number", min, max));
+            html.push('\n');
+        }
+
+        QuestionKind::Float(float_q) => {
+            let min = float_q.min.map(|min| float_q.format(min));
+            let max = float_q.max.map(|max| float_q.format(max));
+            let width = number_width(min.as_ref(), max.as_ref());
+            html.push_str(&format!("{ind}{}", line(prefix, Some(width))));
+            html.push_str(&bounds_hint(prefix, "decimal", min, max));
+            html.push('\n');
+        }
+
+        QuestionKind::Currency(currency_q) => {
+            let min = currency_q.min.map(|minor| currency_q.format(minor));
+            let max = currency_q.max.map(|minor| currency_q.format(minor));
+            let width = number_width(min.as_ref(), max.as_ref());
+            html.push_str(&format!(
+                "{ind}{} {}",
+                escape_html(currency_q.symbol()),
+                line(prefix, Some(width))
+            ));
+            html.push_str(&bounds_hint(prefix, "amount", min, max));
+            html.push('\n');
+        }
+
+        QuestionKind::Color(_) => html.push_str(&format!(
+            "{ind}# {} {}\n",
+            line(prefix, Some(8)),
+            hint(prefix, "hex, e.g. 1e90ff")
+        )),
+
+        QuestionKind::Path(path_q) => {
+            html.push_str(&format!("{ind}{}\n", line(prefix, None)));
+            if let Some(path_hint) = path_q.hint() {
+                html.push_str(&format!("{ind}{}\n", hint(prefix, &path_hint)));
+            }
+        }
+
+        QuestionKind::Confirm(_) => {
+            html.push_str(&format!(
+                "{ind}<span class=\"{prefix}-option\"><span class=\"{prefix}-box\"></span> Yes</span>\n"
+            ));
+        }
+
+        QuestionKind::Select(select_q) => {
+            for option in &select_q.options {
+                html.push_str(&format!(
+                    "{ind}{}\n",
+                    choice(prefix, "circle", &option.label)
+                ));
+            }
+        }
+
+        QuestionKind::List(list_q) => {
+            let element = match &list_q.element_kind {
+                ListElementKind::String => "text".to_string(),
+                ListElementKind::Int { min, max } => bounds_text("whole numbers", *min, *max),
+                ListElementKind::Float { min, max } => bounds_text("decimals", *min, *max),
+            };
+            html.push_str(&format!("{ind}{}\n", hint(prefix, &element)));
+            let count = LIST_LINES
+                .max(list_q.min_items.unwrap_or(0))
+                .min(list_q.max_items.unwrap_or(usize::MAX))
+                .max(1);
+            for number in 1..=count {
+                html.push_str(&format!(
+                    "{ind}<div class=\"{prefix}-list-item\">{number}. {}</div>\n",
+                    line(prefix, None)
+                ));
+            }
+        }
+
+        QuestionKind::Rating(rating_q) => {
+            html.push_str(&format!("{ind}<div class=\"{prefix}-rating-scale\">\n"));
+            if let Some((low, _)) = &rating_q.anchors {
+                html.push_str(&format!(
+                    "{ind}  <span class=\"{prefix}-rating-anchor\">{}</span>\n",
+                    escape_html(low)
+                ));
+            }
+            for point in rating_q.points() {
+                html.push_str(&format!(
+                    "{ind}  {}\n",
+                    choice(prefix, "circle", &point.to_string())
+                ));
+            }
+            if let Some((_, high)) = &rating_q.anchors {
+                html.push_str(&format!(
+                    "{ind}  <span class=\"{prefix}-rating-anchor\">{}</span>\n",
+                    escape_html(high)
+                ));
+            }
+            html.push_str(&format!("{ind}</div>\n"));
+        }
+
+        QuestionKind::Matrix(matrix_q) => {
+            html.push_str(&format!("{ind}<table class=\"{prefix}-matrix-table\">\n"));
+            html.push_str(&format!("{ind}  <tr>\n{ind}    <th></th>\n"));
+            for column in &matrix_q.columns {
+                html.push_str(&format!("{ind}    <th>{}</th>\n", escape_html(column)));
+            }
+            html.push_str(&format!("{ind}  </tr>\n"));
+            for row in &matrix_q.rows {
+                html.push_str(&format!(
+                    "{ind}  <tr>\n{ind}    <th scope=\"row\">{}</th>\n",
+                    escape_html(row)
+                ));
+                for _ in &matrix_q.columns {
+                    html.push_str(&format!(
+                        "{ind}    <td><span class=\"{prefix}-circle\"></span></td>\n"
+                    ));
+                }
+                html.push_str(&format!("{ind}  </tr>\n"));
+            }
+            html.push_str(&format!("{ind}</table>\n"));
+        }
+
+        QuestionKind::OneOf(one_of) => {
+            for variant in &one_of.variants {
+                html.push_str(&format!(
+                    "{ind}{}{}\n",
+                    choice(prefix, "circle", &format_label("", &variant.name)),
+                    variant_hint(prefix, variant)
+                ));
+            }
+            html.push_str(&generate_variants(&one_of.variants, options, indent));
+        }
+
+        QuestionKind::AnyOf(any_of) => {
+            for variant in &any_of.variants {
+                html.push_str(&format!(
+                    "{ind}{}{}\n",
+                    choice(prefix, "box", &format_label("", &variant.name)),
+                    variant_hint(prefix, variant)
+                ));
+            }
+            html.push_str(&generate_variants(&any_of.variants, options, indent));
+        }
+
+        QuestionKind::AllOf(all_of) => {
+            for nested in all_of.questions() {
+                html.push_str(&generate_question(nested, options, indent));
+            }
+        }
+    }
+
+    html
+}
+
+/// The fields of every variant that has any, headed by the variant's name.
+fn generate_variants(variants: &[Variant], options: &HtmlOptions, indent: usize) -> String {
+    let prefix = options.class_prefix.as_str();
+    let ind = "  ".repeat(indent);
+    let mut html = String::new();
+    for variant in variants {
+        if matches!(variant.kind, QuestionKind::Unit) {
+            continue;
+        }
+        html.push_str(&format!("{ind}<div class=\"{prefix}-variant\">\n"));
+        html.push_str(&format!(
+            "{ind}  <div class=\"{prefix}-variant-label\">If {}:</div>\n",
+            escape_html(&format_label("", &variant.name))
+        ));
+        html.push_str(&generate_answer(&variant.kind, options, indent + 1));
+        html.push_str(&format!("{ind}</div>\n"));
+    }
+    html
+}
+
+/// A ruled line to write on, `width` characters wide or as wide as the form.
+fn line(prefix: &str, width: Option<usize>) -> String {
+    match width {
+        Some(width) => {
+            format!("<span class=\"{prefix}-line\" style=\"width: {width}ch\"></span>")
+        }
+        None => format!("<span class=\"{prefix}-line\"></span>"),
+    }
+}
+
+/// A choice with a `circle` or `box` in front of it to tick.
+fn choice(prefix: &str, mark: &str, label: &str) -> String {
+    format!(
+        "<span class=\"{prefix}-option\"><span class=\"{prefix}-{mark}\"></span> {}</span>",
+        escape_html(label)
+    )
+}
+
+/// What choosing a variant means, with a leading space, if it says.
+fn variant_hint(prefix: &str, variant: &Variant) -> String {
+    variant
+        .help
+        .as_ref()
+        .map(|help| format!(" {}", hint(prefix, help)))
+        .unwrap_or_default()
+}
+
+fn hint(prefix: &str, text: &str) -> String {
+    format!(
+        "<span class=\"{prefix}-hint\">({})</span>",
+        escape_html(text)
+    )
+}
+
+/// A hint naming the expected value and its bounds, with a leading space.
+fn bounds_hint<N: std::fmt::Display>(
+    prefix: &str,
+    what: &str,
+    min: Option<N>,
+    max: Option<N>,
+) -> String {
+    format!(" {}", hint(prefix, &bounds_text(what, min, max)))
+}
+
+fn bounds_text<N: std::fmt::Display>(what: &str, min: Option<N>, max: Option<N>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("{what}, {min} to {max}"),
+        (Some(min), None) => format!("{what}, at least {min}"),
+        (None, Some(max)) => format!("{what}, at most {max}"),
+        (None, None) => what.to_string(),
+    }
+}
+
+/// Width of a line for a number: as long as the longer bound, with some room to spare.
+fn number_width<N: std::fmt::Display>(min: Option<N>, max: Option<N>) -> usize {
+    match (min, max) {
+        (Some(min), Some(max)) => {
+            let len = min.to_string().chars().count();
+            len.max(max.to_string().chars().count()) + 2
+        }
+        _ => NUMBER_WIDTH,
+    }
+}
+
+/// Styles of the paper form, added to the default styles.
+pub(crate) fn generate_styles(prefix: &str) -> String {
+    format!(
+        r#"  <style>
+    .{prefix}-line {{
+      display: inline-block;
+      width: 100%;
+      height: 1.8em;
+      border-bottom: 1px solid #333;
+      vertical-align: bottom;
+    }}
+    .{prefix}-box, .{prefix}-circle {{
+      display: inline-block;
+      width: 0.9em;
+      height: 0.9em;
+      border: 1px solid #333;
+      vertical-align: middle;
+    }}
+    .{prefix}-circle {{
+      border-radius: 50%;
+    }}
+    .{prefix}-option {{
+      display: inline-block;
+      margin: 0.25rem 1.5rem 0.25rem 0;
+    }}
+    .{prefix}-hint {{
+      font-size: 0.85em;
+      font-style: italic;
+      color: #666;
+    }}
+    .{prefix}-list-item {{
+      display: flex;
+      align-items: flex-end;
+      gap: 0.5rem;
+    }}
+    .{prefix}-variant {{
+      margin: 0.5rem 0 0.5rem 1.5rem;
+      padding-left: 0.5rem;
+      border-left: 2px solid #ccc;
+    }}
+    .{prefix}-variant-label {{
+      font-style: italic;
+    }}
+    @media print {{
+      .{prefix}-form {{
+        max-width: none;
+        margin: 0;
+      }}
+      .{prefix}-field, .{prefix}-fieldset {{
+        break-inside: avoid;
+      }}
+    }}
+  </style>
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{
+        AnyOfQuestion, ConfirmQuestion, InputQuestion, IntQuestion, MultilineQuestion,
+        OneOfQuestion, SelectOption, SelectQuestion,
+    };
+
+    #[test]
+    fn widgets_become_lines_boxes_and_circles() {
+        let definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "age",
+                "Age:",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new(
+                "bio",
+                "Bio:",
+                QuestionKind::Multiline(MultilineQuestion::new()),
+            ),
+            Question::new("ok", "OK?", QuestionKind::Confirm(ConfirmQuestion::new())),
+            Question::new(
+                "size",
+                "Size:",
+                QuestionKind::Select(SelectQuestion::new(vec![
+                    SelectOption::new("s", "Small"),
+                    SelectOption::new("l", "Large"),
+                ])),
+            ),
+            Question::new(
+                "pets",
+                "Pets:",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![Variant::unit("cat")])),
+            ),
+            Question::new(
+                "contact",
+                "Contact:",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("none"),
+                    Variant::new("phone", QuestionKind::Input(InputQuestion::new())),
+                ])),
+            ),
+        ]);
+        let html = generate_questions(&definition, &HtmlOptions::new());
+
+        assert!(!html.contains("<input"));
+        assert!(!html.contains("<select"));
+        assert!(!html.contains("<textarea"));
+        assert!(html.contains(
+            "<span class=\"survey-line\" style=\"width: 5ch\"></span> <span class=\"survey-hint\">(whole number, 0 to 150)</span>"
+        ));
+        assert_eq!(
+            html.matches("<span class=\"survey-line\"></span>").count(),
+            6
+        );
+        assert!(html.contains(
+            "<span class=\"survey-box\"></span> <span class=\"survey-check-label\">OK?</span>"
+        ));
+        assert!(html.contains("<span class=\"survey-circle\"></span> Small</span>"));
+        assert!(html.contains("<span class=\"survey-box\"></span> Cat</span>"));
+        assert!(html.contains("<div class=\"survey-variant-label\">If Phone:</div>"));
+        assert!(!html.contains("If None:"));
+    }
+}
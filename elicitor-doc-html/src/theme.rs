@@ -0,0 +1,290 @@
+//! CSS framework presets, extra classes and color schemes for generated forms.
+//!
+//! Every generated element carries a `{prefix}-{element}` class, e.g. `survey-input`.
+//! A [`CssFramework`] preset and [`HtmlOptions::with_classes`] add classes next to it,
+//! so the form picks up the look of the site it is embedded in.
+//!
+//! The elements are `form`, `title`, `prelude`, `epilogue`, `field` and `fieldset` (the
+//! wrapper of a question), `label`, `input`, `textarea`, `select`, `color`, `file`, `check`
+//! and `check-label` (checkboxes and radio buttons), `checkbox`, `radio-option` and
+//! `checkbox-option` (their wrappers), `variant-help` (what a choice means), `nested`,
+//! `rating-scale`, `rating-anchor`, `matrix-table`, `comment` and `submit`. Print mode
+//! adds `line`, `box`, `circle`, `option`, `hint`, `list-item`, `variant` and
+//! `variant-label`.
+
+use elicitor::QuestionKind;
+
+use crate::HtmlOptions;
+
+/// CSS framework whose classes are added to the generated elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CssFramework {
+    /// Only the prefixed classes.
+    #[default]
+    None,
+    /// Bootstrap 5 form classes, e.g. `form-control` and `btn btn-primary`.
+    Bootstrap,
+    /// Tailwind CSS utility classes, including `dark:` variants.
+    Tailwind,
+}
+
+impl CssFramework {
+    /// Classes added to elements with the class `{prefix}-{element}`.
+    pub fn classes(self, element: &str) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Bootstrap => match element {
+                "title" => "h3 mb-3",
+                "prelude" | "epilogue" => "alert alert-light",
+                "field" | "fieldset" => "mb-3",
+                "label" => "form-label",
+                "input" | "textarea" | "file" => "form-control",
+                "select" => "form-select",
+                "color" => "form-control form-control-color",
+                "check" => "form-check-input",
+                "check-label" => "form-check-label",
+                "checkbox" | "radio-option" | "checkbox-option" => "form-check",
+                "variant-help" => "form-text d-block",
+                "nested" => "ms-4",
+                "rating-scale" => "d-flex align-items-center gap-3",
+                "rating-anchor" => "fst-italic text-body-secondary",
+                "matrix-table" => "table table-sm",
+                "comment" => "mb-3 small",
+                "submit" => "btn btn-primary",
+                _ => "",
+            },
+            Self::Tailwind => match element {
+                "form" => "mx-auto max-w-xl p-4 text-gray-900 dark:text-gray-100",
+                "title" => "mb-4 text-2xl font-bold",
+                "prelude" | "epilogue" => {
+                    "mb-4 whitespace-pre-wrap rounded-md bg-gray-100 p-3 dark:bg-gray-800"
+                }
+                "field" => "mb-4",
+                "fieldset" => "mb-4 rounded-md border border-gray-300 p-4 dark:border-gray-600",
+                "label" => "mb-1 block text-sm font-medium",
+                "input" | "textarea" | "select" | "file" => {
+                    "block w-full rounded-md border border-gray-300 bg-white px-3 py-2 dark:border-gray-600 dark:bg-gray-800"
+                }
+                "color" => "h-10 w-16 rounded-md",
+                "check" => "h-4 w-4 rounded border-gray-300",
+                "check-label" => "text-sm",
+                "checkbox" | "radio-option" | "checkbox-option" => "mb-2 flex items-center gap-2",
+                "variant-help" => "block ml-6 text-xs text-gray-500 dark:text-gray-400",
+                "nested" => "ml-6 border-l-2 border-gray-300 pl-2 dark:border-gray-600",
+                "rating-scale" => "flex items-center gap-3",
+                "rating-anchor" => "italic text-gray-500 dark:text-gray-400",
+                "comment" => "mb-3 text-sm",
+                "submit" => {
+                    "rounded-md bg-blue-600 px-4 py-2 font-semibold text-white hover:bg-blue-500"
+                }
+                _ => "",
+            },
+        }
+    }
+}
+
+/// Colors of the generated form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorScheme {
+    /// Light colors only.
+    #[default]
+    Light,
+    /// Dark colors only.
+    Dark,
+    /// Follow the browser's `prefers-color-scheme`.
+    Auto,
+}
+
+impl ColorScheme {
+    /// Value of the `color-scheme` meta tag.
+    pub(crate) fn meta_content(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+            Self::Auto => "light dark",
+        }
+    }
+}
+
+/// Name of a question kind as used by [`HtmlOptions::with_kind_classes`].
+pub(crate) fn kind_name(kind: &QuestionKind) -> &'static str {
+    match kind {
+        QuestionKind::Unit => "unit",
+        QuestionKind::Input(_) => "input",
+        QuestionKind::Autocomplete(_) => "autocomplete",
+        QuestionKind::Multiline(_) => "multiline",
+        QuestionKind::Masked(_) => "masked",
+        QuestionKind::Int(_) => "int",
+        QuestionKind::Float(_) => "float",
+        QuestionKind::Currency(_) => "currency",
+        QuestionKind::Color(_) => "color",
+        QuestionKind::Path(_) => "path",
+        QuestionKind::Rating(_) => "rating",
+        QuestionKind::Matrix(_) => "matrix",
+        QuestionKind::Confirm(_) => "confirm",
+        QuestionKind::Select(_) => "select",
+        QuestionKind::List(_) => "list",
+        QuestionKind::AnyOf(_) => "any_of",
+        QuestionKind::AllOf(_) => "all_of",
+        QuestionKind::OneOf(_) => "one_of",
+    }
+}
+
+/// Add the classes configured for `kind` to the wrapper of a question, the first element
+/// of its `html`.
+pub(crate) fn add_kind_classes(html: &mut String, kind: &QuestionKind, options: &HtmlOptions) {
+    let Some(classes) = options.kind_classes.get(kind_name(kind)) else {
+        return;
+    };
+    let Some(start) = html.find("class=\"") else {
+        return;
+    };
+    let value_start = start + "class=\"".len();
+    if let Some(len) = html[value_start..].find('"') {
+        html.insert_str(value_start + len, &format!(" {classes}"));
+    }
+}
+
+/// Add framework and custom classes next to the prefixed classes of a generated form.
+pub(crate) fn apply_classes(form: &str, options: &HtmlOptions) -> String {
+    let dark_tailwind =
+        options.framework == CssFramework::Tailwind && options.color_scheme == ColorScheme::Dark;
+    if options.framework == CssFramework::None && options.classes.is_empty() {
+        return form.to_string();
+    }
+
+    let element_prefix = format!("{}-", options.class_prefix);
+    let mut output = String::with_capacity(form.len());
+    let mut rest = form;
+    while let Some(start) = rest.find("class=\"") {
+        let value_start = start + "class=\"".len();
+        let Some(len) = rest[value_start..].find('"') else {
+            break;
+        };
+        output.push_str(&rest[..value_start]);
+
+        let value = &rest[value_start..value_start + len];
+        let mut classes: Vec<&str> = value.split_whitespace().collect();
+        for token in value.split_whitespace() {
+            let Some(element) = token.strip_prefix(&element_prefix) else {
+                continue;
+            };
+            let mut extra: Vec<&str> = options.framework.classes(element).split(' ').collect();
+            if let Some(custom) = options.classes.get(element) {
+                extra.extend(custom.split_whitespace());
+            }
+            if element == "form" && dark_tailwind {
+                extra.push("dark");
+            }
+            for class in extra {
+                if !class.is_empty() && !classes.contains(&class) {
+                    classes.push(class);
+                }
+            }
+        }
+        output.push_str(&classes.join(" "));
+        rest = &rest[value_start + len..];
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Attributes of the `<form>` element that select the color scheme.
+pub(crate) fn form_attributes(options: &HtmlOptions) -> &'static str {
+    match (options.framework, options.color_scheme) {
+        (CssFramework::Bootstrap, ColorScheme::Dark) => " data-bs-theme=\"dark\"",
+        _ => "",
+    }
+}
+
+/// Script following the form that keeps Bootstrap's theme in line with the browser.
+pub(crate) fn generate_script(options: &HtmlOptions) -> String {
+    if options.framework != CssFramework::Bootstrap || options.color_scheme != ColorScheme::Auto {
+        return String::new();
+    }
+    r#"<script>
+  (function () {
+    var form = document.currentScript.previousElementSibling;
+    while (form && form.tagName !== "FORM") {
+      form = form.previousElementSibling;
+    }
+    var dark = window.matchMedia("(prefers-color-scheme: dark)");
+    function apply() {
+      form.setAttribute("data-bs-theme", dark.matches ? "dark" : "light");
+    }
+    apply();
+    dark.addEventListener("change", apply);
+  })();
+</script>
+"#
+    .to_string()
+}
+
+/// Stylesheet links and the custom `<style>` block.
+pub(crate) fn generate_links(options: &HtmlOptions, indent: &str) -> String {
+    let mut html = String::new();
+    for href in &options.stylesheets {
+        html.push_str(&format!(
+            "{indent}<link rel=\"stylesheet\" href=\"{}\">\n",
+            crate::generator::escape_html(href)
+        ));
+    }
+    if let Some(css) = &options.custom_css {
+        html.push_str(&format!("{indent}<style>\n"));
+        for line in css.lines() {
+            html.push_str(&format!("{indent}  {line}\n"));
+        }
+        html.push_str(&format!("{indent}</style>\n"));
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn framework_classes_follow_the_prefixed_ones() {
+        let options = HtmlOptions::new()
+            .with_framework(CssFramework::Bootstrap)
+            .with_classes("input", "rounded-0");
+        let form = apply_classes(
+            "<input class=\"survey-input\"><button class=\"survey-submit\">",
+            &options,
+        );
+        assert_eq!(
+            form,
+            "<input class=\"survey-input form-control rounded-0\">\
+             <button class=\"survey-submit btn btn-primary\">"
+        );
+    }
+
+    #[test]
+    fn kind_classes_go_on_the_wrapper() {
+        let options = HtmlOptions::new().with_kind_classes("int", "col-6");
+        let mut html =
+            "<div class=\"survey-field\">\n  <input class=\"survey-input\">\n".to_string();
+        add_kind_classes(
+            &mut html,
+            &QuestionKind::Int(elicitor::IntQuestion::new()),
+            &options,
+        );
+        assert!(html.starts_with("<div class=\"survey-field col-6\">"));
+        assert!(html.contains("<input class=\"survey-input\">"));
+    }
+
+    #[test]
+    fn dark_mode_marks_the_form() {
+        let options = HtmlOptions::new()
+            .with_framework(CssFramework::Tailwind)
+            .with_color_scheme(ColorScheme::Dark);
+        assert!(apply_classes("<form class=\"survey-form\">", &options).contains(" dark\""));
+
+        let options = HtmlOptions::new()
+            .with_framework(CssFramework::Bootstrap)
+            .with_color_scheme(ColorScheme::Dark);
+        assert_eq!(form_attributes(&options), " data-bs-theme=\"dark\"");
+        assert!(generate_script(&options).is_empty());
+        assert!(!generate_script(&options.with_color_scheme(ColorScheme::Auto)).is_empty());
+    }
+}
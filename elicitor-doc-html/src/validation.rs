@@ -0,0 +1,479 @@
+//! Client-side validation script for generated forms.
+//!
+//! Validators given with `#[validate]` are Rust functions and only run once the form is
+//! submitted. The script checks what can be checked in the browser beforehand: bounds of
+//! number fields, a selection for every choice and rating, and the [`FieldRules`] given in
+//! [`HtmlOptions`], with the messages the interactive backends show. Number bounds take
+//! them from their question, the other checks from [`HtmlOptions::messages`].
+
+use elicitor::{DefaultValue, Messages, Question, QuestionKind, SurveyDefinition};
+
+use crate::HtmlOptions;
+
+/// Extra checks for one field, run in the browser before the form is submitted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldRules {
+    /// Minimum number of characters of a non-empty answer.
+    pub min_len: Option<usize>,
+    /// Maximum number of characters.
+    pub max_len: Option<usize>,
+    /// Regular expression (JavaScript syntax) the whole answer must match.
+    pub pattern: Option<String>,
+    /// Message shown when the pattern does not match.
+    pub pattern_message: Option<String>,
+    /// Body of a JavaScript function of `value` and `form` that returns an error message,
+    /// or nothing if the answer is fine.
+    pub script: Option<String>,
+}
+
+impl FieldRules {
+    /// No extra checks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit the number of characters.
+    pub fn with_length(mut self, min: Option<usize>, max: Option<usize>) -> Self {
+        self.min_len = min;
+        self.max_len = max;
+        self
+    }
+
+    /// Require the answer to match `pattern`, showing `message` otherwise.
+    pub fn with_pattern(mut self, pattern: impl Into<String>, message: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self.pattern_message = Some(message.into());
+        self
+    }
+
+    /// Run a custom check, e.g. `if (!value.includes("@")) return "Not an email address";`.
+    pub fn with_script(mut self, script: impl Into<String>) -> Self {
+        self.script = Some(script.into());
+        self
+    }
+}
+
+/// Checks of one form field, by field name.
+#[derive(Default)]
+struct FieldCheck {
+    /// The lower bound and the message when it's not met.
+    min: Option<(String, String)>,
+    /// The upper bound and the message when it's exceeded.
+    max: Option<(String, String)>,
+    /// The message for answers that aren't whole numbers, if only those are allowed.
+    integer: Option<String>,
+    rules: FieldRules,
+}
+
+#[derive(Default)]
+struct Checks {
+    fields: Vec<(String, FieldCheck)>,
+    required: Vec<String>,
+}
+
+/// Generate the `<script>` validating the form right before it.
+pub(crate) fn generate_script(definition: &SurveyDefinition, options: &HtmlOptions) -> String {
+    let mut checks = Checks::default();
+    for question in definition.questions() {
+        collect_question(question, None, None, options, &mut checks);
+    }
+
+    let rules = checks
+        .fields
+        .iter()
+        .map(|(name, check)| {
+            format!(
+                "      {}: {}",
+                js_string(name),
+                rule_object(check, &options.messages)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+    let scripts = checks
+        .fields
+        .iter()
+        .filter_map(|(name, check)| {
+            let script = check.rules.script.as_ref()?;
+            Some(format!(
+                "      {}: function (value, form) {{ {} }}",
+                js_string(name),
+                script.replace("</", "<\\/")
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+    let required = checks
+        .required
+        .iter()
+        .map(|name| js_string(name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let select_message = js_string(&options.messages.select_option());
+    let prefix = &options.class_prefix;
+
+    format!(
+        r#"<script>
+  (function () {{
+    var form = document.currentScript.previousElementSibling;
+    var rules = {{
+{rules}
+    }};
+    var scripts = {{
+{scripts}
+    }};
+    var required = [{required}];
+    var selectMessage = {select_message};
+
+    // Fields of variants that are not chosen are not submitted
+    function active(el) {{
+      var nested = el.parentElement.closest(".{prefix}-nested");
+      if (!nested) return true;
+      var toggle = nested.parentElement.querySelector("input");
+      return toggle.checked && active(toggle);
+    }}
+
+    function fields(name) {{
+      return Array.prototype.filter.call(form.elements, function (el) {{
+        return el.name === name && active(el);
+      }});
+    }}
+
+    function check(name, el) {{
+      var rule = rules[name];
+      var value = el.type === "checkbox" ? el.checked : el.value;
+      if (value !== "" && el.type !== "checkbox") {{
+        if (rule.integer !== undefined && !/^-?\d+$/.test(value)) return rule.integer;
+        if (rule.min !== undefined && Number(value) < rule.min) return rule.minMessage;
+        if (rule.max !== undefined && Number(value) > rule.max) return rule.maxMessage;
+        if (rule.minLength !== undefined && value.length < rule.minLength) return rule.minLengthMessage;
+        if (rule.maxLength !== undefined && value.length > rule.maxLength) return rule.maxLengthMessage;
+        if (rule.pattern !== undefined && !new RegExp("^(?:" + rule.pattern + ")$").test(value)) return rule.patternMessage;
+      }}
+      if (scripts[name]) return scripts[name](value, form);
+    }}
+
+    function validate() {{
+      var valid = true;
+      function fail(el, message) {{
+        el.setCustomValidity(message);
+        valid = false;
+      }}
+      Object.keys(rules).forEach(function (name) {{
+        fields(name).forEach(function (el) {{
+          var message = check(name, el);
+          if (message) fail(el, message);
+        }});
+      }});
+      required.forEach(function (name) {{
+        var group = fields(name);
+        var chosen = group.some(function (el) {{ return el.checked; }});
+        if (group.length && !chosen) fail(group[0], selectMessage);
+      }});
+      return valid || form.reportValidity();
+    }}
+
+    form.addEventListener("submit", function (event) {{
+      if (!validate()) event.preventDefault();
+    }});
+    form.addEventListener("input", function (event) {{
+      Array.prototype.forEach.call(form.elements, function (el) {{
+        if (el.name === event.target.name) el.setCustomValidity("");
+      }});
+    }});
+  }})();
+</script>
+"#
+    )
+}
+
+/// Collect the checks of `question`, mirroring the field names of the generator.
+///
+/// `response_path` differs from the field name inside enum variants, where the form
+/// inserts the variant name; [`FieldRules`] are looked up by response path.
+fn collect_question(
+    question: &Question,
+    parent: Option<&str>,
+    response_parent: Option<&str>,
+    options: &HtmlOptions,
+    checks: &mut Checks,
+) {
+    if matches!(question.default(), DefaultValue::Assumed(_)) {
+        return;
+    }
+    let join = |parent: Option<&str>| {
+        let own = question.path().as_str();
+        match (parent, own.is_empty()) {
+            (Some(parent), true) => parent.to_string(),
+            (Some(parent), false) => format!("{parent}.{own}"),
+            (None, _) => own.to_string(),
+        }
+    };
+    let name = join(parent);
+    let response_path = join(response_parent.or(parent));
+
+    match question.kind() {
+        QuestionKind::OneOf(one_of) => {
+            checks.required.push(name.clone());
+            for variant in &one_of.variants {
+                if let QuestionKind::AllOf(all_of) = &variant.kind {
+                    let variant_name = format!("{name}.{}", variant.name);
+                    for nested in all_of.questions() {
+                        collect_question(
+                            nested,
+                            Some(&variant_name),
+                            Some(&response_path),
+                            options,
+                            checks,
+                        );
+                    }
+                }
+            }
+        }
+        QuestionKind::AnyOf(any_of) => {
+            for (idx, variant) in any_of.variants.iter().enumerate() {
+                if let QuestionKind::AllOf(all_of) = &variant.kind {
+                    let item = format!("{name}.{idx}");
+                    for nested in all_of.questions() {
+                        collect_question(nested, Some(&item), None, options, checks);
+                    }
+                }
+            }
+        }
+        QuestionKind::AllOf(all_of) => {
+            for nested in all_of.questions() {
+                collect_question(nested, Some(&name), Some(&response_path), options, checks);
+            }
+        }
+        QuestionKind::Rating(_) => checks.required.push(name),
+        QuestionKind::Matrix(matrix_q) => {
+            for row in &matrix_q.rows {
+                checks.required.push(format!("{name}.{row}"));
+            }
+        }
+        kind => {
+            let mut check = FieldCheck {
+                rules: options
+                    .field_rules
+                    .get(&response_path)
+                    .cloned()
+                    .unwrap_or_default(),
+                ..FieldCheck::default()
+            };
+            match kind {
+                QuestionKind::Int(int_q) => {
+                    let messages = &int_q.messages;
+                    check.integer = Some(messages.invalid_integer(None));
+                    check.min = int_q
+                        .min
+                        .map(|min| bound(min, messages.at_least(&min.to_string())));
+                    check.max = int_q
+                        .max
+                        .map(|max| bound(max, messages.at_most(&max.to_string())));
+                }
+                QuestionKind::Float(float_q) => {
+                    // Infinite and NaN bounds have no JavaScript literal and check nothing
+                    let messages = &float_q.messages;
+                    check.min = float_q
+                        .min
+                        .filter(|min| min.is_finite())
+                        .map(|min| bound(min, messages.at_least(&min.to_string())));
+                    check.max = float_q
+                        .max
+                        .filter(|max| max.is_finite())
+                        .map(|max| bound(max, messages.at_most(&max.to_string())));
+                }
+                _ => {}
+            }
+            let has_checks = check.integer.is_some()
+                || check.min.is_some()
+                || check.max.is_some()
+                || check.rules != FieldRules::default();
+            if has_checks {
+                checks.fields.push((name, check));
+            }
+        }
+    }
+}
+
+/// A bound and its message, as stored in a [`FieldCheck`].
+fn bound(value: impl ToString, message: String) -> (String, String) {
+    (value.to_string(), message)
+}
+
+/// The rule of one field as a JavaScript object literal.
+fn rule_object(check: &FieldCheck, messages: &Messages) -> String {
+    let mut entries = Vec::new();
+    if let Some(message) = &check.integer {
+        entries.push(format!("integer: {}", js_string(message)));
+    }
+    if let Some((min, message)) = &check.min {
+        entries.push(format!("min: {min}, minMessage: {}", js_string(message)));
+    }
+    if let Some((max, message)) = &check.max {
+        entries.push(format!("max: {max}, maxMessage: {}", js_string(message)));
+    }
+    if let Some(min_len) = check.rules.min_len {
+        let message = messages.min_length(&min_len.to_string());
+        entries.push(format!(
+            "minLength: {min_len}, minLengthMessage: {}",
+            js_string(&message)
+        ));
+    }
+    if let Some(max_len) = check.rules.max_len {
+        let message = messages.max_length(&max_len.to_string());
+        entries.push(format!(
+            "maxLength: {max_len}, maxLengthMessage: {}",
+            js_string(&message)
+        ));
+    }
+    if let Some(pattern) = &check.rules.pattern {
+        entries.push(format!("pattern: {}", js_string(pattern)));
+        let message = match &check.rules.pattern_message {
+            Some(message) => message.clone(),
+            None => messages.no_match(),
+        };
+        entries.push(format!("patternMessage: {}", js_string(&message)));
+    }
+    if entries.is_empty() {
+        return "{}".to_string();
+    }
+    format!("{{ {} }}", entries.join(", "))
+}
+
+/// Quote `s` as a JavaScript string that is safe inside a `<script>` element.
+fn js_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '<' => quoted.push_str("\\u003c"),
+            '\u{2028}' => quoted.push_str("\\u2028"),
+            '\u{2029}' => quoted.push_str("\\u2029"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{
+        AllOfQuestion, FloatQuestion, InputQuestion, IntQuestion, OneOfQuestion, RatingQuestion,
+        Variant,
+    };
+    use std::collections::HashMap;
+
+    fn definition() -> SurveyDefinition {
+        let card = AllOfQuestion::new(vec![Question::new(
+            "number",
+            "Card number:",
+            QuestionKind::Input(InputQuestion::new()),
+        )]);
+        SurveyDefinition::new(vec![
+            Question::new("email", "Email:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "age",
+                "Age:",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new(
+                "payment",
+                "Payment:",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Cash"),
+                    Variant::new("Card", QuestionKind::AllOf(card)),
+                ])),
+            ),
+            Question::new(
+                "satisfaction",
+                "How satisfied are you?",
+                QuestionKind::Rating(RatingQuestion::new(1, 5)),
+            ),
+        ])
+    }
+
+    #[test]
+    fn bounds_and_selections_are_checked() {
+        let script = generate_script(&definition(), &HtmlOptions::new());
+        assert!(script.contains(
+            r#""age": { integer: "Please enter a valid integer", min: 0, minMessage: "Value must be at least 0", max: 150, maxMessage: "Value must be at most 150" }"#
+        ));
+        assert!(script.contains(r#"var required = ["payment", "satisfaction"];"#));
+        assert!(!script.contains(r#""email""#));
+    }
+
+    #[test]
+    fn field_rules_are_keyed_by_response_path() {
+        let options = HtmlOptions::new()
+            .with_field_rules(
+                "email",
+                FieldRules::new()
+                    .with_length(None, Some(80))
+                    .with_script("if (!value.includes(\"@\")) return \"Not an email\";"),
+            )
+            .with_field_rules(
+                "payment.number",
+                FieldRules::new().with_pattern(r"\d{16}", "16 digits"),
+            );
+        let script = generate_script(&definition(), &options);
+
+        assert!(script.contains(
+            r#""email": { maxLength: 80, maxLengthMessage: "Please enter at most 80 characters" }"#
+        ));
+        assert!(script.contains(r#""email": function (value, form) { if (!value.includes("@"))"#));
+        assert!(script.contains(
+            r#""payment.Card.number": { pattern: "\\d{16}", patternMessage: "16 digits" }"#
+        ));
+    }
+
+    #[test]
+    fn infinite_bounds_are_left_out() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "ratio",
+            "Ratio:",
+            QuestionKind::Float(FloatQuestion::with_bounds(
+                Some(f64::NEG_INFINITY),
+                Some(1.5),
+            )),
+        )]);
+        let script = generate_script(&definition, &HtmlOptions::new());
+        assert!(
+            script.contains(r#""ratio": { max: 1.5, maxMessage: "Value must be at most 1.5" }"#)
+        );
+        assert!(!script.contains("inf"));
+    }
+
+    #[test]
+    fn messages_are_translated() {
+        let german = HashMap::from([
+            (
+                "elicitor.max-length".to_string(),
+                "Höchstens {max} Zeichen".to_string(),
+            ),
+            (
+                "elicitor.select-option".to_string(),
+                "Bitte wählen".to_string(),
+            ),
+        ]);
+        let options = HtmlOptions::new()
+            .with_messages(Messages::translated(&german))
+            .with_field_rules("email", FieldRules::new().with_length(None, Some(80)));
+        let script = generate_script(&definition(), &options);
+
+        assert!(script.contains(r#"maxLengthMessage: "Höchstens 80 Zeichen""#));
+        assert!(script.contains(r#"var selectMessage = "Bitte wählen";"#));
+    }
+
+    #[test]
+    fn strings_cannot_close_the_script() {
+        assert_eq!(js_string("</script>"), r#""\u003c/script>""#);
+        assert_eq!(js_string("a\"b\\"), r#""a\"b\\""#);
+    }
+}
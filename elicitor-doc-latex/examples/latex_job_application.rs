@@ -0,0 +1,20 @@
+//! Sections example - print the job application with numbered sections and a table
+//! of contents.
+//!
+//! Run with: cargo run -p elicitor-doc-latex --example latex_job_application
+
+use elicitor::Survey;
+use elicitor_doc_latex::{LatexOptions, PaperSize, to_latex_form_with_options};
+use example_surveys::JobApplication;
+
+fn main() {
+    let options = LatexOptions::new()
+        .with_paper_size(PaperSize::A4)
+        .with_title("Job Application")
+        .with_table_of_contents();
+    let latex = to_latex_form_with_options(&JobApplication::survey(), &options);
+
+    std::fs::write("job_application.tex", &latex).expect("Failed to write LaTeX file");
+
+    println!("Generated job_application.tex");
+}
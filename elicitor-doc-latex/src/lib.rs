@@ -1,56 +1,352 @@
 //! LaTeX backend for derive-survey: generates fillable PDF forms from SurveyDefinition.
+//!
+//! [`to_latex_form`] prints every question kind. [`LatexOptions`] chooses how lists are
+//! printed: numbered fields ([`ListLayout::Fields`], the default) or one multiline box.
+//! It also sets the page layout (paper size, margins, font, columns, field widths), a
+//! title, and extra preamble lines for a letterhead.
 
-use elicitor::SurveyDefinition;
+use elicitor::{FormLink, SurveyDefinition};
 
 /// Escape special LaTeX characters in text content.
+///
+/// Every character is replaced at most once, so the braces of `\textbackslash{}` are not
+/// escaped again.
 fn escape_latex(s: &str) -> String {
-    s.replace('\\', "\\textbackslash{}")
-        .replace('&', "\\&")
-        .replace('%', "\\%")
-        .replace('$', "\\$")
-        .replace('#', "\\#")
-        .replace('_', "\\_")
-        .replace('{', "\\{")
-        .replace('}', "\\}")
-        .replace('~', "\\textasciitilde{}")
-        .replace('^', "\\textasciicircum{}")
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape an entry of a `\ChoiceMenu` list, where `,` separates entries and `=` separates
+/// a label from its value.
+fn escape_choice(s: &str) -> String {
+    escape_latex(s).replace(',', "{,}").replace('=', "{=}")
 }
 
 /// Sanitize a field name for use in PDF form field names.
-/// PDF field names should not contain special characters.
+///
+/// PDF field names should not contain special characters. Dots between path segments
+/// become `-`, underscores are kept so `first_name` and `first.name` stay apart.
 fn sanitize_field_name(s: &str) -> String {
     s.chars()
         .map(|c| match c {
-            'a'..='z' | 'A'..='Z' | '0'..='9' => c,
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => c,
             _ => '-',
         })
         .collect()
 }
 
-/// Calculate shade percentage based on nesting depth.
-/// Starts at 5% and increases by 5% per level, capped at 25%.
-fn shade_percent(indent_level: usize) -> usize {
-    (5 + indent_level * 5).min(25)
+/// How list questions are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListLayout {
+    /// Numbered text fields, one per item. The count is raised to the list's minimum
+    /// length and lowered to its maximum length.
+    Fields(usize),
+    /// A multiline box with one item per line.
+    Box,
 }
 
-/// Generate a LaTeX document (as a String) for a fillable form from a SurveyDefinition.
-pub fn to_latex_form(survey: &SurveyDefinition) -> String {
-    let mut latex = String::new();
+impl Default for ListLayout {
+    fn default() -> Self {
+        Self::Fields(3)
+    }
+}
+
+/// Paper size of the generated document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaperSize {
+    /// US Letter, 8.5 x 11 in.
+    #[default]
+    Letter,
+    /// US Legal, 8.5 x 14 in.
+    Legal,
+    /// ISO A4, 210 x 297 mm.
+    A4,
+    /// ISO A5, 148 x 210 mm.
+    A5,
+}
+
+impl PaperSize {
+    /// Name of the paper size as a `geometry` option.
+    fn geometry_name(self) -> &'static str {
+        match self {
+            Self::Letter => "letterpaper",
+            Self::Legal => "legalpaper",
+            Self::A4 => "a4paper",
+            Self::A5 => "a5paper",
+        }
+    }
+}
+
+/// Where the QR code of a [`FormLink`] is printed on every page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QrPosition {
+    /// Top right corner.
+    #[default]
+    Header,
+    /// Bottom right corner.
+    Footer,
+}
+
+/// Widths of the form fields, as LaTeX lengths such as `4in` or `0.8\linewidth`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldWidths {
+    /// Text, multiline, path and list fields.
+    pub text: String,
+    /// Selection menus and masked fields.
+    pub short: String,
+    /// Integer, decimal and currency fields.
+    pub number: String,
+}
+
+impl Default for FieldWidths {
+    fn default() -> Self {
+        Self {
+            text: "4in".to_string(),
+            short: "3in".to_string(),
+            number: "1.5in".to_string(),
+        }
+    }
+}
+
+/// Options for LaTeX generation.
+#[derive(Debug, Clone)]
+pub struct LatexOptions {
+    /// How list questions are printed.
+    pub list_layout: ListLayout,
+    /// Paper size.
+    pub paper_size: PaperSize,
+    /// Page margin on all sides, as a LaTeX length.
+    pub margin: String,
+    /// Base font size in points: 10, 11 or 12.
+    pub font_size: u8,
+    /// Font package and its options. `None` keeps LaTeX's Computer Modern.
+    pub font: Option<(String, Option<String>)>,
+    /// Extra preamble lines, added right before `\begin{document}`.
+    pub preamble: Option<String>,
+    /// Title printed at the top of the first page and stored as the PDF title.
+    pub title: Option<String>,
+    /// Whether the form is set in two columns.
+    pub two_column: bool,
+    /// Widths of the form fields.
+    pub field_widths: FieldWidths,
+    /// Whether every top-level nested question starts on a new page.
+    pub section_page_breaks: bool,
+    /// Whether nested questions become numbered sections, subsections and
+    /// subsubsections headed by their prompt.
+    pub numbered_sections: bool,
+    /// Whether a table of contents is printed before the form. It needs a second
+    /// LaTeX run to be filled in.
+    pub table_of_contents: bool,
+    /// Whether the form is meant to be filled in by hand: fields become ruled lines,
+    /// checkboxes empty squares and choices circles to tick.
+    pub print_mode: bool,
+    /// Link to the online version of the form, printed as a QR code on every page.
+    pub qr_code: Option<FormLink>,
+    /// Where the QR code is printed.
+    pub qr_position: QrPosition,
+}
+
+impl Default for LatexOptions {
+    fn default() -> Self {
+        Self {
+            list_layout: ListLayout::default(),
+            paper_size: PaperSize::default(),
+            margin: "1in".to_string(),
+            font_size: 11,
+            font: Some(("cabin".to_string(), Some("sfdefault".to_string()))),
+            preamble: None,
+            title: None,
+            two_column: false,
+            field_widths: FieldWidths::default(),
+            section_page_breaks: false,
+            numbered_sections: false,
+            table_of_contents: false,
+            print_mode: false,
+            qr_code: None,
+            qr_position: QrPosition::default(),
+        }
+    }
+}
+
+impl LatexOptions {
+    /// Create new options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Print lists as `count` numbered text fields.
+    pub fn with_list_fields(mut self, count: usize) -> Self {
+        self.list_layout = ListLayout::Fields(count);
+        self
+    }
+
+    /// Print lists as a multiline box with one item per line.
+    pub fn with_list_box(mut self) -> Self {
+        self.list_layout = ListLayout::Box;
+        self
+    }
+
+    /// Set the paper size.
+    pub fn with_paper_size(mut self, paper_size: PaperSize) -> Self {
+        self.paper_size = paper_size;
+        self
+    }
+
+    /// Set the page margin, e.g. `2cm`.
+    pub fn with_margin(mut self, margin: impl Into<String>) -> Self {
+        self.margin = margin.into();
+        self
+    }
+
+    /// Set the base font size in points. LaTeX's article class supports 10, 11 and 12.
+    pub fn with_font_size(mut self, points: u8) -> Self {
+        self.font_size = points;
+        self
+    }
+
+    /// Load a font package, e.g. `lmodern` or `helvet`, without options.
+    pub fn with_font(mut self, package: impl Into<String>) -> Self {
+        self.font = Some((package.into(), None));
+        self
+    }
+
+    /// Load a font package with options, e.g. `("roboto", "sfdefault")`.
+    pub fn with_font_options(
+        mut self,
+        package: impl Into<String>,
+        options: impl Into<String>,
+    ) -> Self {
+        self.font = Some((package.into(), Some(options.into())));
+        self
+    }
+
+    /// Use LaTeX's default font.
+    pub fn without_font(mut self) -> Self {
+        self.font = None;
+        self
+    }
+
+    /// Add raw lines to the preamble, e.g. `\usepackage{fancyhdr}` and a letterhead.
+    pub fn with_preamble(mut self, preamble: impl Into<String>) -> Self {
+        self.preamble = Some(preamble.into());
+        self
+    }
+
+    /// Set the title printed above the form.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the form in two columns.
+    pub fn with_two_columns(mut self) -> Self {
+        self.two_column = true;
+        self
+    }
+
+    /// Set the widths of the form fields.
+    pub fn with_field_widths(mut self, field_widths: FieldWidths) -> Self {
+        self.field_widths = field_widths;
+        self
+    }
+
+    /// Start every top-level nested question on a new page.
+    pub fn with_section_page_breaks(mut self) -> Self {
+        self.section_page_breaks = true;
+        self
+    }
+
+    /// Print nested questions as numbered sections instead of shaded blocks.
+    ///
+    /// Three levels become `\section`, `\subsection` and `\subsubsection`; deeper
+    /// levels and the fields of enum variants stay shaded blocks.
+    pub fn with_numbered_sections(mut self) -> Self {
+        self.numbered_sections = true;
+        self
+    }
+
+    /// Print a table of contents of the numbered sections before the form.
+    pub fn with_table_of_contents(mut self) -> Self {
+        self.numbered_sections = true;
+        self.table_of_contents = true;
+        self
+    }
+
+    /// Print a paper form to fill in by hand instead of a fillable PDF form.
+    ///
+    /// Text fields become ruled lines, multiline fields several of them, and number
+    /// fields with both bounds are as wide as the longest allowed value. Checkboxes
+    /// become empty squares and every choice of a menu is listed with a circle.
+    pub fn with_print_mode(mut self) -> Self {
+        self.print_mode = true;
+        self
+    }
 
-    // Document preamble
-    latex.push_str(
-        r#"\documentclass[11pt]{article}
-\usepackage[utf8]{inputenc}
-\usepackage[T1]{fontenc}
-\usepackage[sfdefault]{cabin}
-\usepackage[pdftex]{hyperref}
+    /// Print a QR code linking to the online version of the form on every page,
+    /// together with the form ID if the link has one. Needs the `qrcode` LaTeX package.
+    pub fn with_qr_code(mut self, link: FormLink) -> Self {
+        self.qr_code = Some(link);
+        self
+    }
+
+    /// Print the QR code in the header or the footer.
+    pub fn with_qr_position(mut self, position: QrPosition) -> Self {
+        self.qr_position = position;
+        self
+    }
+}
+
+/// The document class, packages and macros up to `\begin{document}`.
+fn preamble(options: &LatexOptions) -> String {
+    let mut class_options = vec![format!("{}pt", options.font_size)];
+    if options.two_column {
+        class_options.push("twocolumn".to_string());
+    }
+    let mut s = format!(
+        "\\documentclass[{}]{{article}}\n\\usepackage[utf8]{{inputenc}}\n\\usepackage[T1]{{fontenc}}\n",
+        class_options.join(",")
+    );
+    match &options.font {
+        Some((package, Some(font_options))) => {
+            s.push_str(&format!("\\usepackage[{font_options}]{{{package}}}\n"))
+        }
+        Some((package, None)) => s.push_str(&format!("\\usepackage{{{package}}}\n")),
+        None => {}
+    }
+    if options.print_mode {
+        s.push_str("\\usepackage{amssymb}\n");
+    }
+    s.push_str(
+        r#"\usepackage[pdftex]{hyperref}
 \usepackage{geometry}
 \usepackage{xcolor}
 \usepackage{tcolorbox}
 
-\geometry{margin=1in}
-\hypersetup{
-    colorlinks=true,
+"#,
+    );
+    s.push_str(&format!(
+        "\\geometry{{{},margin={}}}\n",
+        options.paper_size.geometry_name(),
+        options.margin
+    ));
+    s.push_str("\\hypersetup{\n");
+    if let Some(title) = &options.title {
+        s.push_str(&format!("    pdftitle={{{}}},\n", escape_latex(title)));
+    }
+    s.push_str(
+        r#"    colorlinks=true,
     linkcolor=blue,
     pdfborder={0 0 0}
 }
@@ -71,11 +367,383 @@ pub fn to_latex_form(survey: &SurveyDefinition) -> String {
     boxsep=0pt
 }
 
+"#,
+    );
+    if let Some(link) = &options.qr_code {
+        s.push_str(&qr_code_header(link, options.qr_position));
+    }
+    if let Some(extra) = &options.preamble {
+        s.push_str(extra);
+        if !extra.ends_with('\n') {
+            s.push('\n');
+        }
+    }
+    s.push_str("\n\n\\begin{document}\n");
+    s
+}
 
+/// Page style that prints the QR code of `link`, and its form ID, on every page.
+fn qr_code_header(link: &FormLink, position: QrPosition) -> String {
+    let id = link
+        .id()
+        .map(|id| {
+            format!(
+                "{{\\footnotesize Form ID: \\texttt{{{}}}}}\\quad",
+                escape_latex(id)
+            )
+        })
+        .unwrap_or_default();
+    let code = format!("{id}\\qrcode[height=1.2cm]{{{}}}", escape_qr(link.url()));
+    let (head, foot) = match position {
+        QrPosition::Header => (
+            format!("\\setlength{{\\headheight}}{{1.4cm}}\n\\fancyhead[R]{{{code}}}\n"),
+            String::new(),
+        ),
+        // Centered on the footer's baseline, so the code stays clear of the text
+        QrPosition::Footer => (
+            String::new(),
+            format!("\\fancyfoot[R]{{\\raisebox{{-0.5\\height}}{{{code}}}}}\n"),
+        ),
+    };
+    format!(
+        "\\usepackage{{qrcode}}\n\\usepackage{{fancyhdr}}\n\\pagestyle{{fancy}}\n\\fancyhf{{}}\n\\renewcommand{{\\headrulewidth}}{{0pt}}\n{head}\\fancyfoot[C]{{\\thepage}}\n{foot}\n"
+    )
+}
 
-\begin{document}
-"#,
+/// Escape a URL for `\qrcode`, which takes the special characters of LaTeX escaped
+/// with a backslash. Backslashes are percent-encoded instead.
+fn escape_qr(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            '\\' => escaped.push_str("%5C"),
+            '#' | '$' | '&' | '^' | '_' | '~' | '%' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Number of ruled lines printed for a multiline field in print mode.
+const PRINT_LINES: usize = 4;
+
+/// A one-line text field, or a ruled line in print mode.
+fn text_field(name: &str, width: &str, options: &LatexOptions) -> String {
+    if options.print_mode {
+        format!("\\rule{{0pt}}{{1.5em}}\\rule{{{width}}}{{0.4pt}}")
+    } else {
+        format!("\\TextField[name={name},width={width},bordercolor={{0.5 0.5 0.5}}]{{}}")
+    }
+}
+
+/// A text field that hides its input, or a ruled line in print mode.
+fn masked_field(name: &str, width: &str, options: &LatexOptions) -> String {
+    if options.print_mode {
+        text_field(name, width, options)
+    } else {
+        format!(
+            "\\TextField[name={name},password=true,width={width},bordercolor={{0.5 0.5 0.5}}]{{}}"
+        )
+    }
+}
+
+/// A text field for a six-digit hex color, or a short ruled line in print mode.
+fn color_field(name: &str, options: &LatexOptions) -> String {
+    if options.print_mode {
+        text_field(name, "1in", options)
+    } else {
+        format!("\\TextField[name={name},width=1in,maxlen=6,bordercolor={{0.5 0.5 0.5}}]{{}}")
+    }
+}
+
+/// A text box for several lines, or ruled lines in print mode.
+fn multiline_field(name: &str, width: &str, options: &LatexOptions) -> String {
+    if options.print_mode {
+        vec![format!("\\rule{{0pt}}{{1.8em}}\\rule{{{width}}}{{0.4pt}}"); PRINT_LINES]
+            .join("\\\\\n")
+    } else {
+        format!(
+            "\\TextField[name={name},multiline=true,width={width},height=1.2in,bordercolor={{0.5 0.5 0.5}}]{{}}"
+        )
+    }
+}
+
+/// A checkbox, or an empty square in print mode.
+fn check_box(name: &str, options: &LatexOptions) -> String {
+    if options.print_mode {
+        "$\\square$".to_string()
+    } else {
+        format!(
+            "\\CheckBox[name={name},width=10pt,height=10pt,borderwidth=1pt,bordercolor={{0.4 0.4 0.4}}]{{}}"
+        )
+    }
+}
+
+/// A `radio` or `combo` choice menu of escaped `choices`, or the choices with a circle
+/// in front of each in print mode.
+fn choice_menu(
+    style: &str,
+    name: &str,
+    width: Option<&str>,
+    choices: &[String],
+    options: &LatexOptions,
+) -> String {
+    if options.print_mode {
+        let circled: Vec<String> = choices
+            .iter()
+            .map(|choice| format!("$\\bigcirc$~{choice}"))
+            .collect();
+        return circled.join(" \\quad ");
+    }
+    match width {
+        Some(width) => format!(
+            "\\ChoiceMenu[{style},name={name},width={width},bordercolor={{0.5 0.5 0.5}}]{{}}{{{}}}",
+            choices.join(",")
+        ),
+        None => format!(
+            "\\ChoiceMenu[{style},name={name},bordercolor={{0.4 0.4 0.4}}]{{}}{{{}}}",
+            choices.join(",")
+        ),
+    }
+}
+
+/// Width of a number field. In print mode a field with both bounds is sized to fit the
+/// longer one.
+fn number_width<N: std::fmt::Display>(
+    min: Option<N>,
+    max: Option<N>,
+    options: &LatexOptions,
+) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) if options.print_mode => {
+            let chars = min
+                .to_string()
+                .chars()
+                .count()
+                .max(max.to_string().chars().count());
+            format!("{}em", chars + 2)
+        }
+        _ => options.field_widths.number.clone(),
+    }
+}
+
+/// Italic hint for a decimal field, with its bounds if any.
+fn float_hint(question: &elicitor::FloatQuestion) -> String {
+    let bounds = match (question.min, question.max) {
+        (Some(min), Some(max)) => format!(", {} -- {}", question.format(min), question.format(max)),
+        (Some(min), None) => format!(", min: {}", question.format(min)),
+        (None, Some(max)) => format!(", max: {}", question.format(max)),
+        (None, None) => String::new(),
+    };
+    format!(" \\textit{{\\small(decimal{bounds})}}")
+}
+
+/// Fields for a list question, either numbered text fields or a multiline box.
+fn list_fields(
+    field_name: &str,
+    question: &elicitor::ListQuestion,
+    options: &LatexOptions,
+    indent: &str,
+) -> String {
+    use elicitor::ListElementKind;
+
+    let (element, bounds) = match &question.element_kind {
+        ListElementKind::String => ("text", (None, None)),
+        ListElementKind::Int { min, max } => (
+            "whole numbers",
+            (min.map(|m| m.to_string()), max.map(|m| m.to_string())),
+        ),
+        ListElementKind::Float { min, max } => (
+            "decimals",
+            (min.map(|m| m.to_string()), max.map(|m| m.to_string())),
+        ),
+    };
+    let bounds = match bounds {
+        (Some(min), Some(max)) => format!(", {min} -- {max}"),
+        (Some(min), None) => format!(", min: {min}"),
+        (None, Some(max)) => format!(", max: {max}"),
+        (None, None) => String::new(),
+    };
+
+    let mut s = String::new();
+    match options.list_layout {
+        ListLayout::Box => {
+            s.push_str(indent);
+            s.push_str("\\noindent");
+            s.push_str(&multiline_field(
+                field_name,
+                &options.field_widths.text,
+                options,
+            ));
+            s.push_str(&format!(
+                " \\textit{{\\small({element}{bounds}, one per line)}}\n\n"
+            ));
+        }
+        ListLayout::Fields(count) => {
+            let count = count
+                .max(question.min_items.unwrap_or(0))
+                .min(question.max_items.unwrap_or(usize::MAX))
+                .max(1);
+            s.push_str(indent);
+            s.push_str(&format!(
+                "\\noindent\\textit{{\\small({element}{bounds})}}\n\n"
+            ));
+            for item in 0..count {
+                s.push_str(indent);
+                s.push_str(&format!("\\noindent {}.~", item + 1));
+                s.push_str(&text_field(
+                    &format!("{field_name}-{item}"),
+                    &options.field_widths.text,
+                    options,
+                ));
+                s.push_str("\n\n");
+            }
+        }
+    }
+    s
+}
+
+/// Render a short italic hint listing the first few suggestions of an autocomplete field.
+fn suggestions_hint(question: &elicitor::AutocompleteQuestion) -> String {
+    let suggestions: Vec<String> = question
+        .completions("")
+        .iter()
+        .take(5)
+        .map(|s| escape_latex(s))
+        .collect();
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(" \\textit{{\\small(e.g. {})}}", suggestions.join(", "))
+    }
+}
+
+/// Render a short italic hint saying what choosing a variant means, if it says.
+fn variant_hint(variant: &elicitor::Variant) -> String {
+    variant
+        .help
+        .as_ref()
+        .map(|help| format!(" \\textit{{\\small({})}}", escape_latex(help)))
+        .unwrap_or_default()
+}
+
+/// Render a description list of what choosing each variant of a choice menu means, or
+/// nothing if none of them says.
+fn variant_descriptions(variants: &[elicitor::Variant], indent: &str) -> String {
+    if variants.iter().all(|v| v.help.is_none()) {
+        return String::new();
+    }
+    let mut s = format!("{indent}\\begin{{description}}\\small\n");
+    for variant in variants {
+        if let Some(help) = &variant.help {
+            s.push_str(&format!(
+                "{indent}  \\item[{{{}}}] {}\n",
+                escape_latex(&variant.name),
+                escape_latex(help)
+            ));
+        }
+    }
+    s.push_str(&format!("{indent}\\end{{description}}\n"));
+    s
+}
+
+/// A text field for a money amount, with the currency symbol in front and the bounds after it.
+fn currency_field(
+    field_name: &str,
+    question: &elicitor::CurrencyQuestion,
+    options: &LatexOptions,
+) -> String {
+    let bound = |minor| escape_latex(&question.format(minor));
+    let width = number_width(
+        question.min.map(|minor| question.format(minor)),
+        question.max.map(|minor| question.format(minor)),
+        options,
     );
+    let mut s = format!(
+        "\\noindent {}~{}",
+        escape_latex(question.symbol()),
+        text_field(field_name, &width, options)
+    );
+    match (question.min, question.max) {
+        (Some(min), Some(max)) => s.push_str(&format!(
+            " \\textit{{\\small({} -- {})}}",
+            bound(min),
+            bound(max)
+        )),
+        (Some(min), None) => s.push_str(&format!(" \\textit{{\\small(min: {})}}", bound(min))),
+        (None, Some(max)) => s.push_str(&format!(" \\textit{{\\small(max: {})}}", bound(max))),
+        (None, None) => {}
+    }
+    s
+}
+
+/// A row of radio buttons for a rating scale, with the anchor labels at the ends.
+fn rating_scale(
+    field_name: &str,
+    question: &elicitor::RatingQuestion,
+    options: &LatexOptions,
+) -> String {
+    let points: Vec<String> = question.points().map(|p| p.to_string()).collect();
+    let mut s = String::from("\\noindent ");
+    if let Some((low, _)) = &question.anchors {
+        s.push_str(&format!("\\textit{{\\small {}}} ", escape_latex(low)));
+    }
+    s.push_str(&choice_menu("radio", field_name, None, &points, options));
+    if let Some((_, high)) = &question.anchors {
+        s.push_str(&format!(" \\textit{{\\small {}}}", escape_latex(high)));
+    }
+    s
+}
+
+/// A table with one row of radio buttons (one per column) for every matrix row.
+fn matrix_table(
+    field_name: &str,
+    question: &elicitor::MatrixQuestion,
+    options: &LatexOptions,
+) -> String {
+    let columns: Vec<String> = question.columns.iter().map(|c| escape_choice(c)).collect();
+    let mut s = String::from("\\noindent\\begin{tabular}{ll}\n");
+    for row in &question.rows {
+        let name = format!("{}-{}", field_name, sanitize_field_name(row));
+        s.push_str(&format!(
+            "{} & {} \\\\\n",
+            escape_latex(row),
+            choice_menu("radio", &name, None, &columns, options)
+        ));
+    }
+    s.push_str("\\end{tabular}");
+    s
+}
+
+/// Calculate shade percentage based on nesting depth.
+/// Starts at 5% and increases by 5% per level, capped at 25%.
+fn shade_percent(indent_level: usize) -> usize {
+    (5 + indent_level * 5).min(25)
+}
+
+/// Generate a LaTeX document (as a String) for a fillable form from a SurveyDefinition.
+pub fn to_latex_form(survey: &SurveyDefinition) -> String {
+    to_latex_form_with_options(survey, &LatexOptions::new())
+}
+
+/// Generate a LaTeX document for a fillable form with custom options.
+pub fn to_latex_form_with_options(survey: &SurveyDefinition, options: &LatexOptions) -> String {
+    let mut latex = String::new();
+
+    latex.push_str(&preamble(options));
+
+    if let Some(title) = &options.title {
+        latex.push_str("\n\\begin{center}\n{\\LARGE\\bfseries ");
+        latex.push_str(&escape_latex(title));
+        latex.push_str("}\n\\end{center}\n\n\\vspace{1em}\n");
+    }
+
+    if options.table_of_contents {
+        latex.push_str("\n\\tableofcontents\n\n\\vspace{1em}\n");
+    }
 
     // Prelude
     if let Some(prelude) = &survey.prelude {
@@ -84,36 +752,49 @@ pub fn to_latex_form(survey: &SurveyDefinition) -> String {
         latex.push_str("\n\n\\vspace{1em}\n");
     }
 
-    latex.push_str("\n\\begin{Form}\n");
+    if !options.print_mode {
+        latex.push_str("\n\\begin{Form}\n");
+    }
 
     for (i, q) in survey.questions.iter().enumerate() {
         if i > 0 {
-            latex.push_str("\n\\vspace{1.5em}\n");
+            if options.section_page_breaks && matches!(q.kind(), elicitor::QuestionKind::AllOf(_)) {
+                latex.push_str("\n\\clearpage\n");
+            } else {
+                latex.push_str("\n\\vspace{1.5em}\n");
+            }
         }
-        latex.push_str(&render_question(q, 0));
+        latex.push_str(&render_question(q, 0, options));
     }
 
-    latex.push_str("\n\\end{Form}\n");
+    if !options.print_mode {
+        latex.push_str("\n\\end{Form}\n");
+    }
 
     // Epilogue
     if let Some(epilogue) = &survey.epilogue {
         latex.push_str("\n\\vspace{2em}\n\\noindent ");
         latex.push_str(&escape_latex(epilogue));
-        latex.push_str("\n");
+        latex.push('\n');
     }
 
     latex.push_str("\n\\end{document}\n");
     latex
 }
 
-fn render_question(q: &elicitor::Question, indent_level: usize) -> String {
-    render_question_with_path(q, indent_level, None)
+fn render_question(q: &elicitor::Question, indent_level: usize, options: &LatexOptions) -> String {
+    render_question_with_path(q, indent_level, None, 0, options)
 }
 
+/// Heading commands for numbered sections, by depth.
+const SECTION_COMMANDS: [&str; 3] = ["section", "subsection", "subsubsection"];
+
 fn render_question_with_path(
     q: &elicitor::Question,
     indent_level: usize,
     parent_path: Option<&str>,
+    section_level: usize,
+    options: &LatexOptions,
 ) -> String {
     use elicitor::QuestionKind;
 
@@ -130,8 +811,25 @@ fn render_question_with_path(
     };
     let field_name = sanitize_field_name(&full_path);
 
+    // Nested questions outside of shaded blocks become sections, headed by their prompt
+    let section = match q.kind() {
+        // Enum fields are wrapped in a group holding just the unprompted choice
+        QuestionKind::AllOf(allof)
+            if options.numbered_sections
+                && indent_level == 0
+                && !ask.is_empty()
+                && !matches!(allof.questions.as_slice(), [only] if only.ask().is_empty()) =>
+        {
+            SECTION_COMMANDS.get(section_level)
+        }
+        _ => None,
+    };
+
     // Render the question text if present
-    if !ask.is_empty() {
+    if let Some(command) = section {
+        let heading = ask.trim_end_matches(':');
+        s.push_str(&format!("\\{command}{{{}}}\n\n", escape_latex(heading)));
+    } else if !ask.is_empty() {
         s.push_str(&indent);
         s.push_str("\\noindent\\textbf{");
         s.push_str(&escape_latex(ask));
@@ -143,79 +841,157 @@ fn render_question_with_path(
     match q.kind() {
         QuestionKind::Input(_) => {
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str(",width=4in,bordercolor={0.5 0.5 0.5}]{}\n");
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &field_name,
+                &options.field_widths.text,
+                options,
+            ));
+            s.push('\n');
+            s.push_str(&indent);
+            s.push_str("\\par\\medskip\n");
+        }
+        QuestionKind::Autocomplete(autocomplete_q) => {
+            s.push_str(&indent);
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &field_name,
+                &options.field_widths.text,
+                options,
+            ));
+            s.push_str(&suggestions_hint(autocomplete_q));
+            s.push('\n');
             s.push_str(&indent);
             s.push_str("\\par\\medskip\n");
         }
         QuestionKind::Int(int_q) => {
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str(",width=1.5in,bordercolor={0.5 0.5 0.5}]{}");
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &field_name,
+                &number_width(int_q.min, int_q.max, options),
+                options,
+            ));
 
             // Add range hint if available
             if int_q.min.is_some() || int_q.max.is_some() {
                 s.push_str(" \\textit{\\small(");
                 match (int_q.min, int_q.max) {
-                    (Some(min), Some(max)) => s.push_str(&format!("{} -- {}", min, max)),
-                    (Some(min), None) => s.push_str(&format!("min: {}", min)),
-                    (None, Some(max)) => s.push_str(&format!("max: {}", max)),
+                    (Some(min), Some(max)) => {
+                        s.push_str(&format!("{} -- {}", int_q.format(min), int_q.format(max)))
+                    }
+                    (Some(min), None) => s.push_str(&format!("min: {}", int_q.format(min))),
+                    (None, Some(max)) => s.push_str(&format!("max: {}", int_q.format(max))),
                     (None, None) => {}
                 }
                 s.push_str(")}");
             }
-            s.push_str("\n");
+            s.push('\n');
             s.push_str(&indent);
             s.push_str("\\par\\medskip\n");
         }
         QuestionKind::Float(float_q) => {
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str(",width=1.5in,bordercolor={0.5 0.5 0.5}]{}");
-
-            // Add range hint if available
-            if float_q.min.is_some() || float_q.max.is_some() {
-                s.push_str(" \\textit{\\small(");
-                match (float_q.min, float_q.max) {
-                    (Some(min), Some(max)) => s.push_str(&format!("{} -- {}", min, max)),
-                    (Some(min), None) => s.push_str(&format!("min: {}", min)),
-                    (None, Some(max)) => s.push_str(&format!("max: {}", max)),
-                    (None, None) => {}
-                }
-                s.push_str(")}");
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &field_name,
+                &number_width(float_q.min, float_q.max, options),
+                options,
+            ));
+            s.push_str(&float_hint(float_q));
+            s.push('\n');
+            s.push_str(&indent);
+            s.push_str("\\par\\medskip\n");
+        }
+        QuestionKind::Currency(currency_q) => {
+            s.push_str(&indent);
+            s.push_str(&currency_field(&field_name, currency_q, options));
+            s.push('\n');
+            s.push_str(&indent);
+            s.push_str("\\par\\medskip\n");
+        }
+        QuestionKind::Color(_) => {
+            s.push_str(&indent);
+            s.push_str("\\noindent\\#");
+            s.push_str(&color_field(&field_name, options));
+            s.push_str(" \\textit{\\small(hex, e.g. 1e90ff)}\n");
+            s.push_str(&indent);
+            s.push_str("\\par\\medskip\n");
+        }
+        QuestionKind::Path(path_q) => {
+            s.push_str(&indent);
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &field_name,
+                &options.field_widths.text,
+                options,
+            ));
+            if let Some(hint) = path_q.hint() {
+                s.push_str(&format!(" \\textit{{\\small({})}}", escape_latex(&hint)));
             }
-            s.push_str("\n");
+            s.push('\n');
+            s.push_str(&indent);
+            s.push_str("\\par\\medskip\n");
+        }
+        QuestionKind::Rating(rating_q) => {
+            s.push_str(&indent);
+            s.push_str(&rating_scale(&field_name, rating_q, options));
+            s.push('\n');
+            s.push_str(&indent);
+            s.push_str("\\par\\medskip\n");
+        }
+        QuestionKind::Matrix(matrix_q) => {
+            s.push_str(&indent);
+            s.push_str(&matrix_table(&field_name, matrix_q, options));
+            s.push('\n');
             s.push_str(&indent);
             s.push_str("\\par\\medskip\n");
         }
         QuestionKind::Confirm(_) => {
             s.push_str(&indent);
-            s.push_str("\\noindent\\CheckBox[name=");
-            s.push_str(&field_name);
-            s.push_str(
-                ",width=10pt,height=10pt,borderwidth=1pt,bordercolor={0.4 0.4 0.4}]{} Yes\n\n",
-            );
+            s.push_str("\\noindent");
+            s.push_str(&check_box(&field_name, options));
+            s.push_str(" Yes\n\n");
+        }
+        QuestionKind::Select(select_q) => {
+            s.push_str(&indent);
+            let choices: Vec<String> = select_q
+                .options
+                .iter()
+                .map(|o| escape_choice(&o.label))
+                .collect();
+            s.push_str("\\noindent");
+            s.push_str(&choice_menu(
+                "combo",
+                &field_name,
+                Some(&options.field_widths.short),
+                &choices,
+                options,
+            ));
+            s.push_str("\n\n");
         }
         QuestionKind::OneOf(oneof) => {
             s.push_str(&indent);
-            s.push_str("\\noindent\\ChoiceMenu[combo,name=");
-            s.push_str(&field_name);
-            s.push_str(",width=3in,bordercolor={0.5 0.5 0.5}]{}{");
-            let options: Vec<String> = oneof
+            let choices: Vec<String> = oneof
                 .variants
                 .iter()
-                .map(|v| escape_latex(&v.name))
+                .map(|v| escape_choice(&v.name))
                 .collect();
-            s.push_str(&options.join(","));
-            s.push_str("}\n");
+            s.push_str("\\noindent");
+            s.push_str(&choice_menu(
+                "combo",
+                &field_name,
+                Some(&options.field_widths.short),
+                &choices,
+                options,
+            ));
+            s.push('\n');
+            s.push_str(&variant_descriptions(&oneof.variants, &indent));
 
             // Render follow-up fields for variants that have nested questions
             for variant in &oneof.variants {
                 if !matches!(variant.kind, elicitor::QuestionKind::Unit) {
-                    s.push_str("\n");
+                    s.push('\n');
                     s.push_str(&indent);
                     s.push_str("\\vspace{0.5em}\n");
                     s.push_str(&indent);
@@ -231,6 +1007,7 @@ fn render_question_with_path(
                         &variant.kind,
                         &full_path,
                         indent_level + 1,
+                        options,
                     ));
                     s.push_str(&indent);
                     s.push_str("\\end{shadedblock}\n");
@@ -242,10 +1019,10 @@ fn render_question_with_path(
                 let checkbox_name =
                     format!("{}-{}", field_name, sanitize_field_name(&variant.name));
                 s.push_str(&indent);
-                s.push_str("\\CheckBox[name=");
-                s.push_str(&checkbox_name);
-                s.push_str(",width=10pt,height=10pt,borderwidth=1pt,bordercolor={0.4 0.4 0.4}]{} ");
+                s.push_str(&check_box(&checkbox_name, options));
+                s.push(' ');
                 s.push_str(&escape_latex(&variant.name));
+                s.push_str(&variant_hint(variant));
                 s.push_str("\n\n");
                 s.push_str(&indent);
                 s.push_str("\\vspace{0.3em}\n");
@@ -254,7 +1031,7 @@ fn render_question_with_path(
             // Render follow-up fields for variants that have nested questions
             for variant in &anyof.variants {
                 if !matches!(variant.kind, elicitor::QuestionKind::Unit) {
-                    s.push_str("\n");
+                    s.push('\n');
                     s.push_str(&indent);
                     s.push_str("\\vspace{0.5em}\n");
                     s.push_str(&indent);
@@ -270,6 +1047,7 @@ fn render_question_with_path(
                         &variant.kind,
                         &full_path,
                         indent_level + 1,
+                        options,
                     ));
                     s.push_str(&indent);
                     s.push_str("\\end{shadedblock}\n");
@@ -283,47 +1061,82 @@ fn render_question_with_path(
             } else {
                 Some(full_path.as_str())
             };
-            s.push_str(&indent);
-            s.push_str(&format!(
-                "\\begin{{shadedblock}}[{}]\n",
-                shade_percent(indent_level + 1)
-            ));
-            for (i, sub) in allof.questions.iter().enumerate() {
-                if i > 0 {
-                    s.push_str("\n");
-                    s.push_str(&indent);
-                    s.push_str("\\vspace{0.8em}\n");
+            if section.is_some() {
+                for (i, sub) in allof.questions.iter().enumerate() {
+                    if i > 0 {
+                        s.push_str("\n\\vspace{1em}\n");
+                    }
+                    s.push_str(&render_question_with_path(
+                        sub,
+                        indent_level,
+                        parent,
+                        section_level + 1,
+                        options,
+                    ));
                 }
-                s.push_str(&render_question_with_path(sub, indent_level + 1, parent));
+            } else {
+                s.push_str(&indent);
+                s.push_str(&format!(
+                    "\\begin{{shadedblock}}[{}]\n",
+                    shade_percent(indent_level + 1)
+                ));
+                for (i, sub) in allof.questions.iter().enumerate() {
+                    if i > 0 {
+                        s.push('\n');
+                        s.push_str(&indent);
+                        s.push_str("\\vspace{0.8em}\n");
+                    }
+                    s.push_str(&render_question_with_path(
+                        sub,
+                        indent_level + 1,
+                        parent,
+                        section_level,
+                        options,
+                    ));
+                }
+                s.push_str(&indent);
+                s.push_str("\\end{shadedblock}\n");
             }
-            s.push_str(&indent);
-            s.push_str("\\end{shadedblock}\n");
         }
         QuestionKind::Multiline(_) => {
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str(",multiline=true,width=4in,height=1.2in,bordercolor={0.5 0.5 0.5}]{}\n\n");
+            s.push_str("\\noindent");
+            s.push_str(&multiline_field(
+                &field_name,
+                &options.field_widths.text,
+                options,
+            ));
+            s.push_str("\n\n");
         }
         QuestionKind::Unit => {
             // No input needed for unit types
         }
         QuestionKind::Masked(_) => {
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str(",password=true,width=3in,bordercolor={0.5 0.5 0.5}]{}\n\n");
+            s.push_str("\\noindent");
+            s.push_str(&masked_field(
+                &field_name,
+                &options.field_widths.short,
+                options,
+            ));
+            s.push_str("\n\n");
         }
-        QuestionKind::List(_) => {
-            s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str(
-                ",width=4in,bordercolor={0.5 0.5 0.5}]{} \\textit{\\small(comma-separated)}\n\n",
-            );
+        QuestionKind::List(list_q) => {
+            s.push_str(&list_fields(&field_name, list_q, options, &indent));
         }
     }
 
+    if q.allows_comment() {
+        s.push_str(&indent);
+        s.push_str("\\noindent\\textit{\\small Comment:}~");
+        s.push_str(&text_field(
+            &sanitize_field_name(&format!("{}.{}", full_path, elicitor::COMMENT_KEY)),
+            &options.field_widths.text,
+            options,
+        ));
+        s.push_str("\n\n");
+    }
+
     s
 }
 
@@ -332,6 +1145,7 @@ fn render_variant_fields(
     kind: &elicitor::QuestionKind,
     parent_path: &str,
     indent_level: usize,
+    options: &LatexOptions,
 ) -> String {
     use elicitor::QuestionKind;
 
@@ -345,9 +1159,12 @@ fn render_variant_fields(
         QuestionKind::Input(input_q) => {
             let field_name = sanitize_field_name(parent_path);
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str("-value,width=4in,bordercolor={0.5 0.5 0.5}]{}");
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &format!("{field_name}-value"),
+                &options.field_widths.text,
+                options,
+            ));
             if let Some(default) = &input_q.default {
                 s.push_str(" \\textit{\\small(default: ");
                 s.push_str(&escape_latex(default));
@@ -355,18 +1172,66 @@ fn render_variant_fields(
             }
             s.push_str("\n\n");
         }
+        QuestionKind::Autocomplete(autocomplete_q) => {
+            let field_name = sanitize_field_name(parent_path);
+            s.push_str(&indent);
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &format!("{field_name}-value"),
+                &options.field_widths.text,
+                options,
+            ));
+            s.push_str(&suggestions_hint(autocomplete_q));
+            s.push_str("\n\n");
+        }
+        QuestionKind::Currency(currency_q) => {
+            let field_name = sanitize_field_name(parent_path);
+            s.push_str(&indent);
+            s.push_str(&currency_field(
+                &format!("{field_name}-value"),
+                currency_q,
+                options,
+            ));
+            s.push_str("\n\n");
+        }
+        QuestionKind::Color(_) => {
+            let field_name = sanitize_field_name(parent_path);
+            s.push_str(&indent);
+            s.push_str("\\noindent\\#");
+            s.push_str(&color_field(&format!("{field_name}-value"), options));
+            s.push_str(" \\textit{\\small(hex, e.g. 1e90ff)}\n\n");
+        }
+        QuestionKind::Path(path_q) => {
+            let field_name = sanitize_field_name(parent_path);
+            s.push_str(&indent);
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &format!("{field_name}-value"),
+                &options.field_widths.text,
+                options,
+            ));
+            if let Some(hint) = path_q.hint() {
+                s.push_str(&format!(" \\textit{{\\small({})}}", escape_latex(&hint)));
+            }
+            s.push_str("\n\n");
+        }
         QuestionKind::Int(int_q) => {
             let field_name = sanitize_field_name(parent_path);
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str("-value,width=1.5in,bordercolor={0.5 0.5 0.5}]{}");
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &format!("{field_name}-value"),
+                &number_width(int_q.min, int_q.max, options),
+                options,
+            ));
             if int_q.min.is_some() || int_q.max.is_some() {
                 s.push_str(" \\textit{\\small(");
                 match (int_q.min, int_q.max) {
-                    (Some(min), Some(max)) => s.push_str(&format!("{} -- {}", min, max)),
-                    (Some(min), None) => s.push_str(&format!("min: {}", min)),
-                    (None, Some(max)) => s.push_str(&format!("max: {}", max)),
+                    (Some(min), Some(max)) => {
+                        s.push_str(&format!("{} -- {}", int_q.format(min), int_q.format(max)))
+                    }
+                    (Some(min), None) => s.push_str(&format!("min: {}", int_q.format(min))),
+                    (None, Some(max)) => s.push_str(&format!("max: {}", int_q.format(max))),
                     (None, None) => {}
                 }
                 s.push_str(")}");
@@ -376,38 +1241,70 @@ fn render_variant_fields(
         QuestionKind::Float(float_q) => {
             let field_name = sanitize_field_name(parent_path);
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str("-value,width=1.5in,bordercolor={0.5 0.5 0.5}]{}");
-            if float_q.min.is_some() || float_q.max.is_some() {
-                s.push_str(" \\textit{\\small(");
-                match (float_q.min, float_q.max) {
-                    (Some(min), Some(max)) => s.push_str(&format!("{} -- {}", min, max)),
-                    (Some(min), None) => s.push_str(&format!("min: {}", min)),
-                    (None, Some(max)) => s.push_str(&format!("max: {}", max)),
-                    (None, None) => {}
-                }
-                s.push_str(")}");
-            }
+            s.push_str("\\noindent");
+            s.push_str(&text_field(
+                &format!("{field_name}-value"),
+                &number_width(float_q.min, float_q.max, options),
+                options,
+            ));
+            s.push_str(&float_hint(float_q));
+            s.push_str("\n\n");
+        }
+        QuestionKind::Rating(rating_q) => {
+            let field_name = sanitize_field_name(parent_path);
+            s.push_str(&indent);
+            s.push_str(&rating_scale(
+                &format!("{}-value", field_name),
+                rating_q,
+                options,
+            ));
+            s.push_str("\n\n");
+        }
+        QuestionKind::Matrix(matrix_q) => {
+            let field_name = sanitize_field_name(parent_path);
+            s.push_str(&indent);
+            s.push_str(&matrix_table(
+                &format!("{}-value", field_name),
+                matrix_q,
+                options,
+            ));
             s.push_str("\n\n");
         }
         QuestionKind::Confirm(_) => {
             let field_name = sanitize_field_name(parent_path);
             s.push_str(&indent);
-            s.push_str("\\noindent\\CheckBox[name=");
-            s.push_str(&field_name);
-            s.push_str(
-                "-value,width=10pt,height=10pt,borderwidth=1pt,bordercolor={0.4 0.4 0.4}]{} Yes\n\n",
-            );
+            s.push_str("\\noindent");
+            s.push_str(&check_box(&format!("{field_name}-value"), options));
+            s.push_str(" Yes\n\n");
+        }
+        QuestionKind::Select(select_q) => {
+            let field_name = sanitize_field_name(parent_path);
+            s.push_str(&indent);
+            let choices: Vec<String> = select_q
+                .options
+                .iter()
+                .map(|o| escape_choice(&o.label))
+                .collect();
+            s.push_str("\\noindent");
+            s.push_str(&choice_menu(
+                "combo",
+                &format!("{field_name}-value"),
+                Some(&options.field_widths.short),
+                &choices,
+                options,
+            ));
+            s.push_str("\n\n");
         }
         QuestionKind::Multiline(_) => {
             let field_name = sanitize_field_name(parent_path);
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str(
-                "-value,multiline=true,width=4in,height=1.2in,bordercolor={0.5 0.5 0.5}]{}\n\n",
-            );
+            s.push_str("\\noindent");
+            s.push_str(&multiline_field(
+                &format!("{field_name}-value"),
+                &options.field_widths.text,
+                options,
+            ));
+            s.push_str("\n\n");
         }
         QuestionKind::AllOf(allof) => {
             // Struct variant - render all nested questions
@@ -420,6 +1317,8 @@ fn render_variant_fields(
                     sub,
                     indent_level,
                     Some(parent_path),
+                    0,
+                    options,
                 ));
             }
         }
@@ -427,21 +1326,26 @@ fn render_variant_fields(
             // Nested enum - render as choice menu with its own follow-ups
             let field_name = sanitize_field_name(parent_path);
             s.push_str(&indent);
-            s.push_str("\\noindent\\ChoiceMenu[combo,name=");
-            s.push_str(&field_name);
-            s.push_str("-value,width=3in,bordercolor={0.5 0.5 0.5}]{}{");
-            let options: Vec<String> = oneof
+            let choices: Vec<String> = oneof
                 .variants
                 .iter()
-                .map(|v| escape_latex(&v.name))
+                .map(|v| escape_choice(&v.name))
                 .collect();
-            s.push_str(&options.join(","));
-            s.push_str("}\n");
+            s.push_str("\\noindent");
+            s.push_str(&choice_menu(
+                "combo",
+                &format!("{field_name}-value"),
+                Some(&options.field_widths.short),
+                &choices,
+                options,
+            ));
+            s.push('\n');
+            s.push_str(&variant_descriptions(&oneof.variants, &indent));
 
             // Recursively render nested variant fields
             for variant in &oneof.variants {
                 if !matches!(variant.kind, QuestionKind::Unit) {
-                    s.push_str("\n");
+                    s.push('\n');
                     s.push_str(&indent);
                     s.push_str("\\vspace{0.3em}\n");
                     s.push_str(&indent);
@@ -459,6 +1363,7 @@ fn render_variant_fields(
                         &variant.kind,
                         &nested_path,
                         indent_level + 1,
+                        options,
                     ));
                     s.push_str(&indent);
                     s.push_str("\\end{shadedblock}\n");
@@ -474,10 +1379,10 @@ fn render_variant_fields(
                     sanitize_field_name(&variant.name)
                 );
                 s.push_str(&indent);
-                s.push_str("\\CheckBox[name=");
-                s.push_str(&checkbox_name);
-                s.push_str(",width=10pt,height=10pt,borderwidth=1pt,bordercolor={0.4 0.4 0.4}]{} ");
+                s.push_str(&check_box(&checkbox_name, options));
+                s.push(' ');
                 s.push_str(&escape_latex(&variant.name));
+                s.push_str(&variant_hint(variant));
                 s.push_str("\n\n");
                 s.push_str(&indent);
                 s.push_str("\\vspace{0.3em}\n");
@@ -486,18 +1391,345 @@ fn render_variant_fields(
         QuestionKind::Masked(_) => {
             let field_name = sanitize_field_name(parent_path);
             s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str("-value,password=true,width=3in,bordercolor={0.5 0.5 0.5}]{}\n");
+            s.push_str("\\noindent");
+            s.push_str(&masked_field(
+                &format!("{field_name}-value"),
+                &options.field_widths.short,
+                options,
+            ));
+            s.push('\n');
         }
-        QuestionKind::List(_) => {
-            let field_name = sanitize_field_name(parent_path);
-            s.push_str(&indent);
-            s.push_str("\\noindent\\TextField[name=");
-            s.push_str(&field_name);
-            s.push_str("-value,width=4in,bordercolor={0.5 0.5 0.5}]{} \\textit{\\small(comma-separated)}\n");
+        QuestionKind::List(list_q) => {
+            let field_name = format!("{}-value", sanitize_field_name(parent_path));
+            s.push_str(&list_fields(&field_name, list_q, options, &indent));
         }
     }
 
     s
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{
+        AllOfQuestion, AnyOfQuestion, AutocompleteQuestion, ColorQuestion, ConfirmQuestion,
+        CurrencyQuestion, FloatQuestion, InputQuestion, IntQuestion, ListQuestion, MaskedQuestion,
+        MatrixQuestion, MultilineQuestion, OneOfQuestion, PathQuestion, Question, QuestionKind,
+        RatingQuestion, SelectOption, SelectQuestion, Variant,
+    };
+
+    const PROMPT: &str = r"50% of $5 & #1_{a}~^\ ok?";
+    const ESCAPED: &str =
+        r"50\% of \$5 \& \#1\_\{a\}\textasciitilde{}\textasciicircum{}\textbackslash{} ok?";
+
+    fn kinds() -> Vec<QuestionKind> {
+        let variants = || {
+            vec![
+                Variant::unit("a_b"),
+                Variant::new("c&d", QuestionKind::Input(InputQuestion::new())),
+            ]
+        };
+        vec![
+            QuestionKind::Unit,
+            QuestionKind::Input(InputQuestion::new()),
+            QuestionKind::Autocomplete(AutocompleteQuestion::from_list(["50%", "#1"])),
+            QuestionKind::Multiline(MultilineQuestion::new()),
+            QuestionKind::Masked(MaskedQuestion::new()),
+            QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(9))),
+            QuestionKind::Float(FloatQuestion::new()),
+            QuestionKind::Currency(CurrencyQuestion::new("USD").with_bounds(Some(0), Some(500))),
+            QuestionKind::Color(ColorQuestion::new()),
+            QuestionKind::Path(PathQuestion::new()),
+            QuestionKind::Rating(RatingQuestion::likert("100% no", "100% yes", 5)),
+            QuestionKind::Matrix(MatrixQuestion::new(
+                vec!["row_1".to_string()],
+                vec!["a, b".to_string(), "x=y".to_string()],
+            )),
+            QuestionKind::Confirm(ConfirmQuestion::new()),
+            QuestionKind::Select(SelectQuestion::new(vec![
+                SelectOption::new("a", "Tom & Jerry"),
+                SelectOption::new("b", "a, b = c"),
+            ])),
+            QuestionKind::List(ListQuestion::strings()),
+            QuestionKind::AnyOf(AnyOfQuestion::new(variants())),
+            QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                "inner",
+                PROMPT,
+                QuestionKind::Input(InputQuestion::new()),
+            )])),
+            QuestionKind::OneOf(OneOfQuestion::new(variants())),
+        ]
+    }
+
+    #[test]
+    fn escapes_every_special_character_once() {
+        assert_eq!(escape_latex(PROMPT), ESCAPED);
+        assert_eq!(escape_latex(r"\{"), r"\textbackslash{}\{");
+        assert_eq!(escape_choice("a, b = c"), "a{,} b {=} c");
+    }
+
+    #[test]
+    fn prompts_of_every_kind_are_escaped() {
+        for kind in kinds() {
+            let question = Question::new("field_name", PROMPT, kind.clone());
+            let latex = render_question(&question, 0, &LatexOptions::new());
+            assert!(latex.contains(ESCAPED), "{kind:?}:\n{latex}");
+            assert!(!latex.contains(PROMPT), "{kind:?}:\n{latex}");
+        }
+    }
+
+    #[test]
+    fn labels_are_escaped() {
+        let latex = to_latex_form(
+            &SurveyDefinition::new(
+                kinds()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, kind)| Question::new(format!("q{i}"), "", kind))
+                    .collect(),
+            )
+            .with_prelude("Costs: 5$ & more")
+            .with_epilogue("100% done_"),
+        );
+        for expected in [
+            r"Costs: 5\$ \& more",
+            r"100\% done\_",
+            r"Tom \& Jerry,a{,} b {=} c",
+            r"a\_b,c\&d",
+            r"\textit{\small 100\% no}",
+            r"row\_1 & ",
+            r"{a{,} b,x{=}y}",
+            r"(e.g. \#1, 50\%)",
+            r"\CheckBox[name=q15-c-d,",
+        ] {
+            assert!(latex.contains(expected), "missing {expected}:\n{latex}");
+        }
+    }
+
+    #[test]
+    fn floats_and_lists_get_fields() {
+        let render = |kind, options: &LatexOptions| {
+            render_question(&Question::new("values", "Values:", kind), 0, options)
+        };
+        let defaults = LatexOptions::new();
+
+        let float = render(
+            QuestionKind::Float(FloatQuestion::with_bounds(Some(0.5), None)),
+            &defaults,
+        );
+        assert!(float.contains("\\TextField[name=values,"));
+        assert!(float.contains("(decimal, min: 0.5)"));
+
+        let list = render(QuestionKind::List(ListQuestion::ints()), &defaults);
+        assert!(list.contains("(whole numbers)"));
+        assert!(list.contains("3.~\\TextField[name=values-2,"));
+        assert!(!list.contains("name=values-3,"));
+
+        let mut short = ListQuestion::strings();
+        short.max_items = Some(2);
+        let list = render(
+            QuestionKind::List(short),
+            &LatexOptions::new().with_list_fields(5),
+        );
+        assert!(list.contains("name=values-1,"));
+        assert!(!list.contains("name=values-2,"));
+
+        let list = render(
+            QuestionKind::List(ListQuestion::strings()),
+            &LatexOptions::new().with_list_box(),
+        );
+        assert!(list.contains("multiline=true"));
+        assert!(list.contains("(text, one per line)"));
+    }
+
+    #[test]
+    fn variants_are_described() {
+        let variants = vec![
+            Variant::unit("Warrior").with_help("Fights up close & hits hard"),
+            Variant::unit("Mage"),
+        ];
+        let render = |kind| {
+            render_question(
+                &Question::new("role", "Role:", kind),
+                0,
+                &LatexOptions::new(),
+            )
+        };
+
+        let one_of = render(QuestionKind::OneOf(OneOfQuestion::new(variants.clone())));
+        assert!(one_of.contains("\\begin{description}"));
+        assert!(one_of.contains("\\item[{Warrior}] Fights up close \\& hits hard"));
+        assert!(!one_of.contains("\\item[{Mage}]"));
+
+        let any_of = render(QuestionKind::AnyOf(AnyOfQuestion::new(variants)));
+        assert!(any_of.contains("Warrior \\textit{\\small(Fights up close \\& hits hard)}"));
+
+        let plain = render(QuestionKind::OneOf(OneOfQuestion::new(vec![
+            Variant::unit("Mage"),
+        ])));
+        assert!(!plain.contains("description"));
+    }
+
+    #[test]
+    fn field_names_keep_underscores() {
+        assert_eq!(sanitize_field_name("first_name"), "first_name");
+        assert_eq!(sanitize_field_name("first.name"), "first-name");
+        assert_eq!(sanitize_field_name("a{b}%c"), "a-b--c");
+    }
+
+    #[test]
+    fn layout_options_shape_the_document() {
+        let survey = SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "address",
+                "Address:",
+                QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                    "city",
+                    "City:",
+                    QuestionKind::Input(InputQuestion::new()),
+                )])),
+            ),
+        ]);
+
+        let latex = to_latex_form(&survey);
+        assert!(latex.starts_with("\\documentclass[11pt]{article}"));
+        assert!(latex.contains("\\usepackage[sfdefault]{cabin}"));
+        assert!(latex.contains("\\geometry{letterpaper,margin=1in}"));
+        assert!(latex.contains("name=name,width=4in,"));
+        assert!(!latex.contains("\\clearpage"));
+
+        let options = LatexOptions::new()
+            .with_paper_size(PaperSize::A4)
+            .with_margin("2cm")
+            .with_font_size(12)
+            .with_font("lmodern")
+            .with_title("R&D sign-up")
+            .with_two_columns()
+            .with_field_widths(FieldWidths {
+                text: "\\linewidth".to_string(),
+                ..FieldWidths::default()
+            })
+            .with_preamble("\\usepackage{fancyhdr}")
+            .with_section_page_breaks();
+        let latex = to_latex_form_with_options(&survey, &options);
+        assert!(latex.starts_with("\\documentclass[12pt,twocolumn]{article}"));
+        assert!(latex.contains("\\usepackage{lmodern}"));
+        assert!(!latex.contains("cabin"));
+        assert!(latex.contains("\\geometry{a4paper,margin=2cm}"));
+        assert!(latex.contains("pdftitle={R\\&D sign-up}"));
+        assert!(latex.contains("\\LARGE\\bfseries R\\&D sign-up}"));
+        assert!(latex.contains("\\usepackage{fancyhdr}\n\n\n\\begin{document}"));
+        assert!(latex.contains("name=name,width=\\linewidth,"));
+        assert!(latex.contains("\\clearpage\n\\noindent\\textbf{Address:}"));
+    }
+
+    #[test]
+    fn nested_questions_become_numbered_sections() {
+        let input = |name| Question::new(name, "Field:", QuestionKind::Input(InputQuestion::new()));
+        let group = |name, ask, questions| {
+            Question::new(
+                name,
+                ask,
+                QuestionKind::AllOf(AllOfQuestion::new(questions)),
+            )
+        };
+        let survey = SurveyDefinition::new(vec![group(
+            "applicant",
+            "Applicant & co:",
+            vec![
+                input("name"),
+                group(
+                    "address",
+                    "Address",
+                    vec![group(
+                        "region",
+                        "Region",
+                        vec![group("detail", "Detail", vec![input("zip")])],
+                    )],
+                ),
+            ],
+        )]);
+
+        let plain = to_latex_form(&survey);
+        assert!(!plain.contains("\\section"));
+        assert!(!plain.contains("\\tableofcontents"));
+
+        let latex =
+            to_latex_form_with_options(&survey, &LatexOptions::new().with_table_of_contents());
+        assert!(latex.contains("\\tableofcontents"));
+        assert!(latex.contains("\\section{Applicant \\& co}"));
+        assert!(latex.contains("\\subsection{Address}"));
+        assert!(latex.contains("\\subsubsection{Region}"));
+        assert!(latex.contains("\\textbf{Detail}"));
+        assert!(latex.contains("name=applicant-name,"));
+        assert!(latex.contains("name=applicant-address-region-detail-zip,"));
+        assert_eq!(latex.matches("\\begin{shadedblock}").count(), 1);
+    }
+
+    #[test]
+    fn print_mode_draws_lines_boxes_and_circles() {
+        let survey = SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "age",
+                "Age:",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new(
+                "bio",
+                "Bio:",
+                QuestionKind::Multiline(MultilineQuestion::new()),
+            ),
+            Question::new("ok", "OK?", QuestionKind::Confirm(ConfirmQuestion::new())),
+            Question::new(
+                "size",
+                "Size:",
+                QuestionKind::Select(SelectQuestion::new(vec![
+                    SelectOption::new("s", "Small"),
+                    SelectOption::new("l", "Large"),
+                ])),
+            ),
+        ]);
+        let latex = to_latex_form_with_options(&survey, &LatexOptions::new().with_print_mode());
+
+        assert!(latex.contains("\\usepackage{amssymb}"));
+        assert!(!latex.contains("\\begin{Form}"));
+        assert!(!latex.contains("\\TextField"));
+        assert!(!latex.contains("\\CheckBox"));
+        assert!(!latex.contains("\\ChoiceMenu"));
+        assert!(latex.contains("\\rule{5em}{0.4pt}"));
+        assert_eq!(latex.matches("\\rule{4in}{0.4pt}").count(), 5);
+        assert!(latex.contains("$\\square$"));
+        assert!(latex.contains("$\\bigcirc$~Small \\quad $\\bigcirc$~Large"));
+    }
+
+    #[test]
+    fn qr_codes_link_to_the_online_form() {
+        let survey = SurveyDefinition::new(vec![Question::new(
+            "name",
+            "Name:",
+            QuestionKind::Input(InputQuestion::new()),
+        )]);
+        let link = FormLink::with_id("https://example.com/apply?a=1", "AB_C");
+
+        let latex = to_latex_form(&survey);
+        assert!(!latex.contains("qrcode"));
+
+        let latex =
+            to_latex_form_with_options(&survey, &LatexOptions::new().with_qr_code(link.clone()));
+        assert!(latex.contains("\\usepackage{qrcode}"));
+        assert!(latex.contains(
+            "\\fancyhead[R]{{\\footnotesize Form ID: \\texttt{AB\\_C}}\\quad\\qrcode[height=1.2cm]{https://example.com/apply?a=1\\&form=AB\\_C}}"
+        ));
+
+        let options = LatexOptions::new()
+            .with_qr_code(FormLink::new("https://example.com"))
+            .with_qr_position(QrPosition::Footer);
+        let latex = to_latex_form_with_options(&survey, &options);
+        assert!(latex.contains(
+            "\\fancyfoot[R]{\\raisebox{-0.5\\height}{\\qrcode[height=1.2cm]{https://example.com}}}"
+        ));
+        assert!(!latex.contains("\\fancyhead[R]"));
+    }
+}
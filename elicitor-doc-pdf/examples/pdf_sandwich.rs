@@ -0,0 +1,16 @@
+//! Sandwich Builder example - generate a fillable PDF form for ordering.
+//!
+//! Run with: cargo run -p elicitor-doc-pdf --example pdf_sandwich
+
+use elicitor_doc_pdf::{PdfOptions, to_pdf_form_with_options};
+use example_surveys::SandwichOrder;
+
+fn main() {
+    let options = PdfOptions::new().with_title("Rusty's Subs - Order Form");
+
+    let pdf = to_pdf_form_with_options::<SandwichOrder>(options);
+
+    std::fs::write("sandwich.pdf", &pdf).expect("Failed to write PDF file");
+
+    println!("Generated sandwich.pdf");
+}
@@ -0,0 +1,1002 @@
+//! PDF form generator implementation.
+
+use std::collections::HashMap;
+
+use elicitor::{
+    DefaultValue, FieldHandling, FormLink, ListElementKind, Question, QuestionKind, ResponsePath,
+    ResponseValue, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SensitiveField, SensitiveSink,
+    Survey, SurveyDefinition,
+};
+
+use qrcode::{Color, QrCode};
+
+use crate::writer::{PdfWriter, content_string, stream, text_string};
+
+const MARGIN: f32 = 56.0;
+const INDENT: f32 = 14.0;
+const LABEL_SIZE: f32 = 10.0;
+const LEADING: f32 = 13.0;
+const FIELD_HEIGHT: f32 = 18.0;
+const MULTILINE_HEIGHT: f32 = 54.0;
+const CHECKBOX_SIZE: f32 = 11.0;
+const GAP: f32 = 8.0;
+const QR_SIZE: f32 = 44.0;
+
+/// Paper size of the generated document.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum PageSize {
+    /// ISO A4, 210 × 297 mm.
+    #[default]
+    A4,
+    /// US Letter, 8.5 × 11 in.
+    Letter,
+}
+
+impl PageSize {
+    /// Width and height in points.
+    pub fn dimensions(self) -> (f32, f32) {
+        match self {
+            Self::A4 => (595.0, 842.0),
+            Self::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+/// Where the QR code of a [`FormLink`] is printed on every page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QrPosition {
+    /// Top right corner, in the margin above the form.
+    #[default]
+    Header,
+    /// Bottom right corner, in the margin below the form.
+    Footer,
+}
+
+/// Options for PDF generation.
+#[derive(Debug, Clone, Default)]
+pub struct PdfOptions {
+    /// Title shown at the top of the first page and in the document metadata.
+    pub title: Option<String>,
+    /// Paper size.
+    pub page_size: PageSize,
+    /// Link to the online version of the form, printed as a QR code on every page.
+    pub qr_code: Option<FormLink>,
+    /// Where the QR code is printed.
+    pub qr_position: QrPosition,
+}
+
+impl PdfOptions {
+    /// Create new options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the document title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the paper size.
+    pub fn with_page_size(mut self, page_size: PageSize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Print a QR code linking to the online version of the form on every page,
+    /// together with the form ID if the link has one.
+    ///
+    /// URLs too long for a QR code (over 2000 characters or so) are left out.
+    pub fn with_qr_code(mut self, link: FormLink) -> Self {
+        self.qr_code = Some(link);
+        self
+    }
+
+    /// Print the QR code in the header or the footer.
+    pub fn with_qr_position(mut self, position: QrPosition) -> Self {
+        self.qr_position = position;
+        self
+    }
+}
+
+/// Generated forms never pre-fill masked fields.
+impl SensitiveSink for PdfOptions {
+    fn sink_name(&self) -> String {
+        "pdf form".to_string()
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        FieldHandling::Omitted
+    }
+}
+
+/// Generate a fillable PDF form from a survey type.
+///
+/// This is a convenience function that uses default options with the given title.
+pub fn to_pdf_form<T: Survey>(title: Option<&str>) -> Vec<u8> {
+    let mut options = PdfOptions::new();
+    if let Some(t) = title {
+        options.title = Some(t.to_string());
+    }
+    to_pdf_form_with_options::<T>(options)
+}
+
+/// Generate a fillable PDF form with custom options.
+pub fn to_pdf_form_with_options<T: Survey>(options: PdfOptions) -> Vec<u8> {
+    definition_to_pdf_form(&T::survey(), &options)
+}
+
+/// Generate a fillable PDF form from a survey definition, e.g. of a `DynamicSurvey`.
+pub fn definition_to_pdf_form(definition: &SurveyDefinition, options: &PdfOptions) -> Vec<u8> {
+    let mut layout = Layout::new(options.page_size);
+
+    if let Some(title) = &options.title {
+        layout.paragraph(title, Font::Bold, 16.0);
+        layout.space(GAP);
+    }
+    if let Some(prelude) = &definition.prelude {
+        layout.paragraph(prelude, Font::Regular, LABEL_SIZE);
+        layout.space(GAP);
+    }
+    for question in definition.questions() {
+        layout.question(question, &ResponsePath::empty());
+    }
+    if let Some(epilogue) = &definition.epilogue {
+        layout.space(GAP);
+        layout.paragraph(epilogue, Font::Regular, LABEL_SIZE);
+    }
+    if let Some(link) = &options.qr_code {
+        let ops = qr_code(link, options.qr_position, layout.width, layout.height);
+        for page in &mut layout.pages {
+            page.push_str(&ops);
+        }
+    }
+
+    write_pdf(layout, options)
+}
+
+/// Page content drawing the QR code of `link` at the right of the header or footer
+/// margin, with the form ID to its left.
+fn qr_code(link: &FormLink, position: QrPosition, width: f32, height: f32) -> String {
+    let x = width - MARGIN - QR_SIZE;
+    let y = match position {
+        QrPosition::Header => height - (MARGIN + QR_SIZE) / 2.0,
+        QrPosition::Footer => (MARGIN - QR_SIZE) / 2.0,
+    };
+
+    let mut ops = String::new();
+    if let Ok(code) = QrCode::new(link.url()) {
+        let modules = code.width();
+        let module = QR_SIZE / modules as f32;
+        ops.push_str("0 g\n");
+        for row in 0..modules {
+            for col in 0..modules {
+                if code[(col, row)] == Color::Dark {
+                    ops.push_str(&format!(
+                        "{:.3} {:.3} {module:.3} {module:.3} re\n",
+                        x + col as f32 * module,
+                        y + QR_SIZE - (row + 1) as f32 * module
+                    ));
+                }
+            }
+        }
+        ops.push_str("f\n");
+    }
+    if let Some(id) = link.id() {
+        let label = format!("Form ID: {id}");
+        // Helvetica averages about half an em per character
+        let text_width = label.chars().count() as f32 * 8.0 * 0.55;
+        ops.push_str(&format!(
+            "BT /Helv 8 Tf {:.1} {:.1} Td {} Tj ET\n",
+            x - GAP - text_width,
+            y + QR_SIZE / 2.0 - 3.0,
+            content_string(&label)
+        ));
+    }
+    ops
+}
+
+/// An interactive field of the form.
+struct Field {
+    path: ResponsePath,
+    page: usize,
+    rect: [f32; 4],
+    widget: Widget,
+}
+
+enum Widget {
+    Text {
+        value: Option<String>,
+        multiline: bool,
+        password: bool,
+    },
+    Checkbox {
+        checked: bool,
+    },
+    Combo {
+        options: Vec<(String, String)>,
+        value: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Self::Regular => "/Helv",
+            Self::Bold => "/HeBo",
+        }
+    }
+}
+
+/// Places text and fields top to bottom, starting a new page when one is full.
+struct Layout {
+    width: f32,
+    height: f32,
+    pages: Vec<String>,
+    fields: Vec<Field>,
+    y: f32,
+    depth: usize,
+}
+
+impl Layout {
+    fn new(page_size: PageSize) -> Self {
+        let (width, height) = page_size.dimensions();
+        Self {
+            width,
+            height,
+            pages: vec![String::new()],
+            fields: Vec::new(),
+            y: height - MARGIN,
+            depth: 0,
+        }
+    }
+
+    fn left(&self) -> f32 {
+        MARGIN + INDENT * self.depth as f32
+    }
+
+    fn right(&self) -> f32 {
+        self.width - MARGIN
+    }
+
+    fn content(&mut self) -> &mut String {
+        self.pages.last_mut().expect("layout has a page")
+    }
+
+    /// Start a new page unless `height` fits on the current one.
+    fn reserve(&mut self, height: f32) {
+        if self.y - height < MARGIN && self.y < self.height - MARGIN {
+            self.pages.push(String::new());
+            self.y = self.height - MARGIN;
+        }
+    }
+
+    fn space(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    fn line(&mut self, text: &str, font: Font, size: f32, x: f32) {
+        let leading = size * 1.3;
+        self.reserve(leading);
+        self.y -= leading;
+        let y = self.y + size * 0.25;
+        let op = format!(
+            "BT {} {size} Tf {x:.1} {y:.1} Td {} Tj ET\n",
+            font.resource(),
+            content_string(text)
+        );
+        self.content().push_str(&op);
+    }
+
+    fn paragraph(&mut self, text: &str, font: Font, size: f32) {
+        // Helvetica averages about half an em per character
+        let max_chars = ((self.right() - self.left()) / (size * 0.5)) as usize;
+        for line in wrap(text, max_chars) {
+            let x = self.left();
+            self.line(&line, font, size, x);
+        }
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.space(GAP / 2.0);
+        self.paragraph(text, Font::Bold, 12.0);
+    }
+
+    /// A label above a field spanning the width of the page.
+    fn labeled(&mut self, label: &str, path: ResponsePath, height: f32, widget: Widget) {
+        // Keep the label on the same page as its field
+        self.reserve(LABEL_SIZE * 1.3 + height);
+        self.paragraph(label, Font::Regular, LABEL_SIZE);
+        self.reserve(height);
+        let (left, right) = (self.left(), self.right());
+        self.y -= height;
+        let rect = [left, self.y, right, self.y + height];
+        self.border(rect);
+        self.push_field(path, rect, widget);
+        self.space(GAP);
+    }
+
+    /// A checkbox with its label to the right.
+    fn checkbox(&mut self, label: &str, path: ResponsePath, checked: bool) {
+        self.reserve(LEADING);
+        self.y -= LEADING;
+        let left = self.left();
+        let rect = [left, self.y, left + CHECKBOX_SIZE, self.y + CHECKBOX_SIZE];
+        self.border(rect);
+        let op = format!(
+            "BT /Helv {LABEL_SIZE} Tf {:.1} {:.1} Td {} Tj ET\n",
+            left + CHECKBOX_SIZE + 6.0,
+            self.y + 2.0,
+            content_string(label)
+        );
+        self.content().push_str(&op);
+        self.push_field(path, rect, Widget::Checkbox { checked });
+        self.space(GAP / 2.0);
+    }
+
+    /// Draw the outline of a field so printed forms show it too.
+    fn border(&mut self, [x1, y1, x2, y2]: [f32; 4]) {
+        let op = format!(
+            "0.6 G 0.5 w {x1:.1} {y1:.1} {:.1} {:.1} re S 0 G\n",
+            x2 - x1,
+            y2 - y1
+        );
+        self.content().push_str(&op);
+    }
+
+    fn push_field(&mut self, path: ResponsePath, rect: [f32; 4], widget: Widget) {
+        self.fields.push(Field {
+            path,
+            page: self.pages.len() - 1,
+            rect,
+            widget,
+        });
+    }
+
+    fn question(&mut self, question: &Question, prefix: &ResponsePath) {
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+
+        // Assumed fields are not part of the form
+        let suggested = match question.default() {
+            DefaultValue::Assumed(_) => return,
+            DefaultValue::Suggested(value) => Some(value),
+            DefaultValue::None => None,
+        };
+        let label = format_label(question.ask(), path.as_str());
+
+        match question.kind() {
+            QuestionKind::Unit => {}
+
+            QuestionKind::Input(input_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_str)
+                    .map(str::to_string)
+                    .or_else(|| input_q.default.clone());
+                self.text(&label, path, value);
+            }
+
+            QuestionKind::Autocomplete(autocomplete_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_str)
+                    .map(str::to_string)
+                    .or_else(|| autocomplete_q.default.clone());
+                self.text(&label, path, value);
+            }
+
+            QuestionKind::Multiline(multiline_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_str)
+                    .map(str::to_string)
+                    .or_else(|| multiline_q.default.clone());
+                let widget = Widget::Text {
+                    value,
+                    multiline: true,
+                    password: false,
+                };
+                self.labeled(&label, path, MULTILINE_HEIGHT, widget);
+            }
+
+            QuestionKind::Masked(_) => {
+                // Don't pre-fill password fields for security
+                let widget = Widget::Text {
+                    value: None,
+                    multiline: false,
+                    password: true,
+                };
+                self.labeled(&label, path, FIELD_HEIGHT, widget);
+            }
+
+            QuestionKind::Int(int_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_int)
+                    .or(int_q.default)
+                    .map(|i| int_q.format(i));
+                let min = int_q.min.map(|min| int_q.format(min));
+                let max = int_q.max.map(|max| int_q.format(max));
+                let label = with_bounds(&label, min, max);
+                self.text(&label, path, value);
+            }
+
+            QuestionKind::Float(float_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_float)
+                    .or(float_q.default)
+                    .map(|f| float_q.format(f));
+                let min = float_q.min.map(|min| float_q.format(min));
+                let max = float_q.max.map(|max| float_q.format(max));
+                let label = with_bounds(&label, min, max);
+                self.text(&label, path, value);
+            }
+
+            QuestionKind::Currency(currency_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_int)
+                    .or(currency_q.default)
+                    .map(|minor| currency_q.format(minor));
+                let label = format!("{label} ({})", currency_q.symbol());
+                self.text(&label, path, value);
+            }
+
+            QuestionKind::Color(color_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_str)
+                    .map(str::to_string)
+                    .or_else(|| color_q.default.map(|color| color.to_hex()));
+                self.text(&format!("{label} (#rrggbb)"), path, value);
+            }
+
+            QuestionKind::Path(path_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_str)
+                    .map(str::to_string)
+                    .or_else(|| path_q.default.clone());
+                let label = match path_q.hint() {
+                    Some(hint) => format!("{label} ({hint})"),
+                    None => label,
+                };
+                self.text(&label, path, value);
+            }
+
+            QuestionKind::List(list_q) => {
+                let hint = match &list_q.element_kind {
+                    ListElementKind::String => "comma-separated text",
+                    ListElementKind::Int { .. } => "comma-separated integers",
+                    ListElementKind::Float { .. } => "comma-separated numbers",
+                };
+                self.text(&format!("{label} ({hint})"), path, None);
+            }
+
+            QuestionKind::Confirm(confirm_q) => {
+                let checked = suggested
+                    .and_then(ResponseValue::as_bool)
+                    .unwrap_or(confirm_q.default);
+                self.checkbox(&label, path, checked);
+            }
+
+            QuestionKind::Select(select_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_str)
+                    .and_then(|v| select_q.position(v))
+                    .or(select_q.default)
+                    .and_then(|idx| select_q.options.get(idx))
+                    .map(|option| option.value.clone());
+                let options = select_q
+                    .options
+                    .iter()
+                    .map(|option| (option.value.clone(), option.label.clone()))
+                    .collect();
+                self.combo(&label, path, options, value);
+            }
+
+            QuestionKind::Rating(rating_q) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_int)
+                    .or(rating_q.default)
+                    .map(|i| i.to_string());
+                let options = rating_q
+                    .points()
+                    .map(|point| (point.to_string(), rating_q.label(point)))
+                    .collect();
+                self.combo(&label, path, options, value);
+            }
+
+            QuestionKind::Matrix(matrix_q) => {
+                // One choice per row, stored below the question's path by row index
+                self.paragraph(&label, Font::Regular, LABEL_SIZE);
+                self.depth += 1;
+                for (row, row_label) in matrix_q.rows.iter().enumerate() {
+                    let options = matrix_q
+                        .columns
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, column)| (idx.to_string(), column.clone()))
+                        .collect();
+                    self.combo(row_label, path.child(&row.to_string()), options, None);
+                }
+                self.depth -= 1;
+            }
+
+            QuestionKind::OneOf(one_of) => {
+                let value = suggested
+                    .and_then(ResponseValue::as_chosen_variant)
+                    .or(one_of.default)
+                    .map(|idx| idx.to_string());
+                let options = one_of
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, variant)| (idx.to_string(), format_label("", &variant.name)))
+                    .collect();
+                self.combo(&label, path.child(SELECTED_VARIANT_KEY), options, value);
+
+                self.depth += 1;
+                for variant in &one_of.variants {
+                    match &variant.kind {
+                        QuestionKind::Unit => {}
+                        QuestionKind::AllOf(all_of) => {
+                            self.paragraph(
+                                &format!("If {}:", format_label("", &variant.name)),
+                                Font::Bold,
+                                LABEL_SIZE,
+                            );
+                            for nested in all_of.questions() {
+                                self.question(nested, &path);
+                            }
+                        }
+                        kind => {
+                            let label = format!("If {}:", format_label("", &variant.name));
+                            let nested = Question::new(variant.name.as_str(), label, kind.clone());
+                            self.question(&nested, &path);
+                        }
+                    }
+                }
+                self.depth -= 1;
+            }
+
+            QuestionKind::AnyOf(any_of) => {
+                let chosen = suggested
+                    .and_then(ResponseValue::as_chosen_variants)
+                    .unwrap_or(&any_of.defaults);
+                self.paragraph(&label, Font::Regular, LABEL_SIZE);
+                self.depth += 1;
+                for (idx, variant) in any_of.variants.iter().enumerate() {
+                    let variant_label = format_label("", &variant.name);
+                    self.checkbox(
+                        &variant_label,
+                        path.child(SELECTED_VARIANTS_KEY).child(&idx.to_string()),
+                        chosen.contains(&idx),
+                    );
+                    if let QuestionKind::AllOf(all_of) = &variant.kind {
+                        self.depth += 1;
+                        let item_path = path.child(&idx.to_string());
+                        for nested in all_of.questions() {
+                            self.question(nested, &item_path);
+                        }
+                        self.depth -= 1;
+                    }
+                }
+                self.depth -= 1;
+            }
+
+            QuestionKind::AllOf(all_of) => {
+                if !question.ask().is_empty() {
+                    self.heading(&label);
+                }
+                self.depth += 1;
+                for nested in all_of.questions() {
+                    self.question(nested, &path);
+                }
+                self.depth -= 1;
+            }
+        }
+    }
+
+    fn text(&mut self, label: &str, path: ResponsePath, value: Option<String>) {
+        let widget = Widget::Text {
+            value,
+            multiline: false,
+            password: false,
+        };
+        self.labeled(label, path, FIELD_HEIGHT, widget);
+    }
+
+    fn combo(
+        &mut self,
+        label: &str,
+        path: ResponsePath,
+        options: Vec<(String, String)>,
+        value: Option<String>,
+    ) {
+        self.labeled(label, path, FIELD_HEIGHT, Widget::Combo { options, value });
+    }
+}
+
+/// A node of the AcroForm field hierarchy, one per segment of a response path.
+struct Node {
+    name: String,
+    parent: Option<usize>,
+    kids: Vec<usize>,
+    fields: Vec<usize>,
+}
+
+/// Assemble the laid out form into a PDF.
+fn write_pdf(layout: Layout, options: &PdfOptions) -> Vec<u8> {
+    let Layout {
+        width,
+        height,
+        pages,
+        fields,
+        ..
+    } = layout;
+
+    // Field names may not contain periods, so response paths become a hierarchy
+    // whose fully qualified names are the paths again
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut lookup: HashMap<(Option<usize>, String), usize> = HashMap::new();
+    for (field_idx, field) in fields.iter().enumerate() {
+        let mut parent = None;
+        for segment in field.path.segments() {
+            let key = (parent, segment.to_string());
+            let node = *lookup.entry(key).or_insert_with(|| {
+                nodes.push(Node {
+                    name: segment.to_string(),
+                    parent,
+                    kids: Vec::new(),
+                    fields: Vec::new(),
+                });
+                let idx = nodes.len() - 1;
+                if let Some(parent) = parent {
+                    nodes[parent].kids.push(idx);
+                }
+                idx
+            });
+            parent = Some(node);
+        }
+        if let Some(node) = parent {
+            nodes[node].fields.push(field_idx);
+        }
+    }
+
+    let mut pdf = PdfWriter::new();
+    let catalog = pdf.reserve();
+    let pages_id = pdf.reserve();
+    let helv = pdf
+        .add("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>");
+    let hebo = pdf.add(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>",
+    );
+    let zadb = pdf.add("<< /Type /Font /Subtype /Type1 /BaseFont /ZapfDingbats >>");
+    let check_bbox = format!("/BBox [0 0 {CHECKBOX_SIZE} {CHECKBOX_SIZE}]");
+    let checked = pdf.add(stream(
+        &format!("/Type /XObject /Subtype /Form {check_bbox} /Resources << /Font << /ZaDb {zadb} 0 R >> >>"),
+        "q 0 g BT /ZaDb 9 Tf 1.5 2 Td (4) Tj ET Q",
+    ));
+    let unchecked = pdf.add(stream(
+        &format!("/Type /XObject /Subtype /Form {check_bbox}"),
+        "",
+    ));
+    let title = options.title.as_deref().unwrap_or_default();
+    let info = pdf.add(format!(
+        "<< /Title {} /Producer (elicitor-doc-pdf) >>",
+        text_string(title)
+    ));
+
+    let page_ids: Vec<usize> = pages.iter().map(|_| pdf.reserve()).collect();
+    let node_ids: Vec<usize> = nodes.iter().map(|_| pdf.reserve()).collect();
+
+    // Fields with a single widget are merged with it, others get one annotation each
+    let mut annots = vec![Vec::new(); pages.len()];
+    for (node_idx, node) in nodes.iter().enumerate() {
+        let node_id = node_ids[node_idx];
+        let mut entries = format!("/T {}", text_string(&node.name));
+        if let Some(parent) = node.parent {
+            entries.push_str(&format!(" /Parent {} 0 R", node_ids[parent]));
+        }
+        let mut kids: Vec<usize> = node.kids.iter().map(|&kid| node_ids[kid]).collect();
+
+        if let Some(&first) = node.fields.first() {
+            entries.push_str(&field_entries(&fields[first].widget));
+        }
+        if node.fields.len() == 1 && node.kids.is_empty() {
+            let field = &fields[node.fields[0]];
+            entries.push_str(&widget_entries(
+                field,
+                page_ids[field.page],
+                checked,
+                unchecked,
+            ));
+            annots[field.page].push(node_id);
+        } else {
+            for &field_idx in &node.fields {
+                let field = &fields[field_idx];
+                let widget = pdf.add(format!(
+                    "<< /Parent {node_id} 0 R{} >>",
+                    widget_entries(field, page_ids[field.page], checked, unchecked)
+                ));
+                annots[field.page].push(widget);
+                kids.push(widget);
+            }
+        }
+
+        if !kids.is_empty() {
+            entries.push_str(&format!(" /Kids [{}]", refs(&kids)));
+        }
+        pdf.set(node_id, format!("<< {entries} >>"));
+    }
+
+    let fonts = format!("/Font << /Helv {helv} 0 R /HeBo {hebo} 0 R >>");
+    for (idx, content) in pages.iter().enumerate() {
+        let contents = pdf.add(stream("", content));
+        pdf.set(
+            page_ids[idx],
+            format!(
+                "<< /Type /Page /Parent {pages_id} 0 R /MediaBox [0 0 {width} {height}] /Resources << {fonts} >> /Contents {contents} 0 R /Annots [{}] >>",
+                refs(&annots[idx])
+            ),
+        );
+    }
+    pdf.set(
+        pages_id,
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            refs(&page_ids),
+            page_ids.len()
+        ),
+    );
+
+    let top_level: Vec<usize> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.parent.is_none())
+        .map(|(idx, _)| node_ids[idx])
+        .collect();
+    pdf.set(
+        catalog,
+        format!(
+            "<< /Type /Catalog /Pages {pages_id} 0 R /AcroForm << /Fields [{}] /NeedAppearances true /DA (/Helv 0 Tf 0 g) /DR << /Font << /Helv {helv} 0 R /ZaDb {zadb} 0 R >> >> >> >>",
+            refs(&top_level)
+        ),
+    );
+
+    pdf.finish(catalog, info)
+}
+
+/// Entries of the field dictionary: type, flags and value.
+fn field_entries(widget: &Widget) -> String {
+    match widget {
+        Widget::Text {
+            value,
+            multiline,
+            password,
+        } => {
+            let flags = if *multiline { 1 << 12 } else { 0 } | if *password { 1 << 13 } else { 0 };
+            let mut entries = String::from(" /FT /Tx /DA (/Helv 10 Tf 0 g)");
+            if flags != 0 {
+                entries.push_str(&format!(" /Ff {flags}"));
+            }
+            if let Some(value) = value {
+                entries.push_str(&format!(" /V {}", text_string(value)));
+            }
+            entries
+        }
+        Widget::Checkbox { checked } => {
+            let state = if *checked { "/Yes" } else { "/Off" };
+            format!(" /FT /Btn /V {state} /DA (/ZaDb 0 Tf 0 g)")
+        }
+        Widget::Combo { options, value } => {
+            let opts: Vec<String> = options
+                .iter()
+                .map(|(export, display)| {
+                    format!("[{} {}]", text_string(export), text_string(display))
+                })
+                .collect();
+            let mut entries = format!(
+                " /FT /Ch /Ff {} /Opt [{}] /DA (/Helv 10 Tf 0 g)",
+                1 << 17,
+                opts.join(" ")
+            );
+            if let Some(value) = value {
+                entries.push_str(&format!(" /V {}", text_string(value)));
+            }
+            entries
+        }
+    }
+}
+
+/// Entries of the widget annotation: placement, border and appearance.
+fn widget_entries(field: &Field, page: usize, checked: usize, unchecked: usize) -> String {
+    let [x1, y1, x2, y2] = field.rect;
+    let mut entries = format!(
+        " /Type /Annot /Subtype /Widget /Rect [{x1:.1} {y1:.1} {x2:.1} {y2:.1}] /P {page} 0 R /F 4 /MK << /BC [0.6 0.6 0.6] /BG [1 1 1]"
+    );
+    if let Widget::Checkbox { checked: on } = field.widget {
+        let state = if on { "/Yes" } else { "/Off" };
+        entries.push_str(&format!(
+            " /CA (4) >> /AS {state} /AP << /N << /Yes {checked} 0 R /Off {unchecked} 0 R >> >>"
+        ));
+    } else {
+        entries.push_str(" >>");
+    }
+    entries
+}
+
+fn refs(ids: &[usize]) -> String {
+    ids.iter()
+        .map(|id| format!("{id} 0 R"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Append `(min–max)` style bounds to a label.
+fn with_bounds<N: std::fmt::Display>(label: &str, min: Option<N>, max: Option<N>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("{label} ({min}–{max})"),
+        (Some(min), None) => format!("{label} (at least {min})"),
+        (None, Some(max)) => format!("{label} (at most {max})"),
+        (None, None) => label.to_string(),
+    }
+}
+
+/// Format a label from the prompt, falling back to the last path segment.
+fn format_label(ask: &str, path: &str) -> String {
+    if ask.is_empty() {
+        path.split('.')
+            .next_back()
+            .unwrap_or("")
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        ask.to_string()
+    }
+}
+
+/// Break `text` into lines of at most `max_chars` characters at word boundaries.
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{ConfirmQuestion, InputQuestion, MaskedQuestion, OneOfQuestion, Variant};
+
+    fn render(definition: &SurveyDefinition) -> String {
+        let pdf = definition_to_pdf_form(definition, &PdfOptions::new().with_title("Test"));
+        String::from_utf8_lossy(&pdf).into_owned()
+    }
+
+    #[test]
+    fn fields_are_named_by_response_path() {
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                "name",
+                "Name:",
+                QuestionKind::Input(InputQuestion::with_default("Ada")),
+            ),
+            Question::new(
+                "password",
+                "Password:",
+                QuestionKind::Masked(MaskedQuestion::new()),
+            ),
+            Question::new(
+                "newsletter",
+                "Newsletter?",
+                QuestionKind::Confirm(ConfirmQuestion::with_default(true)),
+            ),
+            Question::new(
+                "plan",
+                "Plan:",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Free"),
+                    Variant::unit("Pro"),
+                ])),
+            ),
+        ]);
+        let pdf = render(&definition);
+
+        assert!(pdf.starts_with("%PDF-1.7"));
+        assert!(pdf.contains("/T (name) /FT /Tx /DA (/Helv 10 Tf 0 g) /V (Ada)"));
+        assert!(pdf.contains("/T (password) /FT /Tx /DA (/Helv 10 Tf 0 g) /Ff 8192 /Type"));
+        assert!(pdf.contains("/T (newsletter) /FT /Btn /V /Yes"));
+        assert!(pdf.contains("/T (plan) /Kids"));
+        assert!(pdf.contains("/T (selected_variant)"));
+        assert!(pdf.contains("/Opt [[(0) (Free)] [(1) (Pro)]]"));
+        assert!(pdf.trim_end().ends_with("%%EOF"));
+    }
+
+    #[test]
+    fn long_forms_span_pages() {
+        let questions = (0..40)
+            .map(|i| {
+                Question::new(
+                    format!("q{i}").as_str(),
+                    "Question:",
+                    QuestionKind::Input(InputQuestion::new()),
+                )
+            })
+            .collect();
+        let pdf = render(&SurveyDefinition::new(questions));
+        let pages = pdf.matches("/Type /Page ").count();
+        assert!(pages > 1);
+        assert!(pdf.contains(&format!("/Count {pages}")));
+    }
+
+    #[test]
+    fn qr_codes_go_on_every_page() {
+        let questions = (0..40)
+            .map(|i| {
+                Question::new(
+                    format!("q{i}").as_str(),
+                    "Question:",
+                    QuestionKind::Input(InputQuestion::new()),
+                )
+            })
+            .collect();
+        let definition = SurveyDefinition::new(questions);
+        let options = PdfOptions::new()
+            .with_qr_code(FormLink::with_id("https://example.com/apply", "ABC123"));
+        let pdf = definition_to_pdf_form(&definition, &options);
+        let pdf = String::from_utf8_lossy(&pdf);
+
+        let pages = pdf.matches("/Type /Page ").count();
+        assert!(pages > 1);
+        assert_eq!(pdf.matches("(Form ID: ABC123) Tj").count(), pages);
+        // Dark modules are filled at once, after the last one
+        assert_eq!(pdf.matches(" re\nf\n").count(), pages);
+
+        let footer = PdfOptions::new()
+            .with_qr_code(FormLink::new("https://example.com"))
+            .with_qr_position(QrPosition::Footer);
+        let pdf = definition_to_pdf_form(&definition, &footer);
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert!(!pdf.contains("Form ID"));
+        // The first module is the top left corner of the code, in the bottom margin
+        let first = pdf.lines().find(|line| line.ends_with(" re")).unwrap();
+        let [x, y, size, _]: [f32; 4] = first
+            .split(' ')
+            .take(4)
+            .map(|n| n.parse().unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        assert_eq!(x, 595.0 - MARGIN - QR_SIZE);
+        assert!((y + size - (MARGIN + QR_SIZE) / 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn wrap_breaks_at_words() {
+        assert_eq!(wrap("one two three", 8), vec!["one two", "three"]);
+    }
+}
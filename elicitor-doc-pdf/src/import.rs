@@ -0,0 +1,614 @@
+//! Reading filled forms back into responses.
+
+use std::collections::BTreeMap;
+
+use elicitor::{
+    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, Survey, SurveyDefinition, apply_assumed,
+};
+use quick_xml::events::Event;
+
+use crate::reader::{Document, Object, decode_text};
+
+/// Error type for reading filled forms.
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("Malformed form data: {0}")]
+    Malformed(String),
+
+    #[error("Unsupported stream filter: {0}")]
+    UnsupportedFilter(String),
+
+    #[error("The document has no form")]
+    NoForm,
+
+    #[error("No answer for {0}")]
+    MissingValue(ResponsePath),
+
+    #[error("Invalid answer for {path}: {message}")]
+    InvalidValue { path: ResponsePath, message: String },
+}
+
+/// The values of a filled form, keyed by fully qualified field name.
+///
+/// Forms written by this crate name their fields after response paths, so
+/// [`to_responses`](Self::to_responses) can map the values back onto a survey.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormData {
+    fields: BTreeMap<String, String>,
+}
+
+impl FormData {
+    /// Read the field values of a filled PDF form.
+    ///
+    /// Forms saved by PDF viewers (incremental updates, compressed object streams)
+    /// are supported.
+    pub fn from_pdf(pdf: &[u8]) -> Result<Self, ImportError> {
+        Self::from_document(pdf, "AcroForm")
+    }
+
+    /// Read the field values of an FDF file, as exported by PDF viewers.
+    pub fn from_fdf(fdf: &[u8]) -> Result<Self, ImportError> {
+        Self::from_document(fdf, "FDF")
+    }
+
+    /// Read the field values of an XFDF file, the XML variant of FDF.
+    pub fn from_xfdf(xfdf: &str) -> Result<Self, ImportError> {
+        let malformed = |e: quick_xml::Error| ImportError::Malformed(e.to_string());
+        let mut reader = quick_xml::Reader::from_str(xfdf);
+        let mut names: Vec<String> = Vec::new();
+        let mut value: Option<String> = None;
+        let mut fields = BTreeMap::new();
+
+        loop {
+            match reader.read_event().map_err(malformed)? {
+                Event::Start(tag) if tag.local_name().as_ref() == b"field" => {
+                    let name = tag
+                        .try_get_attribute("name")
+                        .map_err(|e| malformed(e.into()))?
+                        .ok_or_else(|| ImportError::Malformed("field without name".to_string()))?
+                        .unescape_value()
+                        .map_err(malformed)?;
+                    names.push(name.into_owned());
+                }
+                Event::End(tag) if tag.local_name().as_ref() == b"field" => {
+                    names.pop();
+                }
+                Event::Start(tag) if tag.local_name().as_ref() == b"value" => {
+                    value = Some(String::new());
+                }
+                Event::Empty(tag) if tag.local_name().as_ref() == b"value" => {
+                    fields.entry(names.join(".")).or_insert_with(String::new);
+                }
+                Event::End(tag) if tag.local_name().as_ref() == b"value" => {
+                    // Multi-select fields list several values, keep the first
+                    if let Some(value) = value.take() {
+                        fields.entry(names.join(".")).or_insert(value);
+                    }
+                }
+                Event::Text(text) => {
+                    if let Some(value) = &mut value {
+                        value.push_str(&text.decode().map_err(|e| malformed(e.into()))?);
+                    }
+                }
+                Event::CData(data) => {
+                    if let Some(value) = &mut value {
+                        value.push_str(&data.decode().map_err(|e| malformed(e.into()))?);
+                    }
+                }
+                Event::GeneralRef(reference) => {
+                    if let Some(value) = &mut value {
+                        match reference.resolve_char_ref().map_err(malformed)? {
+                            Some(c) => value.push(c),
+                            None => {
+                                let name = reference.decode().map_err(|e| malformed(e.into()))?;
+                                let resolved = quick_xml::escape::resolve_predefined_entity(&name)
+                                    .ok_or_else(|| {
+                                        ImportError::Malformed(format!("unknown entity &{name};"))
+                                    })?;
+                                value.push_str(resolved);
+                            }
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(Self { fields })
+    }
+
+    fn from_document(data: &[u8], form_key: &str) -> Result<Self, ImportError> {
+        let document = Document::parse(data)?;
+        let form = document
+            .catalog(form_key)
+            .and_then(|catalog| catalog.get(form_key))
+            .and_then(|form| document.resolve(form).as_dict())
+            .ok_or(ImportError::NoForm)?;
+
+        let mut fields = BTreeMap::new();
+        if let Some(kids) = form
+            .get("Fields")
+            .and_then(|kids| document.resolve(kids).as_array())
+        {
+            collect_fields(&document, kids, "", 0, &mut fields);
+        }
+        Ok(Self { fields })
+    }
+
+    /// The value of the field with the fully qualified name `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+
+    /// All field values, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Map the field values onto the questions of `definition`.
+    ///
+    /// Values are converted to the question types and checked against their bounds
+    /// and choices. Assumed answers, which forms leave out, are filled in.
+    pub fn to_responses(&self, definition: &SurveyDefinition) -> Result<Responses, ImportError> {
+        let mut responses = Responses::new();
+        let root = ResponsePath::empty();
+        for question in definition.questions() {
+            self.import_question(question, &root, &root, &mut responses)?;
+        }
+        apply_assumed(definition, &mut responses);
+        Ok(responses)
+    }
+
+    /// Reconstruct a survey value from the field values.
+    ///
+    /// Answers are checked with the survey's validators, like backends do.
+    pub fn to_survey<T: Survey>(&self) -> Result<T, ImportError> {
+        let responses = self.to_responses(&T::survey())?;
+        for (path, value) in responses.iter() {
+            T::validate_field(value, &responses, path).map_err(|message| {
+                ImportError::InvalidValue {
+                    path: path.clone(),
+                    message,
+                }
+            })?;
+        }
+        Ok(T::from_responses(&responses))
+    }
+
+    /// Import `question`, whose form fields are below `field_prefix` and whose answers
+    /// go below `prefix`. The two differ for the items of multi-select questions, which
+    /// forms number by variant and responses by position.
+    fn import_question(
+        &self,
+        question: &Question,
+        field_prefix: &ResponsePath,
+        prefix: &ResponsePath,
+        responses: &mut Responses,
+    ) -> Result<(), ImportError> {
+        let (field, path) = if question.path().is_empty() {
+            (field_prefix.clone(), prefix.clone())
+        } else {
+            (
+                field_prefix.child(question.path().as_str()),
+                prefix.child(question.path().as_str()),
+            )
+        };
+        if let DefaultValue::Assumed(_) = question.default() {
+            return Ok(());
+        }
+
+        let text = self.get(field.as_str()).map(str::trim);
+        let required = || {
+            text.filter(|text| !text.is_empty())
+                .ok_or_else(|| missing(&path))
+        };
+        let value = match question.kind() {
+            QuestionKind::Unit => return Ok(()),
+
+            QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
+            | QuestionKind::Multiline(_)
+            | QuestionKind::Masked(_)
+            | QuestionKind::Path(_) => {
+                // Keep the whitespace of text answers
+                let text = self.get(field.as_str()).unwrap_or_default();
+                ResponseValue::String(text.to_string())
+            }
+
+            QuestionKind::Int(int_q) => {
+                let value = int_q
+                    .parse(required()?)
+                    .map_err(|message| invalid(&path, message))?;
+                check_bounds(value, int_q.min, int_q.max, &path)?;
+                ResponseValue::Int(value)
+            }
+
+            QuestionKind::Float(float_q) => {
+                let value = float_q
+                    .parse(required()?)
+                    .map_err(|message| invalid(&path, message))?;
+                check_bounds(value, float_q.min, float_q.max, &path)?;
+                ResponseValue::Float(value)
+            }
+
+            QuestionKind::Currency(currency_q) => {
+                let minor = currency_q
+                    .parse(required()?)
+                    .and_then(|minor| currency_q.check_bounds(minor).map(|()| minor))
+                    .map_err(|message| invalid(&path, message))?;
+                ResponseValue::Int(minor)
+            }
+
+            QuestionKind::Color(color_q) => {
+                let color = color_q
+                    .parse(required()?)
+                    .map_err(|message| invalid(&path, message))?;
+                ResponseValue::String(color.to_hex())
+            }
+
+            QuestionKind::List(list_q) => {
+                let items = text
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty());
+                match list_q.element_kind {
+                    ListElementKind::String => {
+                        ResponseValue::StringList(items.map(str::to_string).collect())
+                    }
+                    ListElementKind::Int { min, max } => ResponseValue::IntList(
+                        items
+                            .map(|item| {
+                                let value = parse::<i64>(item, &path)?;
+                                check_bounds(value, min, max, &path).map(|()| value)
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    ListElementKind::Float { min, max } => ResponseValue::FloatList(
+                        items
+                            .map(|item| {
+                                let value = parse::<f64>(item, &path)?;
+                                check_bounds(value, min, max, &path).map(|()| value)
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                }
+            }
+
+            QuestionKind::Confirm(_) => ResponseValue::Bool(text.is_some_and(is_checked)),
+
+            QuestionKind::Select(select_q) => {
+                let value = required()?;
+                if select_q.position(value).is_none() {
+                    return Err(invalid(
+                        &path,
+                        format!("{value:?} is not one of the choices"),
+                    ));
+                }
+                ResponseValue::String(value.to_string())
+            }
+
+            QuestionKind::Rating(rating_q) => {
+                let value = parse::<i64>(required()?, &path)?;
+                if !rating_q.contains(value) {
+                    return Err(invalid(&path, format!("{value} is not on the scale")));
+                }
+                ResponseValue::Int(value)
+            }
+
+            QuestionKind::Matrix(matrix_q) => {
+                let columns = (0..matrix_q.rows.len())
+                    .map(|row| {
+                        let row_path = path.child(&row.to_string());
+                        let text = self
+                            .get(field.child(&row.to_string()).as_str())
+                            .map(str::trim)
+                            .filter(|text| !text.is_empty())
+                            .ok_or_else(|| missing(&row_path))?;
+                        parse_index(text, matrix_q.columns.len(), &row_path)
+                    })
+                    .collect::<Result<_, _>>()?;
+                ResponseValue::ChosenVariants(columns)
+            }
+
+            QuestionKind::OneOf(one_of) => {
+                let choice_path = path.child(SELECTED_VARIANT_KEY);
+                let text = self
+                    .get(field.child(SELECTED_VARIANT_KEY).as_str())
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+                    .ok_or_else(|| missing(&choice_path))?;
+                let idx = parse_index(text, one_of.variants.len(), &choice_path)?;
+                responses.insert(choice_path, ResponseValue::ChosenVariant(idx));
+
+                let variant = &one_of.variants[idx];
+                match &variant.kind {
+                    QuestionKind::Unit => {}
+                    QuestionKind::AllOf(all_of) => {
+                        for nested in all_of.questions() {
+                            self.import_question(nested, &field, &path, responses)?;
+                        }
+                    }
+                    kind => {
+                        let nested = Question::new(variant.name.as_str(), "", kind.clone());
+                        self.import_question(&nested, &field, &path, responses)?;
+                    }
+                }
+                return Ok(());
+            }
+
+            QuestionKind::AnyOf(any_of) => {
+                let choices = field.child(SELECTED_VARIANTS_KEY);
+                let chosen: Vec<usize> = (0..any_of.variants.len())
+                    .filter(|idx| {
+                        self.get(choices.child(&idx.to_string()).as_str())
+                            .is_some_and(is_checked)
+                    })
+                    .collect();
+                for (item, &idx) in chosen.iter().enumerate() {
+                    let item_path = path.child(&item.to_string());
+                    responses.insert(
+                        item_path.child(SELECTED_VARIANT_KEY),
+                        ResponseValue::ChosenVariant(idx),
+                    );
+                    if let QuestionKind::AllOf(all_of) = &any_of.variants[idx].kind {
+                        let item_field = field.child(&idx.to_string());
+                        for nested in all_of.questions() {
+                            self.import_question(nested, &item_field, &item_path, responses)?;
+                        }
+                    }
+                }
+                responses.insert(
+                    path.child(SELECTED_VARIANTS_KEY),
+                    ResponseValue::ChosenVariants(chosen),
+                );
+                return Ok(());
+            }
+
+            QuestionKind::AllOf(all_of) => {
+                for nested in all_of.questions() {
+                    self.import_question(nested, &field, &path, responses)?;
+                }
+                return Ok(());
+            }
+        };
+
+        responses.insert(path, value);
+        Ok(())
+    }
+}
+
+/// Read a filled PDF form written by this crate back into a survey value.
+///
+/// This is a convenience function for [`FormData::from_pdf`] followed by
+/// [`FormData::to_survey`].
+pub fn from_pdf_form<T: Survey>(pdf: &[u8]) -> Result<T, ImportError> {
+    FormData::from_pdf(pdf)?.to_survey()
+}
+
+/// Collect the values of `kids` and their descendants, qualifying names with `prefix`.
+fn collect_fields(
+    document: &Document,
+    kids: &[Object],
+    prefix: &str,
+    depth: usize,
+    fields: &mut BTreeMap<String, String>,
+) {
+    // Guard against reference cycles in malformed files
+    if depth > 32 {
+        return;
+    }
+    for kid in kids {
+        let Some(dict) = document.resolve(kid).as_dict() else {
+            continue;
+        };
+        // Widgets without a name belong to their parent field
+        let name = match dict.get("T") {
+            Some(Object::String(partial)) if prefix.is_empty() => decode_text(partial),
+            Some(Object::String(partial)) => format!("{prefix}.{}", decode_text(partial)),
+            _ => prefix.to_string(),
+        };
+        if let Some(value) = dict
+            .get("V")
+            .and_then(|value| field_value(document.resolve(value)))
+            && !name.is_empty()
+        {
+            fields.insert(name.clone(), value);
+        }
+        if let Some(kids) = dict
+            .get("Kids")
+            .and_then(|kids| document.resolve(kids).as_array())
+        {
+            collect_fields(document, kids, &name, depth + 1, fields);
+        }
+    }
+}
+
+fn field_value(value: &Object) -> Option<String> {
+    match value {
+        Object::String(bytes) => Some(decode_text(bytes)),
+        Object::Name(name) => Some(name.clone()),
+        Object::Int(i) => Some(i.to_string()),
+        Object::Real(f) => Some(f.to_string()),
+        Object::Array(items) => items.first().and_then(field_value),
+        _ => None,
+    }
+}
+
+/// Whether a checkbox value is an "on" state. Viewers may name it other than `Yes`.
+fn is_checked(value: &str) -> bool {
+    !value.is_empty() && value != "Off"
+}
+
+fn parse<N: std::str::FromStr>(text: &str, path: &ResponsePath) -> Result<N, ImportError> {
+    text.parse()
+        .map_err(|_| invalid(path, format!("{text:?} is not a number")))
+}
+
+fn parse_index(text: &str, len: usize, path: &ResponsePath) -> Result<usize, ImportError> {
+    text.parse()
+        .ok()
+        .filter(|idx| *idx < len)
+        .ok_or_else(|| invalid(path, format!("{text:?} is not one of the choices")))
+}
+
+fn check_bounds<N: PartialOrd + std::fmt::Display>(
+    value: N,
+    min: Option<N>,
+    max: Option<N>,
+    path: &ResponsePath,
+) -> Result<(), ImportError> {
+    match (min, max) {
+        (Some(min), _) if value < min => Err(invalid(path, format!("{value} is below {min}"))),
+        (_, Some(max)) if value > max => Err(invalid(path, format!("{value} is above {max}"))),
+        _ => Ok(()),
+    }
+}
+
+fn missing(path: &ResponsePath) -> ImportError {
+    ImportError::MissingValue(path.clone())
+}
+
+fn invalid(path: &ResponsePath, message: String) -> ImportError {
+    ImportError::InvalidValue {
+        path: path.clone(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PdfOptions, definition_to_pdf_form};
+    use elicitor::{AnyOfQuestion, ConfirmQuestion, InputQuestion, IntQuestion, Variant};
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new(
+                "name",
+                "Name:",
+                QuestionKind::Input(InputQuestion::with_default("Ada")),
+            ),
+            Question::new(
+                "age",
+                "Age:",
+                QuestionKind::Int(IntQuestion {
+                    default: Some(36),
+                    ..IntQuestion::with_bounds(Some(0), Some(150))
+                }),
+            ),
+            Question::new(
+                "newsletter",
+                "Newsletter?",
+                QuestionKind::Confirm(ConfirmQuestion::new()),
+            ),
+            Question::new(
+                "toppings",
+                "Toppings:",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![
+                    Variant::unit("Cheese"),
+                    Variant::unit("Olives"),
+                ])),
+            ),
+        ])
+    }
+
+    #[test]
+    fn generated_forms_read_back() {
+        let pdf = definition_to_pdf_form(&definition(), &PdfOptions::new());
+        let data = FormData::from_pdf(&pdf).unwrap();
+        assert_eq!(data.get("name"), Some("Ada"));
+        assert_eq!(data.get("newsletter"), Some("Off"));
+
+        let responses = data.to_responses(&definition()).unwrap();
+        assert_eq!(responses.get_string(&"name".into()).unwrap(), "Ada");
+        assert_eq!(responses.get_int(&"age".into()).unwrap(), 36);
+        assert!(!responses.get_bool(&"newsletter".into()).unwrap());
+    }
+
+    #[test]
+    fn saved_revisions_are_read() {
+        let mut pdf = definition_to_pdf_form(&definition(), &PdfOptions::new());
+        // A viewer appends the changed field objects when saving
+        let text = String::from_utf8_lossy(&pdf).into_owned();
+        let header = text.find("<< /T (newsletter)").unwrap();
+        let number = text[..header]
+            .trim_end()
+            .strip_suffix("0 obj")
+            .unwrap()
+            .trim_end()
+            .rsplit('\n')
+            .next()
+            .unwrap();
+        pdf.extend_from_slice(
+            format!("{number} 0 obj\n<< /T (newsletter) /FT /Btn /V /Yes >>\nendobj\n").as_bytes(),
+        );
+
+        let data = FormData::from_pdf(&pdf).unwrap();
+        assert_eq!(data.get("newsletter"), Some("Yes"));
+    }
+
+    #[test]
+    fn fdf_fields_are_read() {
+        let fdf = b"%FDF-1.2\n1 0 obj\n<< /FDF << /Fields [<< /T (name) /V (Grace) >> << /T (toppings) /Kids [<< /T (selected_variants) /Kids [<< /T (1) /V /Yes >>] >>] >> << /T (age) /V (200) >>] >> >>\nendobj\ntrailer\n<< /Root 1 0 R >>\n%%EOF\n";
+        let data = FormData::from_fdf(fdf).unwrap();
+        assert_eq!(data.get("name"), Some("Grace"));
+        assert_eq!(data.get("toppings.selected_variants.1"), Some("Yes"));
+
+        let err = data.to_responses(&definition()).unwrap_err();
+        assert!(matches!(err, ImportError::InvalidValue { path, .. } if path.as_str() == "age"));
+    }
+
+    #[test]
+    fn xfdf_fields_are_read() {
+        let xfdf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xfdf xmlns="http://ns.adobe.com/xfdf/" xml:space="preserve">
+  <fields>
+    <field name="name"><value>Ada &amp; Grace</value></field>
+    <field name="age"><value>40</value></field>
+    <field name="toppings">
+      <field name="selected_variants">
+        <field name="0"><value>Off</value></field>
+        <field name="1"><value>Yes</value></field>
+      </field>
+    </field>
+  </fields>
+</xfdf>"#;
+        let data = FormData::from_xfdf(xfdf).unwrap();
+        assert_eq!(data.get("name"), Some("Ada & Grace"));
+
+        let responses = data.to_responses(&definition()).unwrap();
+        assert_eq!(responses.get_int(&"age".into()).unwrap(), 40);
+        assert_eq!(
+            responses.get(&"toppings.selected_variants".into()),
+            Some(&ResponseValue::ChosenVariants(vec![1]))
+        );
+        assert_eq!(
+            responses.get(&"toppings.0.selected_variant".into()),
+            Some(&ResponseValue::ChosenVariant(1))
+        );
+        assert!(!responses.get_bool(&"newsletter".into()).unwrap());
+    }
+
+    #[test]
+    fn derived_surveys_are_reconstructed() {
+        use example_surveys::{Checkout, PaymentMethod, ShippingMethod};
+
+        let xfdf = r#"<xfdf><fields>
+            <field name="shipping"><field name="selected_variant"><value>2</value></field></field>
+            <field name="payment">
+              <field name="selected_variant"><value>1</value></field>
+              <field name="0"><value>ada@example.com</value></field>
+            </field>
+        </fields></xfdf>"#;
+        let checkout: Checkout = FormData::from_xfdf(xfdf).unwrap().to_survey().unwrap();
+        assert!(matches!(checkout.shipping, ShippingMethod::Overnight));
+        assert!(
+            matches!(checkout.payment, PaymentMethod::PayPal(email) if email == "ada@example.com")
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! # elicitor-doc-pdf
+//!
+//! PDF document generator for elicitor.
+//!
+//! This crate writes interactive PDF forms (AcroForms) from survey definitions, with
+//! no TeX toolchain or other external dependency. Text questions become text fields,
+//! yes/no questions checkboxes, and selections, ratings and enums combo boxes.
+//!
+//! Field names are the response paths, so the values of a filled form map back onto
+//! the paths of `Responses`. Enum choices are named `<path>.selected_variant` and hold
+//! the variant index; multi-select questions get one checkbox per variant named
+//! `<path>.selected_variants.<index>`.
+//!
+//! [`PdfOptions::with_qr_code`] prints a QR code linking to the online version of the
+//! form on every page, for respondents who would rather fill it in on the web.
+//!
+//! Filled forms are read back with [`from_pdf_form`], or with [`FormData`] for FDF and
+//! XFDF exports, completing the round trip from survey to paper and back.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use elicitor::Survey;
+//! use elicitor_doc_pdf::{from_pdf_form, to_pdf_form};
+//!
+//! #[derive(Survey)]
+//! struct UserProfile {
+//!     #[ask("What is your name?")]
+//!     name: String,
+//!
+//!     #[ask("How old are you?")]
+//!     #[min(0)]
+//!     #[max(150)]
+//!     age: i64,
+//! }
+//!
+//! fn main() {
+//!     let pdf = to_pdf_form::<UserProfile>(Some("User Profile"));
+//!     std::fs::write("form.pdf", pdf).unwrap();
+//!
+//!     // After the form was filled in and saved
+//!     let filled = std::fs::read("form.pdf").unwrap();
+//!     let profile: UserProfile = from_pdf_form(&filled).unwrap();
+//! }
+//! ```
+
+mod generator;
+mod import;
+mod reader;
+mod writer;
+
+pub use generator::{
+    PageSize, PdfOptions, QrPosition, definition_to_pdf_form, to_pdf_form, to_pdf_form_with_options,
+};
+pub use import::{FormData, ImportError, from_pdf_form};
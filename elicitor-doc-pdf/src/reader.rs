@@ -0,0 +1,621 @@
+//! Low-level PDF parsing, just enough to read form fields back.
+//!
+//! Objects are collected by scanning the file, so incremental updates (which PDF viewers
+//! append when saving a filled form) override earlier versions without the cross-reference
+//! table being needed. Objects inside Flate-compressed object streams are read as well.
+
+use std::collections::HashMap;
+
+use crate::import::ImportError;
+
+/// A parsed PDF object. Generation numbers of references are dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Object {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Real(f64),
+    String(Vec<u8>),
+    Name(String),
+    Array(Vec<Object>),
+    Dict(Dict),
+    Stream(Dict, Vec<u8>),
+    Ref(u32),
+}
+
+pub(crate) type Dict = HashMap<String, Object>;
+
+impl Object {
+    pub(crate) fn as_dict(&self) -> Option<&Dict> {
+        match self {
+            Self::Dict(dict) | Self::Stream(dict, _) => Some(dict),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Object]> {
+        match self {
+            Self::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    fn as_name(&self) -> Option<&str> {
+        match self {
+            Self::Name(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// All objects of a PDF or FDF file with its trailer.
+pub(crate) struct Document {
+    objects: HashMap<u32, Object>,
+    trailer: Dict,
+}
+
+impl Document {
+    pub(crate) fn parse(data: &[u8]) -> Result<Self, ImportError> {
+        if !data.starts_with(b"%PDF") && !data.starts_with(b"%FDF") {
+            return Err(ImportError::Malformed("missing PDF header".to_string()));
+        }
+
+        let mut objects = HashMap::new();
+        let mut trailer = Dict::new();
+        let mut pos = 0;
+        let mut next_trailer = find(data, pos, b"trailer");
+        loop {
+            if next_trailer.is_some_and(|at| at < pos) {
+                next_trailer = find(data, pos, b"trailer");
+            }
+            let next_obj = find(data, pos, b"obj");
+            match (next_obj, next_trailer) {
+                (_, Some(at)) if next_obj.is_none_or(|obj| at < obj) => {
+                    let mut lexer = Lexer::new(data, at + b"trailer".len());
+                    match lexer.object() {
+                        Ok(Object::Dict(dict)) => {
+                            trailer = dict;
+                            pos = lexer.pos;
+                        }
+                        _ => pos = at + 1,
+                    }
+                }
+                (Some(at), _) => {
+                    let Some(number) = object_number(data, at) else {
+                        pos = at + 1;
+                        continue;
+                    };
+                    let mut lexer = Lexer::new(data, at + b"obj".len());
+                    let Ok(object) = lexer.indirect_body() else {
+                        pos = at + 1;
+                        continue;
+                    };
+                    pos = lexer.pos;
+                    if let Object::Stream(dict, content) = &object {
+                        match dict.get("Type").and_then(Object::as_name) {
+                            Some("ObjStm") => read_object_stream(dict, content, &mut objects)?,
+                            // Cross-reference streams double as the trailer
+                            Some("XRef") => trailer = dict.clone(),
+                            _ => {}
+                        }
+                    }
+                    objects.insert(number, object);
+                }
+                (None, _) => break,
+            }
+        }
+
+        Ok(Self { objects, trailer })
+    }
+
+    /// Follow `object` if it is a reference. Missing objects resolve to null.
+    pub(crate) fn resolve<'a>(&'a self, mut object: &'a Object) -> &'a Object {
+        for _ in 0..16 {
+            match object {
+                Object::Ref(number) => {
+                    object = self.objects.get(number).unwrap_or(&Object::Null);
+                }
+                _ => return object,
+            }
+        }
+        &Object::Null
+    }
+
+    /// The document catalog, or the dictionary holding `key` if the trailer names no root.
+    pub(crate) fn catalog(&self, key: &str) -> Option<&Dict> {
+        self.trailer
+            .get("Root")
+            .and_then(|root| self.resolve(root).as_dict())
+            .or_else(|| {
+                self.objects
+                    .values()
+                    .filter_map(Object::as_dict)
+                    .find(|dict| dict.contains_key(key))
+            })
+    }
+}
+
+/// Read the objects packed into an object stream.
+fn read_object_stream(
+    dict: &Dict,
+    content: &[u8],
+    objects: &mut HashMap<u32, Object>,
+) -> Result<(), ImportError> {
+    let content = decode_stream(dict, content)?;
+    let malformed = || ImportError::Malformed("invalid object stream".to_string());
+    let count = dict
+        .get("N")
+        .and_then(Object::as_int)
+        .ok_or_else(malformed)?;
+    let first = dict
+        .get("First")
+        .and_then(Object::as_int)
+        .ok_or_else(malformed)? as usize;
+
+    let mut header = Lexer::new(&content, 0);
+    let mut offsets = Vec::new();
+    for _ in 0..count {
+        let number = header.object()?.as_int().ok_or_else(malformed)?;
+        let offset = header.object()?.as_int().ok_or_else(malformed)?;
+        offsets.push((number as u32, first + offset as usize));
+    }
+    for (number, offset) in offsets {
+        let object = Lexer::new(&content, offset).object()?;
+        objects.insert(number, object);
+    }
+    Ok(())
+}
+
+/// The decompressed content of a stream.
+fn decode_stream(dict: &Dict, content: &[u8]) -> Result<Vec<u8>, ImportError> {
+    let filters = match dict.get("Filter") {
+        None => Vec::new(),
+        Some(Object::Name(name)) => vec![name.as_str()],
+        Some(Object::Array(names)) => names.iter().filter_map(Object::as_name).collect(),
+        Some(_) => return Err(ImportError::Malformed("invalid stream filter".to_string())),
+    };
+    let mut data = content.to_vec();
+    for filter in filters {
+        data = match filter {
+            "FlateDecode" => miniz_oxide::inflate::decompress_to_vec_zlib(&data)
+                .map_err(|e| ImportError::Malformed(format!("invalid compressed stream: {e:?}")))?,
+            other => return Err(ImportError::UnsupportedFilter(other.to_string())),
+        };
+    }
+    Ok(data)
+}
+
+/// The object number of an `N G obj` header whose keyword starts at `at`.
+fn object_number(data: &[u8], at: usize) -> Option<u32> {
+    if data
+        .get(at + 3)
+        .is_some_and(|&b| !is_whitespace(b) && !is_delimiter(b))
+    {
+        return None;
+    }
+    // Walk back over the generation, then the object number
+    let (mut start, mut end) = (at, at);
+    for _ in 0..2 {
+        end = start
+            - data[..start]
+                .iter()
+                .rev()
+                .take_while(|b| is_whitespace(**b))
+                .count();
+        let digits = data[..end]
+            .iter()
+            .rev()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return None;
+        }
+        start = end - digits;
+    }
+    if start > 0 && !is_whitespace(data[start - 1]) && !is_delimiter(data[start - 1]) {
+        return None;
+    }
+    std::str::from_utf8(&data[start..end]).ok()?.parse().ok()
+}
+
+fn find(data: &[u8], from: usize, pattern: &[u8]) -> Option<usize> {
+    data.get(from..)?
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .map(|idx| from + idx)
+}
+
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b'\0' | b'\t' | b'\n' | b'\x0c' | b'\r' | b' ')
+}
+
+fn is_delimiter(b: u8) -> bool {
+    matches!(
+        b,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+struct Lexer<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(data: &'a [u8], pos: usize) -> Self {
+        Self { data, pos }
+    }
+
+    fn error(&self, what: &str) -> ImportError {
+        ImportError::Malformed(format!("{what} at byte {}", self.pos))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn starts_with(&self, pattern: &[u8]) -> bool {
+        self.data[self.pos.min(self.data.len())..].starts_with(pattern)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b) = self.peek() {
+            if is_whitespace(b) {
+                self.pos += 1;
+            } else if b == b'%' {
+                while self.peek().is_some_and(|b| b != b'\n' && b != b'\r') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// The object after `N G obj`, with its stream data if it has any.
+    fn indirect_body(&mut self) -> Result<Object, ImportError> {
+        let object = self.object()?;
+        self.skip_whitespace();
+        let object = match object {
+            Object::Dict(dict) if self.starts_with(b"stream") => {
+                self.pos += b"stream".len();
+                if self.starts_with(b"\r\n") {
+                    self.pos += 2;
+                } else if self.starts_with(b"\n") || self.starts_with(b"\r") {
+                    self.pos += 1;
+                }
+                let content = self.stream_content(&dict)?;
+                Object::Stream(dict, content)
+            }
+            object => object,
+        };
+        self.skip_whitespace();
+        if self.starts_with(b"endobj") {
+            self.pos += b"endobj".len();
+        }
+        Ok(object)
+    }
+
+    fn stream_content(&mut self, dict: &Dict) -> Result<Vec<u8>, ImportError> {
+        let start = self.pos;
+        // Trust a direct length if `endstream` follows it
+        if let Some(length) = dict.get("Length").and_then(Object::as_int) {
+            let end = start + length as usize;
+            let mut after = Lexer::new(self.data, end);
+            after.skip_whitespace();
+            if end <= self.data.len() && after.starts_with(b"endstream") {
+                self.pos = after.pos + b"endstream".len();
+                return Ok(self.data[start..end].to_vec());
+            }
+        }
+        let end = find(self.data, start, b"endstream")
+            .ok_or_else(|| self.error("unterminated stream"))?;
+        self.pos = end + b"endstream".len();
+        let mut content = &self.data[start..end];
+        if let Some(stripped) = content.strip_suffix(b"\n") {
+            content = stripped.strip_suffix(b"\r").unwrap_or(stripped);
+        } else if let Some(stripped) = content.strip_suffix(b"\r") {
+            content = stripped;
+        }
+        Ok(content.to_vec())
+    }
+
+    fn object(&mut self) -> Result<Object, ImportError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err(self.error("unexpected end of data")),
+            Some(b'/') => {
+                self.pos += 1;
+                Ok(Object::Name(self.name()))
+            }
+            Some(b'(') => {
+                self.pos += 1;
+                self.literal_string().map(Object::String)
+            }
+            Some(b'<') if self.starts_with(b"<<") => {
+                self.pos += 2;
+                self.dict().map(Object::Dict)
+            }
+            Some(b'<') => {
+                self.pos += 1;
+                self.hex_string().map(Object::String)
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.peek() == Some(b']') {
+                        self.pos += 1;
+                        return Ok(Object::Array(items));
+                    }
+                    items.push(self.object()?);
+                }
+            }
+            Some(b'+' | b'-' | b'.' | b'0'..=b'9') => self.number_or_ref(),
+            Some(_) => match self.keyword().as_str() {
+                "true" => Ok(Object::Bool(true)),
+                "false" => Ok(Object::Bool(false)),
+                "null" => Ok(Object::Null),
+                _ => Err(self.error("unexpected keyword")),
+            },
+        }
+    }
+
+    fn keyword(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| !is_whitespace(b) && !is_delimiter(b))
+        {
+            self.pos += 1;
+        }
+        String::from_utf8_lossy(&self.data[start..self.pos]).into_owned()
+    }
+
+    fn name(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| !is_whitespace(b) && !is_delimiter(b))
+        {
+            self.pos += 1;
+        }
+        let raw = &self.data[start..self.pos];
+        let mut bytes = Vec::with_capacity(raw.len());
+        let mut idx = 0;
+        while idx < raw.len() {
+            let escaped = (raw[idx] == b'#')
+                .then(|| raw.get(idx + 1..idx + 3))
+                .flatten()
+                .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+            match escaped {
+                Some(b) => {
+                    bytes.push(b);
+                    idx += 3;
+                }
+                None => {
+                    bytes.push(raw[idx]);
+                    idx += 1;
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    fn number_or_ref(&mut self) -> Result<Object, ImportError> {
+        let token = self.keyword();
+        if let Ok(number) = token.parse::<i64>() {
+            // `N G R` is a reference
+            let save = self.pos;
+            self.skip_whitespace();
+            let generation = self.keyword();
+            self.skip_whitespace();
+            if number >= 0
+                && !generation.is_empty()
+                && generation.bytes().all(|b| b.is_ascii_digit())
+                && self.peek() == Some(b'R')
+                && self
+                    .data
+                    .get(self.pos + 1)
+                    .is_none_or(|&b| is_whitespace(b) || is_delimiter(b))
+            {
+                self.pos += 1;
+                return Ok(Object::Ref(number as u32));
+            }
+            self.pos = save;
+            return Ok(Object::Int(number));
+        }
+        token
+            .parse::<f64>()
+            .map(Object::Real)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    fn literal_string(&mut self) -> Result<Vec<u8>, ImportError> {
+        let mut bytes = Vec::new();
+        let mut depth = 0;
+        loop {
+            let b = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += 1;
+            match b {
+                b'(' => {
+                    depth += 1;
+                    bytes.push(b);
+                }
+                b')' if depth == 0 => return Ok(bytes),
+                b')' => {
+                    depth -= 1;
+                    bytes.push(b);
+                }
+                b'\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    match escaped {
+                        b'n' => bytes.push(b'\n'),
+                        b'r' => bytes.push(b'\r'),
+                        b't' => bytes.push(b'\t'),
+                        b'b' => bytes.push(b'\x08'),
+                        b'f' => bytes.push(b'\x0c'),
+                        b'0'..=b'7' => {
+                            let mut value = u32::from(escaped - b'0');
+                            for _ in 0..2 {
+                                match self.peek() {
+                                    Some(digit @ b'0'..=b'7') => {
+                                        value = value * 8 + u32::from(digit - b'0');
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            bytes.push(value as u8);
+                        }
+                        // A backslash before a line break continues the line
+                        b'\r' => {
+                            if self.peek() == Some(b'\n') {
+                                self.pos += 1;
+                            }
+                        }
+                        b'\n' => {}
+                        other => bytes.push(other),
+                    }
+                }
+                _ => bytes.push(b),
+            }
+        }
+    }
+
+    fn hex_string(&mut self) -> Result<Vec<u8>, ImportError> {
+        let mut digits = Vec::new();
+        loop {
+            let b = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += 1;
+            match b {
+                b'>' => break,
+                b if b.is_ascii_hexdigit() => digits.push(b),
+                b if is_whitespace(b) => {}
+                _ => return Err(self.error("invalid hex string")),
+            }
+        }
+        if digits.len() % 2 == 1 {
+            digits.push(b'0');
+        }
+        Ok(digits
+            .chunks(2)
+            .map(|pair| {
+                let hex = std::str::from_utf8(pair).unwrap_or("00");
+                u8::from_str_radix(hex, 16).unwrap_or_default()
+            })
+            .collect())
+    }
+
+    fn dict(&mut self) -> Result<Dict, ImportError> {
+        let mut dict = Dict::new();
+        loop {
+            self.skip_whitespace();
+            if self.starts_with(b">>") {
+                self.pos += 2;
+                return Ok(dict);
+            }
+            let Object::Name(key) = self.object()? else {
+                return Err(self.error("dictionary key is not a name"));
+            };
+            let value = self.object()?;
+            dict.insert(key, value);
+        }
+    }
+}
+
+/// Decode a PDF text string: UTF-16 or UTF-8 with a byte order mark, otherwise
+/// PDFDocEncoding, which matches Latin-1 for the characters forms contain.
+pub(crate) fn decode_text(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(b"\xfe\xff") {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    } else if let Some(utf8) = bytes.strip_prefix(b"\xef\xbb\xbf") {
+        String::from_utf8_lossy(utf8).into_owned()
+    } else {
+        bytes.iter().map(|&b| char::from(b)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn objects_are_parsed() {
+        let data =
+            b"<< /Name /A#20B /List [1 -2.5 (s\\(t\\)\\101) <48 69>] /Ref 12 0 R /On true >>";
+        let object = Lexer::new(data, 0).object().unwrap();
+        let dict = object.as_dict().unwrap();
+        assert_eq!(dict["Name"], Object::Name("A B".into()));
+        assert_eq!(
+            dict["List"],
+            Object::Array(vec![
+                Object::Int(1),
+                Object::Real(-2.5),
+                Object::String(b"s(t)A".to_vec()),
+                Object::String(b"Hi".to_vec()),
+            ])
+        );
+        assert_eq!(dict["Ref"], Object::Ref(12));
+        assert_eq!(dict["On"], Object::Bool(true));
+    }
+
+    #[test]
+    fn later_revisions_override_objects() {
+        let data = b"%PDF-1.7\n1 0 obj\n<< /V (old) >>\nendobj\n2 0 obj\n<< /Length 5 >>\nstream\nendob\nendstream\nendobj\n1 0 obj\n<< /V (new) >>\nendobj\ntrailer\n<< /Root 1 0 R >>\n";
+        let document = Document::parse(data).unwrap();
+        let root = document.catalog("V").unwrap();
+        assert_eq!(root["V"], Object::String(b"new".to_vec()));
+        assert_eq!(
+            document.objects[&2],
+            Object::Stream(
+                HashMap::from([("Length".to_string(), Object::Int(5))]),
+                b"endob".to_vec()
+            )
+        );
+    }
+
+    #[test]
+    fn object_streams_are_unpacked() {
+        let packed = b"7 0 8 12 << /V (a) >> << /V (b) >>";
+        let compressed = miniz_oxide::deflate::compress_to_vec_zlib(packed, 6);
+        let mut data = format!(
+            "%PDF-1.7\n3 0 obj\n<< /Type /ObjStm /N 2 /First 9 /Filter /FlateDecode /Length {} >>\nstream\n",
+            compressed.len()
+        )
+        .into_bytes();
+        data.extend_from_slice(&compressed);
+        data.extend_from_slice(b"\nendstream\nendobj\n");
+
+        let document = Document::parse(&data).unwrap();
+        assert_eq!(
+            document.resolve(&Object::Ref(8)).as_dict().unwrap()["V"],
+            Object::String(b"b".to_vec())
+        );
+    }
+
+    #[test]
+    fn text_strings_are_decoded() {
+        assert_eq!(decode_text(b"\xfe\xff\x00G\x00r\x00\xf6"), "Grö");
+        assert_eq!(decode_text(b"Gr\xf6"), "Grö");
+    }
+}
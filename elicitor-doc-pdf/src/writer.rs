@@ -0,0 +1,158 @@
+//! Low-level PDF serialization.
+//!
+//! Objects are written uncompressed so the output needs no external libraries and stays
+//! readable when debugging.
+
+/// Collects numbered objects and writes them with a cross-reference table.
+pub(crate) struct PdfWriter {
+    objects: Vec<Vec<u8>>,
+}
+
+impl PdfWriter {
+    pub(crate) fn new() -> Self {
+        Self {
+            objects: Vec::new(),
+        }
+    }
+
+    /// Reserve an object number, filled in later with `set`.
+    pub(crate) fn reserve(&mut self) -> usize {
+        self.objects.push(Vec::new());
+        self.objects.len()
+    }
+
+    /// Set the body of a reserved object.
+    pub(crate) fn set(&mut self, id: usize, body: impl Into<Vec<u8>>) {
+        self.objects[id - 1] = body.into();
+    }
+
+    /// Add an object and return its number.
+    pub(crate) fn add(&mut self, body: impl Into<Vec<u8>>) -> usize {
+        let id = self.reserve();
+        self.set(id, body);
+        id
+    }
+
+    /// Write the document with `root` as the catalog.
+    pub(crate) fn finish(self, root: usize, info: usize) -> Vec<u8> {
+        let mut pdf = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::with_capacity(self.objects.len());
+        for (idx, body) in self.objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", idx + 1).as_bytes());
+            pdf.extend_from_slice(body);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = pdf.len();
+        pdf.extend_from_slice(format!("xref\n0 {}\n", self.objects.len() + 1).as_bytes());
+        pdf.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root {root} 0 R /Info {info} 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+                self.objects.len() + 1
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+}
+
+/// A stream object with `dict` entries besides `/Length`.
+pub(crate) fn stream(dict: &str, content: &str) -> String {
+    format!(
+        "<< {dict} /Length {} >>\nstream\n{content}\nendstream",
+        content.len()
+    )
+}
+
+/// A string for page content, in the WinAnsi encoding of the standard fonts.
+///
+/// Characters outside WinAnsi are replaced by `?`.
+pub(crate) fn content_string(text: &str) -> String {
+    let mut out = String::from("(");
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            ' '..='~' => out.push(c),
+            '\u{a0}'..='\u{ff}' => out.push_str(&format!("\\{:03o}", c as u32)),
+            _ => match win_ansi(c) {
+                Some(byte) => out.push_str(&format!("\\{byte:03o}")),
+                None => out.push('?'),
+            },
+        }
+    }
+    out.push(')');
+    out
+}
+
+/// WinAnsi codes of the characters outside Latin-1 that prompts commonly use.
+fn win_ansi(c: char) -> Option<u8> {
+    Some(match c {
+        '€' => 0x80,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        _ => return None,
+    })
+}
+
+/// A text string for dictionaries (field names, values, options, metadata).
+///
+/// ASCII is written as a literal string, anything else as UTF-16 with a byte order mark.
+pub(crate) fn text_string(text: &str) -> String {
+    if text.is_ascii() {
+        let escaped = text
+            .replace('\\', "\\\\")
+            .replace('(', "\\(")
+            .replace(')', "\\)")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n");
+        format!("({escaped})")
+    } else {
+        let hex: String = text
+            .encode_utf16()
+            .map(|unit| format!("{unit:04X}"))
+            .collect();
+        format!("<FEFF{hex}>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(content_string("a (b) – ü"), "(a \\(b\\) \\226 \\374)");
+        assert_eq!(text_string("x\\y"), "(x\\\\y)");
+        assert_eq!(text_string("Größe"), "<FEFF0047007200F600DF0065>");
+    }
+
+    #[test]
+    fn cross_references_point_at_objects() {
+        let mut writer = PdfWriter::new();
+        let root = writer.reserve();
+        let info = writer.add("<< /Title (t) >>");
+        writer.set(root, "<< /Type /Catalog >>");
+        let pdf = writer.finish(root, info);
+
+        let xref = pdf.windows(5).position(|w| w == b"xref\n").unwrap();
+        let table = std::str::from_utf8(&pdf[xref..]).unwrap();
+        for (idx, line) in table.lines().skip(3).take(2).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", idx + 1).as_bytes()));
+        }
+    }
+}
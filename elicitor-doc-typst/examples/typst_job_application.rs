@@ -0,0 +1,20 @@
+//! Typst example - print the job application as a Typst form.
+//!
+//! Run with: cargo run -p elicitor-doc-typst --example typst_job_application
+//! Then compile with: typst compile job_application.typ
+
+use elicitor::Survey;
+use elicitor_doc_typst::{PaperSize, TypstOptions, to_typst_form_with_options};
+use example_surveys::JobApplication;
+
+fn main() {
+    let options = TypstOptions::new()
+        .with_title("Job Application")
+        .with_paper_size(PaperSize::Letter)
+        .with_accent_color("#2e7d32");
+    let typst = to_typst_form_with_options(&JobApplication::survey(), &options);
+
+    std::fs::write("job_application.typ", &typst).expect("Failed to write Typst file");
+
+    println!("Generated job_application.typ");
+}
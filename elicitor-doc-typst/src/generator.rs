@@ -0,0 +1,683 @@
+//! Typst form generator implementation.
+
+use elicitor::{DefaultValue, ListElementKind, Question, QuestionKind, SurveyDefinition, Variant};
+
+/// Paper size of the generated document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PaperSize {
+    /// ISO A4, 210 × 297 mm.
+    #[default]
+    A4,
+    /// ISO A5, 148 × 210 mm.
+    A5,
+    /// US Letter, 8.5 × 11 in.
+    Letter,
+    /// US Legal, 8.5 × 14 in.
+    Legal,
+}
+
+impl PaperSize {
+    /// Name of the paper size in Typst's `page` function.
+    fn typst_name(self) -> &'static str {
+        match self {
+            Self::A4 => "a4",
+            Self::A5 => "a5",
+            Self::Letter => "us-letter",
+            Self::Legal => "us-legal",
+        }
+    }
+}
+
+/// Colors of the generated form, as `#rrggbb` hex codes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// Title and question prompts.
+    pub accent: String,
+    /// Outlines of fields, radio marks and checkboxes, and hints.
+    pub border: String,
+    /// Background of the fields.
+    pub field: String,
+    /// Background of the blocks holding nested questions. Deeper blocks are darker.
+    pub shade: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            accent: "#1f4e79".to_string(),
+            border: "#7a8591".to_string(),
+            field: "#ffffff".to_string(),
+            shade: "#eef2f6".to_string(),
+        }
+    }
+}
+
+impl Theme {
+    /// Black and gray only, for photocopies.
+    pub fn grayscale() -> Self {
+        Self {
+            accent: "#000000".to_string(),
+            border: "#555555".to_string(),
+            field: "#ffffff".to_string(),
+            shade: "#eeeeee".to_string(),
+        }
+    }
+}
+
+/// Options for Typst generation.
+#[derive(Debug, Clone)]
+pub struct TypstOptions {
+    /// Title shown at the top of the first page and in the document metadata.
+    pub title: Option<String>,
+    /// Paper size.
+    pub paper_size: PaperSize,
+    /// Page margin on all sides, as a Typst length.
+    pub margin: String,
+    /// Font family. `None` keeps Typst's default font.
+    pub font: Option<String>,
+    /// Base font size, as a Typst length.
+    pub font_size: String,
+    /// Colors of the form.
+    pub theme: Theme,
+    /// Width of text fields, as a Typst length. The default `1fr` fills the line.
+    pub field_width: String,
+    /// Number of fields printed for a list question, raised to the list's minimum
+    /// length and lowered to its maximum length.
+    pub list_fields: usize,
+}
+
+impl Default for TypstOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            paper_size: PaperSize::default(),
+            margin: "2cm".to_string(),
+            font: None,
+            font_size: "11pt".to_string(),
+            theme: Theme::default(),
+            field_width: "1fr".to_string(),
+            list_fields: 3,
+        }
+    }
+}
+
+impl TypstOptions {
+    /// Create new options with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the document title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the paper size.
+    pub fn with_paper_size(mut self, paper_size: PaperSize) -> Self {
+        self.paper_size = paper_size;
+        self
+    }
+
+    /// Set the page margin, e.g. `1in`.
+    pub fn with_margin(mut self, margin: impl Into<String>) -> Self {
+        self.margin = margin.into();
+        self
+    }
+
+    /// Set the font family, e.g. `Inter`.
+    pub fn with_font(mut self, font: impl Into<String>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    /// Set the base font size, e.g. `10pt`.
+    pub fn with_font_size(mut self, size: impl Into<String>) -> Self {
+        self.font_size = size.into();
+        self
+    }
+
+    /// Set the colors of the form.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Set the color of the title and the prompts.
+    pub fn with_accent_color(mut self, color: impl Into<String>) -> Self {
+        self.theme.accent = color.into();
+        self
+    }
+
+    /// Set the width of text fields, e.g. `4in`.
+    pub fn with_field_width(mut self, width: impl Into<String>) -> Self {
+        self.field_width = width.into();
+        self
+    }
+
+    /// Set the number of fields printed for a list question.
+    pub fn with_list_fields(mut self, count: usize) -> Self {
+        self.list_fields = count;
+        self
+    }
+}
+
+/// Generate a Typst document for a printable form from a survey definition.
+pub fn to_typst_form(definition: &SurveyDefinition) -> String {
+    to_typst_form_with_options(definition, &TypstOptions::new())
+}
+
+/// Generate a Typst document for a printable form with custom options.
+pub fn to_typst_form_with_options(definition: &SurveyDefinition, options: &TypstOptions) -> String {
+    let mut writer = Writer::new(options);
+    writer.preamble();
+
+    if let Some(title) = &options.title {
+        writer.line(&format!(
+            "#align(center, text(size: 1.6em, weight: \"bold\", fill: accent, {}))",
+            string(title)
+        ));
+    }
+    if let Some(prelude) = &definition.prelude {
+        writer.line(&format!("#block(above: 1.2em, {})", string(prelude)));
+    }
+    for question in definition.questions() {
+        writer.question(question, 0);
+    }
+    if let Some(epilogue) = &definition.epilogue {
+        writer.line(&format!("#block(above: 2em, {})", string(epilogue)));
+    }
+
+    writer.out
+}
+
+/// Typst source under construction, indented by nesting.
+struct Writer<'a> {
+    out: String,
+    indent: usize,
+    options: &'a TypstOptions,
+}
+
+impl<'a> Writer<'a> {
+    fn new(options: &'a TypstOptions) -> Self {
+        Self {
+            out: String::new(),
+            indent: 0,
+            options,
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.indent {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    /// Open a content block, e.g. `#question[`.
+    fn open(&mut self, text: &str) {
+        self.line(text);
+        self.indent += 1;
+    }
+
+    fn close(&mut self) {
+        self.indent -= 1;
+        self.line("]");
+    }
+
+    /// Page setup, colors and the helper functions used by the questions.
+    fn preamble(&mut self) {
+        let options = self.options;
+        let theme = &options.theme;
+        self.line("// Generated by elicitor-doc-typst. Compile with `typst compile`.");
+        if let Some(title) = &options.title {
+            self.line(&format!("#set document(title: {})", string(title)));
+        }
+        self.line(&format!(
+            "#set page(paper: \"{}\", margin: {})",
+            options.paper_size.typst_name(),
+            options.margin
+        ));
+        match &options.font {
+            Some(font) => self.line(&format!(
+                "#set text(font: {}, size: {})",
+                string(font),
+                options.font_size
+            )),
+            None => self.line(&format!("#set text(size: {})", options.font_size)),
+        }
+        self.line("");
+        self.line(&format!("#let accent = rgb({})", string(&theme.accent)));
+        self.line(&format!("#let border = rgb({})", string(&theme.border)));
+        self.line(&format!("#let field-fill = rgb({})", string(&theme.field)));
+        self.line(&format!("#let shade = rgb({})", string(&theme.shade)));
+        self.line("");
+        self.out.push_str(&format!(
+            r#"#let prompt(body) = block(below: 0.6em, text(weight: "bold", fill: accent, body))
+#let hint(body) = text(size: 0.85em, style: "italic", fill: border.darken(20%), body)
+#let condition(body) = block(below: 0.6em, text(style: "italic", body))
+#let field(width: {}, height: 1.6em) = box(
+  width: width, height: height, fill: field-fill, stroke: 0.5pt + border, radius: 2pt, baseline: 30%,
+)
+#let mark(round: false) = box(
+  width: 0.8em, height: 0.8em, stroke: 0.5pt + border, radius: if round {{ 50% }} else {{ 1pt }}, baseline: 0.1em,
+)
+#let radio(label) = box[#mark(round: true) #label]
+#let checkbox(label) = box[#mark() #label]
+#let choices(..items) = items.pos().join(h(1.2em))
+#let item(number) = block(above: 0.5em, box(width: 1.5em, str(number) + ".") + field())
+#let group(depth, body) = block(
+  width: 100%, above: 0.8em, fill: shade.darken(depth * 4%), inset: 0.7em, radius: 3pt, body,
+)
+#let question(body) = block(above: 1.4em, breakable: false, body)
+#let section(body) = block(above: 1.4em, body)
+
+"#,
+            options.field_width
+        ));
+    }
+
+    fn question(&mut self, question: &Question, depth: usize) {
+        // Assumed fields are not part of the form
+        if matches!(question.default(), DefaultValue::Assumed(_)) {
+            return;
+        }
+        let ask = question.ask();
+
+        match question.kind() {
+            // Flattened structs have no prompt of their own
+            QuestionKind::AllOf(all_of) if ask.is_empty() => {
+                for nested in all_of.questions() {
+                    self.question(nested, depth);
+                }
+                return;
+            }
+            // Enum fields are wrapped in a group holding just the unprompted choice
+            QuestionKind::AllOf(all_of) if matches!(all_of.questions(), [only] if only.ask().is_empty()) =>
+            {
+                self.open("#section[");
+                self.line(&format!("#prompt({})", string(ask)));
+                self.kind(all_of.questions()[0].kind(), depth);
+            }
+            QuestionKind::AllOf(all_of) => {
+                self.open("#section[");
+                self.line(&format!("#prompt({})", string(ask)));
+                self.open(&format!("#group({})[", depth + 1));
+                for nested in all_of.questions() {
+                    self.question(nested, depth + 1);
+                }
+                self.close();
+            }
+            kind @ (QuestionKind::OneOf(_) | QuestionKind::AnyOf(_)) => {
+                self.open("#section[");
+                if !ask.is_empty() {
+                    self.line(&format!("#prompt({})", string(ask)));
+                }
+                self.kind(kind, depth);
+            }
+            kind => {
+                self.open("#question[");
+                if !ask.is_empty() {
+                    self.line(&format!("#prompt({})", string(ask)));
+                }
+                self.kind(kind, depth);
+            }
+        }
+
+        if question.allows_comment() {
+            self.line("#block(above: 0.6em)[#hint(\"Comment:\") #field()]");
+        }
+        self.close();
+    }
+
+    /// The fields of a question, without its prompt.
+    fn kind(&mut self, kind: &QuestionKind, depth: usize) {
+        match kind {
+            QuestionKind::Unit => {}
+
+            QuestionKind::Input(_) | QuestionKind::Masked(_) => self.line("#field()"),
+
+            QuestionKind::Autocomplete(autocomplete_q) => {
+                let suggestions: Vec<String> =
+                    autocomplete_q.completions("").into_iter().take(5).collect();
+                if suggestions.is_empty() {
+                    self.line("#field()");
+                } else {
+                    let hint = format!("(e.g. {})", suggestions.join(", "));
+                    self.line(&format!("#field() #hint({})", string(&hint)));
+                }
+            }
+
+            QuestionKind::Multiline(_) => self.line("#field(height: 5em)"),
+
+            QuestionKind::Int(int_q) => {
+                let min = int_q.min.map(|min| int_q.format(min));
+                let max = int_q.max.map(|max| int_q.format(max));
+                let hint = bounds_hint("whole number", min, max);
+                self.line(&format!("#field(width: 1.5in) #hint({})", string(&hint)));
+            }
+
+            QuestionKind::Float(float_q) => {
+                let min = float_q.min.map(|min| float_q.format(min));
+                let max = float_q.max.map(|max| float_q.format(max));
+                let hint = bounds_hint("decimal", min, max);
+                self.line(&format!("#field(width: 1.5in) #hint({})", string(&hint)));
+            }
+
+            QuestionKind::Currency(currency_q) => {
+                let mut line = format!("#{} #field(width: 1.5in)", string(currency_q.symbol()));
+                let min = currency_q.min.map(|minor| currency_q.format(minor));
+                let max = currency_q.max.map(|minor| currency_q.format(minor));
+                if min.is_some() || max.is_some() {
+                    let hint = bounds_hint("amount", min, max);
+                    line.push_str(&format!(" #hint({})", string(&hint)));
+                }
+                self.line(&line);
+            }
+
+            QuestionKind::Color(_) => self.line("#field(width: 1in) #hint(\"(#rrggbb)\")"),
+
+            QuestionKind::Path(path_q) => match path_q.hint() {
+                Some(hint) => {
+                    self.line(&format!("#field() #hint({})", string(&format!("({hint})"))))
+                }
+                None => self.line("#field()"),
+            },
+
+            QuestionKind::List(list_q) => {
+                let hint = match &list_q.element_kind {
+                    ListElementKind::String => "(text)".to_string(),
+                    ListElementKind::Int { min, max } => bounds_hint("whole numbers", *min, *max),
+                    ListElementKind::Float { min, max } => bounds_hint("decimals", *min, *max),
+                };
+                self.line(&format!("#hint({})", string(&hint)));
+                let count = self
+                    .options
+                    .list_fields
+                    .max(list_q.min_items.unwrap_or(0))
+                    .min(list_q.max_items.unwrap_or(usize::MAX))
+                    .max(1);
+                for number in 1..=count {
+                    self.line(&format!("#item({number})"));
+                }
+            }
+
+            QuestionKind::Confirm(_) => self.line("#choices(radio(\"Yes\"), radio(\"No\"))"),
+
+            QuestionKind::Select(select_q) => {
+                let labels = select_q.options.iter().map(|option| option.label.as_str());
+                self.line(&choices("radio", labels));
+            }
+
+            QuestionKind::Rating(rating_q) => {
+                let points: Vec<String> = rating_q.points().map(|p| p.to_string()).collect();
+                let row = choices("radio", points.iter().map(String::as_str));
+                match &rating_q.anchors {
+                    Some((low, high)) => self.line(&format!(
+                        "#hint({}) #h(0.6em) {row} #h(0.6em) #hint({})",
+                        string(low),
+                        string(high)
+                    )),
+                    None => self.line(&row),
+                }
+            }
+
+            QuestionKind::Matrix(matrix_q) => {
+                self.line("#table(");
+                self.indent += 1;
+                self.line(&format!(
+                    "columns: (auto,) + (1fr,) * {},",
+                    matrix_q.columns.len()
+                ));
+                self.line("align: (x, y) => if x == 0 { left } else { center },");
+                self.line("stroke: none,");
+                let header: Vec<String> = matrix_q.columns.iter().map(|c| string(c)).collect();
+                self.line(&format!("[], {},", header.join(", ")));
+                for row in &matrix_q.rows {
+                    let marks = vec!["mark(round: true)"; matrix_q.columns.len()];
+                    self.line(&format!("{}, {},", string(row), marks.join(", ")));
+                }
+                self.indent -= 1;
+                self.line(")");
+            }
+
+            QuestionKind::OneOf(one_of) => {
+                let names = one_of.variants.iter().map(|v| v.name.as_str());
+                self.line(&choices("radio", names));
+                self.variants(&one_of.variants, depth);
+            }
+
+            QuestionKind::AnyOf(any_of) => {
+                let names = any_of.variants.iter().map(|v| v.name.as_str());
+                self.line(&choices("checkbox", names));
+                self.variants(&any_of.variants, depth);
+            }
+
+            QuestionKind::AllOf(all_of) => {
+                for nested in all_of.questions() {
+                    self.question(nested, depth);
+                }
+            }
+        }
+    }
+
+    /// A shaded block with the fields of every variant that has any.
+    fn variants(&mut self, variants: &[Variant], depth: usize) {
+        for variant in variants {
+            if matches!(variant.kind, QuestionKind::Unit) {
+                continue;
+            }
+            self.open(&format!("#group({})[", depth + 1));
+            let condition = format!("If {}:", variant.name);
+            self.line(&format!("#condition({})", string(&condition)));
+            self.kind(&variant.kind, depth + 1);
+            self.close();
+        }
+    }
+}
+
+/// A row of radio marks or checkboxes, e.g. `#choices(radio("a"), radio("b"))`.
+fn choices<'a>(mark: &str, labels: impl Iterator<Item = &'a str>) -> String {
+    let items: Vec<String> = labels
+        .map(|label| format!("{mark}({})", string(label)))
+        .collect();
+    format!("#choices({})", items.join(", "))
+}
+
+/// A hint naming the expected value and its bounds, e.g. `(whole number, 1–10)`.
+fn bounds_hint<N: std::fmt::Display>(what: &str, min: Option<N>, max: Option<N>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("({what}, {min}–{max})"),
+        (Some(min), None) => format!("({what}, at least {min})"),
+        (None, Some(max)) => format!("({what}, at most {max})"),
+        (None, None) => format!("({what})"),
+    }
+}
+
+/// A Typst string literal. Strings are shown as plain text, so markup characters in
+/// prompts and labels need no escaping.
+fn string(s: &str) -> String {
+    let mut literal = String::with_capacity(s.len() + 2);
+    literal.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '"' => literal.push_str("\\\""),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            _ => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{
+        AllOfQuestion, AnyOfQuestion, AutocompleteQuestion, ColorQuestion, ConfirmQuestion,
+        CurrencyQuestion, FloatQuestion, InputQuestion, IntQuestion, ListQuestion, MaskedQuestion,
+        MatrixQuestion, MultilineQuestion, OneOfQuestion, PathQuestion, RatingQuestion,
+        SelectOption, SelectQuestion,
+    };
+
+    const PROMPT: &str = r#"Say "hi" \ #1 *now* $5 <b>"#;
+    const LITERAL: &str = r#""Say \"hi\" \\ #1 *now* $5 <b>""#;
+
+    fn kinds() -> Vec<QuestionKind> {
+        let variants = || {
+            vec![
+                Variant::unit("a_b"),
+                Variant::new("c\"d", QuestionKind::Input(InputQuestion::new())),
+            ]
+        };
+        vec![
+            QuestionKind::Unit,
+            QuestionKind::Input(InputQuestion::new()),
+            QuestionKind::Autocomplete(AutocompleteQuestion::from_list(["50%", "#1"])),
+            QuestionKind::Multiline(MultilineQuestion::new()),
+            QuestionKind::Masked(MaskedQuestion::new()),
+            QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(9))),
+            QuestionKind::Float(FloatQuestion::with_bounds(Some(0.5), None)),
+            QuestionKind::Currency(CurrencyQuestion::new("USD").with_bounds(Some(0), Some(500))),
+            QuestionKind::Color(ColorQuestion::new()),
+            QuestionKind::Path(PathQuestion::new()),
+            QuestionKind::Rating(RatingQuestion::likert("no", "yes", 5)),
+            QuestionKind::Matrix(MatrixQuestion::new(
+                vec!["row".to_string()],
+                vec!["a".to_string(), "b".to_string()],
+            )),
+            QuestionKind::Confirm(ConfirmQuestion::new()),
+            QuestionKind::Select(SelectQuestion::new(vec![
+                SelectOption::new("a", "Tom & Jerry"),
+                SelectOption::new("b", "Other"),
+            ])),
+            QuestionKind::List(ListQuestion::ints()),
+            QuestionKind::AnyOf(AnyOfQuestion::new(variants())),
+            QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                "inner",
+                PROMPT,
+                QuestionKind::Input(InputQuestion::new()),
+            )])),
+            QuestionKind::OneOf(OneOfQuestion::new(variants())),
+        ]
+    }
+
+    fn render(kind: QuestionKind) -> String {
+        let definition = SurveyDefinition::new(vec![Question::new("field", PROMPT, kind)]);
+        to_typst_form(&definition)
+    }
+
+    #[test]
+    fn text_is_passed_as_string_literals() {
+        assert_eq!(string(PROMPT), LITERAL);
+        assert_eq!(string("a\nb"), r#""a\nb""#);
+        for kind in kinds() {
+            let typst = render(kind.clone());
+            assert!(
+                typst.contains(&format!("#prompt({LITERAL})")),
+                "{kind:?}:\n{typst}"
+            );
+        }
+    }
+
+    #[test]
+    fn every_kind_gets_fields() {
+        let typst = to_typst_form(&SurveyDefinition::new(
+            kinds()
+                .into_iter()
+                .enumerate()
+                .map(|(i, kind)| Question::new(format!("q{i}"), "Prompt:", kind))
+                .collect(),
+        ));
+        for expected in [
+            "#field() #hint(\"(e.g. #1, 50%)\")",
+            "#field(height: 5em)",
+            "#field(width: 1.5in) #hint(\"(whole number, 0–9)\")",
+            "#field(width: 1.5in) #hint(\"(decimal, at least 0.5)\")",
+            "#\"$\" #field(width: 1.5in) #hint(\"(amount, $0.00–$5.00)\")",
+            "#field(width: 1in)",
+            "#hint(\"no\") #h(0.6em) #choices(radio(\"1\"),",
+            "[], \"a\", \"b\",",
+            "\"row\", mark(round: true), mark(round: true),",
+            "#choices(radio(\"Yes\"), radio(\"No\"))",
+            "#choices(radio(\"Tom & Jerry\"), radio(\"Other\"))",
+            "#hint(\"(whole numbers)\")\n  #item(1)\n  #item(2)\n  #item(3)\n",
+            "#choices(checkbox(\"a_b\"), checkbox(\"c\\\"d\"))",
+            "#choices(radio(\"a_b\"), radio(\"c\\\"d\"))",
+            "#condition(\"If c\\\"d:\")\n    #field()",
+        ] {
+            assert!(typst.contains(expected), "missing {expected}:\n{typst}");
+        }
+        assert_eq!(typst.matches("#group(1)[").count(), 3);
+    }
+
+    #[test]
+    fn nested_groups_are_shaded_deeper() {
+        let inner = Question::new(
+            "inner",
+            "Inner:",
+            QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                "name",
+                "Name:",
+                QuestionKind::Input(InputQuestion::new()),
+            )])),
+        );
+        let typst = render(QuestionKind::AllOf(AllOfQuestion::new(vec![inner])));
+        assert!(typst.contains("  #group(1)[\n"));
+        assert!(typst.contains("      #group(2)[\n        #question[\n"));
+
+        // An enum field is printed without a block around its choice
+        let choice = Question::new(
+            "",
+            "",
+            QuestionKind::OneOf(OneOfQuestion::new(vec![Variant::unit("A")])),
+        );
+        let typst = render(QuestionKind::AllOf(AllOfQuestion::new(vec![choice])));
+        assert!(!typst.contains("#group("));
+        assert!(typst.contains("#choices(radio(\"A\"))"));
+    }
+
+    #[test]
+    fn options_set_up_the_page() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "values",
+            "Values:",
+            QuestionKind::List(ListQuestion::strings()),
+        )])
+        .with_prelude("Before")
+        .with_epilogue("After");
+
+        let typst = to_typst_form(&definition);
+        assert!(typst.contains("#set page(paper: \"a4\", margin: 2cm)"));
+        assert!(typst.contains("#set text(size: 11pt)"));
+        assert!(typst.contains("#let accent = rgb(\"#1f4e79\")"));
+        assert!(typst.contains("#block(above: 1.2em, \"Before\")"));
+        assert!(typst.contains("#block(above: 2em, \"After\")"));
+        assert!(!typst.contains("#set document"));
+
+        let options = TypstOptions::new()
+            .with_title("Sign \"up\"")
+            .with_paper_size(PaperSize::Letter)
+            .with_margin("1in")
+            .with_font("Inter")
+            .with_font_size("10pt")
+            .with_theme(Theme::grayscale())
+            .with_accent_color("#aa0000")
+            .with_field_width("4in")
+            .with_list_fields(5);
+        let typst = to_typst_form_with_options(&definition, &options);
+        assert!(typst.contains("#set document(title: \"Sign \\\"up\\\"\")"));
+        assert!(typst.contains("#set page(paper: \"us-letter\", margin: 1in)"));
+        assert!(typst.contains("#set text(font: \"Inter\", size: 10pt)"));
+        assert!(typst.contains("#let accent = rgb(\"#aa0000\")"));
+        assert!(typst.contains("#let shade = rgb(\"#eeeeee\")"));
+        assert!(typst.contains("#let field(width: 4in,"));
+        assert!(typst.contains("fill: accent, \"Sign \\\"up\\\"\"))"));
+        assert!(typst.contains("#item(5)"));
+        assert!(!typst.contains("#item(6)"));
+    }
+}
@@ -0,0 +1,41 @@
+//! # elicitor-doc-typst
+//!
+//! Typst document generator for elicitor.
+//!
+//! This crate writes printable forms as [Typst](https://typst.app) source. Text questions
+//! become boxes to write in, yes/no questions, selections and enums rows of radio
+//! marks, and multi-select questions rows of checkboxes. The fields of an enum variant
+//! follow in a shaded block headed "If <variant>:".
+//!
+//! [`TypstOptions`] sets the title, the theme colors and the page setup. Compile the
+//! output with `typst compile form.typ`.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use elicitor::Survey;
+//! use elicitor_doc_typst::{PaperSize, TypstOptions, to_typst_form_with_options};
+//!
+//! #[derive(Survey)]
+//! struct UserProfile {
+//!     #[ask("What is your name?")]
+//!     name: String,
+//!
+//!     #[ask("How old are you?")]
+//!     #[min(0)]
+//!     #[max(150)]
+//!     age: i64,
+//! }
+//!
+//! fn main() {
+//!     let options = TypstOptions::new()
+//!         .with_title("User Profile")
+//!         .with_paper_size(PaperSize::Letter);
+//!     let typst = to_typst_form_with_options(&UserProfile::survey(), &options);
+//!     std::fs::write("form.typ", typst).unwrap();
+//! }
+//! ```
+
+mod generator;
+
+pub use generator::{PaperSize, Theme, TypstOptions, to_typst_form, to_typst_form_with_options};
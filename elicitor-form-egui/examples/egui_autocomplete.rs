@@ -0,0 +1,17 @@
+//! Autocomplete example
+//!
+//! Demonstrates:
+//! - #[autocomplete] with a fixed list of suggestions
+//! - #[autocomplete] with a completion function that sees the current input
+//!
+//! Run with: cargo run --example egui_autocomplete
+
+use elicitor_form_egui::EguiBackend;
+use example_surveys::EditorSetup;
+
+fn main() -> anyhow::Result<()> {
+    let backend = EguiBackend::new();
+    let result = EditorSetup::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
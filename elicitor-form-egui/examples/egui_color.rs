@@ -0,0 +1,20 @@
+//! Color question example
+//!
+//! Demonstrates:
+//! - elicitor::Color fields
+//! - A color picker button
+//! - Suggesting colors with with_suggestions
+//!
+//! Run with: cargo run --example egui_color
+
+use elicitor_form_egui::EguiBackend;
+use example_surveys::EditorTheme;
+
+fn main() -> anyhow::Result<()> {
+    let backend = EguiBackend::new();
+    let result = EditorTheme::builder()
+        .with_suggestions(&EditorTheme::dark())
+        .run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
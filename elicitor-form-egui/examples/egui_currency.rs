@@ -0,0 +1,17 @@
+//! Currency question example
+//!
+//! Demonstrates:
+//! - #[currency("USD")] on integer fields holding cents
+//! - Amounts are reformatted when the field loses focus
+//!
+//! Run with: cargo run --example egui_currency
+
+use elicitor_form_egui::EguiBackend;
+use example_surveys::MortgageApplication;
+
+fn main() -> anyhow::Result<()> {
+    let backend = EguiBackend::new();
+    let result = MortgageApplication::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! Matrix question example
+//!
+//! Demonstrates:
+//! - HashMap<RowEnum, ColumnEnum> for a grid question
+//! - One radio button per cell
+//!
+//! Run with: cargo run --example egui_matrix
+
+use elicitor_form_egui::EguiBackend;
+use example_surveys::CourseEvaluation;
+
+fn main() -> anyhow::Result<()> {
+    let backend = EguiBackend::new();
+    let result = CourseEvaluation::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
@@ -3,8 +3,9 @@
 //! Demonstrates:
 //! - #[multiselect] attribute for Vec<Enum> fields
 //! - Allows selecting multiple enum variants at once
+//! - Follow-up questions for checked variants that carry data
 //!
-//! Run with: cargo run --example multiselect
+//! Run with: cargo run -p elicitor-form-egui --example egui_multiselect
 
 use elicitor_form_egui::EguiBackend;
 use example_surveys::DeveloperProfile;
@@ -2,47 +2,119 @@
 
 use eframe::egui;
 use elicitor::{
-    AllOfQuestion, AnyOfQuestion, DefaultValue, FloatQuestion, IntQuestion, ListElementKind,
-    ListQuestion, OneOfQuestion, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition, Variant,
+    AllOfQuestion, AnyOfQuestion, COMMENT_KEY, ColorQuestion, CurrencyQuestion, DefaultValue,
+    FloatQuestion, IntQuestion, ListElementKind, ListQuestion, MatrixQuestion, OneOfQuestion,
+    PathQuestion, Question, QuestionKind, RatingQuestion, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SelectQuestion, SurveyDefinition, SurveyError,
+    TextChecker, TextIssue, Variant,
 };
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
+use crate::panel::FormStatus;
+
+/// Choice lists at least this long are shown as a drop-down with a search box.
+const DROPDOWN_MIN_CHOICES: usize = 8;
+
+/// Field validator used by the form while the user is typing.
+pub(crate) type FieldValidator<'v> =
+    Box<dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String> + 'v>;
+
 /// Error type for the Egui backend.
 #[derive(Debug, Error)]
 pub enum EguiError {
     /// User cancelled the survey (closed the window).
+    ///
+    /// Holds the answers given before cancelling.
     #[error("Survey cancelled by user")]
-    Cancelled,
+    Cancelled { partial: Box<Responses> },
 
     /// An error occurred in the egui/eframe backend.
     #[error("Egui error: {0}")]
     EguiError(String),
 }
 
+impl EguiError {
+    /// The answers given before the user cancelled, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            _ => None,
+        }
+    }
+}
+
+impl From<EguiError> for SurveyError {
+    fn from(err: EguiError) -> Self {
+        match err {
+            EguiError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
+        }
+    }
+}
+
+/// Width of the window when none is given.
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_WIDTH: f32 = 500.0;
+/// Height the window opens with before it is fitted to the form.
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_HEIGHT: f32 = 600.0;
+/// Bounds for the height of a window fitted to the form, unless limits are given.
+#[cfg(not(target_arch = "wasm32"))]
+const FIT_MIN_HEIGHT: f32 = 200.0;
+#[cfg(not(target_arch = "wasm32"))]
+const FIT_MAX_HEIGHT: f32 = 800.0;
+/// Key under which eframe stores the window geometry.
+#[cfg(not(target_arch = "wasm32"))]
+const STORAGE_WINDOW_KEY: &str = "window";
+
 /// Builder/configuration for the Egui backend.
+#[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug, Clone)]
 pub struct EguiBackend {
     /// Window title.
     title: String,
-    /// Window size [width, height].
-    window_size: [f32; 2],
+    /// Window size [width, height]. Without one, the window is fitted to the form.
+    window_size: Option<[f32; 2]>,
+    /// Smallest size the window can take.
+    min_window_size: Option<[f32; 2]>,
+    /// Largest size the window can take.
+    max_window_size: Option<[f32; 2]>,
+    /// Whether the user can resize the window.
+    resizable: bool,
+    /// Whether the window stays above other windows.
+    always_on_top: bool,
+    /// Whether size and position are restored from the previous run.
+    persist_window: bool,
+    /// Checker run on multiline answers when the user leaves the field.
+    text_checker: Option<Arc<dyn TextChecker>>,
+    /// High-contrast mode as last switched in a form, shared by clones. Until then,
+    /// forms start in the mode set with `elicitor::set_high_contrast`.
+    high_contrast: Arc<Mutex<Option<bool>>>,
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl Default for EguiBackend {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl EguiBackend {
     /// Create a new Egui backend with default settings.
     pub fn new() -> Self {
         Self {
             title: "Survey".to_string(),
-            window_size: [500.0, 600.0],
+            window_size: None,
+            min_window_size: None,
+            max_window_size: None,
+            resizable: true,
+            always_on_top: false,
+            persist_window: true,
+            text_checker: None,
+            high_contrast: Arc::default(),
         }
     }
 
@@ -52,11 +124,82 @@ impl EguiBackend {
         self
     }
 
-    /// Set the window size.
+    /// Set the window size. Without it, the window opens as tall as the form
+    /// needs, within the size limits.
     pub fn with_window_size(mut self, size: [f32; 2]) -> Self {
-        self.window_size = size;
+        self.window_size = Some(size);
+        self
+    }
+
+    /// Set the smallest size the window can take.
+    pub fn with_min_window_size(mut self, size: [f32; 2]) -> Self {
+        self.min_window_size = Some(size);
         self
     }
+
+    /// Set the largest size the window can take.
+    pub fn with_max_window_size(mut self, size: [f32; 2]) -> Self {
+        self.max_window_size = Some(size);
+        self
+    }
+
+    /// Set whether the user can resize the window (default: `true`).
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Keep the window above other windows.
+    pub fn with_always_on_top(mut self) -> Self {
+        self.always_on_top = true;
+        self
+    }
+
+    /// Set whether the window reopens with the size and position it had when
+    /// last closed (default: `true`). The geometry is stored per window title
+    /// in the platform's application data directory.
+    pub fn with_persistence(mut self, persist: bool) -> Self {
+        self.persist_window = persist;
+        self
+    }
+
+    /// Check multiline answers with `checker` when the user leaves the field.
+    ///
+    /// Findings are shown below the field as hints; they don't keep the form from
+    /// being submitted.
+    pub fn with_text_checker(mut self, checker: impl TextChecker + 'static) -> Self {
+        self.text_checker = Some(Arc::new(checker));
+        self
+    }
+
+    fn native_options(&self) -> eframe::NativeOptions {
+        let mut viewport = egui::ViewportBuilder::default()
+            .with_title(self.title.clone())
+            .with_inner_size(self.window_size.unwrap_or([DEFAULT_WIDTH, DEFAULT_HEIGHT]))
+            .with_resizable(self.resizable);
+        if let Some(size) = self.min_window_size {
+            viewport = viewport.with_min_inner_size(size);
+        }
+        if let Some(size) = self.max_window_size {
+            viewport = viewport.with_max_inner_size(size);
+        }
+        if self.always_on_top {
+            viewport = viewport.with_always_on_top();
+        }
+        eframe::NativeOptions {
+            viewport,
+            persist_window: self.persist_window,
+            ..Default::default()
+        }
+    }
+
+    /// Height of a window that shows the whole form, given its current height
+    /// and how much taller the form is than the room it has.
+    fn fitted_height(&self, height: f32, overflow: f32) -> f32 {
+        let min = self.min_window_size.map_or(FIT_MIN_HEIGHT, |[_, h]| h);
+        let max = self.max_window_size.map_or(FIT_MAX_HEIGHT, |[_, h]| h);
+        (height + overflow).clamp(min, max.max(min))
+    }
 }
 
 /// State for a single field in the form.
@@ -72,6 +215,14 @@ enum FieldState {
     Int { value: String, parsed: Option<i64> },
     /// Float input.
     Float { value: String, parsed: Option<f64> },
+    /// Money amount, parsed into minor units.
+    Currency { value: String, parsed: Option<i64> },
+    /// RGB color from the color picker.
+    Color { rgb: [u8; 3] },
+    /// Point on a rating scale.
+    Rating { value: Option<i64> },
+    /// One column per matrix row.
+    Matrix { selected: Vec<Option<usize>> },
     /// Boolean toggle.
     Bool { value: bool },
     /// List of values (comma-separated input).
@@ -79,6 +230,11 @@ enum FieldState {
         value: String,
         element_kind: ListElementKind,
     },
+    /// Single selection from runtime-provided options (Select).
+    Select {
+        selected: Option<usize>,
+        values: Vec<String>,
+    },
     /// Single selection from options (OneOf).
     OneOf {
         selected: Option<usize>,
@@ -100,6 +256,16 @@ impl FieldState {
             FieldState::Text { value, .. } => Some(ResponseValue::String(value.clone())),
             FieldState::Int { parsed, .. } => parsed.map(ResponseValue::Int),
             FieldState::Float { parsed, .. } => parsed.map(ResponseValue::Float),
+            FieldState::Currency { parsed, .. } => parsed.map(ResponseValue::Int),
+            FieldState::Color { rgb: [r, g, b] } => Some(ResponseValue::String(
+                elicitor::Color::rgb(*r, *g, *b).to_hex(),
+            )),
+            FieldState::Rating { value } => value.map(ResponseValue::Int),
+            FieldState::Matrix { selected } => selected
+                .iter()
+                .copied()
+                .collect::<Option<Vec<_>>>()
+                .map(ResponseValue::ChosenVariants),
             FieldState::Bool { value } => Some(ResponseValue::Bool(*value)),
             FieldState::List {
                 value,
@@ -127,29 +293,98 @@ impl FieldState {
                     }
                 }
             }
+            FieldState::Select { selected, values } => selected
+                .and_then(|idx| values.get(idx))
+                .map(|v| ResponseValue::String(v.clone())),
             FieldState::OneOf { selected, .. } => selected.map(ResponseValue::ChosenVariant),
             FieldState::AnyOf { selected, .. } => {
-                let indices: Vec<usize> = selected
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(i, &s)| if s { Some(i) } else { None })
-                    .collect();
-                Some(ResponseValue::ChosenVariants(indices))
+                Some(ResponseValue::ChosenVariants(chosen(selected)))
+            }
+        }
+    }
+
+    /// Create the state for a currency question, showing the default amount formatted.
+    fn currency(currency_q: &CurrencyQuestion, default_value: Option<&ResponseValue>) -> Self {
+        let parsed = default_value
+            .and_then(|v| v.as_int())
+            .or(currency_q.default);
+        FieldState::Currency {
+            value: parsed
+                .map(|minor| currency_q.format(minor))
+                .unwrap_or_default(),
+            parsed,
+        }
+    }
+
+    /// Create the state for a color question, starting from the suggested or default color.
+    fn color(color_q: &ColorQuestion, default_value: Option<&ResponseValue>) -> Self {
+        let color = default_value
+            .and_then(|v| v.as_str())
+            .and_then(|hex| hex.parse().ok())
+            .or(color_q.default)
+            .unwrap_or_default();
+        FieldState::Color {
+            rgb: [color.r, color.g, color.b],
+        }
+    }
+
+    /// Create the state for a path question, a text field starting from the suggested path.
+    fn path(path_q: &PathQuestion, default_value: Option<&ResponseValue>) -> Self {
+        FieldState::Text {
+            value: default_value
+                .and_then(|v| v.as_str().map(String::from))
+                .or_else(|| path_q.default.clone())
+                .unwrap_or_default(),
+            is_password: false,
+            is_multiline: false,
+        }
+    }
+
+    /// Create the state for a matrix question, filling in the suggested answer if offered.
+    fn matrix(matrix_q: &MatrixQuestion, default_value: Option<&ResponseValue>) -> Self {
+        let mut selected = vec![None; matrix_q.rows.len()];
+        if let Some(ResponseValue::ChosenVariants(columns)) = default_value {
+            for (slot, &column) in selected.iter_mut().zip(columns) {
+                *slot = (column < matrix_q.columns.len()).then_some(column);
             }
         }
+        FieldState::Matrix { selected }
+    }
+
+    /// Create the state for a select question, preselecting the suggested value if offered.
+    fn select(select_q: &SelectQuestion, default_value: Option<&ResponseValue>) -> Self {
+        let selected = default_value
+            .and_then(|v| v.as_str())
+            .and_then(|v| select_q.position(v))
+            .or(select_q.default);
+        FieldState::Select {
+            selected,
+            values: select_q.options.iter().map(|o| o.value.clone()).collect(),
+        }
     }
 }
 
 /// The form state for the entire survey.
-struct FormState {
+pub(crate) struct FormState {
     /// Map from response path to field state.
     fields: HashMap<ResponsePath, FieldState>,
     /// Validation errors for each field.
     errors: HashMap<ResponsePath, String>,
+    /// Findings of the text checker for multiline fields, as of the last edit.
+    findings: HashMap<ResponsePath, Vec<TextIssue>>,
+    /// Free-text comments, keyed by the path of the question they belong to.
+    comments: HashMap<ResponsePath, String>,
+    /// Search text typed into the drop-down of a choice field.
+    filters: HashMap<ResponsePath, String>,
+    /// How much taller the questions are than the area showing them, negative
+    /// when they leave room.
+    overflow: f32,
     /// Whether the form has been submitted.
     submitted: bool,
     /// Whether the window was closed (cancelled).
     cancelled: bool,
+    /// Whether the form is shown in high-contrast mode, switched by its own toggle.
+    high_contrast: bool,
     /// Prelude message.
     prelude: Option<String>,
     /// Epilogue message.
@@ -163,8 +398,13 @@ impl FormState {
         let mut state = Self {
             fields: HashMap::new(),
             errors: HashMap::new(),
+            findings: HashMap::new(),
+            comments: HashMap::new(),
+            filters: HashMap::new(),
+            overflow: 0.0,
             submitted: false,
             cancelled: false,
+            high_contrast: elicitor::high_contrast_enabled(),
             prelude: definition.prelude.clone(),
             epilogue: definition.epilogue.clone(),
             definition,
@@ -205,6 +445,20 @@ impl FormState {
                     },
                 );
             }
+            QuestionKind::Autocomplete(autocomplete_q) => {
+                let default = default_value
+                    .and_then(|v| v.as_str().map(String::from))
+                    .or_else(|| autocomplete_q.default.clone())
+                    .unwrap_or_default();
+                self.fields.insert(
+                    path,
+                    FieldState::Text {
+                        value: default,
+                        is_password: false,
+                        is_multiline: false,
+                    },
+                );
+            }
             QuestionKind::Multiline(multiline_q) => {
                 let default = default_value
                     .and_then(|v| v.as_str().map(String::from))
@@ -233,9 +487,9 @@ impl FormState {
                 let default = default_value
                     .and_then(|v| v.as_int())
                     .or(int_q.default)
-                    .map(|i| i.to_string())
+                    .map(|i| int_q.format(i))
                     .unwrap_or_default();
-                let parsed = default.parse().ok();
+                let parsed = int_q.parse(&default).ok();
                 self.fields.insert(
                     path,
                     FieldState::Int {
@@ -244,13 +498,32 @@ impl FormState {
                     },
                 );
             }
+            QuestionKind::Currency(currency_q) => {
+                self.fields
+                    .insert(path, FieldState::currency(currency_q, default_value));
+            }
+            QuestionKind::Color(color_q) => {
+                self.fields
+                    .insert(path, FieldState::color(color_q, default_value));
+            }
+            QuestionKind::Path(path_q) => {
+                self.fields
+                    .insert(path, FieldState::path(path_q, default_value));
+            }
+            QuestionKind::Rating(rating_q) => {
+                let value = default_value
+                    .and_then(|v| v.as_int())
+                    .or(rating_q.default)
+                    .filter(|&v| rating_q.contains(v));
+                self.fields.insert(path, FieldState::Rating { value });
+            }
             QuestionKind::Float(float_q) => {
                 let default = default_value
                     .and_then(|v| v.as_float())
                     .or(float_q.default)
-                    .map(|f| f.to_string())
+                    .map(|f| float_q.format(f))
                     .unwrap_or_default();
-                let parsed = default.parse().ok();
+                let parsed = float_q.parse(&default).ok();
                 self.fields.insert(
                     path,
                     FieldState::Float {
@@ -266,6 +539,14 @@ impl FormState {
                 self.fields
                     .insert(path, FieldState::Bool { value: default });
             }
+            QuestionKind::Select(select_q) => {
+                self.fields
+                    .insert(path, FieldState::select(select_q, default_value));
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                self.fields
+                    .insert(path, FieldState::matrix(matrix_q, default_value));
+            }
             QuestionKind::List(list_q) => {
                 self.fields.insert(
                     path,
@@ -286,7 +567,7 @@ impl FormState {
 
                 // Initialize nested fields for all variants
                 for variant in &one_of.variants {
-                    self.init_variant_state(&variant, &path);
+                    self.ensure_variant_fields(variant, &path);
                 }
             }
             QuestionKind::AnyOf(any_of) => {
@@ -309,13 +590,13 @@ impl FormState {
                     }
                     sel
                 };
+                // Initialize the follow-up fields of the preselected items
+                for (item_idx, variant_idx) in chosen(&selected).into_iter().enumerate() {
+                    let item_path = path.child(&item_idx.to_string());
+                    self.ensure_variant_fields(&any_of.variants[variant_idx], &item_path);
+                }
                 self.fields
                     .insert(path.clone(), FieldState::AnyOf { selected, variants });
-
-                // Initialize nested fields for all variants (for struct variants)
-                for variant in &any_of.variants {
-                    self.init_variant_state(&variant, &path);
-                }
             }
             QuestionKind::AllOf(all_of) => {
                 // Recursively initialize nested questions
@@ -326,151 +607,123 @@ impl FormState {
         }
     }
 
-    fn init_variant_state(&mut self, variant: &Variant, parent_path: &ResponsePath) {
+    /// Ensure fields exist for a variant at the given path.
+    /// This is called dynamically when AnyOf items are selected.
+    fn ensure_variant_fields(&mut self, variant: &Variant, parent_path: &ResponsePath) {
         match &variant.kind {
+            QuestionKind::Unit => {}
             QuestionKind::AllOf(all_of) => {
                 for nested_q in all_of.questions() {
-                    self.init_question_state(nested_q, Some(parent_path));
+                    self.ensure_question_fields(nested_q, Some(parent_path));
                 }
             }
             QuestionKind::Input(input_q) => {
                 let path = parent_path.child(&variant.name);
-                self.fields.insert(
-                    path,
-                    FieldState::Text {
-                        value: input_q.default.clone().unwrap_or_default(),
-                        is_password: false,
-                        is_multiline: false,
-                    },
-                );
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: input_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: false,
+                });
+            }
+            QuestionKind::Autocomplete(autocomplete_q) => {
+                let path = parent_path.child(&variant.name);
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: autocomplete_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: false,
+                });
+            }
+            QuestionKind::Multiline(multiline_q) => {
+                let path = parent_path.child(&variant.name);
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: multiline_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: true,
+                });
+            }
+            QuestionKind::Masked(_) => {
+                let path = parent_path.child(&variant.name);
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: String::new(),
+                    is_password: true,
+                    is_multiline: false,
+                });
             }
             QuestionKind::Int(int_q) => {
                 let path = parent_path.child(&variant.name);
-                let default = int_q.default.map(|i| i.to_string()).unwrap_or_default();
-                let parsed = default.parse().ok();
-                self.fields.insert(
-                    path,
+                self.fields.entry(path).or_insert_with(|| {
+                    let default = int_q.default.map(|i| int_q.format(i)).unwrap_or_default();
+                    let parsed = int_q.parse(&default).ok();
                     FieldState::Int {
                         value: default,
                         parsed,
-                    },
-                );
+                    }
+                });
             }
-            QuestionKind::Float(float_q) => {
+            QuestionKind::Currency(currency_q) => {
                 let path = parent_path.child(&variant.name);
-                let default = float_q.default.map(|f| f.to_string()).unwrap_or_default();
-                let parsed = default.parse().ok();
-                self.fields.insert(
-                    path,
-                    FieldState::Float {
-                        value: default,
-                        parsed,
-                    },
-                );
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::currency(currency_q, None));
             }
-            _ => {}
-        }
-    }
-
-    /// Ensure fields exist for a variant at the given path.
-    /// This is called dynamically when AnyOf items are selected.
-    fn ensure_variant_fields(&mut self, variant: &Variant, parent_path: &ResponsePath) {
-        match &variant.kind {
-            QuestionKind::Unit => {}
-            QuestionKind::AllOf(all_of) => {
-                for nested_q in all_of.questions() {
-                    self.ensure_question_fields(nested_q, Some(parent_path));
-                }
+            QuestionKind::Color(color_q) => {
+                let path = parent_path.child(&variant.name);
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::color(color_q, None));
             }
-            QuestionKind::Input(input_q) => {
+            QuestionKind::Path(path_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: input_q.default.clone().unwrap_or_default(),
-                            is_password: false,
-                            is_multiline: false,
-                        },
-                    );
-                }
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::path(path_q, None));
             }
-            QuestionKind::Multiline(multiline_q) => {
+            QuestionKind::Rating(rating_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: multiline_q.default.clone().unwrap_or_default(),
-                            is_password: false,
-                            is_multiline: true,
-                        },
-                    );
-                }
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::Rating {
+                        value: rating_q.default,
+                    });
             }
-            QuestionKind::Masked(_) => {
+            QuestionKind::Float(float_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: String::new(),
-                            is_password: true,
-                            is_multiline: false,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| {
+                    let default = float_q
+                        .default
+                        .map(|f| float_q.format(f))
+                        .unwrap_or_default();
+                    let parsed = float_q.parse(&default).ok();
+                    FieldState::Float {
+                        value: default,
+                        parsed,
+                    }
+                });
             }
-            QuestionKind::Int(int_q) => {
+            QuestionKind::Confirm(confirm_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    let default = int_q.default.map(|i| i.to_string()).unwrap_or_default();
-                    let parsed = default.parse().ok();
-                    self.fields.insert(
-                        path,
-                        FieldState::Int {
-                            value: default,
-                            parsed,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert(FieldState::Bool {
+                    value: confirm_q.default,
+                });
             }
-            QuestionKind::Float(float_q) => {
+            QuestionKind::Select(select_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    let default = float_q.default.map(|f| f.to_string()).unwrap_or_default();
-                    let parsed = default.parse().ok();
-                    self.fields.insert(
-                        path,
-                        FieldState::Float {
-                            value: default,
-                            parsed,
-                        },
-                    );
-                }
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::select(select_q, None));
             }
-            QuestionKind::Confirm(confirm_q) => {
+            QuestionKind::Matrix(matrix_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Bool {
-                            value: confirm_q.default,
-                        },
-                    );
-                }
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::matrix(matrix_q, None));
             }
             QuestionKind::List(list_q) => {
                 let path = parent_path.child(&variant.name);
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::List {
-                            value: String::new(),
-                            element_kind: list_q.element_kind.clone(),
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::List {
+                    value: String::new(),
+                    element_kind: list_q.element_kind.clone(),
+                });
             }
             QuestionKind::OneOf(one_of) => {
                 let path = parent_path.child(&variant.name);
@@ -501,12 +754,12 @@ impl FormState {
                             selected[idx] = true;
                         }
                     }
+                    for (item_idx, variant_idx) in chosen(&selected).into_iter().enumerate() {
+                        let item_path = path.child(&item_idx.to_string());
+                        self.ensure_variant_fields(&any_of.variants[variant_idx], &item_path);
+                    }
                     self.fields
                         .insert(path.clone(), FieldState::AnyOf { selected, variants });
-                    // Initialize nested fields for all variants
-                    for v in &any_of.variants {
-                        self.ensure_variant_fields(v, &path);
-                    }
                 }
             }
         }
@@ -522,91 +775,104 @@ impl FormState {
         match question.kind() {
             QuestionKind::Unit => {}
             QuestionKind::Input(input_q) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: input_q.default.clone().unwrap_or_default(),
-                            is_password: false,
-                            is_multiline: false,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: input_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: false,
+                });
+            }
+            QuestionKind::Autocomplete(autocomplete_q) => {
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: autocomplete_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: false,
+                });
             }
             QuestionKind::Multiline(multiline_q) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: multiline_q.default.clone().unwrap_or_default(),
-                            is_password: false,
-                            is_multiline: true,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: multiline_q.default.clone().unwrap_or_default(),
+                    is_password: false,
+                    is_multiline: true,
+                });
             }
             QuestionKind::Masked(_) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Text {
-                            value: String::new(),
-                            is_password: true,
-                            is_multiline: false,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| FieldState::Text {
+                    value: String::new(),
+                    is_password: true,
+                    is_multiline: false,
+                });
             }
             QuestionKind::Int(int_q) => {
-                if !self.fields.contains_key(&path) {
-                    let default = int_q.default.map(|i| i.to_string()).unwrap_or_default();
-                    let parsed = default.parse().ok();
-                    self.fields.insert(
-                        path,
-                        FieldState::Int {
-                            value: default,
-                            parsed,
-                        },
-                    );
-                }
+                self.fields.entry(path).or_insert_with(|| {
+                    let default = int_q.default.map(|i| int_q.format(i)).unwrap_or_default();
+                    let parsed = int_q.parse(&default).ok();
+                    FieldState::Int {
+                        value: default,
+                        parsed,
+                    }
+                });
             }
-            QuestionKind::Float(float_q) => {
-                if !self.fields.contains_key(&path) {
-                    let default = float_q.default.map(|f| f.to_string()).unwrap_or_default();
-                    let parsed = default.parse().ok();
-                    self.fields.insert(
-                        path,
-                        FieldState::Float {
-                            value: default,
-                            parsed,
-                        },
-                    );
-                }
+            QuestionKind::Currency(currency_q) => {
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::currency(currency_q, None));
             }
-            QuestionKind::Confirm(confirm_q) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::Bool {
-                            value: confirm_q.default,
-                        },
-                    );
-                }
+            QuestionKind::Color(color_q) => {
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::color(color_q, None));
             }
-            QuestionKind::List(list_q) => {
-                if !self.fields.contains_key(&path) {
-                    self.fields.insert(
-                        path,
-                        FieldState::List {
-                            value: String::new(),
-                            element_kind: list_q.element_kind.clone(),
-                        },
-                    );
-                }
+            QuestionKind::Path(path_q) => {
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::path(path_q, None));
             }
-            QuestionKind::AllOf(all_of) => {
-                for nested_q in all_of.questions() {
-                    self.ensure_question_fields(nested_q, Some(&path));
+            QuestionKind::Rating(rating_q) => {
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::Rating {
+                        value: rating_q.default,
+                    });
+            }
+            QuestionKind::Float(float_q) => {
+                self.fields.entry(path).or_insert_with(|| {
+                    let default = float_q
+                        .default
+                        .map(|f| float_q.format(f))
+                        .unwrap_or_default();
+                    let parsed = float_q.parse(&default).ok();
+                    FieldState::Float {
+                        value: default,
+                        parsed,
+                    }
+                });
+            }
+            QuestionKind::Confirm(confirm_q) => {
+                self.fields.entry(path).or_insert(FieldState::Bool {
+                    value: confirm_q.default,
+                });
+            }
+            QuestionKind::Select(select_q) => {
+                let default_value = question.default().value();
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::select(select_q, default_value));
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                let default_value = question.default().value();
+                self.fields
+                    .entry(path)
+                    .or_insert_with(|| FieldState::matrix(matrix_q, default_value));
+            }
+            QuestionKind::List(list_q) => {
+                self.fields.entry(path).or_insert_with(|| FieldState::List {
+                    value: String::new(),
+                    element_kind: list_q.element_kind.clone(),
+                });
+            }
+            QuestionKind::AllOf(all_of) => {
+                for nested_q in all_of.questions() {
+                    self.ensure_question_fields(nested_q, Some(&path));
                 }
             }
             QuestionKind::OneOf(one_of) => {
@@ -636,12 +902,12 @@ impl FormState {
                             selected[idx] = true;
                         }
                     }
+                    for (item_idx, variant_idx) in chosen(&selected).into_iter().enumerate() {
+                        let item_path = path.child(&item_idx.to_string());
+                        self.ensure_variant_fields(&any_of.variants[variant_idx], &item_path);
+                    }
                     self.fields
                         .insert(path.clone(), FieldState::AnyOf { selected, variants });
-                    // Initialize nested fields for all variants
-                    for v in &any_of.variants {
-                        self.ensure_variant_fields(v, &path);
-                    }
                 }
             }
         }
@@ -674,88 +940,63 @@ impl FormState {
             return;
         }
 
+        if let Some(comment) = self.comments.get(&path).map(|c| c.trim())
+            && !comment.is_empty()
+        {
+            responses.insert(
+                path.child(COMMENT_KEY),
+                ResponseValue::String(comment.to_string()),
+            );
+        }
+
         match question.kind() {
             QuestionKind::Unit => {
                 // Nothing to collect
             }
-            QuestionKind::Input(_) | QuestionKind::Multiline(_) | QuestionKind::Masked(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+            QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
+            | QuestionKind::Multiline(_)
+            | QuestionKind::Masked(_) => {
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
-            QuestionKind::Int(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+            QuestionKind::Int(_)
+            | QuestionKind::Currency(_)
+            | QuestionKind::Color(_)
+            | QuestionKind::Path(_)
+            | QuestionKind::Rating(_) => {
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
             QuestionKind::Float(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
-            QuestionKind::Confirm(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+            QuestionKind::Confirm(_) | QuestionKind::Select(_) | QuestionKind::Matrix(_) => {
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
                 }
             }
             QuestionKind::List(_) => {
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
-                }
-            }
-            QuestionKind::OneOf(one_of) => {
-                if let Some(FieldState::OneOf {
-                    selected: Some(selected),
-                    ..
-                }) = self.fields.get(&path)
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
                 {
-                    let variant_path = path.child(SELECTED_VARIANT_KEY);
-                    responses.insert(variant_path, ResponseValue::ChosenVariant(*selected));
-
-                    // Collect nested data for the selected variant
-                    let variant = &one_of.variants[*selected];
-                    self.collect_variant_responses(variant, &path, responses);
-                }
-            }
-            QuestionKind::AnyOf(any_of) => {
-                if let Some(FieldState::AnyOf { selected, .. }) = self.fields.get(&path) {
-                    let indices: Vec<usize> = selected
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(i, &s)| if s { Some(i) } else { None })
-                        .collect();
-
-                    let variants_path = path.child(SELECTED_VARIANTS_KEY);
-                    responses.insert(
-                        variants_path,
-                        ResponseValue::ChosenVariants(indices.clone()),
-                    );
-
-                    // Collect nested data for each selected variant
-                    for (item_idx, &variant_idx) in indices.iter().enumerate() {
-                        let variant = &any_of.variants[variant_idx];
-                        let item_path = path.child(&item_idx.to_string());
-
-                        // Store which variant this item is
-                        let item_variant_path = item_path.child(SELECTED_VARIANT_KEY);
-                        responses
-                            .insert(item_variant_path, ResponseValue::ChosenVariant(variant_idx));
-
-                        // Collect variant data
-                        self.collect_variant_responses(variant, &item_path, responses);
-                    }
+                    responses.insert(path, value);
                 }
             }
+            QuestionKind::OneOf(one_of) => self.collect_one_of(one_of, &path, responses),
+            QuestionKind::AnyOf(any_of) => self.collect_any_of(any_of, &path, responses),
             QuestionKind::AllOf(all_of) => {
                 for nested_q in all_of.questions() {
                     self.collect_question_responses(nested_q, responses, Some(&path));
@@ -770,6 +1011,7 @@ impl FormState {
         parent_path: &ResponsePath,
         responses: &mut Responses,
     ) {
+        let path = parent_path.child(&variant.name);
         match &variant.kind {
             QuestionKind::Unit => {}
             QuestionKind::AllOf(all_of) => {
@@ -777,20 +1019,120 @@ impl FormState {
                     self.collect_question_responses(nested_q, responses, Some(parent_path));
                 }
             }
-            QuestionKind::Input(_) | QuestionKind::Int(_) | QuestionKind::Float(_) => {
-                let path = parent_path.child(&variant.name);
-                if let Some(field) = self.fields.get(&path) {
-                    if let Some(value) = field.to_response_value() {
-                        responses.insert(path, value);
-                    }
+            QuestionKind::OneOf(one_of) => self.collect_one_of(one_of, &path, responses),
+            QuestionKind::AnyOf(any_of) => self.collect_any_of(any_of, &path, responses),
+            _ => {
+                if let Some(field) = self.fields.get(&path)
+                    && let Some(value) = field.to_response_value()
+                {
+                    responses.insert(path, value);
+                }
+            }
+        }
+    }
+
+    fn collect_one_of(
+        &self,
+        one_of: &OneOfQuestion,
+        path: &ResponsePath,
+        responses: &mut Responses,
+    ) {
+        if let Some(FieldState::OneOf {
+            selected: Some(selected),
+            ..
+        }) = self.fields.get(path)
+        {
+            let variant_path = path.child(SELECTED_VARIANT_KEY);
+            responses.insert(variant_path, ResponseValue::ChosenVariant(*selected));
+
+            // Collect nested data for the selected variant
+            let variant = &one_of.variants[*selected];
+            self.collect_variant_responses(variant, path, responses);
+        }
+    }
+
+    fn collect_any_of(
+        &self,
+        any_of: &AnyOfQuestion,
+        path: &ResponsePath,
+        responses: &mut Responses,
+    ) {
+        if let Some(FieldState::AnyOf { selected, .. }) = self.fields.get(path) {
+            let indices = chosen(selected);
+
+            let variants_path = path.child(SELECTED_VARIANTS_KEY);
+            responses.insert(
+                variants_path,
+                ResponseValue::ChosenVariants(indices.clone()),
+            );
+
+            // Collect nested data for each selected variant
+            for (item_idx, &variant_idx) in indices.iter().enumerate() {
+                let variant = &any_of.variants[variant_idx];
+                let item_path = path.child(&item_idx.to_string());
+
+                // Store which variant this item is
+                let item_variant_path = item_path.child(SELECTED_VARIANT_KEY);
+                responses.insert(item_variant_path, ResponseValue::ChosenVariant(variant_idx));
+
+                // Collect variant data
+                self.collect_variant_responses(variant, &item_path, responses);
+            }
+        }
+    }
+
+    /// Keep the follow-up answers of a multi-select with their variant when
+    /// the selection changes from `old` to `new`.
+    ///
+    /// Answers are stored per selected item (`path.0`, `path.1`, ...), so
+    /// checking or unchecking a variant shifts the items after it. Answers of
+    /// variants that were unchecked are dropped.
+    fn reindex_items(&mut self, path: &ResponsePath, old: &[bool], new: &[bool]) {
+        let old_items = chosen(old);
+        let new_items = chosen(new);
+        if old_items == new_items {
+            return;
+        }
+
+        let mut moved = Vec::new();
+        for (old_idx, variant_idx) in old_items.iter().enumerate() {
+            let item_path = path.child(&old_idx.to_string());
+            let new_path = new_items
+                .iter()
+                .position(|v| v == variant_idx)
+                .map(|new_idx| path.child(&new_idx.to_string()));
+            let keys: Vec<ResponsePath> = self
+                .fields
+                .keys()
+                .filter(|key| key.is_within(&item_path))
+                .cloned()
+                .collect();
+            for key in keys {
+                let field = self.fields.remove(&key).expect("key was just listed");
+                let error = self.errors.remove(&key);
+                if let Some(new_path) = &new_path {
+                    let suffix = key
+                        .strip_path_prefix(&item_path)
+                        .unwrap_or_else(ResponsePath::empty);
+                    moved.push((new_path.child(suffix.as_str()), field, error));
                 }
             }
-            _ => {}
+        }
+        for (key, field, error) in moved {
+            if let Some(error) = error {
+                self.errors.insert(key.clone(), error);
+            }
+            self.fields.insert(key, field);
         }
     }
 
     /// Validate that all required fields have values.
     /// Adds errors for empty Int/Float fields.
+    /// Number of fields at or below `path` with an error.
+    fn errors_within(&self, path: &ResponsePath) -> usize {
+        self.errors.keys().filter(|p| p.is_within(path)).count()
+    }
+
     fn validate_required_fields(&mut self) {
         for question in self.definition.questions.clone() {
             self.validate_question_required(&question, None);
@@ -810,19 +1152,51 @@ impl FormState {
 
         match question.kind() {
             QuestionKind::Int(_) => {
-                if let Some(FieldState::Int { parsed, .. }) = self.fields.get(&path) {
-                    if parsed.is_none() {
-                        self.errors
-                            .insert(path, "This field is required".to_string());
-                    }
+                if let Some(FieldState::Int { parsed, .. }) = self.fields.get(&path)
+                    && parsed.is_none()
+                {
+                    self.errors
+                        .insert(path, "This field is required".to_string());
                 }
             }
             QuestionKind::Float(_) => {
-                if let Some(FieldState::Float { parsed, .. }) = self.fields.get(&path) {
-                    if parsed.is_none() {
-                        self.errors
-                            .insert(path, "This field is required".to_string());
-                    }
+                if let Some(FieldState::Float { parsed, .. }) = self.fields.get(&path)
+                    && parsed.is_none()
+                {
+                    self.errors
+                        .insert(path, "This field is required".to_string());
+                }
+            }
+            QuestionKind::Currency(_) => {
+                if let Some(FieldState::Currency { parsed: None, .. }) = self.fields.get(&path) {
+                    self.errors
+                        .insert(path, "This field is required".to_string());
+                }
+            }
+            QuestionKind::Path(path_q) => {
+                if let Some(FieldState::Text { value, .. }) = self.fields.get(&path)
+                    && let Err(message) = path_q.check(value)
+                {
+                    self.errors.insert(path, message);
+                }
+            }
+            QuestionKind::Select(_) => {
+                if let Some(FieldState::Select { selected: None, .. }) = self.fields.get(&path) {
+                    self.errors
+                        .insert(path, "Please select an option".to_string());
+                }
+            }
+            QuestionKind::Rating(_) => {
+                if let Some(FieldState::Rating { value: None }) = self.fields.get(&path) {
+                    self.errors.insert(path, "Please pick a rating".to_string());
+                }
+            }
+            QuestionKind::Matrix(_) => {
+                if let Some(FieldState::Matrix { selected }) = self.fields.get(&path)
+                    && selected.contains(&None)
+                {
+                    self.errors
+                        .insert(path, "Please answer every row".to_string());
                 }
             }
             QuestionKind::OneOf(one_of) => {
@@ -841,11 +1215,14 @@ impl FormState {
             QuestionKind::AnyOf(any_of) => {
                 // Validate nested fields of selected variants
                 if let Some(FieldState::AnyOf { selected, .. }) = self.fields.get(&path) {
-                    let indices: Vec<usize> = selected
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(i, &s)| if s { Some(i) } else { None })
-                        .collect();
+                    let indices = chosen(selected);
+                    if let Some(max) = any_of.max_selected.filter(|&max| indices.len() > max) {
+                        let count = indices.len();
+                        self.errors.insert(
+                            path.clone(),
+                            format!("At most {max} can be selected, {count} are"),
+                        );
+                    }
                     for (item_idx, &variant_idx) in indices.iter().enumerate() {
                         let variant = &any_of.variants[variant_idx];
                         let item_path = path.child(&item_idx.to_string());
@@ -864,41 +1241,228 @@ impl FormState {
 
     fn validate_variant_required(&mut self, variant: &Variant, parent_path: &ResponsePath) {
         match &variant.kind {
+            QuestionKind::Unit => {}
             QuestionKind::AllOf(all_of) => {
                 for nested_q in all_of.questions() {
                     self.validate_question_required(nested_q, Some(parent_path));
                 }
             }
-            QuestionKind::Int(_) => {
-                let path = parent_path.child(&variant.name);
-                if let Some(FieldState::Int { parsed, .. }) = self.fields.get(&path) {
-                    if parsed.is_none() {
-                        self.errors
-                            .insert(path, "This field is required".to_string());
-                    }
-                }
+            // The variant's data is answered like a question named after it
+            kind => {
+                let question = Question::new(variant.name.as_str(), "", kind.clone());
+                self.validate_question_required(&question, Some(parent_path));
             }
-            QuestionKind::Float(_) => {
-                let path = parent_path.child(&variant.name);
-                if let Some(FieldState::Float { parsed, .. }) = self.fields.get(&path) {
-                    if parsed.is_none() {
-                        self.errors
-                            .insert(path, "This field is required".to_string());
-                    }
+        }
+    }
+}
+
+/// Indices of the checked variants of a multi-select.
+fn chosen(selected: &[bool]) -> Vec<usize> {
+    selected
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &s)| if s { Some(i) } else { None })
+        .collect()
+}
+
+/// Open the native dialog for a path question, starting next to the current answer.
+#[cfg(not(target_arch = "wasm32"))]
+fn pick_path(path_q: &PathQuestion, current: &str) -> Option<std::path::PathBuf> {
+    let mut dialog = rfd::FileDialog::new();
+    let current = std::path::Path::new(current);
+    if let Some(dir) = [Some(current), current.parent()]
+        .into_iter()
+        .flatten()
+        .find(|dir| dir.is_dir())
+    {
+        dialog = dialog.set_directory(dir);
+    }
+    if !path_q.extensions().is_empty() {
+        dialog = dialog.add_filter(path_q.hint().unwrap_or_default(), path_q.extensions());
+    }
+    match path_q.mode {
+        elicitor::PathMode::Directory => dialog.pick_folder(),
+        elicitor::PathMode::Any | elicitor::PathMode::File { .. } => dialog.pick_file(),
+    }
+}
+
+/// The choice of an enum field: nested surveys of enums consist of a single
+/// unnamed choice question.
+fn enum_choice(all_of: &AllOfQuestion) -> Option<&OneOfQuestion> {
+    match all_of.questions() {
+        [question] if question.path().is_empty() => match question.kind() {
+            QuestionKind::OneOf(one_of) => Some(one_of),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A drop-down of `labels` with `selected` shown closed. Long lists get a search
+/// box on top, typed into `filter`. Returns the index of a newly clicked label.
+fn choice_dropdown(
+    ui: &mut egui::Ui,
+    path: &ResponsePath,
+    labels: &[&str],
+    selected: Option<usize>,
+    filter: &mut String,
+) -> Option<usize> {
+    let current = selected
+        .and_then(|idx| labels.get(idx))
+        .copied()
+        .unwrap_or("Select...");
+    let mut clicked = None;
+    egui::ComboBox::from_id_salt(path.as_str())
+        .selected_text(current)
+        .show_ui(ui, |ui| {
+            if labels.len() >= DROPDOWN_MIN_CHOICES {
+                ui.add(egui::TextEdit::singleline(filter).hint_text("Search"));
+                ui.separator();
+            }
+            for (idx, label) in labels.iter().enumerate() {
+                if (filter.is_empty() || elicitor::fuzzy_match(label, filter).is_some())
+                    && ui.selectable_label(selected == Some(idx), *label).clicked()
+                {
+                    clicked = Some(idx);
                 }
             }
-            _ => {}
+        });
+    if clicked.is_some() {
+        filter.clear();
+    }
+    clicked
+}
+
+/// What is wrong with the comma-separated `value` of a list field: an item that
+/// is no number or out of bounds, or too few or too many items.
+fn list_problem(list_q: &ListQuestion, value: &str) -> Option<String> {
+    let items: Vec<&str> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect();
+    for item in &items {
+        let (below, above) = match &list_q.element_kind {
+            ListElementKind::String => continue,
+            ListElementKind::Int { min, max } => match item.parse::<i64>() {
+                Ok(n) => (
+                    min.filter(|&min| n < min).map(|min| min.to_string()),
+                    max.filter(|&max| n > max).map(|max| max.to_string()),
+                ),
+                Err(_) => return Some(list_q.messages.item_not_integer(&format!("'{item}'"))),
+            },
+            ListElementKind::Float { min, max } => match item.parse::<f64>() {
+                Ok(n) => (
+                    min.filter(|&min| n < min).map(|min| min.to_string()),
+                    max.filter(|&max| n > max).map(|max| max.to_string()),
+                ),
+                Err(_) => return Some(list_q.messages.item_not_number(&format!("'{item}'"))),
+            },
+        };
+        if let Some(min) = below {
+            return Some(list_q.messages.item_at_least(item, &min));
+        }
+        if let Some(max) = above {
+            return Some(list_q.messages.item_at_most(item, &max));
         }
     }
+    if let Some(min) = list_q.min_items.filter(|&min| items.len() < min) {
+        return Some(format!("Enter at least {min} items"));
+    }
+    if let Some(max) = list_q.max_items.filter(|&max| items.len() > max) {
+        return Some(format!("Enter at most {max} items"));
+    }
+    None
+}
+
+/// Style used while high contrast is on: white on black with larger text and spacing.
+pub(crate) fn high_contrast_style(base: &egui::Style) -> egui::Style {
+    let mut style = base.clone();
+    let white = egui::Color32::WHITE;
+    let black = egui::Color32::BLACK;
+
+    style.visuals = egui::Visuals::dark();
+    style.visuals.override_text_color = Some(white);
+    style.visuals.panel_fill = black;
+    style.visuals.window_fill = black;
+    style.visuals.extreme_bg_color = black;
+    style.visuals.faint_bg_color = black;
+    style.visuals.error_fg_color = egui::Color32::from_rgb(255, 110, 110);
+    style.visuals.selection.bg_fill = egui::Color32::from_rgb(0, 90, 200);
+    style.visuals.selection.stroke = egui::Stroke::new(2.0, white);
+    for widget in [
+        &mut style.visuals.widgets.noninteractive,
+        &mut style.visuals.widgets.inactive,
+        &mut style.visuals.widgets.hovered,
+        &mut style.visuals.widgets.active,
+    ] {
+        widget.bg_stroke = egui::Stroke::new(1.5, white);
+        widget.fg_stroke = egui::Stroke::new(1.5, white);
+    }
+
+    style.spacing.item_spacing = egui::vec2(12.0, 10.0);
+    style.spacing.button_padding = egui::vec2(10.0, 6.0);
+    for font in style.text_styles.values_mut() {
+        font.size *= 1.3;
+    }
+    style
 }
 
-/// The egui application that renders the survey form.
-struct SurveyApp {
-    state: Arc<Mutex<FormState>>,
-    validate: Box<dyn Fn(&ResponseValue, &Responses) -> Result<(), String> + Send>,
+/// The form itself, drawn into a window by [`SurveyWindow`] or into an
+/// application's own UI by [`FormPanel`](crate::FormPanel).
+pub(crate) struct SurveyForm<'v> {
+    pub(crate) state: Arc<Mutex<FormState>>,
+    pub(crate) validate: FieldValidator<'v>,
+    pub(crate) text_checker: Option<Arc<dyn TextChecker>>,
 }
 
-impl SurveyApp {
+/// The form as the only content of its own window.
+#[cfg(not(target_arch = "wasm32"))]
+struct SurveyWindow<'v> {
+    form: SurveyForm<'v>,
+    /// Style in effect before high contrast was switched on.
+    base_style: Arc<egui::Style>,
+    /// Whether the style currently applied is the high-contrast one.
+    high_contrast: Option<bool>,
+    /// The backend's window options, while the window still has to be fitted
+    /// to the form.
+    fit: Option<EguiBackend>,
+}
+
+impl<'v> SurveyForm<'v> {
+    pub(crate) fn new(definition: SurveyDefinition, validate: FieldValidator<'v>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FormState::new(definition))),
+            validate,
+            text_checker: None,
+        }
+    }
+
+    /// Whether the form is still being filled in.
+    pub(crate) fn status(&self) -> FormStatus {
+        let state = self.state.lock().unwrap();
+        if state.cancelled {
+            FormStatus::Cancelled
+        } else if state.submitted {
+            FormStatus::Completed
+        } else {
+            FormStatus::Running
+        }
+    }
+
+    /// Whether the form is shown in high-contrast mode.
+    pub(crate) fn high_contrast(&self) -> bool {
+        self.state.lock().unwrap().high_contrast
+    }
+
+    /// The answers as they stand, with masked answers redacted.
+    pub(crate) fn responses(&self) -> Responses {
+        let state = self.state.lock().unwrap();
+        let mut responses = state.collect_responses();
+        responses.redact_masked(&state.definition);
+        responses
+    }
+
     /// Format a prompt as a label, adding a colon only if the prompt doesn't end with punctuation.
     fn format_label(prompt: &str) -> String {
         let trimmed = prompt.trim();
@@ -935,7 +1499,7 @@ impl SurveyApp {
             // Create a readable label from the path
             path.as_str()
                 .split('.')
-                .last()
+                .next_back()
                 .unwrap_or("")
                 .split('_')
                 .map(|word| {
@@ -953,30 +1517,68 @@ impl SurveyApp {
 
         match question.kind() {
             QuestionKind::Unit => {}
-            QuestionKind::Input(_) | QuestionKind::Multiline(_) | QuestionKind::Masked(_) => {
+            QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
+            | QuestionKind::Multiline(_)
+            | QuestionKind::Masked(_) => {
                 self.render_text_field(ui, &path, &prompt, question.kind(), state);
             }
             QuestionKind::Int(int_q) => {
                 self.render_int_field(ui, &path, &prompt, int_q, state);
             }
+            QuestionKind::Currency(currency_q) => {
+                self.render_currency_field(ui, &path, &prompt, currency_q, state);
+            }
+            QuestionKind::Color(_) => {
+                self.render_color_field(ui, &path, &prompt, state);
+            }
+            QuestionKind::Path(path_q) => {
+                self.render_path_field(ui, &path, &prompt, path_q, state);
+            }
+            QuestionKind::Rating(rating_q) => {
+                self.render_rating_field(ui, &path, &prompt, rating_q, state);
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                self.render_matrix_field(ui, &path, &prompt, matrix_q, state);
+            }
             QuestionKind::Float(float_q) => {
                 self.render_float_field(ui, &path, &prompt, float_q, state);
             }
             QuestionKind::Confirm(_) => {
                 self.render_bool_field(ui, &path, &prompt, state);
             }
+            QuestionKind::Select(select_q) => {
+                self.render_select_field(ui, &path, &prompt, select_q, state);
+            }
             QuestionKind::List(list_q) => {
                 self.render_list_field(ui, &path, &prompt, list_q, state);
             }
             QuestionKind::OneOf(one_of) => {
-                self.render_one_of(ui, &path, &prompt, one_of, state);
+                self.render_one_of(ui, &path, &prompt, one_of, question.is_dropdown(), state);
             }
             QuestionKind::AnyOf(any_of) => {
                 self.render_any_of(ui, &path, &prompt, any_of, state);
             }
-            QuestionKind::AllOf(all_of) => {
-                self.render_all_of(ui, &path, &prompt, all_of, state);
-            }
+            QuestionKind::AllOf(all_of) => match enum_choice(all_of) {
+                // An enum field: its choice under the field's prompt
+                Some(one_of) => {
+                    self.render_one_of(ui, &path, &prompt, one_of, question.is_dropdown(), state);
+                }
+                None => self.render_all_of(ui, &path, &prompt, all_of, state),
+            },
+        }
+
+        if question.allows_comment() {
+            egui::CollapsingHeader::new("Add comment")
+                .id_salt(path.child(COMMENT_KEY).as_str())
+                .show(ui, |ui| {
+                    let comment = state.comments.entry(path.clone()).or_default();
+                    ui.add(
+                        egui::TextEdit::multiline(comment)
+                            .desired_rows(2)
+                            .hint_text("Optional remarks"),
+                    );
+                });
         }
     }
 
@@ -985,7 +1587,7 @@ impl SurveyApp {
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
-        _kind: &QuestionKind,
+        kind: &QuestionKind,
         state: &mut FormState,
     ) {
         ui.horizontal(|ui| {
@@ -999,6 +1601,7 @@ impl SurveyApp {
         }) = state.fields.get_mut(path)
         {
             let changed;
+            let mut finished = false;
 
             if *is_multiline {
                 let response = ui.add(
@@ -1007,28 +1610,262 @@ impl SurveyApp {
                         .desired_rows(3),
                 );
                 changed = response.changed();
+                finished = response.lost_focus();
             } else if *is_password {
                 let response = ui.add(egui::TextEdit::singleline(value).password(true));
                 changed = response.changed();
             } else {
                 let response =
                     ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
-                changed = response.changed();
+                let mut picked = false;
+
+                // Searchable popup with completions for the current text
+                if let QuestionKind::Autocomplete(autocomplete_q) = kind {
+                    let popup_id = ui.make_persistent_id(("autocomplete", path.as_str()));
+                    if response.gained_focus() || response.changed() {
+                        ui.memory_mut(|m| m.open_popup(popup_id));
+                    }
+                    let completions = autocomplete_q.completions(value);
+                    if completions.is_empty() {
+                        ui.memory_mut(|m| {
+                            if m.is_popup_open(popup_id) {
+                                m.close_popup();
+                            }
+                        });
+                    }
+                    egui::popup_below_widget(
+                        ui,
+                        popup_id,
+                        &response,
+                        egui::PopupCloseBehavior::CloseOnClickOutside,
+                        |ui| {
+                            egui::ScrollArea::vertical()
+                                .max_height(150.0)
+                                .show(ui, |ui| {
+                                    for completion in &completions {
+                                        if ui.selectable_label(false, completion).clicked() {
+                                            *value = completion.clone();
+                                            picked = true;
+                                        }
+                                    }
+                                });
+                        },
+                    );
+                    if picked {
+                        ui.memory_mut(|m| m.close_popup());
+                    }
+                }
+
+                changed = response.changed() || picked;
+            }
+
+            // Findings hold until the user next leaves the field
+            if changed {
+                state.findings.remove(path);
+            }
+            let issues = self
+                .text_checker
+                .as_ref()
+                .filter(|_| finished)
+                .map(|checker| checker.check(value));
+            if let Some(issues) = issues {
+                state.findings.insert(path.clone(), issues);
+            }
+
+            if changed {
+                // Validate on change
+                let rv = ResponseValue::String(value.clone());
+                let responses = state.collect_responses();
+                if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                    state.errors.insert(path.clone(), msg);
+                } else {
+                    state.errors.remove(path);
+                }
+            }
+        }
+
+        // Show error if any
+        if let Some(error) = state.errors.get(path) {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+        }
+        for issue in state.findings.get(path).into_iter().flatten() {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("✎ {issue}"));
+        }
+
+        ui.add_space(8.0);
+    }
+
+    fn render_int_field(
+        &self,
+        ui: &mut egui::Ui,
+        path: &ResponsePath,
+        prompt: &str,
+        int_q: &IntQuestion,
+        state: &mut FormState,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(Self::format_label(prompt));
+            if let (Some(min), Some(max)) = (int_q.min, int_q.max) {
+                ui.label(format!("({} - {})", int_q.format(min), int_q.format(max)));
+            } else if let Some(min) = int_q.min {
+                ui.label(format!("(min: {})", int_q.format(min)));
+            } else if let Some(max) = int_q.max {
+                ui.label(format!("(max: {})", int_q.format(max)));
+            }
+        });
+
+        if let Some(FieldState::Int { value, parsed }) = state.fields.get_mut(path) {
+            let response = ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
+
+            if response.changed() {
+                let result = int_q.parse(value);
+                *parsed = result.as_ref().ok().copied();
+
+                if let Some(i) = *parsed {
+                    // Clear any previous errors (like "required" or parse errors)
+                    state.errors.remove(path);
+
+                    // Check bounds
+                    if let Some(min) = int_q.min
+                        && i < min
+                    {
+                        state
+                            .errors
+                            .insert(path.clone(), int_q.messages.at_least(&int_q.format(min)));
+                    }
+                    if let Some(max) = int_q.max
+                        && i > max
+                    {
+                        state
+                            .errors
+                            .insert(path.clone(), int_q.messages.at_most(&int_q.format(max)));
+                    }
+
+                    // Custom validation
+                    if !state.errors.contains_key(path) {
+                        let rv = ResponseValue::Int(i);
+                        let responses = state.collect_responses();
+                        if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                            state.errors.insert(path.clone(), msg);
+                        }
+                    }
+                } else if value.is_empty() {
+                    // Blank is checked on submit, as a missing answer
+                    state.errors.remove(path);
+                } else if let Err(message) = result {
+                    state.errors.insert(path.clone(), message);
+                }
+            }
+        }
+
+        if let Some(error) = state.errors.get(path) {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+        }
+
+        ui.add_space(8.0);
+    }
+
+    fn render_currency_field(
+        &self,
+        ui: &mut egui::Ui,
+        path: &ResponsePath,
+        prompt: &str,
+        currency_q: &CurrencyQuestion,
+        state: &mut FormState,
+    ) {
+        ui.horizontal(|ui| {
+            ui.label(Self::format_label(prompt));
+            ui.label(format!("({})", currency_q.symbol()));
+            if let (Some(min), Some(max)) = (currency_q.min, currency_q.max) {
+                ui.label(format!(
+                    "({} - {})",
+                    currency_q.format(min),
+                    currency_q.format(max)
+                ));
+            } else if let Some(min) = currency_q.min {
+                ui.label(format!("(min: {})", currency_q.format(min)));
+            } else if let Some(max) = currency_q.max {
+                ui.label(format!("(max: {})", currency_q.format(max)));
+            }
+        });
+
+        let mut changed = None;
+        if let Some(FieldState::Currency { value, parsed }) = state.fields.get_mut(path) {
+            let response = ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
+
+            if response.changed() {
+                let result = currency_q.parse(value);
+                *parsed = result.as_ref().ok().copied();
+                changed = Some((result, value.is_empty()));
+            }
+
+            // Show the amount in the currency's format once the field is left
+            if response.lost_focus()
+                && let Some(minor) = *parsed
+            {
+                *value = currency_q.format(minor);
+            }
+        }
+
+        if let Some((result, is_empty)) = changed {
+            state.errors.remove(path);
+            match result {
+                Ok(minor) => {
+                    if let Err(msg) = currency_q.check_bounds(minor) {
+                        state.errors.insert(path.clone(), msg);
+                    } else {
+                        let rv = ResponseValue::Int(minor);
+                        let responses = state.collect_responses();
+                        if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                            state.errors.insert(path.clone(), msg);
+                        }
+                    }
+                }
+                Err(msg) if !is_empty => {
+                    state.errors.insert(path.clone(), msg);
+                }
+                Err(_) => {}
+            }
+        }
+
+        if let Some(error) = state.errors.get(path) {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+        }
+
+        ui.add_space(8.0);
+    }
+
+    fn render_color_field(
+        &self,
+        ui: &mut egui::Ui,
+        path: &ResponsePath,
+        prompt: &str,
+        state: &mut FormState,
+    ) {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(Self::format_label(prompt));
+            if let Some(FieldState::Color { rgb }) = state.fields.get_mut(path) {
+                changed = ui.color_edit_button_srgb(rgb).changed();
+                let [r, g, b] = *rgb;
+                ui.monospace(elicitor::Color::rgb(r, g, b).to_hex());
             }
+        });
 
-            if changed {
-                // Validate on change
-                let rv = ResponseValue::String(value.clone());
+        if changed {
+            state.errors.remove(path);
+            if let Some(rv) = state
+                .fields
+                .get(path)
+                .and_then(FieldState::to_response_value)
+            {
                 let responses = state.collect_responses();
-                if let Err(msg) = (self.validate)(&rv, &responses) {
+                if let Err(msg) = (self.validate)(&rv, &responses, path) {
                     state.errors.insert(path.clone(), msg);
-                } else {
-                    state.errors.remove(path);
                 }
             }
         }
 
-        // Show error if any
         if let Some(error) = state.errors.get(path) {
             ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
         }
@@ -1036,65 +1873,48 @@ impl SurveyApp {
         ui.add_space(8.0);
     }
 
-    fn render_int_field(
+    fn render_path_field(
         &self,
         ui: &mut egui::Ui,
         path: &ResponsePath,
         prompt: &str,
-        int_q: &IntQuestion,
+        path_q: &PathQuestion,
         state: &mut FormState,
     ) {
-        ui.horizontal(|ui| {
-            ui.label(Self::format_label(prompt));
-            if let (Some(min), Some(max)) = (int_q.min, int_q.max) {
-                ui.label(format!("({min} - {max})"));
-            } else if let Some(min) = int_q.min {
-                ui.label(format!("(min: {min})"));
-            } else if let Some(max) = int_q.max {
-                ui.label(format!("(max: {max})"));
-            }
-        });
-
-        if let Some(FieldState::Int { value, parsed }) = state.fields.get_mut(path) {
-            let response = ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
-
-            if response.changed() {
-                *parsed = value.parse().ok();
-
-                if let Some(i) = *parsed {
-                    // Clear any previous errors (like "required" or parse errors)
-                    state.errors.remove(path);
-
-                    // Check bounds
-                    if let Some(min) = int_q.min {
-                        if i < min {
-                            state
-                                .errors
-                                .insert(path.clone(), format!("Value must be at least {min}"));
-                        }
-                    }
-                    if let Some(max) = int_q.max {
-                        if i > max {
-                            state
-                                .errors
-                                .insert(path.clone(), format!("Value must be at most {max}"));
-                        }
-                    }
+        ui.label(Self::format_label(prompt));
 
-                    // Custom validation
-                    if state.errors.get(path).is_none() {
-                        let rv = ResponseValue::Int(i);
-                        let responses = state.collect_responses();
-                        if let Err(msg) = (self.validate)(&rv, &responses) {
-                            state.errors.insert(path.clone(), msg);
-                        }
-                    }
-                } else if !value.is_empty() {
-                    state
-                        .errors
-                        .insert(path.clone(), "Please enter a valid integer".to_string());
+        let mut changed = false;
+        if let Some(FieldState::Text { value, .. }) = state.fields.get_mut(path) {
+            ui.horizontal(|ui| {
+                let browse_width = 80.0;
+                changed = ui
+                    .add(
+                        egui::TextEdit::singleline(value)
+                            .desired_width(ui.available_width() - browse_width)
+                            .hint_text(path_q.hint().unwrap_or_default()),
+                    )
+                    .changed();
+                // The browser has no file system to browse
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button("Browse…").clicked()
+                    && let Some(picked) = pick_path(path_q, value)
+                {
+                    *value = picked.display().to_string();
+                    changed = true;
                 }
-            }
+            });
+        }
+
+        if changed && let Some(FieldState::Text { value, .. }) = state.fields.get(path) {
+            let value = value.clone();
+            let checked = path_q.check(&value).and_then(|()| {
+                let responses = state.collect_responses();
+                (self.validate)(&ResponseValue::String(value), &responses, path)
+            });
+            match checked {
+                Ok(()) => state.errors.remove(path),
+                Err(msg) => state.errors.insert(path.clone(), msg),
+            };
         }
 
         if let Some(error) = state.errors.get(path) {
@@ -1115,11 +1935,15 @@ impl SurveyApp {
         ui.horizontal(|ui| {
             ui.label(Self::format_label(prompt));
             if let (Some(min), Some(max)) = (float_q.min, float_q.max) {
-                ui.label(format!("({min} - {max})"));
+                ui.label(format!(
+                    "({} - {})",
+                    float_q.format(min),
+                    float_q.format(max)
+                ));
             } else if let Some(min) = float_q.min {
-                ui.label(format!("(min: {min})"));
+                ui.label(format!("(min: {})", float_q.format(min)));
             } else if let Some(max) = float_q.max {
-                ui.label(format!("(max: {max})"));
+                ui.label(format!("(max: {})", float_q.format(max)));
             }
         });
 
@@ -1127,38 +1951,41 @@ impl SurveyApp {
             let response = ui.add(egui::TextEdit::singleline(value).desired_width(f32::INFINITY));
 
             if response.changed() {
-                *parsed = value.parse().ok();
+                let result = float_q.parse(value);
+                *parsed = result.as_ref().ok().copied();
 
                 if let Some(f) = *parsed {
                     // Clear any previous errors (like "required" or parse errors)
                     state.errors.remove(path);
 
-                    if let Some(min) = float_q.min {
-                        if f < min {
-                            state
-                                .errors
-                                .insert(path.clone(), format!("Value must be at least {min}"));
-                        }
+                    if let Some(min) = float_q.min
+                        && f < min
+                    {
+                        state.errors.insert(
+                            path.clone(),
+                            float_q.messages.at_least(&float_q.format(min)),
+                        );
                     }
-                    if let Some(max) = float_q.max {
-                        if f > max {
-                            state
-                                .errors
-                                .insert(path.clone(), format!("Value must be at most {max}"));
-                        }
+                    if let Some(max) = float_q.max
+                        && f > max
+                    {
+                        state
+                            .errors
+                            .insert(path.clone(), float_q.messages.at_most(&float_q.format(max)));
                     }
 
-                    if state.errors.get(path).is_none() {
+                    if !state.errors.contains_key(path) {
                         let rv = ResponseValue::Float(f);
                         let responses = state.collect_responses();
-                        if let Err(msg) = (self.validate)(&rv, &responses) {
+                        if let Err(msg) = (self.validate)(&rv, &responses, path) {
                             state.errors.insert(path.clone(), msg);
                         }
                     }
-                } else if !value.is_empty() {
-                    state
-                        .errors
-                        .insert(path.clone(), "Please enter a valid number".to_string());
+                } else if value.is_empty() {
+                    // Blank is checked on submit, as a missing answer
+                    state.errors.remove(path);
+                } else if let Err(message) = result {
+                    state.errors.insert(path.clone(), message);
                 }
             }
         }
@@ -1177,8 +2004,20 @@ impl SurveyApp {
         prompt: &str,
         state: &mut FormState,
     ) {
-        if let Some(FieldState::Bool { value }) = state.fields.get_mut(path) {
-            ui.checkbox(value, prompt);
+        if let Some(FieldState::Bool { value }) = state.fields.get_mut(path)
+            && ui.checkbox(value, prompt).changed()
+        {
+            let rv = ResponseValue::Bool(*value);
+            let responses = state.collect_responses();
+            if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                state.errors.insert(path.clone(), msg);
+            } else {
+                state.errors.remove(path);
+            }
+        }
+
+        if let Some(error) = state.errors.get(path) {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
         }
         ui.add_space(8.0);
     }
@@ -1205,13 +2044,163 @@ impl SurveyApp {
         if let Some(FieldState::List { value, .. }) = state.fields.get_mut(path) {
             let response = ui.add(egui::TextEdit::singleline(value).desired_width(300.0));
             if response.changed() {
+                let problem = list_problem(list_q, value);
                 state.errors.remove(path);
+                if let Some(problem) = problem {
+                    state.errors.insert(path.clone(), problem);
+                } else if let Some(rv) = state.fields.get(path).and_then(|f| f.to_response_value())
+                {
+                    let responses = state.collect_responses();
+                    if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                        state.errors.insert(path.clone(), msg);
+                    }
+                }
             }
         }
 
         // Show error if any
         if let Some(error) = state.errors.get(path) {
-            ui.colored_label(egui::Color32::RED, error);
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+        }
+
+        ui.add_space(8.0);
+    }
+
+    fn render_select_field(
+        &self,
+        ui: &mut egui::Ui,
+        path: &ResponsePath,
+        prompt: &str,
+        select_q: &SelectQuestion,
+        state: &mut FormState,
+    ) {
+        ui.label(Self::format_label(prompt));
+
+        let mut changed = false;
+        if let Some(FieldState::Select { selected, .. }) = state.fields.get_mut(path) {
+            let labels: Vec<&str> = select_q.options.iter().map(|o| o.label.as_str()).collect();
+            let filter = state.filters.entry(path.clone()).or_default();
+            if let Some(idx) = choice_dropdown(ui, path, &labels, *selected, filter) {
+                *selected = Some(idx);
+                changed = true;
+            }
+        }
+
+        if changed && let Some(rv) = state.fields.get(path).and_then(|f| f.to_response_value()) {
+            let responses = state.collect_responses();
+            if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                state.errors.insert(path.clone(), msg);
+            } else {
+                state.errors.remove(path);
+            }
+        }
+
+        if let Some(error) = state.errors.get(path) {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+        }
+
+        ui.add_space(8.0);
+    }
+
+    fn render_matrix_field(
+        &self,
+        ui: &mut egui::Ui,
+        path: &ResponsePath,
+        prompt: &str,
+        matrix_q: &MatrixQuestion,
+        state: &mut FormState,
+    ) {
+        ui.label(Self::format_label(prompt));
+
+        let mut changed = false;
+        if let Some(FieldState::Matrix { selected }) = state.fields.get_mut(path) {
+            egui::Grid::new(path.as_str())
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("");
+                    for column in &matrix_q.columns {
+                        ui.weak(column);
+                    }
+                    ui.end_row();
+
+                    for (row, choice) in matrix_q.rows.iter().zip(selected.iter_mut()) {
+                        ui.label(row);
+                        for idx in 0..matrix_q.columns.len() {
+                            if ui.radio(*choice == Some(idx), "").clicked() {
+                                *choice = Some(idx);
+                                changed = true;
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+
+        if changed && let Some(rv) = state.fields.get(path).and_then(|f| f.to_response_value()) {
+            let responses = state.collect_responses();
+            if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                state.errors.insert(path.clone(), msg);
+            } else {
+                state.errors.remove(path);
+            }
+        }
+
+        if let Some(error) = state.errors.get(path) {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
+        }
+
+        ui.add_space(8.0);
+    }
+
+    fn render_rating_field(
+        &self,
+        ui: &mut egui::Ui,
+        path: &ResponsePath,
+        prompt: &str,
+        rating_q: &RatingQuestion,
+        state: &mut FormState,
+    ) {
+        ui.label(Self::format_label(prompt));
+
+        let mut changed = false;
+        if let Some(FieldState::Rating { value }) = state.fields.get_mut(path) {
+            ui.horizontal(|ui| {
+                if let Some((low, _)) = &rating_q.anchors {
+                    ui.weak(low);
+                }
+                for point in rating_q.points() {
+                    let (text, selected) = if rating_q.shows_stars() {
+                        let filled = value.is_some_and(|v| point <= v);
+                        (if filled { "★" } else { "☆" }.to_string(), filled)
+                    } else {
+                        (point.to_string(), *value == Some(point))
+                    };
+                    let response = ui
+                        .selectable_label(selected, text)
+                        .on_hover_text(rating_q.label(point));
+                    if response.clicked() {
+                        *value = Some(point);
+                        changed = true;
+                    }
+                }
+                if let Some((_, high)) = &rating_q.anchors {
+                    ui.weak(high);
+                }
+            });
+        }
+
+        if changed && let Some(rv) = state.fields.get(path).and_then(|f| f.to_response_value()) {
+            let responses = state.collect_responses();
+            if let Err(msg) = (self.validate)(&rv, &responses, path) {
+                state.errors.insert(path.clone(), msg);
+            } else {
+                state.errors.remove(path);
+            }
+        }
+
+        if let Some(error) = state.errors.get(path) {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {error}"));
         }
 
         ui.add_space(8.0);
@@ -1223,6 +2212,7 @@ impl SurveyApp {
         path: &ResponsePath,
         prompt: &str,
         one_of: &OneOfQuestion,
+        dropdown: bool,
         state: &mut FormState,
     ) {
         ui.label(Self::format_label(prompt));
@@ -1233,20 +2223,30 @@ impl SurveyApp {
             None
         };
 
-        // Render radio buttons
+        // Radio buttons, or a drop-down when asked for or the list is long
         let mut new_selected = selected;
-        for (idx, variant) in one_of.variants.iter().enumerate() {
-            if ui.radio(selected == Some(idx), &variant.name).clicked() {
+        if dropdown || one_of.variants.len() >= DROPDOWN_MIN_CHOICES {
+            let labels: Vec<&str> = one_of.variants.iter().map(|v| v.name.as_str()).collect();
+            let filter = state.filters.entry(path.clone()).or_default();
+            if let Some(idx) = choice_dropdown(ui, path, &labels, selected, filter) {
                 new_selected = Some(idx);
-                // Clear any "required" error when user makes a selection
-                state.errors.remove(path);
+            }
+        } else {
+            for (idx, variant) in one_of.variants.iter().enumerate() {
+                if ui.radio(selected == Some(idx), &variant.name).clicked() {
+                    new_selected = Some(idx);
+                }
             }
         }
-
         if new_selected != selected {
-            if let Some(FieldState::OneOf { selected, .. }) = state.fields.get_mut(path) {
-                *selected = new_selected;
-            }
+            // Clear any "required" error when user makes a selection
+            state.errors.remove(path);
+        }
+
+        if new_selected != selected
+            && let Some(FieldState::OneOf { selected, .. }) = state.fields.get_mut(path)
+        {
+            *selected = new_selected;
         }
 
         // Show nested fields for the selected variant (if any)
@@ -1284,10 +2284,8 @@ impl SurveyApp {
         let mut new_selections = selections.clone();
         for (idx, variant) in any_of.variants.iter().enumerate() {
             let mut checked = selections.get(idx).copied().unwrap_or(false);
-            if ui.checkbox(&mut checked, &variant.name).changed() {
-                if idx < new_selections.len() {
-                    new_selections[idx] = checked;
-                }
+            if ui.checkbox(&mut checked, &variant.name).changed() && idx < new_selections.len() {
+                new_selections[idx] = checked;
             }
         }
 
@@ -1296,16 +2294,19 @@ impl SurveyApp {
             if let Some(FieldState::AnyOf { selected, .. }) = state.fields.get_mut(path) {
                 *selected = new_selections.clone();
             }
+            state.reindex_items(path, &selections, &new_selections);
 
             // Validate selection
-            let indices: Vec<usize> = new_selections
-                .iter()
-                .enumerate()
-                .filter_map(|(i, &s)| if s { Some(i) } else { None })
-                .collect();
+            let indices = chosen(&new_selections);
+            let count = indices.len();
             let rv = ResponseValue::ChosenVariants(indices);
             let responses = state.collect_responses();
-            if let Err(msg) = (self.validate)(&rv, &responses) {
+            if let Some(max) = any_of.max_selected.filter(|&max| count > max) {
+                state.errors.insert(
+                    path.clone(),
+                    format!("At most {max} can be selected, {count} are"),
+                );
+            } else if let Err(msg) = (self.validate)(&rv, &responses, path) {
                 state.errors.insert(path.clone(), msg);
             } else {
                 state.errors.remove(path);
@@ -1346,16 +2347,34 @@ impl SurveyApp {
         all_of: &AllOfQuestion,
         state: &mut FormState,
     ) {
-        if !prompt.is_empty() {
-            ui.separator();
-            ui.strong(prompt);
+        if prompt.is_empty() {
+            ui.indent(path.as_str(), |ui| {
+                for nested_q in all_of.questions() {
+                    self.render_question(ui, nested_q, state, Some(path));
+                }
+            });
+            return;
         }
 
-        ui.indent(path.as_str(), |ui| {
-            for nested_q in all_of.questions() {
-                self.render_question(ui, nested_q, state, Some(path));
-            }
-        });
+        // A nested struct gets its own collapsible section, badged with its errors
+        let title = prompt.trim().trim_end_matches(':');
+        let errors = state.errors_within(path);
+        let header = if errors == 0 {
+            egui::RichText::new(title).strong()
+        } else {
+            egui::RichText::new(format!("{title}  ⚠ {errors}"))
+                .strong()
+                .color(egui::Color32::RED)
+        };
+        egui::CollapsingHeader::new(header)
+            .id_salt(path.as_str())
+            .default_open(true)
+            .show(ui, |ui| {
+                for nested_q in all_of.questions() {
+                    self.render_question(ui, nested_q, state, Some(path));
+                }
+            });
+        ui.add_space(8.0);
     }
 
     fn render_variant_fields(
@@ -1374,7 +2393,7 @@ impl SurveyApp {
                     }
                 });
             }
-            QuestionKind::Input(_) => {
+            QuestionKind::Input(_) | QuestionKind::Autocomplete(_) => {
                 let path = parent_path.child(&variant.name);
                 self.render_text_field(ui, &path, "", &variant.kind, state);
             }
@@ -1390,6 +2409,22 @@ impl SurveyApp {
                 let path = parent_path.child(&variant.name);
                 self.render_int_field(ui, &path, "", int_q, state);
             }
+            QuestionKind::Currency(currency_q) => {
+                let path = parent_path.child(&variant.name);
+                self.render_currency_field(ui, &path, "", currency_q, state);
+            }
+            QuestionKind::Color(_) => {
+                let path = parent_path.child(&variant.name);
+                self.render_color_field(ui, &path, "", state);
+            }
+            QuestionKind::Path(path_q) => {
+                let path = parent_path.child(&variant.name);
+                self.render_path_field(ui, &path, "", path_q, state);
+            }
+            QuestionKind::Rating(rating_q) => {
+                let path = parent_path.child(&variant.name);
+                self.render_rating_field(ui, &path, "", rating_q, state);
+            }
             QuestionKind::Float(float_q) => {
                 let path = parent_path.child(&variant.name);
                 self.render_float_field(ui, &path, "", float_q, state);
@@ -1398,13 +2433,21 @@ impl SurveyApp {
                 let path = parent_path.child(&variant.name);
                 self.render_bool_field(ui, &path, "", state);
             }
+            QuestionKind::Select(select_q) => {
+                let path = parent_path.child(&variant.name);
+                self.render_select_field(ui, &path, "", select_q, state);
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                let path = parent_path.child(&variant.name);
+                self.render_matrix_field(ui, &path, "", matrix_q, state);
+            }
             QuestionKind::List(list_q) => {
                 let path = parent_path.child(&variant.name);
                 self.render_list_field(ui, &path, "", list_q, state);
             }
             QuestionKind::OneOf(one_of) => {
                 let path = parent_path.child(&variant.name);
-                self.render_one_of(ui, &path, "", one_of, state);
+                self.render_one_of(ui, &path, "", one_of, false, state);
             }
             QuestionKind::AnyOf(any_of) => {
                 let path = parent_path.child(&variant.name);
@@ -1414,9 +2457,21 @@ impl SurveyApp {
     }
 }
 
-impl eframe::App for SurveyApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
+impl SurveyForm<'_> {
+    /// Draw the high-contrast switch, prelude, questions, epilogue and the
+    /// Submit and Cancel buttons into `ui`.
+    pub(crate) fn show(&self, ui: &mut egui::Ui) -> FormStatus {
+        ui.horizontal(|ui| {
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let mut enabled = self.high_contrast();
+                if ui.toggle_value(&mut enabled, "High contrast").changed() {
+                    self.state.lock().unwrap().high_contrast = enabled;
+                    ui.ctx().request_repaint();
+                }
+            });
+        });
+
+        {
             let mut state = self.state.lock().unwrap();
 
             // Show prelude if present
@@ -1425,7 +2480,7 @@ impl eframe::App for SurveyApp {
                 ui.separator();
             }
 
-            egui::ScrollArea::vertical().show(ui, |ui| {
+            let scroll = egui::ScrollArea::vertical().show(ui, |ui| {
                 // Render all questions
                 for question in state.definition.questions.clone() {
                     self.render_question(ui, &question, &mut state, None);
@@ -1455,23 +2510,25 @@ impl eframe::App for SurveyApp {
                             let responses = state.collect_responses();
                             let mut all_valid = true;
 
-                            for (path, value) in responses.iter() {
-                                if let Err(msg) = (self.validate)(value, &responses) {
-                                    state.errors.insert(path.clone(), msg);
+                            for (path, value) in responses.iter_exposed() {
+                                // A multi-select is checked as its field, as while editing
+                                let path = if path.last() == Some(SELECTED_VARIANTS_KEY) {
+                                    path.parent()
+                                } else {
+                                    path.clone()
+                                };
+                                if let Err(msg) = (self.validate)(value, &responses, &path) {
+                                    state.errors.insert(path, msg);
                                     all_valid = false;
                                 }
                             }
 
-                            if all_valid {
-                                state.submitted = true;
-                                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                            }
+                            state.submitted = all_valid;
                         }
                     }
 
                     if ui.button("Cancel").clicked() {
                         state.cancelled = true;
-                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                     }
 
                     if has_errors || !state.errors.is_empty() {
@@ -1482,49 +2539,84 @@ impl eframe::App for SurveyApp {
                     }
                 });
             });
+            state.overflow = scroll.content_size.y - scroll.inner_rect.height();
+        }
+
+        self.status()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl eframe::App for SurveyWindow<'_> {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let high_contrast = self.form.high_contrast();
+        if self.high_contrast != Some(high_contrast) {
+            if high_contrast {
+                ctx.set_style(high_contrast_style(&self.base_style));
+            } else {
+                ctx.set_style(Arc::clone(&self.base_style));
+            }
+            self.high_contrast = Some(high_contrast);
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if self.form.show(ui) != FormStatus::Running {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
         });
+
+        // Once the form has been laid out, size the window to it
+        if let Some(options) = self.fit.take() {
+            let overflow = self.form.state.lock().unwrap().overflow;
+            let screen = ctx.screen_rect().size();
+            let height = options.fitted_height(screen.y, overflow);
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(
+                screen.x, height,
+            )));
+        }
     }
 }
 
-impl SurveyBackend for EguiBackend {
+#[cfg(not(target_arch = "wasm32"))]
+impl elicitor::SurveyBackend for EguiBackend {
     type Error = EguiError;
 
     fn collect(
         &self,
         definition: &SurveyDefinition,
-        _validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<Responses, Self::Error> {
-        let state = Arc::new(Mutex::new(FormState::new(definition.clone())));
-
-        // Create native options
-        let options = eframe::NativeOptions {
-            viewport: egui::ViewportBuilder::default()
-                .with_title(self.title.clone())
-                .with_inner_size(self.window_size),
-            ..Default::default()
-        };
+        let mut form = SurveyForm::new(definition.clone(), Box::new(validate));
+        form.text_checker = self.text_checker.clone();
+        let started_high_contrast = self
+            .high_contrast
+            .lock()
+            .unwrap()
+            .unwrap_or_else(elicitor::high_contrast_enabled);
+        form.state.lock().unwrap().high_contrast = started_high_contrast;
+        let state = Arc::clone(&form.state);
+
+        let options = self.native_options();
+        let fit_to_form = self.window_size.is_none();
+        let backend = self.clone();
 
         // Run the egui app
         // Note: eframe::run_native blocks until the window is closed
-        let app_state = Arc::clone(&state);
-
-        // We need to handle the validation in a way that works with eframe
-        // Since eframe takes ownership, we'll use a closure that captures what we need
         let title = self.title.clone();
 
         eframe::run_native(
             &title,
             options,
-            Box::new(move |_cc| {
-                // Create a validation function that always succeeds for now
-                // Real validation happens on submit
-                let validate_fn: Box<
-                    dyn Fn(&ResponseValue, &Responses) -> Result<(), String> + Send,
-                > = Box::new(|_value, _responses| Ok(()));
-
-                Ok(Box::new(SurveyApp {
-                    state: app_state,
-                    validate: validate_fn,
+            Box::new(move |cc| {
+                // A window restored from the previous run keeps its size
+                let restored = cc
+                    .storage
+                    .is_some_and(|storage| storage.get_string(STORAGE_WINDOW_KEY).is_some());
+                Ok(Box::new(SurveyWindow {
+                    form,
+                    base_style: cc.egui_ctx.style(),
+                    high_contrast: None,
+                    fit: (fit_to_form && !restored).then_some(backend),
                 }) as Box<dyn eframe::App>)
             }),
         )
@@ -1532,12 +2624,14 @@ impl SurveyBackend for EguiBackend {
 
         // After the window closes, check the result
         let state = state.lock().unwrap();
-        if state.cancelled {
-            return Err(EguiError::Cancelled);
+        // The next form of this backend starts in the mode the user switched to
+        if state.high_contrast != started_high_contrast {
+            *self.high_contrast.lock().unwrap() = Some(state.high_contrast);
         }
-
-        if !state.submitted {
-            return Err(EguiError::Cancelled);
+        if state.cancelled || !state.submitted {
+            return Err(EguiError::Cancelled {
+                partial: Box::new(state.collect_responses()),
+            });
         }
 
         Ok(state.collect_responses())
@@ -1556,12 +2650,218 @@ mod tests {
         let _default = EguiBackend::default();
     }
 
+    #[test]
+    fn window_options() {
+        let options = EguiBackend::new()
+            .with_window_size([400.0, 300.0])
+            .with_min_window_size([300.0, 200.0])
+            .with_resizable(false)
+            .with_always_on_top()
+            .with_persistence(false)
+            .native_options();
+        assert_eq!(options.viewport.inner_size, Some(egui::vec2(400.0, 300.0)));
+        assert_eq!(
+            options.viewport.min_inner_size,
+            Some(egui::vec2(300.0, 200.0))
+        );
+        assert_eq!(options.viewport.resizable, Some(false));
+        assert_eq!(
+            options.viewport.window_level,
+            Some(egui::WindowLevel::AlwaysOnTop)
+        );
+        assert!(!options.persist_window);
+        assert!(EguiBackend::new().native_options().persist_window);
+    }
+
+    #[test]
+    fn window_is_fitted_to_the_form_within_limits() {
+        let backend = EguiBackend::new();
+        // A short form shrinks the window, a long one grows it up to the limit
+        assert_eq!(backend.fitted_height(600.0, -250.0), 350.0);
+        assert_eq!(backend.fitted_height(600.0, -550.0), FIT_MIN_HEIGHT);
+        assert_eq!(backend.fitted_height(600.0, 1000.0), FIT_MAX_HEIGHT);
+
+        let limited = backend.with_max_window_size([500.0, 400.0]);
+        assert_eq!(limited.fitted_height(600.0, 100.0), 400.0);
+    }
+
     #[test]
     fn error_types() {
-        let err = EguiError::Cancelled;
+        let err = EguiError::Cancelled {
+            partial: Box::default(),
+        };
         assert_eq!(err.to_string(), "Survey cancelled by user");
 
         let err = EguiError::EguiError("test error".to_string());
         assert_eq!(err.to_string(), "Egui error: test error");
     }
+
+    #[test]
+    fn list_fields_are_checked_while_typing() {
+        let ints =
+            ListQuestion::ints_with_bounds(Some(1), Some(10)).with_item_bounds(Some(2), Some(3));
+        assert_eq!(list_problem(&ints, "3, 4"), None);
+        assert_eq!(
+            list_problem(&ints, "3, four"),
+            Some("'four' is not a valid integer".to_string())
+        );
+        assert_eq!(
+            list_problem(&ints, "3, 40"),
+            Some("Value 40 must be at most 10".to_string())
+        );
+        assert_eq!(
+            list_problem(&ints, "3"),
+            Some("Enter at least 2 items".to_string())
+        );
+        assert_eq!(
+            list_problem(&ints, "1, 2, 3, 4"),
+            Some("Enter at most 3 items".to_string())
+        );
+        assert_eq!(list_problem(&ListQuestion::strings(), "a, , b"), None);
+    }
+
+    #[test]
+    fn enum_fields_are_recognized_by_their_single_choice() {
+        let choice = || {
+            Question::new(
+                ResponsePath::empty(),
+                "",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Red"),
+                    Variant::unit("Green"),
+                ])),
+            )
+        };
+        let enum_field = AllOfQuestion::new(vec![choice()]);
+        assert_eq!(
+            enum_choice(&enum_field).map(|one_of| one_of.variants.len()),
+            Some(2)
+        );
+
+        let nested_struct = AllOfQuestion::new(vec![Question::new(
+            "host",
+            "Host:",
+            QuestionKind::Input(Default::default()),
+        )]);
+        assert!(enum_choice(&nested_struct).is_none());
+        assert!(enum_choice(&AllOfQuestion::new(vec![choice(), choice()])).is_none());
+    }
+
+    #[test]
+    fn sections_count_the_errors_below_them() {
+        let mut state = FormState::new(SurveyDefinition::new(Vec::new()));
+        state
+            .errors
+            .insert(ResponsePath::new("database.port"), "Too low".to_string());
+        state.errors.insert(
+            ResponsePath::new("database.tls.cert"),
+            "Missing".to_string(),
+        );
+        state
+            .errors
+            .insert(ResponsePath::new("name"), "Missing".to_string());
+
+        assert_eq!(state.errors_within(&ResponsePath::new("database")), 2);
+        assert_eq!(state.errors_within(&ResponsePath::new("database.tls")), 1);
+        assert_eq!(state.errors_within(&ResponsePath::new("cache")), 0);
+    }
+
+    #[test]
+    fn multi_select_follow_ups_stay_with_their_variant() {
+        use elicitor::Survey;
+        use example_surveys::{DeveloperProfile, Hobby};
+
+        let hobbies = ResponsePath::new("hobbies");
+        let mut state = FormState::new(DeveloperProfile::survey());
+        let QuestionKind::AnyOf(any_of) = DeveloperProfile::survey().questions[2].kind().clone()
+        else {
+            panic!("hobbies is a multi-select");
+        };
+        let check = |state: &mut FormState, checked: &[usize]| {
+            let mut new = vec![false; any_of.variants.len()];
+            for &idx in checked {
+                new[idx] = true;
+            }
+            let Some(FieldState::AnyOf { selected, .. }) = state.fields.get_mut(&hobbies) else {
+                panic!("hobbies has checkboxes");
+            };
+            let old = std::mem::replace(selected, new.clone());
+            state.reindex_items(&hobbies, &old, &new);
+            for (item_idx, variant_idx) in chosen(&new).into_iter().enumerate() {
+                let item_path = hobbies.child(&item_idx.to_string());
+                state.ensure_variant_fields(&any_of.variants[variant_idx], &item_path);
+            }
+        };
+
+        // Reading and Sports, then Reading is unchecked again
+        check(&mut state, &[0, 3]);
+        if let Some(FieldState::Text { value, .. }) =
+            state.fields.get_mut(&ResponsePath::new("hobbies.1.0"))
+        {
+            *value = "Rowing".to_string();
+        }
+        check(&mut state, &[3]);
+
+        let profile = DeveloperProfile::from_responses(&state.collect_responses());
+        assert!(matches!(profile.hobbies.as_slice(), [Hobby::Sports(sport)] if sport == "Rowing"));
+    }
+
+    #[test]
+    fn variant_data_of_every_kind_is_collected() {
+        let mut state = FormState::new(SurveyDefinition::new(vec![Question::new(
+            "extras",
+            "Extras?",
+            QuestionKind::AnyOf(AnyOfQuestion {
+                variants: vec![
+                    Variant::new(
+                        "Gift",
+                        QuestionKind::Confirm(elicitor::ConfirmQuestion::with_default(true)),
+                    ),
+                    Variant::new("Notes", QuestionKind::List(ListQuestion::strings())),
+                ],
+                defaults: vec![0, 1],
+                max_selected: Some(1),
+            }),
+        )]));
+        if let Some(FieldState::List { value, .. }) =
+            state.fields.get_mut(&ResponsePath::new("extras.1.Notes"))
+        {
+            *value = "fragile, by noon".to_string();
+        }
+
+        let responses = state.collect_responses();
+        assert_eq!(
+            responses.get(&ResponsePath::new("extras.0.Gift")),
+            Some(&ResponseValue::Bool(true))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("extras.1.Notes")),
+            Some(&ResponseValue::StringList(vec![
+                "fragile".to_string(),
+                "by noon".to_string()
+            ]))
+        );
+
+        state.validate_required_fields();
+        assert_eq!(
+            state
+                .errors
+                .get(&ResponsePath::new("extras"))
+                .map(String::as_str),
+            Some("At most 1 can be selected, 2 are")
+        );
+    }
+
+    #[test]
+    fn high_contrast_enlarges_text() {
+        let base = egui::Style::default();
+        let style = high_contrast_style(&base);
+        let body = |style: &egui::Style| style.text_styles[&egui::TextStyle::Body].size;
+        assert!(body(&style) > body(&base));
+        assert_eq!(style.visuals.panel_fill, egui::Color32::BLACK);
+        assert_eq!(
+            style.visuals.override_text_color,
+            Some(egui::Color32::WHITE)
+        );
+    }
 }
@@ -5,6 +5,8 @@
 //! This backend uses the `eframe` and `egui` crates to provide a native
 //! desktop form interface for surveys. All fields are displayed at once
 //! and can be edited in any order.
+//! Nested structs are collapsible sections, titled with their prompt and
+//! badged with the number of errors inside them.
 //!
 //! ## Usage
 //!
@@ -34,6 +36,18 @@
 //! }
 //! ```
 
+//!
+//! To host the form in an existing eframe application instead, draw a
+//! [`FormPanel`] into any `egui::Ui`, e.g. a tab or side panel.
+//!
+//! The crate builds for `wasm32-unknown-unknown` too. There, [`FormPanel`] is all
+//! it offers, since a browser page cannot block in a native window until the form
+//! is done; `elicitor-web` runs it on a canvas.
+
 mod backend;
+mod panel;
 
-pub use backend::{EguiBackend, EguiError};
+#[cfg(not(target_arch = "wasm32"))]
+pub use backend::EguiBackend;
+pub use backend::EguiError;
+pub use panel::{FormPanel, FormStatus};
@@ -0,0 +1,128 @@
+//! The form as a panel of an existing egui application.
+
+use eframe::egui;
+use elicitor::{ResponsePath, ResponseValue, Responses, Survey, SurveyDefinition, TextChecker};
+use std::sync::Arc;
+
+use crate::backend::{SurveyForm, high_contrast_style};
+
+/// Whether an embedded form is still being filled in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormStatus {
+    /// The form has not been submitted yet.
+    Running,
+    /// The user submitted the form and every answer passed validation.
+    Completed,
+    /// The user pressed Cancel.
+    Cancelled,
+}
+
+/// The form drawn into any [`egui::Ui`] of an application, e.g. a tab, a side
+/// panel or a modal window.
+///
+/// Unlike [`EguiBackend`](crate::EguiBackend), it opens no window of its own:
+/// call [`ui`](Self::ui) every frame and act on the status it returns.
+///
+/// ```rust,ignore
+/// egui::SidePanel::right("settings").show(ctx, |ui| {
+///     match self.settings_form.ui(ui) {
+///         FormStatus::Running => {}
+///         FormStatus::Completed => {
+///             self.settings = Some(Settings::from_responses(&self.settings_form.responses()));
+///         }
+///         FormStatus::Cancelled => self.show_settings = false,
+///     }
+/// });
+/// ```
+pub struct FormPanel {
+    form: SurveyForm<'static>,
+}
+
+impl FormPanel {
+    /// A form with the questions of `definition`. Answers pass until a validator
+    /// is given with [`with_validator`](Self::with_validator).
+    pub fn new(definition: &SurveyDefinition) -> Self {
+        Self {
+            form: SurveyForm::new(definition.clone(), Box::new(|_, _, _| Ok(()))),
+        }
+    }
+
+    /// A form for a `#[derive(Survey)]` type, checking answers with its validators.
+    pub fn for_survey<T: Survey + 'static>() -> Self {
+        Self::new(&T::survey()).with_validator(T::validate_field)
+    }
+
+    /// Check answers with `validate` as they change and on submit.
+    pub fn with_validator(
+        mut self,
+        validate: impl Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.form.validate = Box::new(validate);
+        self
+    }
+
+    /// Check multiline answers with `checker` when the user leaves the field,
+    /// showing its findings below the field.
+    pub fn with_text_checker(mut self, checker: impl TextChecker + 'static) -> Self {
+        self.form.text_checker = Some(Arc::new(checker));
+        self
+    }
+
+    /// Draw the form into `ui`. High contrast applies to the form only, not to
+    /// the rest of the application.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> FormStatus {
+        ui.scope(|ui| {
+            if self.form.high_contrast() {
+                ui.set_style(high_contrast_style(ui.style()));
+            }
+            self.form.show(ui)
+        })
+        .inner
+    }
+
+    /// Whether the form is still being filled in.
+    pub fn status(&self) -> FormStatus {
+        self.form.status()
+    }
+
+    /// The answers as they stand, complete once the status is
+    /// [`Completed`](FormStatus::Completed). Masked answers are redacted, like
+    /// those of a survey run through its builder.
+    pub fn responses(&self) -> Responses {
+        self.form.responses()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{InputQuestion, Question, QuestionKind};
+
+    #[test]
+    fn panel_draws_into_a_caller_ui() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "name",
+            "Name?",
+            QuestionKind::Input(InputQuestion::with_default("Ada")),
+        )]);
+        let mut panel = FormPanel::new(&definition);
+
+        let ctx = egui::Context::default();
+        let mut status = None;
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::SidePanel::left("host").show(ctx, |ui| {
+                ui.label("Host application");
+            });
+            egui::CentralPanel::default().show(ctx, |ui| {
+                status = Some(panel.ui(ui));
+            });
+        });
+
+        assert_eq!(status, Some(FormStatus::Running));
+        assert_eq!(panel.status(), FormStatus::Running);
+        assert_eq!(
+            panel.responses().get(&ResponsePath::new("name")),
+            Some(&ResponseValue::String("Ada".to_string()))
+        );
+    }
+}
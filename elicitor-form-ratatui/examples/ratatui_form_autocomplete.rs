@@ -0,0 +1,17 @@
+//! Autocomplete example
+//!
+//! Demonstrates:
+//! - #[autocomplete] with a fixed list of suggestions
+//! - #[autocomplete] with a completion function that sees the current input
+//!
+//! Run with: cargo run --example ratatui_form_autocomplete
+
+use elicitor_form_ratatui::RatatuiFormBackend;
+use example_surveys::EditorSetup;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RatatuiFormBackend::new();
+    let result = EditorSetup::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
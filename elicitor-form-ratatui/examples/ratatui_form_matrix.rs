@@ -0,0 +1,17 @@
+//! Matrix question example
+//!
+//! Demonstrates:
+//! - HashMap<RowEnum, ColumnEnum> for a grid question
+//! - ←/→ or 1-9 to pick a column in the focused row
+//!
+//! Run with: cargo run --example ratatui_form_matrix
+
+use elicitor_form_ratatui::RatatuiFormBackend;
+use example_surveys::CourseEvaluation;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RatatuiFormBackend::new();
+    let result = CourseEvaluation::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
@@ -10,27 +10,40 @@ use crossterm::{
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use elicitor::{
-    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition, Variant,
+    AutocompleteQuestion, COMMENT_KEY, ColorQuestion, CurrencyQuestion, DefaultValue,
+    ListElementKind, MatrixQuestion, Messages, NumberFormat, PathQuestion, Question, QuestionKind,
+    RatingQuestion, ResponsePath, ResponseValue, Responses, SELECTED_VARIANT_KEY,
+    SELECTED_VARIANTS_KEY, SelectOption, SelectQuestion, SurveyBackend, SurveyDefinition,
+    SurveyError, TextChecker, Variant,
 };
 use ratatui::{
     Frame, Terminal,
     layout::{Constraint, Direction, Layout, Rect},
     prelude::CrosstermBackend,
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{
-        Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Block, Borders, Cell, List, ListItem, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Table,
     },
 };
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::io::{self, Stdout};
+use std::sync::Arc;
 use thiserror::Error;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::theme::Theme;
 
 /// Error type for the Ratatui form backend.
 #[derive(Debug, Error)]
 pub enum RatatuiFormError {
     /// User cancelled the form (e.g., pressed Esc).
+    ///
+    /// Holds the answers given before cancelling.
     #[error("Form cancelled by user")]
-    Cancelled,
+    Cancelled { partial: Box<Responses> },
 
     /// An I/O error occurred.
     #[error("I/O error: {0}")]
@@ -41,36 +54,29 @@ pub enum RatatuiFormError {
     Terminal(String),
 }
 
-/// Color theme for the TUI form.
-#[derive(Debug, Clone)]
-pub struct Theme {
-    pub primary: Color,
-    pub secondary: Color,
-    pub background: Color,
-    pub text: Color,
-    pub highlight: Color,
-    pub error: Color,
-    pub success: Color,
-    pub border: Color,
-    pub selected_bg: Color,
+impl RatatuiFormError {
+    /// The answers given before the user cancelled, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            _ => None,
+        }
+    }
 }
 
-impl Default for Theme {
-    fn default() -> Self {
-        Self {
-            primary: Color::Cyan,
-            secondary: Color::Blue,
-            background: Color::Reset,
-            text: Color::White,
-            highlight: Color::Yellow,
-            error: Color::Red,
-            success: Color::Green,
-            border: Color::Gray,
-            selected_bg: Color::DarkGray,
+impl From<RatatuiFormError> for SurveyError {
+    fn from(err: RatatuiFormError) -> Self {
+        match err {
+            RatatuiFormError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
         }
     }
 }
 
+/// Options shown per page of a choice list unless changed with
+/// [`RatatuiFormBackend::with_page_size`].
+pub const DEFAULT_PAGE_SIZE: usize = 10;
+
 /// Ratatui form backend that displays all fields at once.
 #[derive(Debug, Clone)]
 pub struct RatatuiFormBackend {
@@ -78,6 +84,10 @@ pub struct RatatuiFormBackend {
     title: String,
     /// Color theme for the UI.
     theme: Theme,
+    /// Options shown per page of a choice list.
+    page_size: usize,
+    /// Checker run on multiline answers when the form is submitted.
+    text_checker: Option<Arc<dyn TextChecker>>,
 }
 
 impl Default for RatatuiFormBackend {
@@ -92,6 +102,8 @@ impl RatatuiFormBackend {
         Self {
             title: "Form".to_string(),
             theme: Theme::default(),
+            page_size: DEFAULT_PAGE_SIZE,
+            text_checker: None,
         }
     }
 
@@ -107,6 +119,23 @@ impl RatatuiFormBackend {
         self
     }
 
+    /// Show choice lists `page_size` options at a time (at least one), so long lists
+    /// don't push the other fields off the screen. PageUp and PageDown move through
+    /// the pages of the focused list.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Check multiline answers with `checker` when the form is submitted.
+    ///
+    /// Findings are shown as the field's error; submitting again with the same text
+    /// keeps it as it is.
+    pub fn with_text_checker(mut self, checker: impl TextChecker + 'static) -> Self {
+        self.text_checker = Some(Arc::new(checker));
+        self
+    }
+
     fn setup_terminal(&self) -> Result<Terminal<CrosstermBackend<Stdout>>, RatatuiFormError> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
@@ -138,18 +167,54 @@ enum FieldKind {
         multiline: bool,
         masked: bool,
     },
+    Autocomplete {
+        question: AutocompleteQuestion,
+    },
     Int {
         min: Option<i64>,
         max: Option<i64>,
+        format: NumberFormat,
+        messages: Messages,
     },
     Float {
         min: Option<f64>,
         max: Option<f64>,
+        format: NumberFormat,
+        messages: Messages,
+    },
+    /// A money amount, typed in the currency's format and stored in minor units.
+    Currency {
+        question: CurrencyQuestion,
+    },
+    /// An RGB color, typed as a hex string.
+    Color {
+        question: ColorQuestion,
+    },
+    /// A filesystem path, checked against the question's mode on submit.
+    Path {
+        question: PathQuestion,
     },
     Bool,
+    /// A point on a rating scale, stored in `value` like an integer.
+    Rating {
+        question: RatingQuestion,
+    },
     List {
         element_kind: ListElementKind,
     },
+    /// One column per row of a matrix.
+    Matrix {
+        question: MatrixQuestion,
+        selected: Vec<Option<usize>>,
+        /// Currently highlighted row (for keyboard navigation)
+        highlight: usize,
+    },
+    Select {
+        options: Vec<SelectOption>,
+        selected: Option<usize>,
+        /// Currently highlighted option (for keyboard navigation)
+        highlight: usize,
+    },
     OneOf {
         variants: Vec<Variant>,
         selected: Option<usize>,
@@ -199,6 +264,28 @@ struct FormField {
     visibility: VisibilityCondition,
     /// Whether this is a top-level field (for spacing between sections)
     is_top_level: bool,
+    /// Free-text comment, present only if the question allows one
+    comment: Option<String>,
+}
+
+impl FormField {
+    /// Byte length of the character before the cursor, which is a byte offset.
+    fn char_before_cursor(&self) -> usize {
+        self.value[..self.cursor_pos]
+            .chars()
+            .next_back()
+            .map_or(0, char::len_utf8)
+    }
+
+    /// Terminal column of the cursor within the value, counting wide characters
+    /// such as emoji and CJK as two.
+    fn cursor_column(&self) -> u16 {
+        let before = &self.value[..self.cursor_pos];
+        match self.kind {
+            FieldKind::Text { masked: true, .. } => before.chars().count() as u16,
+            _ => before.width() as u16,
+        }
+    }
 }
 
 /// State for the entire form.
@@ -207,11 +294,25 @@ struct FormState {
     focused_idx: usize,
     /// Scroll offset in pixels (vertical)
     scroll_offset: u16,
+    /// Height of the scrolled area at the last draw
+    viewport_height: u16,
+    /// Options shown per page of a choice list
+    page_size: usize,
+    /// Checker run on multiline answers when the form is submitted
+    text_checker: Option<Arc<dyn TextChecker>>,
+    /// Multiline answers whose findings were shown, kept when submitted again
+    reviewed_texts: HashMap<ResponsePath, String>,
     /// Whether the submit button is focused
     submit_focused: bool,
+    /// Whether keystrokes go to the focused field's comment
+    editing_comment: bool,
     submitted: bool,
     cancelled: bool,
     theme: Theme,
+    /// Theme configured on the backend, restored when high-contrast mode is switched off
+    custom_theme: Theme,
+    /// Whether high-contrast mode with more spacing is on
+    high_contrast: bool,
     title: String,
     prelude: Option<String>,
     #[allow(dead_code)]
@@ -227,16 +328,63 @@ impl FormState {
             fields,
             focused_idx: 0,
             scroll_offset: 0,
+            viewport_height: 0,
+            page_size: DEFAULT_PAGE_SIZE,
+            text_checker: None,
+            reviewed_texts: HashMap::new(),
             submit_focused: false,
+            editing_comment: false,
             submitted: false,
             cancelled: false,
-            theme,
+            theme: theme.clone(),
+            custom_theme: theme,
+            high_contrast: false,
             title,
             prelude: definition.prelude.clone(),
             epilogue: definition.epilogue.clone(),
         }
     }
 
+    /// Build the field kind for a select question, preselecting a suggested value if offered.
+    fn select_kind(select_q: &SelectQuestion, default: &DefaultValue) -> FieldKind {
+        let selected = match default {
+            DefaultValue::Suggested(ResponseValue::String(s)) => select_q.position(s),
+            _ => None,
+        }
+        .or(select_q.default);
+        FieldKind::Select {
+            options: select_q.options.clone(),
+            selected,
+            highlight: selected.unwrap_or(0),
+        }
+    }
+
+    /// Build the field kind for a matrix question, filling in a suggested answer if offered.
+    fn matrix_kind(matrix_q: &MatrixQuestion, default: &DefaultValue) -> FieldKind {
+        let mut selected = vec![None; matrix_q.rows.len()];
+        if let DefaultValue::Suggested(ResponseValue::ChosenVariants(columns)) = default {
+            for (slot, &column) in selected.iter_mut().zip(columns) {
+                *slot = (column < matrix_q.columns.len()).then_some(column);
+            }
+        }
+        FieldKind::Matrix {
+            question: matrix_q.clone(),
+            selected,
+            highlight: 0,
+        }
+    }
+
+    /// The initial value of a rating field: the suggestion or default, if it is on the scale.
+    fn rating_default(rating_q: &RatingQuestion, default: &DefaultValue) -> String {
+        match default {
+            DefaultValue::Suggested(ResponseValue::Int(i)) => Some(*i),
+            _ => rating_q.default,
+        }
+        .filter(|value| rating_q.contains(*value))
+        .map(|value| value.to_string())
+        .unwrap_or_default()
+    }
+
     fn flatten_questions(
         questions: &[Question],
         fields: &mut Vec<FormField>,
@@ -293,6 +441,27 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
+                    });
+                }
+                QuestionKind::Autocomplete(autocomplete_q) => {
+                    let default = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::String(s)) => s.clone(),
+                        _ => autocomplete_q.default.clone().unwrap_or_default(),
+                    };
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        kind: FieldKind::Autocomplete {
+                            question: autocomplete_q.clone(),
+                        },
+                        value: default.clone(),
+                        cursor_pos: default.len(),
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
                 }
                 QuestionKind::Multiline(ml_q) => {
@@ -313,6 +482,7 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
                 }
                 QuestionKind::Masked(_) => {
@@ -329,12 +499,13 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
                 }
                 QuestionKind::Int(int_q) => {
                     let default = match question.default() {
-                        DefaultValue::Suggested(ResponseValue::Int(i)) => i.to_string(),
-                        _ => int_q.default.map(|i| i.to_string()).unwrap_or_default(),
+                        DefaultValue::Suggested(ResponseValue::Int(i)) => int_q.format(*i),
+                        _ => int_q.default.map(|i| int_q.format(i)).unwrap_or_default(),
                     };
                     fields.push(FormField {
                         path,
@@ -342,6 +513,8 @@ impl FormState {
                         kind: FieldKind::Int {
                             min: int_q.min,
                             max: int_q.max,
+                            format: int_q.number_format(),
+                            messages: int_q.messages.clone(),
                         },
                         value: default.clone(),
                         cursor_pos: default.len(),
@@ -349,12 +522,81 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
+                    });
+                }
+                QuestionKind::Currency(currency_q) => {
+                    let default = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::Int(minor)) => {
+                            currency_q.format(*minor)
+                        }
+                        _ => currency_q
+                            .default
+                            .map(|minor| currency_q.format(minor))
+                            .unwrap_or_default(),
+                    };
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        kind: FieldKind::Currency {
+                            question: currency_q.clone(),
+                        },
+                        value: default.clone(),
+                        cursor_pos: default.len(),
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                        comment: question.allows_comment().then(String::new),
+                    });
+                }
+                QuestionKind::Color(color_q) => {
+                    let default = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::String(hex)) => hex.clone(),
+                        _ => color_q.default.map(|c| c.to_hex()).unwrap_or_default(),
+                    };
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        kind: FieldKind::Color {
+                            question: color_q.clone(),
+                        },
+                        value: default.clone(),
+                        cursor_pos: default.len(),
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                        comment: question.allows_comment().then(String::new),
+                    });
+                }
+                QuestionKind::Path(path_q) => {
+                    let default = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::String(s)) => s.clone(),
+                        _ => path_q.default.clone().unwrap_or_default(),
+                    };
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        kind: FieldKind::Path {
+                            question: path_q.clone(),
+                        },
+                        value: default.clone(),
+                        cursor_pos: default.len(),
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
                 }
                 QuestionKind::Float(float_q) => {
                     let default = match question.default() {
-                        DefaultValue::Suggested(ResponseValue::Float(f)) => f.to_string(),
-                        _ => float_q.default.map(|f| f.to_string()).unwrap_or_default(),
+                        DefaultValue::Suggested(ResponseValue::Float(f)) => float_q.format(*f),
+                        _ => float_q
+                            .default
+                            .map(|f| float_q.format(f))
+                            .unwrap_or_default(),
                     };
                     fields.push(FormField {
                         path,
@@ -362,6 +604,8 @@ impl FormState {
                         kind: FieldKind::Float {
                             min: float_q.min,
                             max: float_q.max,
+                            format: float_q.number_format(),
+                            messages: float_q.messages.clone(),
                         },
                         value: default.clone(),
                         cursor_pos: default.len(),
@@ -369,6 +613,37 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
+                    });
+                }
+                QuestionKind::Rating(rating_q) => {
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        kind: FieldKind::Rating {
+                            question: rating_q.clone(),
+                        },
+                        value: Self::rating_default(rating_q, question.default()),
+                        cursor_pos: 0,
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                        comment: question.allows_comment().then(String::new),
+                    });
+                }
+                QuestionKind::Matrix(matrix_q) => {
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        kind: Self::matrix_kind(matrix_q, question.default()),
+                        value: String::new(),
+                        cursor_pos: 0,
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
                 }
                 QuestionKind::Confirm(confirm_q) => {
@@ -386,6 +661,7 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
                 }
                 QuestionKind::List(list_q) => {
@@ -401,6 +677,21 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
+                    });
+                }
+                QuestionKind::Select(select_q) => {
+                    fields.push(FormField {
+                        path,
+                        prompt,
+                        kind: Self::select_kind(select_q, question.default()),
+                        value: String::new(),
+                        cursor_pos: 0,
+                        error: None,
+                        assumed,
+                        visibility: VisibilityCondition::Always,
+                        is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
                 }
                 QuestionKind::OneOf(one_of) => {
@@ -422,6 +713,7 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
 
                     // Add nested fields for all variants
@@ -472,6 +764,7 @@ impl FormState {
                         assumed,
                         visibility: VisibilityCondition::Always,
                         is_top_level,
+                        comment: question.allows_comment().then(String::new),
                     });
 
                     // Add nested fields for all variants
@@ -529,17 +822,41 @@ impl FormState {
                     assumed: false,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: None,
                 });
             }
             QuestionKind::Int(int_q) => {
                 let path = parent_path.child(&variant.name);
-                let default = int_q.default.map(|i| i.to_string()).unwrap_or_default();
+                let default = int_q.default.map(|i| int_q.format(i)).unwrap_or_default();
                 fields.push(FormField {
                     path,
                     prompt: variant.name.clone(),
                     kind: FieldKind::Int {
                         min: int_q.min,
                         max: int_q.max,
+                        format: int_q.number_format(),
+                        messages: int_q.messages.clone(),
+                    },
+                    value: default,
+                    cursor_pos: 0,
+                    error: None,
+                    assumed: false,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: None,
+                });
+            }
+            QuestionKind::Currency(currency_q) => {
+                let path = parent_path.child(&variant.name);
+                let default = currency_q
+                    .default
+                    .map(|minor| currency_q.format(minor))
+                    .unwrap_or_default();
+                fields.push(FormField {
+                    path,
+                    prompt: variant.name.clone(),
+                    kind: FieldKind::Currency {
+                        question: currency_q.clone(),
                     },
                     value: default,
                     cursor_pos: 0,
@@ -547,17 +864,57 @@ impl FormState {
                     assumed: false,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: None,
+                });
+            }
+            QuestionKind::Color(color_q) => {
+                let path = parent_path.child(&variant.name);
+                fields.push(FormField {
+                    path,
+                    prompt: variant.name.clone(),
+                    kind: FieldKind::Color {
+                        question: color_q.clone(),
+                    },
+                    value: color_q.default.map(|c| c.to_hex()).unwrap_or_default(),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed: false,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: None,
+                });
+            }
+            QuestionKind::Path(path_q) => {
+                let path = parent_path.child(&variant.name);
+                fields.push(FormField {
+                    path,
+                    prompt: variant.name.clone(),
+                    kind: FieldKind::Path {
+                        question: path_q.clone(),
+                    },
+                    value: path_q.default.clone().unwrap_or_default(),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed: false,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: None,
                 });
             }
             QuestionKind::Float(float_q) => {
                 let path = parent_path.child(&variant.name);
-                let default = float_q.default.map(|f| f.to_string()).unwrap_or_default();
+                let default = float_q
+                    .default
+                    .map(|f| float_q.format(f))
+                    .unwrap_or_default();
                 fields.push(FormField {
                     path,
                     prompt: variant.name.clone(),
                     kind: FieldKind::Float {
                         min: float_q.min,
                         max: float_q.max,
+                        format: float_q.number_format(),
+                        messages: float_q.messages.clone(),
                     },
                     value: default,
                     cursor_pos: 0,
@@ -565,6 +922,24 @@ impl FormState {
                     assumed: false,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: None,
+                });
+            }
+            QuestionKind::Rating(rating_q) => {
+                let path = parent_path.child(&variant.name);
+                fields.push(FormField {
+                    path,
+                    prompt: variant.name.clone(),
+                    kind: FieldKind::Rating {
+                        question: rating_q.clone(),
+                    },
+                    value: Self::rating_default(rating_q, &DefaultValue::None),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed: false,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: None,
                 });
             }
             QuestionKind::Confirm(confirm_q) => {
@@ -579,6 +954,7 @@ impl FormState {
                     assumed: false,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: None,
                 });
             }
             QuestionKind::OneOf(one_of) => {
@@ -597,6 +973,7 @@ impl FormState {
                     assumed: false,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: None,
                 });
                 for (idx, v) in one_of.variants.iter().enumerate() {
                     Self::add_variant_fields(
@@ -632,6 +1009,7 @@ impl FormState {
                     assumed: false,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: None,
                 });
                 for (idx, v) in any_of.variants.iter().enumerate() {
                     let item_path = path.child(&idx.to_string());
@@ -703,6 +1081,27 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
+                });
+            }
+            QuestionKind::Autocomplete(autocomplete_q) => {
+                let default = match question.default() {
+                    DefaultValue::Suggested(ResponseValue::String(s)) => s.clone(),
+                    _ => autocomplete_q.default.clone().unwrap_or_default(),
+                };
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    kind: FieldKind::Autocomplete {
+                        question: autocomplete_q.clone(),
+                    },
+                    value: default.clone(),
+                    cursor_pos: default.len(),
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
             }
             QuestionKind::Multiline(ml_q) => {
@@ -723,6 +1122,7 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
             }
             QuestionKind::Masked(_) => {
@@ -739,12 +1139,13 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
             }
             QuestionKind::Int(int_q) => {
                 let default = match question.default() {
-                    DefaultValue::Suggested(ResponseValue::Int(i)) => i.to_string(),
-                    _ => int_q.default.map(|i| i.to_string()).unwrap_or_default(),
+                    DefaultValue::Suggested(ResponseValue::Int(i)) => int_q.format(*i),
+                    _ => int_q.default.map(|i| int_q.format(i)).unwrap_or_default(),
                 };
                 fields.push(FormField {
                     path,
@@ -752,6 +1153,31 @@ impl FormState {
                     kind: FieldKind::Int {
                         min: int_q.min,
                         max: int_q.max,
+                        format: int_q.number_format(),
+                        messages: int_q.messages.clone(),
+                    },
+                    value: default.clone(),
+                    cursor_pos: default.len(),
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
+                });
+            }
+            QuestionKind::Currency(currency_q) => {
+                let default = match question.default() {
+                    DefaultValue::Suggested(ResponseValue::Int(minor)) => currency_q.format(*minor),
+                    _ => currency_q
+                        .default
+                        .map(|minor| currency_q.format(minor))
+                        .unwrap_or_default(),
+                };
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    kind: FieldKind::Currency {
+                        question: currency_q.clone(),
                     },
                     value: default.clone(),
                     cursor_pos: default.len(),
@@ -759,12 +1185,56 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
+                });
+            }
+            QuestionKind::Color(color_q) => {
+                let default = match question.default() {
+                    DefaultValue::Suggested(ResponseValue::String(hex)) => hex.clone(),
+                    _ => color_q.default.map(|c| c.to_hex()).unwrap_or_default(),
+                };
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    kind: FieldKind::Color {
+                        question: color_q.clone(),
+                    },
+                    value: default.clone(),
+                    cursor_pos: default.len(),
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
+                });
+            }
+            QuestionKind::Path(path_q) => {
+                let default = match question.default() {
+                    DefaultValue::Suggested(ResponseValue::String(s)) => s.clone(),
+                    _ => path_q.default.clone().unwrap_or_default(),
+                };
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    kind: FieldKind::Path {
+                        question: path_q.clone(),
+                    },
+                    value: default.clone(),
+                    cursor_pos: default.len(),
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
             }
             QuestionKind::Float(float_q) => {
                 let default = match question.default() {
-                    DefaultValue::Suggested(ResponseValue::Float(f)) => f.to_string(),
-                    _ => float_q.default.map(|f| f.to_string()).unwrap_or_default(),
+                    DefaultValue::Suggested(ResponseValue::Float(f)) => float_q.format(*f),
+                    _ => float_q
+                        .default
+                        .map(|f| float_q.format(f))
+                        .unwrap_or_default(),
                 };
                 fields.push(FormField {
                     path,
@@ -772,6 +1242,8 @@ impl FormState {
                     kind: FieldKind::Float {
                         min: float_q.min,
                         max: float_q.max,
+                        format: float_q.number_format(),
+                        messages: float_q.messages.clone(),
                     },
                     value: default.clone(),
                     cursor_pos: default.len(),
@@ -779,6 +1251,37 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
+                });
+            }
+            QuestionKind::Rating(rating_q) => {
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    kind: FieldKind::Rating {
+                        question: rating_q.clone(),
+                    },
+                    value: Self::rating_default(rating_q, question.default()),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
+                });
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    kind: Self::matrix_kind(matrix_q, question.default()),
+                    value: String::new(),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
             }
             QuestionKind::Confirm(confirm_q) => {
@@ -796,6 +1299,7 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
             }
             QuestionKind::List(list_q) => {
@@ -811,6 +1315,21 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
+                });
+            }
+            QuestionKind::Select(select_q) => {
+                fields.push(FormField {
+                    path,
+                    prompt,
+                    kind: Self::select_kind(select_q, question.default()),
+                    value: String::new(),
+                    cursor_pos: 0,
+                    error: None,
+                    assumed,
+                    visibility: visibility.clone(),
+                    is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
             }
             QuestionKind::OneOf(one_of) => {
@@ -832,6 +1351,7 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
 
                 // Add nested fields for all variants
@@ -882,6 +1402,7 @@ impl FormState {
                     assumed,
                     visibility: visibility.clone(),
                     is_top_level: false,
+                    comment: question.allows_comment().then(String::new),
                 });
 
                 // Add nested fields for all variants
@@ -953,6 +1474,25 @@ impl FormState {
             .unwrap_or(false)
     }
 
+    /// Switch to the high-contrast theme with more spacing, or back
+    fn set_high_contrast(&mut self, enabled: bool) {
+        self.high_contrast = enabled;
+        self.theme = if enabled {
+            Theme::high_contrast()
+        } else {
+            self.custom_theme.clone()
+        };
+    }
+
+    /// Extra vertical space before top-level fields
+    fn spacing(&self) -> u16 {
+        if self.high_contrast {
+            HIGH_CONTRAST_SPACING
+        } else {
+            TOP_LEVEL_SPACING
+        }
+    }
+
     /// Calculate the Y position of a field (by index) in the virtual scroll area
     fn field_y_position(&self, target_idx: usize) -> u16 {
         let mut y: u16 = 0;
@@ -965,7 +1505,11 @@ impl FormState {
                 return y;
             }
             // Include spacing for all except the first visible field
-            y += get_field_height(field, !is_first);
+            y += get_field_height(
+                field,
+                if is_first { 0 } else { self.spacing() },
+                self.page_size,
+            );
             is_first = false;
         }
         y
@@ -979,7 +1523,11 @@ impl FormState {
             if !self.is_field_visible(field) {
                 continue;
             }
-            total += get_field_height(field, !is_first);
+            total += get_field_height(
+                field,
+                if is_first { 0 } else { self.spacing() },
+                self.page_size,
+            );
             is_first = false;
         }
         total
@@ -993,19 +1541,20 @@ impl FormState {
         let is_first_visible = self
             .fields
             .iter()
-            .filter(|f| self.is_field_visible(f))
-            .next()
+            .find(|f| self.is_field_visible(f))
             .map(|f| std::ptr::eq(f, &self.fields[self.focused_idx]))
             .unwrap_or(false);
 
         let focused = self.focused_field();
         let spacing = if !is_first_visible && focused.map(|f| f.is_top_level).unwrap_or(false) {
-            TOP_LEVEL_SPACING
+            self.spacing()
         } else {
             0
         };
 
-        let field_height = focused.map(|f| get_field_height(f, false)).unwrap_or(3);
+        let field_height = focused
+            .map(|f| get_field_height(f, 0, self.page_size))
+            .unwrap_or(3);
 
         // If field is above viewport, scroll up (to show spacing too)
         if field_y < self.scroll_offset {
@@ -1040,6 +1589,45 @@ impl FormState {
         }
     }
 
+    /// Move the focus about one screen down or up.
+    fn page_fields(&mut self, down: bool) {
+        let start = self.field_y_position(self.focused_idx);
+        let distance = self.viewport_height.max(1);
+        loop {
+            let before = (self.focused_idx, self.submit_focused);
+            if down {
+                self.next_field();
+            } else {
+                self.prev_field();
+            }
+            if (self.focused_idx, self.submit_focused) == before
+                || self.submit_focused
+                || self.field_y_position(self.focused_idx).abs_diff(start) >= distance
+            {
+                break;
+            }
+        }
+    }
+
+    /// Move the highlight of the focused choice list one page down or up.
+    ///
+    /// Returns `false` if the focused field is not a list with several pages.
+    fn page_options(&mut self, down: bool) -> bool {
+        let page_size = self.page_size;
+        let Some((len, highlight)) = self.focused_field_mut().and_then(choice_highlight) else {
+            return false;
+        };
+        if len <= page_size {
+            return false;
+        }
+        *highlight = if down {
+            (*highlight + page_size).min(len - 1)
+        } else {
+            highlight.saturating_sub(page_size)
+        };
+        true
+    }
+
     fn prev_field(&mut self) {
         if self.submit_focused {
             // Move from submit button back to last visible field
@@ -1070,10 +1658,19 @@ impl FormState {
         }
     }
 
+    /// Start or stop editing the focused field's comment, if it takes one.
+    fn toggle_comment(&mut self) {
+        let allowed = !self.submit_focused
+            && self
+                .focused_field()
+                .is_some_and(|field| field.comment.is_some());
+        self.editing_comment = allowed && !self.editing_comment;
+    }
+
     fn handle_text_input(&mut self, c: char) {
         if let Some(field) = self.focused_field_mut() {
             field.value.insert(field.cursor_pos, c);
-            field.cursor_pos += 1;
+            field.cursor_pos += c.len_utf8();
             field.error = None;
         }
     }
@@ -1082,7 +1679,7 @@ impl FormState {
         if let Some(field) = self.focused_field_mut()
             && field.cursor_pos > 0
         {
-            field.cursor_pos -= 1;
+            field.cursor_pos -= field.char_before_cursor();
             field.value.remove(field.cursor_pos);
             field.error = None;
         }
@@ -1101,7 +1698,7 @@ impl FormState {
         if let Some(field) = self.focused_field_mut()
             && field.cursor_pos > 0
         {
-            field.cursor_pos -= 1;
+            field.cursor_pos -= field.char_before_cursor();
         }
     }
 
@@ -1109,7 +1706,57 @@ impl FormState {
         if let Some(field) = self.focused_field_mut()
             && field.cursor_pos < field.value.len()
         {
-            field.cursor_pos += 1;
+            field.cursor_pos += field.value[field.cursor_pos..]
+                .chars()
+                .next()
+                .map_or(0, char::len_utf8);
+        }
+    }
+
+    /// Complete an autocomplete field with its best suggestion.
+    ///
+    /// Returns `false` if there is nothing (new) to complete, so Tab can fall
+    /// through to moving to the next field.
+    fn accept_completion(&mut self) -> bool {
+        if self.submit_focused {
+            return false;
+        }
+        if let Some(field) = self.focused_field_mut()
+            && let FieldKind::Autocomplete { question } = &field.kind
+            && let Some(best) = question.completions(&field.value).into_iter().next()
+            && best != field.value
+        {
+            field.cursor_pos = best.len();
+            field.value = best;
+            field.error = None;
+            return true;
+        }
+        false
+    }
+
+    /// Move a rating field along its scale, starting at the lowest point if unrated.
+    fn rate_step(&mut self, forward: bool) {
+        if let Some(field) = self.focused_field_mut()
+            && let FieldKind::Rating { question } = &field.kind
+        {
+            let value = match field.value.parse::<i64>() {
+                Ok(current) if forward => (current + 1).min(question.max),
+                Ok(current) => (current - 1).max(question.min),
+                Err(_) => question.min,
+            };
+            field.value = value.to_string();
+            field.error = None;
+        }
+    }
+
+    /// Set a rating field to the given point, if it is on the scale.
+    fn rate(&mut self, value: i64) {
+        if let Some(field) = self.focused_field_mut()
+            && let FieldKind::Rating { question } = &field.kind
+            && question.contains(value)
+        {
+            field.value = value.to_string();
+            field.error = None;
         }
     }
 
@@ -1126,54 +1773,70 @@ impl FormState {
         }
     }
 
-    /// Move highlight up within OneOf/AnyOf options
+    /// Move highlight up within Select/OneOf/AnyOf options or matrix rows
     fn option_up(&mut self) {
         if let Some(field) = self.focused_field_mut() {
             match &mut field.kind {
+                FieldKind::Select {
+                    options, highlight, ..
+                } if !options.is_empty() => {
+                    *highlight = (*highlight + options.len() - 1) % options.len();
+                }
                 FieldKind::OneOf {
                     variants,
                     highlight,
                     ..
-                } => {
-                    if !variants.is_empty() {
-                        *highlight = (*highlight + variants.len() - 1) % variants.len();
-                    }
+                } if !variants.is_empty() => {
+                    *highlight = (*highlight + variants.len() - 1) % variants.len();
                 }
                 FieldKind::AnyOf {
                     variants,
                     highlight,
                     ..
-                } => {
-                    if !variants.is_empty() {
-                        *highlight = (*highlight + variants.len() - 1) % variants.len();
-                    }
+                } if !variants.is_empty() => {
+                    *highlight = (*highlight + variants.len() - 1) % variants.len();
+                }
+                FieldKind::Matrix {
+                    selected,
+                    highlight,
+                    ..
+                } if !selected.is_empty() => {
+                    *highlight = (*highlight + selected.len() - 1) % selected.len();
                 }
                 _ => {}
             }
         }
     }
 
-    /// Move highlight down within OneOf/AnyOf options
+    /// Move highlight down within Select/OneOf/AnyOf options or matrix rows
     fn option_down(&mut self) {
         if let Some(field) = self.focused_field_mut() {
             match &mut field.kind {
+                FieldKind::Select {
+                    options, highlight, ..
+                } if !options.is_empty() => {
+                    *highlight = (*highlight + 1) % options.len();
+                }
                 FieldKind::OneOf {
                     variants,
                     highlight,
                     ..
-                } => {
-                    if !variants.is_empty() {
-                        *highlight = (*highlight + 1) % variants.len();
-                    }
+                } if !variants.is_empty() => {
+                    *highlight = (*highlight + 1) % variants.len();
                 }
                 FieldKind::AnyOf {
                     variants,
                     highlight,
                     ..
-                } => {
-                    if !variants.is_empty() {
-                        *highlight = (*highlight + 1) % variants.len();
-                    }
+                } if !variants.is_empty() => {
+                    *highlight = (*highlight + 1) % variants.len();
+                }
+                FieldKind::Matrix {
+                    selected,
+                    highlight,
+                    ..
+                } if !selected.is_empty() => {
+                    *highlight = (*highlight + 1) % selected.len();
                 }
                 _ => {}
             }
@@ -1184,6 +1847,13 @@ impl FormState {
     fn select_option(&mut self) {
         if let Some(field) = self.focused_field_mut() {
             match &mut field.kind {
+                FieldKind::Select {
+                    options,
+                    highlight,
+                    selected,
+                } if *highlight < options.len() => {
+                    *selected = Some(*highlight);
+                }
                 FieldKind::OneOf {
                     highlight,
                     selected,
@@ -1195,26 +1865,84 @@ impl FormState {
                     highlight,
                     selected,
                     ..
-                } => {
-                    if *highlight < selected.len() {
-                        selected[*highlight] = !selected[*highlight];
-                    }
+                } if *highlight < selected.len() => {
+                    selected[*highlight] = !selected[*highlight];
                 }
                 _ => {}
             }
         }
     }
 
-    /// Check if current field is a selection type (OneOf/AnyOf)
+    /// Check if current field is a selection type (Select/OneOf/AnyOf/Matrix)
     fn is_selection_field(&self) -> bool {
         if self.submit_focused {
             return false;
         }
         self.focused_field()
-            .map(|f| matches!(f.kind, FieldKind::OneOf { .. } | FieldKind::AnyOf { .. }))
+            .map(|f| {
+                matches!(
+                    f.kind,
+                    FieldKind::Select { .. }
+                        | FieldKind::OneOf { .. }
+                        | FieldKind::AnyOf { .. }
+                        | FieldKind::Matrix { .. }
+                )
+            })
             .unwrap_or(false)
     }
 
+    /// Move the choice of the highlighted matrix row one column left or right.
+    fn matrix_step(&mut self, forward: bool) {
+        if let Some(field) = self.focused_field_mut()
+            && let FieldKind::Matrix {
+                question,
+                selected,
+                highlight,
+            } = &mut field.kind
+            && let Some(choice) = selected.get_mut(*highlight)
+        {
+            let last = question.columns.len().saturating_sub(1);
+            *choice = Some(match (*choice, forward) {
+                (None, _) => 0,
+                (Some(column), true) => (column + 1).min(last),
+                (Some(column), false) => column.saturating_sub(1),
+            });
+            field.error = None;
+        }
+    }
+
+    /// Choose a column (by index) for the highlighted matrix row.
+    fn matrix_choose(&mut self, column: usize) {
+        if let Some(field) = self.focused_field_mut()
+            && let FieldKind::Matrix {
+                question,
+                selected,
+                highlight,
+            } = &mut field.kind
+            && column < question.columns.len()
+            && let Some(choice) = selected.get_mut(*highlight)
+        {
+            *choice = Some(column);
+            field.error = None;
+        }
+    }
+
+    /// Check if current field is a matrix
+    fn is_matrix_field(&self) -> bool {
+        !self.submit_focused
+            && self
+                .focused_field()
+                .is_some_and(|f| matches!(f.kind, FieldKind::Matrix { .. }))
+    }
+
+    /// Check if current field is a rating scale
+    fn is_rating_field(&self) -> bool {
+        !self.submit_focused
+            && self
+                .focused_field()
+                .is_some_and(|f| matches!(f.kind, FieldKind::Rating { .. }))
+    }
+
     fn toggle_anyof(&mut self, idx: usize) {
         if let Some(field) = self.focused_field_mut()
             && let FieldKind::AnyOf { selected, .. } = &mut field.kind
@@ -1236,22 +1964,47 @@ impl FormState {
             }
 
             match &field.kind {
-                FieldKind::Text { .. } => {
+                FieldKind::Text { .. } | FieldKind::Autocomplete { .. } => {
                     responses.insert(
                         field.path.clone(),
                         ResponseValue::String(field.value.clone()),
                     );
                 }
-                FieldKind::Int { .. } => {
+                FieldKind::Int {
+                    format, messages, ..
+                } => {
+                    if let Ok(n) = format.parse_int(&field.value, messages) {
+                        responses.insert(field.path.clone(), ResponseValue::Int(n));
+                    }
+                }
+                FieldKind::Rating { .. } => {
                     if let Ok(n) = field.value.parse::<i64>() {
                         responses.insert(field.path.clone(), ResponseValue::Int(n));
                     }
                 }
-                FieldKind::Float { .. } => {
-                    if let Ok(n) = field.value.parse::<f64>() {
+                FieldKind::Float {
+                    format, messages, ..
+                } => {
+                    if let Ok(n) = format.parse_float(&field.value, messages) {
                         responses.insert(field.path.clone(), ResponseValue::Float(n));
                     }
                 }
+                FieldKind::Currency { question } => {
+                    if let Ok(minor) = question.parse(&field.value) {
+                        responses.insert(field.path.clone(), ResponseValue::Int(minor));
+                    }
+                }
+                FieldKind::Color { question } => {
+                    if let Ok(color) = question.parse(&field.value) {
+                        responses.insert(field.path.clone(), ResponseValue::String(color.to_hex()));
+                    }
+                }
+                FieldKind::Path { .. } => {
+                    responses.insert(
+                        field.path.clone(),
+                        ResponseValue::String(field.value.clone()),
+                    );
+                }
                 FieldKind::Bool => {
                     let b = field.value == "true";
                     responses.insert(field.path.clone(), ResponseValue::Bool(b));
@@ -1289,6 +2042,22 @@ impl FormState {
                     };
                     responses.insert(field.path.clone(), rv);
                 }
+                FieldKind::Select {
+                    options, selected, ..
+                } => {
+                    if let Some(option) = selected.and_then(|idx| options.get(idx)) {
+                        responses.insert(
+                            field.path.clone(),
+                            ResponseValue::String(option.value.clone()),
+                        );
+                    }
+                }
+                FieldKind::Matrix { selected, .. } => {
+                    if let Some(columns) = selected.iter().copied().collect::<Option<Vec<_>>>() {
+                        responses
+                            .insert(field.path.clone(), ResponseValue::ChosenVariants(columns));
+                    }
+                }
                 FieldKind::OneOf { selected, .. } => {
                     if let Some(idx) = selected {
                         let variant_path = field.path.child(SELECTED_VARIANT_KEY);
@@ -1316,6 +2085,15 @@ impl FormState {
                     }
                 }
             }
+
+            if let Some(comment) = field.comment.as_deref().map(str::trim)
+                && !comment.is_empty()
+            {
+                responses.insert(
+                    field.path.child(COMMENT_KEY),
+                    ResponseValue::String(comment.to_string()),
+                );
+            }
         }
 
         responses
@@ -1345,48 +2123,87 @@ impl FormState {
 
             // Basic validation (min/max, type parsing, required selections)
             match &field.kind {
-                FieldKind::OneOf { selected, .. } => {
-                    if selected.is_none() {
-                        field.error = Some("Please select an option".to_string());
-                        has_errors = true;
-                    }
+                FieldKind::Select { selected, .. } | FieldKind::OneOf { selected, .. }
+                    if selected.is_none() =>
+                {
+                    field.error = Some("Please select an option".to_string());
+                    has_errors = true;
+                }
+                FieldKind::Rating { .. } if field.value.is_empty() => {
+                    field.error = Some("Please pick a rating".to_string());
+                    has_errors = true;
                 }
-                FieldKind::Int { min, max } => match field.value.parse::<i64>() {
+                FieldKind::Matrix { selected, .. } if selected.contains(&None) => {
+                    field.error = Some("Please answer every row".to_string());
+                    has_errors = true;
+                }
+                FieldKind::Int {
+                    min,
+                    max,
+                    format,
+                    messages,
+                } => match format.parse_int(&field.value, messages) {
                     Ok(n) => {
                         if let Some(m) = min
                             && n < *m
                         {
-                            field.error = Some(format!("Must be at least {}", m));
+                            field.error = Some(messages.at_least(&format.format_int(*m)));
                             has_errors = true;
                         } else if let Some(m) = max
                             && n > *m
                         {
-                            field.error = Some(format!("Must be at most {}", m));
+                            field.error = Some(messages.at_most(&format.format_int(*m)));
                             has_errors = true;
                         }
                     }
-                    Err(_) if !field.value.is_empty() => {
-                        field.error = Some("Invalid integer".to_string());
+                    Err(message) if !field.value.is_empty() => {
+                        field.error = Some(message);
                         has_errors = true;
                     }
                     _ => {}
                 },
-                FieldKind::Float { min, max } => match field.value.parse::<f64>() {
+                FieldKind::Currency { question } if !field.value.is_empty() => {
+                    if let Err(msg) = question
+                        .parse(&field.value)
+                        .and_then(|minor| question.check_bounds(minor))
+                    {
+                        field.error = Some(msg);
+                        has_errors = true;
+                    }
+                }
+                FieldKind::Color { question } if !field.value.is_empty() => {
+                    if let Err(msg) = question.parse(&field.value) {
+                        field.error = Some(msg);
+                        has_errors = true;
+                    }
+                }
+                FieldKind::Path { question } => {
+                    if let Err(msg) = question.check(&field.value) {
+                        field.error = Some(msg);
+                        has_errors = true;
+                    }
+                }
+                FieldKind::Float {
+                    min,
+                    max,
+                    format,
+                    messages,
+                } => match format.parse_float(&field.value, messages) {
                     Ok(n) => {
                         if let Some(m) = min
                             && n < *m
                         {
-                            field.error = Some(format!("Must be at least {}", m));
+                            field.error = Some(messages.at_least(&format.format_float(*m)));
                             has_errors = true;
                         } else if let Some(m) = max
                             && n > *m
                         {
-                            field.error = Some(format!("Must be at most {}", m));
+                            field.error = Some(messages.at_most(&format.format_float(*m)));
                             has_errors = true;
                         }
                     }
-                    Err(_) if !field.value.is_empty() => {
-                        field.error = Some("Invalid number".to_string());
+                    Err(message) if !field.value.is_empty() => {
+                        field.error = Some(message);
                         has_errors = true;
                     }
                     _ => {}
@@ -1400,7 +2217,7 @@ impl FormState {
         let mut validation_errors: std::collections::HashMap<ResponsePath, String> =
             std::collections::HashMap::new();
 
-        for (path, value) in responses.iter() {
+        for (path, value) in responses.iter_exposed() {
             if let Err(e) = validate(value, &responses, path) {
                 validation_errors.insert(path.clone(), e);
             }
@@ -1419,6 +2236,37 @@ impl FormState {
             }
         }
 
+        // Fourth pass: show the findings of the text checker once per text
+        if let Some(checker) = &self.text_checker {
+            for idx in &visible_indices {
+                let field = &mut self.fields[*idx];
+                if field.error.is_some()
+                    || !matches!(
+                        field.kind,
+                        FieldKind::Text {
+                            multiline: true,
+                            ..
+                        }
+                    )
+                    || self.reviewed_texts.get(&field.path) == Some(&field.value)
+                {
+                    continue;
+                }
+                let issues = checker.check(&field.value);
+                if issues.is_empty() {
+                    continue;
+                }
+                self.reviewed_texts
+                    .insert(field.path.clone(), field.value.clone());
+                let findings: Vec<String> = issues.iter().map(ToString::to_string).collect();
+                field.error = Some(format!(
+                    "{} (submit again to keep the text)",
+                    findings.join("; ")
+                ));
+                has_errors = true;
+            }
+        }
+
         // If there are errors, focus the first field with an error
         if has_errors {
             for idx in &visible_indices {
@@ -1438,9 +2286,17 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
     let area = frame.area();
     let theme = state.theme.clone();
 
+    // Background, with all text bold in high-contrast mode
+    let mut background = Style::default().bg(theme.background);
+    if state.high_contrast {
+        background = background.add_modifier(Modifier::BOLD);
+    }
+    frame.render_widget(Block::default().style(background), area);
+
     // Main layout
     let chunks = Layout::default()
         .direction(Direction::Vertical)
+        .horizontal_margin(if state.high_contrast { 2 } else { 0 })
         .constraints([
             Constraint::Length(3), // Title
             Constraint::Min(10),   // Form content
@@ -1474,6 +2330,7 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
     let viewport_height = form_area.height;
 
     // Adjust scroll to keep focused field visible
+    state.viewport_height = viewport_height;
     state.adjust_scroll(viewport_height);
 
     let total_height = state.total_content_height();
@@ -1493,13 +2350,14 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
     for (field_idx, field) in visible_fields.iter() {
         // Field is only focused if it's the focused index AND the submit button is not focused
         let is_focused = !state.submit_focused && *field_idx == state.focused_idx;
+        let editing_comment = is_focused && state.editing_comment;
 
         // Include spacing for top-level fields (except the first visible one)
-        let include_spacing = !is_first_visible;
-        let field_height_with_spacing = get_field_height(field, include_spacing);
-        let field_height_base = get_field_height(field, false);
-        let spacing = if include_spacing && field.is_top_level {
-            TOP_LEVEL_SPACING
+        let spacing_before = if is_first_visible { 0 } else { state.spacing() };
+        let field_height_with_spacing = get_field_height(field, spacing_before, state.page_size);
+        let field_height_base = get_field_height(field, 0, state.page_size);
+        let spacing = if field.is_top_level {
+            spacing_before
         } else {
             0
         };
@@ -1536,10 +2394,26 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
 
             // Only draw if we have the full field height (to avoid partial rendering issues)
             if clip_top == 0 && visible_height >= field_height_base {
-                draw_field(frame, field, field_area, is_focused, &theme);
+                draw_field(
+                    frame,
+                    field,
+                    field_area,
+                    is_focused,
+                    editing_comment,
+                    state.page_size,
+                    &theme,
+                );
             } else if clip_top == 0 {
                 // Field is partially visible at the bottom - draw what we can
-                draw_field(frame, field, field_area, is_focused, &theme);
+                draw_field(
+                    frame,
+                    field,
+                    field_area,
+                    is_focused,
+                    editing_comment,
+                    state.page_size,
+                    &theme,
+                );
             }
             // Skip fields that are clipped at the top (they look weird)
         }
@@ -1602,7 +2476,7 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
     frame.render_widget(submit_button, chunks[2]);
 
     // Help bar
-    let help_text = "Tab: Next  ↑/↓: Navigate  Space/Enter: Select  Esc: Cancel";
+    let help_text = "Tab: Next  ↑/↓: Navigate  PgUp/PgDn: Page  ←/→: Rate/Choose  Space/Enter: Select  F2: Contrast  Esc: Cancel";
     let help = Paragraph::new(help_text).style(Style::default().fg(theme.border));
     frame.render_widget(help, chunks[3]);
 }
@@ -1610,24 +2484,81 @@ fn draw_form(frame: &mut Frame, state: &mut FormState) {
 /// Extra vertical space before top-level fields (section spacing)
 const TOP_LEVEL_SPACING: u16 = 1;
 
-fn get_field_height(field: &FormField, include_spacing: bool) -> u16 {
+/// Section spacing in high-contrast mode
+const HIGH_CONTRAST_SPACING: u16 = 2;
+
+/// Number of options and the highlighted option of a choice list field
+fn choice_highlight(field: &mut FormField) -> Option<(usize, &mut usize)> {
+    match &mut field.kind {
+        FieldKind::Select {
+            options, highlight, ..
+        } => Some((options.len(), highlight)),
+        FieldKind::OneOf {
+            variants,
+            highlight,
+            ..
+        }
+        | FieldKind::AnyOf {
+            variants,
+            highlight,
+            ..
+        } => Some((variants.len(), highlight)),
+        _ => None,
+    }
+}
+
+/// The options of a choice list on the page of the highlighted one
+/// Shorten `text` to `width` terminal columns, ending in `…` when cut.
+fn truncate_to_width(text: &str, width: usize) -> Cow<'_, str> {
+    if text.width() <= width {
+        return Cow::Borrowed(text);
+    }
+    let budget = width.saturating_sub(1);
+    let mut used = 0;
+    let kept = text.chars().take_while(|c| {
+        used += c.width().unwrap_or(0);
+        used <= budget
+    });
+    Cow::Owned(kept.chain(['…']).collect())
+}
+
+fn page_range(len: usize, highlight: usize, page_size: usize) -> std::ops::Range<usize> {
+    let start = highlight.min(len.saturating_sub(1)) / page_size * page_size;
+    start..(start + page_size).min(len)
+}
+
+/// Height of a field, plus `spacing` before it if it is a top-level field
+fn get_field_height(field: &FormField, spacing: u16, page_size: usize) -> u16 {
+    let rows = |len: usize| 2 + len.min(page_size) as u16;
     let base_height = match &field.kind {
         FieldKind::Text {
             multiline: true, ..
         } => 4,
-        FieldKind::OneOf { variants, .. } => 2 + variants.len() as u16,
-        FieldKind::AnyOf { variants, .. } => 2 + variants.len() as u16,
+        FieldKind::Autocomplete { .. } => 4,
+        FieldKind::Select { options, .. } => rows(options.len()),
+        FieldKind::OneOf { variants, .. } => rows(variants.len()),
+        FieldKind::AnyOf { variants, .. } => rows(variants.len()),
+        // Borders, column header and one line per row
+        FieldKind::Matrix { question, .. } => 3 + question.rows.len() as u16,
         _ => 3,
     };
     // Add spacing before top-level fields (except the first one)
-    if include_spacing && field.is_top_level {
-        base_height + TOP_LEVEL_SPACING
+    if field.is_top_level {
+        base_height + spacing
     } else {
         base_height
     }
 }
 
-fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool, theme: &Theme) {
+fn draw_field(
+    frame: &mut Frame,
+    field: &FormField,
+    area: Rect,
+    is_focused: bool,
+    editing_comment: bool,
+    page_size: usize,
+    theme: &Theme,
+) {
     let border_color = if field.error.is_some() {
         theme.error
     } else if is_focused {
@@ -1646,13 +2577,66 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
             theme.text
         }));
 
+    // The comment lives in the bottom border so it does not change the field's height
+    let block = match field.comment.as_deref() {
+        Some(comment) if editing_comment => block.title_bottom(Line::styled(
+            format!(" Comment: {}█ ", comment),
+            Style::default().fg(theme.highlight),
+        )),
+        Some(comment) if !comment.trim().is_empty() => block.title_bottom(Line::styled(
+            format!(" Comment: {} ", comment.trim()),
+            Style::default().fg(theme.secondary),
+        )),
+        Some(_) if is_focused => block.title_bottom(Line::styled(
+            " Ctrl+N: Comment ",
+            Style::default().fg(theme.border),
+        )),
+        _ => block,
+    };
+
+    // Long choice lists show one page at a time, with the page number in the border
+    let page = match &field.kind {
+        FieldKind::Select {
+            options, highlight, ..
+        } => Some((options.len(), *highlight)),
+        FieldKind::OneOf {
+            variants,
+            highlight,
+            ..
+        }
+        | FieldKind::AnyOf {
+            variants,
+            highlight,
+            ..
+        } => Some((variants.len(), *highlight)),
+        _ => None,
+    }
+    .map(|(len, highlight)| (len, page_range(len, highlight, page_size)));
+    let block = match &page {
+        Some((len, range)) if *len > page_size => block.title_bottom(
+            Line::styled(
+                format!(
+                    " {}/{} ",
+                    range.start / page_size + 1,
+                    len.div_ceil(page_size)
+                ),
+                Style::default().fg(theme.secondary),
+            )
+            .right_aligned(),
+        ),
+        _ => block,
+    };
+    let range = page.map(|(_, range)| range).unwrap_or_default();
+
     let inner = block.inner(area);
     frame.render_widget(block, area);
+    // Choice labels after the `(●) ` marker
+    let label_width = usize::from(inner.width.saturating_sub(4));
 
     match &field.kind {
         FieldKind::Text { masked, .. } => {
             let display_text = if *masked {
-                "●".repeat(field.value.len())
+                "●".repeat(field.value.chars().count())
             } else {
                 field.value.clone()
             };
@@ -1660,7 +2644,37 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
             frame.render_widget(text, inner);
 
             if is_focused {
-                let cursor_x = inner.x + field.cursor_pos as u16;
+                let cursor_x = inner.x + field.cursor_column();
+                let cursor_y = inner.y;
+                if cursor_x < inner.x + inner.width {
+                    frame.set_cursor_position((cursor_x, cursor_y));
+                }
+            }
+        }
+        FieldKind::Autocomplete { question } => {
+            let text = Paragraph::new(field.value.clone()).style(Style::default().fg(theme.text));
+            frame.render_widget(text, inner);
+
+            // Fuzzy-filtered suggestions below the input, best match first
+            if is_focused && inner.height > 1 {
+                let suggestions = question
+                    .completions(&field.value)
+                    .into_iter()
+                    .take(5)
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+                let hint = Paragraph::new(format!("↳ {}", suggestions))
+                    .style(Style::default().fg(theme.border));
+                let hint_area = Rect {
+                    y: inner.y + 1,
+                    height: 1,
+                    ..inner
+                };
+                frame.render_widget(hint, hint_area);
+            }
+
+            if is_focused {
+                let cursor_x = inner.x + field.cursor_column();
                 let cursor_y = inner.y;
                 if cursor_x < inner.x + inner.width {
                     frame.set_cursor_position((cursor_x, cursor_y));
@@ -1672,7 +2686,86 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
             frame.render_widget(text, inner);
 
             if is_focused {
-                let cursor_x = inner.x + field.cursor_pos as u16;
+                let cursor_x = inner.x + field.cursor_column();
+                let cursor_y = inner.y;
+                if cursor_x < inner.x + inner.width {
+                    frame.set_cursor_position((cursor_x, cursor_y));
+                }
+            }
+        }
+        FieldKind::Currency { question } => {
+            // Preview the typed amount in the currency's format
+            let mut spans = vec![Span::styled(
+                field.value.clone(),
+                Style::default().fg(theme.text),
+            )];
+            match question.parse(&field.value) {
+                Ok(minor) if question.format(minor) != field.value => {
+                    spans.push(Span::styled(
+                        format!("  = {}", question.format(minor)),
+                        Style::default().fg(theme.border),
+                    ));
+                }
+                Ok(_) => {}
+                Err(_) if field.value.is_empty() => {
+                    spans.push(Span::styled(
+                        question.symbol().to_string(),
+                        Style::default().fg(theme.border),
+                    ));
+                }
+                Err(_) => {}
+            }
+            frame.render_widget(Paragraph::new(Line::from(spans)), inner);
+
+            if is_focused {
+                let cursor_x = inner.x + field.cursor_column();
+                let cursor_y = inner.y;
+                if cursor_x < inner.x + inner.width {
+                    frame.set_cursor_position((cursor_x, cursor_y));
+                }
+            }
+        }
+        FieldKind::Color { question } => {
+            // Swatch of the typed color once it parses
+            let mut spans = vec![Span::styled(
+                field.value.clone(),
+                Style::default().fg(theme.text),
+            )];
+            match question.parse(&field.value) {
+                Ok(color) => {
+                    spans.push(Span::raw("  "));
+                    spans.push(Span::styled(
+                        "    ",
+                        Style::default().bg(Color::Rgb(color.r, color.g, color.b)),
+                    ));
+                }
+                Err(_) if field.value.is_empty() => {
+                    spans.push(Span::styled("#rrggbb", Style::default().fg(theme.border)));
+                }
+                Err(_) => {}
+            }
+            frame.render_widget(Paragraph::new(Line::from(spans)), inner);
+
+            if is_focused {
+                let cursor_x = inner.x + field.cursor_column();
+                let cursor_y = inner.y;
+                if cursor_x < inner.x + inner.width {
+                    frame.set_cursor_position((cursor_x, cursor_y));
+                }
+            }
+        }
+        FieldKind::Path { question } => {
+            // Placeholder naming the expected kind of path while empty
+            let line = match question.hint() {
+                Some(hint) if field.value.is_empty() => {
+                    Line::styled(hint, Style::default().fg(theme.border))
+                }
+                _ => Line::styled(field.value.clone(), Style::default().fg(theme.text)),
+            };
+            frame.render_widget(Paragraph::new(line), inner);
+
+            if is_focused {
+                let cursor_x = inner.x + field.cursor_column();
                 let cursor_y = inner.y;
                 if cursor_x < inner.x + inner.width {
                     frame.set_cursor_position((cursor_x, cursor_y));
@@ -1686,18 +2779,131 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
                 .style(Style::default().fg(if checked { theme.success } else { theme.text }));
             frame.render_widget(text, inner);
         }
+        FieldKind::Rating { question } => {
+            let selected = field.value.parse::<i64>().ok();
+            let mut spans = Vec::new();
+            if let Some((low, _)) = &question.anchors {
+                spans.push(Span::styled(
+                    format!("{} ", low),
+                    Style::default().fg(theme.border),
+                ));
+            }
+            for point in question.points() {
+                let is_selected = selected == Some(point);
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.highlight)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                let symbol = if !question.shows_stars() {
+                    format!(" {} ", point)
+                } else if selected.is_some_and(|s| point <= s) {
+                    " ★ ".to_string()
+                } else {
+                    " ☆ ".to_string()
+                };
+                spans.push(Span::styled(symbol, style));
+            }
+            if let Some((_, high)) = &question.anchors {
+                spans.push(Span::styled(
+                    format!(" {}", high),
+                    Style::default().fg(theme.border),
+                ));
+            }
+            frame.render_widget(Paragraph::new(Line::from(spans)), inner);
+        }
+        FieldKind::Matrix {
+            question,
+            selected,
+            highlight,
+        } => {
+            let header = Row::new(
+                std::iter::once(Cell::from(""))
+                    .chain(question.columns.iter().map(|c| Cell::from(c.clone()))),
+            )
+            .style(Style::default().fg(theme.border));
+
+            let rows = question.rows.iter().enumerate().map(|(row_idx, row)| {
+                let choice = selected.get(row_idx).copied().flatten();
+                let cells = (0..question.columns.len()).map(|column| {
+                    if choice == Some(column) {
+                        Cell::from("(●)").style(Style::default().fg(theme.highlight))
+                    } else {
+                        Cell::from("( )")
+                    }
+                });
+                let style = if is_focused && row_idx == *highlight {
+                    Style::default()
+                        .fg(theme.text)
+                        .bg(theme.selected_bg)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                Row::new(std::iter::once(Cell::from(row.clone())).chain(cells)).style(style)
+            });
+
+            let label_width = question.rows.iter().map(|r| r.len()).max().unwrap_or(0);
+            let widths = std::iter::once(Constraint::Length(label_width as u16 + 1)).chain(
+                question
+                    .columns
+                    .iter()
+                    .map(|c| Constraint::Length(c.len().max(3) as u16 + 1)),
+            );
+            frame.render_widget(Table::new(rows, widths).header(header), inner);
+        }
         FieldKind::List { .. } => {
             let text = Paragraph::new(field.value.clone()).style(Style::default().fg(theme.text));
             frame.render_widget(text, inner);
 
             if is_focused {
-                let cursor_x = inner.x + field.cursor_pos as u16;
+                let cursor_x = inner.x + field.cursor_column();
                 let cursor_y = inner.y;
                 if cursor_x < inner.x + inner.width {
                     frame.set_cursor_position((cursor_x, cursor_y));
                 }
             }
         }
+        FieldKind::Select {
+            options,
+            selected,
+            highlight,
+        } => {
+            let items: Vec<ListItem> = options
+                .iter()
+                .enumerate()
+                .skip(range.start)
+                .take(range.len())
+                .map(|(idx, o)| {
+                    let marker = if *selected == Some(idx) {
+                        "(●)"
+                    } else {
+                        "( )"
+                    };
+                    let is_highlighted = is_focused && idx == *highlight;
+                    let style = if is_highlighted {
+                        Style::default()
+                            .fg(theme.text)
+                            .bg(theme.selected_bg)
+                            .add_modifier(Modifier::BOLD)
+                    } else if *selected == Some(idx) {
+                        Style::default().fg(theme.highlight)
+                    } else {
+                        Style::default().fg(theme.text)
+                    };
+                    ListItem::new(format!(
+                        "{} {}",
+                        marker,
+                        truncate_to_width(&o.label, label_width)
+                    ))
+                    .style(style)
+                })
+                .collect();
+            let list = List::new(items);
+            frame.render_widget(list, inner);
+        }
         FieldKind::OneOf {
             variants,
             selected,
@@ -1706,6 +2912,8 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
             let items: Vec<ListItem> = variants
                 .iter()
                 .enumerate()
+                .skip(range.start)
+                .take(range.len())
                 .map(|(idx, v)| {
                     let marker = if *selected == Some(idx) {
                         "(●)"
@@ -1723,7 +2931,12 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
                     } else {
                         Style::default().fg(theme.text)
                     };
-                    ListItem::new(format!("{} {}", marker, v.name)).style(style)
+                    ListItem::new(format!(
+                        "{} {}",
+                        marker,
+                        truncate_to_width(&v.name, label_width)
+                    ))
+                    .style(style)
                 })
                 .collect();
             let list = List::new(items);
@@ -1737,6 +2950,8 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
             let items: Vec<ListItem> = variants
                 .iter()
                 .enumerate()
+                .skip(range.start)
+                .take(range.len())
                 .map(|(idx, v)| {
                     let checked = selected.get(idx).copied().unwrap_or(false);
                     let marker = if checked { "[✓]" } else { "[ ]" };
@@ -1751,7 +2966,12 @@ fn draw_field(frame: &mut Frame, field: &FormField, area: Rect, is_focused: bool
                     } else {
                         Style::default().fg(theme.text)
                     };
-                    ListItem::new(format!("{} {}", marker, v.name)).style(style)
+                    ListItem::new(format!(
+                        "{} {}",
+                        marker,
+                        truncate_to_width(&v.name, label_width)
+                    ))
+                    .style(style)
                 })
                 .collect();
             let list = List::new(items);
@@ -1786,6 +3006,9 @@ impl SurveyBackend for RatatuiFormBackend {
     ) -> Result<Responses, Self::Error> {
         let mut terminal = self.setup_terminal()?;
         let mut state = FormState::new(definition, self.theme.clone(), self.title.clone());
+        state.page_size = self.page_size;
+        state.text_checker = self.text_checker.clone();
+        state.set_high_contrast(elicitor::high_contrast_enabled());
 
         // Skip to first visible field
         while state.focused_idx < state.fields.len()
@@ -1802,23 +3025,58 @@ impl SurveyBackend for RatatuiFormBackend {
                     continue;
                 }
 
+                // Raw mode swallows the interrupt signal, so handle Ctrl+C like Esc
+                if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    state.cancelled = true;
+                    break;
+                }
+
+                // F2 switches high-contrast mode, kept for later forms
+                if key.code == KeyCode::F(2) {
+                    state.set_high_contrast(elicitor::toggle_high_contrast());
+                    continue;
+                }
+
+                if state.editing_comment {
+                    let comment = state
+                        .focused_field_mut()
+                        .and_then(|field| field.comment.as_mut());
+                    match (key.code, comment) {
+                        (KeyCode::Enter | KeyCode::Esc, _) => state.editing_comment = false,
+                        (KeyCode::Char('n'), _)
+                            if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            state.editing_comment = false;
+                        }
+                        (KeyCode::Backspace, Some(comment)) => {
+                            comment.pop();
+                        }
+                        (KeyCode::Char(c), Some(comment)) => comment.push(c),
+                        _ => {}
+                    }
+                    continue;
+                }
+
                 match key.code {
                     KeyCode::Esc => {
                         state.cancelled = true;
                         break;
                     }
+                    // Ctrl+N: write a comment on the focused field
+                    KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        state.toggle_comment();
+                    }
                     // Ctrl+Enter or F10 to submit the form
-                    KeyCode::Enter if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                        if state.validate_all(validate) {
-                            state.submitted = true;
-                            break;
-                        }
+                    KeyCode::Enter
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && state.validate_all(validate) =>
+                    {
+                        state.submitted = true;
+                        break;
                     }
-                    KeyCode::F(10) => {
-                        if state.validate_all(validate) {
-                            state.submitted = true;
-                            break;
-                        }
+                    KeyCode::F(10) if state.validate_all(validate) => {
+                        state.submitted = true;
+                        break;
                     }
                     // Enter: submit if on button, select option, or move to next field
                     KeyCode::Enter => {
@@ -1839,6 +3097,8 @@ impl SurveyBackend for RatatuiFormBackend {
                     {
                         state.prev_field();
                     }
+                    // Tab: complete an autocomplete field first
+                    KeyCode::Tab if state.accept_completion() => {}
                     // Tab: next field
                     KeyCode::Tab => {
                         state.next_field();
@@ -1865,6 +3125,14 @@ impl SurveyBackend for RatatuiFormBackend {
                     KeyCode::Right if key.modifiers.contains(KeyModifiers::CONTROL) => {
                         state.next_field();
                     }
+                    // Left/Right: move along a rating scale
+                    KeyCode::Left | KeyCode::Right if state.is_rating_field() => {
+                        state.rate_step(key.code == KeyCode::Right);
+                    }
+                    // Left/Right: choose a column for the highlighted matrix row
+                    KeyCode::Left | KeyCode::Right if state.is_matrix_field() => {
+                        state.matrix_step(key.code == KeyCode::Right);
+                    }
                     // Left/Right: cursor movement in text fields
                     KeyCode::Left => {
                         state.cursor_left();
@@ -1872,14 +3140,17 @@ impl SurveyBackend for RatatuiFormBackend {
                     KeyCode::Right => {
                         state.cursor_right();
                     }
-                    // Space: toggle bool, select OneOf option, toggle AnyOf option
+                    // Space: toggle bool, select Select/OneOf option, toggle AnyOf option
                     KeyCode::Char(' ') => {
                         if let Some(field) = state.focused_field() {
                             match &field.kind {
                                 FieldKind::Bool => state.toggle_bool(),
-                                FieldKind::OneOf { .. } | FieldKind::AnyOf { .. } => {
+                                FieldKind::Select { .. }
+                                | FieldKind::OneOf { .. }
+                                | FieldKind::AnyOf { .. } => {
                                     state.select_option();
                                 }
+                                FieldKind::Matrix { .. } => {}
                                 _ => state.handle_text_input(' '),
                             }
                         }
@@ -1894,12 +3165,24 @@ impl SurveyBackend for RatatuiFormBackend {
                                         state.toggle_anyof(idx - 1);
                                     }
                                 }
+                                // Number keys pick a point on a rating scale
+                                FieldKind::Rating { .. } => {
+                                    state.rate(c.to_digit(10).unwrap() as i64);
+                                }
+                                // Number keys pick a column (1-9) of a matrix row
+                                FieldKind::Matrix { .. } => {
+                                    let idx = c.to_digit(10).unwrap() as usize;
+                                    if idx > 0 {
+                                        state.matrix_choose(idx - 1);
+                                    }
+                                }
                                 _ => state.handle_text_input(c),
                             }
                         } else {
                             state.handle_text_input(c);
                         }
                     }
+                    KeyCode::Char(_) if state.is_rating_field() || state.is_matrix_field() => {}
                     KeyCode::Char(c) => {
                         state.handle_text_input(c);
                     }
@@ -1919,16 +3202,11 @@ impl SurveyBackend for RatatuiFormBackend {
                             field.cursor_pos = field.value.len();
                         }
                     }
-                    KeyCode::PageDown => {
-                        // Jump multiple fields down
-                        for _ in 0..5 {
-                            state.next_field();
-                        }
-                    }
-                    KeyCode::PageUp => {
-                        // Jump multiple fields up
-                        for _ in 0..5 {
-                            state.prev_field();
+                    // PageUp/PageDown: next page of a long list, otherwise a screen of fields
+                    KeyCode::PageUp | KeyCode::PageDown => {
+                        let down = key.code == KeyCode::PageDown;
+                        if !state.page_options(down) {
+                            state.page_fields(down);
                         }
                     }
                     _ => {}
@@ -1939,7 +3217,9 @@ impl SurveyBackend for RatatuiFormBackend {
         self.restore_terminal(&mut terminal)?;
 
         if state.cancelled {
-            return Err(RatatuiFormError::Cancelled);
+            return Err(RatatuiFormError::Cancelled {
+                partial: Box::new(state.collect_responses()),
+            });
         }
 
         Ok(state.collect_responses())
@@ -1959,13 +3239,125 @@ mod tests {
 
     #[test]
     fn error_types() {
-        let err = RatatuiFormError::Cancelled;
+        let err = RatatuiFormError::Cancelled {
+            partial: Box::default(),
+        };
         assert_eq!(err.to_string(), "Form cancelled by user");
 
         let err = RatatuiFormError::Terminal("test error".to_string());
         assert_eq!(err.to_string(), "Terminal error: test error");
     }
 
+    #[test]
+    fn high_contrast_adds_spacing() {
+        let definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(Default::default())),
+            Question::new("city", "City:", QuestionKind::Input(Default::default())),
+        ]);
+        let mut state = FormState::new(&definition, Theme::default(), "Form".to_string());
+        let height = state.total_content_height();
+
+        state.set_high_contrast(true);
+        assert_eq!(state.theme.background, Color::Black);
+        assert_eq!(state.total_content_height(), height + 1);
+        state.set_high_contrast(false);
+        assert_eq!(state.total_content_height(), height);
+    }
+
+    #[test]
+    fn text_findings_are_shown_before_the_text_is_kept() {
+        use elicitor::{MultilineQuestion, TextIssue};
+
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "notes",
+            "Notes:",
+            QuestionKind::Multiline(MultilineQuestion::new()),
+        )]);
+        let validate = |_: &ResponseValue, _: &Responses, _: &ResponsePath| Ok(());
+        let mut state = FormState::new(&definition, Theme::default(), "Form".to_string());
+        state.text_checker = Some(Arc::new(|text: &str| {
+            if text.contains("TODO") {
+                vec![TextIssue::warning("Unfinished text")]
+            } else {
+                vec![]
+            }
+        }));
+        state.fields[0].value = "TODO".to_string();
+
+        assert!(!state.validate_all(&validate));
+        assert_eq!(
+            state.fields[0].error.as_deref(),
+            Some("Unfinished text (submit again to keep the text)")
+        );
+        assert!(state.validate_all(&validate));
+
+        state.fields[0].value = "Done, TODO".to_string();
+        assert!(!state.validate_all(&validate));
+    }
+
+    #[test]
+    fn long_choice_lists_are_paged() {
+        use elicitor::OneOfQuestion;
+
+        let zones = (0..25).map(|i| Variant::unit(format!("UTC+{i}"))).collect();
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                "zone",
+                "Time zone:",
+                QuestionKind::OneOf(OneOfQuestion::new(zones)),
+            ),
+            Question::new("name", "Name:", QuestionKind::Input(Default::default())),
+        ]);
+        let mut state = FormState::new(&definition, Theme::default(), "Form".to_string());
+        let field_height =
+            |state: &FormState| get_field_height(&state.fields[0], 0, state.page_size);
+        assert_eq!(field_height(&state), 2 + DEFAULT_PAGE_SIZE as u16);
+
+        assert!(state.page_options(true));
+        assert!(state.page_options(true));
+        assert!(state.page_options(true));
+        let FieldKind::OneOf { highlight, .. } = &state.fields[0].kind else {
+            panic!("zone is a choice list");
+        };
+        assert_eq!(*highlight, 24);
+        assert_eq!(page_range(25, *highlight, state.page_size), 20..25);
+
+        // Lists that fit on one page leave PageDown to move between fields
+        state.page_size = 30;
+        assert_eq!(field_height(&state), 27);
+        assert!(!state.page_options(true));
+        state.viewport_height = 10;
+        state.page_fields(true);
+        assert_eq!(state.focused_idx, 1);
+    }
+
+    #[test]
+    fn wide_characters_move_the_cursor_by_their_width() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "topping",
+            "Topping:",
+            QuestionKind::Input(Default::default()),
+        )]);
+        let mut state = FormState::new(&definition, Theme::default(), "Form".to_string());
+
+        state.handle_text_input('🍕');
+        state.handle_text_input('a');
+        assert_eq!(state.fields[0].cursor_column(), 3);
+        state.cursor_left();
+        state.cursor_left();
+        state.handle_text_input('日');
+        assert_eq!(state.fields[0].value, "日🍕a");
+        assert_eq!(state.fields[0].cursor_column(), 2);
+        state.cursor_right();
+        state.handle_backspace();
+        assert_eq!(state.fields[0].value, "日a");
+        assert_eq!(state.fields[0].cursor_column(), 2);
+
+        assert_eq!(truncate_to_width("💼 Business", 20), "💼 Business");
+        assert_eq!(truncate_to_width("💼 Business", 6), "💼 Bu…");
+        assert_eq!(truncate_to_width("日本語テキスト", 7), "日本語…");
+    }
+
     #[test]
     fn theme_default() {
         let theme = Theme::default();
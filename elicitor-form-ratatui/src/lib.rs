@@ -32,5 +32,7 @@
 //! ```
 
 mod backend;
+mod theme;
 
-pub use backend::{RatatuiFormBackend, RatatuiFormError, Theme};
+pub use backend::{DEFAULT_PAGE_SIZE, RatatuiFormBackend, RatatuiFormError};
+pub use theme::{PRESETS, Theme, ThemeError};
@@ -0,0 +1,11 @@
+//! Print a JSON Schema for the SpookyForest survey.
+
+use elicitor::Survey;
+use elicitor_json_schema::to_json_schema;
+use example_surveys::SpookyForest;
+
+fn main() -> anyhow::Result<()> {
+    let schema = to_json_schema(&SpookyForest::survey());
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
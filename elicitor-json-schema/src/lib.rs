@@ -0,0 +1,390 @@
+//! # elicitor-json-schema
+//!
+//! JSON Schema export for elicitor surveys.
+//!
+//! The schema describes the answers to a survey as a JSON document, so surveys can be
+//! validated, documented and rendered by third-party tools such as
+//! react-jsonschema-form:
+//!
+//! - nested structs become objects with one property per field
+//! - enums become `oneOf`: unit variants as a `const` string, variants with data as an
+//!   object with a single property named after the variant
+//! - bounds become `minimum`/`maximum` and `minItems`/`maxItems`
+//! - prompts become `title`s, suggested and assumed values `default`s
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use elicitor::Survey;
+//! use elicitor_json_schema::to_json_schema;
+//!
+//! let schema = to_json_schema(&UserProfile::survey());
+//! std::fs::write("profile.schema.json", serde_json::to_string_pretty(&schema)?)?;
+//! ```
+//!
+//! The survey definition does not record which fields are `Option`s, so every question
+//! is listed as required.
+
+use elicitor::{
+    DefaultValue, ListElementKind, PathMode, Question, QuestionKind, ResponseValue,
+    SurveyDefinition, Variant,
+};
+use serde_json::{Map, Value, json};
+
+/// JSON Schema dialect of the generated schemas.
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generate a JSON Schema for the answers to `survey`.
+pub fn to_json_schema(survey: &SurveyDefinition) -> Value {
+    let mut schema = Map::new();
+    schema.insert("$schema".into(), SCHEMA_DIALECT.into());
+    if let Some(prelude) = &survey.prelude {
+        schema.insert("description".into(), prelude.as_str().into());
+    }
+    schema.extend(object_schema(&survey.questions));
+    Value::Object(schema)
+}
+
+/// Schema of an object with one property per question.
+///
+/// Questions without a path (e.g. a top-level enum) describe the object itself.
+fn object_schema(questions: &[Question]) -> Map<String, Value> {
+    let mut schema = Map::new();
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for question in questions {
+        let property = question_schema(question);
+        if question.path().is_empty() {
+            schema.extend(property);
+            continue;
+        }
+        let name = question.path().as_str().to_string();
+        required.push(Value::String(name.clone()));
+        properties.insert(name, Value::Object(property));
+    }
+
+    if !properties.is_empty() || schema.is_empty() {
+        schema.insert("type".into(), "object".into());
+        schema.insert("properties".into(), Value::Object(properties));
+        schema.insert("required".into(), Value::Array(required));
+        schema.insert("additionalProperties".into(), false.into());
+    }
+    schema
+}
+
+fn question_schema(question: &Question) -> Map<String, Value> {
+    let mut schema = kind_schema(question.kind());
+    if !question.ask().is_empty() {
+        schema.insert("title".into(), question.ask().into());
+    }
+    let value = match question.default() {
+        DefaultValue::None => None,
+        DefaultValue::Suggested(value) => Some(value),
+        DefaultValue::Assumed(value) => {
+            schema.insert("readOnly".into(), true.into());
+            Some(value)
+        }
+    };
+    if let Some(value) = value.and_then(|value| default_value(question.kind(), value)) {
+        schema.insert("default".into(), value);
+    }
+    schema
+}
+
+fn kind_schema(kind: &QuestionKind) -> Map<String, Value> {
+    let value = match kind {
+        QuestionKind::Unit => json!({ "type": "null" }),
+        QuestionKind::Input(input) => with_default(json!({ "type": "string" }), &input.default),
+        QuestionKind::Autocomplete(autocomplete) => {
+            let mut schema = json!({ "type": "string" });
+            let examples = autocomplete.completions("");
+            if !examples.is_empty() {
+                schema["examples"] = examples.into();
+            }
+            with_default(schema, &autocomplete.default)
+        }
+        QuestionKind::Multiline(multiline) => {
+            with_default(json!({ "type": "string" }), &multiline.default)
+        }
+        QuestionKind::Masked(_) => json!({
+            "type": "string",
+            "format": "password",
+            "writeOnly": true,
+        }),
+        QuestionKind::Int(int) => {
+            let schema = bounded(json!({ "type": "integer" }), int.min, int.max);
+            with_default(schema, &int.default)
+        }
+        QuestionKind::Float(float) => {
+            let schema = bounded(json!({ "type": "number" }), float.min, float.max);
+            with_default(schema, &float.default)
+        }
+        QuestionKind::Currency(currency) => {
+            let schema = bounded(
+                json!({
+                    "type": "integer",
+                    "description": format!("Amount in minor units of {}", currency.currency),
+                }),
+                currency.min,
+                currency.max,
+            );
+            with_default(schema, &currency.default)
+        }
+        QuestionKind::Color(color) => with_default(
+            json!({ "type": "string", "pattern": "^#([0-9a-fA-F]{3}){1,2}$" }),
+            &color.default.map(|color| color.to_hex()),
+        ),
+        QuestionKind::Path(path) => {
+            let mut schema = json!({ "type": "string" });
+            if let Some(hint) = path.hint() {
+                schema["description"] = hint.into();
+            }
+            if let PathMode::File { extensions } = &path.mode
+                && !extensions.is_empty()
+            {
+                let alternatives: Vec<_> = extensions.iter().map(|e| regex_escape(e)).collect();
+                schema["pattern"] = format!("\\.({})$", alternatives.join("|")).into();
+            }
+            with_default(schema, &path.default)
+        }
+        QuestionKind::Rating(rating) => {
+            let mut schema = json!({
+                "type": "integer",
+                "minimum": rating.min,
+                "maximum": rating.max,
+            });
+            if let Some((low, high)) = &rating.anchors {
+                schema["description"] =
+                    format!("{} ({}) to {} ({})", rating.min, low, rating.max, high).into();
+            }
+            with_default(schema, &rating.default)
+        }
+        QuestionKind::Matrix(matrix) => {
+            let column = json!({ "enum": matrix.columns });
+            let properties: Map<String, Value> = matrix
+                .rows
+                .iter()
+                .map(|row| (row.clone(), column.clone()))
+                .collect();
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": matrix.rows,
+                "additionalProperties": false,
+            })
+        }
+        QuestionKind::Confirm(confirm) => json!({ "type": "boolean", "default": confirm.default }),
+        QuestionKind::Select(select) => {
+            let options: Vec<_> = select
+                .options
+                .iter()
+                .map(|option| json!({ "const": option.value, "title": option.label }))
+                .collect();
+            let mut schema = json!({ "type": "string", "oneOf": options });
+            if let Some(option) = select.default.and_then(|idx| select.options.get(idx)) {
+                schema["default"] = option.value.as_str().into();
+            }
+            schema
+        }
+        QuestionKind::List(list) => {
+            let items = match &list.element_kind {
+                ListElementKind::String => json!({ "type": "string" }),
+                ListElementKind::Int { min, max } => {
+                    bounded(json!({ "type": "integer" }), *min, *max)
+                }
+                ListElementKind::Float { min, max } => {
+                    bounded(json!({ "type": "number" }), *min, *max)
+                }
+            };
+            let mut schema = json!({ "type": "array", "items": items });
+            if let Some(min) = list.min_items {
+                schema["minItems"] = min.into();
+            }
+            if let Some(max) = list.max_items {
+                schema["maxItems"] = max.into();
+            }
+            schema
+        }
+        QuestionKind::AnyOf(any_of) => {
+            let mut schema = json!({
+                "type": "array",
+                "items": { "oneOf": variant_schemas(&any_of.variants) },
+            });
+            if !any_of.defaults.is_empty() {
+                let defaults: Vec<_> = any_of
+                    .defaults
+                    .iter()
+                    .filter_map(|&idx| any_of.variants.get(idx))
+                    .filter(|variant| variant.kind.is_unit())
+                    .map(|variant| Value::String(variant.name.clone()))
+                    .collect();
+                schema["default"] = defaults.into();
+            }
+            schema
+        }
+        QuestionKind::AllOf(all_of) => return object_schema(all_of.questions()),
+        QuestionKind::OneOf(one_of) => {
+            let mut schema = json!({ "oneOf": variant_schemas(&one_of.variants) });
+            if let Some(variant) = one_of.default.and_then(|idx| one_of.variants.get(idx))
+                && variant.kind.is_unit()
+            {
+                schema["default"] = variant.name.as_str().into();
+            }
+            schema
+        }
+    };
+    match value {
+        Value::Object(schema) => schema,
+        _ => unreachable!("schemas are objects"),
+    }
+}
+
+/// Unit variants are a string constant, variants with data an object holding the data
+/// under the variant name.
+fn variant_schemas(variants: &[Variant]) -> Vec<Value> {
+    variants
+        .iter()
+        .map(|variant| {
+            if variant.kind.is_unit() {
+                json!({ "const": variant.name, "title": variant.name })
+            } else {
+                json!({
+                    "type": "object",
+                    "title": variant.name,
+                    "properties": { variant.name.as_str(): kind_schema(&variant.kind) },
+                    "required": [variant.name],
+                    "additionalProperties": false,
+                })
+            }
+        })
+        .collect()
+}
+
+fn bounded<T: Into<Value>>(mut schema: Value, min: Option<T>, max: Option<T>) -> Value {
+    if let Some(min) = min {
+        schema["minimum"] = min.into();
+    }
+    if let Some(max) = max {
+        schema["maximum"] = max.into();
+    }
+    schema
+}
+
+fn with_default<T: Clone + Into<Value>>(mut schema: Value, default: &Option<T>) -> Value {
+    if let Some(default) = default {
+        schema["default"] = default.clone().into();
+    }
+    schema
+}
+
+/// The JSON form of a suggested or assumed answer, if it has one.
+fn default_value(kind: &QuestionKind, value: &ResponseValue) -> Option<Value> {
+    Some(match value {
+        ResponseValue::String(s) => s.as_str().into(),
+        ResponseValue::Int(i) => (*i).into(),
+        ResponseValue::Float(f) => (*f).into(),
+        ResponseValue::Bool(b) => (*b).into(),
+        ResponseValue::ChosenVariant(idx) => match kind {
+            QuestionKind::OneOf(one_of) => one_of.variants.get(*idx)?.name.as_str().into(),
+            _ => return None,
+        },
+        ResponseValue::ChosenVariants(indices) => match kind {
+            QuestionKind::AnyOf(any_of) => indices
+                .iter()
+                .filter_map(|&idx| any_of.variants.get(idx))
+                .map(|variant| Value::String(variant.name.clone()))
+                .collect(),
+            QuestionKind::Matrix(matrix) => matrix
+                .rows
+                .iter()
+                .zip(indices)
+                .filter_map(|(row, &column)| {
+                    Some((row.clone(), matrix.columns.get(column)?.as_str().into()))
+                })
+                .collect::<Map<_, _>>()
+                .into(),
+            _ => return None,
+        },
+        ResponseValue::StringList(items) => items.clone().into(),
+        ResponseValue::IntList(items) => items.clone().into(),
+        ResponseValue::FloatList(items) => items.clone().into(),
+    })
+}
+
+fn regex_escape(s: &str) -> String {
+    s.chars()
+        .flat_map(|c| {
+            let escape = "\\.+*?()|[]{}^$".contains(c);
+            escape.then_some('\\').into_iter().chain([c])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{IntQuestion, OneOfQuestion, SelectQuestion};
+
+    #[test]
+    fn bounds_enums_and_nesting() {
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                "age",
+                "Age?",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new(
+                "plan",
+                "Plan?",
+                QuestionKind::Select(SelectQuestion::from_choices(["free", "pro"])),
+            ),
+            Question::new(
+                "contact",
+                "Contact by?",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Mail"),
+                    Variant::new("Phone", QuestionKind::Input(Default::default())),
+                ])),
+            ),
+            Question::new(
+                "address",
+                "",
+                QuestionKind::AllOf(elicitor::AllOfQuestion::new(vec![Question::new(
+                    "city",
+                    "City?",
+                    QuestionKind::Input(Default::default()),
+                )])),
+            ),
+        ]);
+
+        let schema = to_json_schema(&definition);
+        assert_eq!(schema["$schema"], SCHEMA_DIALECT);
+        assert_eq!(
+            schema["required"],
+            json!(["age", "plan", "contact", "address"])
+        );
+
+        let age = &schema["properties"]["age"];
+        assert_eq!(age["type"], "integer");
+        assert_eq!(age["minimum"], 0);
+        assert_eq!(age["maximum"], 150);
+        assert_eq!(age["title"], "Age?");
+
+        assert_eq!(
+            schema["properties"]["plan"]["oneOf"][1],
+            json!({ "const": "pro", "title": "pro" })
+        );
+
+        let contact = &schema["properties"]["contact"]["oneOf"];
+        assert_eq!(contact[0], json!({ "const": "Mail", "title": "Mail" }));
+        assert_eq!(
+            contact[1]["properties"]["Phone"],
+            json!({ "type": "string" })
+        );
+
+        let address = &schema["properties"]["address"];
+        assert_eq!(address["type"], "object");
+        assert_eq!(address["properties"]["city"]["type"], "string");
+    }
+}
@@ -0,0 +1,15 @@
+//! Basic field types over JSON-RPC
+//!
+//! Prints `survey.ask` requests to stdout and reads answers from stdin, one JSON
+//! object per line, e.g. `{"jsonrpc":"2.0","id":1,"result":{"answer":"Ada"}}`.
+//!
+//! Run with: cargo run -p elicitor-jsonrpc --example jsonrpc_basic_fields
+
+use elicitor_jsonrpc::JsonRpcBackend;
+use example_surveys::BasicFields;
+
+fn main() -> anyhow::Result<()> {
+    let result = BasicFields::builder().run(JsonRpcBackend::stdio())?;
+    eprintln!("{result:#?}");
+    Ok(())
+}
@@ -0,0 +1,419 @@
+//! JSON-RPC backend implementation for SurveyBackend trait.
+
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::sync::Mutex;
+
+use elicitor::{
+    FieldHandling, NoObserver, QuestionKind, RenderStep, ResponsePath, ResponseValue, Responses,
+    SensitiveField, SensitiveSink, SurveyBackend, SurveyDefinition, SurveyError, SurveyObserver,
+    apply_assumed, record_answer, render_plan,
+};
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::protocol::{
+    CANCELLED_METHOD, DONE_METHOD, HEADING_METHOD, START_METHOD, ask_request, from_json,
+    notification, question_path, responses_to_json,
+};
+
+/// Error type for the JSON-RPC backend.
+#[derive(Debug, Error)]
+pub enum JsonRpcError {
+    /// The client cancelled the survey or disconnected.
+    ///
+    /// Holds the answers given before that.
+    #[error("Survey cancelled by user")]
+    Cancelled { partial: Box<Responses> },
+
+    /// The client sent something that is not a response to the pending question.
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+
+    /// The client answered the question with a JSON-RPC error.
+    #[error("Client error {code}: {message}")]
+    Client { code: i64, message: String },
+
+    /// Reading from or writing to the client failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl JsonRpcError {
+    /// The answers given before the client cancelled, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            _ => None,
+        }
+    }
+
+    /// A cancellation whose partial answers are filled in by `collect`.
+    fn cancelled() -> Self {
+        Self::Cancelled {
+            partial: Box::default(),
+        }
+    }
+}
+
+impl From<JsonRpcError> for SurveyError {
+    fn from(err: JsonRpcError) -> Self {
+        match err {
+            JsonRpcError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
+        }
+    }
+}
+
+/// The connection to the client.
+struct Connection {
+    input: Box<dyn BufRead + Send>,
+    output: Box<dyn Write + Send>,
+    next_id: u64,
+}
+
+impl Connection {
+    fn send(&mut self, message: &Value) -> Result<(), JsonRpcError> {
+        writeln!(self.output, "{message}")?;
+        self.output.flush()?;
+        Ok(())
+    }
+
+    /// Send a request and wait for the client's result.
+    fn request(&mut self, build: impl FnOnce(u64) -> Value) -> Result<Value, JsonRpcError> {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(&build(id))?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.input.read_line(&mut line)? == 0 {
+                // The client went away
+                return Err(JsonRpcError::cancelled());
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let message: Value = serde_json::from_str(&line)
+                .map_err(|e| JsonRpcError::Protocol(format!("invalid JSON: {e}")))?;
+            if message.get("id").and_then(Value::as_u64) != Some(id) {
+                return Err(JsonRpcError::Protocol(format!(
+                    "expected the response to request {id}, got {message}"
+                )));
+            }
+            if let Some(error) = message.get("error") {
+                return Err(JsonRpcError::Client {
+                    code: error.get("code").and_then(Value::as_i64).unwrap_or(0),
+                    message: error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                });
+            }
+            return message.get("result").cloned().ok_or_else(|| {
+                JsonRpcError::Protocol(format!("response without result: {message}"))
+            });
+        }
+    }
+}
+
+/// JSON-RPC backend, driven by a client over stdio or a socket.
+///
+/// Messages are JSON-RPC 2.0 objects, one per line. For each question the backend
+/// sends a `survey.ask` request and waits for the client's result: `{"answer": …}`
+/// or `{"cancel": true}`. Rejected answers are asked again with the reason in the
+/// request's `error` field.
+pub struct JsonRpcBackend {
+    connection: Mutex<Connection>,
+}
+
+impl std::fmt::Debug for JsonRpcBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonRpcBackend").finish_non_exhaustive()
+    }
+}
+
+impl JsonRpcBackend {
+    /// Talk to a client through `input` and `output`, e.g. the pipes of a child process.
+    pub fn new(input: impl Read + Send + 'static, output: impl Write + Send + 'static) -> Self {
+        Self {
+            connection: Mutex::new(Connection {
+                input: Box::new(BufReader::new(input)),
+                output: Box::new(output),
+                next_id: 0,
+            }),
+        }
+    }
+
+    /// Talk to the client that started this process, over stdin and stdout.
+    pub fn stdio() -> Self {
+        Self::new(std::io::stdin(), std::io::stdout())
+    }
+
+    /// Talk to the client connected to `stream`.
+    pub fn from_tcp(stream: TcpStream) -> std::io::Result<Self> {
+        Ok(Self::new(stream.try_clone()?, stream))
+    }
+}
+
+/// `survey.done` hands every answer back to the client as it was given.
+impl SensitiveSink for JsonRpcBackend {
+    fn sink_name(&self) -> String {
+        "json-rpc client".to_string()
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        FieldHandling::Plaintext
+    }
+}
+
+impl SurveyBackend for JsonRpcBackend {
+    type Error = JsonRpcError;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        let mut connection = self.connection.lock().unwrap_or_else(|e| e.into_inner());
+        let mut responses = Responses::new();
+        apply_assumed(definition, &mut responses);
+
+        connection.send(&notification(
+            START_METHOD,
+            json!({ "prelude": definition.prelude }),
+        ))?;
+
+        match ask_all(
+            &mut connection,
+            definition,
+            &mut responses,
+            validate,
+            observer,
+        ) {
+            Ok(()) => {
+                connection.send(&notification(
+                    DONE_METHOD,
+                    json!({
+                        "responses": responses_to_json(&responses),
+                        "epilogue": definition.epilogue,
+                    }),
+                ))?;
+                Ok(responses)
+            }
+            Err(JsonRpcError::Cancelled { .. }) => {
+                // The client may be gone already
+                let _ = connection.send(&notification(CANCELLED_METHOD, json!({})));
+                Err(JsonRpcError::Cancelled {
+                    partial: Box::new(responses),
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Ask every pending question until the survey is complete.
+fn ask_all(
+    connection: &mut Connection,
+    definition: &SurveyDefinition,
+    responses: &mut Responses,
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    observer: &dyn SurveyObserver,
+) -> Result<(), JsonRpcError> {
+    let mut sent_headings = HashSet::new();
+    loop {
+        let mut headings = Vec::new();
+        let mut next = None;
+        for step in render_plan(definition, responses) {
+            match step {
+                RenderStep::Heading { path, text } => headings.push((path, text)),
+                RenderStep::Question(question) if question.is_pending() => {
+                    next = Some(question);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let Some(step) = next else {
+            return Ok(());
+        };
+
+        for (path, text) in headings {
+            if step.path.is_within(&path) && sent_headings.insert(path.clone()) {
+                connection.send(&notification(
+                    HEADING_METHOD,
+                    json!({ "path": path.as_str(), "text": text }),
+                ))?;
+            }
+        }
+
+        let question_path = question_path(&step);
+        observer.on_question_start(&question_path);
+
+        let mut error: Option<String> = None;
+        let value = loop {
+            let result = connection.request(|id| ask_request(id, &step, error.as_deref()))?;
+            if result.get("cancel").and_then(Value::as_bool) == Some(true) {
+                return Err(JsonRpcError::cancelled());
+            }
+            let Some(answer) = result.get("answer") else {
+                return Err(JsonRpcError::Protocol(format!(
+                    "expected an answer or a cancellation, got {result}"
+                )));
+            };
+
+            // Single choices need no validation, like in the interactive backends
+            let checked = from_json(&step.kind, answer).and_then(|value| match step.kind {
+                QuestionKind::OneOf(_) => Ok(value),
+                _ => validate(&value, responses, &question_path).map(|()| value),
+            });
+            match checked {
+                Ok(value) => break value,
+                Err(message) => error = Some(message),
+            }
+        };
+        record_answer(responses, &step, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{InputQuestion, IntQuestion, MaskedQuestion, PrivacyReport, Question};
+    use std::sync::Arc;
+
+    /// Output shared with the test, to read what the backend sent.
+    #[derive(Clone, Default)]
+    struct Sent(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Sent {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Sent {
+        fn messages(&self) -> Vec<Value> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+    }
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "age",
+                "Age?",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+        ])
+    }
+
+    #[test]
+    fn client_answers_each_question() {
+        // The age is out of bounds at first and asked again
+        let client = [
+            r#"{"jsonrpc":"2.0","id":1,"result":{"answer":"Ada"}}"#,
+            r#"{"jsonrpc":"2.0","id":2,"result":{"answer":200}}"#,
+            r#"{"jsonrpc":"2.0","id":3,"result":{"answer":36}}"#,
+        ]
+        .join("\n");
+        let sent = Sent::default();
+        let backend = JsonRpcBackend::new(std::io::Cursor::new(client), sent.clone());
+
+        let responses = backend.collect(&definition(), &|_, _, _| Ok(())).unwrap();
+        assert_eq!(
+            responses.get(&ResponsePath::new("age")),
+            Some(&ResponseValue::Int(36))
+        );
+
+        let messages = sent.messages();
+        let methods: Vec<&str> = messages
+            .iter()
+            .map(|m| m["method"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            methods,
+            [
+                "survey.start",
+                "survey.ask",
+                "survey.ask",
+                "survey.ask",
+                "survey.done"
+            ]
+        );
+        assert_eq!(
+            messages[1]["params"]["kind"]["Input"]["default"],
+            Value::Null
+        );
+        assert_eq!(messages[3]["params"]["path"], "age");
+        assert_eq!(messages[3]["params"]["error"], "Value must be at most 150");
+        assert_eq!(messages[4]["params"]["responses"]["name"], "Ada");
+    }
+
+    #[test]
+    fn masked_answers_are_sent_back_in_plain_text() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "pin",
+            "PIN?",
+            QuestionKind::Masked(MaskedQuestion::new()),
+        )]);
+        let client = r#"{"jsonrpc":"2.0","id":1,"result":{"answer":"1234"}}"#;
+        let sent = Sent::default();
+        let backend = JsonRpcBackend::new(std::io::Cursor::new(client), sent.clone());
+
+        backend.collect(&definition, &|_, _, _| Ok(())).unwrap();
+        assert_eq!(sent.messages()[2]["params"]["responses"]["pin"], "1234");
+
+        let report = PrivacyReport::new(&definition).with_sink(&backend);
+        assert_eq!(
+            report.handling(&ResponsePath::new("pin"), "json-rpc client"),
+            Some(FieldHandling::Plaintext)
+        );
+    }
+
+    #[test]
+    fn client_cancelling_keeps_partial_answers() {
+        let client = [
+            r#"{"jsonrpc":"2.0","id":1,"result":{"answer":"Ada"}}"#,
+            r#"{"jsonrpc":"2.0","id":2,"result":{"cancel":true}}"#,
+        ]
+        .join("\n");
+        let sent = Sent::default();
+        let backend = JsonRpcBackend::new(std::io::Cursor::new(client), sent.clone());
+
+        let err = backend
+            .collect(&definition(), &|_, _, _| Ok(()))
+            .unwrap_err();
+        assert_eq!(
+            err.partial().unwrap().get(&ResponsePath::new("name")),
+            Some(&ResponseValue::String("Ada".to_string()))
+        );
+        assert_eq!(
+            sent.messages().last().unwrap()["method"],
+            "survey.cancelled"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! # elicitor-jsonrpc
+//!
+//! JSON-RPC backend for elicitor.
+//!
+//! The backend runs a survey headlessly and lets a client answer it over a simple
+//! JSON-RPC 2.0 protocol on stdio or a socket, so IDE plugins, Electron frontends or
+//! remote orchestration tools can drive derived surveys with their own UI.
+//!
+//! # Protocol
+//!
+//! Every message is one JSON object on its own line. The backend sends:
+//!
+//! - `survey.start` (notification) with the `prelude`, if any
+//! - `survey.heading` (notification) with the `path` and `text` of a nested section
+//!   before its first question
+//! - `survey.ask` (request) with the question's `path`, `prompt`, `kind` as serialized
+//!   by elicitor, the suggested `default` and the `error` that rejected the last answer
+//! - `survey.done` (notification) with all `responses` keyed by path and the `epilogue`
+//! - `survey.cancelled` (notification) when the client cancels
+//!
+//! The client answers each `survey.ask` with `{"answer": …}` or `{"cancel": true}`.
+//! Text is a string, numbers are numbers, yes/no is a boolean, lists are arrays, and
+//! choices are given by index or name:
+//!
+//! ```text
+//! → {"jsonrpc":"2.0","id":1,"method":"survey.ask","params":{"path":"age","prompt":"How old are you?","kind":{"Int":{"default":null,"min":0,"max":150,"validate":null}},"default":null,"error":null}}
+//! ← {"jsonrpc":"2.0","id":1,"result":{"answer":36}}
+//! ```
+//!
+//! Closing the connection cancels the survey.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use elicitor_jsonrpc::JsonRpcBackend;
+//!
+//! // Spawned by an editor plugin, which talks over the process's stdin and stdout
+//! let config = Config::builder().run(JsonRpcBackend::stdio())?;
+//!
+//! // Or served to a remote frontend
+//! let listener = std::net::TcpListener::bind("127.0.0.1:7878")?;
+//! let (stream, _) = listener.accept()?;
+//! let config = Config::builder().run(JsonRpcBackend::from_tcp(stream)?)?;
+//! ```
+
+mod backend;
+mod protocol;
+
+pub use backend::{JsonRpcBackend, JsonRpcError};
+pub use protocol::{
+    ASK_METHOD, CANCELLED_METHOD, DONE_METHOD, HEADING_METHOD, JSONRPC_VERSION, START_METHOD,
+};
@@ -0,0 +1,293 @@
+//! Messages of the protocol and conversion of answers to and from JSON.
+
+use elicitor::{
+    AutocompleteQuestion, ListElementKind, Messages, QuestionKind, QuestionStep, ResponsePath,
+    ResponseValue, Responses,
+};
+use serde_json::{Map, Value, json};
+
+/// Version string of every message.
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Request asking the client for one answer.
+pub const ASK_METHOD: &str = "survey.ask";
+
+/// Notification of the prelude, before the first question.
+pub const START_METHOD: &str = "survey.start";
+
+/// Notification of the heading of a nested section.
+pub const HEADING_METHOD: &str = "survey.heading";
+
+/// Notification of the answers and the epilogue once every question is answered.
+pub const DONE_METHOD: &str = "survey.done";
+
+/// Notification that the survey ends without all answers.
+pub const CANCELLED_METHOD: &str = "survey.cancelled";
+
+/// A notification, which the client does not answer.
+pub(crate) fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": JSONRPC_VERSION, "method": method, "params": params })
+}
+
+/// A `survey.ask` request for `step`, with the reason the last answer was rejected.
+pub(crate) fn ask_request(id: u64, step: &QuestionStep, error: Option<&str>) -> Value {
+    json!({
+        "jsonrpc": JSONRPC_VERSION,
+        "id": id,
+        "method": ASK_METHOD,
+        "params": {
+            "path": step.path.as_str(),
+            "prompt": step.prompt,
+            "kind": kind_to_json(&step.kind),
+            "default": step.suggestion.as_ref().map(to_json),
+            "error": error,
+        },
+    })
+}
+
+/// The question kind as serialized by elicitor, with autocomplete callbacks replaced by
+/// the completions they offer for empty input.
+fn kind_to_json(kind: &QuestionKind) -> Value {
+    let kind = match kind {
+        QuestionKind::Autocomplete(q) => {
+            let mut listed = AutocompleteQuestion::from_list(q.completions(""));
+            listed.default = q.default.clone();
+            listed.validate = q.validate.clone();
+            QuestionKind::Autocomplete(listed)
+        }
+        kind => kind.clone(),
+    };
+    serde_json::to_value(&kind).unwrap_or(Value::Null)
+}
+
+/// The answers as a JSON object keyed by response path.
+pub(crate) fn responses_to_json(responses: &Responses) -> Value {
+    let answers: Map<String, Value> = responses
+        .iter()
+        .map(|(path, value)| (path.as_str().to_string(), to_json(value)))
+        .collect();
+    Value::Object(answers)
+}
+
+/// An answer as plain JSON: text as strings, numbers as numbers, choices as indices.
+pub(crate) fn to_json(value: &ResponseValue) -> Value {
+    match value {
+        ResponseValue::String(s) => json!(s),
+        ResponseValue::Int(n) => json!(n),
+        ResponseValue::Float(f) => json!(f),
+        ResponseValue::Bool(b) => json!(b),
+        ResponseValue::ChosenVariant(idx) => json!(idx),
+        ResponseValue::ChosenVariants(indices) => json!(indices),
+        ResponseValue::StringList(items) => json!(items),
+        ResponseValue::IntList(items) => json!(items),
+        ResponseValue::FloatList(items) => json!(items),
+    }
+}
+
+/// The client's answer to a question of `kind`, checked against its constraints.
+///
+/// Choices may be given by index or by name, currency amounts as minor units or as
+/// text to parse.
+pub(crate) fn from_json(kind: &QuestionKind, answer: &Value) -> Result<ResponseValue, String> {
+    let value = match kind {
+        QuestionKind::Int(q) => {
+            let n = int(answer)?;
+            q.check_bounds(n)?;
+            ResponseValue::Int(n)
+        }
+        QuestionKind::Float(q) => {
+            let f = float(answer)?;
+            q.check_bounds(f)?;
+            ResponseValue::Float(f)
+        }
+        QuestionKind::Currency(q) => {
+            let minor = match answer {
+                Value::String(text) => q.parse(text)?,
+                answer => int(answer)?,
+            };
+            q.check_bounds(minor)?;
+            ResponseValue::Int(minor)
+        }
+        QuestionKind::Rating(q) => {
+            let n = int(answer)?;
+            if !q.contains(n) {
+                return Err(format!("{n} is not a point of the rating scale"));
+            }
+            ResponseValue::Int(n)
+        }
+        QuestionKind::Color(q) => ResponseValue::String(q.parse(string(answer)?)?.to_hex()),
+        QuestionKind::Path(q) => {
+            let path = string(answer)?;
+            q.check(path)?;
+            ResponseValue::String(path.to_string())
+        }
+        QuestionKind::Select(q) => {
+            let value = match answer {
+                Value::String(value) if q.position(value).is_some() => value.clone(),
+                answer => {
+                    let labels: Vec<&str> = q.options.iter().map(|o| o.label.as_str()).collect();
+                    q.options[choice(answer, &labels)?].value.clone()
+                }
+            };
+            ResponseValue::String(value)
+        }
+        QuestionKind::Confirm(_) => ResponseValue::Bool(
+            answer
+                .as_bool()
+                .ok_or_else(|| format!("Expected true or false, got {answer}"))?,
+        ),
+        QuestionKind::Matrix(q) => {
+            let columns = array(answer)?
+                .iter()
+                .map(|column| choice(column, &q.columns))
+                .collect::<Result<Vec<_>, _>>()?;
+            if !q.is_complete(&columns) {
+                return Err("Not every row of the matrix is answered".to_string());
+            }
+            ResponseValue::ChosenVariants(columns)
+        }
+        QuestionKind::List(q) => {
+            let items = array(answer)?;
+            if let Some(min) = q.min_items.filter(|&min| items.len() < min) {
+                return Err(format!("Enter at least {min} items"));
+            }
+            if let Some(max) = q.max_items.filter(|&max| items.len() > max) {
+                return Err(format!("Enter at most {max} items"));
+            }
+            match q.element_kind {
+                ListElementKind::String => ResponseValue::StringList(
+                    items
+                        .iter()
+                        .map(|item| string(item).map(str::to_string))
+                        .collect::<Result<_, _>>()?,
+                ),
+                ListElementKind::Int { min, max } => ResponseValue::IntList(
+                    items
+                        .iter()
+                        .map(|item| {
+                            let n = int(item)?;
+                            bounds(
+                                &q.messages,
+                                n as f64,
+                                min.map(|m| m as f64),
+                                max.map(|m| m as f64),
+                            )
+                            .map(|()| n)
+                        })
+                        .collect::<Result<_, _>>()?,
+                ),
+                ListElementKind::Float { min, max } => ResponseValue::FloatList(
+                    items
+                        .iter()
+                        .map(|item| {
+                            float(item).and_then(|f| bounds(&q.messages, f, min, max).map(|()| f))
+                        })
+                        .collect::<Result<_, _>>()?,
+                ),
+            }
+        }
+        QuestionKind::OneOf(q) => {
+            let names: Vec<&str> = q.variants.iter().map(|v| v.name.as_str()).collect();
+            ResponseValue::ChosenVariant(choice(answer, &names)?)
+        }
+        QuestionKind::AnyOf(q) => {
+            let names: Vec<&str> = q.variants.iter().map(|v| v.name.as_str()).collect();
+            let chosen = array(answer)?
+                .iter()
+                .map(|item| choice(item, &names))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(max) = q.max_selected.filter(|&max| chosen.len() > max) {
+                return Err(format!("Select at most {max} options"));
+            }
+            ResponseValue::ChosenVariants(chosen)
+        }
+        _ => ResponseValue::String(string(answer)?.to_string()),
+    };
+    Ok(value)
+}
+
+/// The path the answer to `step` is validated at: choices at their question.
+pub(crate) fn question_path(step: &QuestionStep) -> ResponsePath {
+    match step.kind {
+        QuestionKind::OneOf(_) | QuestionKind::AnyOf(_) => step.path.parent(),
+        _ => step.path.clone(),
+    }
+}
+
+fn string(answer: &Value) -> Result<&str, String> {
+    answer
+        .as_str()
+        .ok_or_else(|| format!("Expected text, got {answer}"))
+}
+
+fn int(answer: &Value) -> Result<i64, String> {
+    answer
+        .as_i64()
+        .ok_or_else(|| format!("Expected a whole number, got {answer}"))
+}
+
+fn float(answer: &Value) -> Result<f64, String> {
+    answer
+        .as_f64()
+        .ok_or_else(|| format!("Expected a number, got {answer}"))
+}
+
+fn array(answer: &Value) -> Result<&Vec<Value>, String> {
+    answer
+        .as_array()
+        .ok_or_else(|| format!("Expected an array, got {answer}"))
+}
+
+fn bounds(
+    messages: &Messages,
+    value: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> Result<(), String> {
+    if let Some(min) = min.filter(|&min| value < min) {
+        Err(messages.at_least(&min.to_string()))
+    } else if let Some(max) = max.filter(|&max| value > max) {
+        Err(messages.at_most(&max.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+/// The index of the choice named by `answer`, either its index or its name.
+fn choice(answer: &Value, choices: &[impl AsRef<str>]) -> Result<usize, String> {
+    let idx = match answer {
+        Value::Number(n) => n.as_u64().map(|n| n as usize),
+        Value::String(name) => choices.iter().position(|c| c.as_ref() == name),
+        _ => None,
+    };
+    idx.filter(|&idx| idx < choices.len())
+        .ok_or_else(|| format!("{answer} is not one of the choices"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{IntQuestion, OneOfQuestion, Variant};
+
+    #[test]
+    fn answers_are_converted_by_question_kind() {
+        let age = QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150)));
+        assert_eq!(from_json(&age, &json!(42)), Ok(ResponseValue::Int(42)));
+        assert!(from_json(&age, &json!(200)).is_err());
+        assert!(from_json(&age, &json!("42")).is_err());
+
+        let plan = QuestionKind::OneOf(OneOfQuestion::new(vec![
+            Variant::unit("Free"),
+            Variant::unit("Pro"),
+        ]));
+        assert_eq!(
+            from_json(&plan, &json!("Pro")),
+            Ok(ResponseValue::ChosenVariant(1))
+        );
+        assert_eq!(
+            from_json(&plan, &json!(0)),
+            Ok(ResponseValue::ChosenVariant(0))
+        );
+        assert!(from_json(&plan, &json!(2)).is_err());
+    }
+}
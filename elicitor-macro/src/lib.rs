@@ -20,14 +20,65 @@ use syn::{
 /// - `#[epilogue("...")]` - Message shown after the survey completes
 /// - `#[validate("fn_name")]` - Composite validator function
 /// - `#[validate_fields("fn_name")]` - Propagate a field-level validator to all numeric child fields
+/// - `#[score(fn_name -> Score)]` - Add `run_scored` to the builder, returning the value together
+///   with `fn_name(&value, &points) -> Score`, where `points` are earned by `#[score]` fields
+/// - `#[partial]` / `#[partial(Debug, Clone)]` - On structs with named fields, generate
+///   `Partial<Name>` with every field optional and `Name::from_partial_responses`, to keep the
+///   answers of cancelled or unfinished runs; the list gives the derives of the partial struct
 ///
 /// ## On fields
 /// - `#[ask("...")]` - The prompt text shown to the user (required for non-primitive types)
-/// - `#[mask]` - Hide input (for passwords)
+/// - `#[ask(key = "profile.name")]` / `#[ask("Name:", key = "profile.name")]` - Look the prompt
+///   up under this key with the `Translator` given to the builder's `with_translator(...)`;
+///   the text, if any, is shown when there is no translation. Works the same for the labels
+///   of enum variants
+/// - `#[mask]` - Hide input (for passwords); `SecretString` fields (feature `secrecy`) are always
+///   masked and kept out of the plain responses
 /// - `#[multiline]` - Open text editor / show textarea
-/// - `#[validate("fn_name")]` - Field-level validator function
-/// - `#[min(n)]` / `#[max(n)]` - Numeric bounds
+/// - `#[validate("fn_name")]` - Field-level validator function, returning `Result<(), String>`
+///   or `Result<(), ValidationError>` for a message translated by the builder's translator
+/// - `#[min(n)]` / `#[max(n)]` - Numeric bounds; `#[max(n)]` on a `#[multiselect]` field
+///   limits the number of selections
 /// - `#[multiselect]` - For `Vec<Enum>` fields, enables multi-select
+/// - `#[choices_from(fn_name)]` - For `String` fields, pick one of the choices returned by
+///   `fn_name()` when the survey is built (`Vec<String>` or `Vec<(value, label)>`)
+/// - `#[autocomplete(["a", "b"])]` / `#[autocomplete(fn_name)]` - For `String` fields, offer
+///   completions while typing, from a fixed list or from `fn_name(&str) -> Vec<String>`
+/// - `#[rating(1..=5)]` - For integer fields, pick a point on a numeric rating scale
+/// - `#[likert("Strongly disagree".."Strongly agree")]` - For integer fields, a Likert scale
+///   with labelled ends (5 points, or `#[likert("Never".."Always", 7)]`)
+/// - `#[currency("USD")]` / `#[currency("EUR", "de")]` - For integer fields holding minor units
+///   (cents), type and show amounts with symbol and the separators of the locale (default `en`);
+///   `#[min]`/`#[max]` are in minor units
+/// - `#[use_question("common.email")]` - Take prompt, widget and validator from the question
+///   registered under this id in `elicitor::question_bank` (replaces `#[ask]`)
+/// - `#[file]` / `#[file(extensions = "png,jpg")]` - For `PathBuf` fields, require an existing
+///   file, optionally with one of the given extensions; GUI backends offer a file dialog
+/// - `#[directory]` - For `PathBuf` fields, require an existing directory
+/// - `#[allow_comment]` - Let the respondent attach a free-text note to the answer, stored at
+///   `<field>.__comment` in the responses
+/// - `#[editable_by("reviewer")]` / `#[editable_by("admin", "reviewer")]` - Only these roles may
+///   edit the answer when the builder runs `with_role(...)`; others keep the suggested value
+/// - `#[tags("pii", "financial")]` - Put the answer in categories, e.g. to strip all `pii`
+///   answers with `Responses::strip_tagged` before sending them to analytics
+/// - `#[dropdown]` - Offer the choices of an enum or `#[choices_from]` field in a drop-down
+///   where the backend can (the egui form does so by itself for long lists)
+/// - `#[help("Used for the delivery only")]` - Longer explanation of the field, shown on
+///   request by backends that support it (F1 in the ratatui wizard)
+/// - `#[flag("collect_salary")]` - Only ask while the feature flag is on, as decided by the
+///   `FlagProvider` given to the builder's `with_flags(...)`; the field must be an `Option`
+/// - `#[replaced_by("contact.email")]` - Deprecate the field in favor of another one: it is not
+///   asked and takes the answer at the given path, to which answers saved under the old field's
+///   name are moved
+/// - `#[score(0, 10, 25)]` - Points per choice of an enum, `#[multiselect]` or `#[choices_from]`
+///   field, in declaration order; a `bool` takes one weight, awarded for "yes"
+///
+/// ## On enum variants
+/// - `#[ask("...")]` - The label of the choice, by default the variant's name
+/// - `/// Doc comment` - The label if there is no `#[ask]`: its first paragraph, with the
+///   rest as help. With `#[ask]`, the whole comment is the help, shown next to the
+///   highlighted option in select lists and as the description in documents
+/// - `#[help("...")]` - Help for the choice in place of the doc comment
 #[proc_macro_derive(
     Survey,
     attributes(
@@ -40,7 +91,24 @@ use syn::{
         max,
         prelude,
         epilogue,
-        multiselect
+        multiselect,
+        choices_from,
+        autocomplete,
+        rating,
+        likert,
+        currency,
+        file,
+        directory,
+        use_question,
+        allow_comment,
+        editable_by,
+        tags,
+        flag,
+        help,
+        dropdown,
+        replaced_by,
+        score,
+        partial
     )
 )]
 pub fn elicit(input: TokenStream) -> TokenStream {
@@ -63,6 +131,9 @@ fn implement_survey(input: &DeriveInput) -> syn::Result<TokenStream2> {
     // Generate from_responses() method
     let from_responses_fn = generate_from_responses_fn(input)?;
 
+    // Generate to_responses() method
+    let to_responses_fn = generate_to_responses_fn(input)?;
+
     // Generate validate_field() method
     let validate_field_fn = generate_validate_field_fn(input)?;
 
@@ -81,6 +152,12 @@ fn implement_survey(input: &DeriveInput) -> syn::Result<TokenStream2> {
     // Generate ValidationContext struct for validators
     let validation_context = generate_validation_context(input)?;
 
+    // Generate the Partial<Name> struct, if requested
+    let partial = match &type_attrs.partial {
+        Some(derives) => generate_partial(input, derives)?,
+        None => quote! {},
+    };
+
     Ok(quote! {
         #validator_checks
 
@@ -95,11 +172,23 @@ fn implement_survey(input: &DeriveInput) -> syn::Result<TokenStream2> {
                 #from_responses_fn
             }
 
+            fn to_responses(&self) -> elicitor::Responses {
+                #to_responses_fn
+            }
+
             fn validate_field(
                 value: &elicitor::ResponseValue,
                 responses: &elicitor::Responses,
                 path: &elicitor::ResponsePath,
             ) -> Result<(), String> {
+                Self::validate_field_keyed(value, responses, path).map_err(String::from)
+            }
+
+            fn validate_field_keyed(
+                value: &elicitor::ResponseValue,
+                responses: &elicitor::Responses,
+                path: &elicitor::ResponsePath,
+            ) -> Result<(), elicitor::ValidationError> {
                 #validate_field_fn
             }
 
@@ -120,6 +209,8 @@ fn implement_survey(input: &DeriveInput) -> syn::Result<TokenStream2> {
         }
 
         #builder_impl
+
+        #partial
     })
 }
 
@@ -134,6 +225,10 @@ struct TypeAttrs {
     validate: Option<Ident>,
     /// Validator to propagate to all numeric child fields
     validate_fields: Option<Ident>,
+    /// Scoring function and the score type it returns
+    score: Option<(Ident, Type)>,
+    /// Derives of the `Partial<Name>` struct, if one is generated
+    partial: Option<Vec<syn::Path>>,
 }
 
 impl TypeAttrs {
@@ -142,9 +237,13 @@ impl TypeAttrs {
         let mut epilogue = None;
         let mut validate = None;
         let mut validate_fields = None;
+        let mut score = None;
+        let mut partial = None;
 
         for attr in attrs {
-            if attr.path().is_ident("prelude") {
+            if attr.path().is_ident("partial") {
+                partial = Some(extract_partial_attr(attr)?);
+            } else if attr.path().is_ident("prelude") {
                 prelude = Some(extract_string_attr(attr)?);
             } else if attr.path().is_ident("epilogue") {
                 epilogue = Some(extract_string_attr(attr)?);
@@ -152,6 +251,8 @@ impl TypeAttrs {
                 validate = Some(extract_ident_attr(attr)?);
             } else if attr.path().is_ident("validate_fields") {
                 validate_fields = Some(extract_ident_attr(attr)?);
+            } else if attr.path().is_ident("score") {
+                score = Some(extract_scoring_attr(attr)?);
             }
         }
 
@@ -160,6 +261,8 @@ impl TypeAttrs {
             epilogue,
             validate,
             validate_fields,
+            score,
+            partial,
         })
     }
 }
@@ -167,27 +270,92 @@ impl TypeAttrs {
 /// Attributes that can appear on fields
 struct FieldAttrs {
     ask: Option<String>,
+    /// Translation key of the prompt, `#[ask(key = "...")]`
+    ask_key: Option<String>,
     mask: bool,
     multiline: bool,
     validate: Option<Ident>,
     min: Option<i64>,
     max: Option<i64>,
     multiselect: bool,
+    choices_from: Option<syn::Path>,
+    autocomplete: Option<AutocompleteSource>,
+    rating: Option<RatingScale>,
+    currency: Option<CurrencyFormat>,
+    path_mode: Option<PathModeAttr>,
+    use_question: Option<String>,
+    allow_comment: bool,
+    editable_by: Vec<String>,
+    tags: Vec<String>,
+    flag: Option<String>,
+    help: Option<String>,
+    dropdown: bool,
+    replaced_by: Option<String>,
+    score: Option<Vec<Expr>>,
+}
+
+/// Restriction given by `#[file(...)]` or `#[directory]`
+enum PathModeAttr {
+    /// `#[file]` or `#[file(extensions = "png,jpg")]`
+    File { extensions: Vec<String> },
+    /// `#[directory]`
+    Directory,
+}
+
+/// Currency code and locale given to `#[currency(...)]`
+struct CurrencyFormat {
+    code: String,
+    locale: Option<String>,
+}
+
+/// Suggestion source given to `#[autocomplete(...)]`
+enum AutocompleteSource {
+    /// `#[autocomplete(["a", "b"])]`
+    List(syn::ExprArray),
+    /// `#[autocomplete(fn_name)]`
+    Fn(syn::Path),
+}
+
+/// Scale given to `#[rating(...)]` or `#[likert(...)]`
+enum RatingScale {
+    /// `#[rating(1..=5)]`
+    Numeric { min: i64, max: i64 },
+    /// `#[likert("Strongly disagree".."Strongly agree")]` or `#[likert("Never".."Always", 7)]`
+    Likert {
+        low: String,
+        high: String,
+        points: i64,
+    },
 }
 
 impl FieldAttrs {
     fn extract(attrs: &[Attribute]) -> syn::Result<Self> {
         let mut ask = None;
+        let mut ask_key = None;
         let mut mask = false;
         let mut multiline = false;
         let mut validate = None;
         let mut min = None;
         let mut max = None;
         let mut multiselect = false;
+        let mut choices_from = None;
+        let mut autocomplete = None;
+        let mut rating = None;
+        let mut currency = None;
+        let mut path_mode = None;
+        let mut use_question = None;
+        let mut allow_comment = false;
+        let mut editable_by = Vec::new();
+        let mut tags = Vec::new();
+        let mut flag = None;
+        let mut help = None;
+        let mut dropdown = false;
+        let mut replaced_by = None;
+        let mut score = None;
 
         for attr in attrs {
             if attr.path().is_ident("ask") {
-                ask = Some(extract_string_attr(attr)?);
+                (ask, ask_key) = extract_ask_attr(attr)?;
             } else if attr.path().is_ident("mask") {
                 mask = true;
             } else if attr.path().is_ident("multiline") {
@@ -200,17 +368,74 @@ impl FieldAttrs {
                 max = Some(extract_int_attr(attr)?);
             } else if attr.path().is_ident("multiselect") {
                 multiselect = true;
+            } else if attr.path().is_ident("choices_from") {
+                choices_from = Some(extract_path_attr(attr)?);
+            } else if attr.path().is_ident("autocomplete") {
+                autocomplete = Some(extract_autocomplete_attr(attr)?);
+            } else if attr.path().is_ident("rating") {
+                rating = Some(extract_rating_attr(attr)?);
+            } else if attr.path().is_ident("likert") {
+                rating = Some(extract_likert_attr(attr)?);
+            } else if attr.path().is_ident("currency") {
+                currency = Some(extract_currency_attr(attr)?);
+            } else if attr.path().is_ident("file") {
+                path_mode = Some(extract_file_attr(attr)?);
+            } else if attr.path().is_ident("directory") {
+                path_mode = Some(PathModeAttr::Directory);
+            } else if attr.path().is_ident("use_question") {
+                use_question = Some(extract_string_attr(attr)?);
+            } else if attr.path().is_ident("allow_comment") {
+                allow_comment = true;
+            } else if attr.path().is_ident("editable_by") {
+                editable_by = extract_string_list_attr(attr, "role", "reviewer")?;
+            } else if attr.path().is_ident("tags") {
+                tags = extract_string_list_attr(attr, "tag", "pii")?;
+            } else if attr.path().is_ident("flag") {
+                flag = Some(extract_string_attr(attr)?);
+            } else if attr.path().is_ident("help") {
+                help = Some(extract_string_attr(attr)?);
+            } else if attr.path().is_ident("dropdown") {
+                dropdown = true;
+            } else if attr.path().is_ident("replaced_by") {
+                replaced_by = Some(extract_string_attr(attr)?);
+            } else if attr.path().is_ident("score") {
+                score = Some(extract_score_weights_attr(attr)?);
             }
         }
 
+        if let (Some(_), Some(attr)) = (
+            &use_question,
+            attrs.iter().find(|attr| attr.path().is_ident("ask")),
+        ) {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "#[use_question] takes its prompt from the question bank, remove #[ask]",
+            ));
+        }
+
         Ok(Self {
             ask,
+            ask_key,
             mask,
             multiline,
             validate,
             min,
             max,
             multiselect,
+            choices_from,
+            autocomplete,
+            rating,
+            currency,
+            path_mode,
+            use_question,
+            allow_comment,
+            editable_by,
+            tags,
+            flag,
+            help,
+            dropdown,
+            replaced_by,
+            score,
         })
     }
 }
@@ -229,6 +454,65 @@ fn extract_string_attr(attr: &Attribute) -> syn::Result<String> {
     }
 }
 
+/// The text of the `///` comments in `attrs`, one line per comment and without the
+/// leading space, or `None` if there are none.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ..
+            }) => Some(lit.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// Parse `#[ask("Prompt")]`, `#[ask(key = "profile.name")]` or both,
+/// `#[ask("Prompt", key = "profile.name")]`, into the prompt and the translation key.
+fn extract_ask_attr(attr: &Attribute) -> syn::Result<(Option<String>, Option<String>)> {
+    let Meta::List(list) = &attr.meta else {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "expected #[ask(\"...\")] or #[ask(key = \"...\")]",
+        ));
+    };
+    list.parse_args_with(|input: syn::parse::ParseStream| {
+        let mut prompt = None;
+        if input.peek(LitStr) {
+            prompt = Some(input.parse::<LitStr>()?.value());
+            if input.is_empty() {
+                return Ok((prompt, None));
+            }
+            input.parse::<syn::Token![,]>()?;
+        }
+        let name: Ident = input.parse()?;
+        if name != "key" {
+            return Err(syn::Error::new(name.span(), "expected `key = \"...\"`"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let key = input.parse::<LitStr>()?.value();
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the translation key"));
+        }
+        Ok((prompt, Some(key)))
+    })
+}
+
 fn extract_ident_attr(attr: &Attribute) -> syn::Result<Ident> {
     let meta = &attr.meta;
     match meta {
@@ -247,33 +531,308 @@ fn extract_ident_attr(attr: &Attribute) -> syn::Result<Ident> {
     }
 }
 
-fn extract_int_attr(attr: &Attribute) -> syn::Result<i64> {
+fn extract_path_attr(attr: &Attribute) -> syn::Result<syn::Path> {
     let meta = &attr.meta;
     match meta {
         Meta::List(list) => {
-            let expr: Expr = list.parse_args()?;
-            match expr {
-                Expr::Lit(lit) => match &lit.lit {
-                    Lit::Int(int) => int.base10_parse(),
-                    _ => Err(syn::Error::new_spanned(lit, "expected integer literal")),
-                },
-                Expr::Unary(ref unary) => {
-                    if matches!(unary.op, syn::UnOp::Neg(_))
-                        && let Expr::Lit(ref lit) = *unary.expr
-                        && let Lit::Int(int) = &lit.lit
-                    {
-                        let val: i64 = int.base10_parse()?;
-                        return Ok(-val);
-                    }
-                    Err(syn::Error::new_spanned(expr, "expected integer literal"))
-                }
-                _ => Err(syn::Error::new_spanned(expr, "expected integer literal")),
+            // Try parsing as a string literal first (e.g., #[choices_from("module::fn_name")])
+            if let Ok(lit) = list.parse_args::<LitStr>() {
+                return lit.parse();
             }
+            // Then try parsing as a path (e.g., #[choices_from(module::fn_name)])
+            list.parse_args()
         }
+        _ => Err(syn::Error::new_spanned(
+            attr,
+            "expected #[attr(path)] or #[attr(\"string\")]",
+        )),
+    }
+}
+
+fn extract_autocomplete_attr(attr: &Attribute) -> syn::Result<AutocompleteSource> {
+    if let Meta::List(list) = &attr.meta
+        && let Ok(array) = list.parse_args::<syn::ExprArray>()
+    {
+        return Ok(AutocompleteSource::List(array));
+    }
+    extract_path_attr(attr).map(AutocompleteSource::Fn)
+}
+
+fn extract_int_attr(attr: &Attribute) -> syn::Result<i64> {
+    let meta = &attr.meta;
+    match meta {
+        Meta::List(list) => int_from_expr(&list.parse_args()?),
         _ => Err(syn::Error::new_spanned(attr, "expected #[attr(number)]")),
     }
 }
 
+fn int_from_expr(expr: &Expr) -> syn::Result<i64> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse(),
+            _ => Err(syn::Error::new_spanned(lit, "expected integer literal")),
+        },
+        Expr::Unary(unary) => {
+            if matches!(unary.op, syn::UnOp::Neg(_))
+                && let Expr::Lit(ref lit) = *unary.expr
+                && let Lit::Int(int) = &lit.lit
+            {
+                let val: i64 = int.base10_parse()?;
+                return Ok(-val);
+            }
+            Err(syn::Error::new_spanned(expr, "expected integer literal"))
+        }
+        _ => Err(syn::Error::new_spanned(expr, "expected integer literal")),
+    }
+}
+
+fn extract_rating_attr(attr: &Attribute) -> syn::Result<RatingScale> {
+    let Meta::List(list) = &attr.meta else {
+        return Err(syn::Error::new_spanned(attr, "expected #[rating(1..=5)]"));
+    };
+    let range: syn::ExprRange = list.parse_args()?;
+    let (Some(start), Some(end)) = (&range.start, &range.end) else {
+        return Err(syn::Error::new_spanned(
+            range,
+            "expected a bounded range like 1..=5",
+        ));
+    };
+    let min = int_from_expr(start)?;
+    let max = match range.limits {
+        syn::RangeLimits::Closed(_) => int_from_expr(end)?,
+        syn::RangeLimits::HalfOpen(_) => int_from_expr(end)? - 1,
+    };
+    if min >= max {
+        return Err(syn::Error::new_spanned(
+            range,
+            "rating scale needs at least two points",
+        ));
+    }
+    Ok(RatingScale::Numeric { min, max })
+}
+
+fn extract_likert_attr(attr: &Attribute) -> syn::Result<RatingScale> {
+    let Meta::List(list) = &attr.meta else {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "expected #[likert(\"low\"..\"high\")]",
+        ));
+    };
+    let args = list
+        .parse_args_with(syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated)?;
+    let mut args = args.iter();
+
+    let Some(Expr::Range(range)) = args.next() else {
+        return Err(syn::Error::new_spanned(
+            list,
+            "expected a range of labels like \"Strongly disagree\"..\"Strongly agree\"",
+        ));
+    };
+    let label = |expr: &Option<Box<Expr>>| match expr.as_deref() {
+        Some(Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(s), ..
+        })) => Ok(s.value()),
+        _ => Err(syn::Error::new_spanned(
+            range,
+            "expected string literal labels",
+        )),
+    };
+    let low = label(&range.start)?;
+    let high = label(&range.end)?;
+
+    let points = match args.next() {
+        Some(expr) => int_from_expr(expr)?,
+        None => 5,
+    };
+    if points < 2 {
+        return Err(syn::Error::new_spanned(
+            list,
+            "Likert scale needs at least two points",
+        ));
+    }
+    Ok(RatingScale::Likert { low, high, points })
+}
+
+fn extract_currency_attr(attr: &Attribute) -> syn::Result<CurrencyFormat> {
+    let Meta::List(list) = &attr.meta else {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "expected #[currency(\"USD\")]",
+        ));
+    };
+    let args = list
+        .parse_args_with(syn::punctuated::Punctuated::<LitStr, syn::Token![,]>::parse_terminated)?;
+    let mut args = args.iter();
+
+    let Some(code) = args.next() else {
+        return Err(syn::Error::new_spanned(
+            list,
+            "expected a currency code like #[currency(\"USD\")]",
+        ));
+    };
+    if code.value().len() != 3 || !code.value().chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(syn::Error::new_spanned(
+            code,
+            "expected a three-letter ISO 4217 currency code",
+        ));
+    }
+    let locale = args.next().map(LitStr::value);
+    if let Some(extra) = args.next() {
+        return Err(syn::Error::new_spanned(
+            extra,
+            "expected #[currency(\"CODE\")] or #[currency(\"CODE\", \"locale\")]",
+        ));
+    }
+    Ok(CurrencyFormat {
+        code: code.value(),
+        locale,
+    })
+}
+
+/// Parse a non-empty list of strings, e.g. `#[editable_by("admin", "reviewer")]`
+fn extract_string_list_attr(
+    attr: &Attribute,
+    item: &str,
+    example: &str,
+) -> syn::Result<Vec<String>> {
+    let name = attr
+        .path()
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let Meta::List(list) = &attr.meta else {
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!("expected #[{name}(\"{item}\")]"),
+        ));
+    };
+    let items = list
+        .parse_args_with(syn::punctuated::Punctuated::<LitStr, syn::Token![,]>::parse_terminated)?;
+    if items.is_empty() {
+        return Err(syn::Error::new_spanned(
+            list,
+            format!("expected at least one {item}, e.g. #[{name}(\"{example}\")]"),
+        ));
+    }
+    Ok(items.iter().map(LitStr::value).collect())
+}
+
+/// Whether the field is deprecated with `#[replaced_by(...)]`.
+fn is_replaced(field: &syn::Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("replaced_by"))
+}
+
+/// Check that `#[replaced_by("path")]` names another field that is still asked.
+fn check_replacement(
+    field: &syn::Field,
+    replacement: &str,
+    fields: &syn::FieldsNamed,
+) -> syn::Result<()> {
+    let first = replacement.split('.').next().unwrap_or_default();
+    let target = fields
+        .named
+        .iter()
+        .find(|other| other.ident.as_ref().is_some_and(|ident| ident == first));
+    match target {
+        Some(target) if !is_replaced(target) => Ok(()),
+        Some(_) => Err(syn::Error::new_spanned(
+            field,
+            format!("#[replaced_by(\"{replacement}\")] points to a field that is replaced itself"),
+        )),
+        None => Err(syn::Error::new_spanned(
+            field,
+            format!("#[replaced_by(\"{replacement}\")] does not name a field of this struct"),
+        )),
+    }
+}
+
+fn extract_file_attr(attr: &Attribute) -> syn::Result<PathModeAttr> {
+    let list = match &attr.meta {
+        Meta::Path(_) => {
+            return Ok(PathModeAttr::File {
+                extensions: Vec::new(),
+            });
+        }
+        Meta::List(list) => list,
+        Meta::NameValue(_) => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "expected #[file] or #[file(extensions = \"png,jpg\")]",
+            ));
+        }
+    };
+    let mut extensions = Vec::new();
+    list.parse_nested_meta(|meta| {
+        if meta.path.is_ident("extensions") {
+            let value: LitStr = meta.value()?.parse()?;
+            extensions = value
+                .value()
+                .split(',')
+                .map(|ext| ext.trim().trim_start_matches('.').to_string())
+                .filter(|ext| !ext.is_empty())
+                .collect();
+            Ok(())
+        } else {
+            Err(meta.error("expected #[file(extensions = \"png,jpg\")]"))
+        }
+    })?;
+    Ok(PathModeAttr::File { extensions })
+}
+
+fn extract_score_weights_attr(attr: &Attribute) -> syn::Result<Vec<Expr>> {
+    let Meta::List(list) = &attr.meta else {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "expected #[score(0, 10, 25)]",
+        ));
+    };
+    let weights = list
+        .parse_args_with(syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated)?;
+    if weights.is_empty() {
+        return Err(syn::Error::new_spanned(
+            list,
+            "expected at least one weight, e.g. #[score(0, 10, 25)]",
+        ));
+    }
+    Ok(weights.into_iter().collect())
+}
+
+/// Parse the struct-level `#[score(fn_name -> Score)]`.
+fn extract_scoring_attr(attr: &Attribute) -> syn::Result<(Ident, Type)> {
+    let Meta::List(list) = &attr.meta else {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "expected #[score(fn_name -> Score)]",
+        ));
+    };
+    list.parse_args_with(|input: syn::parse::ParseStream| {
+        let function: Ident = input.parse()?;
+        input.parse::<syn::Token![->]>()?;
+        let score: Type = input.parse()?;
+        Ok((function, score))
+    })
+}
+
+/// Parse `#[partial]` or `#[partial(Debug, Clone)]` into the derives of the partial struct.
+fn extract_partial_attr(attr: &Attribute) -> syn::Result<Vec<syn::Path>> {
+    match &attr.meta {
+        Meta::Path(_) => Ok(Vec::new()),
+        Meta::List(list) => Ok(list
+            .parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            )?
+            .into_iter()
+            .collect()),
+        Meta::NameValue(_) => Err(syn::Error::new_spanned(
+            attr,
+            "expected #[partial] or #[partial(Debug, ...)]",
+        )),
+    }
+}
+
 // ============================================================================
 // Survey Generation
 // ============================================================================
@@ -321,6 +880,10 @@ fn generate_struct_questions(
                 let field_name = field.ident.as_ref().unwrap();
                 let field_name_str = field_name.to_string();
                 let attrs = FieldAttrs::extract(&field.attrs)?;
+                if let Some(replacement) = &attrs.replaced_by {
+                    check_replacement(field, replacement, fields)?;
+                    continue;
+                }
                 let question = generate_question_for_field(
                     &field_name_str,
                     &field.ty,
@@ -356,9 +919,17 @@ fn generate_enum_questions(data: &syn::DataEnum, _enum_name: &Ident) -> syn::Res
     for variant in &data.variants {
         let variant_name = variant.ident.to_string();
 
-        // Check for #[ask] on the variant itself for display text
+        // Check for #[ask] on the variant itself for display text, else its doc comment
         let variant_attrs = FieldAttrs::extract(&variant.attrs)?;
-        let display_name = variant_attrs.ask.unwrap_or_else(|| variant_name.clone());
+        let (display_name, doc_help) = match (variant_attrs.ask, doc_comment(&variant.attrs)) {
+            (Some(ask), doc) => (ask, doc),
+            (None, Some(doc)) => match doc.split_once("\n\n") {
+                Some((summary, rest)) => (summary.replace('\n', " "), Some(rest.to_string())),
+                None => (doc.replace('\n', " "), None),
+            },
+            (None, None) => (variant_name.clone(), None),
+        };
+        let help = variant_attrs.help.or(doc_help);
 
         let kind = match &variant.fields {
             Fields::Unit => quote! { elicitor::QuestionKind::Unit },
@@ -393,12 +964,14 @@ fn generate_enum_questions(data: &syn::DataEnum, _enum_name: &Ident) -> syn::Res
             }
         };
 
-        variants.push(quote! {
-            elicitor::Variant {
-                name: #display_name.to_string(),
-                kind: #kind,
-            }
-        });
+        let mut variant = quote! { elicitor::Variant::new(#display_name, #kind) };
+        if let Some(key) = &variant_attrs.ask_key {
+            variant = quote! { #variant.with_key(#key) };
+        }
+        if let Some(help) = &help {
+            variant = quote! { #variant.with_help(#help) };
+        }
+        variants.push(variant);
     }
 
     // Return a single-element vec with the OneOf question
@@ -433,15 +1006,82 @@ fn generate_question_for_field(
         .collect::<Vec<_>>()
         .join(" ");
     let ask = attrs.ask.clone().unwrap_or(default_prompt);
+    if attrs.replaced_by.is_some() {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "#[replaced_by] is only supported on fields of structs with named fields",
+        ));
+    }
     let kind = generate_question_kind(ty, attrs, propagated_validator)?;
 
-    Ok(quote! {
-        elicitor::Question::new(
-            elicitor::ResponsePath::new(#field_name),
-            #ask.to_string(),
-            #kind,
-        )
-    })
+    let question = if let Some(id) = &attrs.use_question {
+        quote! {
+            elicitor::question_bank::question(
+                #id,
+                elicitor::ResponsePath::new(#field_name),
+                #kind,
+            )
+        }
+    } else {
+        quote! {
+            elicitor::Question::new(
+                elicitor::ResponsePath::new(#field_name),
+                #ask.to_string(),
+                #kind,
+            )
+        }
+    };
+
+    let question = match &attrs.score {
+        Some(weights) => quote! { #question.with_score([#((#weights) as i64),*]) },
+        None => question,
+    };
+
+    let question = if attrs.editable_by.is_empty() {
+        question
+    } else {
+        let roles = &attrs.editable_by;
+        quote! { #question.with_editable_by([#(#roles),*]) }
+    };
+
+    let question = if attrs.tags.is_empty() {
+        question
+    } else {
+        let tags = &attrs.tags;
+        quote! { #question.with_tags([#(#tags),*]) }
+    };
+
+    let question = match &attrs.flag {
+        Some(_) if extract_option_inner_type(ty).is_none() => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[flag] fields are left unanswered while the flag is off, use Option<T>",
+            ));
+        }
+        Some(flag) => quote! { #question.with_flag(#flag) },
+        None => question,
+    };
+
+    let question = match &attrs.help {
+        Some(help) => quote! { #question.with_help(#help) },
+        None => question,
+    };
+
+    let question = match &attrs.ask_key {
+        Some(key) => quote! { #question.with_key(#key) },
+        None => question,
+    };
+
+    let question = if attrs.dropdown {
+        quote! { #question.with_dropdown() }
+    } else {
+        question
+    };
+
+    if attrs.allow_comment {
+        return Ok(quote! { #question.with_comment() });
+    }
+    Ok(question)
 }
 
 fn generate_question_kind(
@@ -450,7 +1090,39 @@ fn generate_question_kind(
     propagated_validator: Option<&Ident>,
 ) -> syn::Result<TokenStream2> {
     // Handle special attributes first
-    if attrs.mask {
+    if attrs.path_mode.is_some() {
+        let value_ty = extract_option_inner_type(ty).unwrap_or_else(|| ty.clone());
+        if type_to_string(&value_ty) != "PathBuf" {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[file] and #[directory] can only be used on PathBuf fields",
+            ));
+        }
+    }
+
+    if let Some(scale) = &attrs.rating {
+        let value_ty = extract_option_inner_type(ty).unwrap_or_else(|| ty.clone());
+        if !matches!(
+            type_to_string(&value_ty).as_str(),
+            "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize"
+        ) {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[rating] and #[likert] can only be used on integer fields",
+            ));
+        }
+        if attrs.min.is_some() || attrs.max.is_some() {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[rating] and #[likert] set their own bounds, remove #[min]/#[max]",
+            ));
+        }
+        if attrs.currency.is_some() {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[rating] and #[likert] cannot be combined with #[currency]",
+            ));
+        }
         let validate_opt = match (&attrs.validate, propagated_validator) {
             (Some(v), _) => {
                 let v_str = v.to_string();
@@ -462,12 +1134,38 @@ fn generate_question_kind(
             }
             (None, None) => quote! { None },
         };
+        let question = match scale {
+            RatingScale::Numeric { min, max } => {
+                quote! { elicitor::RatingQuestion::new(#min, #max) }
+            }
+            RatingScale::Likert { low, high, points } => {
+                quote! { elicitor::RatingQuestion::likert(#low, #high, #points) }
+            }
+        };
         return Ok(quote! {
-            elicitor::QuestionKind::Masked(elicitor::MaskedQuestion::with_validator(#validate_opt))
+            elicitor::QuestionKind::Rating(#question.with_validator(#validate_opt))
         });
     }
 
-    if attrs.multiline {
+    if let Some(currency) = &attrs.currency {
+        let value_ty = extract_option_inner_type(ty).unwrap_or_else(|| ty.clone());
+        if !matches!(
+            type_to_string(&value_ty).as_str(),
+            "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize"
+        ) {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[currency] can only be used on integer fields holding minor units",
+            ));
+        }
+        let min_opt = match attrs.min {
+            Some(m) => quote! { Some(#m) },
+            None => quote! { None },
+        };
+        let max_opt = match attrs.max {
+            Some(m) => quote! { Some(#m) },
+            None => quote! { None },
+        };
         let validate_opt = match (&attrs.validate, propagated_validator) {
             (Some(v), _) => {
                 let v_str = v.to_string();
@@ -479,65 +1177,221 @@ fn generate_question_kind(
             }
             (None, None) => quote! { None },
         };
+        let code = &currency.code;
+        let locale = currency
+            .locale
+            .as_ref()
+            .map(|locale| quote! { .with_locale(#locale) });
         return Ok(quote! {
-            elicitor::QuestionKind::Multiline(elicitor::MultilineQuestion::with_validator(#validate_opt))
+            elicitor::QuestionKind::Currency(
+                elicitor::CurrencyQuestion::new(#code)
+                    #locale
+                    .with_bounds(#min_opt, #max_opt)
+                    .with_validator(#validate_opt)
+            )
         });
     }
 
-    // Check for Vec<T>
-    if let Some(inner_ty) = extract_vec_inner_type(ty) {
-        // If multiselect is set, use AnyOf for Vec<Enum>
-        if attrs.multiselect {
-            return Ok(quote! {
-                elicitor::QuestionKind::AnyOf(elicitor::AnyOfQuestion {
-                    variants: <#inner_ty as elicitor::Survey>::survey()
-                        .questions
-                        .into_iter()
-                        .flat_map(|q| match q.kind() {
-                            elicitor::QuestionKind::OneOf(one_of) => one_of.variants.clone(),
-                            _ => vec![],
-                        })
-                        .collect(),
-                    defaults: vec![],
-                })
-            });
+    if let Some(source) = &attrs.autocomplete {
+        let value_ty = extract_option_inner_type(ty).unwrap_or_else(|| ty.clone());
+        if !matches!(type_to_string(&value_ty).as_str(), "String" | "PathBuf") {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[autocomplete] can only be used on String or PathBuf fields",
+            ));
         }
-
-        // For Vec<primitive>, generate ListQuestion
-        let inner_type_name = type_to_string(&inner_ty);
-        match inner_type_name.as_str() {
-            "String" => {
-                return Ok(quote! {
-                    elicitor::QuestionKind::List(elicitor::ListQuestion::strings())
-                });
+        if attrs.mask || attrs.multiline || attrs.choices_from.is_some() {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[autocomplete] cannot be combined with #[mask], #[multiline] or #[choices_from]",
+            ));
+        }
+        let validate_opt = match (&attrs.validate, propagated_validator) {
+            (Some(v), _) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
             }
-            "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
-                let min_opt = match attrs.min {
-                    Some(m) => quote! { Some(#m) },
-                    None => quote! { None },
-                };
-                let max_opt = match attrs.max {
-                    Some(m) => quote! { Some(#m) },
-                    None => quote! { None },
-                };
-                return Ok(quote! {
-                    elicitor::QuestionKind::List(elicitor::ListQuestion::ints_with_bounds(#min_opt, #max_opt))
-                });
+            (None, Some(v)) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
             }
-            "f32" | "f64" => {
-                let min_opt = match attrs.min {
-                    Some(m) => {
-                        let f = m as f64;
-                        quote! { Some(#f) }
-                    }
-                    None => quote! { None },
-                };
-                let max_opt = match attrs.max {
-                    Some(m) => {
-                        let f = m as f64;
-                        quote! { Some(#f) }
-                    }
-                    None => quote! { None },
+            (None, None) => quote! { None },
+        };
+        let question = match source {
+            AutocompleteSource::List(items) => {
+                quote! { elicitor::AutocompleteQuestion::from_list(#items) }
+            }
+            AutocompleteSource::Fn(completion_fn) => {
+                quote! { elicitor::AutocompleteQuestion::from_fn(#completion_fn) }
+            }
+        };
+        return Ok(quote! {
+            elicitor::QuestionKind::Autocomplete(#question.with_validator(#validate_opt))
+        });
+    }
+
+    if let Some(choices_fn) = &attrs.choices_from {
+        let value_ty = extract_option_inner_type(ty).unwrap_or_else(|| ty.clone());
+        if !matches!(type_to_string(&value_ty).as_str(), "String" | "PathBuf") {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[choices_from] can only be used on String or PathBuf fields",
+            ));
+        }
+        if attrs.mask || attrs.multiline {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "#[choices_from] cannot be combined with #[mask] or #[multiline]",
+            ));
+        }
+        let validate_opt = match (&attrs.validate, propagated_validator) {
+            (Some(v), _) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
+            }
+            (None, Some(v)) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
+            }
+            (None, None) => quote! { None },
+        };
+        // The choice function runs every time the survey definition is built
+        return Ok(quote! {
+            elicitor::QuestionKind::Select(
+                elicitor::SelectQuestion::from_choices(#choices_fn()).with_validator(#validate_opt)
+            )
+        });
+    }
+
+    if attrs.mask {
+        let validate_opt = match (&attrs.validate, propagated_validator) {
+            (Some(v), _) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
+            }
+            (None, Some(v)) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
+            }
+            (None, None) => quote! { None },
+        };
+        let value_ty = extract_option_inner_type(ty).unwrap_or_else(|| ty.clone());
+        let secret =
+            (type_to_string(&value_ty) == "SecretString").then(|| quote! { .with_secret() });
+        return Ok(quote! {
+            elicitor::QuestionKind::Masked(elicitor::MaskedQuestion::with_validator(#validate_opt)#secret)
+        });
+    }
+
+    if attrs.multiline {
+        let validate_opt = match (&attrs.validate, propagated_validator) {
+            (Some(v), _) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
+            }
+            (None, Some(v)) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
+            }
+            (None, None) => quote! { None },
+        };
+        return Ok(quote! {
+            elicitor::QuestionKind::Multiline(elicitor::MultilineQuestion::with_validator(#validate_opt))
+        });
+    }
+
+    // HashMap<Row, Column> of two unit enums becomes a matrix
+    if let Some((row_ty, column_ty)) = extract_map_types(ty) {
+        let validate_opt = match (&attrs.validate, propagated_validator) {
+            (Some(v), _) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
+            }
+            (None, Some(v)) => {
+                let v_str = v.to_string();
+                quote! { Some(#v_str.to_string()) }
+            }
+            (None, None) => quote! { None },
+        };
+        return Ok(quote! {
+            elicitor::QuestionKind::Matrix(
+                elicitor::MatrixQuestion::from_surveys(
+                    <#row_ty as elicitor::Survey>::survey(),
+                    <#column_ty as elicitor::Survey>::survey(),
+                )
+                .with_validator(#validate_opt)
+            )
+        });
+    }
+
+    // Check for Vec<T>
+    if let Some(inner_ty) = extract_vec_inner_type(ty) {
+        // If multiselect is set, use AnyOf for Vec<Enum>
+        if attrs.multiselect {
+            if attrs.max.is_some_and(|max| max < 0) {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "#[max] on a #[multiselect] field counts selections and cannot be negative",
+                ));
+            }
+            let max_selected = match attrs.max {
+                Some(max) => {
+                    let max = max as usize;
+                    quote! { Some(#max) }
+                }
+                None => quote! { None },
+            };
+            return Ok(quote! {
+                elicitor::QuestionKind::AnyOf(elicitor::AnyOfQuestion {
+                    variants: <#inner_ty as elicitor::Survey>::survey()
+                        .questions
+                        .into_iter()
+                        .flat_map(|q| match q.kind() {
+                            elicitor::QuestionKind::OneOf(one_of) => one_of.variants.clone(),
+                            _ => vec![],
+                        })
+                        .collect(),
+                    defaults: vec![],
+                    max_selected: #max_selected,
+                })
+            });
+        }
+
+        // For Vec<primitive>, generate ListQuestion
+        let inner_type_name = type_to_string(&inner_ty);
+        match inner_type_name.as_str() {
+            "String" => {
+                return Ok(quote! {
+                    elicitor::QuestionKind::List(elicitor::ListQuestion::strings())
+                });
+            }
+            "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
+                let min_opt = match attrs.min {
+                    Some(m) => quote! { Some(#m) },
+                    None => quote! { None },
+                };
+                let max_opt = match attrs.max {
+                    Some(m) => quote! { Some(#m) },
+                    None => quote! { None },
+                };
+                return Ok(quote! {
+                    elicitor::QuestionKind::List(elicitor::ListQuestion::ints_with_bounds(#min_opt, #max_opt))
+                });
+            }
+            "f32" | "f64" => {
+                let min_opt = match attrs.min {
+                    Some(m) => {
+                        let f = m as f64;
+                        quote! { Some(#f) }
+                    }
+                    None => quote! { None },
+                };
+                let max_opt = match attrs.max {
+                    Some(m) => {
+                        let f = m as f64;
+                        quote! { Some(#f) }
+                    }
+                    None => quote! { None },
                 };
                 return Ok(quote! {
                     elicitor::QuestionKind::List(elicitor::ListQuestion::floats_with_bounds(#min_opt, #max_opt))
@@ -628,9 +1482,54 @@ fn generate_question_kind(
                 elicitor::QuestionKind::Float(elicitor::FloatQuestion::with_bounds_and_validator(#min_opt, #max_opt, #validate_opt))
             })
         }
-        "PathBuf" => Ok(quote! {
-            elicitor::QuestionKind::Input(elicitor::InputQuestion::new())
-        }),
+        "PathBuf" => {
+            let validate_opt = match (&attrs.validate, propagated_validator) {
+                (Some(v), _) | (None, Some(v)) => {
+                    let v_str = v.to_string();
+                    quote! { Some(#v_str.to_string()) }
+                }
+                (None, None) => quote! { None },
+            };
+            let question = match &attrs.path_mode {
+                None => quote! { elicitor::PathQuestion::new() },
+                Some(PathModeAttr::File { extensions }) if extensions.is_empty() => {
+                    quote! { elicitor::PathQuestion::file(Vec::<String>::new()) }
+                }
+                Some(PathModeAttr::File { extensions }) => {
+                    quote! { elicitor::PathQuestion::file([#(#extensions),*]) }
+                }
+                Some(PathModeAttr::Directory) => quote! { elicitor::PathQuestion::directory() },
+            };
+            Ok(quote! {
+                elicitor::QuestionKind::Path(#question.with_validator(#validate_opt))
+            })
+        }
+        "SecretString" => {
+            let validate_opt = match (&attrs.validate, propagated_validator) {
+                (Some(v), _) | (None, Some(v)) => {
+                    let v_str = v.to_string();
+                    quote! { Some(#v_str.to_string()) }
+                }
+                (None, None) => quote! { None },
+            };
+            Ok(quote! {
+                elicitor::QuestionKind::Masked(
+                    elicitor::MaskedQuestion::with_validator(#validate_opt).with_secret()
+                )
+            })
+        }
+        "Color" => {
+            let validate_opt = match (&attrs.validate, propagated_validator) {
+                (Some(v), _) | (None, Some(v)) => {
+                    let v_str = v.to_string();
+                    quote! { Some(#v_str.to_string()) }
+                }
+                (None, None) => quote! { None },
+            };
+            Ok(quote! {
+                elicitor::QuestionKind::Color(elicitor::ColorQuestion::with_validator(#validate_opt))
+            })
+        }
         _ => {
             // Check if it's an Option<T>
             if let Some(inner_ty) = extract_option_inner_type(ty) {
@@ -673,6 +1572,20 @@ fn extract_option_inner_type(ty: &Type) -> Option<Type> {
     None
 }
 
+/// Extract the key and value types of a `HashMap<K, V>` or `BTreeMap<K, V>`.
+fn extract_map_types(ty: &Type) -> Option<(Type, Type)> {
+    if let Type::Path(path) = ty
+        && let Some(segment) = path.path.segments.last()
+        && (segment.ident == "HashMap" || segment.ident == "BTreeMap")
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(key)) = args.args.first()
+        && let Some(syn::GenericArgument::Type(value)) = args.args.get(1)
+    {
+        return Some((key.clone(), value.clone()));
+    }
+    None
+}
+
 fn extract_vec_inner_type(ty: &Type) -> Option<Type> {
     if let Type::Path(path) = ty
         && let Some(segment) = path.path.segments.last()
@@ -706,19 +1619,41 @@ fn generate_from_responses_struct(
 ) -> syn::Result<TokenStream2> {
     match &data.fields {
         Fields::Named(fields) => {
-            let field_inits: Vec<_> = fields
-                .named
-                .iter()
-                .map(|f| {
-                    let field_name = f.ident.as_ref().unwrap();
-                    let field_name_str = field_name.to_string();
-                    let ty = &f.ty;
-                    let extraction = generate_value_extraction(&field_name_str, ty);
-                    quote! { #field_name: #extraction }
-                })
-                .collect();
+            let mut renames = Vec::new();
+            let mut field_inits = Vec::new();
+            for f in &fields.named {
+                let field_name = f.ident.as_ref().unwrap();
+                let field_name_str = field_name.to_string();
+                let ty = &f.ty;
+                // Deprecated fields read the answer of their replacement
+                let source = match FieldAttrs::extract(&f.attrs)?.replaced_by {
+                    Some(replacement) => {
+                        renames.push(quote! {
+                            responses.rename(
+                                &elicitor::ResponsePath::new(#field_name_str),
+                                &elicitor::ResponsePath::new(#replacement),
+                            );
+                        });
+                        replacement
+                    }
+                    None => field_name_str,
+                };
+                let extraction = generate_value_extraction(&source, ty);
+                field_inits.push(quote! { #field_name: #extraction });
+            }
 
+            if renames.is_empty() {
+                return Ok(quote! {
+                    #name {
+                        #(#field_inits),*
+                    }
+                });
+            }
+            // Answers saved under the names of deprecated fields move to their replacements
             Ok(quote! {
+                let mut responses = responses.clone();
+                #(#renames)*
+                let responses = &responses;
                 #name {
                     #(#field_inits),*
                 }
@@ -808,7 +1743,7 @@ fn generate_value_extraction(field_name: &str, ty: &Type) -> TokenStream2 {
 
     match type_name.as_str() {
         "String" => quote! {
-            responses.get_string(&#path_expr).expect("missing string").to_string()
+            responses.expose(&#path_expr).expect("missing string").to_string()
         },
         "bool" => quote! {
             responses.get_bool(&#path_expr).expect("missing bool")
@@ -823,99 +1758,597 @@ fn generate_value_extraction(field_name: &str, ty: &Type) -> TokenStream2 {
             responses.get_float(&#path_expr).expect("missing float") as #ty
         },
         "PathBuf" => quote! {
-            std::path::PathBuf::from(responses.get_string(&#path_expr).expect("missing path"))
+            std::path::PathBuf::from(responses.get_string(&#path_expr).expect("missing path"))
+        },
+        "Color" => quote! {
+            responses.get_color(&#path_expr).expect("missing color")
+        },
+        "SecretString" => quote! {
+            responses.get_secret(&#path_expr).expect("missing secret")
+        },
+        _ => {
+            // Check for Option<T>
+            if let Some(inner_ty) = extract_option_inner_type(ty) {
+                let inner_extraction = generate_value_extraction(field_name, &inner_ty);
+                return quote! {
+                    if responses.has_value(&#path_expr) {
+                        Some(#inner_extraction)
+                    } else {
+                        None
+                    }
+                };
+            }
+
+            // Check for HashMap<Row, Column> (matrix questions)
+            if let Some((row_ty, column_ty)) = extract_map_types(ty) {
+                return quote! {
+                    responses
+                        .get_chosen_variants(&#path_expr)
+                        .expect("missing matrix")
+                        .iter()
+                        .enumerate()
+                        .map(|(row, &column)| {
+                            (
+                                elicitor::variant_from_index::<#row_ty>(row),
+                                elicitor::variant_from_index::<#column_ty>(column),
+                            )
+                        })
+                        .collect()
+                };
+            }
+
+            // Check for Vec<T>
+            if let Some(inner_ty) = extract_vec_inner_type(ty) {
+                let inner_type_name = type_to_string(&inner_ty);
+
+                // For primitive types, use the list response values
+                match inner_type_name.as_str() {
+                    "String" => {
+                        return quote! {
+                            responses.get_string_list(&#path_expr)
+                                .expect("missing string list")
+                                .to_vec()
+                        };
+                    }
+                    "i8" | "i16" | "i32" | "i64" | "isize" => {
+                        return quote! {
+                            responses.get_int_list(&#path_expr)
+                                .expect("missing int list")
+                                .iter()
+                                .map(|&n| n as #inner_ty)
+                                .collect()
+                        };
+                    }
+                    "u8" | "u16" | "u32" | "u64" | "usize" => {
+                        return quote! {
+                            responses.get_int_list(&#path_expr)
+                                .expect("missing int list")
+                                .iter()
+                                .map(|&n| n as #inner_ty)
+                                .collect()
+                        };
+                    }
+                    "f32" | "f64" => {
+                        return quote! {
+                            responses.get_float_list(&#path_expr)
+                                .expect("missing float list")
+                                .iter()
+                                .map(|&n| n as #inner_ty)
+                                .collect()
+                        };
+                    }
+                    _ => {
+                        // For complex types (enums with multiselect), use chosen_variants
+                        let variants_path = quote! {
+                            elicitor::ResponsePath::new(
+                                &format!("{}.{}", #field_name, elicitor::SELECTED_VARIANTS_KEY)
+                            )
+                        };
+                        return quote! {
+                            {
+                                let indices = responses
+                                    .get_chosen_variants(&#variants_path)
+                                    .map(|s| s.to_vec())
+                                    .unwrap_or_default();
+
+                                // Reconstruct each item from its indexed responses
+                                indices
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(item_idx, _variant_idx)| {
+                                        let item_prefix = elicitor::ResponsePath::new(
+                                            &format!("{}.{}", #field_name, item_idx)
+                                        );
+                                        let item_responses = responses.filter_prefix(&item_prefix);
+                                        <#inner_ty as elicitor::Survey>::from_responses(&item_responses)
+                                    })
+                                    .collect()
+                            }
+                        };
+                    }
+                }
+            }
+
+            // Nested Survey type - filter responses and call its from_responses
+            quote! {
+                {
+                    let prefix = elicitor::ResponsePath::new(#field_name);
+                    let nested_responses = responses.filter_prefix(&prefix);
+                    <#ty as elicitor::Survey>::from_responses(&nested_responses)
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// to_responses Generation
+// ============================================================================
+
+fn generate_to_responses_fn(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let body = match &input.data {
+        Data::Struct(data) => generate_to_responses_struct(data)?,
+        Data::Enum(data) => generate_to_responses_enum(&input.ident, data),
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "Survey cannot be derived for unions",
+            ));
+        }
+    };
+    Ok(quote! {
+        let mut responses = elicitor::Responses::new();
+        #body
+        responses
+    })
+}
+
+fn generate_to_responses_struct(data: &syn::DataStruct) -> syn::Result<TokenStream2> {
+    let mut insertions = Vec::new();
+    match &data.fields {
+        Fields::Named(fields) => {
+            for f in &fields.named {
+                // Deprecated fields are not asked, their replacement holds the answer
+                if FieldAttrs::extract(&f.attrs)?.replaced_by.is_some() {
+                    continue;
+                }
+                let field_name = f.ident.as_ref().unwrap();
+                insertions.push(generate_value_insertion(
+                    &field_name.to_string(),
+                    &f.ty,
+                    quote! { &self.#field_name },
+                ));
+            }
+        }
+        Fields::Unnamed(fields) => {
+            for (i, f) in fields.unnamed.iter().enumerate() {
+                let index = syn::Index::from(i);
+                insertions.push(generate_value_insertion(
+                    &i.to_string(),
+                    &f.ty,
+                    quote! { &self.#index },
+                ));
+            }
+        }
+        Fields::Unit => {}
+    }
+    Ok(quote! { #(#insertions)* })
+}
+
+fn generate_to_responses_enum(name: &Ident, data: &syn::DataEnum) -> TokenStream2 {
+    let variant_arms: Vec<_> = data
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(idx, variant)| {
+            let variant_name = &variant.ident;
+            let (pattern, insertions) = match &variant.fields {
+                Fields::Unit => (quote! { #name::#variant_name }, Vec::new()),
+                Fields::Unnamed(fields) => {
+                    let bindings: Vec<_> = (0..fields.unnamed.len())
+                        .map(|i| format_ident!("field_{}", i))
+                        .collect();
+                    let insertions = fields
+                        .unnamed
+                        .iter()
+                        .zip(&bindings)
+                        .enumerate()
+                        .map(|(i, (f, binding))| {
+                            generate_value_insertion(&i.to_string(), &f.ty, quote! { #binding })
+                        })
+                        .collect();
+                    (quote! { #name::#variant_name(#(#bindings),*) }, insertions)
+                }
+                Fields::Named(fields) => {
+                    let bindings: Vec<_> = fields
+                        .named
+                        .iter()
+                        .map(|f| f.ident.clone().unwrap())
+                        .collect();
+                    let insertions = fields
+                        .named
+                        .iter()
+                        .map(|f| {
+                            let binding = f.ident.as_ref().unwrap();
+                            generate_value_insertion(
+                                &binding.to_string(),
+                                &f.ty,
+                                quote! { #binding },
+                            )
+                        })
+                        .collect();
+                    (
+                        quote! { #name::#variant_name { #(#bindings),* } },
+                        insertions,
+                    )
+                }
+            };
+            quote! {
+                #pattern => {
+                    responses.insert(
+                        elicitor::ResponsePath::new(elicitor::SELECTED_VARIANT_KEY),
+                        elicitor::ResponseValue::ChosenVariant(#idx),
+                    );
+                    #(#insertions)*
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        match self {
+            #(#variant_arms)*
+        }
+    }
+}
+
+/// Statements inserting `value`, a reference to a field of type `ty`, at `field_name`.
+///
+/// Mirrors [`generate_value_extraction`].
+fn generate_value_insertion(field_name: &str, ty: &Type, value: TokenStream2) -> TokenStream2 {
+    let type_name = type_to_string(ty);
+    let path_expr = quote! { elicitor::ResponsePath::new(#field_name) };
+
+    match type_name.as_str() {
+        "String" => quote! {
+            responses.insert(#path_expr, elicitor::ResponseValue::String((#value).clone()));
+        },
+        "bool" => quote! {
+            responses.insert(#path_expr, elicitor::ResponseValue::Bool(*#value));
+        },
+        "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
+            quote! {
+                responses.insert(#path_expr, elicitor::ResponseValue::Int(*#value as i64));
+            }
+        }
+        "f32" | "f64" => quote! {
+            responses.insert(#path_expr, elicitor::ResponseValue::Float(*#value as f64));
+        },
+        "PathBuf" => quote! {
+            responses.insert(
+                #path_expr,
+                elicitor::ResponseValue::String((#value).display().to_string()),
+            );
+        },
+        "Color" => quote! {
+            responses.insert(#path_expr, elicitor::ResponseValue::String((#value).to_hex()));
+        },
+        "SecretString" => quote! {
+            responses.insert_secret(#path_expr, (#value).clone());
+        },
+        _ => {
+            if let Some(inner_ty) = extract_option_inner_type(ty) {
+                let inner_insertion =
+                    generate_value_insertion(field_name, &inner_ty, quote! { inner });
+                return quote! {
+                    if let Some(inner) = #value {
+                        #inner_insertion
+                    }
+                };
+            }
+
+            // Matrix answers hold the chosen column of every row, in row order
+            if extract_map_types(ty).is_some() {
+                return quote! {
+                    {
+                        let chosen = |answers: elicitor::Responses| {
+                            answers
+                                .get_chosen_variant(&elicitor::ResponsePath::new(
+                                    elicitor::SELECTED_VARIANT_KEY,
+                                ))
+                                .expect("matrix rows and columns are enums")
+                        };
+                        let mut cells: Vec<(usize, usize)> = (#value)
+                            .iter()
+                            .map(|(row, column)| {
+                                (
+                                    chosen(elicitor::Survey::to_responses(row)),
+                                    chosen(elicitor::Survey::to_responses(column)),
+                                )
+                            })
+                            .collect();
+                        cells.sort_unstable();
+                        responses.insert(
+                            #path_expr,
+                            elicitor::ResponseValue::ChosenVariants(
+                                cells.into_iter().map(|(_, column)| column).collect(),
+                            ),
+                        );
+                    }
+                };
+            }
+
+            if let Some(inner_ty) = extract_vec_inner_type(ty) {
+                let list = match type_to_string(&inner_ty).as_str() {
+                    "String" => Some(quote! {
+                        elicitor::ResponseValue::StringList((#value).clone())
+                    }),
+                    "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64"
+                    | "usize" => Some(quote! {
+                        elicitor::ResponseValue::IntList(
+                            (#value).iter().map(|&n| n as i64).collect(),
+                        )
+                    }),
+                    "f32" | "f64" => Some(quote! {
+                        elicitor::ResponseValue::FloatList(
+                            (#value).iter().map(|&n| n as f64).collect(),
+                        )
+                    }),
+                    _ => None,
+                };
+                if let Some(list) = list {
+                    return quote! {
+                        responses.insert(#path_expr, #list);
+                    };
+                }
+
+                // Multi-select: the chosen variants, and each item's answers below its position
+                return quote! {
+                    {
+                        let mut chosen = Vec::new();
+                        for (item_idx, item) in (#value).iter().enumerate() {
+                            let item_responses = elicitor::Survey::to_responses(item);
+                            chosen.push(
+                                item_responses
+                                    .get_chosen_variant(&elicitor::ResponsePath::new(
+                                        elicitor::SELECTED_VARIANT_KEY,
+                                    ))
+                                    .expect("multi-select items are enums"),
+                            );
+                            responses.extend_under(
+                                &elicitor::ResponsePath::new(
+                                    format!("{}.{}", #field_name, item_idx),
+                                ),
+                                item_responses,
+                            );
+                        }
+                        responses.insert(
+                            elicitor::ResponsePath::new(
+                                format!("{}.{}", #field_name, elicitor::SELECTED_VARIANTS_KEY),
+                            ),
+                            elicitor::ResponseValue::ChosenVariants(chosen),
+                        );
+                    }
+                };
+            }
+
+            // Nested Survey type - its answers go below the field's path
+            quote! {
+                responses.extend_under(&#path_expr, elicitor::Survey::to_responses(#value));
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Partial Result Generation
+// ============================================================================
+
+/// Generate `Partial<Name>`, `Name::from_partial_responses` and `Partial<Name>::complete`.
+fn generate_partial(input: &DeriveInput, derives: &[syn::Path]) -> syn::Result<TokenStream2> {
+    let Data::Struct(syn::DataStruct {
+        fields: Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "#[partial] is only supported on structs with named fields",
+        ));
+    };
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let partial_name = format_ident!("Partial{}", name);
+    let struct_doc =
+        format!("Answers to [`{name}`] collected so far, e.g. before the respondent cancelled.");
+
+    let mut partial_fields = Vec::new();
+    let mut extractions = Vec::new();
+    let mut completions = Vec::new();
+    for f in &fields.named {
+        let field_name = f.ident.as_ref().unwrap();
+        let field_vis = &f.vis;
+        let ty = &f.ty;
+        // Deprecated fields read the answer of their replacement
+        let source = FieldAttrs::extract(&f.attrs)?
+            .replaced_by
+            .unwrap_or_else(|| field_name.to_string());
+        let extraction = generate_partial_extraction(&source, ty);
+
+        // Optional fields stay optional, their absence does not make the result incomplete
+        if extract_option_inner_type(ty).is_some() {
+            let doc = format!("Answer to `{field_name}`.");
+            partial_fields.push(quote! { #[doc = #doc] #field_vis #field_name: #ty });
+            completions.push(quote! { #field_name: self.#field_name });
+        } else {
+            let doc = format!("Answer to `{field_name}`, `None` while unanswered.");
+            partial_fields.push(quote! { #[doc = #doc] #field_vis #field_name: Option<#ty> });
+            completions.push(quote! { #field_name: self.#field_name? });
+        }
+        extractions.push(quote! { #field_name: #extraction });
+    }
+
+    let from_partial_doc = format!(
+        "Build a [`{partial_name}`] from incomplete responses, e.g. those of a cancelled run."
+    );
+    let complete_doc = format!("The complete [`{name}`], if every required field is answered.");
+
+    Ok(quote! {
+        #[doc = #struct_doc]
+        #[derive(Default, #(#derives),*)]
+        #vis struct #partial_name {
+            #(#partial_fields,)*
+        }
+
+        impl #partial_name {
+            #[doc = #complete_doc]
+            pub fn complete(self) -> Option<#name> {
+                Some(#name {
+                    #(#completions,)*
+                })
+            }
+        }
+
+        impl #name {
+            #[doc = #from_partial_doc]
+            ///
+            /// Fields without an answer are `None`, as are nested types with unanswered
+            /// questions.
+            pub fn from_partial_responses(responses: &elicitor::Responses) -> #partial_name {
+                #partial_name {
+                    #(#extractions,)*
+                }
+            }
+        }
+    })
+}
+
+/// Expression of type `Option<T>` with the answer at `field_name`, `None` if it is missing.
+///
+/// The non-panicking counterpart of [`generate_value_extraction`]. For `Option<T>` fields
+/// the expression is the field's type itself.
+fn generate_partial_extraction(field_name: &str, ty: &Type) -> TokenStream2 {
+    let type_name = type_to_string(ty);
+    let path_expr = quote! { elicitor::ResponsePath::new(#field_name) };
+
+    match type_name.as_str() {
+        "String" => quote! {
+            responses.expose(&#path_expr).ok().map(str::to_string)
+        },
+        "bool" => quote! {
+            responses.get_bool(&#path_expr).ok()
+        },
+        "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
+            quote! {
+                responses.get_int(&#path_expr).ok().map(|n| n as #ty)
+            }
+        }
+        "f32" | "f64" => quote! {
+            responses.get_float(&#path_expr).ok().map(|n| n as #ty)
+        },
+        "PathBuf" => quote! {
+            responses.get_string(&#path_expr).ok().map(std::path::PathBuf::from)
+        },
+        "Color" => quote! {
+            responses.get_color(&#path_expr).ok()
+        },
+        "SecretString" => quote! {
+            responses.get_secret(&#path_expr).ok()
         },
         _ => {
-            // Check for Option<T>
             if let Some(inner_ty) = extract_option_inner_type(ty) {
-                let inner_extraction = generate_value_extraction(field_name, &inner_ty);
+                let inner_extraction = generate_partial_extraction(field_name, &inner_ty);
                 return quote! {
                     if responses.has_value(&#path_expr) {
-                        Some(#inner_extraction)
+                        #inner_extraction
                     } else {
                         None
                     }
                 };
             }
 
-            // Check for Vec<T>
-            if let Some(inner_ty) = extract_vec_inner_type(ty) {
-                let inner_type_name = type_to_string(&inner_ty);
+            if let Some((row_ty, column_ty)) = extract_map_types(ty) {
+                return quote! {
+                    responses.get_chosen_variants(&#path_expr).ok().map(|chosen| {
+                        chosen
+                            .iter()
+                            .enumerate()
+                            .map(|(row, &column)| {
+                                (
+                                    elicitor::variant_from_index::<#row_ty>(row),
+                                    elicitor::variant_from_index::<#column_ty>(column),
+                                )
+                            })
+                            .collect()
+                    })
+                };
+            }
 
-                // For primitive types, use the list response values
-                match inner_type_name.as_str() {
+            if let Some(inner_ty) = extract_vec_inner_type(ty) {
+                match type_to_string(&inner_ty).as_str() {
                     "String" => {
                         return quote! {
-                            responses.get_string_list(&#path_expr)
-                                .expect("missing string list")
-                                .to_vec()
-                        };
-                    }
-                    "i8" | "i16" | "i32" | "i64" | "isize" => {
-                        return quote! {
-                            responses.get_int_list(&#path_expr)
-                                .expect("missing int list")
-                                .iter()
-                                .map(|&n| n as #inner_ty)
-                                .collect()
+                            responses.get_string_list(&#path_expr).ok().map(<[String]>::to_vec)
                         };
                     }
-                    "u8" | "u16" | "u32" | "u64" | "usize" => {
+                    "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64"
+                    | "usize" => {
                         return quote! {
-                            responses.get_int_list(&#path_expr)
-                                .expect("missing int list")
-                                .iter()
-                                .map(|&n| n as #inner_ty)
-                                .collect()
+                            responses.get_int_list(&#path_expr).ok().map(|list| {
+                                list.iter().map(|&n| n as #inner_ty).collect()
+                            })
                         };
                     }
                     "f32" | "f64" => {
                         return quote! {
-                            responses.get_float_list(&#path_expr)
-                                .expect("missing float list")
-                                .iter()
-                                .map(|&n| n as #inner_ty)
-                                .collect()
+                            responses.get_float_list(&#path_expr).ok().map(|list| {
+                                list.iter().map(|&n| n as #inner_ty).collect()
+                            })
                         };
                     }
                     _ => {
-                        // For complex types (enums with multiselect), use chosen_variants
-                        let variants_path = quote! {
-                            elicitor::ResponsePath::new(
-                                &format!("{}.{}", #field_name, elicitor::SELECTED_VARIANTS_KEY)
-                            )
-                        };
+                        // Multi-select: every chosen item needs all of its answers
                         return quote! {
-                            {
-                                let indices = responses
-                                    .get_chosen_variants(&#variants_path)
-                                    .map(|s| s.to_vec())
-                                    .unwrap_or_default();
-
-                                // Reconstruct each item from its indexed responses
-                                indices
-                                    .iter()
-                                    .enumerate()
-                                    .map(|(item_idx, _variant_idx)| {
-                                        let item_prefix = elicitor::ResponsePath::new(
-                                            &format!("{}.{}", #field_name, item_idx)
-                                        );
-                                        let item_responses = responses.filter_prefix(&item_prefix);
-                                        <#inner_ty as elicitor::Survey>::from_responses(&item_responses)
-                                    })
-                                    .collect()
-                            }
+                            responses
+                                .get_chosen_variants(&elicitor::ResponsePath::new(
+                                    format!("{}.{}", #field_name, elicitor::SELECTED_VARIANTS_KEY),
+                                ))
+                                .ok()
+                                .and_then(|chosen| {
+                                    (0..chosen.len())
+                                        .map(|item_idx| {
+                                            let item_responses = responses.filter_prefix(
+                                                &elicitor::ResponsePath::new(
+                                                    format!("{}.{}", #field_name, item_idx),
+                                                ),
+                                            );
+                                            elicitor::next_step(
+                                                &<#inner_ty as elicitor::Survey>::survey(),
+                                                &item_responses,
+                                            )
+                                            .is_none()
+                                            .then(|| {
+                                                <#inner_ty as elicitor::Survey>::from_responses(
+                                                    &item_responses,
+                                                )
+                                            })
+                                        })
+                                        .collect()
+                                })
                         };
                     }
                 }
             }
 
-            // Nested Survey type - filter responses and call its from_responses
+            // Nested Survey type - complete once none of its questions is pending
             quote! {
                 {
-                    let prefix = elicitor::ResponsePath::new(#field_name);
-                    let nested_responses = responses.filter_prefix(&prefix);
-                    <#ty as elicitor::Survey>::from_responses(&nested_responses)
+                    let nested_responses =
+                        responses.filter_prefix(&elicitor::ResponsePath::new(#field_name));
+                    elicitor::next_step(&<#ty as elicitor::Survey>::survey(), &nested_responses)
+                        .is_none()
+                        .then(|| <#ty as elicitor::Survey>::from_responses(&nested_responses))
                 }
             }
         }
@@ -934,7 +2367,7 @@ fn generate_field_accessors(input: &DeriveInput) -> syn::Result<TokenStream2> {
     match &input.data {
         Data::Struct(data) => {
             if let Fields::Named(fields) = &data.fields {
-                for field in &fields.named {
+                for field in fields.named.iter().filter(|field| !is_replaced(field)) {
                     let field_name = field.ident.as_ref().unwrap();
                     let field_name_str = field_name.to_string();
                     let ty = &field.ty;
@@ -969,7 +2402,7 @@ fn generate_field_accessor_method(
         "String" => quote! {
             /// Get the value of this field from responses, if present.
             pub fn #method_name(responses: &elicitor::Responses) -> Option<String> {
-                responses.get_string(&#path_expr).ok().map(|s| s.to_string())
+                responses.expose(&#path_expr).ok().map(|s| s.to_string())
             }
         },
         "bool" => quote! {
@@ -1002,6 +2435,18 @@ fn generate_field_accessor_method(
                 responses.get_string(&#path_expr).ok().map(std::path::PathBuf::from)
             }
         },
+        "Color" => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(responses: &elicitor::Responses) -> Option<elicitor::Color> {
+                responses.get_color(&#path_expr).ok()
+            }
+        },
+        "SecretString" => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(responses: &elicitor::Responses) -> Option<elicitor::SecretString> {
+                responses.get_secret(&#path_expr).ok()
+            }
+        },
         _ => {
             // For complex types (nested structs, enums, etc.), we don't generate accessors
             // as they would require more complex handling
@@ -1025,7 +2470,7 @@ fn generate_validation_context(input: &DeriveInput) -> syn::Result<TokenStream2>
     match &input.data {
         Data::Struct(data) => {
             if let Fields::Named(fields) = &data.fields {
-                for field in &fields.named {
+                for field in fields.named.iter().filter(|field| !is_replaced(field)) {
                     let field_name = field.ident.as_ref().unwrap();
                     let field_name_str = field_name.to_string();
                     let ty = &field.ty;
@@ -1084,7 +2529,7 @@ fn generate_context_accessor_method(
             /// Get the value of this field from responses, if present.
             pub fn #method_name(&self) -> Option<String> {
                 let path = self.prefix.child(#field_name_str);
-                self.responses.get_string(&path).ok().map(|s| s.to_string())
+                self.responses.expose(&path).ok().map(|s| s.to_string())
             }
         },
         "bool" => quote! {
@@ -1122,6 +2567,20 @@ fn generate_context_accessor_method(
                 self.responses.get_string(&path).ok().map(std::path::PathBuf::from)
             }
         },
+        "Color" => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(&self) -> Option<elicitor::Color> {
+                let path = self.prefix.child(#field_name_str);
+                self.responses.get_color(&path).ok()
+            }
+        },
+        "SecretString" => quote! {
+            /// Get the value of this field from responses, if present.
+            pub fn #method_name(&self) -> Option<elicitor::SecretString> {
+                let path = self.prefix.child(#field_name_str);
+                self.responses.get_secret(&path).ok()
+            }
+        },
         _ => {
             // For complex types, don't generate accessors
             quote! {}
@@ -1180,17 +2639,13 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
             validators.push(quote! {
                 // Only run validate_fields if the path matches one of this struct's fields
                 if #(#field_checks)||* {
-                    if let Err(e) = #validator(value, responses, path) {
-                        return Err(e);
-                    }
+                    #validator(value, responses, path)?;
                 }
             });
         } else {
             // Fallback for non-struct types (shouldn't normally happen)
             validators.push(quote! {
-                if let Err(e) = #validator(value, responses, path) {
-                    return Err(e);
-                }
+                #validator(value, responses, path)?;
             });
         }
     }
@@ -1208,9 +2663,16 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
                         // Only run this validator if the path matches this field
                         validators.push(quote! {
                             if #path_check {
-                                if let Err(e) = #validator(value, responses, path) {
-                                    return Err(e);
-                                }
+                                #validator(value, responses, path)?;
+                            }
+                        });
+                    }
+
+                    if let Some(id) = &attrs.use_question {
+                        let path_check = path_matches_field(&field_name);
+                        validators.push(quote! {
+                            if #path_check {
+                                elicitor::question_bank::validate(#id, value, responses, path)?;
                             }
                         });
                     }
@@ -1235,18 +2697,19 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
                             | "f32"
                             | "f64"
                             | "PathBuf"
+                            | "Color"
+                            | "SecretString"
                     );
 
-                    // Skip Vec types (they're handled differently) and primitives
+                    // Skip Vec and map types (they're handled differently) and primitives
                     if !is_primitive
                         && extract_vec_inner_type(ty).is_none()
+                        && extract_map_types(ty).is_none()
                         && extract_option_inner_type(ty).is_none()
                     {
                         validators.push(quote! {
                             // Delegate validation to nested Survey type
-                            if let Err(e) = <#ty as elicitor::Survey>::validate_field(value, responses, path) {
-                                return Err(e);
-                            }
+                            <#ty as elicitor::Survey>::validate_field_keyed(value, responses, path)?;
                         });
                     }
                 }
@@ -1264,9 +2727,16 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
                                 let path_check = path_matches_field(&field_name);
                                 validators.push(quote! {
                                     if #path_check {
-                                        if let Err(e) = #validator(value, responses, path) {
-                                            return Err(e);
-                                        }
+                                        #validator(value, responses, path)?;
+                                    }
+                                });
+                            }
+
+                            if let Some(id) = &attrs.use_question {
+                                let path_check = path_matches_field(&field_name);
+                                validators.push(quote! {
+                                    if #path_check {
+                                        elicitor::question_bank::validate(#id, value, responses, path)?;
                                     }
                                 });
                             }
@@ -1288,9 +2758,16 @@ fn generate_validate_field_fn(input: &DeriveInput) -> syn::Result<TokenStream2>
                                 let path_check = path_matches_field(&field_name);
                                 validators.push(quote! {
                                     if #path_check {
-                                        if let Err(e) = #validator(value, responses, path) {
-                                            return Err(e);
-                                        }
+                                        #validator(value, responses, path)?;
+                                    }
+                                });
+                            }
+
+                            if let Some(id) = &attrs.use_question {
+                                let path_check = path_matches_field(&field_name);
+                                validators.push(quote! {
+                                    if #path_check {
+                                        elicitor::question_bank::validate(#id, value, responses, path)?;
                                     }
                                 });
                             }
@@ -1337,10 +2814,18 @@ fn generate_validator_checks(input: &DeriveInput) -> syn::Result<TokenStream2> {
         });
     }
 
+    // Check scoring function
+    if let Some((function, score)) = &type_attrs.score {
+        let name = &input.ident;
+        checks.push(quote! {
+            const _: fn(&#name, &elicitor::Points) -> #score = #function;
+        });
+    }
+
     // Check propagated field validator (validate_fields)
     if let Some(validator) = &type_attrs.validate_fields {
         checks.push(quote! {
-            const _: fn(&elicitor::ResponseValue, &elicitor::Responses, &elicitor::ResponsePath) -> Result<(), String> = #validator;
+            const _: () = elicitor::check_field_validator(#validator);
         });
     }
 
@@ -1349,7 +2834,7 @@ fn generate_validator_checks(input: &DeriveInput) -> syn::Result<TokenStream2> {
         let attrs = FieldAttrs::extract(&field.attrs)?;
         if let Some(validator) = &attrs.validate {
             checks.push(quote! {
-                const _: fn(&elicitor::ResponseValue, &elicitor::Responses, &elicitor::ResponsePath) -> Result<(), String> = #validator;
+                const _: () = elicitor::check_field_validator(#validator);
             });
         }
         Ok(())
@@ -1413,11 +2898,42 @@ fn generate_builder(input: &DeriveInput, builder_name: &Ident) -> syn::Result<To
     // Generate with_suggestions body
     let with_suggestions_body = generate_with_suggestions_body(input);
 
+    // Generate edit_<field> shortcuts for the fields of named structs
+    let edit_methods = generate_edit_methods(input);
+
+    // Generate run_scored if the type has a scoring function
+    let run_scored = TypeAttrs::extract(&input.attrs)?
+        .score
+        .map(|(function, score)| {
+            quote! {
+                /// Run the survey and score the result with the type's scoring function
+                pub fn run_scored<B: elicitor::SurveyBackend>(
+                    self,
+                    backend: B,
+                ) -> Result<(#name, #score), anyhow::Error> {
+                    let (value, responses) = self.run_with_responses(backend)?;
+                    let points = elicitor::Points::tally(&#name::survey(), &responses);
+                    let score = #function(&value, &points);
+                    Ok((value, score))
+                }
+            }
+        });
+
     Ok(quote! {
         /// Builder for running surveys with suggestions and assumptions
         pub struct #builder_name {
             suggestions: std::collections::HashMap<String, elicitor::ResponseValue>,
             assumptions: std::collections::HashMap<String, elicitor::ResponseValue>,
+            role: Option<String>,
+            observers: Vec<std::sync::Arc<dyn elicitor::SurveyObserver>>,
+            hooks: elicitor::AnswerHooks,
+            origins: Vec<(elicitor::ResponsePath, elicitor::AnswerOrigin)>,
+            flags: Option<Box<dyn elicitor::FlagProvider>>,
+            translator: Option<Box<dyn elicitor::Translator>>,
+            locale: Option<String>,
+            edited: Option<elicitor::Responses>,
+            edit_paths: Vec<elicitor::ResponsePath>,
+            existing: Option<elicitor::Responses>,
         }
 
         impl #builder_name {
@@ -1426,7 +2942,96 @@ fn generate_builder(input: &DeriveInput, builder_name: &Ident) -> syn::Result<To
                 Self {
                     suggestions: std::collections::HashMap::new(),
                     assumptions: std::collections::HashMap::new(),
+                    role: None,
+                    observers: Vec::new(),
+                    hooks: elicitor::AnswerHooks::new(),
+                    origins: Vec::new(),
+                    flags: None,
+                    translator: None,
+                    locale: None,
+                    edited: None,
+                    edit_paths: Vec::new(),
+                    existing: None,
+                }
+            }
+
+            /// Report progress (questions shown, answers, validation errors) to an observer
+            pub fn with_observer(mut self, observer: impl elicitor::SurveyObserver + 'static) -> Self {
+                self.observers.push(std::sync::Arc::new(observer));
+                self
+            }
+
+            /// Show the prompts of fields marked `#[ask(key = "...")]` and the messages of
+            /// validators returning a keyed `ValidationError` in the language of `translator`
+            pub fn with_translator(mut self, translator: impl elicitor::Translator + 'static) -> Self {
+                self.translator = Some(Box::new(translator));
+                self
+            }
+
+            /// Accept and show numbers in the decimal and group separators of `locale`,
+            /// e.g. `1.234,5` for `de`
+            pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+                self.locale = Some(locale.into());
+                self
+            }
+
+            /// Call `hook` with the path of each question before a wizard backend shows it
+            pub fn before_each(
+                mut self,
+                hook: impl Fn(&elicitor::ResponsePath) + Send + Sync + 'static,
+            ) -> Self {
+                self.hooks.before_each(hook);
+                self
+            }
+
+            /// Call `hook` with each answer that passed validation; it keeps the answer,
+            /// replaces it, or rejects it so the question is asked again
+            pub fn after_each(
+                mut self,
+                hook: impl Fn(&elicitor::ResponsePath, &elicitor::ResponseValue) -> elicitor::AnswerAction
+                    + Send
+                    + Sync
+                    + 'static,
+            ) -> Self {
+                self.hooks.after_each(hook);
+                self
+            }
+
+            /// Run the survey for a role: fields marked `#[editable_by(...)]` for other
+            /// roles keep their suggested value instead of being asked
+            pub fn with_role(mut self, role: impl Into<String>) -> Self {
+                self.role = Some(role.into());
+                self
+            }
+
+            /// Decide with `flags` which fields marked `#[flag(...)]` are asked; fields whose
+            /// flag is off keep their suggested value or are left as `None`
+            pub fn with_flags(mut self, flags: impl elicitor::FlagProvider + 'static) -> Self {
+                self.flags = Some(Box::new(flags));
+                self
+            }
+
+            /// Record where the answer at `path` came from, e.g. the environment variable an
+            /// assumption was read from. Assumed answers default to `AnswerOrigin::Assumed`
+            pub fn with_origin(
+                mut self,
+                path: impl Into<elicitor::ResponsePath>,
+                origin: elicitor::AnswerOrigin,
+            ) -> Self {
+                self.origins.push((path.into(), origin));
+                self
+            }
+
+            /// Suggest and assume the answers of `profile`. Later `suggest_*` and `assume_*`
+            /// calls override them
+            pub fn with_profile(mut self, profile: &elicitor::Profile) -> Self {
+                for (path, value) in &profile.suggestions {
+                    self.suggestions.insert(path.clone(), value.clone());
                 }
+                for (path, value) in &profile.assumptions {
+                    self.assumptions.insert(path.clone(), value.clone());
+                }
+                self
             }
 
             /// Set suggestions from an existing instance (all fields become suggested defaults)
@@ -1435,29 +3040,216 @@ fn generate_builder(input: &DeriveInput, builder_name: &Ident) -> syn::Result<To
                 self
             }
 
+            /// Ask only the question at `path`, pre-filled from `instance`, and keep the
+            /// other fields of `instance`. Call again to edit more fields; a path inside an
+            /// enum asks the whole enum again
+            pub fn edit_field(
+                mut self,
+                instance: &#name,
+                path: impl Into<elicitor::ResponsePath>,
+            ) -> Self {
+                let edited = elicitor::Survey::to_responses(instance);
+                for (path, value) in edited.iter() {
+                    self.suggestions
+                        .entry(path.as_str().to_string())
+                        .or_insert_with(|| value.clone());
+                }
+                self.edited = Some(edited);
+                self.edit_paths.push(path.into());
+                self
+            }
+
+            #(#edit_methods)*
+
+            /// Start from `responses`, e.g. answers loaded from a config file, and ask only
+            /// the questions they leave unanswered or whose answer fails validation
+            pub fn ask_missing(mut self, responses: elicitor::Responses) -> Self {
+                self.existing = Some(responses);
+                self
+            }
+
             #(#suggest_methods)*
             #(#assume_methods)*
 
+            /// Check the assumed answers against the type, bounds and validator of their
+            /// questions, listing every invalid one. `run` does this before asking anything
+            pub fn check_assumptions(&self) -> Result<(), elicitor::InvalidAssumptions> {
+                let mut definition = #name::survey();
+                self.apply_to_definition(&mut definition);
+                let translator = self.translator.as_deref();
+                elicitor::check_assumptions(&definition, &|value, responses, path| {
+                    #name::validate_field_keyed(value, responses, path)
+                        .map_err(|error| error.message(translator))
+                })
+            }
+
+            /// List the questions `run` would ask and why the others are skipped, given the
+            /// suggestions, assumptions, flags and existing answers, without asking anything
+            pub fn plan(&self) -> elicitor::QuestionPlan {
+                let mut definition = #name::survey();
+                self.apply_to_definition(&mut definition);
+                if let Some(translator) = &self.translator {
+                    definition.translate(translator.as_ref());
+                }
+
+                // Answers kept from the instance or config are not asked again
+                let mut answered = elicitor::Responses::new();
+                let mut unchanged = self.edited.clone();
+                let mut edit_paths = self.edit_paths.clone();
+                if let Some(existing) = &self.existing {
+                    edit_paths.extend(elicitor::pending_paths(
+                        &definition,
+                        existing,
+                        #name::validate_field,
+                    ));
+                    edit_paths.extend(#name::validate_all(existing).into_keys());
+                    unchanged = Some(existing.clone());
+                }
+                if let Some(unchanged) = unchanged {
+                    let asked = definition.clone().restrict_to(&edit_paths);
+                    answered = unchanged;
+                    answered.retain(|path, _| !asked.iter().any(|kept| path.is_within(kept)));
+                }
+
+                elicitor::QuestionPlan::new(&definition, &answered, self.flags.as_deref())
+            }
+
             /// Run the survey with the given backend
             pub fn run<B: elicitor::SurveyBackend>(
                 self,
                 backend: B,
             ) -> Result<#name, anyhow::Error> {
-                let mut definition = #name::survey();
+                self.run_with_responses(backend).map(|(value, _)| value)
+            }
+
+            /// Run the survey with the backend registered under `backend_name`
+            /// (see `elicitor::register_backend`)
+            pub fn run_named(self, backend_name: &str) -> Result<#name, anyhow::Error> {
+                let backend = elicitor::backend_named(backend_name)?;
+                self.run(backend)
+            }
+
+            /// Run the survey and also return the raw responses, e.g. to read
+            /// comments attached with `#[allow_comment]`
+            pub fn run_with_responses<B: elicitor::SurveyBackend>(
+                self,
+                backend: B,
+            ) -> Result<(#name, elicitor::Responses), anyhow::Error> {
+                let mut definition = elicitor::question_bank::checked(#name::survey)?;
 
                 // Apply suggestions and assumptions to questions
                 self.apply_to_definition(&mut definition);
+                if let Some(translator) = &self.translator {
+                    definition.translate(translator.as_ref());
+                }
+                if let Some(locale) = &self.locale {
+                    definition.set_locale(locale);
+                }
+
+                // Leave out the fields whose feature flag is off
+                if let Some(flags) = &self.flags {
+                    elicitor::apply_flags(&mut definition, flags.as_ref());
+                }
+
+                // Assumed answers are never asked, so check them before anything is shown,
+                // with the messages of the validators in the translator's language
+                let translator = self.translator.as_deref();
+                elicitor::check_assumptions(&definition, &|value, responses, path| {
+                    #name::validate_field_keyed(value, responses, path)
+                        .map_err(|error| error.message(translator))
+                })?;
+
+                // Ask only the edited fields, the other answers come from the instance
+                let mut unchanged = self.edited.clone();
+                let mut edit_paths = self.edit_paths.clone();
+                if let Some(existing) = &self.existing {
+                    edit_paths.extend(elicitor::pending_paths(
+                        &definition,
+                        existing,
+                        #name::validate_field,
+                    ));
+                    edit_paths.extend(#name::validate_all(existing).into_keys());
+                    unchanged = Some(existing.clone());
+                }
+                if let Some(unchanged) = &mut unchanged {
+                    let asked = definition.restrict_to(&edit_paths);
+                    if let Some(missing) = edit_paths
+                        .iter()
+                        .find(|path| !asked.iter().any(|kept| path.is_within(kept)))
+                    {
+                        anyhow::bail!("no question to edit at `{}`", missing);
+                    }
+                    unchanged.retain(|path, _| !asked.iter().any(|kept| path.is_within(kept)));
+                }
+
+                // Lock the fields the role may not edit
+                let restrictions = self
+                    .role
+                    .as_deref()
+                    .map(|role| elicitor::RoleRestrictions::apply(&mut definition, role))
+                    .transpose()?;
 
                 // Collect responses
-                let responses = backend.collect(
+                let translator = self.translator;
+                let hooks = std::sync::Arc::new(self.hooks);
+                let mut observers = self.observers;
+                if !hooks.is_empty() {
+                    observers.push(hooks.clone());
+                }
+                let observers = &observers;
+                let mut responses = backend.collect_observed(
                     &definition,
-                    &|value, responses, path| #name::validate_field(value, responses, path),
+                    &|value, responses, path| {
+                        let mut all = std::borrow::Cow::Borrowed(responses);
+                        if let Some(unchanged) = &unchanged {
+                            let mut merged = unchanged.clone();
+                            merged.extend(responses.clone());
+                            all = std::borrow::Cow::Owned(merged);
+                        }
+                        // Later answers are validated against the values hooks replaced
+                        if hooks.has_replacements() {
+                            hooks.apply_replacements(all.to_mut());
+                        }
+                        let result = #name::validate_field_keyed(value, &all, path)
+                            .and_then(|()| {
+                                hooks
+                                    .after_answer(path, value)
+                                    .map_err(elicitor::ValidationError::from)
+                            })
+                            .map_err(|error| error.message(translator.as_deref()));
+                        match &result {
+                            Ok(()) => elicitor::SurveyObserver::on_answer(observers, path, value),
+                            Err(message) => {
+                                elicitor::SurveyObserver::on_validation_error(observers, path, message)
+                            }
+                        }
+                        result
+                    },
+                    observers,
                 ).map_err(Into::into)?;
+                hooks.apply_replacements(&mut responses);
+                if let Some(restrictions) = &restrictions {
+                    restrictions.check(&responses)?;
+                }
+                if let Some(mut unchanged) = unchanged {
+                    unchanged.extend(responses);
+                    responses = unchanged;
+                }
+                responses.redact_masked(&definition);
+                for (path, origin) in self.origins {
+                    if responses.contains(&path) {
+                        responses.set_origin(path, origin);
+                    }
+                }
+                responses.record_assumed_origins(&definition);
+                responses.touch();
 
                 // Reconstruct the type
-                Ok(#name::from_responses(&responses))
+                Ok((#name::from_responses(&responses), responses))
             }
 
+            #run_scored
+
             fn apply_to_definition(&self, definition: &mut elicitor::SurveyDefinition) {
                 for question in &mut definition.questions {
                     self.apply_to_question(question, "");
@@ -1496,6 +3288,16 @@ fn generate_builder(input: &DeriveInput, builder_name: &Ident) -> syn::Result<To
                     question.set_suggestion(value.clone());
                 }
 
+                // An assumed variant is chosen without asking, its fields are assumed below
+                let choice_key = match question.kind() {
+                    elicitor::QuestionKind::OneOf(_) => Some(format!("{}.selected_variant", path_str)),
+                    elicitor::QuestionKind::AnyOf(_) => Some(format!("{}.selected_variants", path_str)),
+                    _ => None,
+                };
+                if let Some(value) = choice_key.and_then(|key| self.assumptions.get(&key)) {
+                    question.set_assumption(value.clone());
+                }
+
                 // Recurse into nested questions
                 match question.kind_mut() {
                     elicitor::QuestionKind::AllOf(all_of) => {
@@ -1574,13 +3376,35 @@ fn generate_suggest_builder(input: &DeriveInput) -> syn::Result<TokenStream2> {
     let name = &input.ident;
     let suggest_builder_name = format_ident!("{}SuggestBuilder", name);
 
-    match &input.data {
+    let builder = match &input.data {
         Data::Struct(data) => {
-            generate_suggest_builder_for_struct(name, &suggest_builder_name, data)
+            generate_suggest_builder_for_struct(name, &suggest_builder_name, data)?
         }
-        Data::Enum(data) => generate_suggest_builder_for_enum(name, &suggest_builder_name, data),
-        Data::Union(_) => Ok(quote! {}),
-    }
+        Data::Enum(data) => generate_suggest_builder_for_enum(name, &suggest_builder_name, data)?,
+        Data::Union(_) => return Ok(quote! {}),
+    };
+
+    // Lets outer types reach this builder through the type, wherever it is defined
+    Ok(quote! {
+        #builder
+
+        impl elicitor::SuggestFor for #name {
+            type Builder = #suggest_builder_name;
+
+            fn suggest_builder(
+                map: std::collections::HashMap<String, elicitor::ResponseValue>,
+                prefix: String,
+            ) -> Self::Builder {
+                #suggest_builder_name::new(map, prefix)
+            }
+
+            fn into_suggestions(
+                builder: Self::Builder,
+            ) -> std::collections::HashMap<String, elicitor::ResponseValue> {
+                builder.map
+            }
+        }
+    })
 }
 
 /// Generate SuggestBuilder for a struct type
@@ -1592,7 +3416,7 @@ fn generate_suggest_builder_for_struct(
     let mut field_methods = Vec::new();
 
     if let Fields::Named(fields) = &data.fields {
-        for field in &fields.named {
+        for field in fields.named.iter().filter(|field| !is_replaced(field)) {
             let field_name = field.ident.as_ref().unwrap();
             let field_name_str = field_name.to_string();
             let ty = &field.ty;
@@ -1612,14 +3436,14 @@ fn generate_suggest_builder_for_struct(
 
     Ok(quote! {
         /// Builder for suggesting/assuming values for nested fields
-        pub struct #suggest_builder_name<'a> {
-            map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+        pub struct #suggest_builder_name {
+            map: std::collections::HashMap<String, elicitor::ResponseValue>,
             prefix: String,
         }
 
-        impl<'a> #suggest_builder_name<'a> {
+        impl #suggest_builder_name {
             fn new(
-                map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+                map: std::collections::HashMap<String, elicitor::ResponseValue>,
                 prefix: String,
             ) -> Self {
                 Self { map, prefix }
@@ -1656,7 +3480,7 @@ fn generate_suggest_builder_for_enum(
         // Generate suggest_<variant>() method to pre-select this variant
         select_methods.push(quote! {
             /// Pre-select this variant as the suggested default choice
-            pub fn #select_method_name(self) -> Self {
+            pub fn #select_method_name(mut self) -> Self {
                 self.map.insert(
                     format!("{}.selected_variant", self.prefix),
                     elicitor::ResponseValue::ChosenVariant(#idx),
@@ -1673,12 +3497,15 @@ fn generate_suggest_builder_for_enum(
 
                 variant_methods.push(quote! {
                     /// Suggest values for this variant's fields
-                    pub fn #method_name<F>(self, f: F) -> Self
+                    pub fn #method_name<F>(mut self, f: F) -> Self
                     where
-                        F: FnOnce(#variant_builder_name<'_>) -> #variant_builder_name<'_>,
+                        F: FnOnce(#variant_builder_name) -> #variant_builder_name,
                     {
-                        let builder = #variant_builder_name::new(self.map, self.prefix.clone());
-                        f(builder);
+                        let builder = #variant_builder_name::new(
+                            std::mem::take(&mut self.map),
+                            self.prefix.clone(),
+                        );
+                        self.map = f(builder).map;
                         self
                     }
                 });
@@ -1696,14 +3523,14 @@ fn generate_suggest_builder_for_enum(
 
                 variant_builders.push(quote! {
                     /// Builder for suggesting values for variant fields
-                    pub struct #variant_builder_name<'a> {
-                        map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+                    pub struct #variant_builder_name {
+                        map: std::collections::HashMap<String, elicitor::ResponseValue>,
                         prefix: String,
                     }
 
-                    impl<'a> #variant_builder_name<'a> {
+                    impl #variant_builder_name {
                         fn new(
-                            map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+                            map: std::collections::HashMap<String, elicitor::ResponseValue>,
                             prefix: String,
                         ) -> Self {
                             Self { map, prefix }
@@ -1746,6 +3573,8 @@ fn generate_suggest_builder_for_enum(
                         | "f32"
                         | "f64"
                         | "PathBuf"
+                        | "Color"
+                        | "SecretString"
                 );
 
                 if is_primitive {
@@ -1775,12 +3604,20 @@ fn generate_suggest_builder_for_enum(
                             quote! { impl Into<std::path::PathBuf> },
                             quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
                         ),
+                        "Color" => (
+                            quote! { elicitor::Color },
+                            quote! { elicitor::ResponseValue::String(value.to_hex()) },
+                        ),
+                        "SecretString" => (
+                            quote! { elicitor::SecretString },
+                            quote! { elicitor::ResponseValue::String(elicitor::secrecy::ExposeSecret::expose_secret(&value).to_string()) },
+                        ),
                         _ => unreachable!(),
                     };
 
                     variant_methods.push(quote! {
                         /// Suggest a value for this newtype variant
-                        pub fn #method_name(self, value: #param_type) -> Self {
+                        pub fn #method_name(mut self, value: #param_type) -> Self {
                             self.map.insert(
                                 format!("{}.0", self.prefix),
                                 #conversion,
@@ -1790,19 +3627,19 @@ fn generate_suggest_builder_for_enum(
                     });
                 } else {
                     // For complex types, use the inner type's builder directly
-                    let inner_builder_name = format_ident!("{}SuggestBuilder", type_name);
+                    let inner_builder_name = quote! { <#ty as elicitor::SuggestFor>::Builder };
 
                     variant_methods.push(quote! {
                         /// Suggest values for this newtype variant's inner type
-                        pub fn #method_name<F>(self, f: F) -> Self
+                        pub fn #method_name<F>(mut self, f: F) -> Self
                         where
-                            F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                            F: FnOnce(#inner_builder_name) -> #inner_builder_name,
                         {
-                            let builder = #inner_builder_name::new(
-                                self.map,
+                            let builder = <#ty as elicitor::SuggestFor>::suggest_builder(
+                                std::mem::take(&mut self.map),
                                 format!("{}.0", self.prefix),
                             );
-                            f(builder);
+                            self.map = <#ty as elicitor::SuggestFor>::into_suggestions(f(builder));
                             self
                         }
                     });
@@ -1815,12 +3652,15 @@ fn generate_suggest_builder_for_enum(
 
                 variant_methods.push(quote! {
                     /// Suggest values for this variant's fields
-                    pub fn #method_name<F>(self, f: F) -> Self
+                    pub fn #method_name<F>(mut self, f: F) -> Self
                     where
-                        F: FnOnce(#variant_builder_name<'_>) -> #variant_builder_name<'_>,
+                        F: FnOnce(#variant_builder_name) -> #variant_builder_name,
                     {
-                        let builder = #variant_builder_name::new(self.map, self.prefix.clone());
-                        f(builder);
+                        let builder = #variant_builder_name::new(
+                            std::mem::take(&mut self.map),
+                            self.prefix.clone(),
+                        );
+                        self.map = f(builder).map;
                         self
                     }
                 });
@@ -1837,14 +3677,14 @@ fn generate_suggest_builder_for_enum(
 
                 variant_builders.push(quote! {
                     /// Builder for suggesting values for variant fields
-                    pub struct #variant_builder_name<'a> {
-                        map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+                    pub struct #variant_builder_name {
+                        map: std::collections::HashMap<String, elicitor::ResponseValue>,
                         prefix: String,
                     }
 
-                    impl<'a> #variant_builder_name<'a> {
+                    impl #variant_builder_name {
                         fn new(
-                            map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+                            map: std::collections::HashMap<String, elicitor::ResponseValue>,
                             prefix: String,
                         ) -> Self {
                             Self { map, prefix }
@@ -1870,14 +3710,14 @@ fn generate_suggest_builder_for_enum(
 
     Ok(quote! {
         /// Builder for suggesting/assuming values for enum variants
-        pub struct #suggest_builder_name<'a> {
-            map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+        pub struct #suggest_builder_name {
+            map: std::collections::HashMap<String, elicitor::ResponseValue>,
             prefix: String,
         }
 
-        impl<'a> #suggest_builder_name<'a> {
+        impl #suggest_builder_name {
             fn new(
-                map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+                map: std::collections::HashMap<String, elicitor::ResponseValue>,
                 prefix: String,
             ) -> Self {
                 Self { map, prefix }
@@ -1911,8 +3751,8 @@ fn generate_suggest_builder_field_method(field_name: &str, ty: &Type) -> syn::Re
         return generate_option_suggest_method(field_name, &inner_ty);
     }
 
-    // Skip Vec<T> types - they don't have a simple suggest pattern
-    if extract_vec_inner_type(ty).is_some() {
+    // Skip Vec<T> and map types - they don't have a simple suggest pattern
+    if extract_vec_inner_type(ty).is_some() || extract_map_types(ty).is_some() {
         return Ok(quote! {});
     }
 
@@ -1944,6 +3784,16 @@ fn generate_suggest_builder_field_method(field_name: &str, ty: &Type) -> syn::Re
                 quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
             ),
         ),
+        "Color" => (
+            Some(quote! { elicitor::Color }),
+            Some(quote! { elicitor::ResponseValue::String(value.to_hex()) }),
+        ),
+        "SecretString" => (
+            Some(quote! { elicitor::SecretString }),
+            Some(
+                quote! { elicitor::ResponseValue::String(elicitor::secrecy::ExposeSecret::expose_secret(&value).to_string()) },
+            ),
+        ),
         _ => (None, None), // Complex type - closure-based
     };
 
@@ -1951,23 +3801,26 @@ fn generate_suggest_builder_field_method(field_name: &str, ty: &Type) -> syn::Re
         // Primitive type - direct value method
         Ok(quote! {
             /// Suggest a value for this field
-            pub fn #method_name(self, value: #param_type) -> Self {
+            pub fn #method_name(mut self, value: #param_type) -> Self {
                 self.map.insert(self.path(#field_name), #conversion);
                 self
             }
         })
     } else {
         // Complex type - closure-based method
-        let inner_builder_name = format_ident!("{}SuggestBuilder", type_name);
+        let inner_builder_name = quote! { <#ty as elicitor::SuggestFor>::Builder };
 
         Ok(quote! {
             /// Suggest values for this nested field
-            pub fn #method_name<F>(self, f: F) -> Self
+            pub fn #method_name<F>(mut self, f: F) -> Self
             where
-                F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                F: FnOnce(#inner_builder_name) -> #inner_builder_name,
             {
-                let builder = #inner_builder_name::new(self.map, self.path(#field_name));
-                f(builder);
+                let builder = <#ty as elicitor::SuggestFor>::suggest_builder(
+                    std::mem::take(&mut self.map),
+                    self.path(#field_name),
+                );
+                self.map = <#ty as elicitor::SuggestFor>::into_suggestions(f(builder));
                 self
             }
         })
@@ -1983,12 +3836,13 @@ fn generate_option_suggest_method(field_name: &str, inner_ty: &Type) -> syn::Res
 
     Ok(quote! {
         /// Suggest a value for this optional field
-        pub fn #method_name<F>(self, f: F) -> Self
+        pub fn #method_name<F>(mut self, f: F) -> Self
         where
-            F: FnOnce(#option_builder_name<'_>) -> #option_builder_name<'_>,
+            F: FnOnce(#option_builder_name) -> #option_builder_name,
         {
-            let builder = #option_builder_name::new(self.map, self.path(#field_name));
-            f(builder);
+            let builder =
+                #option_builder_name::new(std::mem::take(&mut self.map), self.path(#field_name));
+            self.map = f(builder).map;
             self
         }
     })
@@ -2018,6 +3872,8 @@ fn generate_option_builder(inner_ty: &Type) -> TokenStream2 {
             | "f32"
             | "f64"
             | "PathBuf"
+            | "Color"
+            | "SecretString"
     );
 
     if is_primitive {
@@ -2047,26 +3903,34 @@ fn generate_option_builder(inner_ty: &Type) -> TokenStream2 {
                 quote! { impl Into<std::path::PathBuf> },
                 quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
             ),
+            "Color" => (
+                quote! { elicitor::Color },
+                quote! { elicitor::ResponseValue::String(value.to_hex()) },
+            ),
+            "SecretString" => (
+                quote! { elicitor::SecretString },
+                quote! { elicitor::ResponseValue::String(elicitor::secrecy::ExposeSecret::expose_secret(&value).to_string()) },
+            ),
             _ => unreachable!(),
         };
 
         quote! {
             /// Builder for suggesting Option<T> values
-            pub struct #option_builder_name<'a> {
-                map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+            pub struct #option_builder_name {
+                map: std::collections::HashMap<String, elicitor::ResponseValue>,
                 prefix: String,
             }
 
-            impl<'a> #option_builder_name<'a> {
+            impl #option_builder_name {
                 fn new(
-                    map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+                    map: std::collections::HashMap<String, elicitor::ResponseValue>,
                     prefix: String,
                 ) -> Self {
                     Self { map, prefix }
                 }
 
                 /// Suggest None (leave empty/skip this field)
-                pub fn none(self) -> Self {
+                pub fn none(mut self) -> Self {
                     self.map.insert(
                         format!("{}.is_none", self.prefix),
                         elicitor::ResponseValue::Bool(true),
@@ -2075,7 +3939,7 @@ fn generate_option_builder(inner_ty: &Type) -> TokenStream2 {
                 }
 
                 /// Suggest Some with a value
-                pub fn some(self, value: #some_param) -> Self {
+                pub fn some(mut self, value: #some_param) -> Self {
                     self.map.insert(self.prefix.clone(), #some_conversion);
                     self
                 }
@@ -2083,25 +3947,25 @@ fn generate_option_builder(inner_ty: &Type) -> TokenStream2 {
         }
     } else {
         // For complex inner types, generate some(closure) method
-        let inner_builder_name = format_ident!("{}SuggestBuilder", inner_type_name);
+        let inner_builder_name = quote! { <#inner_ty as elicitor::SuggestFor>::Builder };
 
         quote! {
             /// Builder for suggesting Option<T> values
-            pub struct #option_builder_name<'a> {
-                map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+            pub struct #option_builder_name {
+                map: std::collections::HashMap<String, elicitor::ResponseValue>,
                 prefix: String,
             }
 
-            impl<'a> #option_builder_name<'a> {
+            impl #option_builder_name {
                 fn new(
-                    map: &'a mut std::collections::HashMap<String, elicitor::ResponseValue>,
+                    map: std::collections::HashMap<String, elicitor::ResponseValue>,
                     prefix: String,
                 ) -> Self {
                     Self { map, prefix }
                 }
 
                 /// Suggest None (leave empty/skip this field)
-                pub fn none(self) -> Self {
+                pub fn none(mut self) -> Self {
                     self.map.insert(
                         format!("{}.is_none", self.prefix),
                         elicitor::ResponseValue::Bool(true),
@@ -2110,12 +3974,15 @@ fn generate_option_builder(inner_ty: &Type) -> TokenStream2 {
                 }
 
                 /// Suggest Some with nested values
-                pub fn some<F>(self, f: F) -> Self
+                pub fn some<F>(mut self, f: F) -> Self
                 where
-                    F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                    F: FnOnce(#inner_builder_name) -> #inner_builder_name,
                 {
-                    let builder = #inner_builder_name::new(self.map, self.prefix.clone());
-                    f(builder);
+                    let builder = <#inner_ty as elicitor::SuggestFor>::suggest_builder(
+                        std::mem::take(&mut self.map),
+                        self.prefix.clone(),
+                    );
+                    self.map = <#inner_ty as elicitor::SuggestFor>::into_suggestions(f(builder));
                     self
                 }
             }
@@ -2253,7 +4120,7 @@ fn generate_builder_methods_for_fields(
 ) -> syn::Result<()> {
     match fields {
         Fields::Named(fields) => {
-            for field in &fields.named {
+            for field in fields.named.iter().filter(|field| !is_replaced(field)) {
                 let field_name = field.ident.as_ref().unwrap();
                 let field_name_str = field_name.to_string();
                 let ty = &field.ty;
@@ -2322,8 +4189,8 @@ fn generate_suggest_assume_methods(
 
     let type_name = type_to_string(ty);
 
-    // Skip Vec<T> types - they use multiselect and don't have a simple suggest pattern
-    if extract_vec_inner_type(ty).is_some() {
+    // Skip Vec<T> and map types - they don't have a simple suggest pattern
+    if extract_vec_inner_type(ty).is_some() || extract_map_types(ty).is_some() {
         return;
     }
 
@@ -2337,10 +4204,13 @@ fn generate_suggest_assume_methods(
             /// Suggest a value for this optional field (user can modify)
             pub fn #suggest_name<F>(mut self, f: F) -> Self
             where
-                F: FnOnce(#option_builder_name<'_>) -> #option_builder_name<'_>,
+                F: FnOnce(#option_builder_name) -> #option_builder_name,
             {
-                let builder = #option_builder_name::new(&mut self.suggestions, #path_key.to_string());
-                f(builder);
+                let builder = #option_builder_name::new(
+                    std::mem::take(&mut self.suggestions),
+                    #path_key.to_string(),
+                );
+                self.suggestions = f(builder).map;
                 self
             }
         });
@@ -2349,10 +4219,13 @@ fn generate_suggest_assume_methods(
             /// Assume a value for this optional field (question is skipped)
             pub fn #assume_name<F>(mut self, f: F) -> Self
             where
-                F: FnOnce(#option_builder_name<'_>) -> #option_builder_name<'_>,
+                F: FnOnce(#option_builder_name) -> #option_builder_name,
             {
-                let builder = #option_builder_name::new(&mut self.assumptions, #path_key.to_string());
-                f(builder);
+                let builder = #option_builder_name::new(
+                    std::mem::take(&mut self.assumptions),
+                    #path_key.to_string(),
+                );
+                self.assumptions = f(builder).map;
                 self
             }
         });
@@ -2387,6 +4260,16 @@ fn generate_suggest_assume_methods(
                 quote! { elicitor::ResponseValue::String(value.into().to_string_lossy().into_owned()) },
             ),
         ),
+        "Color" => (
+            Some(quote! { elicitor::Color }),
+            Some(quote! { elicitor::ResponseValue::String(value.to_hex()) }),
+        ),
+        "SecretString" => (
+            Some(quote! { elicitor::SecretString }),
+            Some(
+                quote! { elicitor::ResponseValue::String(elicitor::secrecy::ExposeSecret::expose_secret(&value).to_string()) },
+            ),
+        ),
         _ => (None, None), // Complex type
     };
 
@@ -2409,16 +4292,19 @@ fn generate_suggest_assume_methods(
         });
     } else {
         // Complex type - closure-based methods
-        let inner_builder_name = format_ident!("{}SuggestBuilder", type_name);
+        let inner_builder_name = quote! { <#ty as elicitor::SuggestFor>::Builder };
 
         suggest_methods.push(quote! {
             /// Suggest values for this nested field (user can modify)
             pub fn #suggest_name<F>(mut self, f: F) -> Self
             where
-                F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                F: FnOnce(#inner_builder_name) -> #inner_builder_name,
             {
-                let builder = #inner_builder_name::new(&mut self.suggestions, #path_key.to_string());
-                f(builder);
+                let builder = <#ty as elicitor::SuggestFor>::suggest_builder(
+                    std::mem::take(&mut self.suggestions),
+                    #path_key.to_string(),
+                );
+                self.suggestions = <#ty as elicitor::SuggestFor>::into_suggestions(f(builder));
                 self
             }
         });
@@ -2427,10 +4313,13 @@ fn generate_suggest_assume_methods(
             /// Assume values for this nested field (questions are skipped)
             pub fn #assume_name<F>(mut self, f: F) -> Self
             where
-                F: FnOnce(#inner_builder_name<'_>) -> #inner_builder_name<'_>,
+                F: FnOnce(#inner_builder_name) -> #inner_builder_name,
             {
-                let builder = #inner_builder_name::new(&mut self.assumptions, #path_key.to_string());
-                f(builder);
+                let builder = <#ty as elicitor::SuggestFor>::suggest_builder(
+                    std::mem::take(&mut self.assumptions),
+                    #path_key.to_string(),
+                );
+                self.assumptions = <#ty as elicitor::SuggestFor>::into_suggestions(f(builder));
                 self
             }
         });
@@ -2444,12 +4333,34 @@ fn generate_with_suggestions_struct(data: &syn::DataStruct) -> TokenStream2 {
             let insertions: Vec<_> = fields
                 .named
                 .iter()
+                .filter(|f| !is_replaced(f))
                 .filter_map(|f| {
                     let field_name = f.ident.as_ref()?;
                     let field_name_str = field_name.to_string();
                     let ty = &f.ty;
                     let type_name = type_to_string(ty);
 
+                    // Multi-select: the chosen variants are preselected
+                    if FieldAttrs::extract(&f.attrs).is_ok_and(|attrs| attrs.multiselect) {
+                        return Some(quote! {
+                            let chosen: Vec<usize> = instance
+                                .#field_name
+                                .iter()
+                                .map(|item| {
+                                    elicitor::Survey::to_responses(item)
+                                        .get_chosen_variant(&elicitor::ResponsePath::new(
+                                            elicitor::SELECTED_VARIANT_KEY,
+                                        ))
+                                        .expect("multi-select items are enums")
+                                })
+                                .collect();
+                            self.suggestions.insert(
+                                format!("{}.{}", #field_name_str, elicitor::SELECTED_VARIANTS_KEY),
+                                elicitor::ResponseValue::ChosenVariants(chosen),
+                            );
+                        });
+                    }
+
                     // Only handle primitive types directly
                     match type_name.as_str() {
                         "String" => Some(quote! {
@@ -2488,6 +4399,12 @@ fn generate_with_suggestions_struct(data: &syn::DataStruct) -> TokenStream2 {
                                 elicitor::ResponseValue::String(instance.#field_name.display().to_string())
                             );
                         }),
+                        "Color" => Some(quote! {
+                            self.suggestions.insert(
+                                #field_name_str.to_string(),
+                                elicitor::ResponseValue::String(instance.#field_name.to_hex())
+                            );
+                        }),
                         _ => None, // Skip complex types
                     }
                 })
@@ -2549,6 +4466,38 @@ fn generate_with_suggestions_struct(data: &syn::DataStruct) -> TokenStream2 {
 }
 
 /// Generate with_suggestions body based on the input type
+/// Generate `edit_<field>(instance)` builder methods, shortcuts for `edit_field` with the
+/// field's path.
+fn generate_edit_methods(input: &DeriveInput) -> Vec<TokenStream2> {
+    let Data::Struct(syn::DataStruct {
+        fields: Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Vec::new();
+    };
+    let name = &input.ident;
+    fields
+        .named
+        .iter()
+        .filter(|f| !is_replaced(f))
+        .filter_map(|f| f.ident.as_ref())
+        // A field called `field` would shadow `edit_field` itself
+        .filter(|ident| *ident != "field")
+        .map(|ident| {
+            let field_name = ident.to_string();
+            let method = format_ident!("edit_{}", field_name);
+            let doc = format!("Ask only `{field_name}` again, see `edit_field`");
+            quote! {
+                #[doc = #doc]
+                pub fn #method(self, instance: &#name) -> Self {
+                    self.edit_field(instance, #field_name)
+                }
+            }
+        })
+        .collect()
+}
+
 fn generate_with_suggestions_body(input: &DeriveInput) -> TokenStream2 {
     match &input.data {
         Data::Struct(data) => generate_with_suggestions_struct(data),
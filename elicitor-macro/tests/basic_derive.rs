@@ -1,12 +1,8 @@
 //! Basic tests for the Survey derive macro
 
-// We need to create a fake elicit module for the macro to work
-mod elicit {
-    pub use elicitor_types::*;
-}
-
-use elicitor::Survey; // Import the trait
-use elicitor_macro::Survey;
+// The generated code refers to `elicitor::*`, so the facade crate provides
+// both the derive macro and the trait here.
+use elicitor::Survey;
 
 #[derive(Survey, Debug, PartialEq)]
 struct SimpleStruct {
@@ -50,7 +46,7 @@ fn simple_struct_from_responses() {
 
     assert_eq!(result.name, "Alice");
     assert_eq!(result.age, 25);
-    assert_eq!(result.student, true);
+    assert!(result.student);
 }
 
 #[test]
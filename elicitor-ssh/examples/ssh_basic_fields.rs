@@ -0,0 +1,23 @@
+//! Basic field types over SSH
+//!
+//! Serves the wizard on port 2222 with a throwaway host key.
+//! Connect with `ssh -t -p 2222 operator@localhost`, password `elicitor`.
+//!
+//! Run with: cargo run -p elicitor-ssh --example ssh_basic_fields
+
+use elicitor::Survey;
+use elicitor_ssh::{SshWizardServer, WizardWidget};
+use example_surveys::BasicFields;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    println!("Serving the wizard on port 2222, log in as operator/elicitor");
+    SshWizardServer::new(|| WizardWidget::for_survey::<BasicFields>().with_title("Basic Fields"))
+        .with_password("operator", "elicitor")
+        .with_completion(|user, responses| {
+            println!("{user}: {:#?}", BasicFields::from_responses(&responses));
+        })
+        .run("127.0.0.1:2222")
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,364 @@
+//! Key presses from the bytes a remote terminal sends.
+//!
+//! Locally crossterm reads the terminal itself, over SSH the keys arrive as the
+//! raw bytes of the client's terminal: plain text, control characters and
+//! escape sequences.
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+const ESC: u8 = 0x1b;
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// Longest escape sequence kept while waiting for its end, longer ones are dropped.
+const MAX_SEQUENCE_LEN: usize = 64;
+
+/// Most bytes of a bracketed paste held at once, longer pastes arrive in pieces.
+const MAX_PASTE_LEN: usize = 64 * 1024;
+
+/// Turns the bytes of a remote terminal into events, keeping incomplete
+/// sequences until the rest arrives.
+///
+/// Both are capped, so a client cannot grow them without end: an escape sequence
+/// longer than [`MAX_SEQUENCE_LEN`] is dropped, and a paste is handed over in events
+/// of at most [`MAX_PASTE_LEN`] bytes.
+#[derive(Debug, Default)]
+pub(crate) struct InputParser {
+    pending: Vec<u8>,
+    /// Text of a bracketed paste that has not ended yet.
+    paste: Option<Vec<u8>>,
+}
+
+/// What the start of the pending bytes is.
+enum Parsed {
+    /// An event, and the number of bytes it took.
+    Event(Event, usize),
+    /// Bytes without a meaning for the wizard.
+    Skip(usize),
+    /// The start of a bracketed paste, and the length of its marker.
+    PasteStart(usize),
+    /// An incomplete sequence.
+    Incomplete,
+}
+
+impl InputParser {
+    /// The events in `bytes`, following those received before.
+    pub(crate) fn feed(&mut self, bytes: &[u8]) -> Vec<Event> {
+        self.pending.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        let mut start = 0;
+        while start < self.pending.len() {
+            let rest = &self.pending[start..];
+            if let Some(paste) = &mut self.paste {
+                match find(rest, PASTE_END) {
+                    Some(end) => {
+                        paste.extend_from_slice(&rest[..end]);
+                        let text = String::from_utf8_lossy(paste).into_owned();
+                        events.push(Event::Paste(text));
+                        self.paste = None;
+                        start += end + PASTE_END.len();
+                    }
+                    None => {
+                        // Keep what could be the start of the end marker
+                        let keep = (1..PASTE_END.len())
+                            .rev()
+                            .find(|&n| rest.ends_with(&PASTE_END[..n]))
+                            .unwrap_or(0);
+                        paste.extend_from_slice(&rest[..rest.len() - keep]);
+                        if paste.len() >= MAX_PASTE_LEN {
+                            events.push(Event::Paste(take_complete_text(paste)));
+                        }
+                        start = self.pending.len() - keep;
+                        break;
+                    }
+                }
+                continue;
+            }
+            match parse(rest) {
+                Parsed::Event(event, len) => {
+                    events.push(event);
+                    start += len;
+                }
+                Parsed::Skip(len) => start += len,
+                Parsed::PasteStart(len) => {
+                    self.paste = Some(Vec::new());
+                    start += len;
+                }
+                Parsed::Incomplete => {
+                    if rest.len() > MAX_SEQUENCE_LEN {
+                        start = self.pending.len();
+                    }
+                    break;
+                }
+            }
+        }
+        self.pending.drain(..start);
+        events
+    }
+}
+
+/// Take the text of `bytes` up to the last complete character, leaving the start
+/// of a character split by the end.
+fn take_complete_text(bytes: &mut Vec<u8>) -> String {
+    let complete = match std::str::from_utf8(bytes) {
+        Err(err) if err.error_len().is_none() => err.valid_up_to(),
+        _ => bytes.len(),
+    };
+    let rest = bytes.split_off(complete);
+    String::from_utf8_lossy(&std::mem::replace(bytes, rest)).into_owned()
+}
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+    Event::Key(KeyEvent::new(code, modifiers))
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn parse(bytes: &[u8]) -> Parsed {
+    let none = KeyModifiers::NONE;
+    match bytes[0] {
+        ESC => match bytes.get(1) {
+            // A lone Esc, since sequences arrive in one piece
+            None => Parsed::Event(key(KeyCode::Esc, none), 1),
+            Some(b'[') => parse_csi(bytes),
+            Some(b'O') => match bytes.get(2) {
+                None => Parsed::Incomplete,
+                Some(&b) => match cursor_key(b) {
+                    Some(code) => Parsed::Event(key(code, none), 3),
+                    None => Parsed::Skip(3),
+                },
+            },
+            Some(&ESC) => Parsed::Event(key(KeyCode::Esc, none), 1),
+            Some(_) => match parse(&bytes[1..]) {
+                Parsed::Event(Event::Key(mut event), len) => {
+                    event.modifiers |= KeyModifiers::ALT;
+                    Parsed::Event(Event::Key(event), len + 1)
+                }
+                Parsed::Incomplete => Parsed::Incomplete,
+                _ => Parsed::Event(key(KeyCode::Esc, none), 1),
+            },
+        },
+        b'\r' if bytes.get(1) == Some(&b'\n') => Parsed::Event(key(KeyCode::Enter, none), 2),
+        b'\r' | b'\n' => Parsed::Event(key(KeyCode::Enter, none), 1),
+        b'\t' => Parsed::Event(key(KeyCode::Tab, none), 1),
+        0x7f | 0x08 => Parsed::Event(key(KeyCode::Backspace, none), 1),
+        0x00 => Parsed::Event(key(KeyCode::Char(' '), KeyModifiers::CONTROL), 1),
+        b @ 0x01..=0x1a => {
+            let c = (b - 1 + b'a') as char;
+            Parsed::Event(key(KeyCode::Char(c), KeyModifiers::CONTROL), 1)
+        }
+        0x1c..=0x1f => Parsed::Skip(1),
+        _ => parse_char(bytes),
+    }
+}
+
+/// A UTF-8 character typed or shifted.
+fn parse_char(bytes: &[u8]) -> Parsed {
+    let len = match bytes[0] {
+        0x00..=0x7f => 1,
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Parsed::Skip(1),
+    };
+    if bytes.len() < len {
+        return Parsed::Incomplete;
+    }
+    match std::str::from_utf8(&bytes[..len])
+        .ok()
+        .and_then(|s| s.chars().next())
+    {
+        Some(c) => {
+            let modifiers = if c.is_uppercase() {
+                KeyModifiers::SHIFT
+            } else {
+                KeyModifiers::NONE
+            };
+            Parsed::Event(key(KeyCode::Char(c), modifiers), len)
+        }
+        None => Parsed::Skip(1),
+    }
+}
+
+/// A control sequence: `ESC [`, numeric parameters and a final byte.
+fn parse_csi(bytes: &[u8]) -> Parsed {
+    let Some(end) = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b)) else {
+        return Parsed::Incomplete;
+    };
+    let len = end + 3;
+    let params: Vec<u16> = std::str::from_utf8(&bytes[2..len - 1])
+        .unwrap_or_default()
+        .split(';')
+        .map(|p| p.parse().unwrap_or(1))
+        .collect();
+    let modifiers = params.get(1).map_or(KeyModifiers::NONE, |&m| modifiers(m));
+
+    let code = match bytes[len - 1] {
+        b'~' => match params[0] {
+            200 => return Parsed::PasteStart(len),
+            1 | 7 => Some(KeyCode::Home),
+            2 => Some(KeyCode::Insert),
+            3 => Some(KeyCode::Delete),
+            4 | 8 => Some(KeyCode::End),
+            5 => Some(KeyCode::PageUp),
+            6 => Some(KeyCode::PageDown),
+            n @ 11..=15 => Some(KeyCode::F((n - 10) as u8)),
+            n @ 17..=21 => Some(KeyCode::F((n - 11) as u8)),
+            n @ 23..=24 => Some(KeyCode::F((n - 12) as u8)),
+            _ => None,
+        },
+        b'Z' => return Parsed::Event(key(KeyCode::BackTab, KeyModifiers::SHIFT), len),
+        b => cursor_key(b),
+    };
+    match code {
+        Some(code) => Parsed::Event(key(code, modifiers), len),
+        None => Parsed::Skip(len),
+    }
+}
+
+/// The key of the final byte of a cursor key sequence.
+fn cursor_key(b: u8) -> Option<KeyCode> {
+    match b {
+        b'A' => Some(KeyCode::Up),
+        b'B' => Some(KeyCode::Down),
+        b'C' => Some(KeyCode::Right),
+        b'D' => Some(KeyCode::Left),
+        b'H' => Some(KeyCode::Home),
+        b'F' => Some(KeyCode::End),
+        b'P'..=b'S' => Some(KeyCode::F(b - b'P' + 1)),
+        _ => None,
+    }
+}
+
+/// The modifiers of a sequence parameter: one more than the bits Shift, Alt and Ctrl.
+fn modifiers(param: u16) -> KeyModifiers {
+    let bits = param.saturating_sub(1);
+    let mut modifiers = KeyModifiers::NONE;
+    if bits & 1 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if bits & 2 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if bits & 4 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(events: Vec<Event>) -> Vec<(KeyCode, KeyModifiers)> {
+        events
+            .into_iter()
+            .map(|event| match event {
+                Event::Key(key) => (key.code, key.modifiers),
+                other => panic!("expected a key, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn terminal_bytes_become_keys() {
+        let mut parser = InputParser::default();
+        let none = KeyModifiers::NONE;
+        assert_eq!(
+            keys(parser.feed("Aé\r\x7f\x03".as_bytes())),
+            [
+                (KeyCode::Char('A'), KeyModifiers::SHIFT),
+                (KeyCode::Char('é'), none),
+                (KeyCode::Enter, none),
+                (KeyCode::Backspace, none),
+                (KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ]
+        );
+        assert_eq!(
+            keys(parser.feed(b"\x1b[A\x1bOB\x1b[5~\x1b[1;5D\x1bOQ")),
+            [
+                (KeyCode::Up, none),
+                (KeyCode::Down, none),
+                (KeyCode::PageUp, none),
+                (KeyCode::Left, KeyModifiers::CONTROL),
+                (KeyCode::F(2), none),
+            ]
+        );
+        assert_eq!(keys(parser.feed(b"\x1b")), [(KeyCode::Esc, none)]);
+    }
+
+    #[test]
+    fn split_sequences_wait_for_the_rest() {
+        let mut parser = InputParser::default();
+        assert!(parser.feed(b"\x1b[1;").is_empty());
+        assert_eq!(
+            keys(parser.feed(b"2C")),
+            [(KeyCode::Right, KeyModifiers::SHIFT)]
+        );
+        assert!(parser.feed(&"ö".as_bytes()[..1]).is_empty());
+        assert_eq!(
+            keys(parser.feed(&"ö".as_bytes()[1..])),
+            [(KeyCode::Char('ö'), KeyModifiers::NONE)]
+        );
+    }
+
+    #[test]
+    fn overlong_sequences_are_dropped() {
+        let mut parser = InputParser::default();
+        let mut sequence = b"\x1b[".to_vec();
+        sequence.resize(MAX_SEQUENCE_LEN + 1, b'1');
+        assert!(parser.feed(&sequence).is_empty());
+        assert!(parser.pending.is_empty());
+
+        // The digits that follow are typed, not part of the dropped sequence
+        assert_eq!(
+            keys(parser.feed(b"1A")),
+            [
+                (KeyCode::Char('1'), KeyModifiers::NONE),
+                (KeyCode::Char('A'), KeyModifiers::SHIFT),
+            ]
+        );
+    }
+
+    #[test]
+    fn long_pastes_arrive_in_pieces() {
+        let mut parser = InputParser::default();
+        assert!(parser.feed(b"\x1b[200~").is_empty());
+        let mut text = "a".repeat(MAX_PASTE_LEN - 1).into_bytes();
+        text.extend_from_slice("ü".as_bytes());
+        text.push(b'b');
+
+        // The ü is split by the cap, and carried over to the next piece
+        let events = parser.feed(&text[..MAX_PASTE_LEN]);
+        assert_eq!(events, [Event::Paste("a".repeat(MAX_PASTE_LEN - 1))]);
+        let events = parser.feed(&text[MAX_PASTE_LEN..]);
+        assert!(events.is_empty());
+        let events = parser.feed(b"\x1b[201~x");
+        assert_eq!(
+            events,
+            [
+                Event::Paste("üb".to_string()),
+                key(KeyCode::Char('x'), KeyModifiers::NONE),
+            ]
+        );
+    }
+
+    #[test]
+    fn bracketed_paste_is_one_event() {
+        let mut parser = InputParser::default();
+        assert!(parser.feed(b"\x1b[200~line one\r\nline").is_empty());
+        let events = parser.feed(b" two\x1b[20");
+        assert!(events.is_empty());
+        let events = parser.feed(b"1~x");
+        assert_eq!(
+            events,
+            [
+                Event::Paste("line one\r\nline two".to_string()),
+                key(KeyCode::Char('x'), KeyModifiers::NONE),
+            ]
+        );
+    }
+}
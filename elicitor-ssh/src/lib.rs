@@ -0,0 +1,46 @@
+//! # elicitor-ssh
+//!
+//! Serve the ratatui wizard of elicitor over SSH.
+//!
+//! One binary can let remote operators run its configuration wizards: they connect
+//! with a plain `ssh -t` client, get the same wizard as a local terminal, and the
+//! answers come back to the server. Nothing has to be installed on their machines.
+//!
+//! ## Features
+//!
+//! - A fresh [`WizardWidget`] for every session, drawn in the operator's terminal
+//! - A limit on the sessions served at once, and on the input each one buffers
+//! - Password and public key authentication, nobody is let in by default
+//! - Resizing, bracketed paste and the wizard's key bindings over the connection
+//! - Completed answers handed to a callback together with the user name
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use elicitor::Survey;
+//! use elicitor_ssh::{PrivateKey, SshWizardServer};
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     SshWizardServer::for_survey::<DeploymentConfig>()
+//!         .with_host_key(PrivateKey::read_openssh_file("host_key".as_ref())?)
+//!         .with_password("operator", "secret")
+//!         .with_completion(|user, responses| {
+//!             let config = DeploymentConfig::from_responses(&responses);
+//!             println!("{user} configured {config:?}");
+//!         })
+//!         .run("0.0.0.0:2222")
+//!         .await?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Operators then run `ssh -t -p 2222 operator@server`.
+
+mod input;
+mod server;
+mod session;
+
+pub use elicitor_wizard_ratatui::WizardWidget;
+pub use russh::keys::{PrivateKey, PublicKey};
+pub use server::{SshError, SshWizardServer};
@@ -0,0 +1,596 @@
+//! SSH server handing each operator a wizard.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::SyncSender;
+
+use elicitor::{Responses, Survey};
+use elicitor_wizard_ratatui::WizardWidget;
+use russh::keys::ssh_key::rand_core::OsRng;
+use russh::keys::{Algorithm, PrivateKey, PublicKey};
+use russh::server::{Auth, Config, Handle, Handler, Msg, Server, Session};
+use russh::{Channel, ChannelId, Pty};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::sync::mpsc::Receiver;
+
+use crate::session::{ChannelWriter, Input, Output, run_session};
+
+type WizardFactory = Box<dyn Fn() -> WizardWidget + Send + Sync>;
+type CompletionHandler = Box<dyn Fn(&str, Responses) + Send + Sync>;
+
+/// Sessions served at once unless set with [`SshWizardServer::with_max_sessions`].
+const DEFAULT_MAX_SESSIONS: usize = 32;
+
+/// Messages queued between a session and its channel, in each direction.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Error type for the SSH server.
+#[derive(Debug, Error)]
+pub enum SshError {
+    /// The SSH protocol failed.
+    #[error("SSH error: {0}")]
+    Ssh(#[from] russh::Error),
+
+    /// Generating the host key failed.
+    #[error("Host key error: {0}")]
+    HostKey(#[from] russh::keys::ssh_key::Error),
+
+    /// Listening for connections failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Settings shared by all connections.
+struct Shared {
+    wizard: WizardFactory,
+    completion: Option<CompletionHandler>,
+    passwords: HashMap<String, String>,
+    authorized_keys: Vec<(String, PublicKey)>,
+    max_sessions: usize,
+    /// Sessions running now, over all connections.
+    sessions: AtomicUsize,
+}
+
+/// A place among the sessions running at once, given back when dropped.
+struct SessionSlot(Arc<Shared>);
+
+impl SessionSlot {
+    /// Take a place if fewer than the maximum number of sessions are running.
+    fn take(shared: &Arc<Shared>) -> Option<Self> {
+        shared
+            .sessions
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                (running < shared.max_sessions).then_some(running + 1)
+            })
+            .ok()
+            .map(|_| Self(shared.clone()))
+    }
+}
+
+impl Drop for SessionSlot {
+    fn drop(&mut self) {
+        self.0.sessions.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// SSH server running a ratatui wizard for every operator who logs in.
+///
+/// Each SSH session with a terminal (`ssh -t`) gets a fresh wizard on a thread of
+/// its own, up to [`with_max_sessions`](Self::with_max_sessions) at once. Nobody can log
+/// in until passwords or public keys are allowed with
+/// [`with_password`](Self::with_password) or
+/// [`with_authorized_key`](Self::with_authorized_key).
+///
+/// ```rust,ignore
+/// SshWizardServer::for_survey::<Config>()
+///     .with_host_key(PrivateKey::read_openssh_file("host_key".as_ref())?)
+///     .with_password("operator", "secret")
+///     .with_completion(|user, responses| save(user, Config::from_responses(&responses)))
+///     .run("0.0.0.0:2222")
+///     .await?;
+/// ```
+pub struct SshWizardServer {
+    shared: Shared,
+    host_keys: Vec<PrivateKey>,
+}
+
+impl std::fmt::Debug for SshWizardServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SshWizardServer")
+            .field("users", &self.shared.passwords.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SshWizardServer {
+    /// Serve the wizards `wizard` creates, one per session.
+    pub fn new(wizard: impl Fn() -> WizardWidget + Send + Sync + 'static) -> Self {
+        Self {
+            shared: Shared {
+                wizard: Box::new(wizard),
+                completion: None,
+                passwords: HashMap::new(),
+                authorized_keys: Vec::new(),
+                max_sessions: DEFAULT_MAX_SESSIONS,
+                sessions: AtomicUsize::new(0),
+            },
+            host_keys: Vec::new(),
+        }
+    }
+
+    /// Serve the wizard of a `#[derive(Survey)]` type.
+    pub fn for_survey<T: Survey + 'static>() -> Self {
+        Self::new(WizardWidget::for_survey::<T>)
+    }
+
+    /// Identify the server with `key`.
+    ///
+    /// Without a host key, the server makes up a new one each time it starts, and
+    /// clients warn that it has changed.
+    pub fn with_host_key(mut self, key: PrivateKey) -> Self {
+        self.host_keys.push(key);
+        self
+    }
+
+    /// Let `user` log in with `password`.
+    pub fn with_password(mut self, user: impl Into<String>, password: impl Into<String>) -> Self {
+        self.shared.passwords.insert(user.into(), password.into());
+        self
+    }
+
+    /// Let `user` log in with the private key of `key`.
+    pub fn with_authorized_key(mut self, user: impl Into<String>, key: PublicKey) -> Self {
+        self.shared.authorized_keys.push((user.into(), key));
+        self
+    }
+
+    /// Serve at most `max` sessions at once, 32 by default. Operators starting
+    /// another one are told to try again later.
+    pub fn with_max_sessions(mut self, max: usize) -> Self {
+        self.shared.max_sessions = max;
+        self
+    }
+
+    /// Call `handler` with the user name and the answers of each completed wizard.
+    ///
+    /// Answers of operators who cancel or disconnect are dropped. The handler runs
+    /// on the session's own thread, so it may block.
+    pub fn with_completion(
+        mut self,
+        handler: impl Fn(&str, Responses) + Send + Sync + 'static,
+    ) -> Self {
+        self.shared.completion = Some(Box::new(handler));
+        self
+    }
+
+    /// Listen on `addr` and serve wizards until an error occurs.
+    pub async fn run(self, addr: impl ToSocketAddrs) -> Result<(), SshError> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(&listener).await
+    }
+
+    /// Serve wizards to the connections of `listener`.
+    pub async fn serve(mut self, listener: &TcpListener) -> Result<(), SshError> {
+        if self.host_keys.is_empty() {
+            self.host_keys
+                .push(PrivateKey::random(&mut OsRng, Algorithm::Ed25519)?);
+        }
+        let config = Config {
+            keys: self.host_keys,
+            nodelay: true,
+            ..Default::default()
+        };
+        let mut acceptor = Acceptor {
+            shared: Arc::new(self.shared),
+        };
+        acceptor.run_on_socket(Arc::new(config), listener).await?;
+        Ok(())
+    }
+}
+
+/// Creates the handler of each connection.
+struct Acceptor {
+    shared: Arc<Shared>,
+}
+
+impl Server for Acceptor {
+    type Handler = Connection;
+
+    fn new_client(&mut self, _: Option<std::net::SocketAddr>) -> Connection {
+        Connection {
+            shared: self.shared.clone(),
+            user: String::new(),
+            channels: HashMap::new(),
+        }
+    }
+}
+
+/// A session channel of a connection.
+#[derive(Default)]
+struct ChannelState {
+    /// Size of the terminal, once the client asked for one.
+    size: Option<(u16, u16)>,
+    /// Input of the running wizard.
+    input: Option<SyncSender<Input>>,
+}
+
+/// One client connection, possibly with several sessions.
+struct Connection {
+    shared: Arc<Shared>,
+    /// The user who logged in.
+    user: String,
+    channels: HashMap<ChannelId, ChannelState>,
+}
+
+impl Connection {
+    /// Start the wizard of `channel` on its own thread, which holds `slot` until
+    /// the wizard ends.
+    fn start_wizard(
+        &mut self,
+        channel: ChannelId,
+        size: (u16, u16),
+        handle: Handle,
+        slot: SessionSlot,
+    ) {
+        let (input, inbox) = std::sync::mpsc::sync_channel(CHANNEL_CAPACITY);
+        let (outbox, output) = tokio::sync::mpsc::channel(CHANNEL_CAPACITY);
+        tokio::spawn(forward(handle, channel, output));
+
+        let shared = self.shared.clone();
+        let user = self.user.clone();
+        std::thread::spawn(move || {
+            let _slot = slot;
+            let wizard = (shared.wizard)();
+            let writer = ChannelWriter::new(outbox.clone());
+            let status = match run_session(wizard, size, inbox, writer) {
+                Ok(Some(responses)) => {
+                    if let Some(completion) = &shared.completion {
+                        completion(&user, responses);
+                    }
+                    0
+                }
+                Ok(None) | Err(_) => 1,
+            };
+            let _ = outbox.blocking_send(Output::Exit(status));
+        });
+
+        if let Some(state) = self.channels.get_mut(&channel) {
+            state.input = Some(input);
+        }
+    }
+}
+
+/// Send the output of a wizard to its channel, and close the channel at the end.
+async fn forward(handle: Handle, channel: ChannelId, mut output: Receiver<Output>) {
+    while let Some(output) = output.recv().await {
+        match output {
+            Output::Data(data) => {
+                if handle.data(channel, data.into()).await.is_err() {
+                    break;
+                }
+            }
+            Output::Exit(status) => {
+                let _ = handle.exit_status_request(channel, status).await;
+                let _ = handle.eof(channel).await;
+                let _ = handle.close(channel).await;
+                break;
+            }
+        }
+    }
+}
+
+fn terminal_size(col_width: u32, row_height: u32) -> (u16, u16) {
+    let clamp = |n: u32| n.clamp(1, u16::MAX as u32) as u16;
+    (clamp(col_width), clamp(row_height))
+}
+
+impl Handler for Connection {
+    type Error = SshError;
+
+    async fn auth_password(&mut self, user: &str, password: &str) -> Result<Auth, Self::Error> {
+        // Compared in constant time, so response times do not give away the password
+        let matches = self
+            .shared
+            .passwords
+            .get(user)
+            .is_some_and(|expected| bool::from(expected.as_bytes().ct_eq(password.as_bytes())));
+        if matches {
+            self.user = user.to_string();
+            return Ok(Auth::Accept);
+        }
+        Ok(Auth::reject())
+    }
+
+    async fn auth_publickey(&mut self, user: &str, key: &PublicKey) -> Result<Auth, Self::Error> {
+        let authorized = self
+            .shared
+            .authorized_keys
+            .iter()
+            .any(|(name, authorized)| name == user && authorized.key_data() == key.key_data());
+        if authorized {
+            self.user = user.to_string();
+            return Ok(Auth::Accept);
+        }
+        Ok(Auth::reject())
+    }
+
+    async fn channel_open_session(
+        &mut self,
+        channel: Channel<Msg>,
+        _: &mut Session,
+    ) -> Result<bool, Self::Error> {
+        self.channels.insert(channel.id(), ChannelState::default());
+        Ok(true)
+    }
+
+    async fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        _: &str,
+        col_width: u32,
+        row_height: u32,
+        _: u32,
+        _: u32,
+        _: &[(Pty, u32)],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        match self.channels.get_mut(&channel) {
+            Some(state) => {
+                state.size = Some(terminal_size(col_width, row_height));
+                session.channel_success(channel)?;
+            }
+            None => session.channel_failure(channel)?,
+        }
+        Ok(())
+    }
+
+    async fn shell_request(
+        &mut self,
+        channel: ChannelId,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let Some(state) = self.channels.get(&channel) else {
+            session.channel_failure(channel)?;
+            return Ok(());
+        };
+        if state.input.is_some() {
+            // The channel already runs a wizard
+            session.channel_failure(channel)?;
+            return Ok(());
+        }
+        let Some(size) = state.size else {
+            // The wizard needs a terminal to draw in
+            session.channel_failure(channel)?;
+            session.data(
+                channel,
+                "The wizard needs a terminal, connect with `ssh -t`.\r\n"
+                    .as_bytes()
+                    .into(),
+            )?;
+            session.close(channel)?;
+            return Ok(());
+        };
+        let Some(slot) = SessionSlot::take(&self.shared) else {
+            session.channel_failure(channel)?;
+            session.data(
+                channel,
+                "Too many operators are connected, try again later.\r\n"
+                    .as_bytes()
+                    .into(),
+            )?;
+            session.close(channel)?;
+            return Ok(());
+        };
+        session.channel_success(channel)?;
+        self.start_wizard(channel, size, session.handle(), slot);
+        Ok(())
+    }
+
+    async fn window_change_request(
+        &mut self,
+        channel: ChannelId,
+        col_width: u32,
+        row_height: u32,
+        _: u32,
+        _: u32,
+        _: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if let Some(state) = self.channels.get_mut(&channel) {
+            let (width, height) = terminal_size(col_width, row_height);
+            state.size = Some((width, height));
+            if let Some(input) = &state.input {
+                let _ = input.try_send(Input::Resize { width, height });
+            }
+        }
+        Ok(())
+    }
+
+    async fn data(
+        &mut self,
+        channel: ChannelId,
+        data: &[u8],
+        _: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if let Some(input) = self.channels.get(&channel).and_then(|s| s.input.as_ref()) {
+            // Input the wizard cannot keep up with is dropped rather than queued
+            let _ = input.try_send(Input::Data(data.to_vec()));
+        }
+        Ok(())
+    }
+
+    async fn channel_eof(
+        &mut self,
+        channel: ChannelId,
+        _: &mut Session,
+    ) -> Result<(), Self::Error> {
+        // Dropping the input ends the wizard
+        self.channels.remove(&channel);
+        Ok(())
+    }
+
+    async fn channel_close(
+        &mut self,
+        channel: ChannelId,
+        _: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.channels.remove(&channel);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{InputQuestion, Question, QuestionKind, ResponsePath, SurveyDefinition};
+    use russh::ChannelMsg;
+    use russh::client;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    struct TrustingClient;
+
+    impl client::Handler for TrustingClient {
+        type Error = russh::Error;
+
+        async fn check_server_key(&mut self, _: &PublicKey) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+    }
+
+    /// The reply to the last request on `channel`, skipping the output before it.
+    async fn reply(channel: &mut Channel<client::Msg>) -> Option<ChannelMsg> {
+        while let Some(message) = channel.wait().await {
+            if matches!(message, ChannelMsg::Success | ChannelMsg::Failure) {
+                return Some(message);
+            }
+        }
+        None
+    }
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![Question::new(
+            "hostname",
+            "Hostname?",
+            QuestionKind::Input(InputQuestion::new()),
+        )])
+    }
+
+    #[tokio::test]
+    async fn operator_completes_wizard_over_ssh() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let server = SshWizardServer::new(|| WizardWidget::new(&definition()))
+            .with_password("operator", "secret")
+            .with_completion({
+                let completed = completed.clone();
+                move |user, responses| {
+                    let hostname = responses
+                        .get_string(&ResponsePath::new("hostname"))
+                        .unwrap()
+                        .to_string();
+                    completed.lock().unwrap().push((user.to_string(), hostname));
+                }
+            });
+        tokio::spawn(async move { server.serve(&listener).await });
+
+        let session = async {
+            let config = Arc::new(client::Config::default());
+            let mut client = client::connect(config, addr, TrustingClient).await.unwrap();
+            let wrong = client.authenticate_password("operator", "guess").await;
+            assert!(!wrong.unwrap().success());
+            let auth = client.authenticate_password("operator", "secret").await;
+            assert!(auth.unwrap().success());
+
+            let mut channel = client.channel_open_session().await.unwrap();
+            channel
+                .request_pty(false, "xterm", 80, 24, 0, 0, &[])
+                .await
+                .unwrap();
+            channel.request_shell(false).await.unwrap();
+            // Answer, then dismiss the completion screen
+            channel.data(&b"db-01\r"[..]).await.unwrap();
+            channel.data(&b"\r"[..]).await.unwrap();
+
+            let mut screen = Vec::new();
+            let mut exit_status = None;
+            while let Some(message) = channel.wait().await {
+                match message {
+                    ChannelMsg::Data { data } => screen.extend_from_slice(&data),
+                    ChannelMsg::ExitStatus {
+                        exit_status: status,
+                    } => exit_status = Some(status),
+                    _ => {}
+                }
+            }
+            (String::from_utf8_lossy(&screen).into_owned(), exit_status)
+        };
+        let (screen, exit_status) = tokio::time::timeout(Duration::from_secs(10), session)
+            .await
+            .expect("the session ends");
+
+        assert!(screen.contains("Hostname?"));
+        assert_eq!(exit_status, Some(0));
+        assert_eq!(
+            *completed.lock().unwrap(),
+            [("operator".to_string(), "db-01".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn sessions_beyond_the_limit_and_repeated_shells_are_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = SshWizardServer::new(|| WizardWidget::new(&definition()))
+            .with_password("operator", "secret")
+            .with_max_sessions(1);
+        tokio::spawn(async move { server.serve(&listener).await });
+
+        let session = async {
+            let config = Arc::new(client::Config::default());
+            let mut client = client::connect(config, addr, TrustingClient).await.unwrap();
+            let auth = client.authenticate_password("operator", "secret").await;
+            assert!(auth.unwrap().success());
+
+            let mut first = client.channel_open_session().await.unwrap();
+            first
+                .request_pty(true, "xterm", 80, 24, 0, 0, &[])
+                .await
+                .unwrap();
+            assert!(matches!(reply(&mut first).await, Some(ChannelMsg::Success)));
+            first.request_shell(true).await.unwrap();
+            assert!(matches!(reply(&mut first).await, Some(ChannelMsg::Success)));
+            first.request_shell(true).await.unwrap();
+            assert!(matches!(reply(&mut first).await, Some(ChannelMsg::Failure)));
+
+            let mut second = client.channel_open_session().await.unwrap();
+            second
+                .request_pty(true, "xterm", 80, 24, 0, 0, &[])
+                .await
+                .unwrap();
+            assert!(matches!(
+                reply(&mut second).await,
+                Some(ChannelMsg::Success)
+            ));
+            second.request_shell(true).await.unwrap();
+            assert!(matches!(
+                reply(&mut second).await,
+                Some(ChannelMsg::Failure)
+            ));
+            let mut screen = Vec::new();
+            while let Some(message) = second.wait().await {
+                if let ChannelMsg::Data { data } = message {
+                    screen.extend_from_slice(&data);
+                }
+            }
+            String::from_utf8_lossy(&screen).into_owned()
+        };
+        let screen = tokio::time::timeout(Duration::from_secs(10), session)
+            .await
+            .expect("the second session is refused");
+        assert!(screen.contains("try again later"));
+    }
+}
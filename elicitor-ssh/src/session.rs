@@ -0,0 +1,128 @@
+//! One operator's wizard, run on a thread of its own.
+
+use std::io::{self, Write};
+use std::sync::mpsc::Receiver;
+
+use crossterm::event::{DisableBracketedPaste, EnableBracketedPaste, Event};
+use crossterm::execute;
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
+use elicitor::Responses;
+use elicitor_wizard_ratatui::{WizardStatus, WizardWidget};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Rect;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+use tokio::sync::mpsc::Sender;
+
+use crate::input::InputParser;
+
+/// What the SSH connection passes to a session.
+pub(crate) enum Input {
+    /// Bytes the operator's terminal sent.
+    Data(Vec<u8>),
+    /// The operator's terminal has a new size.
+    Resize { width: u16, height: u16 },
+}
+
+/// What a session passes back to the SSH connection.
+pub(crate) enum Output {
+    /// Bytes for the operator's terminal.
+    Data(Vec<u8>),
+    /// The session has ended with an exit status: 0 when completed.
+    Exit(u32),
+}
+
+/// Terminal output collected until flushed, then sent to the SSH channel.
+pub(crate) struct ChannelWriter {
+    buffer: Vec<u8>,
+    output: Sender<Output>,
+}
+
+impl ChannelWriter {
+    pub(crate) fn new(output: Sender<Output>) -> Self {
+        Self {
+            buffer: Vec::new(),
+            output,
+        }
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        // Waits while the channel is behind, the session runs on a thread of its own
+        self.output
+            .blocking_send(Output::Data(std::mem::take(&mut self.buffer)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "SSH channel closed"))
+    }
+}
+
+fn area(width: u16, height: u16) -> Rect {
+    Rect::new(0, 0, width, height)
+}
+
+/// Run `wizard` in a terminal of `width` × `height` written to `output`.
+///
+/// Returns the answers once the operator completes the wizard and dismisses the
+/// completion screen, `None` if they cancel or disconnect before completing.
+pub(crate) fn run_session(
+    mut wizard: WizardWidget,
+    (width, height): (u16, u16),
+    input: Receiver<Input>,
+    output: impl Write,
+) -> io::Result<Option<Responses>> {
+    let options = TerminalOptions {
+        viewport: Viewport::Fixed(area(width, height)),
+    };
+    let mut terminal = Terminal::with_options(CrosstermBackend::new(output), options)?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableBracketedPaste
+    )?;
+    terminal.clear()?;
+
+    let mut parser = InputParser::default();
+    let mut status = WizardStatus::Running;
+    'session: loop {
+        terminal.draw(|frame| wizard.render(frame, frame.area()))?;
+
+        let events = match input.recv() {
+            Ok(Input::Data(bytes)) => parser.feed(&bytes),
+            Ok(Input::Resize { width, height }) => {
+                terminal.resize(area(width, height))?;
+                continue;
+            }
+            // The operator disconnected
+            Err(_) => break,
+        };
+        for event in events {
+            if status == WizardStatus::Completed {
+                // Any key dismisses the completion screen
+                if matches!(event, Event::Key(_)) {
+                    break 'session;
+                }
+                continue;
+            }
+            status = wizard.handle_event(&event);
+            if status == WizardStatus::Cancelled {
+                break 'session;
+            }
+        }
+    }
+
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
+    terminal.show_cursor()?;
+
+    Ok((status == WizardStatus::Completed).then(|| wizard.into_responses()))
+}
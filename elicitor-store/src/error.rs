@@ -0,0 +1,29 @@
+//! Error type for the response store.
+
+use elicitor::ResponsePath;
+use thiserror::Error;
+
+/// Errors that can occur while storing or loading responses.
+#[derive(Debug, Error)]
+pub enum StoreError {
+    /// The database refused a statement.
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+
+    /// The responses hold an answer the survey has no question for.
+    #[error("No column for the answer at '{0}'")]
+    UnknownAnswer(ResponsePath),
+
+    /// An answer does not have the type of its question.
+    #[error("Answer at '{path}' is {actual}, the column holds {expected}")]
+    TypeMismatch {
+        path: ResponsePath,
+        expected: &'static str,
+        actual: &'static str,
+    },
+
+    /// A stored value cannot be read back as an answer, e.g. an enum variant that was
+    /// renamed since.
+    #[error("Invalid value in column '{column}': {message}")]
+    InvalidValue { column: String, message: String },
+}
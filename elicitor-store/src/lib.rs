@@ -0,0 +1,52 @@
+//! # elicitor-store
+//!
+//! SQLite storage for elicitor surveys.
+//!
+//! A [`SurveyStore`] keeps the answers of repeated survey runs in one table, one row per
+//! run, so they add up to a dataset that can be queried with plain SQL. The table is
+//! derived from the [`SurveyDefinition`](elicitor::SurveyDefinition): every answer gets a
+//! column named after its response path, e.g. `address.city`, typed by its question:
+//!
+//! | Question                                  | Column                                  |
+//! |-------------------------------------------|-----------------------------------------|
+//! | text, selection, color, path, masked      | `TEXT`                                  |
+//! | integer, currency (minor units), rating   | `INTEGER`                               |
+//! | float                                     | `REAL`                                  |
+//! | yes/no                                    | `INTEGER`, `0` or `1`                   |
+//! | enum (`.selected_variant`)                | `TEXT`, the variant name                |
+//! | multi-select (`.selected_variants`)       | `TEXT`, JSON array of variant names     |
+//! | matrix                                    | `TEXT`, JSON array of column labels     |
+//! | list                                      | `TEXT`, JSON array                      |
+//!
+//! The fields of multi-select variants go in columns below the variant's name, e.g.
+//! `hobbies.Gaming.hours_per_week`. Every table also has an `_id` primary key and a
+//! `_recorded_at` timestamp.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use elicitor::Survey;
+//! use elicitor_store::SurveyStore;
+//!
+//! let store = SurveyStore::open("answers.db", "profiles", &UserProfile::survey())?;
+//!
+//! let (profile, responses) = UserProfile::builder().run_with_responses(backend)?;
+//! store.insert(&responses)?;
+//!
+//! let profiles: Vec<UserProfile> = store.load()?;
+//! let adults: i64 = store.connection().query_row(
+//!     "SELECT COUNT(*) FROM profiles WHERE age >= 18",
+//!     [],
+//!     |row| row.get(0),
+//! )?;
+//! ```
+
+mod error;
+mod schema;
+mod store;
+
+pub use error::StoreError;
+pub use schema::{Column, ID_COLUMN, RECORDED_AT_COLUMN};
+pub use store::{SurveyStore, create_table_sql};
+
+pub use rusqlite;
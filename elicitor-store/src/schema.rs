@@ -0,0 +1,554 @@
+//! Table layout of a survey: one column per answer, typed by its question.
+
+use elicitor::{
+    COMMENT_KEY, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyDefinition,
+};
+use rusqlite::types::Value;
+
+use crate::StoreError;
+
+/// Name of the column holding the row id.
+pub const ID_COLUMN: &str = "_id";
+
+/// Name of the column holding the time a row was inserted.
+pub const RECORDED_AT_COLUMN: &str = "_recorded_at";
+
+/// A column of the table of a survey.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    /// Name of the column, the response path of the answer.
+    pub name: String,
+    kind: ColumnKind,
+}
+
+impl Column {
+    /// SQLite type of the column.
+    pub fn sql_type(&self) -> &'static str {
+        match self.kind {
+            ColumnKind::Integer | ColumnKind::Bool => "INTEGER",
+            ColumnKind::Real => "REAL",
+            ColumnKind::Text
+            | ColumnKind::Variant(_)
+            | ColumnKind::Variants(_)
+            | ColumnKind::Matrix(_)
+            | ColumnKind::List(_) => "TEXT",
+        }
+    }
+
+    /// Convert the answer at `path` to the value stored in this column.
+    fn encode(&self, path: &ResponsePath, value: &ResponseValue) -> Result<Value, StoreError> {
+        let names = |names: &[String], indices: &[usize]| {
+            let names: Option<Vec<&String>> = indices.iter().map(|&idx| names.get(idx)).collect();
+            names.map(|names| Value::Text(serde_json::to_string(&names).unwrap_or_default()))
+        };
+        let converted = match (&self.kind, value) {
+            (ColumnKind::Text, ResponseValue::String(s)) => Some(Value::Text(s.clone())),
+            (ColumnKind::Integer, ResponseValue::Int(i)) => Some(Value::Integer(*i)),
+            (ColumnKind::Real, ResponseValue::Float(f)) => Some(Value::Real(*f)),
+            (ColumnKind::Bool, ResponseValue::Bool(b)) => Some(Value::Integer(i64::from(*b))),
+            (ColumnKind::Variant(variants), ResponseValue::ChosenVariant(idx)) => {
+                variants.get(*idx).map(|name| Value::Text(name.clone()))
+            }
+            (ColumnKind::Variants(variants), ResponseValue::ChosenVariants(indices)) => {
+                names(variants, indices)
+            }
+            (ColumnKind::Matrix(columns), ResponseValue::ChosenVariants(indices)) => {
+                names(columns, indices)
+            }
+            (ColumnKind::List(ListKind::Strings), ResponseValue::StringList(items)) => json(items),
+            (ColumnKind::List(ListKind::Ints), ResponseValue::IntList(items)) => json(items),
+            (ColumnKind::List(ListKind::Floats), ResponseValue::FloatList(items)) => json(items),
+            _ => None,
+        };
+        converted.ok_or_else(|| StoreError::TypeMismatch {
+            path: path.clone(),
+            expected: self.kind.name(),
+            actual: value.type_name(),
+        })
+    }
+
+    /// Convert a stored value back to an answer. `NULL` is no answer.
+    fn decode(&self, value: Value) -> Result<Option<ResponseValue>, StoreError> {
+        let invalid = |message: String| StoreError::InvalidValue {
+            column: self.name.clone(),
+            message,
+        };
+        let position = |names: &[String], name: &str| {
+            names
+                .iter()
+                .position(|candidate| candidate == name)
+                .ok_or_else(|| invalid(format!("{name:?} is not one of the choices")))
+        };
+        let positions = |names: &[String], text: &str| {
+            serde_json::from_str::<Vec<String>>(text)
+                .map_err(|err| invalid(err.to_string()))?
+                .iter()
+                .map(|name| position(names, name))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let parse_list = |text: &str, kind: ListKind| {
+            let value = match kind {
+                ListKind::Strings => serde_json::from_str(text).map(ResponseValue::StringList),
+                ListKind::Ints => serde_json::from_str(text).map(ResponseValue::IntList),
+                ListKind::Floats => serde_json::from_str(text).map(ResponseValue::FloatList),
+            };
+            value.map_err(|err| invalid(err.to_string()))
+        };
+
+        let answer = match (&self.kind, value) {
+            (_, Value::Null) => return Ok(None),
+            (ColumnKind::Text, Value::Text(s)) => ResponseValue::String(s),
+            (ColumnKind::Integer, Value::Integer(i)) => ResponseValue::Int(i),
+            (ColumnKind::Real, Value::Real(f)) => ResponseValue::Float(f),
+            (ColumnKind::Real, Value::Integer(i)) => ResponseValue::Float(i as f64),
+            (ColumnKind::Bool, Value::Integer(i)) => ResponseValue::Bool(i != 0),
+            (ColumnKind::Variant(variants), Value::Text(name)) => {
+                ResponseValue::ChosenVariant(position(variants, &name)?)
+            }
+            (ColumnKind::Variants(variants), Value::Text(text)) => {
+                ResponseValue::ChosenVariants(positions(variants, &text)?)
+            }
+            (ColumnKind::Matrix(columns), Value::Text(text)) => {
+                ResponseValue::ChosenVariants(positions(columns, &text)?)
+            }
+            (ColumnKind::List(kind), Value::Text(text)) => parse_list(&text, *kind)?,
+            (kind, value) => {
+                return Err(invalid(format!(
+                    "expected {}, found {:?}",
+                    kind.name(),
+                    value.data_type()
+                )));
+            }
+        };
+        Ok(Some(answer))
+    }
+}
+
+fn json<T: Clone + Into<serde_json::Value>>(items: &[T]) -> Option<Value> {
+    Some(Value::Text(
+        serde_json::Value::from(items.to_vec()).to_string(),
+    ))
+}
+
+/// What a column holds and how it is stored.
+#[derive(Debug, Clone, PartialEq)]
+enum ColumnKind {
+    Text,
+    Integer,
+    Real,
+    /// `0` or `1`.
+    Bool,
+    /// The name of the chosen variant.
+    Variant(Vec<String>),
+    /// The names of the chosen variants as a JSON array.
+    Variants(Vec<String>),
+    /// The chosen column label of every row as a JSON array.
+    Matrix(Vec<String>),
+    /// The items as a JSON array.
+    List(ListKind),
+}
+
+impl ColumnKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Integer => "an integer",
+            Self::Real => "a number",
+            Self::Bool => "a yes/no answer",
+            Self::Variant(_) => "a variant name",
+            Self::Variants(_) | Self::Matrix(_) => "a list of names",
+            Self::List(_) => "a list",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListKind {
+    Strings,
+    Ints,
+    Floats,
+}
+
+/// Columns of the table of a survey.
+///
+/// The fields of multi-select variants are stored below the variant's name rather than
+/// the position of the item, so every variant has columns of its own.
+#[derive(Debug, Clone)]
+pub(crate) struct Schema {
+    pub(crate) columns: Vec<Column>,
+    /// Paths of multi-select questions and the names of their variants.
+    multi_selects: Vec<(ResponsePath, Vec<String>)>,
+}
+
+impl Schema {
+    pub(crate) fn new(definition: &SurveyDefinition) -> Self {
+        let mut schema = Self {
+            columns: Vec::new(),
+            multi_selects: Vec::new(),
+        };
+        for question in definition.questions() {
+            schema.collect(question, &ResponsePath::empty());
+        }
+        schema
+    }
+
+    fn push(&mut self, path: ResponsePath, kind: ColumnKind) {
+        let name = path.as_str();
+        if !self.columns.iter().any(|column| column.name == name) {
+            self.columns.push(Column {
+                name: name.to_string(),
+                kind,
+            });
+        }
+    }
+
+    fn collect(&mut self, question: &Question, prefix: &ResponsePath) {
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+
+        match question.kind() {
+            QuestionKind::Unit => {}
+            QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
+            | QuestionKind::Multiline(_)
+            | QuestionKind::Masked(_)
+            | QuestionKind::Path(_)
+            | QuestionKind::Color(_)
+            | QuestionKind::Select(_) => self.push(path.clone(), ColumnKind::Text),
+            QuestionKind::Int(_) | QuestionKind::Currency(_) | QuestionKind::Rating(_) => {
+                self.push(path.clone(), ColumnKind::Integer)
+            }
+            QuestionKind::Float(_) => self.push(path.clone(), ColumnKind::Real),
+            QuestionKind::Confirm(_) => self.push(path.clone(), ColumnKind::Bool),
+            QuestionKind::List(list_q) => {
+                let kind = match list_q.element_kind {
+                    ListElementKind::String => ListKind::Strings,
+                    ListElementKind::Int { .. } => ListKind::Ints,
+                    ListElementKind::Float { .. } => ListKind::Floats,
+                };
+                self.push(path.clone(), ColumnKind::List(kind));
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                self.push(path.clone(), ColumnKind::Matrix(matrix_q.columns.clone()))
+            }
+            QuestionKind::OneOf(one_of) => {
+                let names = one_of.variants.iter().map(|v| v.name.clone()).collect();
+                self.push(path.child(SELECTED_VARIANT_KEY), ColumnKind::Variant(names));
+                for variant in &one_of.variants {
+                    match &variant.kind {
+                        QuestionKind::Unit => {}
+                        QuestionKind::AllOf(all_of) => {
+                            for nested in all_of.questions() {
+                                self.collect(nested, &path);
+                            }
+                        }
+                        kind => {
+                            let nested = Question::new(variant.name.as_str(), "", kind.clone());
+                            self.collect(&nested, &path);
+                        }
+                    }
+                }
+            }
+            QuestionKind::AnyOf(any_of) => {
+                let names: Vec<String> = any_of.variants.iter().map(|v| v.name.clone()).collect();
+                self.push(
+                    path.child(SELECTED_VARIANTS_KEY),
+                    ColumnKind::Variants(names.clone()),
+                );
+                for variant in &any_of.variants {
+                    if let QuestionKind::AllOf(all_of) = &variant.kind {
+                        let variant_path = path.child(&variant.name);
+                        for nested in all_of.questions() {
+                            self.collect(nested, &variant_path);
+                        }
+                    }
+                }
+                self.multi_selects.push((path.clone(), names));
+            }
+            QuestionKind::AllOf(all_of) => {
+                for nested in all_of.questions() {
+                    self.collect(nested, &path);
+                }
+            }
+        }
+
+        if question.allows_comment() {
+            self.push(path.child(COMMENT_KEY), ColumnKind::Text);
+        }
+    }
+
+    /// Values of a row, by column index.
+    pub(crate) fn row(&self, responses: &Responses) -> Result<Vec<(usize, Value)>, StoreError> {
+        let mut row = Vec::new();
+        for (path, value) in responses.iter_exposed() {
+            let Some(column) = self.column_path(path, responses) else {
+                continue;
+            };
+            let idx = self
+                .columns
+                .iter()
+                .position(|c| c.name == column.as_str())
+                .ok_or_else(|| StoreError::UnknownAnswer(path.clone()))?;
+            row.push((idx, self.columns[idx].encode(path, value)?));
+        }
+        Ok(row)
+    }
+
+    /// Path of the column storing the answer at `path`. Multi-select items are stored
+    /// below their variant's name, their variant itself is part of the list of choices.
+    fn column_path(&self, path: &ResponsePath, responses: &Responses) -> Option<ResponsePath> {
+        for (prefix, names) in &self.multi_selects {
+            let Some(rest) = path.strip_path_prefix(prefix) else {
+                continue;
+            };
+            let Some(item) = rest.first().filter(|s| s.parse::<usize>().is_ok()) else {
+                continue;
+            };
+            let item_path = prefix.child(item);
+            let field = rest.strip_prefix(item)?;
+            if field.as_str() == SELECTED_VARIANT_KEY {
+                return None;
+            }
+            let variant = responses
+                .get(&item_path.child(SELECTED_VARIANT_KEY))
+                .and_then(ResponseValue::as_chosen_variant)
+                .and_then(|idx| names.get(idx));
+            return match variant {
+                Some(name) => Some(prefix.child(name).child(field.as_str())),
+                None => Some(path.clone()),
+            };
+        }
+        Some(path.clone())
+    }
+
+    /// Responses of a row of values in column order.
+    pub(crate) fn responses(&self, values: Vec<Value>) -> Result<Responses, StoreError> {
+        let mut responses = Responses::new();
+        for (column, value) in self.columns.iter().zip(values) {
+            if let Some(answer) = column.decode(value)? {
+                responses.insert(ResponsePath::new(column.name.as_str()), answer);
+            }
+        }
+
+        // Move the fields of multi-select variants to the position of their item
+        for (prefix, names) in &self.multi_selects {
+            let chosen = responses
+                .get(&prefix.child(SELECTED_VARIANTS_KEY))
+                .and_then(ResponseValue::as_chosen_variants)
+                .map(<[usize]>::to_vec)
+                .unwrap_or_default();
+            for (item, idx) in chosen.into_iter().enumerate() {
+                let item_path = prefix.child(&item.to_string());
+                responses.insert(
+                    item_path.child(SELECTED_VARIANT_KEY),
+                    ResponseValue::ChosenVariant(idx),
+                );
+                let variant_path = prefix.child(&names[idx]);
+                let fields: Vec<ResponsePath> = responses
+                    .paths()
+                    .filter(|path| path.strip_path_prefix(&variant_path).is_some())
+                    .cloned()
+                    .collect();
+                for path in fields {
+                    if let Some(field) = path.strip_path_prefix(&variant_path) {
+                        responses.rename(&path, &item_path.child(field.as_str()));
+                    }
+                }
+            }
+        }
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{
+        AllOfQuestion, AnyOfQuestion, ConfirmQuestion, CurrencyQuestion, FloatQuestion,
+        InputQuestion, IntQuestion, ListQuestion, MaskedQuestion, OneOfQuestion, RatingQuestion,
+        SelectOption, SelectQuestion, Variant,
+    };
+
+    fn input(path: &str) -> Question {
+        Question::new(path, "", QuestionKind::Input(InputQuestion::new()))
+    }
+
+    fn all_of(path: &str, questions: Vec<Question>) -> Question {
+        Question::new(path, "", QuestionKind::AllOf(AllOfQuestion::new(questions)))
+    }
+
+    /// Name and SQL type of every column of `questions`.
+    fn columns(questions: Vec<Question>) -> Vec<(String, &'static str)> {
+        Schema::new(&SurveyDefinition::new(questions))
+            .columns
+            .iter()
+            .map(|column| (column.name.clone(), column.sql_type()))
+            .collect()
+    }
+
+    fn named(columns: &[(&str, &'static str)]) -> Vec<(String, &'static str)> {
+        columns
+            .iter()
+            .map(|&(name, sql_type)| (name.to_string(), sql_type))
+            .collect()
+    }
+
+    #[test]
+    fn leaves_are_typed_by_their_question() {
+        let select = SelectQuestion::new(vec![
+            SelectOption::new("s", "Small"),
+            SelectOption::new("l", "Large"),
+        ]);
+        assert_eq!(
+            columns(vec![
+                input("name"),
+                Question::new("pin", "", QuestionKind::Masked(MaskedQuestion::new())),
+                Question::new("size", "", QuestionKind::Select(select)),
+                Question::new("age", "", QuestionKind::Int(IntQuestion::new())),
+                Question::new(
+                    "price",
+                    "",
+                    QuestionKind::Currency(CurrencyQuestion::new("EUR"))
+                ),
+                Question::new("stars", "", QuestionKind::Rating(RatingQuestion::new(1, 5)))
+                    .with_comment(),
+                Question::new("height", "", QuestionKind::Float(FloatQuestion::new())),
+                Question::new("member", "", QuestionKind::Confirm(ConfirmQuestion::new())),
+                Question::new("done", "", QuestionKind::Unit),
+            ]),
+            named(&[
+                ("name", "TEXT"),
+                ("pin", "TEXT"),
+                ("size", "TEXT"),
+                ("age", "INTEGER"),
+                ("price", "INTEGER"),
+                ("stars", "INTEGER"),
+                ("stars.__comment", "TEXT"),
+                ("height", "REAL"),
+                ("member", "INTEGER"),
+            ])
+        );
+    }
+
+    #[test]
+    fn nested_structs_and_enums_are_flattened_into_paths() {
+        let address = all_of(
+            "address",
+            vec![
+                input("street"),
+                all_of(
+                    "geo",
+                    vec![Question::new(
+                        "lat",
+                        "",
+                        QuestionKind::Float(FloatQuestion::new()),
+                    )],
+                ),
+            ],
+        );
+        let card = |field: &str| {
+            QuestionKind::AllOf(AllOfQuestion::new(vec![input("holder"), input(field)]))
+        };
+        let payment = Question::new(
+            "payment",
+            "",
+            QuestionKind::OneOf(OneOfQuestion::new(vec![
+                Variant::new("Cash", QuestionKind::Unit),
+                Variant::new("Card", card("number")),
+                Variant::new("Iban", card("iban")),
+                // Derived newtype variants hold a single question at `0`
+                Variant::new(
+                    "Voucher",
+                    QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                        "0",
+                        "",
+                        QuestionKind::Int(IntQuestion::new()),
+                    )])),
+                ),
+                Variant::new("Points", QuestionKind::Int(IntQuestion::new())),
+            ])),
+        );
+
+        assert_eq!(
+            columns(vec![address, payment]),
+            named(&[
+                ("address.street", "TEXT"),
+                ("address.geo.lat", "REAL"),
+                ("payment.selected_variant", "TEXT"),
+                // Variants share the columns of fields with the same name
+                ("payment.holder", "TEXT"),
+                ("payment.number", "TEXT"),
+                ("payment.iban", "TEXT"),
+                ("payment.0", "INTEGER"),
+                ("payment.Points", "INTEGER"),
+            ])
+        );
+    }
+
+    #[test]
+    fn lists_and_multi_selects_are_stored_as_text() {
+        let pets = Question::new(
+            "pets",
+            "",
+            QuestionKind::AnyOf(AnyOfQuestion::new(vec![
+                Variant::new("Cat", QuestionKind::Unit),
+                Variant::new(
+                    "Dog",
+                    QuestionKind::AllOf(AllOfQuestion::new(vec![input("name")])),
+                ),
+            ])),
+        );
+        let schema = Schema::new(&SurveyDefinition::new(vec![
+            Question::new("tags", "", QuestionKind::List(ListQuestion::strings())),
+            Question::new("lucky", "", QuestionKind::List(ListQuestion::ints())),
+            Question::new("weights", "", QuestionKind::List(ListQuestion::floats())),
+            pets,
+        ]));
+
+        let kinds: Vec<(&str, &'static str, &ColumnKind)> = schema
+            .columns
+            .iter()
+            .map(|column| (column.name.as_str(), column.sql_type(), &column.kind))
+            .collect();
+        let variants = vec!["Cat".to_string(), "Dog".to_string()];
+        assert_eq!(
+            kinds,
+            [
+                ("tags", "TEXT", &ColumnKind::List(ListKind::Strings)),
+                ("lucky", "TEXT", &ColumnKind::List(ListKind::Ints)),
+                ("weights", "TEXT", &ColumnKind::List(ListKind::Floats)),
+                (
+                    "pets.selected_variants",
+                    "TEXT",
+                    &ColumnKind::Variants(variants)
+                ),
+                ("pets.Dog.name", "TEXT", &ColumnKind::Text),
+            ]
+        );
+
+        // Items are stored as JSON arrays and read back with their element type
+        let lucky = &schema.columns[1];
+        let stored = lucky
+            .encode(
+                &ResponsePath::new("lucky"),
+                &ResponseValue::IntList(vec![3, 7]),
+            )
+            .unwrap();
+        assert_eq!(stored, Value::Text("[3,7]".to_string()));
+        assert_eq!(
+            lucky.decode(stored).unwrap(),
+            Some(ResponseValue::IntList(vec![3, 7]))
+        );
+        assert!(matches!(
+            lucky.encode(
+                &ResponsePath::new("lucky"),
+                &ResponseValue::FloatList(vec![0.5])
+            ),
+            Err(StoreError::TypeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,421 @@
+//! The table of a survey in a SQLite database.
+
+use std::path::Path;
+
+use elicitor::{FieldHandling, Responses, SensitiveField, SensitiveSink, Survey, SurveyDefinition};
+use rusqlite::{Connection, params_from_iter, types::Value};
+
+use crate::StoreError;
+use crate::schema::{Column, ID_COLUMN, RECORDED_AT_COLUMN, Schema};
+
+/// Quote an identifier, so column names may contain dots.
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// `CREATE TABLE` statement for the answers of `definition`.
+///
+/// Besides one column per answer, the table has an `_id` primary key and a
+/// `_recorded_at` timestamp.
+pub fn create_table_sql(table: &str, definition: &SurveyDefinition) -> String {
+    let mut sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (\n    {} INTEGER PRIMARY KEY,\n    {} TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP",
+        quote(table),
+        quote(ID_COLUMN),
+        quote(RECORDED_AT_COLUMN)
+    );
+    for column in Schema::new(definition).columns {
+        sql.push_str(&format!(
+            ",\n    {} {}",
+            quote(&column.name),
+            column.sql_type()
+        ));
+    }
+    sql.push_str("\n)");
+    sql
+}
+
+/// Responses of repeated survey runs, one row per run.
+///
+/// The table is created on first use. When the survey gains questions, opening the store
+/// adds their columns; rows stored before have `NULL` in them.
+#[derive(Debug)]
+pub struct SurveyStore {
+    connection: Connection,
+    table: String,
+    schema: Schema,
+}
+
+impl SurveyStore {
+    /// Open the database at `path` and store answers to `definition` in `table`.
+    pub fn open(
+        path: impl AsRef<Path>,
+        table: impl Into<String>,
+        definition: &SurveyDefinition,
+    ) -> Result<Self, StoreError> {
+        Self::with_connection(Connection::open(path)?, table, definition)
+    }
+
+    /// Keep the answers in memory, e.g. for tests.
+    pub fn open_in_memory(
+        table: impl Into<String>,
+        definition: &SurveyDefinition,
+    ) -> Result<Self, StoreError> {
+        Self::with_connection(Connection::open_in_memory()?, table, definition)
+    }
+
+    /// Store answers to `definition` in `table` of an open database.
+    pub fn with_connection(
+        connection: Connection,
+        table: impl Into<String>,
+        definition: &SurveyDefinition,
+    ) -> Result<Self, StoreError> {
+        let table = table.into();
+        let schema = Schema::new(definition);
+        connection.execute(&create_table_sql(&table, definition), [])?;
+
+        let existing: Vec<String> = connection
+            .prepare(&format!("PRAGMA table_info({})", quote(&table)))?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<Result<_, _>>()?;
+        for column in &schema.columns {
+            if !existing.contains(&column.name) {
+                connection.execute(
+                    &format!(
+                        "ALTER TABLE {} ADD COLUMN {} {}",
+                        quote(&table),
+                        quote(&column.name),
+                        column.sql_type()
+                    ),
+                    [],
+                )?;
+            }
+        }
+
+        Ok(Self {
+            connection,
+            table,
+            schema,
+        })
+    }
+
+    /// Name of the table.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Columns holding answers, without `_id` and `_recorded_at`.
+    pub fn columns(&self) -> &[Column] {
+        &self.schema.columns
+    }
+
+    /// The database, for queries of your own.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Store the responses of a run and return the id of its row.
+    ///
+    /// Sealed answers to secret questions are left out.
+    pub fn insert(&self, responses: &Responses) -> Result<i64, StoreError> {
+        let row = self.schema.row(responses)?;
+        if row.is_empty() {
+            self.connection.execute(
+                &format!("INSERT INTO {} DEFAULT VALUES", quote(&self.table)),
+                [],
+            )?;
+        } else {
+            let names: Vec<String> = row
+                .iter()
+                .map(|(idx, _)| quote(&self.schema.columns[*idx].name))
+                .collect();
+            let placeholders = vec!["?"; row.len()].join(", ");
+            let sql = format!(
+                "INSERT INTO {} ({}) VALUES ({placeholders})",
+                quote(&self.table),
+                names.join(", ")
+            );
+            self.connection
+                .execute(&sql, params_from_iter(row.into_iter().map(|(_, v)| v)))?;
+        }
+        Ok(self.connection.last_insert_rowid())
+    }
+
+    /// Number of stored runs.
+    pub fn len(&self) -> Result<usize, StoreError> {
+        let count: i64 = self.connection.query_row(
+            &format!("SELECT COUNT(*) FROM {}", quote(&self.table)),
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Whether no run has been stored yet.
+    pub fn is_empty(&self) -> Result<bool, StoreError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Responses of the run stored in row `id`.
+    pub fn get(&self, id: i64) -> Result<Option<Responses>, StoreError> {
+        let mut rows = self.select(&format!("WHERE {} = ?1", quote(ID_COLUMN)), [id])?;
+        Ok(rows.pop())
+    }
+
+    /// Responses of all stored runs, oldest first.
+    pub fn responses(&self) -> Result<Vec<Responses>, StoreError> {
+        self.select("", [])
+    }
+
+    /// All stored runs as survey types, oldest first.
+    ///
+    /// Answers to secret questions are never stored, so surveys with secret questions
+    /// can only be read back as [`responses`](Self::responses).
+    pub fn load<T: Survey>(&self) -> Result<Vec<T>, StoreError> {
+        Ok(self.responses()?.iter().map(T::from_responses).collect())
+    }
+
+    fn select(
+        &self,
+        filter: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<Responses>, StoreError> {
+        let names: Vec<String> = self
+            .schema
+            .columns
+            .iter()
+            .map(|column| quote(&column.name))
+            .collect();
+        let sql = format!(
+            "SELECT {} FROM {} {filter} ORDER BY {}",
+            if names.is_empty() {
+                quote(ID_COLUMN)
+            } else {
+                names.join(", ")
+            },
+            quote(&self.table),
+            quote(ID_COLUMN)
+        );
+        let mut statement = self.connection.prepare(&sql)?;
+        let rows = statement
+            .query_map(params, |row| {
+                (0..names.len())
+                    .map(|idx| row.get::<_, Value>(idx))
+                    .collect::<Result<Vec<_>, _>>()
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(|values| self.schema.responses(values))
+            .collect()
+    }
+}
+
+impl SensitiveSink for SurveyStore {
+    fn sink_name(&self) -> String {
+        format!("sqlite table {}", self.table)
+    }
+
+    fn handling(&self, field: &SensitiveField) -> FieldHandling {
+        // Sealed answers are not part of the responses the store iterates
+        if field.secret {
+            FieldHandling::Omitted
+        } else {
+            FieldHandling::Plaintext
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{
+        AllOfQuestion, AnyOfQuestion, ConfirmQuestion, FloatQuestion, InputQuestion, IntQuestion,
+        ListQuestion, MatrixQuestion, Question, QuestionKind, RandomAnswerBackend, ResponsePath,
+        ResponseValue, Variant,
+    };
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            Question::new("age", "Age:", QuestionKind::Int(IntQuestion::new())),
+            Question::new(
+                "height",
+                "Height:",
+                QuestionKind::Float(FloatQuestion::new()),
+            ),
+            Question::new(
+                "member",
+                "Member?",
+                QuestionKind::Confirm(ConfirmQuestion::new()),
+            ),
+            Question::new("tags", "Tags:", QuestionKind::List(ListQuestion::strings())),
+            Question::new(
+                "ratings",
+                "Rate:",
+                QuestionKind::Matrix(MatrixQuestion::new(
+                    vec!["Food".into(), "Service".into()],
+                    vec!["Bad".into(), "Good".into()],
+                )),
+            ),
+            Question::new(
+                "pets",
+                "Pets:",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![
+                    Variant::new("Cat", QuestionKind::Unit),
+                    Variant::new(
+                        "Dog",
+                        QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                            "name",
+                            "Dog's name:",
+                            QuestionKind::Input(InputQuestion::new()),
+                        )])),
+                    ),
+                ])),
+            ),
+        ])
+    }
+
+    fn responses(name: &str, age: i64, dog: Option<&str>) -> Responses {
+        let mut responses = Responses::new();
+        responses.insert("name", name);
+        responses.insert("age", ResponseValue::Int(age));
+        responses.insert("height", ResponseValue::Float(1.8));
+        responses.insert("member", ResponseValue::Bool(true));
+        responses.insert(
+            "tags",
+            ResponseValue::StringList(vec!["a".into(), "b".into()]),
+        );
+        responses.insert("ratings", ResponseValue::ChosenVariants(vec![1, 0]));
+        match dog {
+            Some(dog) => {
+                responses.insert(
+                    "pets.selected_variants",
+                    ResponseValue::ChosenVariants(vec![0, 1]),
+                );
+                responses.insert("pets.0.selected_variant", ResponseValue::ChosenVariant(0));
+                responses.insert("pets.1.selected_variant", ResponseValue::ChosenVariant(1));
+                responses.insert("pets.1.name", dog);
+            }
+            None => responses.insert(
+                "pets.selected_variants",
+                ResponseValue::ChosenVariants(vec![]),
+            ),
+        }
+        responses
+    }
+
+    fn assert_same(stored: &Responses, original: &Responses) {
+        assert_eq!(stored.len(), original.len());
+        for (path, value) in original.iter() {
+            assert_eq!(stored.get(path), Some(value), "answer at '{path}'");
+        }
+    }
+
+    #[test]
+    fn columns_follow_the_questions() {
+        let sql = create_table_sql("people", &definition());
+        assert!(sql.starts_with(
+            "CREATE TABLE IF NOT EXISTS \"people\" (\n    \"_id\" INTEGER PRIMARY KEY"
+        ));
+        assert!(sql.contains("\"age\" INTEGER"));
+        assert!(sql.contains("\"height\" REAL"));
+        assert!(sql.contains("\"member\" INTEGER"));
+        assert!(sql.contains("\"pets.selected_variants\" TEXT"));
+        assert!(sql.contains("\"pets.Dog.name\" TEXT"));
+    }
+
+    #[test]
+    fn runs_accumulate_and_read_back() {
+        let store = SurveyStore::open_in_memory("people", &definition()).unwrap();
+        let ada = responses("Ada", 36, Some("Rex"));
+        let bob = responses("Bob", 17, None);
+        let id = store.insert(&ada).unwrap();
+        store.insert(&bob).unwrap();
+        assert_eq!(store.len().unwrap(), 2);
+
+        let stored = store.responses().unwrap();
+        assert_same(&stored[0], &ada);
+        assert_same(&stored[1], &bob);
+        assert_same(&store.get(id).unwrap().unwrap(), &ada);
+
+        let (ratings, pets, dog): (String, String, String) = store
+            .connection()
+            .query_row(
+                "SELECT ratings, \"pets.selected_variants\", \"pets.Dog.name\" \
+                 FROM people WHERE age >= 18",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+        assert_eq!(ratings, r#"["Good","Bad"]"#);
+        assert_eq!(pets, r#"["Cat","Dog"]"#);
+        assert_eq!(dog, "Rex");
+    }
+
+    #[test]
+    fn new_questions_add_columns() {
+        let mut old = definition();
+        old.questions_mut().retain(|q| q.path().as_str() != "age");
+        let store = SurveyStore::open_in_memory("people", &old).unwrap();
+        let mut early = responses("Ada", 36, None);
+        early.remove(&ResponsePath::new("age"));
+        store.insert(&early).unwrap();
+
+        let store =
+            SurveyStore::with_connection(store.connection, "people", &definition()).unwrap();
+        store.insert(&responses("Bob", 17, None)).unwrap();
+        let stored = store.responses().unwrap();
+        assert_same(&stored[0], &early);
+        assert_eq!(stored[1].get_int(&ResponsePath::new("age")).unwrap(), 17);
+    }
+
+    #[test]
+    fn answers_of_the_wrong_type_are_rejected() {
+        let store = SurveyStore::open_in_memory("people", &definition()).unwrap();
+        let mut wrong = responses("Ada", 36, None);
+        wrong.insert("age", "thirty-six");
+        assert!(matches!(
+            store.insert(&wrong),
+            Err(StoreError::TypeMismatch { .. })
+        ));
+        let mut unknown = responses("Ada", 36, None);
+        unknown.insert("shoe_size", ResponseValue::Int(42));
+        assert!(matches!(
+            store.insert(&unknown),
+            Err(StoreError::UnknownAnswer(_))
+        ));
+    }
+
+    #[test]
+    fn example_surveys_round_trip() {
+        use example_surveys::{
+            Checkout, DeveloperProfile, MortgageApplication, StudentGrades, UserRegistration,
+        };
+
+        macro_rules! round_trip {
+            ($survey:ty, $seed:expr) => {{
+                let store = SurveyStore::open_in_memory("runs", &<$survey>::survey()).unwrap();
+                let mut runs = Vec::new();
+                for n in 0..5 {
+                    let backend = RandomAnswerBackend::new().with_seed($seed + n);
+                    let (value, responses) =
+                        <$survey>::builder().run_with_responses(backend).unwrap();
+                    store.insert(&responses).unwrap();
+                    runs.push((format!("{value:?}"), responses));
+                }
+                let stored = store.responses().unwrap();
+                let loaded = store.load::<$survey>().unwrap();
+                for (((debug, original), stored), loaded) in runs.iter().zip(&stored).zip(&loaded) {
+                    assert_same(stored, original);
+                    assert_eq!(&format!("{loaded:?}"), debug);
+                }
+            }};
+        }
+
+        round_trip!(DeveloperProfile, 1);
+        round_trip!(Checkout, 10);
+        round_trip!(StudentGrades, 50);
+        round_trip!(MortgageApplication, 60);
+        round_trip!(UserRegistration, 30);
+    }
+}
@@ -0,0 +1,46 @@
+//! Common questions for the question bank.
+//!
+//! Call [`register`] once at startup, then reference the questions from your own
+//! surveys by id:
+//!
+//! ```rust,ignore
+//! elicitor_templates::common::register();
+//!
+//! #[derive(Survey, Debug)]
+//! struct Newsletter {
+//!     #[use_question("common.email")]
+//!     email: String,
+//!
+//!     #[use_question("common.consent")]
+//!     consent: bool,
+//! }
+//! ```
+
+use elicitor::question_bank::{self, BankQuestion};
+
+use crate::validators::{validate_email, validate_phone};
+
+/// Id of the email address question (`String`).
+pub const EMAIL: &str = "common.email";
+
+/// Id of the optional phone number question (`String`).
+pub const PHONE: &str = "common.phone";
+
+/// Id of the data processing consent question (`bool`).
+pub const CONSENT: &str = "common.consent";
+
+/// Add the common questions to the question bank.
+///
+/// Questions registered earlier under the same ids are replaced.
+pub fn register() {
+    question_bank::register(
+        BankQuestion::new(EMAIL, "Email address:").with_validator(validate_email),
+    );
+    question_bank::register(
+        BankQuestion::new(PHONE, "Phone number (optional):").with_validator(validate_phone),
+    );
+    question_bank::register(BankQuestion::new(
+        CONSENT,
+        "I agree that my answers are stored and processed.",
+    ));
+}
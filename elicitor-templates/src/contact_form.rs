@@ -0,0 +1,49 @@
+//! Contact form template.
+
+use elicitor::Survey;
+
+use crate::validators::{required, validate_email, validate_phone};
+
+/// How the sender would like to be contacted back.
+#[derive(Survey, Debug, Clone, PartialEq)]
+pub enum ReplyChannel {
+    #[ask("Email", key = "contact_form.reply_via.email")]
+    Email,
+
+    #[ask("Phone", key = "contact_form.reply_via.phone")]
+    Phone,
+
+    #[ask("No reply needed", key = "contact_form.reply_via.no_reply")]
+    NoReply,
+}
+
+/// A general-purpose contact form.
+///
+/// Can be used on its own or as a nested field, e.g. for the reporter of an
+/// incident or the customer in an order form.
+#[derive(Survey, Debug, Clone, PartialEq)]
+pub struct ContactForm {
+    #[ask("Your name:", key = "contact_form.name")]
+    #[validate(required)]
+    pub name: String,
+
+    #[ask("Email address:", key = "contact_form.email")]
+    #[validate(validate_email)]
+    pub email: String,
+
+    #[ask("Phone number (optional):", key = "contact_form.phone")]
+    #[validate(validate_phone)]
+    pub phone: Option<String>,
+
+    #[ask("Subject:", key = "contact_form.subject")]
+    #[validate(required)]
+    pub subject: String,
+
+    #[ask("Message:", key = "contact_form.message")]
+    #[multiline]
+    #[validate(required)]
+    pub message: String,
+
+    #[ask("How should we reply?", key = "contact_form.reply_via")]
+    pub reply_via: ReplyChannel,
+}
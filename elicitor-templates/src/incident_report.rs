@@ -0,0 +1,103 @@
+//! Incident report template.
+
+use elicitor::Survey;
+
+use crate::validators::{required, validate_email, validate_timestamp};
+
+/// How severe the incident is.
+#[derive(Survey, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    #[ask(
+        "Critical - complete outage or data loss",
+        key = "incident_report.severity.critical"
+    )]
+    Critical,
+
+    #[ask(
+        "High - major feature unavailable",
+        key = "incident_report.severity.high"
+    )]
+    High,
+
+    #[ask(
+        "Medium - degraded service with a workaround",
+        key = "incident_report.severity.medium"
+    )]
+    Medium,
+
+    #[ask("Low - minor or cosmetic issue", key = "incident_report.severity.low")]
+    Low,
+}
+
+/// Who is affected by the incident.
+#[derive(Survey, Debug, Clone, PartialEq)]
+pub enum Impact {
+    #[ask("Internal only", key = "incident_report.impact.internal")]
+    Internal,
+
+    #[ask("Customers", key = "incident_report.impact.customers")]
+    Customers {
+        #[ask(
+            "Approximate number of customers affected:",
+            key = "incident_report.impact.customers_affected"
+        )]
+        customers_affected: u32,
+
+        #[ask(
+            "Have customers been notified?",
+            key = "incident_report.impact.notified"
+        )]
+        notified: bool,
+    },
+}
+
+/// The person filing an incident report.
+#[derive(Survey, Debug, Clone, PartialEq)]
+pub struct Reporter {
+    #[ask("Your name:", key = "incident_report.reporter.name")]
+    #[validate(required)]
+    pub name: String,
+
+    #[ask("Your email address:", key = "incident_report.reporter.email")]
+    #[validate(validate_email)]
+    pub email: String,
+}
+
+/// An incident report for operations and support teams.
+#[derive(Survey, Debug, Clone, PartialEq)]
+pub struct IncidentReport {
+    #[ask("Short summary of the incident:", key = "incident_report.title")]
+    #[validate(required)]
+    pub title: String,
+
+    #[ask("Severity:", key = "incident_report.severity")]
+    pub severity: Severity,
+
+    #[ask(
+        "When did it start? (YYYY-MM-DD HH:MM)",
+        key = "incident_report.started_at"
+    )]
+    #[validate(validate_timestamp)]
+    pub started_at: String,
+
+    #[ask(
+        "Affected services (comma separated):",
+        key = "incident_report.affected_services"
+    )]
+    pub affected_services: Vec<String>,
+
+    #[ask("Impact:", key = "incident_report.impact")]
+    pub impact: Impact,
+
+    #[ask("What happened?", key = "incident_report.description")]
+    #[multiline]
+    #[validate(required)]
+    pub description: String,
+
+    #[ask("Steps taken so far (optional):", key = "incident_report.mitigation")]
+    #[multiline]
+    pub mitigation: Option<String>,
+
+    #[ask("Reported by:", key = "incident_report.reporter")]
+    pub reporter: Reporter,
+}
@@ -0,0 +1,274 @@
+//! Ready-made survey types for elicitor.
+//!
+//! This crate ships production-ready surveys for common tasks:
+//! - [`ContactForm`] - Name, email, optional phone and a message
+//! - [`NpsSurvey`] - Net Promoter Score with follow-up questions
+//! - [`IncidentReport`] - Severity, impact and timeline of an incident
+//! - [`OnboardingChecklist`] - Preparing a new team member's first day
+//!
+//! Every template derives `Survey`, so it can be run on any backend or used as a
+//! nested field in your own surveys:
+//!
+//! ```rust,ignore
+//! use elicitor::Survey;
+//! use elicitor_templates::ContactForm;
+//!
+//! #[derive(Survey, Debug)]
+//! struct SupportTicket {
+//!     #[ask("Product:")]
+//!     product: String,
+//!
+//!     #[ask("Contact details:")]
+//!     contact: ContactForm,
+//! }
+//! ```
+//!
+//! Every prompt and choice carries a translation key of the form
+//! `<template>.<field path>`, e.g. `contact_form.email`, so templates are translated
+//! like any survey with `#[ask(key = "...")]`: with the builder's `with_translator`,
+//! or with `SurveyDefinition::translate` for documents. The English text is kept where
+//! there is no translation, and `SurveyDefinition::translation_keys` lists the keys
+//! for translators.
+//!
+//! Common single questions (email, phone, consent) can be added to the question bank
+//! with [`common::register`] and reused via `#[use_question("common.email")]`.
+
+pub mod common;
+pub mod validators;
+
+mod contact_form;
+pub use contact_form::{ContactForm, ReplyChannel};
+
+mod nps;
+pub use nps::{NpsCategory, NpsSurvey, net_promoter_score};
+
+mod incident_report;
+pub use incident_report::{Impact, IncidentReport, Reporter, Severity};
+
+mod onboarding;
+pub use onboarding::{Account, Equipment, OnboardingChecklist, OnboardingTask};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use elicitor::{QuestionKind, ResponsePath, ResponseValue, Responses, Survey, TestBackend};
+
+    fn check(
+        validator: fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        input: &str,
+    ) -> bool {
+        validator(
+            &ResponseValue::String(input.to_string()),
+            &Responses::new(),
+            &ResponsePath::new("field"),
+        )
+        .is_ok()
+    }
+
+    #[test]
+    fn validators_accept_and_reject() {
+        assert!(check(validators::validate_email, "jane@example.com"));
+        assert!(!check(validators::validate_email, "jane@example"));
+        assert!(!check(validators::validate_email, "@example.com"));
+
+        assert!(check(validators::validate_phone, "+49 (0)30 123456"));
+        assert!(check(validators::validate_phone, ""));
+        assert!(!check(validators::validate_phone, "call me"));
+
+        assert!(check(validators::validate_date, "2024-02-29"));
+        assert!(check(validators::validate_date, "2000-02-29"));
+        assert!(!check(validators::validate_date, "2023-02-29"));
+        assert!(!check(validators::validate_date, "1900-02-29"));
+        assert!(!check(validators::validate_date, "2024-04-31"));
+        assert!(!check(validators::validate_date, "24-01-01"));
+
+        assert!(check(validators::validate_timestamp, "2024-05-01 09:30"));
+        assert!(!check(validators::validate_timestamp, "2024-05-01 9:30"));
+
+        assert!(!check(validators::required, "   "));
+    }
+
+    #[derive(Survey, Debug)]
+    struct Signup {
+        #[use_question("common.email")]
+        email: String,
+
+        #[use_question("common.consent")]
+        consent: bool,
+    }
+
+    #[test]
+    fn common_questions_are_shared_through_the_bank() {
+        common::register();
+
+        let mut survey = Signup::survey();
+        assert_eq!(survey.questions[0].ask(), "Email address:");
+        // Bank questions are translated under their prompt key
+        survey.translate(&|key: &str| (key == "common.email").then(|| "E-Mail:".to_string()));
+        assert_eq!(survey.questions[0].ask(), "E-Mail:");
+        assert!(matches!(
+            survey.questions[1].kind(),
+            QuestionKind::Confirm(_)
+        ));
+
+        let signup = Signup::builder()
+            .run(
+                TestBackend::new()
+                    .with_string("email", "jane@example.com")
+                    .with_bool("consent", true),
+            )
+            .unwrap();
+        assert_eq!(signup.email, "jane@example.com");
+        assert!(signup.consent);
+
+        let result = Signup::builder().run(
+            TestBackend::new()
+                .with_string("email", "jane@")
+                .with_bool("consent", true),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn nps_runs_on_test_backend() {
+        let nps = NpsSurvey::builder()
+            .assume_reason(|opt| opt.none())
+            .run(
+                TestBackend::new()
+                    .with_int("score", 9)
+                    .with_bool("may_contact", false),
+            )
+            .unwrap();
+
+        assert_eq!(nps.score, 9);
+        assert_eq!(nps.category(), NpsCategory::Promoter);
+    }
+
+    #[test]
+    fn net_promoter_score_of_responses() {
+        let response = |score| NpsSurvey {
+            score,
+            reason: None,
+            may_contact: false,
+        };
+        let responses = [response(10), response(9), response(7), response(3)];
+
+        assert_eq!(net_promoter_score(&responses), Some(25));
+        assert_eq!(net_promoter_score(&[]), None);
+    }
+
+    #[test]
+    fn contact_form_rejects_invalid_email() {
+        let result = ContactForm::builder().assume_phone(|opt| opt.none()).run(
+            TestBackend::new()
+                .with_string("name", "Jane")
+                .with_string("email", "not-an-email")
+                .with_string("subject", "Hello")
+                .with_string("message", "Hi there")
+                .with_variant("reply_via.selected_variant", 0),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn translation_keys_cover_nested_fields() {
+        let keys = IncidentReport::survey().translation_keys();
+        let has = |key: &str| keys.iter().any(|(k, _)| k == key);
+
+        assert!(has("incident_report.title"));
+        assert!(has("incident_report.severity.critical"));
+        assert!(has("incident_report.impact.customers_affected"));
+        assert!(has("incident_report.reporter.email"));
+    }
+
+    #[test]
+    fn translation_replaces_known_keys() {
+        let german: HashMap<String, String> = [
+            ("contact_form.name", "Ihr Name:"),
+            ("contact_form.reply_via.phone", "Telefon"),
+        ]
+        .into_iter()
+        .map(|(key, text)| (key.to_string(), text.to_string()))
+        .collect();
+        let mut survey = ContactForm::survey();
+        survey.translate(&german);
+
+        assert_eq!(survey.questions[0].ask(), "Ihr Name:");
+        assert_eq!(survey.questions[1].ask(), "Email address:");
+        let phone = (
+            "contact_form.reply_via.phone".to_string(),
+            "Telefon".to_string(),
+        );
+        assert!(survey.translation_keys().contains(&phone));
+    }
+
+    #[derive(Survey, Debug)]
+    struct SupportTicket {
+        #[ask("Product:")]
+        product: String,
+
+        #[ask("Contact details:")]
+        contact: ContactForm,
+    }
+
+    #[test]
+    fn templates_nest_in_user_surveys() {
+        let german = |key: &str| (key == "contact_form.email").then(|| "E-Mail:".to_string());
+        let mut survey = SupportTicket::survey();
+        survey.translate(&german);
+
+        let QuestionKind::AllOf(contact) = survey.questions[1].kind() else {
+            panic!("Expected AllOf question kind");
+        };
+        assert_eq!(contact.questions()[1].ask(), "E-Mail:");
+
+        // Validators of the nested template still apply
+        let invalid = SupportTicket::validate_field(
+            &ResponseValue::String("nope".to_string()),
+            &Responses::new(),
+            &ResponsePath::new("contact.email"),
+        );
+        assert!(invalid.is_err());
+
+        // The builder translates the nested template as well
+        let plan = SupportTicket::builder()
+            .with_translator(german)
+            .plan()
+            .to_string();
+        assert!(plan.contains("E-Mail: (contact.email)"), "{plan}");
+    }
+
+    #[test]
+    fn nested_template_builders_and_responses() {
+        // The suggestion builder of a template is reachable from outer surveys
+        let _builder = SupportTicket::builder()
+            .suggest_product("Widget")
+            .assume_contact(|contact| {
+                contact
+                    .name("Jane")
+                    .email("jane@example.com")
+                    .phone(|opt| opt.none())
+                    .reply_via(|reply| reply.suggest_email())
+            });
+
+        let ticket = SupportTicket::builder()
+            .run(
+                TestBackend::new()
+                    .with_string("product", "Widget")
+                    .with_string("contact.name", "Jane")
+                    .with_string("contact.email", "jane@example.com")
+                    .with_string("contact.phone", "+49 30 123456")
+                    .with_string("contact.subject", "Login")
+                    .with_string("contact.message", "Cannot log in")
+                    .with_variant("contact.reply_via.selected_variant", 0),
+            )
+            .unwrap();
+
+        assert_eq!(ticket.product, "Widget");
+        assert_eq!(ticket.contact.email, "jane@example.com");
+        assert_eq!(ticket.contact.reply_via, ReplyChannel::Email);
+    }
+}
@@ -0,0 +1,65 @@
+//! Net Promoter Score survey template.
+
+use elicitor::Survey;
+
+/// The classic NPS bucket a score falls into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NpsCategory {
+    /// Scores 0 to 6.
+    Detractor,
+    /// Scores 7 and 8.
+    Passive,
+    /// Scores 9 and 10.
+    Promoter,
+}
+
+/// A Net Promoter Score survey: a 0-10 likelihood to recommend plus follow-ups.
+#[derive(Survey, Debug, Clone, PartialEq)]
+#[prelude("Thanks for taking a minute to give us feedback.")]
+#[epilogue("Thank you! Your feedback helps us improve.")]
+pub struct NpsSurvey {
+    #[ask(
+        "How likely are you to recommend us to a friend or colleague?",
+        key = "nps_survey.score"
+    )]
+    #[rating(0..=10)]
+    pub score: u8,
+
+    #[ask("What is the main reason for your score?", key = "nps_survey.reason")]
+    #[multiline]
+    pub reason: Option<String>,
+
+    #[ask(
+        "May we contact you about your feedback?",
+        key = "nps_survey.may_contact"
+    )]
+    pub may_contact: bool,
+}
+
+impl NpsSurvey {
+    /// The NPS bucket of this response.
+    pub fn category(&self) -> NpsCategory {
+        match self.score {
+            0..=6 => NpsCategory::Detractor,
+            7 | 8 => NpsCategory::Passive,
+            _ => NpsCategory::Promoter,
+        }
+    }
+}
+
+/// Compute the Net Promoter Score (-100 to 100) of a set of responses.
+///
+/// Returns `None` if there are no responses.
+pub fn net_promoter_score(responses: &[NpsSurvey]) -> Option<i32> {
+    if responses.is_empty() {
+        return None;
+    }
+    let count = |category| {
+        responses
+            .iter()
+            .filter(|response| response.category() == category)
+            .count() as i32
+    };
+    let total = responses.len() as i32;
+    Some((count(NpsCategory::Promoter) - count(NpsCategory::Detractor)) * 100 / total)
+}
@@ -0,0 +1,116 @@
+//! Employee onboarding checklist template.
+
+use elicitor::Survey;
+
+use crate::validators::{required, validate_date, validate_email};
+
+/// Hardware to prepare for the new hire.
+#[derive(Survey, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Equipment {
+    #[ask(key = "onboarding_checklist.equipment.laptop")]
+    Laptop,
+    #[ask(key = "onboarding_checklist.equipment.monitor")]
+    Monitor,
+    #[ask(key = "onboarding_checklist.equipment.keyboard")]
+    Keyboard,
+    #[ask(key = "onboarding_checklist.equipment.headset")]
+    Headset,
+    #[ask(key = "onboarding_checklist.equipment.phone")]
+    Phone,
+}
+
+/// Accounts to create for the new hire.
+#[derive(Survey, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Account {
+    #[ask(key = "onboarding_checklist.accounts.email")]
+    Email,
+    #[ask(key = "onboarding_checklist.accounts.chat")]
+    Chat,
+    #[ask(key = "onboarding_checklist.accounts.vpn")]
+    Vpn,
+    #[ask(key = "onboarding_checklist.accounts.code_hosting")]
+    CodeHosting,
+    #[ask(key = "onboarding_checklist.accounts.issue_tracker")]
+    IssueTracker,
+}
+
+/// Onboarding tasks that have already been completed.
+#[derive(Survey, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingTask {
+    #[ask(
+        "Contract signed",
+        key = "onboarding_checklist.completed.contract_signed"
+    )]
+    ContractSigned,
+
+    #[ask(
+        "Tax and payroll details received",
+        key = "onboarding_checklist.completed.payroll_details"
+    )]
+    PayrollDetails,
+
+    #[ask(
+        "Security training completed",
+        key = "onboarding_checklist.completed.security_training"
+    )]
+    SecurityTraining,
+
+    #[ask(
+        "Welcome meeting scheduled",
+        key = "onboarding_checklist.completed.welcome_meeting"
+    )]
+    WelcomeMeeting,
+}
+
+/// A checklist for preparing a new team member's first day.
+#[derive(Survey, Debug, Clone, PartialEq)]
+pub struct OnboardingChecklist {
+    #[ask("Full name of the new hire:", key = "onboarding_checklist.full_name")]
+    #[validate(required)]
+    pub full_name: String,
+
+    #[ask("Work email address:", key = "onboarding_checklist.work_email")]
+    #[validate(validate_email)]
+    pub work_email: String,
+
+    #[ask(
+        "First working day (YYYY-MM-DD):",
+        key = "onboarding_checklist.start_date"
+    )]
+    #[validate(validate_date)]
+    pub start_date: String,
+
+    #[ask("Team:", key = "onboarding_checklist.team")]
+    #[validate(required)]
+    pub team: String,
+
+    #[ask("Onboarding buddy (optional):", key = "onboarding_checklist.buddy")]
+    pub buddy: Option<String>,
+
+    #[ask("Equipment to prepare:", key = "onboarding_checklist.equipment")]
+    #[multiselect]
+    pub equipment: Vec<Equipment>,
+
+    #[ask("Accounts to create:", key = "onboarding_checklist.accounts")]
+    #[multiselect]
+    pub accounts: Vec<Account>,
+
+    #[ask("Already completed:", key = "onboarding_checklist.completed")]
+    #[multiselect]
+    pub completed: Vec<OnboardingTask>,
+}
+
+impl OnboardingChecklist {
+    /// Tasks that still need to be done before the first day.
+    pub fn open_tasks(&self) -> Vec<OnboardingTask> {
+        [
+            OnboardingTask::ContractSigned,
+            OnboardingTask::PayrollDetails,
+            OnboardingTask::SecurityTraining,
+            OnboardingTask::WelcomeMeeting,
+        ]
+        .into_iter()
+        .filter(|task| !self.completed.contains(task))
+        .collect()
+    }
+}
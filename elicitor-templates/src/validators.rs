@@ -0,0 +1,129 @@
+//! Field validators shared by the templates.
+//!
+//! All validators follow the `#[validate(fn_name)]` signature and can be reused
+//! on fields of your own surveys.
+
+use elicitor::{ResponsePath, ResponseValue, Responses};
+
+/// Rejects empty or whitespace-only text.
+pub fn required(
+    value: &ResponseValue,
+    _responses: &Responses,
+    _path: &ResponsePath,
+) -> Result<(), String> {
+    let ResponseValue::String(text) = value else {
+        return Ok(());
+    };
+    if text.trim().is_empty() {
+        return Err("This field is required".to_string());
+    }
+    Ok(())
+}
+
+/// Accepts addresses of the form `local@domain.tld`.
+pub fn validate_email(
+    value: &ResponseValue,
+    _responses: &Responses,
+    _path: &ResponsePath,
+) -> Result<(), String> {
+    let ResponseValue::String(email) = value else {
+        return Ok(());
+    };
+    let Some((local, domain)) = email.trim().split_once('@') else {
+        return Err("Email must contain '@'".to_string());
+    };
+    if local.is_empty() {
+        return Err("Email is missing the part before '@'".to_string());
+    }
+    if domain.contains('@') {
+        return Err("Email must contain exactly one '@'".to_string());
+    }
+    match domain.rsplit_once('.') {
+        Some((name, tld)) if !name.is_empty() && tld.len() >= 2 => Ok(()),
+        _ => Err("Email must end in a domain such as example.com".to_string()),
+    }
+}
+
+/// Accepts phone numbers with 6 to 15 digits, optionally with `+`, spaces, dashes and parentheses.
+pub fn validate_phone(
+    value: &ResponseValue,
+    _responses: &Responses,
+    _path: &ResponsePath,
+) -> Result<(), String> {
+    let ResponseValue::String(phone) = value else {
+        return Ok(());
+    };
+    if phone.is_empty() {
+        // Optional phone numbers may be left blank
+        return Ok(());
+    }
+    if !phone
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '+' | ' ' | '-' | '(' | ')'))
+    {
+        return Err(
+            "Phone number may only contain digits, spaces, '+', '-' and parentheses".to_string(),
+        );
+    }
+    let digits = phone.chars().filter(char::is_ascii_digit).count();
+    if !(6..=15).contains(&digits) {
+        return Err("Phone number must have between 6 and 15 digits".to_string());
+    }
+    Ok(())
+}
+
+/// Accepts dates in `YYYY-MM-DD` format.
+pub fn validate_date(
+    value: &ResponseValue,
+    _responses: &Responses,
+    _path: &ResponsePath,
+) -> Result<(), String> {
+    let ResponseValue::String(date) = value else {
+        return Ok(());
+    };
+    let parts: Vec<&str> = date.trim().split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return Err("Date must be in YYYY-MM-DD format".to_string());
+    };
+    let parsed = (
+        year.len() == 4,
+        month.parse::<u32>(),
+        day.parse::<u32>(),
+        year.parse::<u32>(),
+    );
+    match parsed {
+        (true, Ok(m @ 1..=12), Ok(d @ 1..=31), Ok(y)) if d <= days_in_month(y, m) => Ok(()),
+        _ => Err("Date must be in YYYY-MM-DD format".to_string()),
+    }
+}
+
+/// Accepts date and time in `YYYY-MM-DD HH:MM` format.
+pub fn validate_timestamp(
+    value: &ResponseValue,
+    responses: &Responses,
+    path: &ResponsePath,
+) -> Result<(), String> {
+    let ResponseValue::String(timestamp) = value else {
+        return Ok(());
+    };
+    let error = || "Time must be in YYYY-MM-DD HH:MM format".to_string();
+    let (date, time) = timestamp.trim().split_once(' ').ok_or_else(error)?;
+    validate_date(&ResponseValue::String(date.to_string()), responses, path)
+        .map_err(|_| error())?;
+    let (hours, minutes) = time.split_once(':').ok_or_else(error)?;
+    match (hours.parse::<u32>(), minutes.parse::<u32>()) {
+        (Ok(0..=23), Ok(0..=59)) if hours.len() == 2 && minutes.len() == 2 => Ok(()),
+        _ => Err(error()),
+    }
+}
+
+/// Number of days in `month` of `year`, in the Gregorian calendar.
+fn days_in_month(year: u32, month: u32) -> u32 {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
@@ -0,0 +1,60 @@
+//! Saving the answers of an unfinished run.
+//!
+//! Backends that let the user leave a survey half-way offer to save a draft
+//! through a [`Checkpoint`]. Where the draft goes is up to the application:
+//!
+//! ```rust,ignore
+//! let backend = RatatuiBackend::new().with_checkpoint(|draft: &Responses| {
+//!     store.save_draft("profile", draft).map_err(|e| e.to_string())
+//! });
+//! ```
+//!
+//! A saved draft is picked up again with the builder's `ask_missing`, which asks
+//! only the questions the draft leaves open.
+
+use std::fmt;
+
+use crate::Responses;
+
+/// Keeps the answers of a run the user leaves before finishing it.
+///
+/// Any `Fn(&Responses) -> Result<(), String>` closure implements this trait.
+pub trait Checkpoint: Send + Sync {
+    /// Save `draft`, or explain why it could not be saved.
+    fn save(&self, draft: &Responses) -> Result<(), String>;
+}
+
+impl<F> Checkpoint for F
+where
+    F: Fn(&Responses) -> Result<(), String> + Send + Sync,
+{
+    fn save(&self, draft: &Responses) -> Result<(), String> {
+        self(draft)
+    }
+}
+
+impl fmt::Debug for dyn Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Checkpoint")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn closure_checkpoint() {
+        let saved = Mutex::new(None);
+        let checkpoint = |draft: &Responses| {
+            *saved.lock().unwrap() = Some(draft.len());
+            Ok(())
+        };
+
+        let mut draft = Responses::new();
+        draft.insert("name", "Ada");
+        assert!(Checkpoint::save(&checkpoint, &draft).is_ok());
+        assert_eq!(*saved.lock().unwrap(), Some(1));
+    }
+}
@@ -0,0 +1,13 @@
+//! The current time, where the platform has a clock.
+
+use std::time::SystemTime;
+
+/// The current time, or `None` in the browser, where `SystemTime::now` panics
+/// without JavaScript bindings.
+pub(crate) fn now() -> Option<SystemTime> {
+    if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+        None
+    } else {
+        Some(SystemTime::now())
+    }
+}
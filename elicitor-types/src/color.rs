@@ -0,0 +1,79 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// An RGB color, answered with a color picker or as a hex string.
+///
+/// Stored in responses as a `ResponseValue::String` in the form `#rrggbb`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    /// Create a color from its red, green and blue components.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Format as a lowercase `#rrggbb` hex string.
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// Error returned when a string is not a hex color.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Expected a hex color like #1e90ff or #fff, got `{0}`")]
+pub struct ParseColorError(String);
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parse `#rrggbb` or the short form `#rgb`; the `#` is optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseColorError(s.to_string());
+        let hex = s.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+        let digit = |i: usize| u8::from_str_radix(&hex[i..=i], 16).map_err(|_| error());
+        let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| error());
+        match hex.len() {
+            3 => Ok(Self::rgb(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+            6 => Ok(Self::rgb(pair(0)?, pair(2)?, pair(4)?)),
+            _ => Err(error()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_long_and_short_hex() {
+        assert_eq!("#1e90ff".parse(), Ok(Color::rgb(0x1e, 0x90, 0xff)));
+        assert_eq!("1E90FF".parse(), Ok(Color::rgb(0x1e, 0x90, 0xff)));
+        assert_eq!("#fa0".parse(), Ok(Color::rgb(0xff, 0xaa, 0x00)));
+        assert!("#12345".parse::<Color>().is_err());
+        assert!("#gggggg".parse::<Color>().is_err());
+        assert!("".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn formats_as_lowercase_hex() {
+        assert_eq!(Color::rgb(30, 144, 255).to_string(), "#1e90ff");
+        let color: Color = "#ABCDEF".parse().unwrap();
+        assert_eq!(color.to_hex(), "#abcdef");
+    }
+}
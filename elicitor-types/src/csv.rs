@@ -0,0 +1,757 @@
+//! Bulk import of responses from CSV, e.g. a spreadsheet of paper forms typed in.
+//!
+//! [`csv_header`] writes the header row of a template with one column per answer, named
+//! by response path. [`from_csv_reader`] reads the filled-in spreadsheet back into one
+//! value per row and reports the rows that could not be imported:
+//!
+//! ```rust,ignore
+//! std::fs::write("applicants.csv", csv_header(&Applicant::survey()))?;
+//!
+//! // After the spreadsheet was filled in
+//! let import = from_csv_reader::<Applicant>(File::open("applicants.csv")?)?;
+//! for error in &import.errors {
+//!     eprintln!("{error}"); // e.g. "Row 4: Invalid answer for age: 200 is above 150"
+//! }
+//! ```
+//!
+//! Cells hold answers as they are typed:
+//!
+//! - yes/no questions `yes`, `no`, `true`, `false`, `1`, `0` or `x`, empty for no
+//! - selections the value or label of an option, lists items separated by `;`
+//! - matrix questions the column label, in one column per row named `<path>.<row>`
+//! - enums the variant name in `<path>.selected_variant`, multi-selects the variant
+//!   names separated by `;` in `<path>.selected_variants`
+//!
+//! The fields of enum variants have columns of their own, which are only read for the
+//! chosen variant. The fields of a multi-select variant are named `<path>.<variant>.*`.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use crate::{
+    COMMENT_KEY, DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, Survey,
+    SurveyDefinition, Variant, apply_assumed,
+};
+
+/// Separator of list items and multi-select choices within a cell.
+const ITEM_SEPARATOR: char = ';';
+
+/// Why a row could not be turned into a value.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CsvFieldError {
+    #[error("No answer for {0}")]
+    MissingValue(ResponsePath),
+
+    #[error("Invalid answer for {path}: {reason}")]
+    InvalidValue { path: ResponsePath, reason: String },
+
+    #[error("Unknown column {0:?}")]
+    UnknownColumn(String),
+
+    #[error("Expected at most {expected} cells, found {found}")]
+    TooManyCells { expected: usize, found: usize },
+}
+
+impl CsvFieldError {
+    /// Path of the answer the error is about, if it is about a single answer.
+    pub fn path(&self) -> Option<&ResponsePath> {
+        match self {
+            Self::MissingValue(path) | Self::InvalidValue { path, .. } => Some(path),
+            Self::UnknownColumn(_) | Self::TooManyCells { .. } => None,
+        }
+    }
+}
+
+/// A row of a spreadsheet that could not be imported.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("Row {row}: {error}")]
+pub struct CsvRowError {
+    /// Number of the row as a spreadsheet shows it, the header being row 1.
+    pub row: usize,
+    /// What is wrong with the row.
+    #[source]
+    pub error: CsvFieldError,
+}
+
+/// Errors that stop a whole spreadsheet from being imported.
+#[derive(Debug, thiserror::Error)]
+pub enum CsvError {
+    #[error("Failed to read CSV: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("The CSV has no header row")]
+    MissingHeader,
+
+    #[error("Unknown column {0:?}")]
+    UnknownColumn(String),
+
+    #[error("Unterminated quoted cell in row {0}")]
+    UnterminatedQuote(usize),
+}
+
+/// The result of importing a spreadsheet: the rows that could be read and the errors of
+/// those that could not.
+#[derive(Debug)]
+pub struct CsvImport<T> {
+    /// Values of the imported rows, in order.
+    pub records: Vec<T>,
+    /// Errors of the other rows, in order.
+    pub errors: Vec<CsvRowError>,
+}
+
+/// Response paths of the columns of a CSV template for `definition`.
+///
+/// Assumed answers have no column, they are filled in on import.
+pub fn csv_columns(definition: &SurveyDefinition) -> Vec<ResponsePath> {
+    let mut columns = Vec::new();
+    for question in definition.questions() {
+        collect_columns(question, &ResponsePath::empty(), &mut columns);
+    }
+    columns
+}
+
+/// The header row of a CSV template for `definition`, ending with a newline.
+pub fn csv_header(definition: &SurveyDefinition) -> String {
+    let mut header = csv_columns(definition)
+        .iter()
+        .map(|path| csv_field(path.as_str()))
+        .collect::<Vec<_>>()
+        .join(",");
+    header.push('\n');
+    header
+}
+
+/// Build `T` from one row of a spreadsheet, given its `header` row.
+///
+/// Answers are checked against bounds and choices and with the survey's validators,
+/// like backends do. Cells missing at the end of the row count as empty.
+pub fn from_csv_row<T: Survey>(header: &[&str], cells: &[&str]) -> Result<T, CsvFieldError> {
+    let responses = csv_row_to_responses(&T::survey(), header, cells)?;
+    for (path, value) in responses.iter() {
+        T::validate_field(value, &responses, path).map_err(|reason| {
+            CsvFieldError::InvalidValue {
+                path: path.clone(),
+                reason,
+            }
+        })?;
+    }
+    if let Some((path, reason)) = T::validate_all(&responses).into_iter().next() {
+        return Err(CsvFieldError::InvalidValue { path, reason });
+    }
+    Ok(T::from_responses(&responses))
+}
+
+/// Build one `T` per row of a spreadsheet whose first row is the header.
+///
+/// Empty rows are skipped. Rows that cannot be imported are reported in
+/// [`CsvImport::errors`] with their row number, the others returned in order.
+pub fn from_csv_reader<T: Survey>(mut reader: impl Read) -> Result<CsvImport<T>, CsvError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut records = parse_csv(&text)?.into_iter();
+    let header = records.next().ok_or(CsvError::MissingHeader)?;
+    let header: Vec<&str> = header.iter().map(String::as_str).collect();
+
+    let columns = csv_columns(&T::survey());
+    if let Some(unknown) = header
+        .iter()
+        .find(|name| !columns.iter().any(|path| path.as_str() == **name))
+    {
+        return Err(CsvError::UnknownColumn(unknown.to_string()));
+    }
+
+    let mut import = CsvImport {
+        records: Vec::new(),
+        errors: Vec::new(),
+    };
+    for (idx, record) in records.enumerate() {
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        let cells: Vec<&str> = record.iter().map(String::as_str).collect();
+        match from_csv_row(&header, &cells) {
+            Ok(value) => import.records.push(value),
+            Err(error) => import.errors.push(CsvRowError {
+                row: idx + 2,
+                error,
+            }),
+        }
+    }
+    Ok(import)
+}
+
+/// Map one row of a spreadsheet onto the questions of `definition`.
+///
+/// Values are converted to the question types and checked against their bounds and
+/// choices. Assumed answers are filled in. Every column of `header` has to be one of
+/// [`csv_columns`].
+pub fn csv_row_to_responses(
+    definition: &SurveyDefinition,
+    header: &[&str],
+    cells: &[&str],
+) -> Result<Responses, CsvFieldError> {
+    if cells.len() > header.len() {
+        return Err(CsvFieldError::TooManyCells {
+            expected: header.len(),
+            found: cells.len(),
+        });
+    }
+    let columns = csv_columns(definition);
+    if let Some(unknown) = header
+        .iter()
+        .find(|name| !columns.iter().any(|path| path.as_str() == **name))
+    {
+        return Err(CsvFieldError::UnknownColumn(unknown.to_string()));
+    }
+    let row = Row {
+        cells: header
+            .iter()
+            .zip(cells.iter().chain(std::iter::repeat(&"")))
+            .map(|(name, cell)| (*name, *cell))
+            .collect(),
+    };
+
+    let mut responses = Responses::new();
+    for question in definition.questions() {
+        row.import_question(question, &ResponsePath::empty(), &mut responses)?;
+    }
+    apply_assumed(definition, &mut responses);
+    Ok(responses)
+}
+
+/// Quote a CSV cell if it contains a separator, quote or line break.
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Split CSV text into records of cells. Quoted cells may contain separators, doubled
+/// quotes and line breaks.
+fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, CsvError> {
+    // Spreadsheets often save with a byte order mark
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            (true, '"') => quoted = false,
+            (true, c) => cell.push(c),
+            (false, '"') if cell.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut cell)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut cell));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => cell.push(c),
+        }
+    }
+    if quoted {
+        return Err(CsvError::UnterminatedQuote(records.len() + 1));
+    }
+    if !cell.is_empty() || !record.is_empty() {
+        record.push(cell);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn collect_columns(question: &Question, prefix: &ResponsePath, columns: &mut Vec<ResponsePath>) {
+    if let DefaultValue::Assumed(_) = question.default() {
+        return;
+    }
+    let path = if question.path().is_empty() {
+        prefix.clone()
+    } else {
+        prefix.child(question.path().as_str())
+    };
+    let mut push = |path: ResponsePath| {
+        if !columns.contains(&path) {
+            columns.push(path);
+        }
+    };
+
+    match question.kind() {
+        QuestionKind::Unit => {}
+        QuestionKind::AllOf(all_of) => {
+            for nested in all_of.questions() {
+                collect_columns(nested, &path, columns);
+            }
+        }
+        QuestionKind::Matrix(matrix_q) => {
+            for row in &matrix_q.rows {
+                push(path.child(row));
+            }
+        }
+        QuestionKind::OneOf(one_of) => {
+            push(path.child(SELECTED_VARIANT_KEY));
+            for variant in &one_of.variants {
+                collect_variant_columns(variant, &path, columns);
+            }
+        }
+        QuestionKind::AnyOf(any_of) => {
+            push(path.child(SELECTED_VARIANTS_KEY));
+            for variant in &any_of.variants {
+                if let QuestionKind::AllOf(all_of) = &variant.kind {
+                    let variant_path = path.child(&variant.name);
+                    for nested in all_of.questions() {
+                        collect_columns(nested, &variant_path, columns);
+                    }
+                }
+            }
+        }
+        _ => push(path.clone()),
+    }
+    if question.allows_comment() {
+        let comment = path.child(COMMENT_KEY);
+        if !columns.contains(&comment) {
+            columns.push(comment);
+        }
+    }
+}
+
+/// Columns of the fields of an enum variant, which go below the enum's path.
+fn collect_variant_columns(
+    variant: &Variant,
+    path: &ResponsePath,
+    columns: &mut Vec<ResponsePath>,
+) {
+    match &variant.kind {
+        QuestionKind::Unit => {}
+        QuestionKind::AllOf(all_of) => {
+            for nested in all_of.questions() {
+                collect_columns(nested, path, columns);
+            }
+        }
+        kind => {
+            let nested = Question::new(variant.name.as_str(), "", kind.clone());
+            collect_columns(&nested, path, columns);
+        }
+    }
+}
+
+/// Cells of a row by column name.
+struct Row<'a> {
+    cells: HashMap<&'a str, &'a str>,
+}
+
+impl Row<'_> {
+    fn get(&self, path: &ResponsePath) -> &str {
+        self.cells.get(path.as_str()).copied().unwrap_or_default()
+    }
+
+    fn import_question(
+        &self,
+        question: &Question,
+        prefix: &ResponsePath,
+        responses: &mut Responses,
+    ) -> Result<(), CsvFieldError> {
+        if let DefaultValue::Assumed(_) = question.default() {
+            return Ok(());
+        }
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+
+        if let Some(value) = self.convert(question.kind(), &path, responses)? {
+            responses.insert(path.clone(), value);
+        }
+        if question.allows_comment() {
+            let comment = self.get(&path.child(COMMENT_KEY));
+            if !comment.trim().is_empty() {
+                responses.insert(path.child(COMMENT_KEY), comment.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// The answer to a question of `kind`. Enums and groups store their answers
+    /// themselves and return `None`.
+    fn convert(
+        &self,
+        kind: &QuestionKind,
+        path: &ResponsePath,
+        responses: &mut Responses,
+    ) -> Result<Option<ResponseValue>, CsvFieldError> {
+        let text = self.get(path).trim();
+        let required = || {
+            Some(text)
+                .filter(|text| !text.is_empty())
+                .ok_or_else(|| CsvFieldError::MissingValue(path.clone()))
+        };
+        let value = match kind {
+            QuestionKind::Unit => return Ok(None),
+
+            QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
+            | QuestionKind::Multiline(_)
+            | QuestionKind::Masked(_)
+            | QuestionKind::Path(_) => {
+                // Keep the whitespace of text answers
+                ResponseValue::String(self.get(path).to_string())
+            }
+
+            QuestionKind::Int(int_q) => {
+                let value = parse::<i64>(required()?, path)?;
+                check_bounds(value, int_q.min, int_q.max, path)?;
+                ResponseValue::Int(value)
+            }
+
+            QuestionKind::Float(float_q) => {
+                let value = parse::<f64>(required()?, path)?;
+                check_bounds(value, float_q.min, float_q.max, path)?;
+                ResponseValue::Float(value)
+            }
+
+            QuestionKind::Currency(currency_q) => {
+                let minor = currency_q
+                    .parse(required()?)
+                    .and_then(|minor| currency_q.check_bounds(minor).map(|()| minor))
+                    .map_err(|reason| invalid(path, reason))?;
+                ResponseValue::Int(minor)
+            }
+
+            QuestionKind::Color(color_q) => {
+                let color = color_q
+                    .parse(required()?)
+                    .map_err(|reason| invalid(path, reason))?;
+                ResponseValue::String(color.to_hex())
+            }
+
+            QuestionKind::List(list_q) => {
+                let items = items(text);
+                match list_q.element_kind {
+                    ListElementKind::String => {
+                        ResponseValue::StringList(items.map(str::to_string).collect())
+                    }
+                    ListElementKind::Int { min, max } => ResponseValue::IntList(
+                        items
+                            .map(|item| {
+                                let value = parse::<i64>(item, path)?;
+                                check_bounds(value, min, max, path).map(|()| value)
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    ListElementKind::Float { min, max } => ResponseValue::FloatList(
+                        items
+                            .map(|item| {
+                                let value = parse::<f64>(item, path)?;
+                                check_bounds(value, min, max, path).map(|()| value)
+                            })
+                            .collect::<Result<_, _>>()?,
+                    ),
+                }
+            }
+
+            QuestionKind::Confirm(_) => {
+                let value = match text.to_ascii_lowercase().as_str() {
+                    "yes" | "y" | "true" | "1" | "x" => true,
+                    "" | "no" | "n" | "false" | "0" => false,
+                    _ => return Err(invalid(path, format!("{text:?} is not yes or no"))),
+                };
+                ResponseValue::Bool(value)
+            }
+
+            QuestionKind::Select(select_q) => {
+                let value = required()?;
+                let option = select_q
+                    .options
+                    .iter()
+                    .find(|option| option.value == value)
+                    .or_else(|| select_q.options.iter().find(|option| option.label == value))
+                    .ok_or_else(|| invalid(path, format!("{value:?} is not one of the choices")))?;
+                ResponseValue::String(option.value.clone())
+            }
+
+            QuestionKind::Rating(rating_q) => {
+                let value = parse::<i64>(required()?, path)?;
+                if !rating_q.contains(value) {
+                    return Err(invalid(path, format!("{value} is not on the scale")));
+                }
+                ResponseValue::Int(value)
+            }
+
+            QuestionKind::Matrix(matrix_q) => {
+                let columns = matrix_q
+                    .rows
+                    .iter()
+                    .map(|row| {
+                        let row_path = path.child(row);
+                        let column = self.get(&row_path).trim();
+                        if column.is_empty() {
+                            return Err(CsvFieldError::MissingValue(row_path));
+                        }
+                        matrix_q
+                            .columns
+                            .iter()
+                            .position(|label| label == column)
+                            .ok_or_else(|| {
+                                invalid(&row_path, format!("{column:?} is not one of the columns"))
+                            })
+                    })
+                    .collect::<Result<_, _>>()?;
+                ResponseValue::ChosenVariants(columns)
+            }
+
+            QuestionKind::OneOf(one_of) => {
+                let choice_path = path.child(SELECTED_VARIANT_KEY);
+                let name = self.get(&choice_path).trim();
+                if name.is_empty() {
+                    return Err(CsvFieldError::MissingValue(choice_path));
+                }
+                let idx = variant_index(&one_of.variants, name, &choice_path)?;
+                responses.insert(choice_path, ResponseValue::ChosenVariant(idx));
+
+                let variant = &one_of.variants[idx];
+                match &variant.kind {
+                    QuestionKind::Unit => {}
+                    QuestionKind::AllOf(all_of) => {
+                        for nested in all_of.questions() {
+                            self.import_question(nested, path, responses)?;
+                        }
+                    }
+                    kind => {
+                        let nested = Question::new(variant.name.as_str(), "", kind.clone());
+                        self.import_question(&nested, path, responses)?;
+                    }
+                }
+                return Ok(None);
+            }
+
+            QuestionKind::AnyOf(any_of) => {
+                let choices_path = path.child(SELECTED_VARIANTS_KEY);
+                let mut chosen = items(self.get(&choices_path))
+                    .map(|name| variant_index(&any_of.variants, name, &choices_path))
+                    .collect::<Result<Vec<_>, _>>()?;
+                chosen.sort_unstable();
+                chosen.dedup();
+
+                for (item, &idx) in chosen.iter().enumerate() {
+                    let item_path = path.child(&item.to_string());
+                    responses.insert(
+                        item_path.child(SELECTED_VARIANT_KEY),
+                        ResponseValue::ChosenVariant(idx),
+                    );
+                    let variant = &any_of.variants[idx];
+                    if let QuestionKind::AllOf(all_of) = &variant.kind {
+                        // Read below the variant's name, store below the item's position
+                        let variant_path = path.child(&variant.name);
+                        for nested in all_of.questions() {
+                            let mut answers = Responses::new();
+                            self.import_question(nested, &variant_path, &mut answers)?;
+                            for (answer_path, value) in answers.iter() {
+                                let relative = answer_path
+                                    .strip_path_prefix(&variant_path)
+                                    .unwrap_or_else(|| answer_path.clone());
+                                responses.insert(item_path.child(relative.as_str()), value.clone());
+                            }
+                        }
+                    }
+                }
+                responses.insert(choices_path, ResponseValue::ChosenVariants(chosen));
+                return Ok(None);
+            }
+
+            QuestionKind::AllOf(all_of) => {
+                for nested in all_of.questions() {
+                    self.import_question(nested, path, responses)?;
+                }
+                return Ok(None);
+            }
+        };
+        Ok(Some(value))
+    }
+}
+
+/// Non-empty items of a cell holding several.
+fn items(text: &str) -> impl Iterator<Item = &str> {
+    text.split(ITEM_SEPARATOR)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+/// Index of the variant called `name`, ignoring case, or at the index `name` spells.
+fn variant_index(
+    variants: &[Variant],
+    name: &str,
+    path: &ResponsePath,
+) -> Result<usize, CsvFieldError> {
+    variants
+        .iter()
+        .position(|variant| variant.name.eq_ignore_ascii_case(name))
+        .or_else(|| name.parse().ok().filter(|idx| *idx < variants.len()))
+        .ok_or_else(|| invalid(path, format!("{name:?} is not one of the choices")))
+}
+
+fn parse<N: std::str::FromStr>(text: &str, path: &ResponsePath) -> Result<N, CsvFieldError> {
+    text.parse()
+        .map_err(|_| invalid(path, format!("{text:?} is not a number")))
+}
+
+fn check_bounds<N: PartialOrd + std::fmt::Display>(
+    value: N,
+    min: Option<N>,
+    max: Option<N>,
+    path: &ResponsePath,
+) -> Result<(), CsvFieldError> {
+    match (min, max) {
+        (Some(min), _) if value < min => Err(invalid(path, format!("{value} is below {min}"))),
+        (_, Some(max)) if value > max => Err(invalid(path, format!("{value} is above {max}"))),
+        _ => Ok(()),
+    }
+}
+
+fn invalid(path: &ResponsePath, reason: String) -> CsvFieldError {
+    CsvFieldError::InvalidValue {
+        path: path.clone(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AllOfQuestion, AnyOfQuestion, ConfirmQuestion, InputQuestion, IntQuestion, OneOfQuestion,
+        Variant,
+    };
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "age",
+                "Age:",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new(
+                "member",
+                "Member?",
+                QuestionKind::Confirm(ConfirmQuestion::new()),
+            ),
+            Question::new(
+                "contact",
+                "Contact:",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("None"),
+                    Variant::new("Phone", QuestionKind::Input(InputQuestion::new())),
+                ])),
+            ),
+            Question::new(
+                "pets",
+                "Pets:",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![
+                    Variant::unit("Cat"),
+                    Variant::new(
+                        "Dog",
+                        QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                            "name",
+                            "Name:",
+                            QuestionKind::Input(InputQuestion::new()),
+                        )])),
+                    ),
+                ])),
+            ),
+        ])
+    }
+
+    #[test]
+    fn header_names_every_answer() {
+        assert_eq!(
+            csv_header(&definition()),
+            "name,age,member,contact.selected_variant,contact.Phone,pets.selected_variants,pets.Dog.name\n"
+        );
+    }
+
+    #[test]
+    fn rows_become_responses() {
+        let header = csv_columns(&definition());
+        let header: Vec<&str> = header.iter().map(ResponsePath::as_str).collect();
+        let responses = csv_row_to_responses(
+            &definition(),
+            &header,
+            &["Ada", " 36 ", "x", "phone", "555-1234", "dog; cat", "Rex"],
+        )
+        .unwrap();
+
+        let get = |path: &str| responses.get(&ResponsePath::new(path)).cloned();
+        assert_eq!(get("name"), Some(ResponseValue::String("Ada".into())));
+        assert_eq!(get("age"), Some(ResponseValue::Int(36)));
+        assert_eq!(get("member"), Some(ResponseValue::Bool(true)));
+        assert_eq!(
+            get("contact.selected_variant"),
+            Some(ResponseValue::ChosenVariant(1))
+        );
+        assert_eq!(
+            get("contact.Phone"),
+            Some(ResponseValue::String("555-1234".into()))
+        );
+        assert_eq!(
+            get("pets.selected_variants"),
+            Some(ResponseValue::ChosenVariants(vec![0, 1]))
+        );
+        assert_eq!(
+            get("pets.1.name"),
+            Some(ResponseValue::String("Rex".into()))
+        );
+    }
+
+    #[test]
+    fn errors_name_the_field() {
+        let header = ["name", "age", "contact.selected_variant"];
+        let row = |cells: &[&str]| csv_row_to_responses(&definition(), &header, cells);
+
+        assert_eq!(
+            row(&["Ada", "200", "None"]).unwrap_err(),
+            CsvFieldError::InvalidValue {
+                path: ResponsePath::new("age"),
+                reason: "200 is above 150".to_string(),
+            }
+        );
+        assert_eq!(
+            row(&["Ada", ""]).unwrap_err(),
+            CsvFieldError::MissingValue(ResponsePath::new("age"))
+        );
+        assert_eq!(
+            row(&["Ada", "1", "Fax"]).unwrap_err().path(),
+            Some(&ResponsePath::new("contact.selected_variant"))
+        );
+        assert!(matches!(
+            row(&["Ada", "1", "None", "extra"]),
+            Err(CsvFieldError::TooManyCells {
+                expected: 3,
+                found: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn quoted_cells_keep_separators_and_line_breaks() {
+        let records = parse_csv("\u{feff}a,b\r\n\"x, \"\"y\"\"\",\"line\nbreak\"\n,\n").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["a".to_string(), "b".to_string()],
+                vec!["x, \"y\"".to_string(), "line\nbreak".to_string()],
+                vec![String::new(), String::new()],
+            ]
+        );
+        assert!(matches!(
+            parse_csv("a\n\"open"),
+            Err(CsvError::UnterminatedQuote(2))
+        ));
+    }
+}
@@ -4,6 +4,7 @@ use crate::ResponseValue;
 ///
 /// Controls whether a question has a pre-filled value and whether it's shown to the user.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DefaultValue {
     /// No default value - user must provide input.
     #[default]
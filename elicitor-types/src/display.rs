@@ -0,0 +1,45 @@
+//! Display preferences shared by the backends of an application.
+//!
+//! Interactive backends let the respondent switch to a high-contrast mode with more
+//! spacing in the middle of a survey (F2 in the terminal backends, a button in the egui
+//! form). The switch only affects that backend, so one user of an SSH server doesn't
+//! change the display of another. The preference here is the mode surveys start in,
+//! set by the application, e.g. from a command line flag:
+//!
+//! ```rust,ignore
+//! elicitor::set_high_contrast(args.high_contrast);
+//! let config = Config::builder().run(RatatuiBackend::new())?;
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static HIGH_CONTRAST: AtomicBool = AtomicBool::new(false);
+
+/// Whether surveys start in high-contrast mode.
+pub fn high_contrast_enabled() -> bool {
+    HIGH_CONTRAST.load(Ordering::Relaxed)
+}
+
+/// Start the following surveys in high-contrast mode, or not.
+pub fn set_high_contrast(enabled: bool) {
+    HIGH_CONTRAST.store(enabled, Ordering::Relaxed);
+}
+
+/// Switch the mode surveys start in and return whether it is now high contrast.
+pub fn toggle_high_contrast() -> bool {
+    !HIGH_CONTRAST.fetch_xor(true, Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggling_flips_the_preference() {
+        let before = high_contrast_enabled();
+        assert_eq!(toggle_high_contrast(), !before);
+        assert_eq!(high_contrast_enabled(), !before);
+        set_high_contrast(before);
+        assert_eq!(high_contrast_enabled(), before);
+    }
+}
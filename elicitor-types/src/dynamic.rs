@@ -0,0 +1,358 @@
+//! Surveys built at runtime, without `#[derive(Survey)]`.
+//!
+//! A [`DynamicSurvey`] is assembled question by question, e.g. from a config file or a
+//! schema stored in a database, and returns the raw [`Responses`]:
+//!
+//! ```rust,ignore
+//! let survey = DynamicSurvey::builder()
+//!     .input("name", "Your name?")
+//!     .int("age", "Your age?")
+//!     .validate("age", |value, _| match value {
+//!         ResponseValue::Int(age) if *age < 18 => Err("Must be an adult".into()),
+//!         _ => Ok(()),
+//!     })
+//!     .select("plan", "Plan?", ["free", "pro"])
+//!     .build();
+//!
+//! let responses = survey.run(backend)?;
+//! let name = responses.get_string(&ResponsePath::new("name"))?;
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{
+    AllOfQuestion, AnyOfQuestion, ConfirmQuestion, FloatQuestion, InputQuestion, IntQuestion,
+    MaskedQuestion, MultilineQuestion, OneOfQuestion, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SelectOption, SelectQuestion, SurveyBackend, SurveyDefinition,
+    SurveyObserver, Variant,
+};
+
+/// Validates the answer to a single question of a [`DynamicSurvey`].
+///
+/// Receives the answer and all responses collected so far.
+pub type DynamicValidator =
+    Box<dyn Fn(&ResponseValue, &Responses) -> Result<(), String> + Send + Sync>;
+
+/// A survey defined at runtime.
+pub struct DynamicSurvey {
+    definition: SurveyDefinition,
+    validators: HashMap<ResponsePath, DynamicValidator>,
+    observers: Vec<Arc<dyn SurveyObserver>>,
+}
+
+impl DynamicSurvey {
+    /// Start building a survey.
+    pub fn builder() -> DynamicSurveyBuilder {
+        DynamicSurveyBuilder::default()
+    }
+
+    /// Wrap an existing definition, e.g. one deserialized or generated elsewhere.
+    pub fn new(definition: SurveyDefinition) -> Self {
+        Self {
+            definition,
+            validators: HashMap::new(),
+            observers: Vec::new(),
+        }
+    }
+
+    /// The survey structure, e.g. to pass to a document generator.
+    pub fn definition(&self) -> &SurveyDefinition {
+        &self.definition
+    }
+
+    /// Get a mutable reference to the survey structure, e.g. to set suggestions.
+    pub fn definition_mut(&mut self) -> &mut SurveyDefinition {
+        &mut self.definition
+    }
+
+    /// Report progress to `observer` while the survey runs.
+    pub fn with_observer(mut self, observer: impl SurveyObserver + 'static) -> Self {
+        self.observers.push(Arc::new(observer));
+        self
+    }
+
+    /// Check an answer against the validator registered for `path`, if any.
+    pub fn validate_field(
+        &self,
+        value: &ResponseValue,
+        responses: &Responses,
+        path: &ResponsePath,
+    ) -> Result<(), String> {
+        match self.validators.get(path) {
+            Some(validate) => validate(value, responses),
+            None => Ok(()),
+        }
+    }
+
+    /// Run the survey and return the collected responses.
+    ///
+    /// Masked answers are redacted like for derived surveys.
+    pub fn run<B: SurveyBackend>(&self, backend: B) -> Result<Responses, anyhow::Error> {
+        let observers = &self.observers;
+        let mut responses = backend
+            .collect_observed(
+                &self.definition,
+                &|value, responses, path| {
+                    let result = self.validate_field(value, responses, path);
+                    match &result {
+                        Ok(()) => observers.on_answer(path, value),
+                        Err(message) => observers.on_validation_error(path, message),
+                    }
+                    result
+                },
+                observers,
+            )
+            .map_err(Into::into)?;
+        responses.redact_masked(&self.definition);
+        responses.record_assumed_origins(&self.definition);
+        responses.touch();
+        Ok(responses)
+    }
+}
+
+impl fmt::Debug for DynamicSurvey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut validated: Vec<_> = self.validators.keys().map(ResponsePath::as_str).collect();
+        validated.sort_unstable();
+        f.debug_struct("DynamicSurvey")
+            .field("definition", &self.definition)
+            .field("validated", &validated)
+            .field("observers", &self.observers.len())
+            .finish()
+    }
+}
+
+impl From<SurveyDefinition> for DynamicSurvey {
+    fn from(definition: SurveyDefinition) -> Self {
+        Self::new(definition)
+    }
+}
+
+/// Builder for a [`DynamicSurvey`].
+///
+/// Questions are asked in the order they are added.
+#[derive(Default)]
+pub struct DynamicSurveyBuilder {
+    prelude: Option<String>,
+    epilogue: Option<String>,
+    questions: Vec<Question>,
+    validators: HashMap<ResponsePath, DynamicValidator>,
+}
+
+impl DynamicSurveyBuilder {
+    /// Set the message shown before the survey starts.
+    pub fn prelude(mut self, prelude: impl Into<String>) -> Self {
+        self.prelude = Some(prelude.into());
+        self
+    }
+
+    /// Set the message shown after the survey completes.
+    pub fn epilogue(mut self, epilogue: impl Into<String>) -> Self {
+        self.epilogue = Some(epilogue.into());
+        self
+    }
+
+    /// Add a question.
+    pub fn question(mut self, question: Question) -> Self {
+        self.questions.push(question);
+        self
+    }
+
+    /// Ask for a line of text.
+    pub fn input(self, path: &str, ask: impl Into<String>) -> Self {
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::Input(InputQuestion::new()),
+        ))
+    }
+
+    /// Ask for multiple lines of text.
+    pub fn multiline(self, path: &str, ask: impl Into<String>) -> Self {
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::Multiline(MultilineQuestion::new()),
+        ))
+    }
+
+    /// Ask for text with masked input.
+    pub fn masked(self, path: &str, ask: impl Into<String>) -> Self {
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::Masked(MaskedQuestion::new()),
+        ))
+    }
+
+    /// Ask for an integer.
+    pub fn int(self, path: &str, ask: impl Into<String>) -> Self {
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::Int(IntQuestion::new()),
+        ))
+    }
+
+    /// Ask for a floating point number.
+    pub fn float(self, path: &str, ask: impl Into<String>) -> Self {
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::Float(FloatQuestion::new()),
+        ))
+    }
+
+    /// Ask a yes/no question.
+    pub fn confirm(self, path: &str, ask: impl Into<String>) -> Self {
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::Confirm(ConfirmQuestion::new()),
+        ))
+    }
+
+    /// Pick one of `options`. The chosen option is stored as a string.
+    pub fn select<I, O>(self, path: &str, ask: impl Into<String>, options: I) -> Self
+    where
+        I: IntoIterator<Item = O>,
+        O: Into<SelectOption>,
+    {
+        let options = options.into_iter().map(Into::into).collect();
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::Select(SelectQuestion::new(options)),
+        ))
+    }
+
+    /// Pick one of `variants`, each with its own follow-up questions.
+    ///
+    /// The index of the chosen variant is stored under `SELECTED_VARIANT_KEY` below `path`.
+    pub fn one_of(
+        self,
+        path: &str,
+        ask: impl Into<String>,
+        variants: impl IntoIterator<Item = Variant>,
+    ) -> Self {
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::OneOf(OneOfQuestion::new(variants.into_iter().collect())),
+        ))
+    }
+
+    /// Pick any number of `variants`.
+    ///
+    /// The indices of the chosen variants are stored under `SELECTED_VARIANTS_KEY` below
+    /// `path`.
+    pub fn any_of(
+        self,
+        path: &str,
+        ask: impl Into<String>,
+        variants: impl IntoIterator<Item = Variant>,
+    ) -> Self {
+        self.question(Question::new(
+            path,
+            ask,
+            QuestionKind::AnyOf(AnyOfQuestion::new(variants.into_iter().collect())),
+        ))
+    }
+
+    /// Add a group of questions stored below `path`.
+    ///
+    /// Validators registered inside the group use paths relative to the group.
+    pub fn group(self, path: &str, build: impl FnOnce(Self) -> Self) -> Self {
+        let inner = build(Self::default());
+        let prefix = ResponsePath::new(path);
+        let mut this = self.question(Question::new(
+            path,
+            "",
+            QuestionKind::AllOf(AllOfQuestion::new(inner.questions)),
+        ));
+        this.validators.extend(
+            inner
+                .validators
+                .into_iter()
+                .map(|(inner_path, validate)| (prefix.child(inner_path.as_str()), validate)),
+        );
+        this
+    }
+
+    /// Validate the answer at `path`.
+    ///
+    /// The validator receives the answer and all responses collected so far. A second
+    /// validator for the same path replaces the first.
+    pub fn validate(
+        mut self,
+        path: &str,
+        validate: impl Fn(&ResponseValue, &Responses) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.validators
+            .insert(ResponsePath::new(path), Box::new(validate));
+        self
+    }
+
+    /// Finish the survey.
+    pub fn build(self) -> DynamicSurvey {
+        let mut definition = SurveyDefinition::new(self.questions);
+        definition.prelude = self.prelude;
+        definition.epilogue = self.epilogue;
+        DynamicSurvey {
+            definition,
+            validators: self.validators,
+            observers: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for DynamicSurveyBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicSurveyBuilder")
+            .field("prelude", &self.prelude)
+            .field("epilogue", &self.epilogue)
+            .field("questions", &self.questions)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_prefix_paths_and_validators() {
+        let survey = DynamicSurvey::builder()
+            .input("name", "Name?")
+            .group("address", |group| {
+                group
+                    .input("city", "City?")
+                    .validate("city", |value, _| match value {
+                        ResponseValue::String(city) if city.is_empty() => {
+                            Err("City is required".to_string())
+                        }
+                        _ => Ok(()),
+                    })
+            })
+            .build();
+
+        assert_eq!(survey.definition().len(), 2);
+        let empty = ResponseValue::String(String::new());
+        assert!(
+            survey
+                .validate_field(
+                    &empty,
+                    &Responses::new(),
+                    &ResponsePath::new("address.city")
+                )
+                .is_err()
+        );
+        assert!(
+            survey
+                .validate_field(&empty, &Responses::new(), &ResponsePath::new("name"))
+                .is_ok()
+        );
+    }
+}
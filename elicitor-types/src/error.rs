@@ -1,13 +1,22 @@
+use crate::Responses;
+use crate::question_bank::BankError;
+
 /// Error type for survey operations.
 #[derive(Debug, thiserror::Error)]
 pub enum SurveyError {
     /// User cancelled the survey (Ctrl+C, closed window, etc.)
+    ///
+    /// Holds the answers given before cancelling, e.g. to save them as a draft.
     #[error("Survey cancelled by user")]
-    Cancelled,
+    Cancelled { partial: Box<Responses> },
 
     /// Backend-specific failure (I/O, UI framework crash, etc.)
     #[error("Backend error: {0}")]
     Backend(#[from] anyhow::Error),
+
+    /// A `#[use_question]` field has no fitting question in the question bank.
+    #[error(transparent)]
+    QuestionBank(#[from] BankError),
 }
 
 impl SurveyError {
@@ -18,6 +27,14 @@ impl SurveyError {
 
     /// Check if this error represents user cancellation.
     pub fn is_cancelled(&self) -> bool {
-        matches!(self, Self::Cancelled)
+        matches!(self, Self::Cancelled { .. })
+    }
+
+    /// The answers given before the user cancelled, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            Self::Backend(_) | Self::QuestionBank(_) => None,
+        }
     }
 }
@@ -0,0 +1,230 @@
+//! A/B testing of prompt wording.
+//!
+//! A [`PromptExperiment`] holds alternative prompts for some questions and picks one
+//! variant per question for each session. Variant 0 is the prompt from `#[ask]`,
+//! alternatives are numbered from 1 in the order they were added. The shown variant is
+//! recorded in the response metadata under `prompt_variant.<path>`:
+//!
+//! ```rust,ignore
+//! let experiment = PromptExperiment::new(VariantStrategy::Bucket(user_id))
+//!     .with_variants("email", ["Where can we reach you?"]);
+//!
+//! let (signup, responses) = experiment.run::<Signup, _>(backend)?;
+//! let shown = PromptExperiment::shown_variant(&responses, &ResponsePath::new("email"));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+
+use crate::{
+    Question, QuestionKind, ResponsePath, Responses, Survey, SurveyBackend, SurveyDefinition,
+};
+
+/// Metadata key prefix for recorded prompt variants.
+pub const PROMPT_VARIANT_METADATA_PREFIX: &str = "prompt_variant";
+
+/// How a [`PromptExperiment`] picks the variant shown for a question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantStrategy {
+    /// Pick at random once per experiment, i.e. per session.
+    Random,
+    /// Pick deterministically from a bucket id, e.g. a hashed user id.
+    ///
+    /// The same bucket always sees the same variants.
+    Bucket(u64),
+}
+
+/// Alternative prompts for questions of a survey.
+#[derive(Debug, Clone)]
+pub struct PromptExperiment {
+    seed: u64,
+    variants: HashMap<ResponsePath, Vec<String>>,
+}
+
+impl PromptExperiment {
+    /// Create an experiment without alternative prompts.
+    pub fn new(strategy: VariantStrategy) -> Self {
+        let seed = match strategy {
+            VariantStrategy::Random => RandomState::new().hash_one(0u8),
+            VariantStrategy::Bucket(bucket) => bucket,
+        };
+        Self {
+            seed,
+            variants: HashMap::new(),
+        }
+    }
+
+    /// Add alternative prompts for the question at `path`.
+    pub fn with_variants(
+        mut self,
+        path: impl Into<ResponsePath>,
+        prompts: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.variants
+            .entry(path.into())
+            .or_default()
+            .extend(prompts.into_iter().map(Into::into));
+        self
+    }
+
+    /// The variant shown for the question at `path`, out of `count` variants.
+    pub fn variant_for(&self, path: &ResponsePath, count: usize) -> usize {
+        if count == 0 {
+            return 0;
+        }
+        (fnv1a(self.seed, path.as_str()) % count as u64) as usize
+    }
+
+    /// Replace prompts in the definition with the picked variants.
+    ///
+    /// Returns the picked variant of every question that has alternatives.
+    pub fn apply(&self, definition: &mut SurveyDefinition) -> HashMap<ResponsePath, usize> {
+        let mut shown = HashMap::new();
+        self.apply_to(
+            &mut definition.questions,
+            &ResponsePath::empty(),
+            &mut shown,
+        );
+        shown
+    }
+
+    /// Run a survey with this experiment and return the value together with the responses.
+    ///
+    /// The responses carry the shown variant of every question with alternatives as metadata.
+    pub fn run<T: Survey, B: SurveyBackend>(&self, backend: B) -> anyhow::Result<(T, Responses)> {
+        let mut definition = T::survey();
+        let shown = self.apply(&mut definition);
+
+        let mut responses = backend
+            .collect(&definition, &|value, responses, path| {
+                T::validate_field(value, responses, path)
+            })
+            .map_err(Into::into)?;
+        responses.redact_masked(&definition);
+        responses.touch();
+        for (path, variant) in shown {
+            responses.insert_metadata(metadata_key(&path), variant.to_string());
+        }
+
+        Ok((T::from_responses(&responses), responses))
+    }
+
+    /// The variant recorded for the question at `path`, if it was part of an experiment.
+    pub fn shown_variant(responses: &Responses, path: &ResponsePath) -> Option<usize> {
+        responses.metadata(&metadata_key(path))?.parse().ok()
+    }
+
+    fn apply_to(
+        &self,
+        questions: &mut [Question],
+        prefix: &ResponsePath,
+        shown: &mut HashMap<ResponsePath, usize>,
+    ) {
+        for question in questions {
+            let path = if question.path().is_empty() {
+                prefix.clone()
+            } else if prefix.is_empty() {
+                question.path().clone()
+            } else {
+                prefix.child(question.path().as_str())
+            };
+
+            if let Some(alternatives) = self.variants.get(&path) {
+                let variant = self.variant_for(&path, alternatives.len() + 1);
+                if variant > 0 {
+                    question.set_prompt(alternatives[variant - 1].clone());
+                }
+                shown.insert(path.clone(), variant);
+            }
+
+            match question.kind_mut() {
+                QuestionKind::AllOf(all_of) => self.apply_to(all_of.questions_mut(), &path, shown),
+                QuestionKind::OneOf(one_of) => {
+                    for variant in one_of.variants_mut() {
+                        if let QuestionKind::AllOf(all_of) = &mut variant.kind {
+                            self.apply_to(all_of.questions_mut(), &path, shown);
+                        }
+                    }
+                }
+                QuestionKind::AnyOf(any_of) => {
+                    for variant in &mut any_of.variants {
+                        if let QuestionKind::AllOf(all_of) = &mut variant.kind {
+                            self.apply_to(all_of.questions_mut(), &path, shown);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn metadata_key(path: &ResponsePath) -> String {
+    format!("{PROMPT_VARIANT_METADATA_PREFIX}.{}", path.as_str())
+}
+
+/// FNV-1a, so that bucket assignments are stable across builds and platforms.
+fn fnv1a(seed: u64, text: &str) -> u64 {
+    seed.to_le_bytes()
+        .iter()
+        .chain(text.as_bytes())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputQuestion, ResponseValue};
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            Question::new("email", "Email:", QuestionKind::Input(InputQuestion::new())),
+        ])
+    }
+
+    #[test]
+    fn buckets_pick_stable_variants() {
+        let experiment = PromptExperiment::new(VariantStrategy::Bucket(7))
+            .with_variants("email", ["Your email:", "Where can we reach you?"]);
+
+        let mut first = definition();
+        let mut second = definition();
+        let shown = experiment.apply(&mut first);
+        assert_eq!(shown, experiment.apply(&mut second));
+        assert_eq!(first.questions[1].ask(), second.questions[1].ask());
+
+        let variant = shown[&ResponsePath::new("email")];
+        let expected = ["Email:", "Your email:", "Where can we reach you?"][variant];
+        assert_eq!(first.questions[1].ask(), expected);
+        assert_eq!(first.questions[0].ask(), "Name:");
+        assert!(!shown.contains_key(&ResponsePath::new("name")));
+    }
+
+    #[test]
+    fn buckets_cover_all_variants() {
+        let path = ResponsePath::new("email");
+        let mut seen = [false; 3];
+        for bucket in 0..100 {
+            let experiment = PromptExperiment::new(VariantStrategy::Bucket(bucket));
+            seen[experiment.variant_for(&path, 3)] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+    }
+
+    #[test]
+    fn shown_variant_reads_metadata() {
+        let mut responses = Responses::new();
+        responses.insert("email", ResponseValue::String("a@b.c".to_string()));
+        responses.insert_metadata("prompt_variant.email", "2");
+
+        let path = ResponsePath::new("email");
+        assert_eq!(PromptExperiment::shown_variant(&responses, &path), Some(2));
+        assert_eq!(
+            PromptExperiment::shown_variant(&responses, &ResponsePath::new("name")),
+            None
+        );
+    }
+}
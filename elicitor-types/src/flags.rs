@@ -0,0 +1,271 @@
+//! Questions switched on and off at runtime.
+//!
+//! Questions can be guarded by a feature flag with [`Question::with_flag`]. Before a survey
+//! runs, [`apply_flags`] asks a [`FlagProvider`] about every flag and takes the questions
+//! whose flag is off out of the survey, so operators can stop collecting sensitive answers
+//! without a new release:
+//!
+//! ```rust,ignore
+//! #[derive(Survey)]
+//! struct Onboarding {
+//!     #[ask("Your name:")]
+//!     name: String,
+//!
+//!     #[ask("Expected salary:")]
+//!     #[flag("collect_salary")]
+//!     salary: Option<u32>,
+//! }
+//!
+//! // Asked only while ELICITOR_FLAG_COLLECT_SALARY=1
+//! let onboarding = Onboarding::builder()
+//!     .with_flags(EnvFlags::new())
+//!     .run(backend)?;
+//!
+//! // Or ask a flag service
+//! let onboarding = Onboarding::builder()
+//!     .with_flags(|flag: &str| client.bool_variation(&context, flag, false))
+//!     .run(backend)?;
+//! ```
+//!
+//! A question whose flag is off keeps its suggested value if it has one and is left
+//! unanswered otherwise, which is why the derive requires an `Option` field.
+
+use std::collections::HashMap;
+
+use crate::{DefaultValue, Question, QuestionKind, ResponsePath, SurveyDefinition};
+
+/// Environment variable prefix used by [`EnvFlags::new`].
+pub const FLAG_ENV_PREFIX: &str = "ELICITOR_FLAG_";
+
+/// Decides whether a feature flag is on.
+///
+/// Implemented for closures, so a client of a remote flag service can be wrapped in place.
+pub trait FlagProvider {
+    /// Whether the questions guarded by `flag` are asked.
+    fn is_enabled(&self, flag: &str) -> bool;
+}
+
+impl<F: Fn(&str) -> bool> FlagProvider for F {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self(flag)
+    }
+}
+
+/// Flags read from environment variables, e.g. `ELICITOR_FLAG_COLLECT_SALARY=1` for the
+/// flag `collect_salary`.
+///
+/// `1`, `true`, `yes` and `on` turn a flag on, `0`, `false`, `no` and `off` turn it off.
+/// Unset flags are off unless changed with [`EnvFlags::with_default`].
+#[derive(Debug, Clone)]
+pub struct EnvFlags {
+    prefix: String,
+    default: bool,
+}
+
+impl EnvFlags {
+    /// Read flags from variables starting with [`FLAG_ENV_PREFIX`].
+    pub fn new() -> Self {
+        Self::with_prefix(FLAG_ENV_PREFIX)
+    }
+
+    /// Read flags from variables starting with `prefix`, e.g. `MYAPP_`.
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            default: false,
+        }
+    }
+
+    /// Whether flags without a variable are on.
+    pub fn with_default(mut self, enabled: bool) -> Self {
+        self.default = enabled;
+        self
+    }
+
+    /// The variable holding `flag`.
+    pub fn variable(&self, flag: &str) -> String {
+        let name: String = flag
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}{name}", self.prefix)
+    }
+}
+
+impl Default for EnvFlags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlagProvider for EnvFlags {
+    fn is_enabled(&self, flag: &str) -> bool {
+        let Ok(value) = std::env::var(self.variable(flag)) else {
+            return self.default;
+        };
+        match value.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" => false,
+            _ => self.default,
+        }
+    }
+}
+
+/// Flags fixed in code, e.g. for tests. Unknown flags are off.
+#[derive(Debug, Clone, Default)]
+pub struct StaticFlags {
+    flags: HashMap<String, bool>,
+}
+
+impl StaticFlags {
+    /// No flag is on.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Turn `flag` on or off.
+    pub fn with_flag(mut self, flag: impl Into<String>, enabled: bool) -> Self {
+        self.flags.insert(flag.into(), enabled);
+        self
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, bool)> for StaticFlags {
+    fn from_iter<I: IntoIterator<Item = (S, bool)>>(iter: I) -> Self {
+        Self {
+            flags: iter
+                .into_iter()
+                .map(|(flag, enabled)| (flag.into(), enabled))
+                .collect(),
+        }
+    }
+}
+
+impl FlagProvider for StaticFlags {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self.flags.get(flag).copied().unwrap_or(false)
+    }
+}
+
+/// Take the questions whose flag is off out of `definition`.
+///
+/// Questions with a suggested value keep it as an assumption, all others are removed and
+/// stay unanswered. Returns the paths of the questions switched off.
+pub fn apply_flags(
+    definition: &mut SurveyDefinition,
+    flags: &dyn FlagProvider,
+) -> Vec<ResponsePath> {
+    let mut disabled = Vec::new();
+    switch_off(
+        &mut definition.questions,
+        &ResponsePath::empty(),
+        flags,
+        &mut disabled,
+    );
+    disabled
+}
+
+fn switch_off(
+    questions: &mut Vec<Question>,
+    prefix: &ResponsePath,
+    flags: &dyn FlagProvider,
+    disabled: &mut Vec<ResponsePath>,
+) {
+    questions.retain_mut(|question| {
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+
+        if question.flag().is_some_and(|flag| !flags.is_enabled(flag)) {
+            let keep = match question.default() {
+                DefaultValue::Assumed(_) => true,
+                DefaultValue::Suggested(value) if !question.kind().is_structural() => {
+                    question.set_assumption(value.clone());
+                    true
+                }
+                _ => false,
+            };
+            disabled.push(path);
+            return keep;
+        }
+
+        match question.kind_mut() {
+            QuestionKind::AllOf(all_of) => {
+                switch_off(all_of.questions_mut(), &path, flags, disabled);
+            }
+            QuestionKind::OneOf(one_of) => {
+                for variant in one_of.variants_mut() {
+                    if let QuestionKind::AllOf(all_of) = &mut variant.kind {
+                        switch_off(all_of.questions_mut(), &path, flags, disabled);
+                    }
+                }
+            }
+            _ => {}
+        }
+        true
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IntQuestion, ResponseValue};
+
+    fn definition() -> SurveyDefinition {
+        let int = || QuestionKind::Int(IntQuestion::new());
+        SurveyDefinition::new(vec![
+            Question::new("age", "Age:", int()),
+            Question::new("salary", "Salary:", int()).with_flag("collect_salary"),
+            Question::new("bonus", "Bonus:", int()).with_flag("collect_salary"),
+        ])
+    }
+
+    #[test]
+    fn disabled_questions_are_left_out() {
+        let mut definition = definition();
+        definition.questions[2].set_suggestion(ResponseValue::Int(0));
+
+        let disabled = apply_flags(&mut definition, &StaticFlags::new());
+        assert_eq!(
+            disabled,
+            vec![ResponsePath::new("salary"), ResponsePath::new("bonus")]
+        );
+        let paths: Vec<_> = definition.questions.iter().map(|q| q.path()).collect();
+        assert_eq!(
+            paths,
+            [&ResponsePath::new("age"), &ResponsePath::new("bonus")]
+        );
+        assert!(definition.questions[1].is_assumed());
+    }
+
+    #[test]
+    fn enabled_questions_are_asked() {
+        let mut definition = definition();
+        let flags: StaticFlags = [("collect_salary", true)].into_iter().collect();
+        assert!(apply_flags(&mut definition, &flags).is_empty());
+        assert_eq!(definition.questions.len(), 3);
+
+        let mut definition = self::definition();
+        apply_flags(&mut definition, &|flag: &str| flag == "collect_salary");
+        assert_eq!(definition.questions.len(), 3);
+    }
+
+    #[test]
+    fn env_flags_name_variables_after_the_flag() {
+        let flags = EnvFlags::with_prefix("SURVEY_FLAG_TEST_");
+        assert_eq!(
+            flags.variable("collect-salary"),
+            "SURVEY_FLAG_TEST_COLLECT_SALARY"
+        );
+        assert!(!flags.is_enabled("never_set"));
+        assert!(flags.clone().with_default(true).is_enabled("never_set"));
+    }
+}
@@ -0,0 +1,117 @@
+//! Links from printed forms to their online version.
+//!
+//! Document generators print a [`FormLink`] as a QR code, so respondents of a paper form
+//! can switch to the web version. A link either points at a fixed URL or carries a form
+//! ID generated for the printed copy, which the web version can use to match answers to
+//! the paper they started on:
+//!
+//! ```rust,ignore
+//! let link = FormLink::with_generated_id("https://example.com/apply");
+//! // e.g. https://example.com/apply?form=7KQ2M9XD
+//! let options = LatexOptions::new().with_qr_code(link);
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Query parameter that carries the form ID.
+pub const FORM_ID_PARAMETER: &str = "form";
+
+/// Length of generated form IDs.
+const ID_LENGTH: usize = 8;
+
+/// Crockford's base32 alphabet, without letters that are easily confused when typed.
+const ID_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Link from a printed form to its online version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormLink {
+    url: String,
+    id: Option<String>,
+}
+
+impl FormLink {
+    /// Link to `url` as is.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            id: None,
+        }
+    }
+
+    /// Link to `base_url` with `id` in the `form` query parameter.
+    pub fn with_id(base_url: impl Into<String>, id: impl Into<String>) -> Self {
+        let mut url = base_url.into();
+        let id = id.into();
+        let separator = if url.contains('?') { '&' } else { '?' };
+        url.push(separator);
+        url.push_str(FORM_ID_PARAMETER);
+        url.push('=');
+        url.push_str(&id);
+        Self { url, id: Some(id) }
+    }
+
+    /// Link to `base_url` with a new, random form ID.
+    ///
+    /// Every call generates a different ID, so generate one link per printed copy.
+    pub fn with_generated_id(base_url: impl Into<String>) -> Self {
+        Self::with_id(base_url, generate_id())
+    }
+
+    /// The full URL, including the form ID.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The form ID, printed next to the code so it can be typed in as well.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+/// A random ID of [`ID_LENGTH`] characters from [`ID_ALPHABET`].
+fn generate_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    // The std hasher is randomly keyed per process, the counter and time tell calls apart
+    let mut hasher = RandomState::new().build_hasher();
+    COUNTER.fetch_add(1, Ordering::Relaxed).hash(&mut hasher);
+    crate::clock::now().hash(&mut hasher);
+    let mut bits = hasher.finish();
+
+    (0..ID_LENGTH)
+        .map(|_| {
+            let c = ID_ALPHABET[(bits % 32) as usize] as char;
+            bits /= 32;
+            c
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_go_in_the_query() {
+        let link = FormLink::with_id("https://example.com/apply", "ABC");
+        assert_eq!(link.url(), "https://example.com/apply?form=ABC");
+        assert_eq!(link.id(), Some("ABC"));
+
+        let link = FormLink::with_id("https://example.com/apply?lang=de", "ABC");
+        assert_eq!(link.url(), "https://example.com/apply?lang=de&form=ABC");
+
+        assert_eq!(FormLink::new("https://example.com").id(), None);
+    }
+
+    #[test]
+    fn generated_ids_differ() {
+        let first = FormLink::with_generated_id("https://example.com");
+        let second = FormLink::with_generated_id("https://example.com");
+        let id = first.id().unwrap();
+        assert_eq!(id.len(), ID_LENGTH);
+        assert!(id.bytes().all(|b| ID_ALPHABET.contains(&b)));
+        assert_ne!(first.id(), second.id());
+    }
+}
@@ -0,0 +1,184 @@
+//! Callbacks around each question of a survey run.
+//!
+//! The builders' `before_each` and `after_each` attach [`AnswerHooks`]: the first is
+//! called before a question is shown, the second with every answer that passed
+//! validation, and decides with an [`AnswerAction`] whether to keep it, store another
+//! value instead, or ask the question again:
+//!
+//! ```rust,ignore
+//! let signup = Signup::builder()
+//!     .before_each(|path| log::debug!("asking {path}"))
+//!     .after_each(|path, answer| match answer {
+//!         ResponseValue::String(email) if path.as_str() == "email" => {
+//!             AnswerAction::Replace(ResponseValue::String(email.trim().to_lowercase()))
+//!         }
+//!         ResponseValue::String(name) if name == "root" => {
+//!             AnswerAction::Reask("This name is reserved".to_string())
+//!         }
+//!         _ => AnswerAction::Keep,
+//!     })
+//!     .run(backend)?;
+//! ```
+//!
+//! Hooks see what backends validate: enum choices are not passed to `after_each`, and
+//! form backends report no shown questions to `before_each`. A replaced value is what
+//! later validators and the result see, but does not change which follow-up questions
+//! the backend asks.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::{ResponsePath, ResponseValue, Responses, SurveyObserver};
+
+/// Hook called before a question is shown.
+type BeforeHook = Box<dyn Fn(&ResponsePath) + Send + Sync>;
+
+/// Hook called with each validated answer.
+type AfterHook = Box<dyn Fn(&ResponsePath, &ResponseValue) -> AnswerAction + Send + Sync>;
+
+/// What to do with an answer, decided by an `after_each` hook.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnswerAction {
+    /// Store the answer as given.
+    Keep,
+    /// Store this value instead of the answer.
+    Replace(ResponseValue),
+    /// Reject the answer with this message and ask the question again.
+    Reask(String),
+}
+
+/// The `before_each` and `after_each` hooks of a survey run.
+///
+/// The hooks run in the order they were added. Each `after_each` hook sees the value
+/// the previous ones left, and the first to ask again wins.
+#[derive(Default)]
+pub struct AnswerHooks {
+    before: Vec<BeforeHook>,
+    after: Vec<AfterHook>,
+    replaced: Mutex<HashMap<ResponsePath, ResponseValue>>,
+}
+
+impl std::fmt::Debug for AnswerHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnswerHooks")
+            .field("before", &self.before.len())
+            .field("after", &self.after.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AnswerHooks {
+    /// Create a set without hooks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a hook called with the path of each question before it is shown.
+    pub fn before_each(&mut self, hook: impl Fn(&ResponsePath) + Send + Sync + 'static) {
+        self.before.push(Box::new(hook));
+    }
+
+    /// Add a hook called with each answer that passed validation.
+    pub fn after_each(
+        &mut self,
+        hook: impl Fn(&ResponsePath, &ResponseValue) -> AnswerAction + Send + Sync + 'static,
+    ) {
+        self.after.push(Box::new(hook));
+    }
+
+    /// Whether no hooks are added.
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+
+    /// Run the `after_each` hooks on the answer at `path`.
+    ///
+    /// Returns the message of a hook that asks again as the validation error, and
+    /// remembers the value to store if a hook replaced the answer.
+    pub fn after_answer(&self, path: &ResponsePath, value: &ResponseValue) -> Result<(), String> {
+        let mut current = value.clone();
+        for hook in &self.after {
+            match hook(path, &current) {
+                AnswerAction::Keep => {}
+                AnswerAction::Replace(replacement) => current = replacement,
+                AnswerAction::Reask(message) => return Err(message),
+            }
+        }
+
+        // An answer given again replaces what was stored for the earlier one
+        let mut replaced = self.replaced.lock().unwrap_or_else(|e| e.into_inner());
+        if &current == value {
+            replaced.remove(path);
+        } else {
+            replaced.insert(path.clone(), current);
+        }
+        Ok(())
+    }
+
+    /// Whether a hook replaced any answer so far.
+    pub fn has_replacements(&self) -> bool {
+        !self
+            .replaced
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    /// Store the values the hooks replaced answers with in `responses`.
+    pub fn apply_replacements(&self, responses: &mut Responses) {
+        let replaced = self.replaced.lock().unwrap_or_else(|e| e.into_inner());
+        for (path, value) in replaced.iter() {
+            if responses.contains(path) {
+                responses.insert(path.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl SurveyObserver for AnswerHooks {
+    fn on_question_start(&self, path: &ResponsePath) {
+        for hook in &self.before {
+            hook(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hooks_replace_and_reject_answers() {
+        let mut hooks = AnswerHooks::new();
+        hooks.after_each(|_, value| match value {
+            ResponseValue::String(s) if s.is_empty() => AnswerAction::Reask("required".into()),
+            ResponseValue::String(s) if s != s.trim() => {
+                AnswerAction::Replace(ResponseValue::String(s.trim().to_string()))
+            }
+            _ => AnswerAction::Keep,
+        });
+        let path = ResponsePath::new("name");
+
+        assert_eq!(
+            hooks.after_answer(&path, &ResponseValue::String(String::new())),
+            Err("required".to_string())
+        );
+        assert_eq!(
+            hooks.after_answer(&path, &ResponseValue::String(" Ada ".into())),
+            Ok(())
+        );
+        let mut responses = Responses::new();
+        responses.insert("name", " Ada ");
+        hooks.apply_replacements(&mut responses);
+        assert_eq!(
+            responses.get(&path),
+            Some(&ResponseValue::String("Ada".into()))
+        );
+
+        // Kept when answered again
+        hooks
+            .after_answer(&path, &ResponseValue::String("Bob".into()))
+            .unwrap();
+        assert!(!hooks.has_replacements());
+    }
+}
@@ -5,6 +5,31 @@
 //! - `Question` and `QuestionKind` - Individual questions and their types
 //! - `Responses` and `ResponsePath` - Collected data and path-based keys
 //! - `Survey` and `SurveyBackend` traits - For implementing surveys and backends
+//! - `TextChecker` - Optional hook for checking free-text answers
+//! - `question_bank` - Shared questions reused via `#[use_question]`
+//! - `PromptExperiment` - A/B testing of prompt wording
+//! - `Color` - RGB color values for color questions
+//! - `Points` - Points earned by scored questions
+//! - `Submission` - Review and approval of collected responses
+//! - `FormLink` - Links from printed forms to their online version
+//! - `from_csv_reader` - Bulk import of responses from spreadsheets
+//! - `SurveyStats` - Answer distributions over many survey runs
+//! - `RoleRestrictions` - Field-level permissions for forms edited by several roles
+//! - `FlagProvider` - Questions switched on and off by feature flags at runtime
+//! - `MergeStrategy` - Reconciling two diverged sets of responses
+//! - `SurveyObserver` - Progress events of a survey run
+//! - `PrivacyReport` - How sinks handle the answers to masked fields
+//! - `DynamicSurvey` - Surveys built at runtime, without the derive
+//! - `register_backend` and `backend_named` - Backends looked up by name
+//! - `render_plan` - Stateless rendering steps for custom frontends
+//! - `SurveyDefinition::snapshot` - Stable text rendering of surveys for snapshot tests
+//! - `set_high_contrast` - Whether the backends start in high-contrast mode
+//! - `parse_palette` - Backend color themes read from TOML or JSON (feature `themes`)
+
+#[cfg(feature = "inventory")]
+pub use inventory;
+#[cfg(feature = "secrecy")]
+pub use secrecy::{self, SecretString};
 
 mod response_path;
 pub use response_path::ResponsePath;
@@ -13,23 +38,125 @@ mod response_value;
 pub use response_value::ResponseValue;
 
 mod responses;
-pub use responses::{ResponseError, Responses};
+pub use responses::{REDACTED, ResponseError, Responses, ResponsesIter};
 
 mod default_value;
 pub use default_value::DefaultValue;
 
+mod color;
+pub use color::{Color, ParseColorError};
+
+mod number_format;
+pub use number_format::NumberFormat;
+
 mod question;
 pub use question::{
-    AllOfQuestion, AnyOfQuestion, ConfirmQuestion, FloatQuestion, InputQuestion, IntQuestion,
-    ListElementKind, ListQuestion, MaskedQuestion, MultilineQuestion, OneOfQuestion, Question,
-    QuestionKind, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, Variant,
+    AllOfQuestion, AnyOfQuestion, AutocompleteQuestion, COMMENT_KEY, ColorQuestion, CompletionFn,
+    ConfirmQuestion, CurrencyQuestion, FloatQuestion, FuzzyMatch, InputQuestion, IntQuestion,
+    ListElementKind, ListQuestion, MaskedQuestion, MatrixQuestion, MultilineQuestion,
+    OneOfQuestion, PathMode, PathQuestion, Question, QuestionKind, RatingQuestion,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SelectOption, SelectQuestion, Variant,
+    fuzzy_match,
+};
+
+pub mod question_bank;
+
+mod experiment;
+pub use experiment::{PROMPT_VARIANT_METADATA_PREFIX, PromptExperiment, VariantStrategy};
+
+mod score;
+pub use score::Points;
+
+mod permissions;
+pub use permissions::{PermissionError, RoleRestrictions};
+
+mod flags;
+pub use flags::{EnvFlags, FLAG_ENV_PREFIX, FlagProvider, StaticFlags, apply_flags};
+
+mod merge;
+pub use merge::{MergeStrategy, UPDATED_AT_METADATA_KEY};
+
+mod privacy;
+pub use privacy::{FieldHandling, PrivacyReport, RESPONSES_SINK, SensitiveField, SensitiveSink};
+
+mod provenance;
+pub use provenance::{AnswerOrigin, PROVENANCE_METADATA_PREFIX};
+
+mod profile;
+pub use profile::Profile;
+
+mod workflow;
+pub use workflow::{Submission, SubmissionState, WORKFLOW_METADATA_PREFIX, WorkflowError};
+
+mod csv;
+pub use csv::{
+    CsvError, CsvFieldError, CsvImport, CsvRowError, csv_columns, csv_header, csv_row_to_responses,
+    from_csv_reader, from_csv_row,
 };
 
+mod stats;
+pub use stats::{AnswerSummary, NumberStats, QuestionStats, SurveyStats, TextStats};
+
+mod clock;
+
+mod form_link;
+pub use form_link::{FORM_ID_PARAMETER, FormLink};
+
 mod survey_definition;
 pub use survey_definition::SurveyDefinition;
 
+mod render;
+pub use render::{
+    QuestionStep, RenderStep, apply_assumed, next_step, pending_paths, record_answer, render_plan,
+};
+
+mod snapshot;
+
+mod plan;
+pub use plan::{ANY_ITEM, PlanNode, PlanStatus, QuestionPlan, SkipReason};
+
+mod dynamic;
+pub use dynamic::{DynamicSurvey, DynamicSurveyBuilder, DynamicValidator};
+
 mod error;
 pub use error::SurveyError;
 
+mod checkpoint;
+pub use checkpoint::Checkpoint;
+
+mod display;
+pub use display::{high_contrast_enabled, set_high_contrast, toggle_high_contrast};
+
+#[cfg(feature = "themes")]
+mod palette;
+#[cfg(feature = "themes")]
+pub use palette::{Palette, THEME_KEYS, THEME_PRESETS, ThemeError, parse_palette};
+
+mod text_check;
+#[cfg(feature = "spellcheck")]
+pub use text_check::DictionaryChecker;
+pub use text_check::{TextChecker, TextIssue, TextIssueSeverity};
+
+mod observer;
+#[cfg(feature = "tracing")]
+pub use observer::TracingObserver;
+pub use observer::{NoObserver, SurveyObserver};
+
+mod hooks;
+pub use hooks::{AnswerAction, AnswerHooks};
+
+mod translate;
+#[cfg(feature = "fluent")]
+pub use translate::{FluentError, FluentTranslator};
+pub use translate::{Messages, Translator, ValidationError};
+
+mod registry;
+#[cfg(feature = "inventory")]
+pub use registry::BackendRegistration;
+pub use registry::{
+    BoxedBackend, UnknownBackendError, backend_named, boxed_backend, register_backend,
+    registered_backends,
+};
+
 mod traits;
-pub use traits::{Survey, SurveyBackend};
+pub use traits::{SuggestFor, Survey, SurveyBackend, check_field_validator, variant_from_index};
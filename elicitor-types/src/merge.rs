@@ -0,0 +1,371 @@
+//! Reconciling two sets of responses to the same survey.
+//!
+//! When a survey is filled in on two devices, or a resumed session diverged from the
+//! saved one, [`Responses::merge_resolving`] combines both sets. Answers present in only
+//! one set are kept, and answers that differ are resolved by a [`MergeStrategy`]:
+//!
+//! ```rust,ignore
+//! let definition = Profile::survey();
+//! let conflicts = local.merge_resolving(remote, MergeStrategy::PreferNonDefault(&definition))?;
+//!
+//! // Or let the user pick
+//! local.merge_resolving(remote, MergeStrategy::interactive(&definition, &backend))?;
+//! ```
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    DefaultValue, OneOfQuestion, Question, QuestionKind, REDACTED, ResponsePath, ResponseValue,
+    Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition,
+    SurveyError, Variant,
+};
+
+/// Metadata key holding when responses were last updated, in seconds since the Unix epoch.
+pub const UPDATED_AT_METADATA_KEY: &str = "updated_at";
+
+type AskFn<'a> = dyn Fn(&SurveyDefinition) -> Result<Responses, SurveyError> + 'a;
+
+/// How [`Responses::merge_resolving`] resolves answers that differ between two sets.
+///
+/// Ties, and answers a strategy cannot decide, go to the newer set.
+pub enum MergeStrategy<'a> {
+    /// Keep the answers of the set that was updated last (see [`Responses::touch`]).
+    ///
+    /// Without timestamps on both sides, the incoming set counts as newer.
+    PreferNewer,
+
+    /// Keep the answer that differs from the question's default in `definition`.
+    ///
+    /// Empty strings count as defaults.
+    PreferNonDefault(&'a SurveyDefinition),
+
+    /// Ask which answer to keep, one question per conflict.
+    ///
+    /// Build with [`MergeStrategy::interactive`].
+    Interactive {
+        /// The survey the responses belong to, used for the conflict prompts.
+        definition: &'a SurveyDefinition,
+        /// Collects the answers to the conflict questions.
+        ask: Box<AskFn<'a>>,
+    },
+}
+
+impl<'a> MergeStrategy<'a> {
+    /// Resolve conflicts by asking with `backend`.
+    pub fn interactive<B: SurveyBackend>(definition: &'a SurveyDefinition, backend: &'a B) -> Self {
+        Self::Interactive {
+            definition,
+            ask: Box::new(move |conflicts| {
+                backend
+                    .collect(conflicts, &|_, _, _| Ok(()))
+                    .map_err(SurveyError::backend)
+            }),
+        }
+    }
+}
+
+impl Responses {
+    /// Record the current time as the time these responses were last updated.
+    ///
+    /// Does nothing where there is no clock, as in the browser.
+    pub fn touch(&mut self) {
+        let Some(now) = crate::clock::now() else {
+            return;
+        };
+        let seconds = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.insert_metadata(UPDATED_AT_METADATA_KEY, seconds.to_string());
+    }
+
+    /// When these responses were last updated, if recorded.
+    pub fn updated_at(&self) -> Option<SystemTime> {
+        let seconds = self.metadata(UPDATED_AT_METADATA_KEY)?.parse().ok()?;
+        Some(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+
+    /// Merge `other` into these responses, resolving differing answers with `strategy`.
+    ///
+    /// Returns the paths of the answers that differed, in the order of these responses.
+    /// Answers keep their position here, answers only `other` has are appended in its
+    /// order. Metadata and secret answers are taken from the newer set where both have
+    /// them.
+    pub fn merge_resolving(
+        &mut self,
+        other: Responses,
+        strategy: MergeStrategy<'_>,
+    ) -> Result<Vec<ResponsePath>, SurveyError> {
+        let other_is_newer = match (self.updated_at(), other.updated_at()) {
+            (Some(ours), Some(theirs)) => theirs >= ours,
+            _ => true,
+        };
+
+        let conflicts = self
+            .iter()
+            .filter_map(|(path, ours)| {
+                let theirs = other.get(path)?;
+                (theirs != ours).then(|| (path.clone(), ours.clone(), theirs.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        let keep_ours = match strategy {
+            MergeStrategy::PreferNewer => vec![!other_is_newer; conflicts.len()],
+            MergeStrategy::PreferNonDefault(definition) => {
+                let defaults = defaults(definition);
+                conflicts
+                    .iter()
+                    .map(|(path, ours, theirs)| {
+                        let default = defaults.get(path);
+                        match (is_default(ours, default), is_default(theirs, default)) {
+                            (false, true) => true,
+                            (true, false) => false,
+                            _ => !other_is_newer,
+                        }
+                    })
+                    .collect()
+            }
+            MergeStrategy::Interactive { definition, ask } => {
+                let questions = conflicts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (path, ours, theirs))| {
+                        conflict_question(i, definition, path, [ours, theirs], self)
+                    })
+                    .collect::<Vec<_>>();
+                if questions.is_empty() {
+                    Vec::new()
+                } else {
+                    let answers = ask(&SurveyDefinition::new(questions))?;
+                    (0..conflicts.len())
+                        .map(|i| {
+                            let key =
+                                ResponsePath::new(conflict_key(i)).child(SELECTED_VARIANT_KEY);
+                            answers
+                                .get_chosen_variant(&key)
+                                .map_or(!other_is_newer, |idx| idx == 0)
+                        })
+                        .collect()
+                }
+            }
+        };
+
+        if other_is_newer {
+            self.extend(other);
+        } else {
+            let ours = self.clone();
+            self.extend(other);
+            self.extend(ours);
+        }
+        for ((path, ours, theirs), keep_ours) in conflicts.iter().zip(keep_ours) {
+            let value = if keep_ours { ours } else { theirs };
+            self.insert(path.clone(), value.clone());
+        }
+
+        Ok(conflicts.into_iter().map(|(path, _, _)| path).collect())
+    }
+}
+
+fn conflict_key(i: usize) -> String {
+    format!("conflict_{i}")
+}
+
+fn conflict_question(
+    i: usize,
+    definition: &SurveyDefinition,
+    path: &ResponsePath,
+    values: [&ResponseValue; 2],
+    responses: &Responses,
+) -> Question {
+    let prompt = find_question(&definition.questions, &ResponsePath::empty(), path)
+        .map_or_else(|| path.as_str().to_string(), |q| q.ask().to_string());
+    let describe = |value: &ResponseValue| {
+        if responses.is_redacted(path) {
+            REDACTED.to_string()
+        } else {
+            describe(value)
+        }
+    };
+    let variants = vec![
+        Variant::unit(format!("Keep: {}", describe(values[0]))),
+        Variant::unit(format!("Take: {}", describe(values[1]))),
+    ];
+    Question::new(
+        conflict_key(i),
+        format!("Conflicting answers for '{prompt}'"),
+        QuestionKind::OneOf(OneOfQuestion::new(variants)),
+    )
+}
+
+fn describe(value: &ResponseValue) -> String {
+    fn join<T: ToString>(items: &[T]) -> String {
+        items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    match value {
+        ResponseValue::String(s) => s.clone(),
+        ResponseValue::Int(i) => i.to_string(),
+        ResponseValue::Float(f) => f.to_string(),
+        ResponseValue::Bool(true) => "yes".to_string(),
+        ResponseValue::Bool(false) => "no".to_string(),
+        ResponseValue::ChosenVariant(idx) => format!("option {}", idx + 1),
+        ResponseValue::ChosenVariants(indices) => {
+            join(&indices.iter().map(|idx| idx + 1).collect::<Vec<_>>())
+        }
+        ResponseValue::StringList(items) => join(items),
+        ResponseValue::IntList(items) => join(items),
+        ResponseValue::FloatList(items) => join(items),
+    }
+}
+
+fn is_default(value: &ResponseValue, default: Option<&ResponseValue>) -> bool {
+    default == Some(value) || matches!(value, ResponseValue::String(s) if s.is_empty())
+}
+
+fn question_path(question: &Question, prefix: &ResponsePath) -> ResponsePath {
+    if question.path().is_empty() {
+        prefix.clone()
+    } else {
+        prefix.child(question.path().as_str())
+    }
+}
+
+fn find_question<'q>(
+    questions: &'q [Question],
+    prefix: &ResponsePath,
+    target: &ResponsePath,
+) -> Option<&'q Question> {
+    for question in questions {
+        let path = question_path(question, prefix);
+        if &path == target
+            || target.strip_path_prefix(&path).is_some_and(|rest| {
+                rest.as_str() == SELECTED_VARIANT_KEY || rest.as_str() == SELECTED_VARIANTS_KEY
+            })
+        {
+            return Some(question);
+        }
+        let nested = match question.kind() {
+            QuestionKind::AllOf(all_of) => find_question(all_of.questions(), &path, target),
+            QuestionKind::OneOf(one_of) => {
+                one_of
+                    .variants
+                    .iter()
+                    .find_map(|variant| match &variant.kind {
+                        QuestionKind::AllOf(all_of) => {
+                            find_question(all_of.questions(), &path, target)
+                        }
+                        _ => None,
+                    })
+            }
+            _ => None,
+        };
+        if nested.is_some() {
+            return nested;
+        }
+    }
+    None
+}
+
+fn defaults(definition: &SurveyDefinition) -> HashMap<ResponsePath, ResponseValue> {
+    let mut defaults = HashMap::new();
+    collect_defaults(&definition.questions, &ResponsePath::empty(), &mut defaults);
+    defaults
+}
+
+fn collect_defaults(
+    questions: &[Question],
+    prefix: &ResponsePath,
+    defaults: &mut HashMap<ResponsePath, ResponseValue>,
+) {
+    for question in questions {
+        let path = question_path(question, prefix);
+        match question.kind() {
+            QuestionKind::AllOf(all_of) => collect_defaults(all_of.questions(), &path, defaults),
+            QuestionKind::OneOf(one_of) => {
+                if let Some(idx) = one_of.default {
+                    defaults.insert(
+                        path.child(SELECTED_VARIANT_KEY),
+                        ResponseValue::ChosenVariant(idx),
+                    );
+                }
+                for variant in &one_of.variants {
+                    if let QuestionKind::AllOf(all_of) = &variant.kind {
+                        collect_defaults(all_of.questions(), &path, defaults);
+                    }
+                }
+            }
+            QuestionKind::AnyOf(any_of) => {
+                defaults.insert(
+                    path.child(SELECTED_VARIANTS_KEY),
+                    ResponseValue::ChosenVariants(any_of.defaults.clone()),
+                );
+            }
+            _ => match question.default() {
+                DefaultValue::Suggested(value) | DefaultValue::Assumed(value) => {
+                    defaults.insert(path, value.clone());
+                }
+                DefaultValue::None => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConfirmQuestion, InputQuestion};
+
+    fn stamped(seconds: u64, entries: &[(&str, ResponseValue)]) -> Responses {
+        let mut responses = Responses::new();
+        responses.insert_metadata(UPDATED_AT_METADATA_KEY, seconds.to_string());
+        for (path, value) in entries {
+            responses.insert(*path, value.clone());
+        }
+        responses
+    }
+
+    #[test]
+    fn newer_answers_win() {
+        let mut ours = stamped(20, &[("name", "Alice".into()), ("city", "Berlin".into())]);
+        let theirs = stamped(10, &[("name", "Bob".into()), ("age", 30.into())]);
+
+        let conflicts = ours
+            .merge_resolving(theirs, MergeStrategy::PreferNewer)
+            .unwrap();
+        assert_eq!(conflicts, vec![ResponsePath::new("name")]);
+        assert_eq!(
+            ours.get_string(&ResponsePath::new("name")).unwrap(),
+            "Alice"
+        );
+        assert_eq!(ours.get_int(&ResponsePath::new("age")).unwrap(), 30);
+        assert_eq!(ours.len(), 3);
+        assert_eq!(ours.metadata(UPDATED_AT_METADATA_KEY), Some("20"));
+    }
+
+    #[test]
+    fn non_default_answers_win() {
+        let mut newsletter = Question::new(
+            "newsletter",
+            "Newsletter?",
+            QuestionKind::Confirm(ConfirmQuestion::new()),
+        );
+        newsletter.set_suggestion(false);
+        let definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            newsletter,
+        ]);
+
+        let mut ours = stamped(10, &[("name", "Alice".into()), ("newsletter", true.into())]);
+        let theirs = stamped(20, &[("name", "".into()), ("newsletter", false.into())]);
+
+        ours.merge_resolving(theirs, MergeStrategy::PreferNonDefault(&definition))
+            .unwrap();
+        assert_eq!(
+            ours.get_string(&ResponsePath::new("name")).unwrap(),
+            "Alice"
+        );
+        assert!(ours.get_bool(&ResponsePath::new("newsletter")).unwrap());
+    }
+}
@@ -0,0 +1,240 @@
+//! Numbers in the separators of a locale.
+//!
+//! Int and float questions can carry a locale, set for a whole survey with the builder's
+//! `with_locale` or [`SurveyDefinition::set_locale`]. Backends then accept and show
+//! numbers with that locale's decimal and group separators, so `1.234,56` is a valid
+//! answer in `de` and `1,234.56` in `en`:
+//!
+//! ```rust,ignore
+//! let order = Order::builder().with_locale("de-AT").run(backend)?;
+//! ```
+//!
+//! Without a locale, numbers are typed and shown as Rust parses and prints them.
+
+use crate::{Messages, QuestionKind, SurveyDefinition};
+
+/// The decimal and group separators of a locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Separator between the whole and the fractional part.
+    pub decimal: char,
+    /// Separator between groups of three digits, if the locale groups them.
+    pub group: Option<char>,
+}
+
+impl NumberFormat {
+    /// The format of numbers as Rust parses and prints them: `1234.5`.
+    pub const PLAIN: Self = Self {
+        decimal: '.',
+        group: None,
+    };
+
+    /// The separators of the language of `locale`, e.g. `de-AT`. Unknown languages use
+    /// the English separators.
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match language.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" => Self {
+                decimal: ',',
+                group: Some('.'),
+            },
+            "fr" | "sv" | "nb" | "fi" | "pl" | "cs" | "ru" => Self {
+                decimal: ',',
+                group: Some(' '),
+            },
+            _ => Self {
+                decimal: '.',
+                group: Some(','),
+            },
+        }
+    }
+
+    /// The format for an optional locale, [`PLAIN`](Self::PLAIN) without one.
+    pub fn for_optional_locale(locale: Option<&str>) -> Self {
+        locale.map_or(Self::PLAIN, Self::for_locale)
+    }
+
+    /// Parse a whole number, e.g. `1.234` in `de`, with an error from `messages`.
+    pub fn parse_int(&self, input: &str, messages: &Messages) -> Result<i64, String> {
+        let invalid = || messages.invalid_integer(self.example(&self.format_int(1234)));
+        let text = self.normalize(input).ok_or_else(invalid)?;
+        text.parse().map_err(|_| invalid())
+    }
+
+    /// Parse a number, e.g. `1.234,5` in `de`, with an error from `messages`.
+    pub fn parse_float(&self, input: &str, messages: &Messages) -> Result<f64, String> {
+        let invalid = || messages.invalid_number(self.example(&self.format_float(1234.5)));
+        let text = self.normalize(input).ok_or_else(invalid)?;
+        text.parse().map_err(|_| invalid())
+    }
+
+    /// Show a whole number with group separators, e.g. `1.234` in `de`.
+    pub fn format_int(&self, value: i64) -> String {
+        let sign = if value < 0 { "-" } else { "" };
+        format!("{sign}{}", self.group(&value.unsigned_abs().to_string()))
+    }
+
+    /// Show a number with the locale's separators, e.g. `1.234,5` in `de`.
+    pub fn format_float(&self, value: f64) -> String {
+        let text = value.to_string();
+        if !value.is_finite() {
+            return text;
+        }
+        let (sign, text) = match text.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", text.as_str()),
+        };
+        match text.split_once('.') {
+            Some((whole, fraction)) => {
+                format!("{sign}{}{}{fraction}", self.group(whole), self.decimal)
+            }
+            None => format!("{sign}{}", self.group(text)),
+        }
+    }
+
+    /// `example`, if the locale's separators differ from Rust's.
+    fn example<'a>(&self, example: &'a str) -> Option<&'a str> {
+        (*self != Self::PLAIN).then_some(example)
+    }
+
+    /// Insert group separators into a run of digits.
+    fn group(&self, digits: &str) -> String {
+        let Some(group) = self.group else {
+            return digits.to_string();
+        };
+        let mut grouped = String::new();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push(group);
+            }
+            grouped.push(c);
+        }
+        grouped
+    }
+
+    /// Rewrite `input` in Rust's number syntax, `None` if the separators are misplaced.
+    ///
+    /// Group separators must split the whole part into groups of three digits, so a
+    /// `1.5` typed in `de` is rejected instead of read as `15`.
+    fn normalize(&self, input: &str) -> Option<String> {
+        let input = input.trim();
+        let (sign, input) = match input.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", input),
+        };
+        let (whole, fraction) = match input.split_once(self.decimal) {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (input, None),
+        };
+
+        let whole = match self.group {
+            // Spaces are typed for thin and non-breaking spaces alike
+            Some(group)
+                if whole.contains(group) || group == ' ' && whole.contains(char::is_whitespace) =>
+            {
+                let groups: Vec<&str> = if group == ' ' {
+                    whole.split(char::is_whitespace).collect()
+                } else {
+                    whole.split(group).collect()
+                };
+                let well_formed = groups.iter().enumerate().all(|(i, part)| {
+                    part.chars().all(|c| c.is_ascii_digit())
+                        && if i == 0 {
+                            (1..=3).contains(&part.len())
+                        } else {
+                            part.len() == 3
+                        }
+                });
+                if !well_formed {
+                    return None;
+                }
+                groups.concat()
+            }
+            _ => whole.to_string(),
+        };
+        Some(match fraction {
+            Some(fraction) => format!("{sign}{whole}.{fraction}"),
+            None => format!("{sign}{whole}"),
+        })
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::PLAIN
+    }
+}
+
+impl SurveyDefinition {
+    /// Type and show the answers of all int and float questions in the separators of
+    /// `locale`, e.g. `de`.
+    ///
+    /// Currency questions keep the locale they were declared with.
+    pub fn set_locale(&mut self, locale: &str) {
+        for question in &mut self.questions {
+            set_locale(question.kind_mut(), locale);
+        }
+    }
+}
+
+fn set_locale(kind: &mut QuestionKind, locale: &str) {
+    match kind {
+        QuestionKind::Int(q) => q.locale = Some(locale.to_string()),
+        QuestionKind::Float(q) => q.locale = Some(locale.to_string()),
+        QuestionKind::AllOf(all_of) => {
+            for question in all_of.questions_mut() {
+                set_locale(question.kind_mut(), locale);
+            }
+        }
+        QuestionKind::OneOf(one_of) => {
+            for variant in &mut one_of.variants {
+                set_locale(&mut variant.kind, locale);
+            }
+        }
+        QuestionKind::AnyOf(any_of) => {
+            for variant in &mut any_of.variants {
+                set_locale(&mut variant.kind, locale);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separators_follow_the_locale() {
+        let english = Messages::default();
+        let de = NumberFormat::for_locale("de-AT");
+        assert_eq!(de.parse_float("1.234,56", &english), Ok(1234.56));
+        assert_eq!(de.parse_float("0,5", &english), Ok(0.5));
+        assert_eq!(de.parse_int("-12.345", &english), Ok(-12345));
+        assert_eq!(
+            de.parse_float("1.5", &english),
+            Err("Please enter a valid number, e.g. 1.234,5".to_string())
+        );
+        assert_eq!(de.format_float(-1234.5), "-1.234,5");
+        assert_eq!(de.format_int(1234567), "1.234.567");
+
+        let en = NumberFormat::for_locale("en-US");
+        assert_eq!(en.parse_float("1,234.56", &english), Ok(1234.56));
+        assert_eq!(en.parse_float("1234.56", &english), Ok(1234.56));
+        assert!(en.parse_float("1,234,5", &english).is_err());
+
+        let fr = NumberFormat::for_locale("fr");
+        assert_eq!(fr.parse_float("1 234,5", &english), Ok(1234.5));
+        assert_eq!(fr.format_float(1234.5), "1 234,5");
+
+        assert_eq!(NumberFormat::PLAIN.format_int(1234), "1234");
+        assert_eq!(
+            NumberFormat::PLAIN.parse_int("1,234", &english),
+            Err("Please enter a valid integer".to_string())
+        );
+    }
+}
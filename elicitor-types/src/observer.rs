@@ -0,0 +1,188 @@
+//! Progress events of a survey run.
+//!
+//! A [`SurveyObserver`] is told when a question is shown, answered, or rejected by
+//! validation. Attach observers with the builder's `with_observer`:
+//!
+//! ```rust,ignore
+//! struct Funnel;
+//!
+//! impl SurveyObserver for Funnel {
+//!     fn on_question_start(&self, path: &ResponsePath) {
+//!         log::info!("reached {path}");
+//!     }
+//! }
+//!
+//! let onboarding = Onboarding::builder().with_observer(Funnel).run(backend)?;
+//! ```
+//!
+//! Wizard backends report every question they show. Form backends show all questions at
+//! once and report answers and validation errors only.
+
+use std::sync::Arc;
+#[cfg(feature = "tracing")]
+use std::sync::Mutex;
+
+use crate::{ResponsePath, ResponseValue};
+
+/// Receives progress events while a survey runs.
+///
+/// All methods do nothing by default.
+pub trait SurveyObserver: Send + Sync {
+    /// A question at `path` is shown.
+    fn on_question_start(&self, path: &ResponsePath) {
+        let _ = path;
+    }
+
+    /// The answer at `path` passed validation.
+    fn on_answer(&self, path: &ResponsePath, value: &ResponseValue) {
+        let _ = (path, value);
+    }
+
+    /// The answer at `path` was rejected by a validator.
+    fn on_validation_error(&self, path: &ResponsePath, message: &str) {
+        let _ = (path, message);
+    }
+}
+
+/// Observer that ignores all events.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoObserver;
+
+impl SurveyObserver for NoObserver {}
+
+impl<O: SurveyObserver + ?Sized> SurveyObserver for Arc<O> {
+    fn on_question_start(&self, path: &ResponsePath) {
+        (**self).on_question_start(path);
+    }
+
+    fn on_answer(&self, path: &ResponsePath, value: &ResponseValue) {
+        (**self).on_answer(path, value);
+    }
+
+    fn on_validation_error(&self, path: &ResponsePath, message: &str) {
+        (**self).on_validation_error(path, message);
+    }
+}
+
+impl<O: SurveyObserver> SurveyObserver for [O] {
+    fn on_question_start(&self, path: &ResponsePath) {
+        self.iter().for_each(|o| o.on_question_start(path));
+    }
+
+    fn on_answer(&self, path: &ResponsePath, value: &ResponseValue) {
+        self.iter().for_each(|o| o.on_answer(path, value));
+    }
+
+    fn on_validation_error(&self, path: &ResponsePath, message: &str) {
+        self.iter()
+            .for_each(|o| o.on_validation_error(path, message));
+    }
+}
+
+impl<O: SurveyObserver> SurveyObserver for Vec<O> {
+    fn on_question_start(&self, path: &ResponsePath) {
+        self.as_slice().on_question_start(path);
+    }
+
+    fn on_answer(&self, path: &ResponsePath, value: &ResponseValue) {
+        self.as_slice().on_answer(path, value);
+    }
+
+    fn on_validation_error(&self, path: &ResponsePath, message: &str) {
+        self.as_slice().on_validation_error(path, message);
+    }
+}
+
+/// Observer that emits a `tracing` span per question.
+///
+/// The span `question` opens when the question is shown and closes when it is answered,
+/// so subscribers that time spans report how long each question took. Validation errors
+/// are emitted as warnings inside the span. A span left open when the survey is
+/// abandoned closes when the observer is dropped, with `answered = false`.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Default)]
+pub struct TracingObserver {
+    current: Mutex<Option<(ResponsePath, tracing::Span)>>,
+}
+
+#[cfg(feature = "tracing")]
+impl TracingObserver {
+    /// Create a tracing observer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl SurveyObserver for TracingObserver {
+    fn on_question_start(&self, path: &ResponsePath) {
+        let span = tracing::info_span!(
+            "question",
+            path = %path,
+            answered = false,
+        );
+        *self.current.lock().unwrap_or_else(|e| e.into_inner()) = Some((path.clone(), span));
+    }
+
+    fn on_answer(&self, path: &ResponsePath, _value: &ResponseValue) {
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if current.as_ref().is_some_and(|(shown, _)| shown == path) {
+            if let Some((_, span)) = current.take() {
+                span.record("answered", true);
+            }
+        } else {
+            tracing::info!(path = %path, "answered");
+        }
+    }
+
+    fn on_validation_error(&self, path: &ResponsePath, message: &str) {
+        let current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        match current.as_ref() {
+            Some((shown, span)) if shown == path => {
+                span.in_scope(|| tracing::warn!(path = %path, message, "validation failed"));
+            }
+            _ => tracing::warn!(path = %path, message, "validation failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<String>>);
+
+    impl SurveyObserver for Recorder {
+        fn on_question_start(&self, path: &ResponsePath) {
+            self.0.lock().unwrap().push(format!("start {path}"));
+        }
+
+        fn on_validation_error(&self, path: &ResponsePath, message: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("error {path}: {message}"));
+        }
+    }
+
+    #[test]
+    fn events_fan_out_to_all_observers() {
+        let first = Arc::new(Recorder::default());
+        let second = Arc::new(Recorder::default());
+        let observers: Vec<Arc<dyn SurveyObserver>> = vec![first.clone(), second.clone()];
+
+        let path = ResponsePath::new("age");
+        observers.on_question_start(&path);
+        observers.on_answer(&path, &ResponseValue::Int(3));
+        observers.on_validation_error(&path, "too young");
+
+        for recorder in [first, second] {
+            assert_eq!(
+                *recorder.0.lock().unwrap(),
+                vec!["start age", "error age: too young"]
+            );
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Color themes read from TOML or JSON palettes, shared by the backends with a theme.
+//!
+//! A palette is a table of colors, optionally starting from a named preset:
+//!
+//! ```toml
+//! base = "solarized"     # optional preset to start from
+//! primary = "#268bd2"
+//! error = "light-red"
+//! ```
+//!
+//! One file can theme every backend: keys of [`THEME_KEYS`] that a theme has no
+//! color for, like the form's `selected_bg` in the wizard, are checked and then left
+//! out. Any other key is an error.
+
+use std::io;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// Names of the built-in presets a palette can start from with `base`.
+pub const THEME_PRESETS: &[&str] = &["dark", "light", "solarized", "high-contrast"];
+
+/// The color keys of the themes of all backends.
+pub const THEME_KEYS: &[&str] = &[
+    "primary",
+    "secondary",
+    "background",
+    "text",
+    "highlight",
+    "error",
+    "success",
+    "border",
+    "selected_bg",
+];
+
+/// Errors from loading a theme.
+#[derive(Debug, Error)]
+pub enum ThemeError {
+    #[error("Cannot read theme file: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Invalid TOML theme: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Invalid JSON theme: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("A theme must be a table of colors")]
+    NotATable,
+
+    #[error("Invalid color `{value}` for `{key}`")]
+    InvalidColor { key: String, value: String },
+
+    #[error("Unknown theme key `{0}`")]
+    UnknownKey(String),
+
+    #[error("Unknown theme preset `{0}`, expected one of: {presets}", presets = THEME_PRESETS.join(", "))]
+    UnknownPreset(String),
+}
+
+/// A backend's color theme, filled in from a palette by [`parse_palette`].
+pub trait Palette: Default {
+    /// Color type of the theme, parsed from the palette's strings.
+    type Color: FromStr;
+
+    /// The built-in preset called `name`, one of [`THEME_PRESETS`].
+    fn preset(name: &str) -> Option<Self>;
+
+    /// The color of `key`, `None` if the theme has no such color.
+    fn color_mut(&mut self, key: &str) -> Option<&mut Self::Color>;
+}
+
+/// Parse a palette, as JSON if it starts with `{` and as TOML otherwise.
+///
+/// Keys left out keep the color of the `base` preset, or of the default theme.
+pub fn parse_palette<T: Palette>(text: &str) -> Result<T, ThemeError> {
+    let entries = palette_entries(text)?;
+
+    let mut theme = match entries.iter().find(|(key, _)| key == "base") {
+        Some((_, name)) => {
+            T::preset(name).ok_or_else(|| ThemeError::UnknownPreset(name.clone()))?
+        }
+        None => T::default(),
+    };
+    for (key, value) in entries {
+        if key == "base" {
+            continue;
+        }
+        if !THEME_KEYS.contains(&key.as_str()) {
+            return Err(ThemeError::UnknownKey(key));
+        }
+        let color = T::Color::from_str(&value).map_err(|_| ThemeError::InvalidColor {
+            key: key.clone(),
+            value: value.clone(),
+        })?;
+        if let Some(slot) = theme.color_mut(&key) {
+            *slot = color;
+        }
+    }
+    Ok(theme)
+}
+
+/// Flatten a TOML or JSON table of strings into key/value pairs.
+fn palette_entries(text: &str) -> Result<Vec<(String, String)>, ThemeError> {
+    if text.trim_start().starts_with('{') {
+        let serde_json::Value::Object(map) = serde_json::from_str(text)? else {
+            return Err(ThemeError::NotATable);
+        };
+        map.into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(value) => Ok((key, value)),
+                other => Err(ThemeError::InvalidColor {
+                    key,
+                    value: other.to_string(),
+                }),
+            })
+            .collect()
+    } else {
+        let table: toml::Table = toml::from_str(text)?;
+        table
+            .into_iter()
+            .map(|(key, value)| match value {
+                toml::Value::String(value) => Ok((key, value)),
+                other => Err(ThemeError::InvalidColor {
+                    key,
+                    value: other.to_string(),
+                }),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A theme with one color, numbered rather than named.
+    #[derive(Debug, Default, PartialEq)]
+    struct Mono {
+        text: u8,
+    }
+
+    impl Palette for Mono {
+        type Color = u8;
+
+        fn preset(name: &str) -> Option<Self> {
+            (name == "dark").then_some(Self { text: 7 })
+        }
+
+        fn color_mut(&mut self, key: &str) -> Option<&mut u8> {
+            (key == "text").then_some(&mut self.text)
+        }
+    }
+
+    #[test]
+    fn keys_of_other_themes_are_checked_but_left_out() {
+        assert_eq!(
+            parse_palette::<Mono>("base = \"dark\"\nborder = \"3\"").unwrap(),
+            Mono { text: 7 }
+        );
+        assert_eq!(
+            parse_palette::<Mono>(r#"{ "text": "4" }"#).unwrap(),
+            Mono { text: 4 }
+        );
+        assert!(matches!(
+            parse_palette::<Mono>("border = \"grey\""),
+            Err(ThemeError::InvalidColor { key, .. }) if key == "border"
+        ));
+        assert!(matches!(
+            parse_palette::<Mono>("accent = \"1\""),
+            Err(ThemeError::UnknownKey(key)) if key == "accent"
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+//! Field-level permissions for forms edited by several roles.
+//!
+//! Questions can be limited to some roles with [`Question::with_editable_by`]. When a
+//! survey is run for a role, [`RoleRestrictions::apply`] locks the questions the role may
+//! not edit to their suggested values, so they are not asked:
+//!
+//! ```rust,ignore
+//! #[derive(Survey)]
+//! struct Application {
+//!     #[ask("Your name:")]
+//!     name: String,
+//!
+//!     #[ask("Decision:")]
+//!     #[editable_by("reviewer")]
+//!     decision: Decision,
+//! }
+//!
+//! // The reviewer sees the applicant's answers as given and only decides
+//! let reviewed = Application::builder()
+//!     .with_suggestions(&application)
+//!     .with_role("reviewer")
+//!     .run(backend)?;
+//! ```
+//!
+//! Enum and multi-select choices cannot be locked in every backend, so the choice is
+//! preselected and [`RoleRestrictions::check`] rejects responses that changed it.
+
+use crate::{
+    DefaultValue, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyDefinition,
+};
+
+/// Error type for field-level permissions.
+#[derive(Debug, thiserror::Error)]
+pub enum PermissionError {
+    #[error("'{path}' cannot be edited by role '{role}' and has no value to keep")]
+    MissingValue { path: ResponsePath, role: String },
+
+    #[error("'{path}' cannot be changed by role '{role}'")]
+    Changed { path: ResponsePath, role: String },
+
+    #[error("'{path}' holds enum data that cannot be locked for role '{role}'")]
+    Unsupported { path: ResponsePath, role: String },
+}
+
+/// Choices a role may not change, checked once the survey has run.
+#[derive(Debug, Clone, Default)]
+pub struct RoleRestrictions {
+    role: String,
+    locked: Vec<(ResponsePath, ResponseValue)>,
+}
+
+impl RoleRestrictions {
+    /// Lock the questions of `definition` that `role` may not edit.
+    ///
+    /// Restricted questions with a suggested value are assumed instead. Restrictions
+    /// on a nested struct apply to all of its fields.
+    pub fn apply(definition: &mut SurveyDefinition, role: &str) -> Result<Self, PermissionError> {
+        let mut restrictions = Self {
+            role: role.to_string(),
+            locked: Vec::new(),
+        };
+        restrictions.restrict(&mut definition.questions, &ResponsePath::empty(), false)?;
+        Ok(restrictions)
+    }
+
+    /// The role the survey is run for.
+    pub fn role(&self) -> &str {
+        &self.role
+    }
+
+    /// Check that the responses kept every locked choice.
+    pub fn check(&self, responses: &Responses) -> Result<(), PermissionError> {
+        for (path, expected) in &self.locked {
+            if responses.get(path) != Some(expected) {
+                return Err(PermissionError::Changed {
+                    path: path.clone(),
+                    role: self.role.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn restrict(
+        &mut self,
+        questions: &mut [Question],
+        prefix: &ResponsePath,
+        parent_restricted: bool,
+    ) -> Result<(), PermissionError> {
+        for question in questions {
+            let path = if question.path().is_empty() {
+                prefix.clone()
+            } else {
+                prefix.child(question.path().as_str())
+            };
+            let restricted = parent_restricted || !question.is_editable_by(&self.role);
+
+            match question.kind_mut() {
+                QuestionKind::AllOf(all_of) => {
+                    self.restrict(all_of.questions_mut(), &path, restricted)?
+                }
+                QuestionKind::OneOf(one_of) if restricted => {
+                    let idx = one_of.default.ok_or_else(|| self.missing(&path))?;
+                    self.locked.push((
+                        path.child(SELECTED_VARIANT_KEY),
+                        ResponseValue::ChosenVariant(idx),
+                    ));
+                    match one_of.variants_mut().get_mut(idx).map(|v| &mut v.kind) {
+                        Some(QuestionKind::AllOf(all_of)) => {
+                            self.restrict(all_of.questions_mut(), &path, true)?
+                        }
+                        Some(QuestionKind::Unit) | None => {}
+                        Some(_) => return Err(self.unsupported(&path)),
+                    }
+                }
+                QuestionKind::OneOf(one_of) => {
+                    for variant in one_of.variants_mut() {
+                        if let QuestionKind::AllOf(all_of) = &mut variant.kind {
+                            self.restrict(all_of.questions_mut(), &path, false)?;
+                        }
+                    }
+                }
+                QuestionKind::AnyOf(any_of) if restricted => {
+                    let with_data = any_of.defaults.iter().any(|&idx| {
+                        any_of
+                            .variants
+                            .get(idx)
+                            .is_some_and(|v| !matches!(v.kind, QuestionKind::Unit))
+                    });
+                    if with_data {
+                        return Err(self.unsupported(&path));
+                    }
+                    self.locked.push((
+                        path.child(SELECTED_VARIANTS_KEY),
+                        ResponseValue::ChosenVariants(any_of.defaults.clone()),
+                    ));
+                }
+                QuestionKind::AnyOf(_) => {}
+                _ if restricted => match question.default() {
+                    DefaultValue::Assumed(_) => {}
+                    DefaultValue::Suggested(value) => question.set_assumption(value.clone()),
+                    DefaultValue::None => return Err(self.missing(&path)),
+                },
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn missing(&self, path: &ResponsePath) -> PermissionError {
+        PermissionError::MissingValue {
+            path: path.clone(),
+            role: self.role.clone(),
+        }
+    }
+
+    fn unsupported(&self, path: &ResponsePath) -> PermissionError {
+        PermissionError::Unsupported {
+            path: path.clone(),
+            role: self.role.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConfirmQuestion, InputQuestion, OneOfQuestion, Variant};
+
+    fn definition() -> SurveyDefinition {
+        let decision = OneOfQuestion::new(vec![
+            Variant::unit("Pending"),
+            Variant::unit("Accepted"),
+            Variant::unit("Declined"),
+        ]);
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "urgent",
+                "Urgent?",
+                QuestionKind::Confirm(ConfirmQuestion::new()),
+            )
+            .with_editable_by(["reviewer"]),
+            Question::new("decision", "Decision:", QuestionKind::OneOf(decision))
+                .with_editable_by(["reviewer"]),
+        ])
+    }
+
+    #[test]
+    fn restricted_questions_are_assumed() {
+        let mut definition = definition();
+        definition.questions[1].set_suggestion(ResponseValue::Bool(false));
+        if let QuestionKind::OneOf(one_of) = definition.questions[2].kind_mut() {
+            one_of.default = Some(0);
+        }
+
+        let restrictions = RoleRestrictions::apply(&mut definition, "applicant").unwrap();
+        assert!(!definition.questions[0].is_assumed());
+        assert!(definition.questions[1].is_assumed());
+
+        let mut responses = Responses::new();
+        responses.insert(
+            ResponsePath::new("decision").child(SELECTED_VARIANT_KEY),
+            ResponseValue::ChosenVariant(1),
+        );
+        assert!(matches!(
+            restrictions.check(&responses),
+            Err(PermissionError::Changed { .. })
+        ));
+        responses.insert(
+            ResponsePath::new("decision").child(SELECTED_VARIANT_KEY),
+            ResponseValue::ChosenVariant(0),
+        );
+        assert!(restrictions.check(&responses).is_ok());
+    }
+
+    #[test]
+    fn permitted_roles_edit_freely() {
+        let mut definition = definition();
+        let restrictions = RoleRestrictions::apply(&mut definition, "reviewer").unwrap();
+        assert!(!definition.questions[1].is_assumed());
+        assert!(restrictions.check(&Responses::new()).is_ok());
+
+        let err = RoleRestrictions::apply(&mut definition, "applicant").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "'urgent' cannot be edited by role 'applicant' and has no value to keep"
+        );
+    }
+}
@@ -0,0 +1,533 @@
+//! Listing the questions a survey would ask, without asking them.
+//!
+//! A [`QuestionPlan`] walks a survey definition with its suggestions and assumptions
+//! applied and tells for every question whether it is asked and, if not, why. The
+//! follow-up questions of enum variants are listed below their variant, marked with
+//! whether the variant is already chosen. The plan prints as a tree, which helps to
+//! debug conditional surveys and to document them:
+//!
+//! ```rust,ignore
+//! let plan = AppConfig::builder()
+//!     .assume_debug(false)
+//!     .suggest_server(|server| server.port(8080))
+//!     .plan();
+//! println!("{plan}");
+//! ```
+//!
+//! ```text
+//! Application name: (name)
+//! Server settings:
+//! ├── Host: (server.host)
+//! └── Port: (server.port) [suggested: 8080]
+//! Enable debug mode? (debug) [skipped: assumed false]
+//! Database: (database.selected_variant)
+//! ├── Sqlite (if chosen)
+//! │   └── Database file: (database.path)
+//! └── Postgres (if chosen)
+//!     └── Connection URL: (database.url)
+//! ```
+
+use std::fmt;
+
+use crate::render::{newtype_question, prompt_of};
+use crate::snapshot::value_text;
+use crate::{
+    DefaultValue, FlagProvider, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyDefinition, Variant,
+};
+
+/// Path segment standing for the item index of multi-select follow-up questions whose
+/// variant is not chosen yet.
+pub const ANY_ITEM: &str = "*";
+
+/// Why a question of a [`QuestionPlan`] is not asked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkipReason {
+    /// The answer is assumed.
+    Assumed(ResponseValue),
+    /// The `Option` field is assumed to be `None`.
+    AssumedNone,
+    /// The answer is known already, e.g. from a config file.
+    Answered(ResponseValue),
+    /// The feature flag of the question is off. The suggested value, if any, is kept.
+    FlagOff {
+        /// Name of the flag.
+        flag: String,
+        /// The answer kept in place of asking.
+        value: Option<ResponseValue>,
+    },
+    /// Another variant of the enum is chosen.
+    NotChosen,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Assumed(value) => write!(f, "assumed {}", value_text(value)),
+            Self::AssumedNone => f.write_str("assumed none"),
+            Self::Answered(value) => write!(f, "answered {}", value_text(value)),
+            Self::FlagOff { flag, value: None } => write!(f, "flag '{flag}' is off"),
+            Self::FlagOff {
+                flag,
+                value: Some(value),
+            } => write!(f, "flag '{flag}' is off, kept {}", value_text(value)),
+            Self::NotChosen => f.write_str("not chosen"),
+        }
+    }
+}
+
+/// What happens to an entry of a [`QuestionPlan`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanStatus {
+    /// The question is asked, pre-filled with the suggestion if there is one.
+    Asked {
+        /// Value offered as an editable default.
+        suggestion: Option<ResponseValue>,
+    },
+    /// A group of questions, e.g. a nested struct.
+    Group,
+    /// A variant whose follow-up questions are asked only if the respondent chooses it.
+    IfChosen,
+    /// A variant that is chosen already, its follow-up questions are planned below it.
+    Chosen,
+    /// The question is not asked.
+    Skipped(SkipReason),
+}
+
+/// An entry of a [`QuestionPlan`]: a question, a group of questions, or an enum variant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanNode {
+    /// Where the answer is stored. For enum and multi-select questions this is the
+    /// `selected_variant(s)` key, for variants the path of their enum.
+    pub path: ResponsePath,
+    /// The prompt, or the name of a variant.
+    pub label: String,
+    /// Whether the entry is asked and why not.
+    pub status: PlanStatus,
+    /// Questions of a group and variants of an enum, in the order they come up.
+    pub children: Vec<PlanNode>,
+}
+
+impl PlanNode {
+    /// Whether this is a question the respondent is asked, or may be asked depending on
+    /// the variants they choose.
+    pub fn is_asked(&self) -> bool {
+        matches!(self.status, PlanStatus::Asked { .. })
+    }
+
+    /// Whether this is a question that is not asked.
+    pub fn is_skipped(&self) -> bool {
+        matches!(self.status, PlanStatus::Skipped(_))
+    }
+}
+
+/// The questions of a survey in order, with whether each is asked and why not.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestionPlan {
+    nodes: Vec<PlanNode>,
+}
+
+impl QuestionPlan {
+    /// Plan `definition` with its suggestions and assumptions applied.
+    ///
+    /// Questions with an answer in `answered` are skipped, and with `flags`, questions
+    /// whose flag is off are skipped as [`apply_flags`](crate::apply_flags) would.
+    pub fn new(
+        definition: &SurveyDefinition,
+        answered: &Responses,
+        flags: Option<&dyn FlagProvider>,
+    ) -> Self {
+        let planner = Planner { answered, flags };
+        let mut nodes = Vec::new();
+        planner.plan_questions(&definition.questions, &ResponsePath::empty(), &mut nodes);
+        Self { nodes }
+    }
+
+    /// The top-level entries of the plan.
+    pub fn nodes(&self) -> &[PlanNode] {
+        &self.nodes
+    }
+
+    /// Every entry of the plan, depth first.
+    pub fn iter(&self) -> impl Iterator<Item = &PlanNode> {
+        let mut stack: Vec<&PlanNode> = self.nodes.iter().rev().collect();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    /// Paths of the questions that may be asked, in order.
+    pub fn asked(&self) -> Vec<&ResponsePath> {
+        self.iter()
+            .filter(|node| node.is_asked())
+            .map(|node| &node.path)
+            .collect()
+    }
+
+    /// Paths of the questions that are not asked, with the reason.
+    pub fn skipped(&self) -> Vec<(&ResponsePath, &SkipReason)> {
+        self.iter()
+            .filter_map(|node| match &node.status {
+                PlanStatus::Skipped(reason) => Some((&node.path, reason)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for QuestionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for node in &self.nodes {
+            write_node(f, node, "", "")?;
+        }
+        Ok(())
+    }
+}
+
+/// Write `node` after `lead`, and its children below it, indented by `indent`.
+fn write_node(
+    f: &mut fmt::Formatter<'_>,
+    node: &PlanNode,
+    lead: &str,
+    indent: &str,
+) -> fmt::Result {
+    write!(f, "{lead}{}", node.label)?;
+    match &node.status {
+        PlanStatus::Group => {}
+        PlanStatus::IfChosen => f.write_str(" (if chosen)")?,
+        PlanStatus::Chosen => f.write_str(" (chosen)")?,
+        PlanStatus::Asked { suggestion } => {
+            write!(f, " ({})", node.path)?;
+            if let Some(value) = suggestion {
+                write!(f, " [suggested: {}]", value_text(value))?;
+            }
+        }
+        PlanStatus::Skipped(SkipReason::NotChosen) => f.write_str(" [skipped: not chosen]")?,
+        PlanStatus::Skipped(reason) => write!(f, " ({}) [skipped: {reason}]", node.path)?,
+    }
+    writeln!(f)?;
+
+    for (idx, child) in node.children.iter().enumerate() {
+        let (lead, more) = if idx + 1 == node.children.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        write_node(
+            f,
+            child,
+            &format!("{indent}{lead}"),
+            &format!("{indent}{more}"),
+        )?;
+    }
+    Ok(())
+}
+
+struct Planner<'a> {
+    answered: &'a Responses,
+    flags: Option<&'a dyn FlagProvider>,
+}
+
+impl Planner<'_> {
+    fn plan_questions(
+        &self,
+        questions: &[Question],
+        prefix: &ResponsePath,
+        nodes: &mut Vec<PlanNode>,
+    ) {
+        for question in questions {
+            self.plan_question(question, prefix, nodes);
+        }
+    }
+
+    fn plan_question(&self, question: &Question, prefix: &ResponsePath, nodes: &mut Vec<PlanNode>) {
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+        let answer_path = match question.kind() {
+            QuestionKind::Unit => return,
+            QuestionKind::OneOf(_) => path.child(SELECTED_VARIANT_KEY),
+            QuestionKind::AnyOf(_) => path.child(SELECTED_VARIANTS_KEY),
+            _ => path.clone(),
+        };
+        let leaf = |status| PlanNode {
+            path: answer_path.clone(),
+            label: prompt_of(question, &path),
+            status,
+            children: Vec::new(),
+        };
+
+        if let Some(flag) = question
+            .flag()
+            .filter(|flag| self.flags.is_some_and(|flags| !flags.is_enabled(flag)))
+        {
+            let value = match question.default() {
+                DefaultValue::Assumed(value) => Some(value.clone()),
+                DefaultValue::Suggested(value) if !question.kind().is_structural() => {
+                    Some(value.clone())
+                }
+                _ => None,
+            };
+            let flag = flag.to_string();
+            nodes.push(leaf(PlanStatus::Skipped(SkipReason::FlagOff {
+                flag,
+                value,
+            })));
+            return;
+        }
+        if matches!(
+            question.default(),
+            DefaultValue::Assumed(ResponseValue::Bool(false))
+        ) && !matches!(question.kind(), QuestionKind::Confirm(_))
+        {
+            let mut node = leaf(PlanStatus::Skipped(SkipReason::AssumedNone));
+            node.path = path;
+            nodes.push(node);
+            return;
+        }
+
+        let answer = self.answered.get(&answer_path).cloned();
+        let status = match (&answer, question.default()) {
+            (Some(value), _) => PlanStatus::Skipped(SkipReason::Answered(value.clone())),
+            (None, DefaultValue::Assumed(value)) => {
+                PlanStatus::Skipped(SkipReason::Assumed(value.clone()))
+            }
+            (None, DefaultValue::Suggested(value)) => PlanStatus::Asked {
+                suggestion: Some(value.clone()),
+            },
+            (None, DefaultValue::None) => PlanStatus::Asked {
+                suggestion: match question.kind() {
+                    QuestionKind::OneOf(one_of) => one_of.default.map(ResponseValue::ChosenVariant),
+                    QuestionKind::AnyOf(any_of) if !any_of.defaults.is_empty() => {
+                        Some(ResponseValue::ChosenVariants(any_of.defaults.clone()))
+                    }
+                    _ => None,
+                },
+            },
+        };
+        let choice = answer.or_else(|| match question.default() {
+            DefaultValue::Assumed(value) => Some(value.clone()),
+            _ => None,
+        });
+
+        match question.kind() {
+            QuestionKind::AllOf(all_of) if question.ask().is_empty() => {
+                self.plan_questions(all_of.questions(), &path, nodes);
+            }
+            // Enum fields wrap their choice in a group, which shows with the group's prompt
+            QuestionKind::AllOf(all_of) if matches!(all_of.questions(), [inner] if inner.path().is_empty() && inner.ask().is_empty()) =>
+            {
+                let mut inner = all_of.questions()[0].clone();
+                inner.set_prompt(question.ask());
+                self.plan_question(&inner, &path, nodes);
+            }
+            QuestionKind::AllOf(all_of) => {
+                let mut children = Vec::new();
+                self.plan_questions(all_of.questions(), &path, &mut children);
+                nodes.push(PlanNode {
+                    path: path.clone(),
+                    label: question.ask().to_string(),
+                    status: PlanStatus::Group,
+                    children,
+                });
+            }
+            QuestionKind::OneOf(one_of) => {
+                let chosen = match choice {
+                    Some(ResponseValue::ChosenVariant(idx)) => Some(idx),
+                    _ => None,
+                };
+                let mut node = leaf(status);
+                for (idx, variant) in one_of.variants.iter().enumerate() {
+                    let status = match chosen {
+                        None => PlanStatus::IfChosen,
+                        Some(chosen) if chosen == idx => PlanStatus::Chosen,
+                        Some(_) => PlanStatus::Skipped(SkipReason::NotChosen),
+                    };
+                    let mut children = Vec::new();
+                    if !matches!(status, PlanStatus::Skipped(_)) {
+                        self.plan_variant(variant, &path, &mut children);
+                    }
+                    node.children
+                        .push(variant_node(variant, &path, status, children));
+                }
+                nodes.push(node);
+            }
+            QuestionKind::AnyOf(any_of) => {
+                let chosen = match choice {
+                    Some(ResponseValue::ChosenVariants(indices)) => Some(indices),
+                    _ => None,
+                };
+                let mut node = leaf(status);
+                for (idx, variant) in any_of.variants.iter().enumerate() {
+                    // Follow-up questions are asked per selected item
+                    let items: Vec<String> = match &chosen {
+                        None => vec![ANY_ITEM.to_string()],
+                        Some(indices) => indices
+                            .iter()
+                            .enumerate()
+                            .filter(|&(_, &chosen)| chosen == idx)
+                            .map(|(item, _)| item.to_string())
+                            .collect(),
+                    };
+                    let status = match &chosen {
+                        None => PlanStatus::IfChosen,
+                        Some(_) if items.is_empty() => PlanStatus::Skipped(SkipReason::NotChosen),
+                        Some(_) => PlanStatus::Chosen,
+                    };
+                    let mut children = Vec::new();
+                    if let QuestionKind::AllOf(all_of) = &variant.kind {
+                        for item in &items {
+                            self.plan_questions(
+                                all_of.questions(),
+                                &path.child(item),
+                                &mut children,
+                            );
+                        }
+                    }
+                    node.children
+                        .push(variant_node(variant, &path, status, children));
+                }
+                nodes.push(node);
+            }
+            _ => nodes.push(leaf(status)),
+        }
+    }
+
+    /// Follow-up questions of `variant` of the enum question at `path`.
+    fn plan_variant(&self, variant: &Variant, path: &ResponsePath, nodes: &mut Vec<PlanNode>) {
+        match &variant.kind {
+            QuestionKind::Unit => {}
+            QuestionKind::AllOf(all_of) => self.plan_questions(all_of.questions(), path, nodes),
+            _ => self.plan_question(&newtype_question(variant), path, nodes),
+        }
+    }
+}
+
+fn variant_node(
+    variant: &Variant,
+    path: &ResponsePath,
+    status: PlanStatus,
+    children: Vec<PlanNode>,
+) -> PlanNode {
+    PlanNode {
+        path: path.clone(),
+        label: variant.name.clone(),
+        status,
+        children,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AllOfQuestion, ConfirmQuestion, InputQuestion, IntQuestion, OneOfQuestion, StaticFlags,
+    };
+
+    fn definition() -> SurveyDefinition {
+        let mut port = Question::new("port", "Port:", QuestionKind::Int(IntQuestion::new()));
+        port.set_suggestion(ResponseValue::Int(8080));
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "server",
+                "Server:",
+                QuestionKind::AllOf(AllOfQuestion::new(vec![
+                    Question::new("host", "Host:", QuestionKind::Input(InputQuestion::new())),
+                    port,
+                ])),
+            ),
+            Question::new("salary", "Salary:", QuestionKind::Int(IntQuestion::new()))
+                .with_flag("collect_salary"),
+            Question::new(
+                "contact",
+                "Contact by?",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Mail"),
+                    Variant::new(
+                        "Phone",
+                        QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                            "number",
+                            "Number:",
+                            QuestionKind::Input(InputQuestion::new()),
+                        )])),
+                    ),
+                ])),
+            ),
+            Question::new(
+                "debug",
+                "Debug?",
+                QuestionKind::Confirm(ConfirmQuestion::new()),
+            ),
+        ])
+    }
+
+    #[test]
+    fn plan_prints_as_a_tree() {
+        let plan = QuestionPlan::new(&definition(), &Responses::new(), None);
+        assert_eq!(
+            plan.to_string(),
+            "Name: (name)\n\
+             Server:\n\
+             ├── Host: (server.host)\n\
+             └── Port: (server.port) [suggested: 8080]\n\
+             Salary: (salary)\n\
+             Contact by? (contact.selected_variant)\n\
+             ├── Mail (if chosen)\n\
+             └── Phone (if chosen)\n\
+             \u{20}   └── Number: (contact.number)\n\
+             Debug? (debug)\n"
+        );
+    }
+
+    #[test]
+    fn skipped_questions_tell_why() {
+        let mut definition = definition();
+        definition.questions[3].set_assumption(ResponseValue::ChosenVariant(0));
+        definition.questions[4].set_assumption(ResponseValue::Bool(false));
+        let mut answered = Responses::new();
+        answered.insert("name", "Ada");
+
+        let plan = QuestionPlan::new(&definition, &answered, Some(&StaticFlags::new()));
+        assert_eq!(
+            plan.asked(),
+            vec![
+                &ResponsePath::new("server.host"),
+                &ResponsePath::new("server.port")
+            ]
+        );
+        assert_eq!(
+            plan.skipped(),
+            vec![
+                (
+                    &ResponsePath::new("name"),
+                    &SkipReason::Answered(ResponseValue::String("Ada".into()))
+                ),
+                (
+                    &ResponsePath::new("salary"),
+                    &SkipReason::FlagOff {
+                        flag: "collect_salary".into(),
+                        value: None
+                    }
+                ),
+                (
+                    &ResponsePath::new("contact.selected_variant"),
+                    &SkipReason::Assumed(ResponseValue::ChosenVariant(0))
+                ),
+                (&ResponsePath::new("contact"), &SkipReason::NotChosen),
+                (
+                    &ResponsePath::new("debug"),
+                    &SkipReason::Assumed(ResponseValue::Bool(false))
+                ),
+            ]
+        );
+        assert!(
+            plan.to_string()
+                .contains("└── Phone [skipped: not chosen]\n")
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Reporting how sensitive answers are handled, for privacy reviews.
+//!
+//! A [`PrivacyReport`] lists every masked field of a survey and, for each sink the
+//! application writes answers to, whether the answer ends up in plain text, redacted,
+//! sealed, hashed, or not at all:
+//!
+//! ```rust,ignore
+//! let report = PrivacyReport::new(&Signup::survey())
+//!     .with_sink(&Configure::new("app.toml"))
+//!     .with_sink(&EnvFile::new(".env"));
+//!
+//! std::fs::write("privacy.csv", report.to_csv())?;
+//! ```
+//!
+//! The in-memory [`Responses`](crate::Responses) are always part of the report under
+//! the sink name `responses`.
+
+use std::fmt;
+
+use crate::csv::csv_field;
+use crate::{Question, QuestionKind, ResponsePath, SurveyDefinition};
+
+/// Sink name of the in-memory responses in a [`PrivacyReport`].
+pub const RESPONSES_SINK: &str = "responses";
+
+/// How a sink handles the answer to a sensitive field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldHandling {
+    /// Stored or shown as given.
+    Plaintext,
+    /// Replaced by a placeholder in output, the raw value is kept.
+    Redacted,
+    /// Held as a secret that is redacted in output and zeroized on drop.
+    Sealed,
+    /// Stored as a hash of the answer.
+    Hashed,
+    /// Never written.
+    Omitted,
+}
+
+impl FieldHandling {
+    /// Name of the handling as used in exports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Plaintext => "plaintext",
+            Self::Redacted => "redacted",
+            Self::Sealed => "sealed",
+            Self::Hashed => "hashed",
+            Self::Omitted => "omitted",
+        }
+    }
+}
+
+impl fmt::Display for FieldHandling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A masked field of a survey.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitiveField {
+    /// Where the answer is stored in the responses.
+    pub path: ResponsePath,
+    /// The prompt shown for the field.
+    pub prompt: String,
+    /// Whether the answer is a secret (a `SecretString` field).
+    pub secret: bool,
+}
+
+/// Something answers are written to, e.g. a config file or a log.
+pub trait SensitiveSink {
+    /// Name of the sink in reports, e.g. the file it writes.
+    fn sink_name(&self) -> String;
+
+    /// How the sink handles the answer to `field`.
+    fn handling(&self, field: &SensitiveField) -> FieldHandling;
+}
+
+/// The masked fields of a survey and how each sink handles them.
+#[derive(Debug, Clone)]
+pub struct PrivacyReport {
+    fields: Vec<SensitiveField>,
+    sinks: Vec<(String, Vec<FieldHandling>)>,
+}
+
+impl PrivacyReport {
+    /// Collect the masked fields of `definition`.
+    pub fn new(definition: &SurveyDefinition) -> Self {
+        let mut fields = Vec::new();
+        collect_fields(&definition.questions, &ResponsePath::empty(), &mut fields);
+        let responses = fields
+            .iter()
+            .map(|field| {
+                if field.secret {
+                    FieldHandling::Sealed
+                } else {
+                    FieldHandling::Redacted
+                }
+            })
+            .collect();
+        Self {
+            fields,
+            sinks: vec![(RESPONSES_SINK.to_string(), responses)],
+        }
+    }
+
+    /// Add a sink to the report.
+    pub fn with_sink(mut self, sink: &impl SensitiveSink) -> Self {
+        let handling = self.fields.iter().map(|f| sink.handling(f)).collect();
+        self.sinks.push((sink.sink_name(), handling));
+        self
+    }
+
+    /// The masked fields of the survey.
+    pub fn fields(&self) -> &[SensitiveField] {
+        &self.fields
+    }
+
+    /// Names of the sinks in the report.
+    pub fn sinks(&self) -> impl Iterator<Item = &str> {
+        self.sinks.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// How `sink` handles the field at `path`, if both are part of the report.
+    pub fn handling(&self, path: &ResponsePath, sink: &str) -> Option<FieldHandling> {
+        let idx = self.fields.iter().position(|f| &f.path == path)?;
+        let (_, handling) = self.sinks.iter().find(|(name, _)| name == sink)?;
+        Some(handling[idx])
+    }
+
+    /// Every field-sink pair with its handling.
+    pub fn entries(&self) -> impl Iterator<Item = (&SensitiveField, &str, FieldHandling)> {
+        self.sinks.iter().flat_map(move |(sink, handling)| {
+            self.fields
+                .iter()
+                .zip(handling)
+                .map(move |(field, &handling)| (field, sink.as_str(), handling))
+        })
+    }
+
+    /// Fields that some sink stores in plain text.
+    pub fn plaintext_fields(&self) -> impl Iterator<Item = &SensitiveField> {
+        self.fields.iter().enumerate().filter_map(|(idx, field)| {
+            self.sinks
+                .iter()
+                .any(|(_, handling)| handling[idx] == FieldHandling::Plaintext)
+                .then_some(field)
+        })
+    }
+
+    /// Export the report as CSV with the columns `path,prompt,secret,sink,handling`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("path,prompt,secret,sink,handling\n");
+        for (field, sink, handling) in self.entries() {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                csv_field(field.path.as_str()),
+                csv_field(&field.prompt),
+                field.secret,
+                csv_field(sink),
+                handling
+            ));
+        }
+        csv
+    }
+}
+
+fn collect_fields(questions: &[Question], prefix: &ResponsePath, fields: &mut Vec<SensitiveField>) {
+    for question in questions {
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+        match question.kind() {
+            QuestionKind::Masked(masked) => fields.push(SensitiveField {
+                path,
+                prompt: question.ask().to_string(),
+                secret: masked.secret,
+            }),
+            QuestionKind::AllOf(all_of) => collect_fields(all_of.questions(), &path, fields),
+            QuestionKind::OneOf(one_of) => {
+                for variant in &one_of.variants {
+                    match &variant.kind {
+                        QuestionKind::AllOf(all_of) => {
+                            collect_fields(all_of.questions(), &path, fields)
+                        }
+                        QuestionKind::Masked(masked) => fields.push(SensitiveField {
+                            path: path.child(&variant.name),
+                            prompt: variant.name.clone(),
+                            secret: masked.secret,
+                        }),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputQuestion, MaskedQuestion};
+
+    struct AuditLog;
+
+    impl SensitiveSink for AuditLog {
+        fn sink_name(&self) -> String {
+            "audit log".to_string()
+        }
+
+        fn handling(&self, field: &SensitiveField) -> FieldHandling {
+            if field.secret {
+                FieldHandling::Omitted
+            } else {
+                FieldHandling::Plaintext
+            }
+        }
+    }
+
+    #[test]
+    fn report_lists_masked_fields_per_sink() {
+        let definition = SurveyDefinition::new(vec![
+            Question::new("user", "User:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "pin",
+                "PIN, 4 digits:",
+                QuestionKind::Masked(MaskedQuestion::new()),
+            ),
+            Question::new(
+                "token",
+                "Token:",
+                QuestionKind::Masked(MaskedQuestion::new().with_secret()),
+            ),
+        ]);
+
+        let report = PrivacyReport::new(&definition).with_sink(&AuditLog);
+        assert_eq!(report.fields().len(), 2);
+        assert_eq!(
+            report.handling(&ResponsePath::new("token"), RESPONSES_SINK),
+            Some(FieldHandling::Sealed)
+        );
+        assert_eq!(
+            report
+                .plaintext_fields()
+                .map(|f| f.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["pin"]
+        );
+        assert_eq!(
+            report.to_csv(),
+            "path,prompt,secret,sink,handling\n\
+             pin,\"PIN, 4 digits:\",false,responses,redacted\n\
+             token,Token:,true,responses,sealed\n\
+             pin,\"PIN, 4 digits:\",false,audit log,plaintext\n\
+             token,Token:,true,audit log,omitted\n"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Named bundles of suggested and assumed answers.
+//!
+//! A [`Profile`] collects the answers one setting of a survey always starts from, e.g.
+//! a "production" and a "staging" preset of a deployment config. Applying it to a
+//! builder suggests and assumes its answers like the `suggest_*` and `assume_*`
+//! methods do:
+//!
+//! ```rust,ignore
+//! let production = Profile::new("production")
+//!     .assume("host", ResponseValue::String("prod.example.com".into()))
+//!     .suggest("replicas", ResponseValue::Int(3));
+//!
+//! let config = DeployConfig::builder()
+//!     .with_profile(&production)
+//!     .run(backend)?;
+//! ```
+//!
+//! `elicitor-configure` loads profiles from TOML or JSON files, typed by the survey.
+
+use std::collections::BTreeMap;
+
+use crate::{ResponsePath, ResponseValue};
+
+/// A named set of suggested and assumed answers, keyed by response path.
+///
+/// Enum choices are given at their `selected_variant` path, with the fields of the
+/// variant next to it.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Profile {
+    /// Name of the profile, e.g. the environment it is for.
+    pub name: String,
+
+    /// Answers offered as editable defaults.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub suggestions: BTreeMap<String, ResponseValue>,
+
+    /// Answers whose questions are skipped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub assumptions: BTreeMap<String, ResponseValue>,
+}
+
+impl Profile {
+    /// Create an empty profile.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Suggest `value` for the question at `path`.
+    pub fn suggest(mut self, path: impl Into<ResponsePath>, value: ResponseValue) -> Self {
+        self.suggestions
+            .insert(path.into().as_str().to_string(), value);
+        self
+    }
+
+    /// Assume `value` for the question at `path`, so it is not asked.
+    pub fn assume(mut self, path: impl Into<ResponsePath>, value: ResponseValue) -> Self {
+        self.assumptions
+            .insert(path.into().as_str().to_string(), value);
+        self
+    }
+
+    /// Whether the profile neither suggests nor assumes anything.
+    pub fn is_empty(&self) -> bool {
+        self.suggestions.is_empty() && self.assumptions.is_empty()
+    }
+}
@@ -0,0 +1,200 @@
+//! Where answers came from when they were not typed in.
+//!
+//! Answers can be filled in from configuration files, environment variables, command line
+//! flags or assumptions instead of being asked. Recording an [`AnswerOrigin`] per answer
+//! lets a review show why a question was skipped:
+//!
+//! ```rust,ignore
+//! let (config, responses) = Config::builder()
+//!     .assume_port(port)
+//!     .with_origin("port", AnswerOrigin::Environment("APP_PORT".into()))
+//!     .run_with_responses(backend)?;
+//!
+//! for (path, origin) in responses.origins() {
+//!     println!("{path}: {origin}"); // "port: from environment (APP_PORT)"
+//! }
+//! ```
+//!
+//! Origins are kept in the response metadata under `provenance.<path>`. Derived and
+//! dynamic surveys record [`AnswerOrigin::Assumed`] for assumed answers without an
+//! explicit origin.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{RenderStep, ResponsePath, Responses, SurveyDefinition, render_plan};
+
+/// Metadata key prefix for answer origins.
+pub const PROVENANCE_METADATA_PREFIX: &str = "provenance";
+
+/// Where an answer that was not typed in came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AnswerOrigin {
+    /// Read from a file, e.g. a saved configuration.
+    File(String),
+    /// Read from an environment variable.
+    Environment(String),
+    /// Passed as a command line flag.
+    CommandLine(String),
+    /// Assumed by the program, the question was not asked.
+    Assumed,
+    /// Any other source, described in words.
+    Other(String),
+}
+
+impl AnswerOrigin {
+    /// The stored form, e.g. `env:APP_PORT`.
+    fn encode(&self) -> String {
+        match self {
+            Self::File(path) => format!("file:{path}"),
+            Self::Environment(var) => format!("env:{var}"),
+            Self::CommandLine(flag) => format!("flag:{flag}"),
+            Self::Assumed => "assumed".to_string(),
+            Self::Other(source) => format!("other:{source}"),
+        }
+    }
+}
+
+impl fmt::Display for AnswerOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "from file {path}"),
+            Self::Environment(var) => write!(f, "from environment ({var})"),
+            Self::CommandLine(flag) => write!(f, "from command line ({flag})"),
+            Self::Assumed => f.write_str("assumed by policy"),
+            Self::Other(source) => f.write_str(source),
+        }
+    }
+}
+
+impl FromStr for AnswerOrigin {
+    type Err = std::convert::Infallible;
+
+    /// Parse the stored form. Unknown forms are kept as [`AnswerOrigin::Other`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let origin = match s.split_once(':') {
+            Some(("file", path)) => Self::File(path.to_string()),
+            Some(("env", var)) => Self::Environment(var.to_string()),
+            Some(("flag", flag)) => Self::CommandLine(flag.to_string()),
+            Some(("other", source)) => Self::Other(source.to_string()),
+            _ if s == "assumed" => Self::Assumed,
+            _ => Self::Other(s.to_string()),
+        };
+        Ok(origin)
+    }
+}
+
+impl Responses {
+    /// Record where the answer at `path` came from, replacing an earlier origin.
+    pub fn set_origin(&mut self, path: impl Into<ResponsePath>, origin: AnswerOrigin) {
+        self.insert_metadata(origin_key(&path.into()), origin.encode());
+    }
+
+    /// Where the answer at `path` came from, `None` if it was typed in.
+    pub fn origin(&self, path: &ResponsePath) -> Option<AnswerOrigin> {
+        self.metadata(&origin_key(path))
+            .and_then(|origin| origin.parse().ok())
+    }
+
+    /// All recorded origins, keyed by the path of the answer they belong to.
+    pub fn origins(&self) -> impl Iterator<Item = (ResponsePath, AnswerOrigin)> + '_ {
+        let prefix = format!("{PROVENANCE_METADATA_PREFIX}.");
+        self.metadata_iter().filter_map(move |(key, origin)| {
+            let path = key.strip_prefix(&prefix)?;
+            Some((ResponsePath::new(path), origin.parse().ok()?))
+        })
+    }
+
+    /// Record [`AnswerOrigin::Assumed`] for the assumed answers of `definition` that
+    /// have no origin yet.
+    pub fn record_assumed_origins(&mut self, definition: &SurveyDefinition) {
+        let assumed: Vec<ResponsePath> = render_plan(definition, self)
+            .into_iter()
+            .filter_map(|step| match step {
+                RenderStep::Question(question)
+                    if question.assumed && self.contains(&question.path) =>
+                {
+                    Some(question.path)
+                }
+                _ => None,
+            })
+            .filter(|path| self.origin(path).is_none())
+            .collect();
+        for path in assumed {
+            self.set_origin(path, AnswerOrigin::Assumed);
+        }
+    }
+}
+
+fn origin_key(path: &ResponsePath) -> String {
+    format!("{PROVENANCE_METADATA_PREFIX}.{}", path.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputQuestion, Question, QuestionKind, ResponseValue};
+
+    #[test]
+    fn origins_round_trip_through_metadata() {
+        let mut responses = Responses::new();
+        responses.set_origin("port", AnswerOrigin::Environment("APP_PORT".into()));
+        responses.set_origin("db.host", AnswerOrigin::File("app.toml".into()));
+
+        assert_eq!(
+            responses.origin(&ResponsePath::new("port")),
+            Some(AnswerOrigin::Environment("APP_PORT".into()))
+        );
+        assert_eq!(responses.origin(&ResponsePath::new("name")), None);
+        assert_eq!(
+            responses.metadata("provenance.db.host"),
+            Some("file:app.toml")
+        );
+        assert_eq!(responses.origins().count(), 2);
+    }
+
+    #[test]
+    fn origins_are_described_for_review() {
+        assert_eq!(
+            AnswerOrigin::Environment("APP_PORT".into()).to_string(),
+            "from environment (APP_PORT)"
+        );
+        assert_eq!(AnswerOrigin::Assumed.to_string(), "assumed by policy");
+        assert_eq!(
+            "flag:--port".parse::<AnswerOrigin>().unwrap(),
+            AnswerOrigin::CommandLine("--port".into())
+        );
+        assert_eq!(
+            "imported".parse::<AnswerOrigin>().unwrap(),
+            AnswerOrigin::Other("imported".into())
+        );
+    }
+
+    #[test]
+    fn assumed_answers_get_an_origin() {
+        let mut definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new())),
+            Question::new("host", "Host?", QuestionKind::Input(InputQuestion::new())),
+            Question::new("user", "User?", QuestionKind::Input(InputQuestion::new())),
+        ]);
+        definition.questions[1].set_assumption(ResponseValue::String("localhost".into()));
+        definition.questions[2].set_assumption(ResponseValue::String("admin".into()));
+
+        let mut responses = Responses::new();
+        responses.insert("name", ResponseValue::String("Ada".into()));
+        responses.insert("host", ResponseValue::String("localhost".into()));
+        responses.insert("user", ResponseValue::String("admin".into()));
+        responses.set_origin("user", AnswerOrigin::CommandLine("--user".into()));
+        responses.record_assumed_origins(&definition);
+
+        assert_eq!(responses.origin(&ResponsePath::new("name")), None);
+        assert_eq!(
+            responses.origin(&ResponsePath::new("host")),
+            Some(AnswerOrigin::Assumed)
+        );
+        assert_eq!(
+            responses.origin(&ResponsePath::new("user")),
+            Some(AnswerOrigin::CommandLine("--user".into()))
+        );
+    }
+}
@@ -1,7 +1,14 @@
-use crate::{DefaultValue, ResponsePath, ResponseValue};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{
+    Color, DefaultValue, Messages, NumberFormat, ParseColorError, ResponsePath, ResponseValue,
+    SurveyDefinition,
+};
 
 /// A single question in a survey.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Question {
     /// The path to this question's response in the Responses map.
     path: ResponsePath,
@@ -13,7 +20,40 @@ pub struct Question {
     kind: QuestionKind,
 
     /// Default value for this question (none, suggested, or assumed).
+    #[cfg_attr(feature = "serde", serde(default))]
     default: DefaultValue,
+
+    /// Whether the respondent may attach a free-text comment to the answer.
+    #[cfg_attr(feature = "serde", serde(default))]
+    allow_comment: bool,
+
+    /// Points awarded per choice, see [`Points`](crate::Points).
+    #[cfg_attr(feature = "serde", serde(default))]
+    score_weights: Vec<i64>,
+
+    /// Roles allowed to edit the answer, empty if anyone may.
+    #[cfg_attr(feature = "serde", serde(default))]
+    editable_by: Vec<String>,
+
+    /// Categories of the answer, e.g. `pii`, see [`Responses::strip_tagged`](crate::Responses::strip_tagged).
+    #[cfg_attr(feature = "serde", serde(default))]
+    tags: Vec<String>,
+
+    /// Feature flag that must be on for the question to be asked, see [`apply_flags`](crate::apply_flags).
+    #[cfg_attr(feature = "serde", serde(default))]
+    flag: Option<String>,
+
+    /// Longer explanation shown on request, besides the prompt.
+    #[cfg_attr(feature = "serde", serde(default))]
+    help: Option<String>,
+
+    /// Whether backends should offer the choices in a drop-down instead of a list.
+    #[cfg_attr(feature = "serde", serde(default))]
+    dropdown: bool,
+
+    /// Translation key of the prompt, see [`Translator`](crate::Translator).
+    #[cfg_attr(feature = "serde", serde(default))]
+    key: Option<String>,
 }
 
 impl Question {
@@ -24,9 +64,125 @@ impl Question {
             ask: ask.into(),
             kind,
             default: DefaultValue::None,
+            allow_comment: false,
+            score_weights: Vec::new(),
+            editable_by: Vec::new(),
+            tags: Vec::new(),
+            flag: None,
+            help: None,
+            dropdown: false,
+            key: None,
         }
     }
 
+    /// Let the respondent attach a free-text comment to the answer.
+    ///
+    /// The comment is stored at `<path>.__comment` (see [`COMMENT_KEY`]).
+    pub fn with_comment(mut self) -> Self {
+        self.allow_comment = true;
+        self
+    }
+
+    /// Whether the respondent may attach a comment to the answer.
+    pub fn allows_comment(&self) -> bool {
+        self.allow_comment
+    }
+
+    /// Award points for the answer, one weight per choice.
+    ///
+    /// For enums the weights follow the variant order, for selections the option order.
+    /// A `bool` takes a single weight, awarded for "yes".
+    pub fn with_score(mut self, weights: impl IntoIterator<Item = i64>) -> Self {
+        self.score_weights = weights.into_iter().collect();
+        self
+    }
+
+    /// Points awarded per choice, empty if the question is not scored.
+    pub fn score_weights(&self) -> &[i64] {
+        &self.score_weights
+    }
+
+    /// Only let the given roles edit the answer, see [`RoleRestrictions`](crate::RoleRestrictions).
+    pub fn with_editable_by(mut self, roles: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.editable_by = roles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Roles allowed to edit the answer, empty if anyone may.
+    pub fn editable_by(&self) -> &[String] {
+        &self.editable_by
+    }
+
+    /// Whether `role` may edit the answer.
+    pub fn is_editable_by(&self, role: &str) -> bool {
+        self.editable_by.is_empty() || self.editable_by.iter().any(|allowed| allowed == role)
+    }
+
+    /// Put the answer in categories, e.g. `pii` or `financial`.
+    ///
+    /// Tags on a group apply to every question in it.
+    pub fn with_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Categories of the answer.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Whether the question itself carries `tag`.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Only ask the question while the feature flag `flag` is on.
+    pub fn with_flag(mut self, flag: impl Into<String>) -> Self {
+        self.flag = Some(flag.into());
+        self
+    }
+
+    /// The feature flag guarding the question, if any.
+    pub fn flag(&self) -> Option<&str> {
+        self.flag.as_deref()
+    }
+
+    /// Explain the question at more length than the prompt, e.g. what the answer
+    /// is used for. Backends show it on request.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// The longer explanation of the question, if any.
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    /// Offer the choices of an enum or runtime selection in a drop-down, as a
+    /// hint to backends that can show either.
+    pub fn with_dropdown(mut self) -> Self {
+        self.dropdown = true;
+        self
+    }
+
+    /// Whether the choices are meant for a drop-down.
+    pub fn is_dropdown(&self) -> bool {
+        self.dropdown
+    }
+
+    /// Look the prompt up under `key` when the survey is translated, see
+    /// [`SurveyDefinition::translate`].
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// The translation key of the prompt, if any.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
     /// Get the response path for this question.
     pub fn path(&self) -> &ResponsePath {
         &self.path
@@ -37,6 +193,11 @@ impl Question {
         &self.ask
     }
 
+    /// Replace the prompt text (e.g. with a translation).
+    pub fn set_prompt(&mut self, ask: impl Into<String>) {
+        self.ask = ask.into();
+    }
+
     /// Get the question kind.
     pub fn kind(&self) -> &QuestionKind {
         &self.kind
@@ -75,6 +236,7 @@ impl Question {
 
 /// The kind of question, determining input type and structure.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum QuestionKind {
     /// No data to collect (unit enum variants, unit structs).
     Unit,
@@ -82,6 +244,9 @@ pub enum QuestionKind {
     /// Single-line text input.
     Input(InputQuestion),
 
+    /// Single-line text input with completions from a suggestion source.
+    Autocomplete(AutocompleteQuestion),
+
     /// Multi-line text input (opens editor or textarea).
     Multiline(MultilineQuestion),
 
@@ -94,9 +259,27 @@ pub enum QuestionKind {
     /// Floating-point input with optional min/max bounds.
     Float(FloatQuestion),
 
+    /// Money amount in a fixed currency, stored in minor units.
+    Currency(CurrencyQuestion),
+
+    /// Pick an RGB color, stored as a `#rrggbb` string.
+    Color(ColorQuestion),
+
+    /// Filesystem path, optionally restricted to existing files or directories.
+    Path(PathQuestion),
+
+    /// Pick a point on a rating scale (star ratings, Likert items).
+    Rating(RatingQuestion),
+
+    /// Grid of rows that are each answered with one column of a shared scale.
+    Matrix(MatrixQuestion),
+
     /// Yes/no confirmation.
     Confirm(ConfirmQuestion),
 
+    /// Pick one string value from a list of options provided at runtime.
+    Select(SelectQuestion),
+
     /// List of values (Vec<T> where T is a primitive type).
     List(ListQuestion),
 
@@ -120,11 +303,17 @@ impl QuestionKind {
         matches!(
             self,
             Self::Input(_)
+                | Self::Autocomplete(_)
                 | Self::Multiline(_)
                 | Self::Masked(_)
                 | Self::Int(_)
                 | Self::Float(_)
+                | Self::Currency(_)
+                | Self::Color(_)
+                | Self::Rating(_)
+                | Self::Matrix(_)
                 | Self::Confirm(_)
+                | Self::Select(_)
                 | Self::List(_)
         )
     }
@@ -133,10 +322,29 @@ impl QuestionKind {
     pub fn is_structural(&self) -> bool {
         matches!(self, Self::AllOf(_) | Self::OneOf(_) | Self::AnyOf(_))
     }
+
+    /// The [`ResponseValue::type_name`](crate::ResponseValue::type_name) of answers to a
+    /// question of this kind.
+    pub fn answer_type(&self) -> &'static str {
+        match self {
+            Self::Int(_) | Self::Currency(_) | Self::Rating(_) => "Int",
+            Self::Float(_) => "Float",
+            Self::Confirm(_) => "Bool",
+            Self::OneOf(_) => "ChosenVariant",
+            Self::AnyOf(_) | Self::Matrix(_) => "ChosenVariants",
+            Self::List(list) => match list.element_kind {
+                ListElementKind::String => "StringList",
+                ListElementKind::Int { .. } => "IntList",
+                ListElementKind::Float { .. } => "FloatList",
+            },
+            _ => "String",
+        }
+    }
 }
 
 /// A variant in a OneOf question (enum variant).
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variant {
     /// Variant name for display (e.g., "Male", "Female", "Other").
     pub name: String,
@@ -147,6 +355,15 @@ pub struct Variant {
     /// - AllOf for struct variants
     /// - OneOf for nested enums
     pub kind: QuestionKind,
+
+    /// Translation key of the name, see [`Translator`](crate::Translator).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub key: Option<String>,
+
+    /// What choosing the variant means, shown next to the highlighted option and in
+    /// documents.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub help: Option<String>,
 }
 
 impl Variant {
@@ -155,6 +372,8 @@ impl Variant {
         Self {
             name: name.into(),
             kind,
+            key: None,
+            help: None,
         }
     }
 
@@ -162,16 +381,33 @@ impl Variant {
     pub fn unit(name: impl Into<String>) -> Self {
         Self::new(name, QuestionKind::Unit)
     }
+
+    /// Look the name up under `key` when the survey is translated.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Explain what choosing the variant means.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
 }
 
 /// Configuration for an AnyOf question (multi-select with potential follow-up questions).
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnyOfQuestion {
     /// The available variants to choose from.
     pub variants: Vec<Variant>,
 
     /// Default selected indices (if any).
     pub defaults: Vec<usize>,
+
+    /// Most variants that may be selected at once.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_selected: Option<usize>,
 }
 
 impl AnyOfQuestion {
@@ -180,12 +416,23 @@ impl AnyOfQuestion {
         Self {
             variants,
             defaults: Vec::new(),
+            max_selected: None,
         }
     }
 
     /// Create with default selections.
     pub fn with_defaults(variants: Vec<Variant>, defaults: Vec<usize>) -> Self {
-        Self { variants, defaults }
+        Self {
+            variants,
+            defaults,
+            max_selected: None,
+        }
+    }
+
+    /// Allow at most `max` selected variants.
+    pub fn with_max_selected(mut self, max: usize) -> Self {
+        self.max_selected = Some(max);
+        self
     }
 }
 
@@ -193,6 +440,7 @@ impl AnyOfQuestion {
 ///
 /// Used for nested structs and struct enum variants.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AllOfQuestion {
     /// The questions in this group.
     pub questions: Vec<Question>,
@@ -227,6 +475,7 @@ impl AllOfQuestion {
 /// Used for enums where the user selects one variant, then answers
 /// any follow-up questions for that variant.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OneOfQuestion {
     /// The available variants to choose from.
     pub variants: Vec<Variant>,
@@ -265,6 +514,7 @@ impl OneOfQuestion {
 
 /// Configuration for a text input question.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputQuestion {
     /// Optional default value.
     pub default: Option<String>,
@@ -296,8 +546,191 @@ impl InputQuestion {
     }
 }
 
+/// Callback that returns completions for the current input.
+pub type CompletionFn = Arc<dyn Fn(&str) -> Vec<String> + Send + Sync>;
+
+/// Where an [`AutocompleteQuestion`] gets its completions from.
+#[derive(Clone)]
+pub enum SuggestionSource {
+    /// A fixed list of suggestions, fuzzy-filtered against the input.
+    Static(Vec<String>),
+
+    /// A callback that receives the current input and returns completions.
+    Callback(CompletionFn),
+}
+
+impl fmt::Debug for SuggestionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Static(items) => f.debug_tuple("Static").field(items).finish(),
+            Self::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+/// Only static suggestions can be serialized, callbacks fail with an error.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SuggestionSource {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Static(items) => {
+                serializer.serialize_newtype_variant("SuggestionSource", 0, "Static", items)
+            }
+            Self::Callback(_) => Err(serde::ser::Error::custom(
+                "autocomplete callbacks cannot be serialized",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SuggestionSource {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "SuggestionSource")]
+        enum Repr {
+            Static(Vec<String>),
+        }
+
+        let Repr::Static(items) = Repr::deserialize(deserializer)?;
+        Ok(Self::Static(items))
+    }
+}
+
+impl PartialEq for SuggestionSource {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Static(a), Self::Static(b)) => a == b,
+            (Self::Callback(a), Self::Callback(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// Configuration for a text input question with autocompletion.
+///
+/// The answer is free text stored as a `ResponseValue::String`; completions are
+/// only offered as help while typing.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutocompleteQuestion {
+    /// Where completions come from.
+    pub source: SuggestionSource,
+
+    /// Optional default value.
+    pub default: Option<String>,
+
+    /// Validation function name.
+    pub validate: Option<String>,
+}
+
+impl AutocompleteQuestion {
+    /// Create a new autocomplete question with the given suggestion source.
+    pub fn new(source: SuggestionSource) -> Self {
+        Self {
+            source,
+            default: None,
+            validate: None,
+        }
+    }
+
+    /// Create with a fixed list of suggestions.
+    pub fn from_list<I, S>(items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(SuggestionSource::Static(
+            items.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Create with a callback that computes completions for the current input.
+    pub fn from_fn(f: impl Fn(&str) -> Vec<String> + Send + Sync + 'static) -> Self {
+        Self::new(SuggestionSource::Callback(Arc::new(f)))
+    }
+
+    /// Set a validator function.
+    pub fn with_validator(mut self, validate: Option<String>) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Get the completions for the given input, best match first.
+    ///
+    /// Static lists are fuzzy-filtered: every character of the input must appear in
+    /// the suggestion in order (case-insensitive). Callbacks are used as-is.
+    pub fn completions(&self, input: &str) -> Vec<String> {
+        match &self.source {
+            SuggestionSource::Static(items) => {
+                let mut scored: Vec<(i64, &String)> = items
+                    .iter()
+                    .filter_map(|item| fuzzy_match(item, input).map(|found| (found.score, item)))
+                    .collect();
+                // Stable sort keeps the original order for equal scores
+                scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+                scored.into_iter().map(|(_, item)| item.clone()).collect()
+            }
+            SuggestionSource::Callback(f) => f(input),
+        }
+    }
+}
+
+/// Where a fuzzy pattern matched a candidate, see [`fuzzy_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher scores favour prefix matches, consecutive characters and short candidates.
+    pub score: i64,
+
+    /// Char indices of the matched characters in the candidate, e.g. to highlight them.
+    pub indices: Vec<usize>,
+}
+
+/// Match `pattern` against `candidate` as a fuzzy subsequence, ignoring case.
+///
+/// Returns `None` if the pattern's characters don't all appear in order. This is the
+/// matching used by [`AutocompleteQuestion::completions`], for frontends that filter
+/// long lists of options the same way.
+///
+/// # Example
+/// ```
+/// use elicitor_types::fuzzy_match;
+///
+/// let found = fuzzy_match("New Zealand", "nzl").unwrap();
+/// assert_eq!(found.indices, vec![0, 4, 7]);
+/// assert!(fuzzy_match("Norway", "nzl").is_none());
+/// ```
+pub fn fuzzy_match(candidate: &str, pattern: &str) -> Option<FuzzyMatch> {
+    // Lowercase char by char so the indices stay those of the candidate
+    let candidate: Vec<char> = candidate
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut indices = Vec::new();
+
+    for p in pattern.to_lowercase().chars() {
+        let idx = pos + candidate[pos..].iter().position(|&c| c == p)?;
+        score += match indices.last() {
+            None if idx == 0 => 10,
+            Some(&prev) if idx == prev + 1 => 5,
+            _ => 1,
+        };
+        indices.push(idx);
+        pos = idx + 1;
+    }
+
+    // Prefer shorter candidates among equally good matches
+    Some(FuzzyMatch {
+        score: score * 100 - candidate.len() as i64,
+        indices,
+    })
+}
+
 /// Configuration for a multi-line text editor question.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MultilineQuestion {
     /// Optional default value.
     pub default: Option<String>,
@@ -323,12 +756,17 @@ impl MultilineQuestion {
 
 /// Configuration for a password/masked input question.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MaskedQuestion {
     /// The masking character (default: '*').
     pub mask: Option<char>,
 
     /// Validation function name.
     pub validate: Option<String>,
+
+    /// Whether the answer is a secret, kept out of the plain responses
+    /// (see [`Responses::redact_masked`](crate::Responses::redact_masked)).
+    pub secret: bool,
 }
 
 impl MaskedQuestion {
@@ -341,21 +779,28 @@ impl MaskedQuestion {
     pub fn with_mask(mask: char) -> Self {
         Self {
             mask: Some(mask),
-            validate: None,
+            ..Self::default()
         }
     }
 
     /// Create with a validator.
     pub fn with_validator(validate: Option<String>) -> Self {
         Self {
-            mask: None,
             validate,
+            ..Self::default()
         }
     }
+
+    /// Mark the answer as a secret.
+    pub fn with_secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
 }
 
 /// Configuration for an integer input question.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntQuestion {
     /// Optional default value.
     pub default: Option<i64>,
@@ -368,6 +813,15 @@ pub struct IntQuestion {
 
     /// Validation function name.
     pub validate: Option<String>,
+
+    /// Language tag whose separators answers are typed and shown in, e.g. `"de"`.
+    /// Without one, numbers are written as Rust parses them.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub locale: Option<String>,
+
+    /// Messages of the built-in checks, English unless the survey was translated.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub messages: Messages,
 }
 
 impl IntQuestion {
@@ -379,10 +833,9 @@ impl IntQuestion {
     /// Create with bounds.
     pub fn with_bounds(min: Option<i64>, max: Option<i64>) -> Self {
         Self {
-            default: None,
             min,
             max,
-            validate: None,
+            ..Self::default()
         }
     }
 
@@ -393,16 +846,202 @@ impl IntQuestion {
         validate: Option<String>,
     ) -> Self {
         Self {
-            default: None,
             min,
             max,
             validate,
+            ..Self::default()
+        }
+    }
+
+    /// Type and show answers in the separators of `locale`, e.g. `"de"`.
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// The separators answers are typed and shown in.
+    pub fn number_format(&self) -> NumberFormat {
+        NumberFormat::for_optional_locale(self.locale.as_deref())
+    }
+
+    /// Parse an answer typed in the question's locale.
+    pub fn parse(&self, input: &str) -> Result<i64, String> {
+        self.number_format().parse_int(input, &self.messages)
+    }
+
+    /// Check a value against the bounds.
+    pub fn check_bounds(&self, value: i64) -> Result<(), String> {
+        if let Some(min) = self.min
+            && value < min
+        {
+            return Err(self.messages.at_least(&self.format(min)));
+        }
+        if let Some(max) = self.max
+            && value > max
+        {
+            return Err(self.messages.at_most(&self.format(max)));
+        }
+        Ok(())
+    }
+
+    /// Show a value in the question's locale, e.g. a default.
+    pub fn format(&self, value: i64) -> String {
+        self.number_format().format_int(value)
+    }
+}
+
+/// Configuration for a rating scale question.
+///
+/// The answer is one point of the scale, stored as a `ResponseValue::Int`.
+/// Likert scales additionally carry labels for their lowest and highest point.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatingQuestion {
+    /// Lowest point of the scale.
+    pub min: i64,
+
+    /// Highest point of the scale.
+    pub max: i64,
+
+    /// Labels for the lowest and highest point (Likert scales).
+    pub anchors: Option<(String, String)>,
+
+    /// Optional default value.
+    pub default: Option<i64>,
+
+    /// Validation function name.
+    pub validate: Option<String>,
+}
+
+impl RatingQuestion {
+    /// Create a numeric rating scale from `min` to `max` (inclusive).
+    pub fn new(min: i64, max: i64) -> Self {
+        Self {
+            min,
+            max,
+            anchors: None,
+            default: None,
+            validate: None,
+        }
+    }
+
+    /// Create a Likert scale with `points` points, labelled `low` and `high` at the ends.
+    ///
+    /// The points are numbered from 1.
+    pub fn likert(low: impl Into<String>, high: impl Into<String>, points: i64) -> Self {
+        Self {
+            anchors: Some((low.into(), high.into())),
+            ..Self::new(1, points)
+        }
+    }
+
+    /// Set a validator function.
+    pub fn with_validator(mut self, validate: Option<String>) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// All points of the scale, lowest first.
+    pub fn points(&self) -> std::ops::RangeInclusive<i64> {
+        self.min..=self.max
+    }
+
+    /// Whether `value` is a point of this scale.
+    pub fn contains(&self, value: i64) -> bool {
+        self.points().contains(&value)
+    }
+
+    /// Whether the scale reads naturally as stars: numeric, from 1, at most 10 points.
+    ///
+    /// Other scales (Likert items, 0-10 scores) are shown as a row of numbers.
+    pub fn shows_stars(&self) -> bool {
+        self.anchors.is_none() && self.min == 1 && self.max <= 10
+    }
+
+    /// The anchor label of `value`, if it is the lowest or highest point.
+    pub fn anchor(&self, value: i64) -> Option<&str> {
+        let (low, high) = self.anchors.as_ref()?;
+        if value == self.min {
+            Some(low)
+        } else if value == self.max {
+            Some(high)
+        } else {
+            None
+        }
+    }
+
+    /// A display label for `value`: the number, followed by its anchor if any.
+    pub fn label(&self, value: i64) -> String {
+        match self.anchor(value) {
+            Some(anchor) => format!("{value} - {anchor}"),
+            None => value.to_string(),
+        }
+    }
+}
+
+/// Configuration for a matrix (grid) question.
+///
+/// Every row is answered with one of the columns, e.g. rating several aspects
+/// on the same scale. The answer is stored as a `ResponseValue::ChosenVariants`
+/// holding the chosen column index for each row, in row order.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatrixQuestion {
+    /// Row labels (the items being answered).
+    pub rows: Vec<String>,
+
+    /// Column labels (the shared scale).
+    pub columns: Vec<String>,
+
+    /// Validation function name.
+    pub validate: Option<String>,
+}
+
+impl MatrixQuestion {
+    /// Create a matrix with the given row and column labels.
+    pub fn new(rows: Vec<String>, columns: Vec<String>) -> Self {
+        Self {
+            rows,
+            columns,
+            validate: None,
         }
     }
+
+    /// Create a matrix from the variant names of two enum surveys.
+    ///
+    /// Used by the derive macro for `HashMap<Row, Column>` fields.
+    pub fn from_surveys(rows: SurveyDefinition, columns: SurveyDefinition) -> Self {
+        Self::new(variant_names(rows), variant_names(columns))
+    }
+
+    /// Set a validator function.
+    pub fn with_validator(mut self, validate: Option<String>) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Whether `answer` picks a valid column for every row.
+    pub fn is_complete(&self, answer: &[usize]) -> bool {
+        answer.len() == self.rows.len() && answer.iter().all(|&column| column < self.columns.len())
+    }
+}
+
+/// Variant names of an enum survey (a single OneOf question).
+fn variant_names(definition: SurveyDefinition) -> Vec<String> {
+    definition
+        .questions
+        .into_iter()
+        .flat_map(|question| match question.kind {
+            QuestionKind::OneOf(one_of) => one_of.variants,
+            _ => vec![],
+        })
+        .map(|variant| variant.name)
+        .collect()
 }
 
 /// Configuration for a floating-point input question.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FloatQuestion {
     /// Optional default value.
     pub default: Option<f64>,
@@ -415,6 +1054,15 @@ pub struct FloatQuestion {
 
     /// Validation function name.
     pub validate: Option<String>,
+
+    /// Language tag whose separators answers are typed and shown in, e.g. `"de"`.
+    /// Without one, numbers are written as Rust parses them.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub locale: Option<String>,
+
+    /// Messages of the built-in checks, English unless the survey was translated.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub messages: Messages,
 }
 
 impl FloatQuestion {
@@ -426,10 +1074,9 @@ impl FloatQuestion {
     /// Create with bounds.
     pub fn with_bounds(min: Option<f64>, max: Option<f64>) -> Self {
         Self {
-            default: None,
             min,
             max,
-            validate: None,
+            ..Self::default()
         }
     }
 
@@ -440,16 +1087,410 @@ impl FloatQuestion {
         validate: Option<String>,
     ) -> Self {
         Self {
-            default: None,
             min,
             max,
             validate,
+            ..Self::default()
+        }
+    }
+
+    /// Type and show answers in the separators of `locale`, e.g. `"de"`.
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// The separators answers are typed and shown in.
+    pub fn number_format(&self) -> NumberFormat {
+        NumberFormat::for_optional_locale(self.locale.as_deref())
+    }
+
+    /// Parse an answer typed in the question's locale.
+    pub fn parse(&self, input: &str) -> Result<f64, String> {
+        self.number_format().parse_float(input, &self.messages)
+    }
+
+    /// Check a value against the bounds.
+    pub fn check_bounds(&self, value: f64) -> Result<(), String> {
+        if let Some(min) = self.min
+            && value < min
+        {
+            return Err(self.messages.at_least(&self.format(min)));
+        }
+        if let Some(max) = self.max
+            && value > max
+        {
+            return Err(self.messages.at_most(&self.format(max)));
+        }
+        Ok(())
+    }
+
+    /// Show a value in the question's locale, e.g. a default.
+    pub fn format(&self, value: f64) -> String {
+        self.number_format().format_float(value)
+    }
+}
+
+/// Configuration for a money amount in a fixed currency.
+///
+/// The answer is stored as a `ResponseValue::Int` in minor units (cents for USD).
+/// Amounts are shown and typed with the currency symbol and the separators of the
+/// question's locale, e.g. `$1,250.50` in `en` or `1.250,50 €` in `de`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurrencyQuestion {
+    /// ISO 4217 currency code, e.g. `"USD"`.
+    pub currency: String,
+
+    /// Language tag that selects separators and symbol placement, e.g. `"en"` or `"de-AT"`.
+    pub locale: String,
+
+    /// Optional default amount in minor units.
+    pub default: Option<i64>,
+
+    /// Optional minimum amount in minor units.
+    pub min: Option<i64>,
+
+    /// Optional maximum amount in minor units.
+    pub max: Option<i64>,
+
+    /// Validation function name.
+    pub validate: Option<String>,
+    /// Messages of the built-in checks, English unless the survey was translated.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub messages: Messages,
+}
+
+impl CurrencyQuestion {
+    /// Create a currency question formatted for the `en` locale.
+    pub fn new(currency: impl Into<String>) -> Self {
+        Self {
+            currency: currency.into().to_uppercase(),
+            locale: "en".to_string(),
+            default: None,
+            min: None,
+            max: None,
+            validate: None,
+            messages: Messages::default(),
+        }
+    }
+
+    /// Format and parse amounts for another locale.
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = locale.into();
+        self
+    }
+
+    /// Set bounds in minor units.
+    pub fn with_bounds(mut self, min: Option<i64>, max: Option<i64>) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set a validator function.
+    pub fn with_validator(mut self, validate: Option<String>) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// The currency symbol, or the currency code if it has no common symbol.
+    pub fn symbol(&self) -> &str {
+        match self.currency.as_str() {
+            "USD" => "$",
+            "EUR" => "€",
+            "GBP" => "£",
+            "JPY" | "CNY" => "¥",
+            "INR" => "₹",
+            "KRW" => "₩",
+            "BRL" => "R$",
+            "CAD" => "CA$",
+            "AUD" => "A$",
+            code => code,
+        }
+    }
+
+    /// Number of digits after the decimal separator (2 for most currencies).
+    pub fn minor_digits(&self) -> u32 {
+        match self.currency.as_str() {
+            "JPY" | "KRW" | "ISK" | "VND" | "CLP" => 0,
+            "BHD" | "KWD" | "OMR" | "JOD" | "TND" => 3,
+            _ => 2,
+        }
+    }
+
+    /// Format an amount in minor units with symbol and separators, e.g. `$1,250.50`.
+    pub fn format(&self, minor: i64) -> String {
+        let style = self.style();
+        let number = self.format_number(minor.unsigned_abs(), style.group, style.decimal);
+        let sign = if minor < 0 { "-" } else { "" };
+        let symbol = self.symbol();
+        if style.symbol_after {
+            format!("{sign}{number} {symbol}")
+        } else if symbol.chars().all(|c| c.is_ascii_uppercase()) {
+            format!("{sign}{symbol} {number}")
+        } else {
+            format!("{sign}{symbol}{number}")
+        }
+    }
+
+    /// Parse an amount typed in the question's locale into minor units.
+    ///
+    /// The symbol, the currency code, whitespace and group separators are ignored,
+    /// so `"1,250.50"`, `"$1,250.50"` and `"1250.5 USD"` all parse in `en`.
+    pub fn parse(&self, input: &str) -> Result<i64, String> {
+        let style = self.style();
+        let mut text: String = input
+            .replace(self.symbol(), "")
+            .replace(&self.currency, "")
+            .replace(&self.currency.to_lowercase(), "")
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        if let Some(group) = style.group {
+            text.retain(|c| c != group);
+        }
+
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.as_str()),
+        };
+        let (whole, fraction) = match text.split_once(style.decimal) {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (text, ""),
+        };
+        let digits = self.minor_digits();
+        let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if whole.is_empty() && fraction.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+            return Err(format!("Enter an amount like {}", self.format(125_050)));
+        }
+        if fraction.len() > digits as usize {
+            return Err(match digits {
+                0 => format!("{} has no decimal places", self.currency),
+                _ => format!("Use at most {digits} decimal places"),
+            });
+        }
+
+        let too_large = || "Amount is too large".to_string();
+        let whole: i64 = if whole.is_empty() {
+            0
+        } else {
+            whole.parse().map_err(|_| too_large())?
+        };
+        let fraction: i64 = format!("{fraction:0<width$}", width = digits as usize)
+            .parse()
+            .unwrap_or(0);
+        let minor = whole
+            .checked_mul(10_i64.pow(digits))
+            .and_then(|whole| whole.checked_add(fraction))
+            .ok_or_else(too_large)?;
+        Ok(if negative { -minor } else { minor })
+    }
+
+    /// Check an amount in minor units against the bounds.
+    pub fn check_bounds(&self, minor: i64) -> Result<(), String> {
+        if let Some(min) = self.min
+            && minor < min
+        {
+            return Err(self.messages.amount_at_least(&self.format(min)));
+        }
+        if let Some(max) = self.max
+            && minor > max
+        {
+            return Err(self.messages.amount_at_most(&self.format(max)));
+        }
+        Ok(())
+    }
+
+    fn format_number(&self, minor: u64, group: Option<char>, decimal: char) -> String {
+        let scale = 10_u64.pow(self.minor_digits());
+        let whole = (minor / scale).to_string();
+
+        let mut number = String::new();
+        for (i, c) in whole.chars().enumerate() {
+            if let Some(group) = group
+                && i > 0
+                && (whole.len() - i).is_multiple_of(3)
+            {
+                number.push(group);
+            }
+            number.push(c);
+        }
+        if self.minor_digits() > 0 {
+            let digits = self.minor_digits() as usize;
+            number.push(decimal);
+            number.push_str(&format!("{:0digits$}", minor % scale));
+        }
+        number
+    }
+
+    fn style(&self) -> NumberStyle {
+        let format = NumberFormat::for_locale(&self.locale);
+        NumberStyle {
+            group: format.group,
+            decimal: format.decimal,
+            // Locales with a decimal comma put the symbol after the amount
+            symbol_after: format.decimal == ',',
+        }
+    }
+}
+
+/// Separators and symbol placement of a locale.
+struct NumberStyle {
+    group: Option<char>,
+    decimal: char,
+    symbol_after: bool,
+}
+
+/// Configuration for a color question.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorQuestion {
+    /// Default color.
+    pub default: Option<Color>,
+
+    /// Validation function name.
+    pub validate: Option<String>,
+}
+
+impl ColorQuestion {
+    /// Create a new color question.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create with a validator.
+    pub fn with_validator(validate: Option<String>) -> Self {
+        Self {
+            default: None,
+            validate,
+        }
+    }
+
+    /// Parse a hex string answer, e.g. `#1e90ff` or `#fff`.
+    pub fn parse(&self, input: &str) -> Result<Color, String> {
+        input
+            .parse()
+            .map_err(|err: ParseColorError| err.to_string())
+    }
+}
+
+/// Which paths a path question accepts.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PathMode {
+    /// Any path, existing or not (e.g. an output file).
+    #[default]
+    Any,
+    /// An existing file, with one of the given extensions if any are listed.
+    File {
+        /// Allowed extensions without the leading dot, e.g. `["png", "jpg"]`.
+        extensions: Vec<String>,
+    },
+    /// An existing directory.
+    Directory,
+}
+
+/// Configuration for a filesystem path question.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathQuestion {
+    /// Which paths are accepted.
+    pub mode: PathMode,
+
+    /// Default path.
+    pub default: Option<String>,
+
+    /// Validation function name.
+    pub validate: Option<String>,
+}
+
+impl PathQuestion {
+    /// Create a question for any path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a question for an existing file, restricted to the given extensions if any.
+    pub fn file(extensions: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            mode: PathMode::File {
+                extensions: extensions.into_iter().map(Into::into).collect(),
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Create a question for an existing directory.
+    pub fn directory() -> Self {
+        Self {
+            mode: PathMode::Directory,
+            ..Self::default()
+        }
+    }
+
+    /// Set the validator.
+    pub fn with_validator(mut self, validate: Option<String>) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Allowed file extensions, empty if any file (or any path) is accepted.
+    pub fn extensions(&self) -> &[String] {
+        match &self.mode {
+            PathMode::File { extensions } => extensions,
+            _ => &[],
+        }
+    }
+
+    /// Short description of the accepted paths for prompts, e.g. `.png/.jpg file`.
+    pub fn hint(&self) -> Option<String> {
+        match &self.mode {
+            PathMode::Any => None,
+            PathMode::File { extensions } if extensions.is_empty() => Some("file".to_string()),
+            PathMode::File { extensions } => Some(format!(".{} file", extensions.join("/."))),
+            PathMode::Directory => Some("directory".to_string()),
+        }
+    }
+
+    /// Check an answer against the mode, looking at the filesystem.
+    ///
+    /// Any input is accepted in [`PathMode::Any`].
+    pub fn check(&self, input: &str) -> Result<(), String> {
+        if self.mode == PathMode::Any {
+            return Ok(());
+        }
+        if input.trim().is_empty() {
+            return Err("Please enter a path".to_string());
+        }
+        let path = std::path::Path::new(input);
+        match &self.mode {
+            PathMode::Any => Ok(()),
+            PathMode::Directory if path.is_dir() => Ok(()),
+            PathMode::Directory => Err(format!("{input} is not an existing directory")),
+            PathMode::File { .. } if !path.is_file() => {
+                Err(format!("{input} is not an existing file"))
+            }
+            PathMode::File { extensions } => {
+                let matches = extensions.is_empty()
+                    || path.extension().is_some_and(|ext| {
+                        extensions
+                            .iter()
+                            .any(|allowed| ext.eq_ignore_ascii_case(allowed.as_str()))
+                    });
+                if matches {
+                    Ok(())
+                } else {
+                    Err(format!("Expected a .{} file", extensions.join(", .")))
+                }
+            }
         }
     }
 }
 
 /// Configuration for a yes/no confirmation question.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConfirmQuestion {
     /// Default value (true for yes, false for no).
     pub default: bool,
@@ -467,8 +1508,100 @@ impl ConfirmQuestion {
     }
 }
 
+/// A single option in a [`SelectQuestion`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelectOption {
+    /// The value stored in the responses when this option is chosen.
+    pub value: String,
+
+    /// The text shown to the user.
+    pub label: String,
+}
+
+impl SelectOption {
+    /// Create an option with a separate display label.
+    pub fn new(value: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+        }
+    }
+}
+
+impl From<String> for SelectOption {
+    fn from(value: String) -> Self {
+        Self {
+            label: value.clone(),
+            value,
+        }
+    }
+}
+
+impl From<&str> for SelectOption {
+    fn from(value: &str) -> Self {
+        Self::from(value.to_string())
+    }
+}
+
+impl<V: Into<String>, L: Into<String>> From<(V, L)> for SelectOption {
+    fn from((value, label): (V, L)) -> Self {
+        Self::new(value, label)
+    }
+}
+
+/// Configuration for a select question (choose one of a runtime-provided list).
+///
+/// Unlike [`OneOfQuestion`], the options are plain strings that are only known
+/// when the survey is built (e.g., from `#[choices_from(fn)]`). The chosen
+/// option's `value` is stored as a `ResponseValue::String`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelectQuestion {
+    /// The available options.
+    pub options: Vec<SelectOption>,
+
+    /// Default selected option index (if any).
+    pub default: Option<usize>,
+
+    /// Validation function name.
+    pub validate: Option<String>,
+}
+
+impl SelectQuestion {
+    /// Create a new select question with the given options.
+    pub fn new(options: Vec<SelectOption>) -> Self {
+        Self {
+            options,
+            default: None,
+            validate: None,
+        }
+    }
+
+    /// Create from anything convertible into options (strings or `(value, label)` pairs).
+    pub fn from_choices<I, T>(choices: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<SelectOption>,
+    {
+        Self::new(choices.into_iter().map(Into::into).collect())
+    }
+
+    /// Set a validator function.
+    pub fn with_validator(mut self, validate: Option<String>) -> Self {
+        self.validate = validate;
+        self
+    }
+
+    /// Find the index of the option with the given value.
+    pub fn position(&self, value: &str) -> Option<usize> {
+        self.options.iter().position(|o| o.value == value)
+    }
+}
+
 /// The type of elements in a list question.
 #[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ListElementKind {
     /// String elements.
     #[default]
@@ -483,6 +1616,7 @@ pub enum ListElementKind {
 ///
 /// Allows collecting multiple values of the same type.
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListQuestion {
     /// The type of elements in the list.
     pub element_kind: ListElementKind,
@@ -495,6 +1629,10 @@ pub struct ListQuestion {
 
     /// Validation function name.
     pub validate: Option<String>,
+
+    /// Messages of the built-in checks, English unless the survey was translated.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub messages: Messages,
 }
 
 impl ListQuestion {
@@ -567,6 +1705,76 @@ impl ListQuestion {
 /// For a field "method", the selection is stored at "method.selected_variant".
 pub const SELECTED_VARIANT_KEY: &str = "selected_variant";
 
+/// The key suffix used to store the comment attached to an answer.
+/// For a field "rating", the comment is stored at "rating.__comment".
+pub const COMMENT_KEY: &str = "__comment";
+
 /// The key suffix used to store selected variant indices for AnyOf questions.
 /// For a field "features", the selections are stored at "features.selected_variants".
 pub const SELECTED_VARIANTS_KEY: &str = "selected_variants";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_completions_are_fuzzy_filtered() {
+        let q = AutocompleteQuestion::from_list(["rust", "ruby", "python", "haskell"]);
+        assert_eq!(q.completions("ru"), vec!["rust", "ruby"]);
+        assert_eq!(q.completions("pyn"), vec!["python"]);
+        assert_eq!(q.completions("HSK"), vec!["haskell"]);
+        assert!(q.completions("java").is_empty());
+    }
+
+    #[test]
+    fn empty_input_keeps_order() {
+        let q = AutocompleteQuestion::from_list(["b", "a", "c"]);
+        assert_eq!(q.completions(""), vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn prefix_matches_rank_first() {
+        let q = AutocompleteQuestion::from_list(["marge", "emma"]);
+        assert_eq!(q.completions("ma"), vec!["marge", "emma"]);
+    }
+
+    #[test]
+    fn likert_points_and_labels() {
+        let q = RatingQuestion::likert("Strongly disagree", "Strongly agree", 5);
+        assert_eq!(q.points().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(q.label(1), "1 - Strongly disagree");
+        assert_eq!(q.label(3), "3");
+        assert_eq!(q.anchor(5), Some("Strongly agree"));
+        assert!(!q.contains(6));
+    }
+
+    #[test]
+    fn currency_formats_and_parses() {
+        let usd = CurrencyQuestion::new("USD");
+        assert_eq!(usd.format(125_050), "$1,250.50");
+        assert_eq!(usd.format(-5), "-$0.05");
+        assert_eq!(usd.parse("1,250.50"), Ok(125_050));
+        assert_eq!(usd.parse("$1250.5"), Ok(125_050));
+        assert_eq!(usd.parse("12 USD"), Ok(1_200));
+        assert!(usd.parse("1.2.3").is_err());
+        assert!(usd.parse("1.005").is_err());
+
+        let eur = CurrencyQuestion::new("EUR").with_locale("de-AT");
+        assert_eq!(eur.format(123_456_789), "1.234.567,89 €");
+        assert_eq!(eur.parse("1.250,50 €"), Ok(125_050));
+
+        let jpy = CurrencyQuestion::new("JPY");
+        assert_eq!(jpy.format(1_500), "¥1,500");
+        assert!(jpy.parse("1500.5").is_err());
+
+        let chf = CurrencyQuestion::new("CHF").with_bounds(Some(100), None);
+        assert_eq!(chf.format(100), "CHF 1.00");
+        assert!(chf.check_bounds(99).is_err());
+    }
+
+    #[test]
+    fn callback_completions() {
+        let q = AutocompleteQuestion::from_fn(|input| vec![format!("{input}.rs")]);
+        assert_eq!(q.completions("main"), vec!["main.rs"]);
+    }
+}
@@ -0,0 +1,228 @@
+//! Shared questions that are defined once and reused across surveys.
+//!
+//! Common questions such as an email address or a consent checkbox are registered
+//! under a stable id and referenced from derives with `#[use_question("common.email")]`.
+//! Every survey that uses the id gets the same prompt, widget and validator:
+//!
+//! ```rust,ignore
+//! use elicitor::question_bank::{self, BankQuestion};
+//!
+//! question_bank::register(
+//!     BankQuestion::new("common.email", "Email address:").with_validator(validate_email),
+//! );
+//!
+//! #[derive(Survey)]
+//! struct Signup {
+//!     #[use_question("common.email")]
+//!     email: String,
+//! }
+//! ```
+//!
+//! Questions must be registered before the survey definition of a type that uses
+//! them is built. Running a survey that uses an unknown id, or a bank question whose
+//! kind does not fit the field, fails with [`SurveyError::QuestionBank`].
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::sync::{PoisonError, RwLock};
+
+use thiserror::Error;
+
+use crate::{Question, QuestionKind, ResponsePath, ResponseValue, Responses, SurveyError};
+
+/// A `#[use_question]` field that the question bank cannot serve.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BankError {
+    /// No question is registered under the id.
+    #[error("question `{0}` is not registered in the question bank")]
+    Unregistered(String),
+
+    /// The kind set with [`BankQuestion::with_kind`] answers with another type than the field.
+    #[error("question `{id}` answers with {banked}, but the field expects {derived}")]
+    KindMismatch {
+        id: String,
+        banked: &'static str,
+        derived: &'static str,
+    },
+}
+
+/// Validator signature shared with `#[validate(fn_name)]`.
+pub type BankValidator = fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>;
+
+/// A question that is registered once and reused by id.
+#[derive(Debug, Clone)]
+pub struct BankQuestion {
+    id: String,
+    prompt_key: String,
+    ask: String,
+    kind: Option<QuestionKind>,
+    validator: Option<BankValidator>,
+}
+
+impl BankQuestion {
+    /// Create a bank question with the given id and prompt.
+    ///
+    /// The prompt key defaults to the id.
+    pub fn new(id: impl Into<String>, ask: impl Into<String>) -> Self {
+        let id = id.into();
+        Self {
+            prompt_key: id.clone(),
+            id,
+            ask: ask.into(),
+            kind: None,
+            validator: None,
+        }
+    }
+
+    /// Use a different key when translating the prompt.
+    pub fn with_prompt_key(mut self, prompt_key: impl Into<String>) -> Self {
+        self.prompt_key = prompt_key.into();
+        self
+    }
+
+    /// Replace the question kind derived from the field type, e.g. to mask the input.
+    ///
+    /// The kind must produce the same kind of response as the field type expects.
+    pub fn with_kind(mut self, kind: QuestionKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Check every answer to this question.
+    ///
+    /// Like `#[validate]`, this applies to answers that backends validate; yes/no answers are not.
+    pub fn with_validator(mut self, validator: BankValidator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// The id this question is registered under.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The key used to look up translations of the prompt.
+    pub fn prompt_key(&self) -> &str {
+        &self.prompt_key
+    }
+
+    /// The prompt text.
+    pub fn ask(&self) -> &str {
+        &self.ask
+    }
+
+    /// The question kind that replaces the derived one, if any.
+    pub fn kind(&self) -> Option<&QuestionKind> {
+        self.kind.as_ref()
+    }
+
+    /// The validator, if any.
+    pub fn validator(&self) -> Option<BankValidator> {
+        self.validator
+    }
+}
+
+static BANK: RwLock<BTreeMap<String, BankQuestion>> = RwLock::new(BTreeMap::new());
+
+/// Register a question, replacing any question with the same id.
+pub fn register(question: BankQuestion) {
+    BANK.write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(question.id.clone(), question);
+}
+
+/// Look up a registered question.
+pub fn get(id: &str) -> Option<BankQuestion> {
+    BANK.read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(id)
+        .cloned()
+}
+
+/// Ids of all registered questions, sorted.
+pub fn ids() -> Vec<String> {
+    BANK.read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .keys()
+        .cloned()
+        .collect()
+}
+
+/// All prompt keys of registered questions, paired with their current prompt.
+pub fn prompt_keys() -> Vec<(String, String)> {
+    BANK.read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .values()
+        .map(|question| (question.prompt_key.clone(), question.ask.clone()))
+        .collect()
+}
+
+thread_local! {
+    /// Fields that [`question`] could not serve since the last call of [`checked`].
+    static ERRORS: RefCell<Vec<BankError>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Build a survey definition, failing if one of its `#[use_question]` fields has no
+/// fitting question in the bank.
+///
+/// The derived builders run this before asking anything. Call it at startup, e.g.
+/// `question_bank::checked(Signup::survey)`, to find missing registrations early.
+pub fn checked<T>(build: impl FnOnce() -> T) -> Result<T, SurveyError> {
+    ERRORS.with_borrow_mut(Vec::clear);
+    let built = build();
+    match ERRORS.with_borrow_mut(std::mem::take).into_iter().next() {
+        Some(err) => Err(SurveyError::QuestionBank(err)),
+        None => Ok(built),
+    }
+}
+
+/// Build the question for a `#[use_question]` field.
+///
+/// If the bank cannot serve the field, the derived question is used with the id as
+/// prompt, and [`checked`] reports the error.
+#[doc(hidden)]
+pub fn question(id: &str, path: ResponsePath, derived_kind: QuestionKind) -> Question {
+    match lookup(id, &derived_kind) {
+        Ok(question) => Question::new(path, question.ask, question.kind.unwrap_or(derived_kind))
+            .with_key(question.prompt_key),
+        Err(err) => {
+            ERRORS.with_borrow_mut(|errors| errors.push(err));
+            Question::new(path, id, derived_kind)
+        }
+    }
+}
+
+/// The question registered under `id`, if its kind fits a field of `derived_kind`.
+fn lookup(id: &str, derived_kind: &QuestionKind) -> Result<BankQuestion, BankError> {
+    let question = get(id).ok_or_else(|| BankError::Unregistered(id.to_string()))?;
+    if let Some(kind) = &question.kind {
+        let nested =
+            |kind: &QuestionKind| matches!(kind, QuestionKind::AllOf(_) | QuestionKind::Unit);
+        if kind.answer_type() != derived_kind.answer_type() || nested(kind) != nested(derived_kind)
+        {
+            return Err(BankError::KindMismatch {
+                id: id.to_string(),
+                banked: kind.answer_type(),
+                derived: derived_kind.answer_type(),
+            });
+        }
+    }
+    Ok(question)
+}
+
+/// Run the validator of a `#[use_question]` field.
+#[doc(hidden)]
+pub fn validate(
+    id: &str,
+    value: &ResponseValue,
+    responses: &Responses,
+    path: &ResponsePath,
+) -> Result<(), String> {
+    let validator = get(id)
+        .ok_or_else(|| BankError::Unregistered(id.to_string()).to_string())?
+        .validator;
+    match validator {
+        Some(validator) => validator(value, responses, path),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,212 @@
+//! Backends looked up by name at runtime.
+//!
+//! Backend crates register a factory under a name, applications pick a backend by
+//! name (e.g. from a command line flag) without depending on the backend crate
+//! directly:
+//!
+//! ```rust,ignore
+//! elicitor::register_backend("my-tui", || elicitor::boxed_backend(MyTui::new()));
+//!
+//! let config = Config::builder().run_named("my-tui")?;
+//! ```
+//!
+//! With the `inventory` feature, backend crates can register at link time instead, so
+//! linking the crate is enough:
+//!
+//! ```rust,ignore
+//! elicitor::inventory::submit! {
+//!     elicitor::BackendRegistration::new("my-tui", || elicitor::boxed_backend(MyTui::new()))
+//! }
+//! ```
+//!
+//! Runtime registrations take precedence over link-time ones with the same name.
+
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    ResponsePath, ResponseValue, Responses, SurveyBackend, SurveyDefinition, SurveyObserver,
+};
+
+/// A backend with its error type erased, as returned by the registry.
+pub type BoxedBackend = Box<dyn SurveyBackend<Error = anyhow::Error>>;
+
+type Factory = Arc<dyn Fn() -> BoxedBackend + Send + Sync>;
+
+static REGISTRY: Mutex<Vec<(String, Factory)>> = Mutex::new(Vec::new());
+
+/// Error returned when no backend is registered under a name.
+#[derive(Debug, thiserror::Error)]
+#[error("No backend registered as `{name}` (available: {})", available.join(", "))]
+pub struct UnknownBackendError {
+    /// The name that was looked up.
+    pub name: String,
+    /// Names of all registered backends.
+    pub available: Vec<String>,
+}
+
+/// A backend registered at link time with `inventory::submit!`.
+#[cfg(feature = "inventory")]
+#[derive(Debug)]
+pub struct BackendRegistration {
+    name: &'static str,
+    factory: fn() -> BoxedBackend,
+}
+
+#[cfg(feature = "inventory")]
+impl BackendRegistration {
+    /// Register `factory` under `name`.
+    pub const fn new(name: &'static str, factory: fn() -> BoxedBackend) -> Self {
+        Self { name, factory }
+    }
+}
+
+#[cfg(feature = "inventory")]
+inventory::collect!(BackendRegistration);
+
+/// Register a backend factory under `name`, replacing an earlier one with that name.
+pub fn register_backend(
+    name: impl Into<String>,
+    factory: impl Fn() -> BoxedBackend + Send + Sync + 'static,
+) {
+    let name = name.into();
+    let mut registry = REGISTRY.lock().unwrap_or_else(|e| e.into_inner());
+    registry.retain(|(registered, _)| *registered != name);
+    registry.push((name, Arc::new(factory)));
+}
+
+/// Create the backend registered under `name`.
+pub fn backend_named(name: &str) -> Result<BoxedBackend, UnknownBackendError> {
+    let factory = REGISTRY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(registered, _)| registered == name)
+        .map(|(_, factory)| factory.clone());
+    if let Some(factory) = factory {
+        return Ok(factory());
+    }
+
+    #[cfg(feature = "inventory")]
+    if let Some(registration) =
+        inventory::iter::<BackendRegistration>().find(|registration| registration.name == name)
+    {
+        return Ok((registration.factory)());
+    }
+
+    Err(UnknownBackendError {
+        name: name.to_string(),
+        available: registered_backends(),
+    })
+}
+
+/// Names of all registered backends, sorted.
+pub fn registered_backends() -> Vec<String> {
+    let mut names: Vec<String> = REGISTRY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    #[cfg(feature = "inventory")]
+    names.extend(
+        inventory::iter::<BackendRegistration>().map(|registration| registration.name.to_string()),
+    );
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Erase the error type of `backend` so it can be returned from a registry factory.
+pub fn boxed_backend<B>(backend: B) -> BoxedBackend
+where
+    B: SurveyBackend + 'static,
+{
+    Box::new(Erased(backend))
+}
+
+struct Erased<B>(B);
+
+impl<B: SurveyBackend> SurveyBackend for Erased<B> {
+    type Error = anyhow::Error;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.0.collect(definition, validate).map_err(Into::into)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        self.0
+            .collect_observed(definition, validate, observer)
+            .map_err(Into::into)
+    }
+}
+
+impl<B: SurveyBackend + ?Sized> SurveyBackend for Box<B> {
+    type Error = B::Error;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        (**self).collect(definition, validate)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        (**self).collect_observed(definition, validate, observer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Canned;
+
+    impl SurveyBackend for Canned {
+        type Error = std::io::Error;
+
+        fn collect(
+            &self,
+            _definition: &SurveyDefinition,
+            _validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        ) -> Result<Responses, Self::Error> {
+            let mut responses = Responses::new();
+            responses.insert("name", "canned");
+            Ok(responses)
+        }
+    }
+
+    #[test]
+    fn backends_are_found_by_name() {
+        register_backend("registry-test", || boxed_backend(Canned));
+
+        assert!(registered_backends().contains(&"registry-test".to_string()));
+        let backend = backend_named("registry-test").unwrap();
+        let responses = backend
+            .collect(&SurveyDefinition::empty(), &|_, _, _| Ok(()))
+            .unwrap();
+        assert_eq!(
+            responses.get_string(&ResponsePath::new("name")).unwrap(),
+            "canned"
+        );
+
+        let err = backend_named("registry-missing").err().unwrap();
+        assert!(err.available.contains(&"registry-test".to_string()));
+    }
+}
@@ -0,0 +1,411 @@
+//! Stateless rendering steps for custom frontends.
+//!
+//! Frontends that cannot implement [`SurveyBackend`](crate::SurveyBackend) as one blocking
+//! call (games, embedded displays, web servers) ask for the [`render_plan`] of a survey
+//! given the answers so far, show what it lists, and store answers with
+//! [`record_answer`]. The plan grows as answers come in, e.g. with the follow-up
+//! questions of the chosen enum variant:
+//!
+//! ```rust,ignore
+//! let definition = Order::survey();
+//! let mut responses = Responses::new();
+//! apply_assumed(&definition, &mut responses);
+//!
+//! while let Some(step) = next_step(&definition, &responses) {
+//!     let value = my_frontend.ask(&step);
+//!     Order::validate_field(&value, &responses, &step.path)?;
+//!     record_answer(&mut responses, &step, value);
+//! }
+//! let order = Order::from_responses(&responses);
+//! ```
+//!
+//! Answers are stored at the same paths as with the built-in backends, so derived
+//! surveys can be reconstructed from them.
+
+use crate::{
+    DefaultValue, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyDefinition, Variant,
+};
+
+/// One thing for a frontend to show.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderStep {
+    /// Text without input: the prelude (first) or the epilogue (last).
+    Message(String),
+
+    /// Heading of a group of questions, e.g. a nested struct.
+    Heading {
+        /// Path of the group, its questions are below it.
+        path: ResponsePath,
+        /// The group's prompt.
+        text: String,
+    },
+
+    /// A question to show, answered or not.
+    Question(QuestionStep),
+}
+
+/// A question in a [`render_plan`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestionStep {
+    /// Where the answer is stored.
+    ///
+    /// For enum and multi-select questions this is the `selected_variant(s)` key below
+    /// the question's path.
+    pub path: ResponsePath,
+
+    /// The prompt to show.
+    pub prompt: String,
+
+    /// What to collect. Variants of enum and multi-select questions are listed for the
+    /// choice only, their follow-up questions become steps of their own once chosen.
+    pub kind: QuestionKind,
+
+    /// Value to pre-fill, if any.
+    pub suggestion: Option<ResponseValue>,
+
+    /// Whether the answer is assumed and the question should not be shown.
+    pub assumed: bool,
+
+    /// The current answer, if any.
+    pub answer: Option<ResponseValue>,
+}
+
+impl QuestionStep {
+    /// Whether the step still needs an answer from the respondent.
+    pub fn is_pending(&self) -> bool {
+        self.answer.is_none() && !self.assumed
+    }
+}
+
+/// The steps of `definition` given the answers in `responses`, in the order to show them.
+pub fn render_plan(definition: &SurveyDefinition, responses: &Responses) -> Vec<RenderStep> {
+    let mut steps = Vec::new();
+    if let Some(prelude) = &definition.prelude {
+        steps.push(RenderStep::Message(prelude.clone()));
+    }
+    plan_questions(
+        &definition.questions,
+        &ResponsePath::empty(),
+        responses,
+        &mut steps,
+    );
+    if let Some(epilogue) = &definition.epilogue {
+        steps.push(RenderStep::Message(epilogue.clone()));
+    }
+    steps
+}
+
+/// The first question that still needs an answer, `None` once the survey is complete.
+pub fn next_step(definition: &SurveyDefinition, responses: &Responses) -> Option<QuestionStep> {
+    render_plan(definition, responses)
+        .into_iter()
+        .find_map(|step| match step {
+            RenderStep::Question(question) if question.is_pending() => Some(question),
+            _ => None,
+        })
+}
+
+/// Paths of the questions that `responses` leaves unanswered or whose answer `validate`
+/// rejects, e.g. after new fields were added to a stored configuration.
+///
+/// The paths are the [`QuestionStep::path`]s, so enum and multi-select questions are
+/// listed with their `selected_variant(s)` key. Assumed questions are never listed.
+pub fn pending_paths(
+    definition: &SurveyDefinition,
+    responses: &Responses,
+    validate: impl Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+) -> Vec<ResponsePath> {
+    render_plan(definition, responses)
+        .into_iter()
+        .filter_map(|step| match step {
+            RenderStep::Question(question) if !question.assumed => match &question.answer {
+                None => Some(question.path),
+                Some(answer) => validate(answer, responses, &question.path)
+                    .is_err()
+                    .then_some(question.path),
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Store the answer to `step`.
+///
+/// For multi-select questions this also records which variant each selected item is,
+/// as the built-in backends do.
+pub fn record_answer(responses: &mut Responses, step: &QuestionStep, value: ResponseValue) {
+    if let (QuestionKind::AnyOf(_), ResponseValue::ChosenVariants(indices)) = (&step.kind, &value) {
+        let parent = step.path.parent();
+        for (item, &variant) in indices.iter().enumerate() {
+            responses.insert(
+                parent.child(&item.to_string()).child(SELECTED_VARIANT_KEY),
+                ResponseValue::ChosenVariant(variant),
+            );
+        }
+    }
+    responses.insert(step.path.clone(), value);
+}
+
+/// Store the assumed answers of `definition` that are not answered yet.
+///
+/// Assumed enum choices can unlock further assumed questions, which are stored as well.
+/// A `false` assumed for anything but a confirmation marks an `Option` assumed to be
+/// `None` and stores nothing.
+pub fn apply_assumed(definition: &SurveyDefinition, responses: &mut Responses) {
+    loop {
+        let assumed: Vec<QuestionStep> = render_plan(definition, responses)
+            .into_iter()
+            .filter_map(|step| match step {
+                RenderStep::Question(question)
+                    if question.assumed
+                        && !responses.contains(&question.path)
+                        && !is_none_marker(&question) =>
+                {
+                    Some(question)
+                }
+                _ => None,
+            })
+            .collect();
+        if assumed.is_empty() {
+            return;
+        }
+        for step in assumed {
+            if let Some(value) = step.answer.clone() {
+                record_answer(responses, &step, value);
+            }
+        }
+    }
+}
+
+/// Whether the assumed answer of `step` marks an `Option` assumed to be `None`.
+fn is_none_marker(step: &QuestionStep) -> bool {
+    matches!(step.answer, Some(ResponseValue::Bool(false)))
+        && !matches!(step.kind, QuestionKind::Confirm(_))
+}
+
+fn plan_questions(
+    questions: &[Question],
+    prefix: &ResponsePath,
+    responses: &Responses,
+    steps: &mut Vec<RenderStep>,
+) {
+    for question in questions {
+        plan_question(question, prefix, responses, steps);
+    }
+}
+
+fn plan_question(
+    question: &Question,
+    prefix: &ResponsePath,
+    responses: &Responses,
+    steps: &mut Vec<RenderStep>,
+) {
+    let path = if question.path().is_empty() {
+        prefix.clone()
+    } else {
+        prefix.child(question.path().as_str())
+    };
+
+    match question.kind() {
+        QuestionKind::Unit => {}
+        QuestionKind::AllOf(all_of) => {
+            if !question.ask().is_empty() {
+                steps.push(RenderStep::Heading {
+                    path: path.clone(),
+                    text: question.ask().to_string(),
+                });
+            }
+            plan_questions(all_of.questions(), &path, responses, steps);
+        }
+        QuestionKind::OneOf(one_of) => {
+            let step = question_step(question, path.child(SELECTED_VARIANT_KEY), &path, responses);
+            let chosen = match &step.answer {
+                Some(ResponseValue::ChosenVariant(idx)) => one_of.variants.get(*idx),
+                _ => None,
+            };
+            steps.push(RenderStep::Question(step));
+            if let Some(variant) = chosen {
+                plan_variant(variant, &path, responses, steps);
+            }
+        }
+        QuestionKind::AnyOf(any_of) => {
+            let step = question_step(
+                question,
+                path.child(SELECTED_VARIANTS_KEY),
+                &path,
+                responses,
+            );
+            let chosen = match &step.answer {
+                Some(ResponseValue::ChosenVariants(indices)) => indices.clone(),
+                _ => Vec::new(),
+            };
+            steps.push(RenderStep::Question(step));
+            for (item, idx) in chosen.into_iter().enumerate() {
+                if let Some(Variant {
+                    kind: QuestionKind::AllOf(all_of),
+                    ..
+                }) = any_of.variants.get(idx)
+                {
+                    let item_path = path.child(&item.to_string());
+                    plan_questions(all_of.questions(), &item_path, responses, steps);
+                }
+            }
+        }
+        _ => {
+            let step = question_step(question, path.clone(), &path, responses);
+            steps.push(RenderStep::Question(step));
+        }
+    }
+}
+
+/// Follow-up steps of the chosen variant of an enum question at `path`.
+fn plan_variant(
+    variant: &Variant,
+    path: &ResponsePath,
+    responses: &Responses,
+    steps: &mut Vec<RenderStep>,
+) {
+    match &variant.kind {
+        QuestionKind::Unit => {}
+        QuestionKind::AllOf(all_of) => plan_questions(all_of.questions(), path, responses, steps),
+        _ => plan_question(&newtype_question(variant), path, responses, steps),
+    }
+}
+
+/// The question asking for the value of a newtype variant, stored below the enum's path.
+pub(crate) fn newtype_question(variant: &Variant) -> Question {
+    let prompt = match &variant.kind {
+        QuestionKind::OneOf(_) => format!("Select {}:", variant.name),
+        QuestionKind::AnyOf(_) => format!("Select {} options:", variant.name),
+        _ => format!("Enter {} value:", variant.name),
+    };
+    Question::new(variant.name.as_str(), prompt, variant.kind.clone())
+}
+
+/// The prompt of `question` at `path`, made from the field name if it has none.
+pub(crate) fn prompt_of(question: &Question, path: &ResponsePath) -> String {
+    if question.ask().is_empty() {
+        path.last().unwrap_or_default().replace('_', " ")
+    } else {
+        question.ask().to_string()
+    }
+}
+
+fn question_step(
+    question: &Question,
+    answer_path: ResponsePath,
+    path: &ResponsePath,
+    responses: &Responses,
+) -> QuestionStep {
+    let prompt = prompt_of(question, path);
+    let (suggestion, assumed) = match question.default() {
+        DefaultValue::None => (None, None),
+        DefaultValue::Suggested(value) => (Some(value.clone()), None),
+        DefaultValue::Assumed(value) => (None, Some(value.clone())),
+    };
+    let suggestion = suggestion.or_else(|| match question.kind() {
+        QuestionKind::OneOf(one_of) => one_of.default.map(ResponseValue::ChosenVariant),
+        QuestionKind::AnyOf(any_of) if !any_of.defaults.is_empty() => {
+            Some(ResponseValue::ChosenVariants(any_of.defaults.clone()))
+        }
+        _ => None,
+    });
+    QuestionStep {
+        answer: responses.get(&answer_path).cloned().or(assumed.clone()),
+        path: answer_path,
+        prompt,
+        kind: question.kind().clone(),
+        suggestion,
+        assumed: assumed.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AllOfQuestion, InputQuestion, OneOfQuestion};
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "contact",
+                "Contact by?",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Mail"),
+                    Variant::new(
+                        "Phone",
+                        QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                            "number",
+                            "Number?",
+                            QuestionKind::Input(InputQuestion::new()),
+                        )])),
+                    ),
+                ])),
+            ),
+        ])
+        .with_epilogue("Thanks!")
+    }
+
+    #[test]
+    fn plan_grows_with_chosen_variants() {
+        let definition = definition();
+        let mut responses = Responses::new();
+
+        let step = next_step(&definition, &responses).unwrap();
+        assert_eq!(step.path.as_str(), "name");
+        record_answer(&mut responses, &step, ResponseValue::String("Ada".into()));
+
+        let step = next_step(&definition, &responses).unwrap();
+        assert_eq!(step.path.as_str(), "contact.selected_variant");
+        assert_eq!(render_plan(&definition, &responses).len(), 3);
+        record_answer(&mut responses, &step, ResponseValue::ChosenVariant(1));
+
+        let plan = render_plan(&definition, &responses);
+        assert_eq!(plan.len(), 4);
+        assert_eq!(plan.last(), Some(&RenderStep::Message("Thanks!".into())));
+
+        let step = next_step(&definition, &responses).unwrap();
+        assert_eq!(step.path.as_str(), "contact.number");
+        record_answer(&mut responses, &step, ResponseValue::String("555".into()));
+        assert!(next_step(&definition, &responses).is_none());
+    }
+
+    #[test]
+    fn pending_paths_list_missing_and_rejected_answers() {
+        let definition = definition();
+        let mut responses = Responses::new();
+        responses.insert("name", "");
+        responses.insert("contact.selected_variant", ResponseValue::ChosenVariant(1));
+
+        let pending = pending_paths(&definition, &responses, |value, _, _| match value {
+            ResponseValue::String(s) if s.is_empty() => Err("required".into()),
+            _ => Ok(()),
+        });
+        assert_eq!(
+            pending,
+            vec![
+                ResponsePath::new("name"),
+                ResponsePath::new("contact.number")
+            ]
+        );
+    }
+
+    #[test]
+    fn assumed_answers_are_applied() {
+        let mut definition = definition();
+        definition.questions[1].set_assumption(ResponseValue::ChosenVariant(0));
+
+        let mut responses = Responses::new();
+        apply_assumed(&definition, &mut responses);
+        assert_eq!(
+            responses.get(&ResponsePath::new("contact.selected_variant")),
+            Some(&ResponseValue::ChosenVariant(0))
+        );
+        assert_eq!(
+            next_step(&definition, &responses).map(|step| step.path),
+            Some(ResponsePath::new("name"))
+        );
+    }
+}
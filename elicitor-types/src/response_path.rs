@@ -7,7 +7,9 @@ use std::fmt;
 ///
 /// This is an internal type. Users interact with surveys through the
 /// generated builder methods like `suggest_name()` or `assume_address_street()`.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct ResponsePath {
     /// Dot-separated path string, e.g., "address.street"
     path: String,
@@ -77,6 +79,11 @@ impl ResponsePath {
         self.strip_prefix(prefix.as_str())
     }
 
+    /// Whether this path is `ancestor` or lies below it. Every path lies below the empty path.
+    pub fn is_within(&self, ancestor: &ResponsePath) -> bool {
+        ancestor.is_empty() || self.strip_path_prefix(ancestor).is_some()
+    }
+
     /// Get the first segment, if any.
     pub fn first(&self) -> Option<&str> {
         self.segments().next()
@@ -160,6 +167,16 @@ mod tests {
         assert!(stripped.is_empty());
     }
 
+    #[test]
+    fn is_within() {
+        let path = ResponsePath::new("address.street");
+        assert!(path.is_within(&ResponsePath::new("address")));
+        assert!(path.is_within(&path));
+        assert!(path.is_within(&ResponsePath::empty()));
+        assert!(!path.is_within(&ResponsePath::new("addr")));
+        assert!(!ResponsePath::new("address").is_within(&path));
+    }
+
     #[test]
     fn segments() {
         let path = ResponsePath::new("address.location.city");
@@ -2,6 +2,7 @@
 ///
 /// This is the value stored in `Responses` for each answered question.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ResponseValue {
     /// A string value (from Input, Multiline, or Masked questions).
     String(String),
@@ -1,6 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::sync::LazyLock;
 
-use crate::{ResponsePath, ResponseValue};
+#[cfg(feature = "secrecy")]
+use secrecy::SecretString;
+
+use crate::{
+    COMMENT_KEY, Color, Question, QuestionKind, ResponsePath, ResponseValue, SurveyDefinition,
+};
 
 /// Error type for response access operations.
 #[derive(Debug, thiserror::Error)]
@@ -14,49 +21,207 @@ pub enum ResponseError {
         expected: &'static str,
         actual: &'static str,
     },
+
+    #[error("Response at path '{0}' is redacted, use `expose` to read it")]
+    Redacted(ResponsePath),
 }
 
+/// Placeholder printed instead of redacted answers.
+pub const REDACTED: &str = "***";
+
 /// Collected responses from a survey.
 ///
 /// Uses `ResponsePath` as keys to support hierarchical field access.
 /// Response paths are flat (not nested) - a nested field like `address.street`
 /// is stored with the key `ResponsePath::from("address.street")`.
-#[derive(Debug, Clone, Default)]
+///
+/// Answers keep the order they were inserted in, which for collected responses is the
+/// order the questions were asked. [`iter`](Self::iter), `IntoIterator` and `Debug`
+/// output follow it; replacing an answer keeps its position. Metadata and secret answers
+/// are listed sorted by key. Output built from responses is therefore the same for the
+/// same answers.
+///
+/// Besides the answers, responses can carry metadata about how they were collected
+/// (e.g. which prompt variant was shown). Metadata is not part of the answers and is
+/// not counted by [`len`](Self::len).
+///
+/// Answers to masked questions are redacted: `Debug` output and [`iter`](Self::iter)
+/// show [`REDACTED`] in their place and [`get_string`](Self::get_string) refuses them,
+/// so they must be read with [`expose`](Self::expose) or
+/// [`iter_exposed`](Self::iter_exposed).
+///
+/// With the `secrecy` feature, answers to secret questions are held as
+/// [`SecretString`](secrecy::SecretString) apart from the plain values: they are redacted
+/// in `Debug` output, left out of [`iter`](Self::iter), and zeroized on drop.
+#[derive(Clone, Default)]
 pub struct Responses {
-    values: HashMap<ResponsePath, ResponseValue>,
+    values: Vec<(ResponsePath, ResponseValue)>,
+    index: HashMap<ResponsePath, usize>,
+    metadata: BTreeMap<String, String>,
+    redacted: HashSet<ResponsePath>,
+    #[cfg(feature = "secrecy")]
+    secrets: BTreeMap<ResponsePath, SecretString>,
 }
 
 impl Responses {
     /// Create a new empty responses collection.
     pub fn new() -> Self {
         Self {
-            values: HashMap::new(),
+            values: Vec::new(),
+            index: HashMap::new(),
+            metadata: BTreeMap::new(),
+            redacted: HashSet::new(),
+            #[cfg(feature = "secrecy")]
+            secrets: BTreeMap::new(),
         }
     }
 
     /// Insert a response value at the given path.
+    ///
+    /// Replacing an answer keeps its position.
     pub fn insert(&mut self, path: impl Into<ResponsePath>, value: impl Into<ResponseValue>) {
-        self.values.insert(path.into(), value.into());
+        let path = path.into();
+        let value = value.into();
+        match self.index.get(&path) {
+            Some(&idx) => self.values[idx].1 = value,
+            None => {
+                self.index.insert(path.clone(), self.values.len());
+                self.values.push((path, value));
+            }
+        }
     }
 
     /// Get a response value at the given path.
     pub fn get(&self, path: &ResponsePath) -> Option<&ResponseValue> {
-        self.values.get(path)
+        self.index.get(path).map(|&idx| &self.values[idx].1)
     }
 
     /// Check if a response exists at the given path.
     pub fn contains(&self, path: &ResponsePath) -> bool {
-        self.values.contains_key(path)
+        self.index.contains_key(path)
+    }
+
+    /// Check if a response exists at the given path, given as a string.
+    pub fn contains_path(&self, path: &str) -> bool {
+        self.contains(&ResponsePath::new(path))
     }
 
     /// Remove a response at the given path.
     pub fn remove(&mut self, path: &ResponsePath) -> Option<ResponseValue> {
-        self.values.remove(path)
+        let idx = self.index.remove(path)?;
+        let (_, value) = self.values.remove(idx);
+        for later in self.index.values_mut() {
+            if *later > idx {
+                *later -= 1;
+            }
+        }
+        Some(value)
+    }
+
+    /// Move the answers at or below `from` to `to`, e.g. to read answers saved before a
+    /// field was renamed.
+    ///
+    /// Nothing is moved if `to` already has answers. Returns whether anything was moved.
+    pub fn rename(&mut self, from: &ResponsePath, to: &ResponsePath) -> bool {
+        let renamed = |path: &ResponsePath| {
+            path.strip_path_prefix(from).map(|rest| {
+                if rest.is_empty() {
+                    to.clone()
+                } else {
+                    to.child(rest.as_str())
+                }
+            })
+        };
+        let paths = self.paths();
+        #[cfg(feature = "secrecy")]
+        let paths = paths.chain(self.secrets.keys());
+        let paths: Vec<&ResponsePath> = paths.collect();
+        let occupied = paths
+            .iter()
+            .any(|path| path.strip_path_prefix(to).is_some());
+        if occupied || !paths.iter().any(|path| renamed(path).is_some()) {
+            return false;
+        }
+
+        for (path, _) in &mut self.values {
+            if let Some(new_path) = renamed(path) {
+                *path = new_path;
+            }
+        }
+        self.index = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(idx, (path, _))| (path.clone(), idx))
+            .collect();
+        self.redacted = self
+            .redacted
+            .drain()
+            .map(|path| renamed(&path).unwrap_or(path))
+            .collect();
+        #[cfg(feature = "secrecy")]
+        {
+            self.secrets = std::mem::take(&mut self.secrets)
+                .into_iter()
+                .map(|(path, secret)| (renamed(&path).unwrap_or(path), secret))
+                .collect();
+        }
+        true
+    }
+
+    /// Get an iterator over all path-value pairs, in insertion order.
+    ///
+    /// Redacted answers are given as [`REDACTED`], so output built from the iterator
+    /// does not reveal them.
+    pub fn iter(&self) -> ResponsesIter<'_> {
+        ResponsesIter {
+            values: self.values.iter(),
+            redacted: Some(&self.redacted),
+        }
+    }
+
+    /// Get an iterator over all path-value pairs with redacted answers as given, e.g. to
+    /// validate them or to write them to a sink that stores them in plain text.
+    pub fn iter_exposed(&self) -> ResponsesIter<'_> {
+        ResponsesIter {
+            values: self.values.iter(),
+            redacted: None,
+        }
     }
 
-    /// Get an iterator over all path-value pairs.
-    pub fn iter(&self) -> impl Iterator<Item = (&ResponsePath, &ResponseValue)> {
-        self.values.iter()
+    /// Get an iterator over all paths, in insertion order.
+    pub fn paths(&self) -> impl Iterator<Item = &ResponsePath> {
+        self.values.iter().map(|(path, _)| path)
+    }
+
+    /// Get the answers at or below `prefix`, in insertion order, with their full paths.
+    ///
+    /// A trailing `.` on the prefix is ignored, so `"database"` and `"database."` match
+    /// the same answers. Unlike [`filter_prefix`](Self::filter_prefix), `database_url`
+    /// is not below `database`.
+    ///
+    /// # Example
+    /// ```
+    /// use elicitor_types::Responses;
+    ///
+    /// let mut responses = Responses::new();
+    /// responses.insert("database.host", "localhost");
+    /// responses.insert("name", "Alice");
+    /// responses.insert("database.port", 5432i64);
+    ///
+    /// let database: Vec<_> = responses
+    ///     .all_under("database.")
+    ///     .map(|(path, _)| path.as_str())
+    ///     .collect();
+    /// assert_eq!(database, ["database.host", "database.port"]);
+    /// ```
+    pub fn all_under<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a ResponsePath, &'a ResponseValue)> {
+        let prefix = prefix.trim_end_matches('.');
+        self.iter()
+            .filter(move |(path, _)| path.strip_prefix(prefix).is_some())
     }
 
     /// Get the number of responses.
@@ -71,7 +236,71 @@ impl Responses {
 
     /// Merge another responses collection into this one.
     pub fn extend(&mut self, other: Responses) {
-        self.values.extend(other.values);
+        for (path, value) in other.values {
+            self.insert(path, value);
+        }
+        self.metadata.extend(other.metadata);
+        self.redacted.extend(other.redacted);
+        #[cfg(feature = "secrecy")]
+        self.secrets.extend(other.secrets);
+    }
+
+    /// Attach a metadata entry, replacing any previous value for the key.
+    pub fn insert_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.metadata.insert(key.into(), value.into());
+    }
+
+    /// Get a metadata entry.
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Remove a metadata entry.
+    pub fn remove_metadata(&mut self, key: &str) -> Option<String> {
+        self.metadata.remove(key)
+    }
+
+    /// Get an iterator over all metadata entries, sorted by key.
+    pub fn metadata_iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.metadata
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Merge another responses collection into this one below `prefix`.
+    ///
+    /// The counterpart of [`filter_prefix`](Self::filter_prefix): the answers of a nested
+    /// struct, keyed from its root, move to the path of the field holding it.
+    ///
+    /// # Example
+    /// ```
+    /// use elicitor_types::{Responses, ResponsePath};
+    ///
+    /// let mut address = Responses::new();
+    /// address.insert("city", "Springfield");
+    ///
+    /// let mut responses = Responses::new();
+    /// responses.extend_under(&ResponsePath::new("address"), address);
+    /// assert!(responses.get(&ResponsePath::new("address.city")).is_some());
+    /// ```
+    pub fn extend_under(&mut self, prefix: &ResponsePath, other: Responses) {
+        for (path, value) in other.values {
+            self.insert(prefix.child(path.as_str()), value);
+        }
+        self.metadata.extend(other.metadata);
+        self.redacted.extend(
+            other
+                .redacted
+                .into_iter()
+                .map(|path| prefix.child(path.as_str())),
+        );
+        #[cfg(feature = "secrecy")]
+        self.secrets.extend(
+            other
+                .secrets
+                .into_iter()
+                .map(|(path, secret)| (prefix.child(path.as_str()), secret)),
+        );
     }
 
     /// Filter responses to only those with the given path prefix, removing the prefix from keys.
@@ -97,21 +326,203 @@ impl Responses {
         let mut filtered = Responses::new();
         for (path, value) in &self.values {
             if let Some(stripped) = path.strip_path_prefix(prefix) {
-                filtered.values.insert(stripped, value.clone());
+                filtered.insert(stripped, value.clone());
             }
         }
+        for path in &self.redacted {
+            if let Some(stripped) = path.strip_path_prefix(prefix) {
+                filtered.redacted.insert(stripped);
+            }
+        }
+        #[cfg(feature = "secrecy")]
+        for (path, secret) in &self.secrets {
+            if let Some(stripped) = path.strip_path_prefix(prefix) {
+                filtered.secrets.insert(stripped, secret.clone());
+            }
+        }
+        filtered
+    }
+
+    /// Keep only the answers for which `keep` returns `true`, in their order.
+    ///
+    /// Secret answers are kept or dropped by path as well, with the value left out.
+    pub fn retain(&mut self, mut keep: impl FnMut(&ResponsePath, Option<&ResponseValue>) -> bool) {
+        self.values.retain(|(path, value)| keep(path, Some(value)));
+        self.index = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(idx, (path, _))| (path.clone(), idx))
+            .collect();
+        #[cfg(feature = "secrecy")]
+        self.secrets.retain(|path, _| keep(path, None));
+        let mut redacted = std::mem::take(&mut self.redacted);
+        redacted.retain(|path| self.holds(path));
+        self.redacted = redacted;
+    }
+
+    fn holds(&self, path: &ResponsePath) -> bool {
+        #[cfg(feature = "secrecy")]
+        if self.secrets.contains_key(path) {
+            return true;
+        }
+        self.contains(path)
+    }
+
+    /// Copy of these responses without the answers in the `tag` category of `definition`,
+    /// e.g. to strip personal data before sending answers to analytics.
+    ///
+    /// Answers below a tagged group and comments on tagged answers are removed as well.
+    /// Metadata is kept.
+    pub fn strip_tagged(&self, definition: &SurveyDefinition, tag: &str) -> Self {
+        let tagged = definition.tagged_paths(tag);
+        let mut stripped = self.clone();
+        stripped.retain(|path, _| !is_below_any(path, &tagged));
+        stripped
+    }
+
+    /// Copy of these responses with only the answers in the `tag` category of
+    /// `definition`. Metadata is kept.
+    pub fn filter_tagged(&self, definition: &SurveyDefinition, tag: &str) -> Self {
+        let tagged = definition.tagged_paths(tag);
+        let mut filtered = self.clone();
+        filtered.retain(|path, _| is_below_any(path, &tagged));
         filtered
     }
 
+    /// Redact the answers to masked questions of `definition`.
+    ///
+    /// `Debug` output, [`iter`](Self::iter) and `IntoIterator` then give [`REDACTED`] in
+    /// their place. With the `secrecy` feature, answers to secret questions are also moved out of the
+    /// plain values.
+    pub fn redact_masked(&mut self, definition: &SurveyDefinition) {
+        self.redact_questions(&definition.questions, &ResponsePath::empty());
+    }
+
+    /// Redact the answer at the given path.
+    pub fn redact(&mut self, path: impl Into<ResponsePath>) {
+        self.redacted.insert(path.into());
+    }
+
+    /// Check if the answer at the given path is redacted.
+    pub fn is_redacted(&self, path: &ResponsePath) -> bool {
+        self.redacted.contains(path)
+    }
+
+    /// Get a string value at the given path, even if it is redacted.
+    pub fn expose(&self, path: &ResponsePath) -> Result<&str, ResponseError> {
+        match self.get(path) {
+            Some(ResponseValue::String(s)) => Ok(s),
+            Some(other) => Err(ResponseError::TypeMismatch {
+                path: path.clone(),
+                expected: "String",
+                actual: other.type_name(),
+            }),
+            None => Err(ResponseError::MissingPath(path.clone())),
+        }
+    }
+
+    fn redact_questions(&mut self, questions: &[Question], prefix: &ResponsePath) {
+        for question in questions {
+            let path = if question.path().is_empty() {
+                prefix.clone()
+            } else {
+                prefix.child(question.path().as_str())
+            };
+            match question.kind() {
+                QuestionKind::Masked(masked) => self.redact_answer(&path, masked.secret),
+                QuestionKind::AllOf(all_of) => self.redact_questions(all_of.questions(), &path),
+                QuestionKind::OneOf(one_of) => {
+                    for variant in &one_of.variants {
+                        match &variant.kind {
+                            QuestionKind::AllOf(all_of) => {
+                                self.redact_questions(all_of.questions(), &path)
+                            }
+                            QuestionKind::Masked(masked) => {
+                                self.redact_answer(&path.child(&variant.name), masked.secret)
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn redact_answer(&mut self, path: &ResponsePath, secret: bool) {
+        self.redacted.insert(path.clone());
+        if secret {
+            self.seal(path);
+        }
+    }
+
+    #[cfg(feature = "secrecy")]
+    fn seal(&mut self, path: &ResponsePath) {
+        if let Some(ResponseValue::String(value)) = self.remove(path) {
+            self.secrets.insert(path.clone(), SecretString::from(value));
+        }
+    }
+
+    #[cfg(not(feature = "secrecy"))]
+    fn seal(&mut self, _path: &ResponsePath) {}
+
+    /// Store a secret answer at the given path.
+    #[cfg(feature = "secrecy")]
+    pub fn insert_secret(&mut self, path: impl Into<ResponsePath>, secret: SecretString) {
+        self.secrets.insert(path.into(), secret);
+    }
+
+    /// Get a secret answer at the given path.
+    ///
+    /// Falls back to a plain string value for responses that were not sealed.
+    #[cfg(feature = "secrecy")]
+    pub fn get_secret(&self, path: &ResponsePath) -> Result<SecretString, ResponseError> {
+        if let Some(secret) = self.secrets.get(path) {
+            return Ok(secret.clone());
+        }
+        self.expose(path).map(SecretString::from)
+    }
+
     // === Convenience accessors ===
 
     /// Get a string value at the given path.
+    ///
+    /// Redacted answers are refused, read them with [`expose`](Self::expose).
     pub fn get_string(&self, path: &ResponsePath) -> Result<&str, ResponseError> {
+        if self.is_redacted(path) {
+            return Err(ResponseError::Redacted(path.clone()));
+        }
+        self.expose(path)
+    }
+
+    /// Get the comment attached to the answer at the given path, if any.
+    pub fn comment(&self, path: &ResponsePath) -> Option<&str> {
+        self.get_string(&path.child(COMMENT_KEY)).ok()
+    }
+
+    /// Get all comments, keyed by the path of the answer they belong to.
+    pub fn comments(&self) -> impl Iterator<Item = (ResponsePath, &str)> {
+        self.values.iter().filter_map(|(path, value)| {
+            let answer = match path.as_str().strip_suffix(COMMENT_KEY)? {
+                "" => "",
+                answer => answer.strip_suffix('.')?,
+            };
+            Some((ResponsePath::new(answer), value.as_str()?))
+        })
+    }
+
+    /// Get a color at the given path, parsed from its hex string.
+    pub fn get_color(&self, path: &ResponsePath) -> Result<Color, ResponseError> {
         match self.get(path) {
-            Some(ResponseValue::String(s)) => Ok(s),
+            Some(ResponseValue::String(s)) => s.parse().map_err(|_| ResponseError::TypeMismatch {
+                path: path.clone(),
+                expected: "Color",
+                actual: "String",
+            }),
             Some(other) => Err(ResponseError::TypeMismatch {
                 path: path.clone(),
-                expected: "String",
+                expected: "Color",
                 actual: other.type_name(),
             }),
             None => Err(ResponseError::MissingPath(path.clone())),
@@ -227,6 +638,11 @@ impl Responses {
     /// This is used for `Option<T>` fields: returns `false` if the response
     /// is missing OR if it's an empty string (user skipped the optional field).
     pub fn has_value(&self, path: &ResponsePath) -> bool {
+        #[cfg(feature = "secrecy")]
+        if let Some(secret) = self.secrets.get(path) {
+            use secrecy::ExposeSecret;
+            return !secret.expose_secret().is_empty();
+        }
         match self.get(path) {
             Some(ResponseValue::String(s)) => !s.is_empty(),
             Some(_) => true,
@@ -235,21 +651,113 @@ impl Responses {
     }
 }
 
+impl fmt::Debug for Responses {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Values<'a>(&'a Responses);
+
+        impl fmt::Debug for Values<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut map = f.debug_map();
+                for (path, value) in &self.0.values {
+                    if self.0.is_redacted(path) {
+                        map.entry(path, &REDACTED);
+                    } else {
+                        map.entry(path, value);
+                    }
+                }
+                map.finish()
+            }
+        }
+
+        let mut debug = f.debug_struct("Responses");
+        debug
+            .field("values", &Values(self))
+            .field("metadata", &self.metadata);
+        #[cfg(feature = "secrecy")]
+        debug.field("secrets", &self.secrets);
+        debug.finish()
+    }
+}
+
+fn is_below_any(path: &ResponsePath, prefixes: &[ResponsePath]) -> bool {
+    prefixes
+        .iter()
+        .any(|prefix| prefix.is_empty() || path.strip_path_prefix(prefix).is_some())
+}
+
+/// Iterator over the answers of [`Responses`], in insertion order.
+#[derive(Debug, Clone)]
+pub struct ResponsesIter<'a> {
+    values: std::slice::Iter<'a, (ResponsePath, ResponseValue)>,
+    /// Answers given as [`REDACTED`], `None` if they are exposed.
+    redacted: Option<&'a HashSet<ResponsePath>>,
+}
+
+/// [`REDACTED`] as an answer.
+static REDACTED_VALUE: LazyLock<ResponseValue> =
+    LazyLock::new(|| ResponseValue::String(REDACTED.to_string()));
+
+impl<'a> ResponsesIter<'a> {
+    fn entry(
+        &self,
+        (path, value): &'a (ResponsePath, ResponseValue),
+    ) -> (&'a ResponsePath, &'a ResponseValue) {
+        match self.redacted {
+            Some(redacted) if redacted.contains(path) => (path, &REDACTED_VALUE),
+            _ => (path, value),
+        }
+    }
+}
+
+impl<'a> Iterator for ResponsesIter<'a> {
+    type Item = (&'a ResponsePath, &'a ResponseValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.values.next()?;
+        Some(self.entry(entry))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ResponsesIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let entry = self.values.next_back()?;
+        Some(self.entry(entry))
+    }
+}
+
+impl ExactSizeIterator for ResponsesIter<'_> {}
+
+/// Redacted answers are given as [`REDACTED`], like by [`Responses::iter`].
 impl IntoIterator for Responses {
     type Item = (ResponsePath, ResponseValue);
-    type IntoIter = std::collections::hash_map::IntoIter<ResponsePath, ResponseValue>;
+    type IntoIter = std::vec::IntoIter<(ResponsePath, ResponseValue)>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.values.into_iter()
+        let redacted = self.redacted;
+        self.values
+            .into_iter()
+            .map(|(path, value)| {
+                if redacted.contains(&path) {
+                    (path, REDACTED_VALUE.clone())
+                } else {
+                    (path, value)
+                }
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
     }
 }
 
 impl<'a> IntoIterator for &'a Responses {
     type Item = (&'a ResponsePath, &'a ResponseValue);
-    type IntoIter = std::collections::hash_map::Iter<'a, ResponsePath, ResponseValue>;
+    type IntoIter = ResponsesIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.values.iter()
+        self.iter()
     }
 }
 
@@ -270,6 +778,25 @@ mod tests {
         assert_eq!(responses.get_int(&ResponsePath::new("age")).unwrap(), 30);
     }
 
+    #[test]
+    fn iteration_keeps_insertion_order() {
+        let mut responses = Responses::new();
+        for name in ["zeta", "alpha", "mid", "beta"] {
+            responses.insert(name, name);
+        }
+        responses.insert("alpha", "again");
+        responses.remove(&ResponsePath::new("mid"));
+
+        let paths: Vec<_> = responses.paths().map(ResponsePath::as_str).collect();
+        assert_eq!(paths, ["zeta", "alpha", "beta"]);
+        assert_eq!(
+            responses.get_string(&ResponsePath::new("beta")).unwrap(),
+            "beta"
+        );
+        assert!(responses.contains_path("alpha"));
+        assert!(!responses.contains_path("mid"));
+    }
+
     #[test]
     fn filter_prefix() {
         let mut responses = Responses::new();
@@ -289,6 +816,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rename_moves_legacy_answers() {
+        let mut responses = Responses::new();
+        responses.insert("email", "a@example.com");
+        responses.insert("email.__comment", "work address");
+        responses.insert("name", "Alice");
+
+        let to = ResponsePath::new("contact.email");
+        assert!(responses.rename(&ResponsePath::new("email"), &to));
+        let paths: Vec<_> = responses.paths().map(ResponsePath::as_str).collect();
+        assert_eq!(paths, ["contact.email", "contact.email.__comment", "name"]);
+
+        // Answers already at the new path win
+        responses.insert("email", "old@example.com");
+        assert!(!responses.rename(&ResponsePath::new("email"), &to));
+        assert_eq!(responses.get_string(&to).unwrap(), "a@example.com");
+    }
+
     #[test]
     fn type_mismatch_error() {
         let mut responses = Responses::new();
@@ -297,4 +842,59 @@ mod tests {
         let result = responses.get_string(&ResponsePath::new("age"));
         assert!(matches!(result, Err(ResponseError::TypeMismatch { .. })));
     }
+
+    #[test]
+    fn redacted_answers() {
+        let mut responses = Responses::new();
+        responses.insert("name", "Alice");
+        responses.insert("password", "hunter2");
+        responses.redact("password");
+
+        let path = ResponsePath::new("password");
+        assert!(matches!(
+            responses.get_string(&path),
+            Err(ResponseError::Redacted(_))
+        ));
+        assert_eq!(responses.expose(&path).unwrap(), "hunter2");
+
+        let debug = format!("{responses:?}");
+        assert!(debug.contains("\"***\""));
+        assert!(debug.contains("Alice"));
+        assert!(!debug.contains("hunter2"));
+
+        let password = ResponseValue::String(REDACTED.to_string());
+        let iterated: Vec<_> = responses.iter().map(|(_, value)| value.clone()).collect();
+        assert_eq!(iterated, [ResponseValue::from("Alice"), password.clone()]);
+        let reversed = responses.iter().next_back().map(|(_, value)| value);
+        assert_eq!(reversed, Some(&password));
+        let exposed = responses.iter_exposed().nth(1).map(|(_, value)| value);
+        assert_eq!(exposed, Some(&ResponseValue::from("hunter2")));
+        let owned: Vec<_> = responses.into_iter().map(|(_, value)| value).collect();
+        assert_eq!(owned[1], password);
+    }
+
+    #[test]
+    fn comments() {
+        let mut responses = Responses::new();
+        responses.insert("rating", ResponseValue::Int(4));
+        responses.insert("rating.__comment", "Too long");
+        responses.insert("address.city.__comment", "Moving soon");
+        responses.insert("name__comment", "Not a comment");
+
+        assert_eq!(
+            responses.comment(&ResponsePath::new("rating")),
+            Some("Too long")
+        );
+        assert_eq!(responses.comment(&ResponsePath::new("name")), None);
+
+        let mut comments: Vec<_> = responses.comments().collect();
+        comments.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        assert_eq!(
+            comments,
+            vec![
+                (ResponsePath::new("address.city"), "Moving soon"),
+                (ResponsePath::new("rating"), "Too long"),
+            ]
+        );
+    }
 }
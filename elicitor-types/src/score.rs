@@ -0,0 +1,210 @@
+//! Scoring of survey results.
+//!
+//! Questions carry weights, one per choice (see [`Question::with_score`]). After a run,
+//! [`Points::tally`] looks up the chosen answers and records the points each scored
+//! question earned. A scoring function then turns the points into a typed score:
+//!
+//! ```rust,ignore
+//! #[derive(Survey)]
+//! #[score(risk -> Risk)]
+//! struct Declarations {
+//!     #[score(0, 20, 50)]
+//!     smoking: Smoking,
+//!     #[score(30)]
+//!     extreme_sports: bool,
+//! }
+//!
+//! fn risk(_: &Declarations, points: &Points) -> Risk { Risk(points.total()) }
+//!
+//! let (declarations, risk) = Declarations::builder().run_scored(backend)?;
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    Question, QuestionKind, ResponsePath, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY,
+    SurveyDefinition,
+};
+
+/// Points earned by the scored questions of a survey run, keyed by question path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Points {
+    points: HashMap<ResponsePath, i64>,
+}
+
+impl Points {
+    /// Look up the answers to all scored questions of `definition` and weigh them.
+    ///
+    /// Questions that were not answered, e.g. inside an enum variant that was not
+    /// chosen, earn no points and are left out.
+    pub fn tally(definition: &SurveyDefinition, responses: &Responses) -> Self {
+        let mut points = Self::default();
+        points.tally_questions(&definition.questions, &ResponsePath::empty(), responses);
+        points
+    }
+
+    /// Points earned by the question at `path`, if it was scored and answered.
+    pub fn get(&self, path: &ResponsePath) -> Option<i64> {
+        self.points.get(path).copied()
+    }
+
+    /// Sum of the points earned by all questions.
+    pub fn total(&self) -> i64 {
+        self.points.values().sum()
+    }
+
+    /// Sum of the points earned by questions at or below `prefix`.
+    pub fn total_under(&self, prefix: &ResponsePath) -> i64 {
+        self.points
+            .iter()
+            .filter(|(path, _)| prefix.is_empty() || path.strip_path_prefix(prefix).is_some())
+            .map(|(_, points)| points)
+            .sum()
+    }
+
+    /// Points of every scored and answered question.
+    pub fn iter(&self) -> impl Iterator<Item = (&ResponsePath, i64)> {
+        self.points.iter().map(|(path, &points)| (path, points))
+    }
+
+    fn tally_questions(
+        &mut self,
+        questions: &[Question],
+        prefix: &ResponsePath,
+        responses: &Responses,
+    ) {
+        for question in questions {
+            let path = prefix.child(question.path().as_str());
+
+            let weights = question.score_weights();
+            if !weights.is_empty()
+                && let Some(points) = weigh(question.kind(), weights, &path, responses)
+            {
+                self.points.insert(path.clone(), points);
+            }
+
+            match question.kind() {
+                QuestionKind::AllOf(all_of) => {
+                    self.tally_questions(all_of.questions(), &path, responses)
+                }
+                QuestionKind::OneOf(one_of) => {
+                    let chosen = responses
+                        .get_chosen_variant(&path.child(SELECTED_VARIANT_KEY))
+                        .ok()
+                        .and_then(|idx| one_of.variants.get(idx));
+                    if let Some(variant) = chosen
+                        && let QuestionKind::AllOf(all_of) = &variant.kind
+                    {
+                        self.tally_questions(all_of.questions(), &path, responses);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Points for the answer to a question of the given kind, if it was answered.
+fn weigh(
+    kind: &QuestionKind,
+    weights: &[i64],
+    path: &ResponsePath,
+    responses: &Responses,
+) -> Option<i64> {
+    let weight = |idx: usize| weights.get(idx).copied().unwrap_or(0);
+    match kind {
+        QuestionKind::Confirm(_) => {
+            let yes = responses.get_bool(path).ok()?;
+            Some(if yes { weight(0) } else { 0 })
+        }
+        QuestionKind::Select(select) => {
+            let answer = responses.get_string(path).ok()?;
+            select.position(answer).map(weight)
+        }
+        QuestionKind::OneOf(_) => responses
+            .get_chosen_variant(&path.child(SELECTED_VARIANT_KEY))
+            .ok()
+            .map(weight),
+        QuestionKind::AnyOf(_) => responses
+            .get_chosen_variants(&path.child(SELECTED_VARIANTS_KEY))
+            .ok()
+            .map(|chosen| chosen.iter().map(|&idx| weight(idx)).sum()),
+        // An enum field is a group holding the enum's single choice question
+        QuestionKind::AllOf(all_of) => match all_of.questions() {
+            [choice] if choice.path().is_empty() => weigh(choice.kind(), weights, path, responses),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnyOfQuestion, ConfirmQuestion, OneOfQuestion, ResponseValue, Variant};
+
+    fn definition() -> SurveyDefinition {
+        let smoking = OneOfQuestion::new(vec![
+            Variant::unit("Never"),
+            Variant::unit("Occasionally"),
+            Variant::unit("Daily"),
+        ]);
+        SurveyDefinition::new(vec![
+            Question::new("smoking", "Do you smoke?", QuestionKind::OneOf(smoking))
+                .with_score([0, 20, 50]),
+            Question::new(
+                "climbing",
+                "Do you climb?",
+                QuestionKind::Confirm(ConfirmQuestion::new()),
+            )
+            .with_score([30]),
+            Question::new(
+                "diving",
+                "Do you dive?",
+                QuestionKind::Confirm(ConfirmQuestion::new()),
+            ),
+            Question::new(
+                "conditions",
+                "Known conditions:",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![
+                    Variant::unit("Asthma"),
+                    Variant::unit("Diabetes"),
+                ])),
+            )
+            .with_score([10, 25]),
+        ])
+    }
+
+    #[test]
+    fn tally_weighs_chosen_answers() {
+        let mut responses = Responses::new();
+        responses.insert(
+            ResponsePath::new("smoking").child(SELECTED_VARIANT_KEY),
+            ResponseValue::ChosenVariant(1),
+        );
+        responses.insert("climbing", ResponseValue::Bool(true));
+        responses.insert("diving", ResponseValue::Bool(true));
+        responses.insert(
+            ResponsePath::new("conditions").child(SELECTED_VARIANTS_KEY),
+            ResponseValue::ChosenVariants(vec![0, 1]),
+        );
+
+        let points = Points::tally(&definition(), &responses);
+        assert_eq!(points.get(&ResponsePath::new("smoking")), Some(20));
+        assert_eq!(points.get(&ResponsePath::new("climbing")), Some(30));
+        assert_eq!(points.get(&ResponsePath::new("diving")), None);
+        assert_eq!(points.get(&ResponsePath::new("conditions")), Some(35));
+        assert_eq!(points.total(), 85);
+    }
+
+    #[test]
+    fn unanswered_questions_earn_nothing() {
+        let mut responses = Responses::new();
+        responses.insert("climbing", ResponseValue::Bool(false));
+
+        let points = Points::tally(&definition(), &responses);
+        assert_eq!(points.get(&ResponsePath::new("smoking")), None);
+        assert_eq!(points.get(&ResponsePath::new("climbing")), Some(0));
+        assert_eq!(points.total(), 0);
+    }
+}
@@ -0,0 +1,399 @@
+//! A stable text rendering of survey definitions, for snapshot tests.
+//!
+//! [`SurveyDefinition::snapshot`] lists every question in order, one per line, with its
+//! path, kind, prompt and constraints. Defaults and other settings follow on indented
+//! lines, and the questions of nested structs and enum variants are indented below
+//! their parent. Comparing the text against a stored copy catches changes to the
+//! generated survey, e.g. with `insta`:
+//!
+//! ```rust,ignore
+//! #[test]
+//! fn order_form_is_unchanged() {
+//!     insta::assert_snapshot!(OrderForm::survey().snapshot());
+//! }
+//! ```
+//!
+//! ```text
+//! customer_name: Input "Customer name:"
+//! shipping_address: AllOf "Shipping address:"
+//!   street: Input "Street address:"
+//! payment: AllOf "Payment method:"
+//!   OneOf
+//!     Cash payment: Unit
+//!     Credit card: AllOf
+//!       number: Input "Card number:"
+//! ```
+//!
+//! The format only changes when the definition does: no addresses, hash map order or
+//! debug output of callbacks end up in it.
+
+use std::fmt::Write;
+
+use crate::{
+    DefaultValue, ListElementKind, PathMode, Question, QuestionKind, ResponseValue,
+    SurveyDefinition, Variant, question::SuggestionSource,
+};
+
+/// Indentation of one nesting level.
+const INDENT: &str = "  ";
+
+impl SurveyDefinition {
+    /// The questions of the survey as stable, human-readable text, for snapshot tests.
+    ///
+    /// Each question is one line with its path, kind, prompt and constraints, e.g.
+    /// `age: Int "Age?" [min=0, max=150]`. Defaults, help and other settings follow
+    /// on indented lines, and nested questions and enum variants are indented below
+    /// their parent.
+    pub fn snapshot(&self) -> String {
+        let mut out = String::new();
+        if let Some(prelude) = &self.prelude {
+            line(&mut out, 0, &format!("prelude: {prelude:?}"));
+        }
+        for question in &self.questions {
+            write_question(&mut out, 0, question);
+        }
+        if let Some(epilogue) = &self.epilogue {
+            line(&mut out, 0, &format!("epilogue: {epilogue:?}"));
+        }
+        out
+    }
+}
+
+fn line(out: &mut String, depth: usize, text: &str) {
+    let _ = writeln!(out, "{}{text}", INDENT.repeat(depth));
+}
+
+fn write_question(out: &mut String, depth: usize, question: &Question) {
+    let mut head = String::new();
+    if !question.path().is_empty() {
+        head.push_str(question.path().as_str());
+        head.push_str(": ");
+    }
+    head.push_str(kind_name(question.kind()));
+    if !question.ask().is_empty() {
+        let _ = write!(head, " {:?}", question.ask());
+    }
+    let constraints = constraints(question.kind());
+    if !constraints.is_empty() {
+        let _ = write!(head, " [{}]", constraints.join(", "));
+    }
+    line(out, depth, &head);
+
+    let depth = depth + 1;
+    match question.default() {
+        DefaultValue::None => {}
+        DefaultValue::Suggested(value) => {
+            line(out, depth, &format!("suggested: {}", value_text(value)))
+        }
+        DefaultValue::Assumed(value) => {
+            line(out, depth, &format!("assumed: {}", value_text(value)))
+        }
+    }
+    if let Some(key) = question.key() {
+        line(out, depth, &format!("key: {key}"));
+    }
+    if let Some(help) = question.help() {
+        line(out, depth, &format!("help: {help:?}"));
+    }
+    if question.allows_comment() {
+        line(out, depth, "comment allowed");
+    }
+    if question.is_dropdown() {
+        line(out, depth, "dropdown");
+    }
+    if !question.tags().is_empty() {
+        line(out, depth, &format!("tags: {}", question.tags().join(", ")));
+    }
+    if let Some(flag) = question.flag() {
+        line(out, depth, &format!("flag: {flag}"));
+    }
+    if !question.editable_by().is_empty() {
+        line(
+            out,
+            depth,
+            &format!("editable by: {}", question.editable_by().join(", ")),
+        );
+    }
+    if !question.score_weights().is_empty() {
+        line(
+            out,
+            depth,
+            &format!("score: {:?}", question.score_weights()),
+        );
+    }
+
+    write_children(out, depth, question.kind());
+}
+
+/// The nested questions and variants of `kind`.
+fn write_children(out: &mut String, depth: usize, kind: &QuestionKind) {
+    match kind {
+        QuestionKind::AllOf(all_of) => {
+            for question in all_of.questions() {
+                write_question(out, depth, question);
+            }
+        }
+        QuestionKind::OneOf(one_of) => {
+            for (idx, variant) in one_of.variants.iter().enumerate() {
+                write_variant(out, depth, variant, one_of.default == Some(idx));
+            }
+        }
+        QuestionKind::AnyOf(any_of) => {
+            for (idx, variant) in any_of.variants.iter().enumerate() {
+                write_variant(out, depth, variant, any_of.defaults.contains(&idx));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn write_variant(out: &mut String, depth: usize, variant: &Variant, is_default: bool) {
+    let mut head = format!("{}: {}", variant.name, kind_name(&variant.kind));
+    let constraints = constraints(&variant.kind);
+    if !constraints.is_empty() {
+        let _ = write!(head, " [{}]", constraints.join(", "));
+    }
+    if is_default {
+        head.push_str(" (default)");
+    }
+    line(out, depth, &head);
+    if let Some(key) = &variant.key {
+        line(out, depth + 1, &format!("key: {key}"));
+    }
+    if let Some(help) = &variant.help {
+        line(out, depth + 1, &format!("help: {help:?}"));
+    }
+    write_children(out, depth + 1, &variant.kind);
+}
+
+fn kind_name(kind: &QuestionKind) -> &'static str {
+    match kind {
+        QuestionKind::Unit => "Unit",
+        QuestionKind::Input(_) => "Input",
+        QuestionKind::Autocomplete(_) => "Autocomplete",
+        QuestionKind::Multiline(_) => "Multiline",
+        QuestionKind::Masked(_) => "Masked",
+        QuestionKind::Int(_) => "Int",
+        QuestionKind::Float(_) => "Float",
+        QuestionKind::Currency(_) => "Currency",
+        QuestionKind::Color(_) => "Color",
+        QuestionKind::Path(_) => "Path",
+        QuestionKind::Rating(_) => "Rating",
+        QuestionKind::Matrix(_) => "Matrix",
+        QuestionKind::Confirm(_) => "Confirm",
+        QuestionKind::Select(_) => "Select",
+        QuestionKind::List(_) => "List",
+        QuestionKind::AnyOf(_) => "AnyOf",
+        QuestionKind::AllOf(_) => "AllOf",
+        QuestionKind::OneOf(_) => "OneOf",
+    }
+}
+
+/// The settings of `kind` that restrict or pre-fill the answer, as `key=value` items.
+fn constraints(kind: &QuestionKind) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut push = |key: &str, value: Option<String>| {
+        if let Some(value) = value {
+            items.push(format!("{key}={value}"));
+        }
+    };
+    match kind {
+        QuestionKind::Unit | QuestionKind::AllOf(_) | QuestionKind::OneOf(_) => {}
+        QuestionKind::Input(q) => {
+            push("default", q.default.as_ref().map(|d| format!("{d:?}")));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Autocomplete(q) => {
+            push(
+                "source",
+                Some(match &q.source {
+                    SuggestionSource::Static(items) => format!("{items:?}"),
+                    SuggestionSource::Callback(_) => "fn".to_string(),
+                }),
+            );
+            push("default", q.default.as_ref().map(|d| format!("{d:?}")));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Multiline(q) => {
+            push("default", q.default.as_ref().map(|d| format!("{d:?}")));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Masked(q) => {
+            push("mask", q.mask.map(|m| format!("{m:?}")));
+            push("secret", q.secret.then(|| "true".to_string()));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Int(q) => {
+            push("min", q.min.map(|v| v.to_string()));
+            push("max", q.max.map(|v| v.to_string()));
+            push("default", q.default.map(|v| v.to_string()));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Float(q) => {
+            push("min", q.min.map(|v| v.to_string()));
+            push("max", q.max.map(|v| v.to_string()));
+            push("default", q.default.map(|v| v.to_string()));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Currency(q) => {
+            push("currency", Some(q.currency.clone()));
+            push("locale", Some(q.locale.clone()));
+            push("min", q.min.map(|v| v.to_string()));
+            push("max", q.max.map(|v| v.to_string()));
+            push("default", q.default.map(|v| v.to_string()));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Color(q) => {
+            push("default", q.default.map(|c| c.to_hex()));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Path(q) => {
+            push(
+                "mode",
+                Some(match &q.mode {
+                    PathMode::Any => "any".to_string(),
+                    PathMode::File { extensions } if extensions.is_empty() => "file".to_string(),
+                    PathMode::File { extensions } => format!("file({})", extensions.join("|")),
+                    PathMode::Directory => "directory".to_string(),
+                }),
+            );
+            push("default", q.default.as_ref().map(|d| format!("{d:?}")));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Rating(q) => {
+            push("min", Some(q.min.to_string()));
+            push("max", Some(q.max.to_string()));
+            push(
+                "anchors",
+                q.anchors
+                    .as_ref()
+                    .map(|(low, high)| format!("{low:?}..{high:?}")),
+            );
+            push("default", q.default.map(|v| v.to_string()));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Matrix(q) => {
+            push("rows", Some(format!("{:?}", q.rows)));
+            push("columns", Some(format!("{:?}", q.columns)));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::Confirm(q) => push("default", Some(q.default.to_string())),
+        QuestionKind::Select(q) => {
+            let options: Vec<String> = q
+                .options
+                .iter()
+                .map(|o| {
+                    if o.value == o.label {
+                        format!("{:?}", o.value)
+                    } else {
+                        format!("{:?}={:?}", o.value, o.label)
+                    }
+                })
+                .collect();
+            push("options", Some(format!("[{}]", options.join(", "))));
+            push("default", q.default.map(|v| v.to_string()));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::List(q) => {
+            let (element, min, max) = match &q.element_kind {
+                ListElementKind::String => ("String", None, None),
+                ListElementKind::Int { min, max } => (
+                    "Int",
+                    min.map(|v| v.to_string()),
+                    max.map(|v| v.to_string()),
+                ),
+                ListElementKind::Float { min, max } => (
+                    "Float",
+                    min.map(|v| v.to_string()),
+                    max.map(|v| v.to_string()),
+                ),
+            };
+            push("items", Some(element.to_string()));
+            push("min", min);
+            push("max", max);
+            push("min_items", q.min_items.map(|v| v.to_string()));
+            push("max_items", q.max_items.map(|v| v.to_string()));
+            push("validate", q.validate.clone());
+        }
+        QuestionKind::AnyOf(q) => {
+            push("max_selected", q.max_selected.map(|v| v.to_string()));
+        }
+    }
+    items
+}
+
+pub(crate) fn value_text(value: &ResponseValue) -> String {
+    match value {
+        ResponseValue::String(s) => format!("{s:?}"),
+        ResponseValue::Int(n) => n.to_string(),
+        ResponseValue::Float(f) => f.to_string(),
+        ResponseValue::Bool(b) => b.to_string(),
+        ResponseValue::ChosenVariant(idx) => format!("variant {idx}"),
+        ResponseValue::ChosenVariants(indices) => format!("variants {indices:?}"),
+        ResponseValue::StringList(items) => format!("{items:?}"),
+        ResponseValue::IntList(items) => format!("{items:?}"),
+        ResponseValue::FloatList(items) => format!("{items:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AllOfQuestion, InputQuestion, IntQuestion, OneOfQuestion, Question, QuestionKind,
+        SurveyDefinition, Variant,
+    };
+
+    #[test]
+    fn snapshot_lists_questions_in_order() {
+        let mut name = Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new()))
+            .with_help("As on your passport");
+        name.set_suggestion("Ada");
+        let definition = SurveyDefinition::new(vec![
+            name,
+            Question::new(
+                "age",
+                "Age?",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new(
+                "role",
+                "Role",
+                QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                    "",
+                    "",
+                    QuestionKind::OneOf(OneOfQuestion::with_default(
+                        vec![
+                            Variant::unit("Guest"),
+                            Variant::new(
+                                "Admin",
+                                QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                                    "level",
+                                    "Level?",
+                                    QuestionKind::Int(IntQuestion::new()),
+                                )])),
+                            ),
+                        ],
+                        0,
+                    )),
+                )])),
+            ),
+        ])
+        .with_prelude("Welcome");
+
+        assert_eq!(
+            definition.snapshot(),
+            "\
+prelude: \"Welcome\"
+name: Input \"Name?\"
+  suggested: \"Ada\"
+  help: \"As on your passport\"
+age: Int \"Age?\" [min=0, max=150]
+role: AllOf \"Role\"
+  OneOf
+    Guest: Unit (default)
+    Admin: AllOf
+      level: Int \"Level?\"
+"
+        );
+    }
+}
@@ -0,0 +1,834 @@
+//! Statistics over many runs of the same survey.
+//!
+//! [`SurveyStats::collect`] summarizes every question across a set of responses: how
+//! often each choice was picked, the range and mean of numbers, and the length of text
+//! answers. The summary renders as plain text or as an HTML report:
+//!
+//! ```rust,ignore
+//! let runs: Vec<Responses> = store.responses()?;
+//! let stats = SurveyStats::collect(&Feedback::survey(), &runs);
+//! println!("{}", stats.to_text());
+//! std::fs::write("report.html", stats.to_html())?;
+//! ```
+//!
+//! Masked answers are left out of the report.
+
+use crate::{
+    CurrencyQuestion, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyDefinition,
+};
+
+/// Range and mean of numeric answers.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NumberStats {
+    /// Number of values.
+    pub count: usize,
+    /// Smallest value, 0 without values.
+    pub min: f64,
+    /// Mean of the values, 0 without values.
+    pub mean: f64,
+    /// Largest value, 0 without values.
+    pub max: f64,
+}
+
+impl NumberStats {
+    fn from_values(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        Self {
+            count: values.len(),
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+}
+
+/// Word counts of text answers.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextStats {
+    /// Number of non-empty answers.
+    pub count: usize,
+    /// Words in all answers together.
+    pub total_words: usize,
+    /// Words in the shortest answer.
+    pub min_words: usize,
+    /// Mean words per answer.
+    pub mean_words: f64,
+    /// Words in the longest answer.
+    pub max_words: usize,
+}
+
+impl TextStats {
+    fn from_texts<'a>(texts: impl IntoIterator<Item = &'a str>) -> Self {
+        let words: Vec<usize> = texts
+            .into_iter()
+            .filter(|text| !text.trim().is_empty())
+            .map(|text| text.split_whitespace().count())
+            .collect();
+        if words.is_empty() {
+            return Self::default();
+        }
+        let total_words = words.iter().sum();
+        Self {
+            count: words.len(),
+            total_words,
+            min_words: words.iter().copied().min().unwrap_or(0),
+            mean_words: total_words as f64 / words.len() as f64,
+            max_words: words.iter().copied().max().unwrap_or(0),
+        }
+    }
+}
+
+/// Summary of the answers to one question.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnswerSummary {
+    /// How often each choice was picked: enums, multi-selects, selections and yes/no.
+    Counts(Vec<(String, usize)>),
+    /// How often each point of a rating scale was picked, and their mean.
+    Rating {
+        counts: Vec<(String, usize)>,
+        stats: NumberStats,
+    },
+    /// Integers, floats and the items of numeric lists.
+    Numbers(NumberStats),
+    /// Money amounts in minor units.
+    Amounts {
+        stats: NumberStats,
+        currency: CurrencyQuestion,
+    },
+    /// Text answers and the items of text lists.
+    Text(TextStats),
+    /// How often each column was picked, per row of a matrix.
+    Matrix {
+        columns: Vec<String>,
+        rows: Vec<(String, Vec<usize>)>,
+    },
+}
+
+/// Statistics of one question.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestionStats {
+    /// Path of the answer, for enums and multi-selects the path of the question.
+    pub path: ResponsePath,
+    /// The question's prompt.
+    pub prompt: String,
+    /// Number of runs that answered the question.
+    pub answered: usize,
+    /// Summary of the answers.
+    pub summary: AnswerSummary,
+}
+
+/// Statistics of every question of a survey over many runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurveyStats {
+    runs: usize,
+    questions: Vec<QuestionStats>,
+}
+
+impl SurveyStats {
+    /// Summarize the answers of `runs` to the questions of `definition`.
+    ///
+    /// The fields of enum variants count the runs that chose the variant.
+    pub fn collect<'a>(
+        definition: &SurveyDefinition,
+        runs: impl IntoIterator<Item = &'a Responses>,
+    ) -> Self {
+        let mut runs: Vec<Responses> = runs.into_iter().cloned().collect();
+        let mut questions = Vec::new();
+        for question in definition.questions() {
+            collect_question(
+                question,
+                &ResponsePath::empty(),
+                "",
+                &mut runs,
+                &mut questions,
+            );
+        }
+        Self {
+            runs: runs.len(),
+            questions,
+        }
+    }
+
+    /// Number of runs summarized.
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    /// Statistics of every question, in survey order.
+    pub fn questions(&self) -> &[QuestionStats] {
+        &self.questions
+    }
+
+    /// Statistics of the question at `path`.
+    pub fn get(&self, path: &ResponsePath) -> Option<&QuestionStats> {
+        self.questions
+            .iter()
+            .find(|question| &question.path == path)
+    }
+
+    /// Render the statistics as a plain-text report.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} responses\n", self.runs);
+        for question in &self.questions {
+            text.push_str(&format!(
+                "\n{} ({})\n  answered: {}\n",
+                question.prompt, question.path, question.answered
+            ));
+            if question.answered == 0 {
+                continue;
+            }
+            match &question.summary {
+                AnswerSummary::Counts(counts) => {
+                    text.push_str(&text_counts(counts, question.answered));
+                }
+                AnswerSummary::Rating { counts, stats } => {
+                    text.push_str(&text_counts(counts, question.answered));
+                    text.push_str(&format!("  mean: {:.2}\n", stats.mean));
+                }
+                AnswerSummary::Numbers(stats) => text.push_str(&format!(
+                    "  min: {}, mean: {:.2}, max: {}\n",
+                    stats.min, stats.mean, stats.max
+                )),
+                AnswerSummary::Amounts { stats, currency } => text.push_str(&format!(
+                    "  min: {}, mean: {}, max: {}\n",
+                    currency.format(stats.min as i64),
+                    currency.format(stats.mean.round() as i64),
+                    currency.format(stats.max as i64)
+                )),
+                AnswerSummary::Text(stats) => text.push_str(&format!(
+                    "  words: {} to {}, {:.1} on average, {} in total\n",
+                    stats.min_words, stats.max_words, stats.mean_words, stats.total_words
+                )),
+                AnswerSummary::Matrix { columns, rows } => {
+                    for (row, counts) in rows {
+                        let cells: Vec<String> = columns
+                            .iter()
+                            .zip(counts)
+                            .map(|(column, count)| format!("{column} {count}"))
+                            .collect();
+                        text.push_str(&format!("  {row}: {}\n", cells.join(", ")));
+                    }
+                }
+            }
+        }
+        text
+    }
+
+    /// Render the statistics as a standalone HTML page with one table per question.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Survey statistics</title>\n\
+             <style>\n\
+             body { font-family: sans-serif; max-width: 48rem; margin: 2rem auto; }\n\
+             table { border-collapse: collapse; margin-bottom: 1.5rem; }\n\
+             th, td { padding: 0.25rem 0.75rem; text-align: left; border-bottom: 1px solid #ddd; }\n\
+             .bar { display: inline-block; height: 0.75rem; background: #4a7bd0; }\n\
+             </style>\n</head>\n<body>\n",
+        );
+        html.push_str(&format!(
+            "<h1>Survey statistics</h1>\n<p>{} responses</p>\n",
+            self.runs
+        ));
+        for question in &self.questions {
+            html.push_str(&format!(
+                "<section>\n<h2>{}</h2>\n<p><code>{}</code>, answered by {}</p>\n",
+                escape_html(&question.prompt),
+                escape_html(question.path.as_str()),
+                question.answered
+            ));
+            if question.answered > 0 {
+                html.push_str(&html_summary(&question.summary, question.answered));
+            }
+            html.push_str("</section>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Share of `count` in `total` in percent.
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 * 100.0 / total as f64
+    }
+}
+
+fn text_counts(counts: &[(String, usize)], answered: usize) -> String {
+    let width = counts
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    counts
+        .iter()
+        .map(|(label, count)| {
+            format!(
+                "  {label:<width$}  {count:>4}  {:>5.1}%\n",
+                percent(*count, answered)
+            )
+        })
+        .collect()
+}
+
+fn html_summary(summary: &AnswerSummary, answered: usize) -> String {
+    let counts_table = |counts: &[(String, usize)]| {
+        let mut html = String::from("<table>\n");
+        for (label, count) in counts {
+            let share = percent(*count, answered);
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{count}</td><td>{share:.1}%</td>\
+                 <td><span class=\"bar\" style=\"width: {:.0}px\"></span></td></tr>\n",
+                escape_html(label),
+                share * 2.0
+            ));
+        }
+        html.push_str("</table>\n");
+        html
+    };
+    let row = |label: &str, value: String| {
+        format!(
+            "<tr><th>{label}</th><td>{}</td></tr>\n",
+            escape_html(&value)
+        )
+    };
+
+    match summary {
+        AnswerSummary::Counts(counts) => counts_table(counts),
+        AnswerSummary::Rating { counts, stats } => {
+            let mut html = counts_table(counts);
+            html.push_str(&format!("<p>Mean: {:.2}</p>\n", stats.mean));
+            html
+        }
+        AnswerSummary::Numbers(stats) => format!(
+            "<table>\n{}{}{}</table>\n",
+            row("Min", stats.min.to_string()),
+            row("Mean", format!("{:.2}", stats.mean)),
+            row("Max", stats.max.to_string())
+        ),
+        AnswerSummary::Amounts { stats, currency } => format!(
+            "<table>\n{}{}{}</table>\n",
+            row("Min", currency.format(stats.min as i64)),
+            row("Mean", currency.format(stats.mean.round() as i64)),
+            row("Max", currency.format(stats.max as i64))
+        ),
+        AnswerSummary::Text(stats) => format!(
+            "<table>\n{}{}{}{}</table>\n",
+            row("Fewest words", stats.min_words.to_string()),
+            row("Mean words", format!("{:.1}", stats.mean_words)),
+            row("Most words", stats.max_words.to_string()),
+            row("Total words", stats.total_words.to_string())
+        ),
+        AnswerSummary::Matrix { columns, rows } => {
+            let mut html = String::from("<table>\n<tr><th></th>");
+            for column in columns {
+                html.push_str(&format!("<th>{}</th>", escape_html(column)));
+            }
+            html.push_str("</tr>\n");
+            for (label, counts) in rows {
+                html.push_str(&format!("<tr><th>{}</th>", escape_html(label)));
+                for count in counts {
+                    html.push_str(&format!("<td>{count}</td>"));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n");
+            html
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Answers at `path` in all runs.
+fn answers<'a>(
+    runs: &'a [Responses],
+    path: &'a ResponsePath,
+) -> impl Iterator<Item = &'a ResponseValue> {
+    runs.iter().filter_map(move |responses| responses.get(path))
+}
+
+fn collect_question(
+    question: &Question,
+    prefix: &ResponsePath,
+    inherited_prompt: &str,
+    runs: &mut [Responses],
+    stats: &mut Vec<QuestionStats>,
+) {
+    let path = if question.path().is_empty() {
+        prefix.clone()
+    } else {
+        prefix.child(question.path().as_str())
+    };
+    // Enums are wrapped in a question without prompt of their own
+    let prompt = if question.ask().is_empty() {
+        inherited_prompt
+    } else {
+        question.ask()
+    };
+
+    let mut push = |answered: usize, summary: AnswerSummary| {
+        stats.push(QuestionStats {
+            path: path.clone(),
+            prompt: prompt.to_string(),
+            answered,
+            summary,
+        })
+    };
+
+    match question.kind() {
+        QuestionKind::Unit | QuestionKind::Masked(_) => {}
+        QuestionKind::Input(_)
+        | QuestionKind::Autocomplete(_)
+        | QuestionKind::Multiline(_)
+        | QuestionKind::Path(_) => {
+            let texts: Vec<&str> = answers(runs, &path)
+                .filter_map(ResponseValue::as_str)
+                .collect();
+            push(
+                texts.len(),
+                AnswerSummary::Text(TextStats::from_texts(texts)),
+            );
+        }
+        QuestionKind::Color(_) => {
+            let mut counts: Vec<(String, usize)> = Vec::new();
+            let colors: Vec<&str> = answers(runs, &path)
+                .filter_map(ResponseValue::as_str)
+                .collect();
+            for color in &colors {
+                match counts.iter_mut().find(|(value, _)| value == color) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((color.to_string(), 1)),
+                }
+            }
+            push(colors.len(), AnswerSummary::Counts(counts));
+        }
+        QuestionKind::Select(select) => {
+            let values: Vec<&str> = answers(runs, &path)
+                .filter_map(ResponseValue::as_str)
+                .collect();
+            let counts = select
+                .options
+                .iter()
+                .map(|option| {
+                    let count = values.iter().filter(|v| **v == option.value).count();
+                    (option.label.clone(), count)
+                })
+                .collect();
+            push(values.len(), AnswerSummary::Counts(counts));
+        }
+        QuestionKind::Confirm(_) => {
+            let values: Vec<bool> = answers(runs, &path)
+                .filter_map(ResponseValue::as_bool)
+                .collect();
+            let yes = values.iter().filter(|v| **v).count();
+            push(
+                values.len(),
+                AnswerSummary::Counts(vec![
+                    ("Yes".to_string(), yes),
+                    ("No".to_string(), values.len() - yes),
+                ]),
+            );
+        }
+        QuestionKind::Int(_) => {
+            let values: Vec<f64> = answers(runs, &path)
+                .filter_map(ResponseValue::as_int)
+                .map(|i| i as f64)
+                .collect();
+            push(
+                values.len(),
+                AnswerSummary::Numbers(NumberStats::from_values(&values)),
+            );
+        }
+        QuestionKind::Float(_) => {
+            let values: Vec<f64> = answers(runs, &path)
+                .filter_map(ResponseValue::as_float)
+                .collect();
+            push(
+                values.len(),
+                AnswerSummary::Numbers(NumberStats::from_values(&values)),
+            );
+        }
+        QuestionKind::Currency(currency) => {
+            let values: Vec<f64> = answers(runs, &path)
+                .filter_map(ResponseValue::as_int)
+                .map(|i| i as f64)
+                .collect();
+            push(
+                values.len(),
+                AnswerSummary::Amounts {
+                    stats: NumberStats::from_values(&values),
+                    currency: currency.clone(),
+                },
+            );
+        }
+        QuestionKind::Rating(rating) => {
+            let values: Vec<i64> = answers(runs, &path)
+                .filter_map(ResponseValue::as_int)
+                .collect();
+            let counts = (rating.min..=rating.max)
+                .map(|point| {
+                    let label = match &rating.anchors {
+                        Some((low, _)) if point == rating.min => format!("{point} ({low})"),
+                        Some((_, high)) if point == rating.max => format!("{point} ({high})"),
+                        _ => point.to_string(),
+                    };
+                    (label, values.iter().filter(|v| **v == point).count())
+                })
+                .collect();
+            let as_floats: Vec<f64> = values.iter().map(|v| *v as f64).collect();
+            push(
+                values.len(),
+                AnswerSummary::Rating {
+                    counts,
+                    stats: NumberStats::from_values(&as_floats),
+                },
+            );
+        }
+        QuestionKind::Matrix(matrix) => {
+            let choices: Vec<&[usize]> = answers(runs, &path)
+                .filter_map(ResponseValue::as_chosen_variants)
+                .collect();
+            let rows = matrix
+                .rows
+                .iter()
+                .enumerate()
+                .map(|(row, label)| {
+                    let counts = (0..matrix.columns.len())
+                        .map(|column| {
+                            choices
+                                .iter()
+                                .filter(|chosen| chosen.get(row) == Some(&column))
+                                .count()
+                        })
+                        .collect();
+                    (label.clone(), counts)
+                })
+                .collect();
+            push(
+                choices.len(),
+                AnswerSummary::Matrix {
+                    columns: matrix.columns.clone(),
+                    rows,
+                },
+            );
+        }
+        QuestionKind::List(_) => {
+            let mut answered = 0;
+            let mut numbers = Vec::new();
+            let mut texts = Vec::new();
+            for value in answers(runs, &path) {
+                answered += 1;
+                match value {
+                    ResponseValue::IntList(items) => {
+                        numbers.extend(items.iter().map(|i| *i as f64))
+                    }
+                    ResponseValue::FloatList(items) => numbers.extend(items),
+                    ResponseValue::StringList(items) => {
+                        texts.extend(items.iter().map(String::as_str))
+                    }
+                    _ => answered -= 1,
+                }
+            }
+            let summary = if texts.is_empty() && !numbers.is_empty() {
+                AnswerSummary::Numbers(NumberStats::from_values(&numbers))
+            } else {
+                AnswerSummary::Text(TextStats::from_texts(texts))
+            };
+            push(answered, summary);
+        }
+        QuestionKind::OneOf(one_of) => {
+            let chosen: Vec<usize> = answers(runs, &path.child(SELECTED_VARIANT_KEY))
+                .filter_map(ResponseValue::as_chosen_variant)
+                .collect();
+            let counts = one_of
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(idx, variant)| {
+                    (
+                        variant.name.clone(),
+                        chosen.iter().filter(|c| **c == idx).count(),
+                    )
+                })
+                .collect();
+            push(chosen.len(), AnswerSummary::Counts(counts));
+
+            for variant in &one_of.variants {
+                match &variant.kind {
+                    QuestionKind::Unit => {}
+                    QuestionKind::AllOf(all_of) => {
+                        for nested in all_of.questions() {
+                            collect_question(nested, &path, prompt, runs, stats);
+                        }
+                    }
+                    kind => {
+                        let nested = Question::new(variant.name.as_str(), "", kind.clone());
+                        collect_question(&nested, &path, &variant.name, runs, stats);
+                    }
+                }
+            }
+        }
+        QuestionKind::AnyOf(any_of) => {
+            let choices_path = path.child(SELECTED_VARIANTS_KEY);
+            let chosen: Vec<Vec<usize>> = answers(runs, &choices_path)
+                .filter_map(ResponseValue::as_chosen_variants)
+                .map(<[usize]>::to_vec)
+                .collect();
+            let counts = any_of
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(idx, variant)| {
+                    let count = chosen.iter().filter(|c| c.contains(&idx)).count();
+                    (variant.name.clone(), count)
+                })
+                .collect();
+            push(chosen.len(), AnswerSummary::Counts(counts));
+
+            // Fields of chosen items are stored by position, summarize them by variant
+            let names: Vec<&str> = any_of.variants.iter().map(|v| v.name.as_str()).collect();
+            for responses in runs.iter_mut() {
+                move_items_to_variants(responses, &path, &names);
+            }
+            for variant in &any_of.variants {
+                if let QuestionKind::AllOf(all_of) = &variant.kind {
+                    let variant_path = path.child(&variant.name);
+                    for nested in all_of.questions() {
+                        collect_question(nested, &variant_path, prompt, runs, stats);
+                    }
+                }
+            }
+        }
+        QuestionKind::AllOf(all_of) => {
+            for nested in all_of.questions() {
+                collect_question(nested, &path, prompt, runs, stats);
+            }
+        }
+    }
+}
+
+/// Rename `<path>.<item>.<field>` to `<path>.<variant>.<field>` for every chosen item.
+fn move_items_to_variants(responses: &mut Responses, path: &ResponsePath, names: &[&str]) {
+    let Some(chosen) = responses
+        .get(&path.child(SELECTED_VARIANTS_KEY))
+        .and_then(ResponseValue::as_chosen_variants)
+        .map(<[usize]>::to_vec)
+    else {
+        return;
+    };
+    for (item, idx) in chosen.into_iter().enumerate() {
+        let Some(name) = names.get(idx) else {
+            continue;
+        };
+        let item_path = path.child(&item.to_string());
+        let variant_path = path.child(name);
+        let fields: Vec<ResponsePath> = responses
+            .paths()
+            .filter(|p| p.strip_path_prefix(&item_path).is_some())
+            .cloned()
+            .collect();
+        for field_path in fields {
+            let Some(field) = field_path.strip_path_prefix(&item_path) else {
+                continue;
+            };
+            if field.as_str() == SELECTED_VARIANT_KEY {
+                continue;
+            }
+            responses.rename(&field_path, &variant_path.child(field.as_str()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AllOfQuestion, AnyOfQuestion, ConfirmQuestion, InputQuestion, IntQuestion, OneOfQuestion,
+        RatingQuestion, Variant,
+    };
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("age", "Age:", QuestionKind::Int(IntQuestion::new())),
+            Question::new(
+                "member",
+                "Member?",
+                QuestionKind::Confirm(ConfirmQuestion::new()),
+            ),
+            Question::new(
+                "mood",
+                "How was it?",
+                QuestionKind::Rating(RatingQuestion::likert("Bad", "Great", 3)),
+            ),
+            Question::new(
+                "remarks",
+                "Remarks:",
+                QuestionKind::Input(InputQuestion::new()),
+            ),
+            Question::new(
+                "plan",
+                "Plan:",
+                QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                    "",
+                    "",
+                    QuestionKind::OneOf(OneOfQuestion::new(vec![
+                        Variant::new("Free", QuestionKind::Unit),
+                        Variant::new(
+                            "Paid",
+                            QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                                "seats",
+                                "Seats:",
+                                QuestionKind::Int(IntQuestion::new()),
+                            )])),
+                        ),
+                    ])),
+                )])),
+            ),
+            Question::new(
+                "pets",
+                "Pets:",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![
+                    Variant::new("Cat", QuestionKind::Unit),
+                    Variant::new(
+                        "Dog",
+                        QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                            "name",
+                            "Dog's name:",
+                            QuestionKind::Input(InputQuestion::new()),
+                        )])),
+                    ),
+                ])),
+            ),
+        ])
+    }
+
+    fn run(age: i64, member: bool, mood: i64, remarks: &str, seats: Option<i64>) -> Responses {
+        let mut responses = Responses::new();
+        responses.insert("age", ResponseValue::Int(age));
+        responses.insert("member", ResponseValue::Bool(member));
+        responses.insert("mood", ResponseValue::Int(mood));
+        responses.insert("remarks", remarks);
+        match seats {
+            Some(seats) => {
+                responses.insert("plan.selected_variant", ResponseValue::ChosenVariant(1));
+                responses.insert("plan.seats", ResponseValue::Int(seats));
+                responses.insert(
+                    "pets.selected_variants",
+                    ResponseValue::ChosenVariants(vec![1]),
+                );
+                responses.insert("pets.0.selected_variant", ResponseValue::ChosenVariant(1));
+                responses.insert("pets.0.name", "Rex the dog");
+            }
+            None => {
+                responses.insert("plan.selected_variant", ResponseValue::ChosenVariant(0));
+                responses.insert(
+                    "pets.selected_variants",
+                    ResponseValue::ChosenVariants(vec![0]),
+                );
+                responses.insert("pets.0.selected_variant", ResponseValue::ChosenVariant(0));
+            }
+        }
+        responses
+    }
+
+    fn stats() -> SurveyStats {
+        let runs = [
+            run(20, true, 3, "Great fun", Some(4)),
+            run(30, false, 2, "Too long to read through", None),
+            run(40, true, 3, "", None),
+        ];
+        SurveyStats::collect(&definition(), &runs)
+    }
+
+    #[test]
+    fn questions_are_summarized_by_kind() {
+        let stats = stats();
+        assert_eq!(stats.runs(), 3);
+
+        let age = stats.get(&ResponsePath::new("age")).unwrap();
+        assert_eq!(
+            age.summary,
+            AnswerSummary::Numbers(NumberStats {
+                count: 3,
+                min: 20.0,
+                mean: 30.0,
+                max: 40.0
+            })
+        );
+
+        let member = stats.get(&ResponsePath::new("member")).unwrap();
+        assert_eq!(
+            member.summary,
+            AnswerSummary::Counts(vec![("Yes".to_string(), 2), ("No".to_string(), 1)])
+        );
+
+        let AnswerSummary::Rating {
+            counts,
+            stats: mood,
+        } = &stats.get(&ResponsePath::new("mood")).unwrap().summary
+        else {
+            panic!("rating expected");
+        };
+        assert_eq!(counts[0], ("1 (Bad)".to_string(), 0));
+        assert_eq!(counts[2], ("3 (Great)".to_string(), 2));
+        assert!((mood.mean - 8.0 / 3.0).abs() < 1e-9);
+
+        let AnswerSummary::Text(remarks) =
+            stats.get(&ResponsePath::new("remarks")).unwrap().summary
+        else {
+            panic!("text expected");
+        };
+        assert_eq!(
+            (remarks.count, remarks.min_words, remarks.max_words),
+            (2, 2, 5)
+        );
+    }
+
+    #[test]
+    fn variant_fields_count_the_runs_that_chose_them() {
+        let stats = stats();
+        let plan = stats.get(&ResponsePath::new("plan")).unwrap();
+        assert_eq!(plan.prompt, "Plan:");
+        assert_eq!(
+            plan.summary,
+            AnswerSummary::Counts(vec![("Free".to_string(), 2), ("Paid".to_string(), 1)])
+        );
+        assert_eq!(
+            stats
+                .get(&ResponsePath::new("plan.seats"))
+                .unwrap()
+                .answered,
+            1
+        );
+
+        let dog_name = stats.get(&ResponsePath::new("pets.Dog.name")).unwrap();
+        assert_eq!(dog_name.answered, 1);
+        assert!(matches!(
+            dog_name.summary,
+            AnswerSummary::Text(TextStats { total_words: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn reports_render_as_text_and_html() {
+        let stats = stats();
+        let text = stats.to_text();
+        assert!(text.starts_with("3 responses\n"));
+        assert!(text.contains("Member? (member)\n  answered: 3\n  Yes     2   66.7%\n"));
+        assert!(text.contains("  min: 20, mean: 30.00, max: 40\n"));
+
+        let html = stats.to_html();
+        assert!(html.contains("<h2>Dog's name:</h2>"));
+        assert!(html.contains("<tr><td>Paid</td><td>1</td><td>33.3%</td>"));
+    }
+}
@@ -1,11 +1,12 @@
-use crate::Question;
+use crate::{Question, QuestionKind, ResponsePath};
 
 /// The top-level structure containing all questions and metadata for a survey.
 ///
 /// A survey is a structured collection of questions. It's presentation-agnostic —
 /// it can be rendered as a sequential interview, a fill-in form, or used to
 /// generate documents.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SurveyDefinition {
     /// Optional message shown before the survey starts.
     pub prelude: Option<String>,
@@ -67,6 +68,93 @@ impl SurveyDefinition {
     pub fn len(&self) -> usize {
         self.questions.len()
     }
+
+    /// Paths of the questions tagged with `tag`, in question order.
+    ///
+    /// Answers at or below these paths belong to the category, so a tagged group is
+    /// listed once rather than per question.
+    pub fn tagged_paths(&self, tag: &str) -> Vec<ResponsePath> {
+        let mut paths = Vec::new();
+        collect_tagged(&self.questions, &ResponsePath::empty(), tag, &mut paths);
+        paths
+    }
+
+    /// Keep only the questions that answer `paths`, e.g. to change a single setting.
+    ///
+    /// A question is kept whole when it is at or below one of `paths`, and groups on the
+    /// way to a path keep just the questions leading there. A path inside an enum keeps
+    /// the whole enum question, since its fields depend on the chosen variant.
+    ///
+    /// Returns the paths of the kept questions: their answers are the ones the restricted
+    /// survey asks for again. Paths without a question are not covered by any of them.
+    pub fn restrict_to(&mut self, paths: &[ResponsePath]) -> Vec<ResponsePath> {
+        let mut kept = Vec::new();
+        restrict(
+            &mut self.questions,
+            &ResponsePath::empty(),
+            paths,
+            &mut kept,
+        );
+        kept
+    }
+}
+
+fn restrict(
+    questions: &mut Vec<Question>,
+    prefix: &ResponsePath,
+    paths: &[ResponsePath],
+    kept: &mut Vec<ResponsePath>,
+) {
+    questions.retain_mut(|question| {
+        let path = prefix.child(question.path().as_str());
+        if paths.iter().any(|wanted| path.is_within(wanted)) {
+            kept.push(path);
+            return true;
+        }
+        if !paths.iter().any(|wanted| wanted.is_within(&path)) {
+            return false;
+        }
+        match question.kind_mut() {
+            QuestionKind::AllOf(all_of) => {
+                restrict(all_of.questions_mut(), &path, paths, kept);
+                !all_of.questions().is_empty()
+            }
+            _ => {
+                kept.push(path);
+                true
+            }
+        }
+    });
+}
+
+fn collect_tagged(
+    questions: &[Question],
+    prefix: &ResponsePath,
+    tag: &str,
+    paths: &mut Vec<ResponsePath>,
+) {
+    for question in questions {
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+        if question.has_tag(tag) {
+            paths.push(path);
+            continue;
+        }
+        match question.kind() {
+            QuestionKind::AllOf(all_of) => collect_tagged(all_of.questions(), &path, tag, paths),
+            QuestionKind::OneOf(one_of) => {
+                for variant in &one_of.variants {
+                    if let QuestionKind::AllOf(all_of) = &variant.kind {
+                        collect_tagged(all_of.questions(), &path, tag, paths);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 impl Default for SurveyDefinition {
@@ -74,3 +162,95 @@ impl Default for SurveyDefinition {
         Self::empty()
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{
+        AutocompleteQuestion, DefaultValue, IntQuestion, OneOfQuestion, QuestionKind,
+        ResponseValue, Variant,
+    };
+
+    #[test]
+    fn restricting_keeps_the_questions_on_the_way() {
+        let int = || QuestionKind::Int(IntQuestion::new());
+        let mut definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(Default::default())),
+            Question::new(
+                "database",
+                "Database",
+                QuestionKind::AllOf(crate::AllOfQuestion::new(vec![
+                    Question::new("host", "Host?", QuestionKind::Input(Default::default())),
+                    Question::new("port", "Port?", int()),
+                ])),
+            ),
+        ]);
+
+        let kept = definition.restrict_to(&[ResponsePath::new("database.port")]);
+
+        assert_eq!(kept, vec![ResponsePath::new("database.port")]);
+        assert_eq!(definition.questions().len(), 1);
+        let QuestionKind::AllOf(database) = definition.questions()[0].kind() else {
+            panic!("database is a group");
+        };
+        assert_eq!(database.questions().len(), 1);
+        assert_eq!(database.questions()[0].path().as_str(), "port");
+
+        let mut empty = definition.clone();
+        assert!(
+            empty
+                .restrict_to(&[ResponsePath::new("missing")])
+                .is_empty()
+        );
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn definitions_round_trip_through_json() {
+        let mut age = Question::new("age", "Age?", QuestionKind::Int(IntQuestion::new()));
+        age.set_suggestion(ResponseValue::Int(30));
+        let definition = SurveyDefinition::new(vec![
+            age,
+            Question::new(
+                "city",
+                "City?",
+                QuestionKind::Autocomplete(AutocompleteQuestion::from_list(["Berlin", "Bern"])),
+            ),
+            Question::new(
+                "",
+                "Plan?",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Free"),
+                    Variant::unit("Pro"),
+                ])),
+            ),
+        ])
+        .with_prelude("Welcome");
+
+        let json = serde_json::to_string(&definition).unwrap();
+        let loaded: SurveyDefinition = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, definition);
+        assert_eq!(
+            loaded.questions[0].default(),
+            &DefaultValue::Suggested(ResponseValue::Int(30))
+        );
+    }
+
+    #[test]
+    fn optional_question_fields_may_be_left_out() {
+        let json = r#"{"questions": [{"path": "name", "ask": "Name?", "kind": {"Input": {"default": null, "validate": null}}}]}"#;
+        let loaded: SurveyDefinition = serde_json::from_str(json).unwrap();
+        assert_eq!(loaded.questions[0].ask(), "Name?");
+        assert!(loaded.prelude.is_none());
+    }
+
+    #[test]
+    fn autocomplete_callbacks_are_not_serialized() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "branch",
+            "Branch?",
+            QuestionKind::Autocomplete(AutocompleteQuestion::from_fn(|_| Vec::new())),
+        )]);
+        assert!(serde_json::to_string(&definition).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+use std::fmt;
+use std::ops::Range;
+
+/// How serious a text check finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextIssueSeverity {
+    /// A hint the user may want to act on (e.g., a likely misspelling).
+    Suggestion,
+
+    /// Something that probably needs attention before submitting.
+    Warning,
+}
+
+/// A single finding reported by a [`TextChecker`].
+///
+/// Issues are advisory: backends show them before the answer is accepted and let
+/// the user decide whether to revise the text. Hard requirements belong in
+/// `#[validate]` functions instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextIssue {
+    /// How serious this finding is.
+    pub severity: TextIssueSeverity,
+
+    /// Human-readable description of the problem.
+    pub message: String,
+
+    /// Byte range in the checked text that the issue refers to, if any.
+    pub span: Option<Range<usize>>,
+
+    /// Suggested replacements for the text in `span`.
+    pub replacements: Vec<String>,
+}
+
+impl TextIssue {
+    /// Create a suggestion-level issue.
+    pub fn suggestion(message: impl Into<String>) -> Self {
+        Self {
+            severity: TextIssueSeverity::Suggestion,
+            message: message.into(),
+            span: None,
+            replacements: Vec::new(),
+        }
+    }
+
+    /// Create a warning-level issue.
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: TextIssueSeverity::Warning,
+            message: message.into(),
+            span: None,
+            replacements: Vec::new(),
+        }
+    }
+
+    /// Set the byte range the issue refers to.
+    pub fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Set the suggested replacements.
+    pub fn with_replacements(mut self, replacements: Vec<String>) -> Self {
+        self.replacements = replacements;
+        self
+    }
+
+    /// Check if this is a warning.
+    pub fn is_warning(&self) -> bool {
+        self.severity == TextIssueSeverity::Warning
+    }
+}
+
+impl fmt::Display for TextIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if !self.replacements.is_empty() {
+            write!(f, " (did you mean: {}?)", self.replacements.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Hook for checking free-text answers (spelling, grammar, style).
+///
+/// Backends invoke the checker on multiline answers once the user has finished
+/// editing, and display the returned issues before the answer is accepted.
+///
+/// Any `Fn(&str) -> Vec<TextIssue>` closure implements this trait.
+pub trait TextChecker: Send + Sync {
+    /// Check the given text and return all findings (empty if the text looks fine).
+    fn check(&self, text: &str) -> Vec<TextIssue>;
+}
+
+impl<F> TextChecker for F
+where
+    F: Fn(&str) -> Vec<TextIssue> + Send + Sync,
+{
+    fn check(&self, text: &str) -> Vec<TextIssue> {
+        self(text)
+    }
+}
+
+impl fmt::Debug for dyn TextChecker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TextChecker")
+    }
+}
+
+#[cfg(feature = "spellcheck")]
+pub use dictionary::DictionaryChecker;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closure_checker() {
+        let checker = |text: &str| {
+            if text.contains("TODO") {
+                vec![TextIssue::warning("Unfinished text")]
+            } else {
+                vec![]
+            }
+        };
+        assert!(checker.check("Done.").is_empty());
+        assert!(checker.check("TODO: write this")[0].is_warning());
+    }
+
+    #[test]
+    fn display_with_replacements() {
+        let issue = TextIssue::suggestion("Unknown word \"teh\"")
+            .with_replacements(vec!["the".to_string(), "ten".to_string()]);
+        assert_eq!(
+            issue.to_string(),
+            "Unknown word \"teh\" (did you mean: the, ten?)"
+        );
+    }
+}
+
+#[cfg(feature = "spellcheck")]
+mod dictionary {
+    use std::collections::HashSet;
+
+    use super::{TextChecker, TextIssue};
+
+    /// A basic spell checker backed by a word list.
+    ///
+    /// Words not found in the dictionary are reported as suggestions, together with
+    /// dictionary words within a small edit distance. Immediately repeated words
+    /// ("the the") are reported as warnings.
+    ///
+    /// # Example
+    /// ```
+    /// use elicitor_types::{DictionaryChecker, TextChecker};
+    ///
+    /// let checker = DictionaryChecker::new(["hello", "world"]);
+    /// let issues = checker.check("helo world");
+    /// assert_eq!(issues.len(), 1);
+    /// assert_eq!(issues[0].replacements, vec!["hello".to_string()]);
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct DictionaryChecker {
+        words: HashSet<String>,
+        max_distance: usize,
+        max_replacements: usize,
+    }
+
+    impl DictionaryChecker {
+        /// Create a checker from a list of known words (case-insensitive).
+        pub fn new<I, S>(words: I) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: AsRef<str>,
+        {
+            Self {
+                words: words
+                    .into_iter()
+                    .map(|w| w.as_ref().trim().to_lowercase())
+                    .filter(|w| !w.is_empty())
+                    .collect(),
+                max_distance: 2,
+                max_replacements: 3,
+            }
+        }
+
+        /// Create a checker from a newline-separated word list (e.g., `/usr/share/dict/words`).
+        pub fn from_word_list(list: &str) -> Self {
+            Self::new(list.lines())
+        }
+
+        /// Load a newline-separated word list from a file.
+        pub fn from_file(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+            Ok(Self::from_word_list(&std::fs::read_to_string(path)?))
+        }
+
+        /// Add more known words (e.g., domain-specific terms).
+        pub fn with_words<I, S>(mut self, words: I) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: AsRef<str>,
+        {
+            self.words
+                .extend(words.into_iter().map(|w| w.as_ref().trim().to_lowercase()));
+            self
+        }
+
+        /// Set the maximum edit distance for replacement suggestions (default: 2).
+        pub fn with_max_distance(mut self, distance: usize) -> Self {
+            self.max_distance = distance;
+            self
+        }
+
+        /// Check if a word is in the dictionary.
+        pub fn contains(&self, word: &str) -> bool {
+            self.words.contains(&word.to_lowercase())
+        }
+
+        fn replacements(&self, word: &str) -> Vec<String> {
+            let word = word.to_lowercase();
+            let mut candidates: Vec<(usize, &String)> = self
+                .words
+                .iter()
+                .filter(|w| w.len().abs_diff(word.len()) <= self.max_distance)
+                .map(|w| (edit_distance(&word, w), w))
+                .filter(|(d, _)| *d <= self.max_distance)
+                .collect();
+            candidates.sort();
+            candidates
+                .into_iter()
+                .take(self.max_replacements)
+                .map(|(_, w)| w.clone())
+                .collect()
+        }
+    }
+
+    impl TextChecker for DictionaryChecker {
+        fn check(&self, text: &str) -> Vec<TextIssue> {
+            let mut issues = Vec::new();
+            let mut previous: Option<&str> = None;
+
+            for (start, word) in words(text) {
+                let span = start..start + word.len();
+
+                if previous.is_some_and(|p| p.eq_ignore_ascii_case(word)) {
+                    issues.push(
+                        TextIssue::warning(format!("Repeated word \"{word}\""))
+                            .with_span(span.clone()),
+                    );
+                }
+                previous = Some(word);
+
+                // Numbers and single letters are not spell-checked
+                if word.len() < 2 || word.chars().any(|c| c.is_numeric()) {
+                    continue;
+                }
+                if !self.contains(word) {
+                    issues.push(
+                        TextIssue::suggestion(format!("Unknown word \"{word}\""))
+                            .with_span(span)
+                            .with_replacements(self.replacements(word)),
+                    );
+                }
+            }
+
+            issues
+        }
+    }
+
+    /// Split text into words with their byte offsets. Apostrophes inside words are kept.
+    fn words(text: &str) -> Vec<(usize, &str)> {
+        let mut start = None;
+        let mut result = Vec::new();
+        for (i, c) in text.char_indices() {
+            let is_word_char = c.is_alphanumeric() || (c == '\'' && start.is_some());
+            match (is_word_char, start) {
+                (true, None) => start = Some(i),
+                (false, Some(s)) => {
+                    result.push((s, text[s..i].trim_end_matches('\'')));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            result.push((s, text[s..].trim_end_matches('\'')));
+        }
+        result
+    }
+
+    /// Levenshtein distance between two words.
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, ca) in a.chars().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for (j, cb) in b.iter().enumerate() {
+                let above = row[j + 1];
+                row[j + 1] = if ca == *cb {
+                    diagonal
+                } else {
+                    1 + diagonal.min(above).min(row[j])
+                };
+                diagonal = above;
+            }
+        }
+        row[b.len()]
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn known_words_pass() {
+            let checker = DictionaryChecker::new(["i", "like", "rust"]);
+            assert!(checker.check("I like Rust.").is_empty());
+        }
+
+        #[test]
+        fn unknown_word_with_replacements() {
+            let checker = DictionaryChecker::new(["experience", "with", "rust"]);
+            let issues = checker.check("experiance with rust");
+            assert_eq!(issues.len(), 1);
+            assert_eq!(issues[0].span, Some(0..10));
+            assert_eq!(issues[0].replacements, vec!["experience".to_string()]);
+        }
+
+        #[test]
+        fn repeated_word_warning() {
+            let checker = DictionaryChecker::new(["the", "cat"]);
+            let issues = checker.check("the the cat");
+            assert_eq!(issues.len(), 1);
+            assert!(issues[0].is_warning());
+        }
+
+        #[test]
+        fn distance() {
+            assert_eq!(edit_distance("kitten", "sitting"), 3);
+            assert_eq!(edit_distance("", "abc"), 3);
+            assert_eq!(edit_distance("same", "same"), 0);
+        }
+    }
+}
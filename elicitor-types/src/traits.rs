@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use crate::{ResponsePath, ResponseValue, Responses, SurveyDefinition};
+use crate::{
+    ResponsePath, ResponseValue, Responses, SELECTED_VARIANT_KEY, SurveyDefinition, SurveyObserver,
+    ValidationError,
+};
 
 /// Trait for types that can be collected via a survey.
 ///
@@ -16,6 +19,19 @@ pub trait Survey: Sized {
     /// guaranteeing they are consistent. If all questions are answered, reconstruction succeeds.
     fn from_responses(responses: &Responses) -> Self;
 
+    /// Turns an instance back into the responses it would be built from.
+    ///
+    /// The inverse of [`from_responses`](Self::from_responses): the result can prefill
+    /// forms and documents, serve as suggestions when editing the instance, or be compared
+    /// with other responses. `None` fields have no answer.
+    ///
+    /// Derived implementations fill in every field. The default implementation, for
+    /// types implementing the trait by hand, returns no answers, so editing such a
+    /// type starts from an empty survey.
+    fn to_responses(&self) -> Responses {
+        Responses::new()
+    }
+
     /// Validates a field's value.
     ///
     /// Called by backends during input collection to provide immediate feedback.
@@ -34,6 +50,20 @@ pub trait Survey: Sized {
         path: &ResponsePath,
     ) -> Result<(), String>;
 
+    /// Validates a field's value like [`validate_field`](Self::validate_field), keeping
+    /// the translation key of the error.
+    ///
+    /// Used by the builder to show the messages of validators in the language of its
+    /// translator. The default implementation wraps the message of `validate_field`,
+    /// which has no key.
+    fn validate_field_keyed(
+        value: &ResponseValue,
+        responses: &Responses,
+        path: &ResponsePath,
+    ) -> Result<(), ValidationError> {
+        Self::validate_field(value, responses, path).map_err(ValidationError::from)
+    }
+
     /// Validates the entire survey (composite validators, inter-field conditions).
     ///
     /// Called by form backends to validate all fields at once, typically on submit.
@@ -45,6 +75,47 @@ pub trait Survey: Sized {
     }
 }
 
+/// Gives access to the suggestion builder of a `#[derive(Survey)]` type.
+///
+/// Implemented by the derive macro so that nested fields can use the builder of
+/// their type even when it is defined in another module or crate. Not meant to be
+/// implemented or called by hand.
+#[doc(hidden)]
+pub trait SuggestFor {
+    /// The builder used in `suggest_*`/`assume_*` closures for this type.
+    type Builder;
+
+    /// Create a builder that records values under `prefix`, starting from `map`.
+    fn suggest_builder(map: HashMap<String, ResponseValue>, prefix: String) -> Self::Builder;
+
+    /// Take the recorded values back out of a builder.
+    fn into_suggestions(builder: Self::Builder) -> HashMap<String, ResponseValue>;
+}
+
+/// Check at compile time that a `#[validate]` function has the signature of a field
+/// validator, returning a `String` or a [`ValidationError`].
+///
+/// Used by the derive macro. Not meant to be called by hand.
+#[doc(hidden)]
+pub const fn check_field_validator<E: Into<ValidationError>>(
+    _: fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), E>,
+) {
+}
+
+/// Build the unit variant at `index` of an enum survey.
+///
+/// Used by the derive macro to turn matrix answers back into enum values. Not
+/// meant to be called by hand.
+#[doc(hidden)]
+pub fn variant_from_index<T: Survey>(index: usize) -> T {
+    let mut responses = Responses::new();
+    responses.insert(
+        ResponsePath::new(SELECTED_VARIANT_KEY),
+        ResponseValue::ChosenVariant(index),
+    );
+    T::from_responses(&responses)
+}
+
 /// Trait for backend implementations that collect survey responses.
 ///
 /// Backends receive a `SurveyDefinition` and return `Responses`.
@@ -73,4 +144,20 @@ pub trait SurveyBackend {
         definition: &SurveyDefinition,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<Responses, Self::Error>;
+
+    /// Collect responses, telling `observer` which question is shown.
+    ///
+    /// Backends that ask one question at a time override this to call
+    /// [`SurveyObserver::on_question_start`] before each question. Answers and validation
+    /// errors are reported by the caller through `validate`. The default implementation
+    /// reports no question starts.
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        let _ = observer;
+        self.collect(definition, validate)
+    }
 }
@@ -0,0 +1,712 @@
+//! Prompts and messages in the respondent's language.
+//!
+//! Fields and enum variants marked `#[ask(key = "profile.name")]` carry a translation key.
+//! A [`Translator`] resolves the keys when the survey is built, so one derive produces
+//! wizards and documents in several languages:
+//!
+//! ```rust,ignore
+//! #[derive(Survey)]
+//! struct Profile {
+//!     #[ask("Name:", key = "profile.name")]
+//!     name: String,
+//!
+//!     #[ask(key = "profile.age")]
+//!     #[validate(adult)]
+//!     age: u32,
+//! }
+//!
+//! fn adult(
+//!     value: &ResponseValue,
+//!     _: &Responses,
+//!     _: &ResponsePath,
+//! ) -> Result<(), ValidationError> {
+//!     // Looked up like the prompts, the English text is shown without a translation
+//!     match value {
+//!         ResponseValue::Int(age) if *age < 18 => Err(ValidationError::keyed(
+//!             "error.too-young",
+//!             "You must be at least 18.",
+//!         )),
+//!         _ => Ok(()),
+//!     }
+//! }
+//!
+//! let german = FluentTranslator::new("de", include_str!("de.ftl"))?;
+//!
+//! // Documents are generated from the translated definition
+//! let mut definition = Profile::survey();
+//! definition.translate(&german);
+//!
+//! let profile = Profile::builder().with_translator(german).run(backend)?;
+//! ```
+//!
+//! Keys without a translation keep the text given in `#[ask]`, or the field name.
+//! Validators returning a plain `String` show it as it is. The messages of the
+//! built-in checks, e.g. "Value must be at least 1", are looked up under the keys
+//! listed at [`Messages`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{Question, QuestionKind, SurveyDefinition};
+
+/// Looks up the text for translation keys.
+pub trait Translator: Send + Sync {
+    /// The text for `key`, `None` if there is no translation.
+    fn translate(&self, key: &str) -> Option<String>;
+
+    /// The text for `key` with its placeholders filled in from `args`.
+    ///
+    /// By default the placeholders of the translation are written `{name}`.
+    fn translate_with(&self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+        let mut text = self.translate(key)?;
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), value);
+        }
+        Some(text)
+    }
+}
+
+impl<F: Fn(&str) -> Option<String> + Send + Sync> Translator for F {
+    fn translate(&self, key: &str) -> Option<String> {
+        self(key)
+    }
+}
+
+/// A fixed table of translations.
+impl Translator for HashMap<String, String> {
+    fn translate(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+}
+
+impl SurveyDefinition {
+    /// Replace the prompts and variant names that have a translation key with their
+    /// translation, where `translator` has one.
+    ///
+    /// The messages of the built-in checks of number, amount and list questions are
+    /// replaced as well.
+    pub fn translate(&mut self, translator: &dyn Translator) {
+        let messages = Messages::translated(translator);
+        translate_questions(&mut self.questions, translator, &messages);
+    }
+
+    /// The translation keys of the prompts and variant names, each with its current
+    /// text, in the order they are asked. Useful as the source catalog for translators.
+    pub fn translation_keys(&self) -> Vec<(String, String)> {
+        let mut keys = Vec::new();
+        collect_questions(&self.questions, &mut keys);
+        keys
+    }
+}
+
+fn collect_questions(questions: &[Question], keys: &mut Vec<(String, String)>) {
+    for question in questions {
+        if let Some(key) = question.key() {
+            keys.push((key.to_string(), question.ask().to_string()));
+        }
+        collect_kind(question.kind(), keys);
+    }
+}
+
+fn collect_kind(kind: &QuestionKind, keys: &mut Vec<(String, String)>) {
+    let variants = match kind {
+        QuestionKind::AllOf(all_of) => return collect_questions(all_of.questions(), keys),
+        QuestionKind::OneOf(one_of) => &one_of.variants,
+        QuestionKind::AnyOf(any_of) => &any_of.variants,
+        _ => return,
+    };
+    for variant in variants {
+        if let Some(key) = &variant.key {
+            keys.push((key.clone(), variant.name.clone()));
+        }
+        collect_kind(&variant.kind, keys);
+    }
+}
+
+fn translate_questions(
+    questions: &mut [Question],
+    translator: &dyn Translator,
+    messages: &Messages,
+) {
+    for question in questions {
+        if let Some(text) = question.key().and_then(|key| translator.translate(key)) {
+            question.set_prompt(text);
+        }
+        translate_kind(question.kind_mut(), translator, messages);
+    }
+}
+
+fn translate_kind(kind: &mut QuestionKind, translator: &dyn Translator, messages: &Messages) {
+    let variants = match kind {
+        QuestionKind::AllOf(all_of) => {
+            translate_questions(all_of.questions_mut(), translator, messages);
+            return;
+        }
+        QuestionKind::Int(int_q) => return int_q.messages = messages.clone(),
+        QuestionKind::Float(float_q) => return float_q.messages = messages.clone(),
+        QuestionKind::Currency(currency_q) => return currency_q.messages = messages.clone(),
+        QuestionKind::List(list_q) => return list_q.messages = messages.clone(),
+        QuestionKind::OneOf(one_of) => &mut one_of.variants,
+        QuestionKind::AnyOf(any_of) => &mut any_of.variants,
+        _ => return,
+    };
+    for variant in variants {
+        if let Some(text) = variant
+            .key
+            .as_deref()
+            .and_then(|key| translator.translate(key))
+        {
+            variant.name = text;
+        }
+        translate_kind(&mut variant.kind, translator, messages);
+    }
+}
+
+/// The keys and English texts of the built-in messages.
+const BUILT_IN_MESSAGES: [(&str, &str); 19] = [
+    ("elicitor.at-least", "Value must be at least {min}"),
+    ("elicitor.at-most", "Value must be at most {max}"),
+    (
+        "elicitor.item-at-least",
+        "Value {item} must be at least {min}",
+    ),
+    (
+        "elicitor.item-at-most",
+        "Value {item} must be at most {max}",
+    ),
+    ("elicitor.amount-at-least", "Amount must be at least {min}"),
+    ("elicitor.amount-at-most", "Amount must be at most {max}"),
+    ("elicitor.invalid-integer", "Please enter a valid integer"),
+    (
+        "elicitor.invalid-integer-example",
+        "Please enter a valid integer, e.g. {example}",
+    ),
+    ("elicitor.invalid-number", "Please enter a valid number"),
+    (
+        "elicitor.invalid-number-example",
+        "Please enter a valid number, e.g. {example}",
+    ),
+    ("elicitor.item-not-integer", "{item} is not a valid integer"),
+    ("elicitor.item-not-number", "{item} is not a valid number"),
+    (
+        "elicitor.min-length",
+        "Please enter at least {min} characters",
+    ),
+    (
+        "elicitor.max-length",
+        "Please enter at most {max} characters",
+    ),
+    ("elicitor.no-match", "Please match the requested format"),
+    ("elicitor.select-option", "Please select an option"),
+    ("elicitor.pick-point", "Please pick a point of the scale"),
+    (
+        "elicitor.not-a-point",
+        "{point} is not a point of the rating scale",
+    ),
+    ("elicitor.yes-or-no", "Please answer yes or no"),
+];
+
+/// The messages of the built-in checks: bounds of numbers, amounts and list items,
+/// numbers that don't parse, the lengths, formats and selections checked in forms, and
+/// chat replies that don't fit the question.
+///
+/// Number, amount and list questions carry them, English unless
+/// [`SurveyDefinition::translate`] found translations. Checks without a question to
+/// carry them use [`Messages::translated`] directly. Each message is looked up under
+/// its key with its placeholders as arguments, e.g. in Fluent:
+///
+/// ```ftl
+/// elicitor-at-least = Der Wert muss mindestens { $min } sein
+/// ```
+///
+/// | Key | English |
+/// |-----|---------|
+/// | `elicitor.at-least` | Value must be at least {min} |
+/// | `elicitor.at-most` | Value must be at most {max} |
+/// | `elicitor.item-at-least` | Value {item} must be at least {min} |
+/// | `elicitor.item-at-most` | Value {item} must be at most {max} |
+/// | `elicitor.amount-at-least` | Amount must be at least {min} |
+/// | `elicitor.amount-at-most` | Amount must be at most {max} |
+/// | `elicitor.invalid-integer` | Please enter a valid integer |
+/// | `elicitor.invalid-integer-example` | Please enter a valid integer, e.g. {example} |
+/// | `elicitor.invalid-number` | Please enter a valid number |
+/// | `elicitor.invalid-number-example` | Please enter a valid number, e.g. {example} |
+/// | `elicitor.item-not-integer` | {item} is not a valid integer |
+/// | `elicitor.item-not-number` | {item} is not a valid number |
+/// | `elicitor.min-length` | Please enter at least {min} characters |
+/// | `elicitor.max-length` | Please enter at most {max} characters |
+/// | `elicitor.no-match` | Please match the requested format |
+/// | `elicitor.select-option` | Please select an option |
+/// | `elicitor.pick-point` | Please pick a point of the scale |
+/// | `elicitor.not-a-point` | {point} is not a point of the rating scale |
+/// | `elicitor.yes-or-no` | Please answer yes or no |
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Messages {
+    /// Translated texts by key, with `{name}` placeholders, shared by the questions.
+    translations: Option<Arc<HashMap<&'static str, String>>>,
+}
+
+impl Messages {
+    /// The messages `translator` has translations for, English for the others.
+    pub fn translated(translator: &dyn Translator) -> Self {
+        let translations = BUILT_IN_MESSAGES
+            .iter()
+            .filter_map(|&(key, english)| {
+                // Placeholders are passed through so they can be filled in later
+                let args = placeholders(english);
+                Some((key, translator.translate_with(key, &args)?))
+            })
+            .collect();
+        Self {
+            translations: Some(Arc::new(translations)),
+        }
+    }
+
+    /// "Value must be at least {min}".
+    pub fn at_least(&self, min: &str) -> String {
+        self.text("elicitor.at-least", &[("min", min)])
+    }
+
+    /// "Value must be at most {max}".
+    pub fn at_most(&self, max: &str) -> String {
+        self.text("elicitor.at-most", &[("max", max)])
+    }
+
+    /// "Value {item} must be at least {min}", for an item of a list.
+    pub fn item_at_least(&self, item: &str, min: &str) -> String {
+        self.text("elicitor.item-at-least", &[("item", item), ("min", min)])
+    }
+
+    /// "Value {item} must be at most {max}", for an item of a list.
+    pub fn item_at_most(&self, item: &str, max: &str) -> String {
+        self.text("elicitor.item-at-most", &[("item", item), ("max", max)])
+    }
+
+    /// "Amount must be at least {min}".
+    pub fn amount_at_least(&self, min: &str) -> String {
+        self.text("elicitor.amount-at-least", &[("min", min)])
+    }
+
+    /// "Amount must be at most {max}".
+    pub fn amount_at_most(&self, max: &str) -> String {
+        self.text("elicitor.amount-at-most", &[("max", max)])
+    }
+
+    /// "Please enter a valid integer", with an example in the answer's format if given.
+    pub fn invalid_integer(&self, example: Option<&str>) -> String {
+        match example {
+            Some(example) => self.text("elicitor.invalid-integer-example", &[("example", example)]),
+            None => self.text("elicitor.invalid-integer", &[]),
+        }
+    }
+
+    /// "Please enter a valid number", with an example in the answer's format if given.
+    pub fn invalid_number(&self, example: Option<&str>) -> String {
+        match example {
+            Some(example) => self.text("elicitor.invalid-number-example", &[("example", example)]),
+            None => self.text("elicitor.invalid-number", &[]),
+        }
+    }
+
+    /// "{item} is not a valid integer", for an item of a list.
+    pub fn item_not_integer(&self, item: &str) -> String {
+        self.text("elicitor.item-not-integer", &[("item", item)])
+    }
+
+    /// "{item} is not a valid number", for an item of a list.
+    pub fn item_not_number(&self, item: &str) -> String {
+        self.text("elicitor.item-not-number", &[("item", item)])
+    }
+
+    /// "Please enter at least {min} characters".
+    pub fn min_length(&self, min: &str) -> String {
+        self.text("elicitor.min-length", &[("min", min)])
+    }
+
+    /// "Please enter at most {max} characters".
+    pub fn max_length(&self, max: &str) -> String {
+        self.text("elicitor.max-length", &[("max", max)])
+    }
+
+    /// "Please match the requested format", for an answer that doesn't match a pattern.
+    pub fn no_match(&self) -> String {
+        self.text("elicitor.no-match", &[])
+    }
+
+    /// "Please select an option".
+    pub fn select_option(&self) -> String {
+        self.text("elicitor.select-option", &[])
+    }
+
+    /// "Please pick a point of the scale", for a rating that is not a number.
+    pub fn pick_point(&self) -> String {
+        self.text("elicitor.pick-point", &[])
+    }
+
+    /// "{point} is not a point of the rating scale".
+    pub fn not_a_point(&self, point: &str) -> String {
+        self.text("elicitor.not-a-point", &[("point", point)])
+    }
+
+    /// "Please answer yes or no".
+    pub fn yes_or_no(&self) -> String {
+        self.text("elicitor.yes-or-no", &[])
+    }
+
+    fn text(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let template = self
+            .translations
+            .as_ref()
+            .and_then(|t| t.get(key))
+            .map_or_else(
+                || {
+                    BUILT_IN_MESSAGES
+                        .iter()
+                        .find(|(built_in, _)| *built_in == key)
+                        .map_or(key, |(_, english)| english)
+                },
+                String::as_str,
+            );
+        let mut text = template.to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{name}}}"), value);
+        }
+        text
+    }
+}
+
+/// The names of the `{name}` placeholders of `template`, each with its braced text.
+fn placeholders(template: &str) -> Vec<(&str, &str)> {
+    let mut found = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        found.push((&rest[start + 1..end], &rest[start..=end]));
+        rest = &rest[end + 1..];
+    }
+    found
+}
+
+/// Error of a field validator, with the key of its translation.
+///
+/// Validators return `Result<(), String>` for a message that is shown as it is, or
+/// `Result<(), ValidationError>` for one the builder's translator looks up under `key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Key of the translated message, `None` for a message shown as it is.
+    pub key: Option<String>,
+    /// Message shown without a key or a translation for it.
+    pub fallback: String,
+}
+
+impl ValidationError {
+    /// A message shown as it is.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            key: None,
+            fallback: message.into(),
+        }
+    }
+
+    /// A message translated under `key`, `fallback` where there is no translation.
+    pub fn keyed(key: impl Into<String>, fallback: impl Into<String>) -> Self {
+        Self {
+            key: Some(key.into()),
+            fallback: fallback.into(),
+        }
+    }
+
+    /// The message in the language of `translator`.
+    pub fn message(self, translator: Option<&dyn Translator>) -> String {
+        self.key
+            .zip(translator)
+            .and_then(|(key, translator)| translator.translate(&key))
+            .unwrap_or(self.fallback)
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.fallback)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<String> for ValidationError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for ValidationError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<ValidationError> for String {
+    fn from(error: ValidationError) -> Self {
+        error.fallback
+    }
+}
+
+/// Error type for [`FluentTranslator`].
+#[cfg(feature = "fluent")]
+#[derive(Debug, thiserror::Error)]
+pub enum FluentError {
+    /// The locale is not a valid language identifier.
+    #[error("Invalid locale '{0}'")]
+    Locale(String),
+
+    /// The Fluent source does not parse.
+    #[error("Invalid Fluent source: {0}")]
+    Syntax(String),
+
+    /// The source defines a message twice.
+    #[error("Conflicting Fluent messages: {0}")]
+    Conflict(String),
+}
+
+/// Translator reading [Fluent](https://projectfluent.org) resources.
+///
+/// A key is looked up as the message of the same name with dots replaced by dashes,
+/// then as an attribute: `profile.name` is the message `profile-name` or the `.name`
+/// attribute of the message `profile`. A message that does not format, e.g. because it
+/// refers to a missing message, counts as untranslated.
+///
+/// ```ftl
+/// profile-name = Name:
+/// profile =
+///     .age = Age:
+/// error-too-young = You must be at least 18.
+/// ```
+#[cfg(feature = "fluent")]
+pub struct FluentTranslator {
+    bundle: fluent_bundle::concurrent::FluentBundle<fluent_bundle::FluentResource>,
+}
+
+#[cfg(feature = "fluent")]
+impl std::fmt::Debug for FluentTranslator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FluentTranslator")
+            .field("locales", &self.bundle.locales)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "fluent")]
+impl FluentTranslator {
+    /// Create a translator for `locale`, e.g. `de-CH`, from the Fluent `source`.
+    pub fn new(locale: &str, source: &str) -> Result<Self, FluentError> {
+        let locale: unic_langid::LanguageIdentifier = locale
+            .parse()
+            .map_err(|_| FluentError::Locale(locale.to_string()))?;
+        let resource = fluent_bundle::FluentResource::try_new(source.to_string())
+            .map_err(|(_, errors)| FluentError::Syntax(join(&errors)))?;
+
+        let mut bundle = fluent_bundle::concurrent::FluentBundle::new_concurrent(vec![locale]);
+        // Prompts are shown as they are, without bidi isolation marks around arguments
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .map_err(|errors| FluentError::Conflict(join(&errors)))?;
+        Ok(Self { bundle })
+    }
+}
+
+#[cfg(feature = "fluent")]
+impl Translator for FluentTranslator {
+    fn translate(&self, key: &str) -> Option<String> {
+        self.translate_with(key, &[])
+    }
+
+    /// Fills in the Fluent variables of the message, e.g. `{ $min }`.
+    fn translate_with(&self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+        let pattern = match self.bundle.get_message(&key.replace('.', "-")) {
+            Some(message) => message.value()?,
+            None => {
+                let (id, attribute) = key.rsplit_once('.')?;
+                let message = self.bundle.get_message(&id.replace('.', "-"))?;
+                message.get_attribute(attribute)?.value()
+            }
+        };
+        let args: fluent_bundle::FluentArgs = args.iter().copied().collect();
+        let mut errors = Vec::new();
+        let text = self
+            .bundle
+            .format_pattern(pattern, Some(&args), &mut errors);
+        // A message that refers to missing messages or variables is left untranslated
+        errors.is_empty().then(|| text.into_owned())
+    }
+}
+
+#[cfg(feature = "fluent")]
+fn join(errors: &[impl std::fmt::Display]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatQuestion, InputQuestion, IntQuestion, ListQuestion, OneOfQuestion, Variant};
+
+    #[test]
+    fn keyed_prompts_and_variants_are_translated() {
+        let mut definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name:", QuestionKind::Input(InputQuestion::new()))
+                .with_key("profile.name"),
+            Question::new("city", "City:", QuestionKind::Input(InputQuestion::new()))
+                .with_key("profile.city"),
+            Question::new(
+                "pet",
+                "Pet:",
+                QuestionKind::OneOf(OneOfQuestion::new(vec![
+                    Variant::unit("Cat").with_key("pet.cat"),
+                    Variant::unit("Dog"),
+                ])),
+            ),
+        ]);
+        let german: HashMap<String, String> = [("profile.name", "Vorname:"), ("pet.cat", "Katze")]
+            .into_iter()
+            .map(|(key, text)| (key.to_string(), text.to_string()))
+            .collect();
+
+        assert_eq!(
+            definition.translation_keys(),
+            [
+                ("profile.name".to_string(), "Name:".to_string()),
+                ("profile.city".to_string(), "City:".to_string()),
+                ("pet.cat".to_string(), "Cat".to_string()),
+            ]
+        );
+
+        definition.translate(&german);
+        assert_eq!(definition.questions[0].ask(), "Vorname:");
+        // Untranslated keys keep their text
+        assert_eq!(definition.questions[1].ask(), "City:");
+        let QuestionKind::OneOf(pet) = definition.questions[2].kind() else {
+            panic!("expected a choice");
+        };
+        assert_eq!(pet.variants[0].name, "Katze");
+        assert_eq!(pet.variants[1].name, "Dog");
+    }
+
+    #[test]
+    fn built_in_messages_are_translated_with_their_placeholders() {
+        let mut definition = SurveyDefinition::new(vec![
+            Question::new(
+                "age",
+                "Age:",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(18), None).with_locale("de")),
+            ),
+            Question::new(
+                "scores",
+                "Scores:",
+                QuestionKind::List(ListQuestion::ints_with_bounds(None, Some(10))),
+            ),
+            Question::new("ratio", "Ratio:", QuestionKind::Float(FloatQuestion::new())),
+        ]);
+        let german: HashMap<String, String> = [
+            ("elicitor.at-least", "Mindestens {min}"),
+            ("elicitor.item-at-most", "{item} ist größer als {max}"),
+            (
+                "elicitor.invalid-integer-example",
+                "Bitte eine ganze Zahl eingeben, z.B. {example}",
+            ),
+        ]
+        .into_iter()
+        .map(|(key, text)| (key.to_string(), text.to_string()))
+        .collect();
+        definition.translate(&german);
+
+        let QuestionKind::Int(age) = definition.questions[0].kind() else {
+            panic!("expected an int question");
+        };
+        assert_eq!(age.check_bounds(1200), Ok(()));
+        assert_eq!(age.check_bounds(3), Err("Mindestens 18".to_string()));
+        assert_eq!(
+            age.parse("3.5"),
+            Err("Bitte eine ganze Zahl eingeben, z.B. 1.234".to_string())
+        );
+        let QuestionKind::List(scores) = definition.questions[1].kind() else {
+            panic!("expected a list question");
+        };
+        assert_eq!(
+            scores.messages.item_at_most("40", "10"),
+            "40 ist größer als 10"
+        );
+        // Messages without a translation stay English
+        let QuestionKind::Float(ratio) = definition.questions[2].kind() else {
+            panic!("expected a float question");
+        };
+        assert_eq!(
+            ratio.parse("half"),
+            Err("Please enter a valid number".to_string())
+        );
+    }
+
+    #[test]
+    fn validation_errors_are_translated_by_key_only() {
+        let german: HashMap<String, String> =
+            [("error.too-young", "Zu jung."), ("Too old.", "Zu alt.")]
+                .into_iter()
+                .map(|(key, text)| (key.to_string(), text.to_string()))
+                .collect();
+        let translator: Option<&dyn Translator> = Some(&german);
+
+        let keyed = ValidationError::keyed("error.too-young", "Too young.");
+        assert_eq!(keyed.clone().message(translator), "Zu jung.");
+        assert_eq!(keyed.message(None), "Too young.");
+        // A plain message is never taken for a key
+        assert_eq!(
+            ValidationError::from("Too old.").message(translator),
+            "Too old."
+        );
+        // A missing translation shows the fallback, not the key
+        let missing = ValidationError::keyed("error.too-tall", "Too tall.");
+        assert_eq!(missing.message(translator), "Too tall.");
+    }
+
+    #[cfg(feature = "fluent")]
+    #[test]
+    fn fluent_messages_and_attributes_are_looked_up() {
+        let translator = FluentTranslator::new(
+            "de",
+            "profile-name = Vorname:\nprofile =\n    .age = Alter:\n",
+        )
+        .unwrap();
+        assert_eq!(
+            translator.translate("profile.name").as_deref(),
+            Some("Vorname:")
+        );
+        assert_eq!(
+            translator.translate("profile.age").as_deref(),
+            Some("Alter:")
+        );
+        assert_eq!(translator.translate("profile.city"), None);
+        assert!(FluentTranslator::new("de", "profile-name = {").is_err());
+
+        let broken = FluentTranslator::new("de", "profile-name = { missing-message }:\n").unwrap();
+        assert_eq!(broken.translate("profile.name"), None);
+    }
+
+    #[cfg(feature = "fluent")]
+    #[test]
+    fn fluent_variables_fill_the_placeholders_of_built_in_messages() {
+        let translator = FluentTranslator::new(
+            "de",
+            "elicitor-at-most = Der Wert darf höchstens { $max } sein\n",
+        )
+        .unwrap();
+        let messages = Messages::translated(&translator);
+        assert_eq!(messages.at_most("10"), "Der Wert darf höchstens 10 sein");
+        assert_eq!(messages.at_least("1"), "Value must be at least 1");
+    }
+}
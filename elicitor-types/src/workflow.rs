@@ -0,0 +1,309 @@
+//! Review and approval of collected responses.
+//!
+//! A [`Submission`] wraps the result of a survey run together with its responses and
+//! moves through [`SubmissionState`]s: a draft is submitted, and a reviewer approves or
+//! rejects it, leaving comments on individual answers. A rejected submission can be
+//! revised and submitted again:
+//!
+//! ```rust,ignore
+//! let (form, responses) = Intake::builder().run_with_responses(backend)?;
+//! let mut submission = Submission::draft(form, responses);
+//! submission.submit()?;
+//!
+//! submission.add_review_comment("budget", "Needs sign-off above 10k");
+//! submission.reject()?;
+//!
+//! // Persist alongside the answers, load again later
+//! let stored = submission.to_responses();
+//! let submission = Submission::<Intake>::load(stored)?;
+//! ```
+//!
+//! The state and review comments are kept in the response metadata under
+//! `workflow.state` and `workflow.review.<path>`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ResponsePath, Responses, Survey};
+
+/// Metadata key prefix for the review workflow.
+pub const WORKFLOW_METADATA_PREFIX: &str = "workflow";
+
+/// Where a submission is in the review workflow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SubmissionState {
+    /// Being filled in or revised, not yet handed in.
+    #[default]
+    Draft,
+    /// Handed in and waiting for review.
+    Submitted,
+    /// Accepted by a reviewer. Final.
+    Approved,
+    /// Sent back by a reviewer, can be revised and submitted again.
+    Rejected,
+}
+
+impl SubmissionState {
+    /// Name of the state as stored in the response metadata.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Submitted => "submitted",
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+impl fmt::Display for SubmissionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SubmissionState {
+    type Err = WorkflowError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(Self::Draft),
+            "submitted" => Ok(Self::Submitted),
+            "approved" => Ok(Self::Approved),
+            "rejected" => Ok(Self::Rejected),
+            _ => Err(WorkflowError::UnknownState(s.to_string())),
+        }
+    }
+}
+
+/// Error type for review workflow operations.
+#[derive(Debug, thiserror::Error)]
+pub enum WorkflowError {
+    #[error("Cannot {action} a submission that is {state}")]
+    InvalidTransition {
+        action: &'static str,
+        state: SubmissionState,
+    },
+
+    #[error("Responses carry no workflow state")]
+    MissingState,
+
+    #[error("Unknown workflow state: {0}")]
+    UnknownState(String),
+}
+
+/// A survey result under review.
+#[derive(Debug, Clone)]
+pub struct Submission<T> {
+    value: T,
+    responses: Responses,
+    state: SubmissionState,
+    review_comments: HashMap<ResponsePath, String>,
+}
+
+impl<T> Submission<T> {
+    /// Start a draft from the result of a survey run.
+    pub fn draft(value: T, responses: Responses) -> Self {
+        Self {
+            value,
+            responses,
+            state: SubmissionState::Draft,
+            review_comments: HashMap::new(),
+        }
+    }
+
+    /// The current state.
+    pub fn state(&self) -> SubmissionState {
+        self.state
+    }
+
+    /// The submitted value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// The responses the value was built from.
+    pub fn responses(&self) -> &Responses {
+        &self.responses
+    }
+
+    /// Consume the submission and return the value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Hand in a draft for review.
+    pub fn submit(&mut self) -> Result<(), WorkflowError> {
+        self.transition("submit", SubmissionState::Draft, SubmissionState::Submitted)
+    }
+
+    /// Accept a submitted value.
+    pub fn approve(&mut self) -> Result<(), WorkflowError> {
+        self.transition(
+            "approve",
+            SubmissionState::Submitted,
+            SubmissionState::Approved,
+        )
+    }
+
+    /// Send a submitted value back to the respondent.
+    pub fn reject(&mut self) -> Result<(), WorkflowError> {
+        self.transition(
+            "reject",
+            SubmissionState::Submitted,
+            SubmissionState::Rejected,
+        )
+    }
+
+    /// Replace the answers of a rejected submission or draft, making it a draft again.
+    ///
+    /// Review comments are kept so the respondent can see what was asked for.
+    pub fn revise(&mut self, value: T, responses: Responses) -> Result<(), WorkflowError> {
+        if !matches!(
+            self.state,
+            SubmissionState::Draft | SubmissionState::Rejected
+        ) {
+            return Err(WorkflowError::InvalidTransition {
+                action: "revise",
+                state: self.state,
+            });
+        }
+        self.value = value;
+        self.responses = responses;
+        self.state = SubmissionState::Draft;
+        Ok(())
+    }
+
+    /// Leave a reviewer comment on the answer at `path`, replacing an earlier one.
+    pub fn add_review_comment(
+        &mut self,
+        path: impl Into<ResponsePath>,
+        comment: impl Into<String>,
+    ) {
+        self.review_comments.insert(path.into(), comment.into());
+    }
+
+    /// Remove the reviewer comment on the answer at `path`.
+    pub fn resolve_review_comment(&mut self, path: &ResponsePath) -> Option<String> {
+        self.review_comments.remove(path)
+    }
+
+    /// The reviewer comment on the answer at `path`, if any.
+    pub fn review_comment(&self, path: &ResponsePath) -> Option<&str> {
+        self.review_comments.get(path).map(String::as_str)
+    }
+
+    /// All reviewer comments, keyed by the path of the answer they belong to.
+    pub fn review_comments(&self) -> impl Iterator<Item = (&ResponsePath, &str)> {
+        self.review_comments
+            .iter()
+            .map(|(path, comment)| (path, comment.as_str()))
+    }
+
+    /// The responses with the workflow state and review comments attached as metadata.
+    pub fn to_responses(&self) -> Responses {
+        let mut responses = self.responses.clone();
+        responses.insert_metadata(state_key(), self.state.as_str());
+        for (path, comment) in &self.review_comments {
+            responses.insert_metadata(review_key(path), comment.clone());
+        }
+        responses
+    }
+
+    fn transition(
+        &mut self,
+        action: &'static str,
+        from: SubmissionState,
+        to: SubmissionState,
+    ) -> Result<(), WorkflowError> {
+        if self.state != from {
+            return Err(WorkflowError::InvalidTransition {
+                action,
+                state: self.state,
+            });
+        }
+        self.state = to;
+        Ok(())
+    }
+}
+
+impl<T: Survey> Submission<T> {
+    /// Restore a submission from responses produced by [`to_responses`](Self::to_responses).
+    pub fn load(mut responses: Responses) -> Result<Self, WorkflowError> {
+        let state = responses
+            .remove_metadata(&state_key())
+            .ok_or(WorkflowError::MissingState)?
+            .parse()?;
+
+        let review_prefix = format!("{WORKFLOW_METADATA_PREFIX}.review.");
+        let review_keys = responses
+            .metadata_iter()
+            .filter(|(key, _)| key.starts_with(&review_prefix))
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<_>>();
+        let mut review_comments = HashMap::new();
+        for key in review_keys {
+            if let Some(comment) = responses.remove_metadata(&key) {
+                review_comments.insert(ResponsePath::new(&key[review_prefix.len()..]), comment);
+            }
+        }
+
+        Ok(Self {
+            value: T::from_responses(&responses),
+            responses,
+            state,
+            review_comments,
+        })
+    }
+}
+
+fn state_key() -> String {
+    format!("{WORKFLOW_METADATA_PREFIX}.state")
+}
+
+fn review_key(path: &ResponsePath) -> String {
+    format!("{WORKFLOW_METADATA_PREFIX}.review.{}", path.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transitions_follow_the_review_cycle() {
+        let mut submission = Submission::draft("v1", Responses::new());
+        assert!(submission.approve().is_err());
+
+        submission.submit().unwrap();
+        assert!(submission.revise("v2", Responses::new()).is_err());
+        submission.reject().unwrap();
+        assert_eq!(submission.state(), SubmissionState::Rejected);
+
+        submission.revise("v2", Responses::new()).unwrap();
+        assert_eq!(submission.state(), SubmissionState::Draft);
+        submission.submit().unwrap();
+        submission.approve().unwrap();
+        assert_eq!(*submission.value(), "v2");
+
+        let err = submission.reject().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot reject a submission that is approved"
+        );
+    }
+
+    #[test]
+    fn state_and_comments_are_stored_as_metadata() {
+        let mut submission = Submission::draft((), Responses::new());
+        submission.submit().unwrap();
+        submission.add_review_comment("budget", "Needs sign-off");
+
+        let responses = submission.to_responses();
+        assert_eq!(responses.metadata("workflow.state"), Some("submitted"));
+        assert_eq!(
+            responses.metadata("workflow.review.budget"),
+            Some("Needs sign-off")
+        );
+        assert!(responses.is_empty());
+    }
+}
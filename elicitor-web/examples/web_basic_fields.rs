@@ -0,0 +1,35 @@
+//! Basic field types in the browser
+//!
+//! Build for the web and generate the JavaScript bindings:
+//!
+//! ```text
+//! cargo build -p elicitor-web --example web_basic_fields --target wasm32-unknown-unknown
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/debug/examples/web_basic_fields.wasm
+//! ```
+//!
+//! Then serve a page with `<canvas id="survey"></canvas>` that imports
+//! `pkg/web_basic_fields.js` and calls its default export.
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use elicitor_web::WebForm;
+    use example_surveys::BasicFields;
+
+    wasm_bindgen_futures::spawn_local(async {
+        WebForm::for_survey::<BasicFields>()
+            .with_title("Basic Fields")
+            .with_submit(|responses| {
+                web_sys::console::log_1(
+                    &elicitor_web::answers_to_json(responses).to_string().into(),
+                );
+            })
+            .start("survey")
+            .await
+            .expect("the form starts");
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    eprintln!("Build this example for wasm32-unknown-unknown, see the top of the file.");
+}
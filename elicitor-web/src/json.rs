@@ -0,0 +1,101 @@
+//! Answers as the JSON posted back to the server.
+
+use elicitor::{REDACTED, ResponseValue, Responses};
+use serde_json::{Map, Value, json};
+
+/// The answers as a JSON object keyed by response path.
+///
+/// Text is a string, numbers are numbers, yes/no is a boolean, lists are arrays and
+/// choices are the indices of the chosen variants, e.g.
+/// `{"name": "Ada", "age": 36, "role": 1, "role.1.team": "Compilers"}`.
+///
+/// Redacted answers, e.g. to masked questions, are written as [`REDACTED`]; use
+/// [`answers_to_json_with_secrets`] to send them.
+pub fn answers_to_json(responses: &Responses) -> Value {
+    let answers: Map<String, Value> = responses
+        .iter()
+        .map(|(path, value)| {
+            let value = if responses.is_redacted(path) {
+                json!(REDACTED)
+            } else {
+                to_json(value)
+            };
+            (path.as_str().to_string(), value)
+        })
+        .collect();
+    Value::Object(answers)
+}
+
+/// The answers as a JSON object keyed by response path, like [`answers_to_json`]
+/// but with the plain text of redacted answers.
+pub fn answers_to_json_with_secrets(responses: &Responses) -> Value {
+    let answers: Map<String, Value> = responses
+        .iter_exposed()
+        .map(|(path, value)| (path.as_str().to_string(), to_json(value)))
+        .collect();
+    Value::Object(answers)
+}
+
+fn to_json(value: &ResponseValue) -> Value {
+    match value {
+        ResponseValue::String(s) => json!(s),
+        ResponseValue::Int(n) => json!(n),
+        ResponseValue::Float(f) => json!(f),
+        ResponseValue::Bool(b) => json!(b),
+        ResponseValue::ChosenVariant(idx) => json!(idx),
+        ResponseValue::ChosenVariants(indices) => json!(indices),
+        ResponseValue::StringList(items) => json!(items),
+        ResponseValue::IntList(items) => json!(items),
+        ResponseValue::FloatList(items) => json!(items),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elicitor::{ResponsePath, Survey, TestBackend};
+
+    #[test]
+    fn answers_are_keyed_by_path() {
+        let mut responses = Responses::new();
+        responses.insert(
+            ResponsePath::new("name"),
+            ResponseValue::String("Ada".into()),
+        );
+        responses.insert(ResponsePath::new("age"), ResponseValue::Int(36));
+        responses.insert(
+            ResponsePath::new("tags"),
+            ResponseValue::StringList(vec!["admin".into(), "ops".into()]),
+        );
+
+        assert_eq!(
+            answers_to_json(&responses).to_string(),
+            r#"{"name":"Ada","age":36,"tags":["admin","ops"]}"#
+        );
+    }
+
+    #[derive(Survey)]
+    struct Signup {
+        #[ask("User:")]
+        user: String,
+        #[ask("Password:")]
+        #[mask]
+        password: String,
+    }
+
+    #[test]
+    fn masked_answers_are_not_sent() {
+        let backend = TestBackend::new()
+            .with_string("user", "ada")
+            .with_string("password", "hunter2");
+        let (signup, responses) = Signup::builder().run_with_responses(backend).unwrap();
+        assert_eq!(signup.password, "hunter2");
+
+        let json = answers_to_json(&responses).to_string();
+        assert!(!json.contains("hunter2"), "{json}");
+        assert_eq!(json, r#"{"user":"ada","password":"***"}"#);
+
+        let json = answers_to_json_with_secrets(&responses).to_string();
+        assert_eq!(json, r#"{"user":"ada","password":"hunter2"}"#);
+    }
+}
@@ -0,0 +1,40 @@
+//! # elicitor-web
+//!
+//! Run elicitor surveys in the browser.
+//!
+//! Built for `wasm32-unknown-unknown`, [`WebForm`] shows the egui form of
+//! `elicitor-form-egui` on a canvas of the page. The same derived struct is filled in
+//! as on the desktop, and once submitted the answers are posted back as JSON.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! use elicitor_web::WebForm;
+//!
+//! fn main() {
+//!     wasm_bindgen_futures::spawn_local(async {
+//!         WebForm::for_survey::<Registration>()
+//!             .with_title("Registration")
+//!             .with_post_url("/api/registrations")
+//!             .start("survey-canvas")
+//!             .await
+//!             .expect("the form starts");
+//!     });
+//! }
+//! ```
+//!
+//! The server receives the object made by [`answers_to_json`], keyed by response
+//! path. Masked answers such as passwords are sent as `"***"` unless the form is
+//! built with `WebForm::with_secrets_exposed`. File and directory questions cannot be answered in the browser, which has
+//! no file system to check them against.
+//!
+//! On other targets, the crate only offers [`answers_to_json`] and
+//! [`answers_to_json_with_secrets`].
+
+mod json;
+#[cfg(target_arch = "wasm32")]
+mod runner;
+
+pub use json::{answers_to_json, answers_to_json_with_secrets};
+#[cfg(target_arch = "wasm32")]
+pub use runner::{WebError, WebForm};
@@ -0,0 +1,248 @@
+//! The egui form on a canvas of the page.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use eframe::egui;
+use elicitor::{FieldHandling, Responses, SensitiveField, SensitiveSink, Survey};
+use elicitor_form_egui::{FormPanel, FormStatus};
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::json::{answers_to_json, answers_to_json_with_secrets};
+
+type SubmitHandler = Box<dyn FnOnce(&Responses)>;
+
+/// Error type for starting a form in the browser.
+#[derive(Debug, Error)]
+pub enum WebError {
+    /// The page has no canvas with the given ID.
+    #[error("No canvas with id {0:?} on the page")]
+    MissingCanvas(String),
+
+    /// eframe could not start on the canvas, e.g. without WebGL.
+    #[error("Starting the form failed: {0}")]
+    Start(String),
+}
+
+/// A survey form filled in on a canvas of a web page.
+///
+/// Once the form is submitted, the answers go to the submit handler and, as JSON
+/// made by [`answers_to_json`], in a `POST` request to the post URL. Masked answers
+/// are posted as `"***"` unless the form is built with
+/// [`with_secrets_exposed`](Self::with_secrets_exposed).
+///
+/// ```rust,ignore
+/// wasm_bindgen_futures::spawn_local(async {
+///     WebForm::for_survey::<Registration>()
+///         .with_title("Registration")
+///         .with_post_url("/api/registrations")
+///         .start("survey-canvas")
+///         .await
+///         .expect("the form starts");
+/// });
+/// ```
+pub struct WebForm {
+    panel: FormPanel,
+    title: Option<String>,
+    post_url: Option<String>,
+    secrets_exposed: bool,
+    on_submit: Option<SubmitHandler>,
+}
+
+impl WebForm {
+    /// Show `panel`, e.g. one made with [`FormPanel::new`] for a survey definition
+    /// fetched at runtime.
+    pub fn new(panel: FormPanel) -> Self {
+        Self {
+            panel,
+            title: None,
+            post_url: None,
+            secrets_exposed: false,
+            on_submit: None,
+        }
+    }
+
+    /// A form for a `#[derive(Survey)]` type, checking answers with its validators.
+    pub fn for_survey<T: Survey + 'static>() -> Self {
+        Self::new(FormPanel::for_survey::<T>())
+    }
+
+    /// Show `title` above the form.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Post the answers as JSON to `url` once the form is submitted.
+    pub fn with_post_url(mut self, url: impl Into<String>) -> Self {
+        self.post_url = Some(url.into());
+        self
+    }
+
+    /// Post the plain text of masked answers such as passwords instead of `"***"`,
+    /// for servers that need them, e.g. to create an account.
+    pub fn with_secrets_exposed(mut self) -> Self {
+        self.secrets_exposed = true;
+        self
+    }
+
+    /// Call `handler` with the answers once the form is submitted.
+    pub fn with_submit(mut self, handler: impl FnOnce(&Responses) + 'static) -> Self {
+        self.on_submit = Some(Box::new(handler));
+        self
+    }
+
+    /// Run the form on the canvas with the ID `canvas_id`.
+    pub async fn start(self, canvas_id: &str) -> Result<(), WebError> {
+        let canvas = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id(canvas_id))
+            .and_then(|element| element.dyn_into::<web_sys::HtmlCanvasElement>().ok())
+            .ok_or_else(|| WebError::MissingCanvas(canvas_id.to_string()))?;
+
+        eframe::WebRunner::new()
+            .start(
+                canvas,
+                eframe::WebOptions::default(),
+                Box::new(move |_| Ok(Box::new(WebApp::new(self)))),
+            )
+            .await
+            .map_err(|e| WebError::Start(js_error(e)))
+    }
+}
+
+impl SensitiveSink for WebForm {
+    fn sink_name(&self) -> String {
+        match &self.post_url {
+            Some(url) => format!("web form post to {url}"),
+            None => "web form".to_string(),
+        }
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        if self.secrets_exposed {
+            FieldHandling::Plaintext
+        } else {
+            FieldHandling::Redacted
+        }
+    }
+}
+
+/// Where the posted answers are.
+enum Delivery {
+    /// Nothing is posted.
+    None,
+    Sending,
+    Sent,
+    Failed(String),
+}
+
+struct WebApp {
+    form: WebForm,
+    status: FormStatus,
+    delivery: Rc<RefCell<Delivery>>,
+}
+
+impl WebApp {
+    fn new(form: WebForm) -> Self {
+        Self {
+            form,
+            status: FormStatus::Running,
+            delivery: Rc::new(RefCell::new(Delivery::None)),
+        }
+    }
+
+    fn submit(&mut self, ctx: &egui::Context) {
+        let responses = self.form.panel.responses();
+        if let Some(on_submit) = self.form.on_submit.take() {
+            on_submit(&responses);
+        }
+        if let Some(url) = self.form.post_url.clone() {
+            *self.delivery.borrow_mut() = Delivery::Sending;
+            let body = if self.form.secrets_exposed {
+                answers_to_json_with_secrets(&responses)
+            } else {
+                answers_to_json(&responses)
+            }
+            .to_string();
+            let delivery = Rc::clone(&self.delivery);
+            let ctx = ctx.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                *delivery.borrow_mut() = match post(&url, &body).await {
+                    Ok(()) => Delivery::Sent,
+                    Err(message) => Delivery::Failed(message),
+                };
+                ctx.request_repaint();
+            });
+        }
+    }
+}
+
+impl eframe::App for WebApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            if let Some(title) = &self.form.title {
+                ui.heading(title);
+                ui.separator();
+            }
+            match self.status {
+                FormStatus::Running => {
+                    self.status = self.form.panel.ui(ui);
+                    if self.status == FormStatus::Completed {
+                        self.submit(ctx);
+                    }
+                }
+                FormStatus::Completed => match &*self.delivery.borrow() {
+                    Delivery::None | Delivery::Sent => {
+                        ui.label("Thank you, your answers have been submitted.");
+                    }
+                    Delivery::Sending => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Sending your answers…");
+                        });
+                    }
+                    Delivery::Failed(message) => {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            format!("Your answers could not be sent: {message}"),
+                        );
+                    }
+                },
+                FormStatus::Cancelled => {
+                    ui.label("The form was cancelled.");
+                }
+            }
+        });
+    }
+}
+
+/// Post `body` as JSON to `url`.
+async fn post(url: &str, body: &str) -> Result<(), String> {
+    let init = web_sys::RequestInit::new();
+    init.set_method("POST");
+    init.set_body(&JsValue::from_str(body));
+    let request = web_sys::Request::new_with_str_and_init(url, &init).map_err(js_error)?;
+    request
+        .headers()
+        .set("Content-Type", "application/json")
+        .map_err(js_error)?;
+
+    let window = web_sys::window().ok_or("the page has no window")?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(js_error)?;
+    if response.ok() {
+        Ok(())
+    } else {
+        Err(format!("the server answered {}", response.status()))
+    }
+}
+
+fn js_error(error: JsValue) -> String {
+    error.as_string().unwrap_or_else(|| format!("{error:?}"))
+}
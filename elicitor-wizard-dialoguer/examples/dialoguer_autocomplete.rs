@@ -0,0 +1,11 @@
+//! Autocomplete example. Run with: cargo run --example dialoguer_autocomplete
+
+use elicitor_wizard_dialoguer::DialoguerBackend;
+use example_surveys::EditorSetup;
+
+fn main() -> anyhow::Result<()> {
+    let backend = DialoguerBackend::new();
+    let result = EditorSetup::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! Currency question example
+//!
+//! Demonstrates:
+//! - #[currency("USD")] on integer fields holding cents
+//! - The parsed amount is echoed with separators and symbol
+//!
+//! Run with: cargo run --example dialoguer_currency
+
+use elicitor_wizard_dialoguer::DialoguerBackend;
+use example_surveys::MortgageApplication;
+
+fn main() -> anyhow::Result<()> {
+    let backend = DialoguerBackend::new();
+    let result = MortgageApplication::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
@@ -0,0 +1,11 @@
+//! Runtime choices example. Run with: cargo run --example dialoguer_runtime_choices
+
+use elicitor_wizard_dialoguer::DialoguerBackend;
+use example_surveys::FilePicker;
+
+fn main() -> anyhow::Result<()> {
+    let backend = DialoguerBackend::new();
+    let result = FilePicker::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
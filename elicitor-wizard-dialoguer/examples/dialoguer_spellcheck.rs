@@ -0,0 +1,25 @@
+//! Spell-checking multiline answers. Run with: cargo run --example dialoguer_spellcheck
+
+use elicitor::{DictionaryChecker, TextChecker, TextIssue};
+use elicitor_wizard_dialoguer::DialoguerBackend;
+use example_surveys::BlogPost;
+
+const WORDS: &str = "a\nabout\nand\nblog\nby\nfor\nhello\nin\nis\nit\nmy\nof\non\npost\nrust\nthe\nthis\nto\nwith\nworld\nwrite\nwritten";
+
+fn main() -> anyhow::Result<()> {
+    let dictionary = DictionaryChecker::from_word_list(WORDS);
+
+    // Combine the dictionary with a custom style rule
+    let checker = move |text: &str| {
+        let mut issues = dictionary.check(text);
+        if text.contains("TODO") {
+            issues.push(TextIssue::warning("Text still contains a TODO marker"));
+        }
+        issues
+    };
+
+    let backend = DialoguerBackend::new().with_text_checker(checker);
+    let result = BlogPost::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
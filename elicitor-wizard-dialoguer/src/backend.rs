@@ -1,18 +1,24 @@
 //! Dialoguer backend implementation for SurveyBackend trait.
 
-use dialoguer::{Confirm, Editor, Input, MultiSelect, Password, Select, theme::ColorfulTheme};
+use dialoguer::{
+    Confirm, Editor, FuzzySelect, Input, MultiSelect, Password, Select, theme::ColorfulTheme,
+};
 use elicitor::{
-    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition,
+    COMMENT_KEY, DefaultValue, ListElementKind, Messages, NoObserver, Question, QuestionKind,
+    ResponsePath, ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY,
+    SurveyBackend, SurveyDefinition, SurveyError, SurveyObserver, TextChecker, TextIssueSeverity,
 };
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Error type for the Dialoguer backend.
 #[derive(Debug, Error)]
 pub enum DialoguerError {
     /// User cancelled the survey (e.g., pressed Ctrl+C or Escape).
+    ///
+    /// Holds the answers given before cancelling.
     #[error("Survey cancelled by user")]
-    Cancelled,
+    Cancelled { partial: Box<Responses> },
 
     /// An I/O error occurred during prompting.
     #[error("Dialoguer error: {0}")]
@@ -23,11 +29,90 @@ pub enum DialoguerError {
     ValidationError(String),
 }
 
+impl DialoguerError {
+    /// The answers given before the user cancelled, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            _ => None,
+        }
+    }
+
+    /// A cancellation whose partial answers are filled in by `collect`.
+    fn cancelled() -> Self {
+        Self::Cancelled {
+            partial: Box::default(),
+        }
+    }
+}
+
+impl From<DialoguerError> for SurveyError {
+    fn from(err: DialoguerError) -> Self {
+        match err {
+            DialoguerError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
+        }
+    }
+}
+
 /// Helper to check if a dialoguer error is a cancellation (Ctrl+C / Escape)
 fn is_cancelled(err: &dialoguer::Error) -> bool {
     matches!(err, dialoguer::Error::IO(io_err) if io_err.kind() == std::io::ErrorKind::Interrupted)
 }
 
+/// The choice of an enum field, which the derive wraps in a section of its own.
+fn enum_choice(all_of: &elicitor::AllOfQuestion) -> Option<&elicitor::OneOfQuestion> {
+    match all_of.questions() {
+        [choice] if choice.path().is_empty() && choice.ask().is_empty() => match choice.kind() {
+            QuestionKind::OneOf(one_of) => Some(one_of),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Check a list item against the bounds of the list's elements.
+fn check_bounds<T: PartialOrd + std::fmt::Display>(
+    messages: &Messages,
+    value: T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<(), String> {
+    if let Some(min) = min
+        && value < min
+    {
+        return Err(messages.at_least(&min.to_string()));
+    }
+    if let Some(max) = max
+        && value > max
+    {
+        return Err(messages.at_most(&max.to_string()));
+    }
+    Ok(())
+}
+
+/// Read typed lines up to the first empty one, for when no editor can be started.
+fn read_until_blank(input: impl std::io::BufRead) -> std::io::Result<String> {
+    let mut lines = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Tab completion backed by an autocomplete question's suggestion source.
+struct Completer<'a>(&'a elicitor::AutocompleteQuestion);
+
+impl dialoguer::Completion for Completer<'_> {
+    fn get(&self, input: &str) -> Option<String> {
+        self.0.completions(input).into_iter().next()
+    }
+}
+
 /// Dialoguer backend for interactive CLI prompts.
 ///
 /// This backend uses the `dialoguer` library to present questions
@@ -36,17 +121,130 @@ fn is_cancelled(err: &dialoguer::Error) -> bool {
 pub struct DialoguerBackend {
     /// Use colorful theme for prompts.
     colorful: bool,
+
+    /// Optional checker run on multiline answers before they are accepted.
+    text_checker: Option<Arc<dyn TextChecker>>,
+
+    /// Pick single choices by typing to filter them.
+    fuzzy_select: bool,
 }
 
 impl DialoguerBackend {
     /// Create a new Dialoguer backend with default (colorful) theme.
     pub fn new() -> Self {
-        Self { colorful: true }
+        Self {
+            colorful: true,
+            text_checker: None,
+            fuzzy_select: false,
+        }
     }
 
     /// Create a backend with plain (no color) theme.
     pub fn plain() -> Self {
-        Self { colorful: false }
+        Self {
+            colorful: false,
+            text_checker: None,
+            fuzzy_select: false,
+        }
+    }
+
+    /// Check multiline answers with the given checker before accepting them.
+    ///
+    /// Findings are printed after the editor closes and the user can choose to
+    /// revise the text or submit it as-is.
+    pub fn with_text_checker(mut self, checker: impl TextChecker + 'static) -> Self {
+        self.text_checker = Some(Arc::new(checker));
+        self
+    }
+
+    /// Pick enum variants and runtime choices with a fuzzy search: typing
+    /// narrows the list to the matching entries.
+    pub fn with_fuzzy_select(mut self) -> Self {
+        self.fuzzy_select = true;
+        self
+    }
+
+    /// Let the user pick one of `items`, with a fuzzy search if enabled.
+    fn choose(
+        &self,
+        prompt: &str,
+        items: &[&str],
+        default: Option<usize>,
+    ) -> Result<usize, DialoguerError> {
+        let _theme;
+        let result = if self.fuzzy_select {
+            let mut builder: FuzzySelect;
+            if self.colorful {
+                _theme = ColorfulTheme::default();
+                builder = FuzzySelect::with_theme(&_theme);
+            } else {
+                builder = FuzzySelect::new();
+            }
+            builder = builder.with_prompt(prompt).items(items);
+            if let Some(idx) = default {
+                builder = builder.default(idx);
+            }
+            builder.interact()
+        } else {
+            let mut builder: Select;
+            if self.colorful {
+                _theme = ColorfulTheme::default();
+                builder = Select::with_theme(&_theme);
+            } else {
+                builder = Select::new();
+            }
+            builder = builder.with_prompt(prompt).items(items);
+            if let Some(idx) = default {
+                builder = builder.default(idx);
+            }
+            builder.interact()
+        };
+
+        match result {
+            Ok(idx) => Ok(idx),
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
+    }
+
+    /// Run the text checker (if any) and ask whether to keep the text.
+    ///
+    /// Returns `true` if the answer should be accepted.
+    fn review_text(&self, text: &str) -> Result<bool, DialoguerError> {
+        let Some(checker) = &self.text_checker else {
+            return Ok(true);
+        };
+        let issues = checker.check(text);
+        if issues.is_empty() {
+            return Ok(true);
+        }
+
+        for issue in &issues {
+            let label = match issue.severity {
+                TextIssueSeverity::Suggestion => "Suggestion",
+                TextIssueSeverity::Warning => "Warning",
+            };
+            println!("  {label}: {issue}");
+        }
+
+        let mut builder: Confirm;
+        let _theme;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Confirm::with_theme(&_theme);
+        } else {
+            builder = Confirm::new();
+        }
+
+        // Only default to submitting when there is nothing more serious than suggestions
+        let default = !issues.iter().any(|issue| issue.is_warning());
+        builder = builder.with_prompt("Submit anyway?").default(default);
+
+        match builder.interact() {
+            Ok(keep) => Ok(keep),
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
     }
 
     /// Ask a single question and store the response.
@@ -55,6 +253,7 @@ impl DialoguerBackend {
         question: &Question,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
         path_prefix: Option<&ResponsePath>,
     ) -> Result<(), DialoguerError> {
         let path = match path_prefix {
@@ -66,7 +265,7 @@ impl DialoguerBackend {
         let prompt = if question.ask().is_empty() {
             path.as_str()
                 .split('.')
-                .last()
+                .next_back()
                 .unwrap_or("")
                 .split('_')
                 .map(|word| {
@@ -88,7 +287,11 @@ impl DialoguerBackend {
             return Ok(());
         }
 
-        match question.kind() {
+        if !matches!(question.kind(), QuestionKind::Unit | QuestionKind::AllOf(_)) {
+            observer.on_question_start(&path);
+        }
+
+        let answered = match question.kind() {
             QuestionKind::Unit => Ok(()),
 
             QuestionKind::Input(input_q) => self.ask_input(
@@ -100,9 +303,23 @@ impl DialoguerBackend {
                 validate,
             ),
 
-            QuestionKind::Multiline(_multiline_q) => {
-                self.ask_multiline(&path, &prompt, question.default(), responses, validate)
-            }
+            QuestionKind::Autocomplete(autocomplete_q) => self.ask_autocomplete(
+                &path,
+                &prompt,
+                autocomplete_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Multiline(multiline_q) => self.ask_multiline(
+                &path,
+                &prompt,
+                multiline_q,
+                question.default(),
+                responses,
+                validate,
+            ),
 
             QuestionKind::Masked(masked_q) => self.ask_masked(
                 &path,
@@ -122,6 +339,51 @@ impl DialoguerBackend {
                 validate,
             ),
 
+            QuestionKind::Currency(currency_q) => self.ask_currency(
+                &path,
+                &prompt,
+                currency_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Color(color_q) => self.ask_color(
+                &path,
+                &prompt,
+                color_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Path(path_q) => self.ask_path(
+                &path,
+                &prompt,
+                path_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Rating(rating_q) => self.ask_rating(
+                &path,
+                &prompt,
+                rating_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Matrix(matrix_q) => self.ask_matrix(
+                &path,
+                &prompt,
+                matrix_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
             QuestionKind::Float(float_q) => self.ask_float(
                 &path,
                 &prompt,
@@ -131,166 +393,582 @@ impl DialoguerBackend {
                 validate,
             ),
 
-            QuestionKind::Confirm(confirm_q) => {
-                self.ask_confirm(&path, &prompt, confirm_q, question.default(), responses)
+            QuestionKind::Confirm(confirm_q) => self.ask_confirm(
+                &path,
+                &prompt,
+                confirm_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Select(select_q) => self.ask_select(
+                &path,
+                &prompt,
+                select_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::List(list_q) => self.ask_list(
+                &path,
+                &prompt,
+                list_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::OneOf(one_of) => {
+                self.ask_one_of(&path, &prompt, one_of, responses, validate, observer)
+            }
+
+            QuestionKind::AnyOf(any_of) => {
+                self.ask_any_of(&path, &prompt, any_of, responses, validate, observer)
+            }
+
+            QuestionKind::AllOf(all_of) => match enum_choice(all_of) {
+                // An enum field: its choice is asked with the field's prompt
+                Some(one_of) => {
+                    observer.on_question_start(&path);
+                    self.ask_one_of(&path, &prompt, one_of, responses, validate, observer)
+                }
+                None => {
+                    // A nested struct is introduced by its own prompt
+                    if !question.ask().is_empty() {
+                        println!("\n{}", question.ask());
+                    }
+                    for nested_q in all_of.questions() {
+                        self.ask_question(nested_q, responses, validate, observer, Some(&path))?;
+                    }
+                    Ok(())
+                }
+            },
+        };
+        answered?;
+
+        if question.allows_comment() {
+            self.ask_comment(&path, responses)?;
+        }
+        Ok(())
+    }
+
+    /// Offer an optional free-text comment on the answer at `path`.
+    fn ask_comment(
+        &self,
+        path: &ResponsePath,
+        responses: &mut Responses,
+    ) -> Result<(), DialoguerError> {
+        let mut _theme;
+        let builder: Input<String>;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Input::with_theme(&_theme);
+        } else {
+            builder = Input::new();
+        }
+
+        let result = builder
+            .with_prompt("Comment (optional, Enter to skip)")
+            .allow_empty(true)
+            .interact_text();
+
+        match result {
+            Ok(comment) => {
+                if !comment.trim().is_empty() {
+                    responses.insert(path.child(COMMENT_KEY), ResponseValue::String(comment));
+                }
+                Ok(())
+            }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
+    }
+
+    fn ask_input(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        input_q: &elicitor::InputQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        let _theme;
+        let mut builder: Input<String>;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Input::with_theme(&_theme);
+        } else {
+            builder = Input::new();
+        }
+
+        builder = builder.with_prompt(prompt).allow_empty(false);
+
+        // Apply default value
+        if let Some(default_val) = default.value() {
+            if let ResponseValue::String(s) = default_val {
+                builder = builder.default(s.clone());
+            }
+        } else if let Some(ref def) = input_q.default {
+            builder = builder.default(def.clone());
+        }
+
+        let result = builder
+            .validate_with(|value: &String| {
+                validate(&ResponseValue::String(value.clone()), responses, path)
+            })
+            .interact_text();
+
+        match result {
+            Ok(value) => {
+                responses.insert(path.clone(), ResponseValue::String(value));
+                Ok(())
+            }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
+    }
+
+    fn ask_path(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        path_q: &elicitor::PathQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        let prompt = match path_q.hint() {
+            Some(hint) => format!("{prompt} ({hint})"),
+            None => prompt.to_string(),
+        };
+        let _theme;
+        let mut builder: Input<String>;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Input::with_theme(&_theme);
+        } else {
+            builder = Input::new();
+        }
+
+        builder = builder.with_prompt(&prompt).allow_empty(false);
+
+        if let Some(ResponseValue::String(s)) = default.value() {
+            builder = builder.default(s.clone());
+        } else if let Some(ref def) = path_q.default {
+            builder = builder.default(def.clone());
+        }
+
+        // Check existence and extension before custom validation
+        let result = builder
+            .validate_with(|value: &String| {
+                path_q
+                    .check(value)
+                    .and_then(|()| validate(&ResponseValue::String(value.clone()), responses, path))
+            })
+            .interact_text();
+
+        match result {
+            Ok(value) => {
+                responses.insert(path.clone(), ResponseValue::String(value));
+                Ok(())
+            }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
+    }
+
+    fn ask_autocomplete(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        autocomplete_q: &elicitor::AutocompleteQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        let completer = Completer(autocomplete_q);
+
+        // Hint at what Tab will complete to
+        let hints = autocomplete_q.completions("");
+        if !hints.is_empty() {
+            let shown: Vec<&str> = hints.iter().take(5).map(String::as_str).collect();
+            let more = if hints.len() > shown.len() {
+                ", ..."
+            } else {
+                ""
+            };
+            println!("  Tab completes: {}{more}", shown.join(", "));
+        }
+        let _theme;
+        let mut builder: Input<String>;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Input::with_theme(&_theme);
+        } else {
+            builder = Input::new();
+        }
+
+        builder = builder
+            .with_prompt(prompt)
+            .allow_empty(false)
+            .completion_with(&completer);
+
+        // Apply default value
+        if let Some(default_val) = default.value() {
+            if let ResponseValue::String(s) = default_val {
+                builder = builder.default(s.clone());
+            }
+        } else if let Some(ref def) = autocomplete_q.default {
+            builder = builder.default(def.clone());
+        }
+
+        let result = builder
+            .validate_with(|value: &String| {
+                validate(&ResponseValue::String(value.clone()), responses, path)
+            })
+            .interact_text();
+
+        match result {
+            Ok(value) => {
+                responses.insert(path.clone(), ResponseValue::String(value));
+                Ok(())
+            }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
+    }
+
+    fn ask_multiline(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        multiline_q: &elicitor::MultilineQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        // Get default text if available; replaced by the last draft when revising
+        let mut draft = if let Some(ResponseValue::String(s)) = default.value() {
+            s.clone()
+        } else {
+            multiline_q.default.clone().unwrap_or_default()
+        };
+
+        loop {
+            println!("{prompt}");
+
+            let value = match Editor::new().edit(&draft) {
+                Ok(Some(value)) => value,
+                // Closed without saving: the text stays as it was
+                Ok(None) => draft.clone(),
+                Err(e) if is_cancelled(&e) => {
+                    return Err(DialoguerError::cancelled());
+                }
+                Err(dialoguer::Error::IO(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                    println!(
+                        "No editor could be started. Type the text, ending with an empty line:"
+                    );
+                    read_until_blank(std::io::stdin().lock())
+                        .map_err(|e| DialoguerError::Dialoguer(e.into()))?
+                }
+                Err(e) => return Err(DialoguerError::Dialoguer(e)),
+            };
+
+            let rv = ResponseValue::String(value.clone());
+            if let Err(msg) = validate(&rv, responses, path) {
+                println!("Error: {msg}");
+                draft = value;
+                continue;
+            }
+            if !self.review_text(&value)? {
+                draft = value;
+                continue;
+            }
+            responses.insert(path.clone(), rv);
+            return Ok(());
+        }
+    }
+
+    fn ask_masked(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        _masked_q: &elicitor::MaskedQuestion,
+        _default: &DefaultValue, // Passwords don't have visible defaults
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        let _theme;
+        let builder: Password;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Password::with_theme(&_theme);
+        } else {
+            builder = Password::new();
+        }
+
+        let result = builder
+            .with_prompt(prompt)
+            .validate_with(|value: &String| {
+                validate(&ResponseValue::String(value.clone()), responses, path)
+            })
+            .interact();
+
+        match result {
+            Ok(value) => {
+                responses.insert(path.clone(), ResponseValue::String(value));
+                Ok(())
+            }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
+    }
+
+    fn ask_currency(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        currency_q: &elicitor::CurrencyQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        let _theme;
+        let mut builder: Input<String>;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Input::with_theme(&_theme);
+        } else {
+            builder = Input::new();
+        }
+
+        builder = builder.with_prompt(format!("{prompt} ({})", currency_q.symbol()));
+
+        // Apply default value, formatted like the answer will be shown
+        let default_minor = match default.value() {
+            Some(ResponseValue::Int(minor)) => Some(*minor),
+            _ => currency_q.default,
+        };
+        if let Some(minor) = default_minor {
+            builder = builder.default(currency_q.format(minor));
+        }
+
+        let parse = |value: &str| {
+            currency_q
+                .parse(value)
+                .and_then(|minor| currency_q.check_bounds(minor).map(|()| minor))
+        };
+        let result = builder
+            .validate_with(|value: &String| {
+                parse(value).and_then(|minor| validate(&ResponseValue::Int(minor), responses, path))
+            })
+            .interact_text();
+
+        match result {
+            Ok(value) => {
+                let minor = parse(&value).map_err(DialoguerError::ValidationError)?;
+                println!("  = {}", currency_q.format(minor));
+                responses.insert(path.clone(), ResponseValue::Int(minor));
+                Ok(())
             }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
+        }
+    }
 
-            QuestionKind::List(list_q) => self.ask_list(
-                &path,
-                &prompt,
-                list_q,
-                question.default(),
-                responses,
-                validate,
-            ),
+    fn ask_color(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        color_q: &elicitor::ColorQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), DialoguerError> {
+        let _theme;
+        let mut builder: Input<String>;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Input::with_theme(&_theme);
+        } else {
+            builder = Input::new();
+        }
 
-            QuestionKind::OneOf(one_of) => {
-                self.ask_one_of(&path, &prompt, one_of, responses, validate)
-            }
+        builder = builder.with_prompt(format!("{prompt} (hex, e.g. #1e90ff)"));
 
-            QuestionKind::AnyOf(any_of) => {
-                self.ask_any_of(&path, &prompt, any_of, responses, validate)
-            }
+        let default_color = match default.value() {
+            Some(ResponseValue::String(hex)) => hex.parse().ok(),
+            _ => color_q.default,
+        };
+        if let Some(color) = default_color {
+            builder = builder.default(color.to_hex());
+        }
 
-            QuestionKind::AllOf(all_of) => {
-                for nested_q in all_of.questions() {
-                    self.ask_question(nested_q, responses, validate, Some(&path))?;
+        let result = builder
+            .validate_with(|value: &String| {
+                color_q.parse(value).and_then(|color| {
+                    validate(&ResponseValue::String(color.to_hex()), responses, path)
+                })
+            })
+            .interact_text();
+
+        match result {
+            Ok(value) => {
+                let color = color_q
+                    .parse(&value)
+                    .map_err(DialoguerError::ValidationError)?;
+                if self.colorful {
+                    // Truecolor swatch of the picked color
+                    println!(
+                        "  \x1b[48;2;{};{};{}m    \x1b[0m {color}",
+                        color.r, color.g, color.b
+                    );
                 }
+                responses.insert(path.clone(), ResponseValue::String(color.to_hex()));
                 Ok(())
             }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
         }
     }
 
-    fn ask_input(
+    fn ask_int(
         &self,
         path: &ResponsePath,
         prompt: &str,
-        input_q: &elicitor::InputQuestion,
+        int_q: &elicitor::IntQuestion,
         default: &DefaultValue,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), DialoguerError> {
-        loop {
-            let mut _theme;
-            let mut builder: Input<String>;
-            if self.colorful {
-                _theme = ColorfulTheme::default();
-                builder = Input::with_theme(&_theme);
-            } else {
-                builder = Input::new();
-            }
+        let _theme;
+        let mut builder: Input<String>;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Input::with_theme(&_theme);
+        } else {
+            builder = Input::new();
+        }
 
-            builder = builder.with_prompt(prompt).allow_empty(false);
+        builder = builder.with_prompt(prompt);
 
-            // Apply default value
-            if let Some(default_val) = default.value() {
-                if let ResponseValue::String(s) = default_val {
-                    builder = builder.default(s.clone());
-                }
-            } else if let Some(ref def) = input_q.default {
-                builder = builder.default(def.clone());
+        // Apply default value, in the separators of the question's locale
+        if let Some(default_val) = default.value() {
+            if let ResponseValue::Int(value) = default_val {
+                builder = builder.default(int_q.format(*value));
             }
+        } else if let Some(def) = int_q.default {
+            builder = builder.default(int_q.format(def));
+        }
 
-            let result = builder.interact_text();
+        // Bounds first, then the field's own validators
+        let parse = |value: &str| {
+            int_q
+                .parse(value)
+                .and_then(|n| int_q.check_bounds(n).map(|()| n))
+        };
+        let result = builder
+            .validate_with(|value: &String| {
+                parse(value).and_then(|n| validate(&ResponseValue::Int(n), responses, path))
+            })
+            .interact_text();
 
-            match result {
-                Ok(value) => {
-                    let rv = ResponseValue::String(value.clone());
-                    if let Err(msg) = validate(&rv, responses, path) {
-                        println!("Error: {msg}");
-                        continue;
-                    }
-                    responses.insert(path.clone(), rv);
-                    return Ok(());
-                }
-                Err(e) if is_cancelled(&e) => {
-                    return Err(DialoguerError::Cancelled);
-                }
-                Err(e) => return Err(DialoguerError::Dialoguer(e)),
+        match result {
+            Ok(value) => {
+                let value = parse(&value).map_err(DialoguerError::ValidationError)?;
+                responses.insert(path.clone(), ResponseValue::Int(value));
+                Ok(())
             }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
         }
     }
 
-    fn ask_multiline(
+    fn ask_float(
         &self,
         path: &ResponsePath,
         prompt: &str,
+        float_q: &elicitor::FloatQuestion,
         default: &DefaultValue,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), DialoguerError> {
-        loop {
-            println!("{prompt}");
+        let _theme;
+        let mut builder: Input<String>;
+        if self.colorful {
+            _theme = ColorfulTheme::default();
+            builder = Input::with_theme(&_theme);
+        } else {
+            builder = Input::new();
+        }
 
-            let editor = Editor::new();
+        builder = builder.with_prompt(prompt);
 
-            // Get default text if available
-            let default_text = if let Some(default_val) = default.value() {
-                if let ResponseValue::String(s) = default_val {
-                    s.as_str()
-                } else {
-                    ""
-                }
-            } else {
-                ""
-            };
+        // Apply default value, in the separators of the question's locale
+        if let Some(default_val) = default.value() {
+            if let ResponseValue::Float(value) = default_val {
+                builder = builder.default(float_q.format(*value));
+            }
+        } else if let Some(def) = float_q.default {
+            builder = builder.default(float_q.format(def));
+        }
 
-            let result = editor.edit(default_text);
+        // Bounds first, then the field's own validators
+        let parse = |value: &str| {
+            float_q
+                .parse(value)
+                .and_then(|n| float_q.check_bounds(n).map(|()| n))
+        };
+        let result = builder
+            .validate_with(|value: &String| {
+                parse(value).and_then(|n| validate(&ResponseValue::Float(n), responses, path))
+            })
+            .interact_text();
 
-            match result {
-                Ok(Some(value)) => {
-                    let rv = ResponseValue::String(value.clone());
-                    if let Err(msg) = validate(&rv, responses, path) {
-                        println!("Error: {msg}");
-                        continue;
-                    }
-                    responses.insert(path.clone(), rv);
-                    return Ok(());
-                }
-                Ok(None) => {
-                    // Editor was aborted or empty, use empty string
-                    let rv = ResponseValue::String(String::new());
-                    if let Err(msg) = validate(&rv, responses, path) {
-                        println!("Error: {msg}");
-                        continue;
-                    }
-                    responses.insert(path.clone(), rv);
-                    return Ok(());
-                }
-                Err(e) if is_cancelled(&e) => {
-                    return Err(DialoguerError::Cancelled);
-                }
-                Err(e) => return Err(DialoguerError::Dialoguer(e)),
+        match result {
+            Ok(value) => {
+                let value = parse(&value).map_err(DialoguerError::ValidationError)?;
+                responses.insert(path.clone(), ResponseValue::Float(value));
+                Ok(())
             }
+            Err(e) if is_cancelled(&e) => Err(DialoguerError::cancelled()),
+            Err(e) => Err(DialoguerError::Dialoguer(e)),
         }
     }
 
-    fn ask_masked(
+    fn ask_confirm(
         &self,
         path: &ResponsePath,
         prompt: &str,
-        _masked_q: &elicitor::MaskedQuestion,
-        _default: &DefaultValue, // Passwords don't have visible defaults
+        confirm_q: &elicitor::ConfirmQuestion,
+        default: &DefaultValue,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), DialoguerError> {
+        let default_val = if let Some(ResponseValue::Bool(b)) = default.value() {
+            *b
+        } else {
+            confirm_q.default
+        };
+
         loop {
-            let mut _theme;
-            let mut builder: Password;
+            let mut builder: Confirm;
+            let _theme;
             if self.colorful {
                 _theme = ColorfulTheme::default();
-                builder = Password::with_theme(&_theme);
+                builder = Confirm::with_theme(&_theme);
             } else {
-                builder = Password::new();
+                builder = Confirm::new();
             }
 
-            builder = builder.with_prompt(prompt);
-
-            let result = builder.interact();
+            builder = builder.with_prompt(prompt).default(default_val);
 
-            match result {
+            match builder.interact() {
                 Ok(value) => {
-                    let rv = ResponseValue::String(value.clone());
+                    let rv = ResponseValue::Bool(value);
                     if let Err(msg) = validate(&rv, responses, path) {
                         println!("Error: {msg}");
                         continue;
@@ -298,176 +976,155 @@ impl DialoguerBackend {
                     responses.insert(path.clone(), rv);
                     return Ok(());
                 }
-                Err(e) if is_cancelled(&e) => {
-                    return Err(DialoguerError::Cancelled);
-                }
+                Err(e) if is_cancelled(&e) => return Err(DialoguerError::cancelled()),
                 Err(e) => return Err(DialoguerError::Dialoguer(e)),
             }
         }
     }
 
-    fn ask_int(
+    fn ask_rating(
         &self,
         path: &ResponsePath,
         prompt: &str,
-        int_q: &elicitor::IntQuestion,
+        rating_q: &elicitor::RatingQuestion,
         default: &DefaultValue,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), DialoguerError> {
+        let points: Vec<i64> = rating_q.points().collect();
+        let items: Vec<String> = points.iter().map(|&p| rating_q.label(p)).collect();
+        let default_idx = default
+            .value()
+            .and_then(|v| v.as_int())
+            .or(rating_q.default)
+            .and_then(|v| points.iter().position(|&p| p == v));
+
         loop {
-            let mut _theme;
-            let mut builder: Input<i64>;
+            let mut builder: Select;
+            let _theme;
             if self.colorful {
                 _theme = ColorfulTheme::default();
-                builder = Input::with_theme(&_theme);
+                builder = Select::with_theme(&_theme);
             } else {
-                builder = Input::new();
+                builder = Select::new();
             }
 
-            builder = builder.with_prompt(prompt);
+            builder = builder.with_prompt(prompt).items(&items);
 
-            // Apply default value
-            if let Some(default_val) = default.value() {
-                if let ResponseValue::Int(i) = default_val {
-                    builder = builder.default(*i);
-                }
-            } else if let Some(def) = int_q.default {
-                builder = builder.default(def);
+            if let Some(idx) = default_idx {
+                builder = builder.default(idx);
             }
 
-            let result = builder.interact_text();
-
-            match result {
-                Ok(value) => {
-                    // Check bounds
-                    if let Some(min) = int_q.min {
-                        if value < min {
-                            println!("Error: Value must be at least {min}");
-                            continue;
-                        }
-                    }
-                    if let Some(max) = int_q.max {
-                        if value > max {
-                            println!("Error: Value must be at most {max}");
-                            continue;
-                        }
-                    }
-
-                    let rv = ResponseValue::Int(value);
-                    if let Err(msg) = validate(&rv, responses, path) {
-                        println!("Error: {msg}");
-                        continue;
-                    }
-                    responses.insert(path.clone(), rv);
-                    return Ok(());
-                }
+            let selection = match builder.interact() {
+                Ok(idx) => idx,
                 Err(e) if is_cancelled(&e) => {
-                    return Err(DialoguerError::Cancelled);
+                    return Err(DialoguerError::cancelled());
                 }
                 Err(e) => return Err(DialoguerError::Dialoguer(e)),
+            };
+
+            let rv = ResponseValue::Int(points[selection]);
+            if let Err(msg) = validate(&rv, responses, path) {
+                eprintln!("Error: {msg}");
+                continue;
             }
+
+            responses.insert(path.clone(), rv);
+            return Ok(());
         }
     }
 
-    fn ask_float(
+    fn ask_matrix(
         &self,
         path: &ResponsePath,
         prompt: &str,
-        float_q: &elicitor::FloatQuestion,
+        matrix_q: &elicitor::MatrixQuestion,
         default: &DefaultValue,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), DialoguerError> {
-        loop {
-            let mut _theme;
-            let mut builder: Input<f64>;
-            if self.colorful {
-                _theme = ColorfulTheme::default();
-                builder = Input::with_theme(&_theme);
-            } else {
-                builder = Input::new();
-            }
+        let defaults = match default.value() {
+            Some(ResponseValue::ChosenVariants(columns)) => columns.clone(),
+            _ => Vec::new(),
+        };
 
-            builder = builder.with_prompt(prompt);
+        loop {
+            println!("{prompt}");
 
-            // Apply default value
-            if let Some(default_val) = default.value() {
-                if let ResponseValue::Float(f) = default_val {
-                    builder = builder.default(*f);
+            // One selection per row, all on the same scale
+            let mut columns = Vec::with_capacity(matrix_q.rows.len());
+            for (row_idx, row) in matrix_q.rows.iter().enumerate() {
+                let mut builder: Select;
+                let _theme;
+                if self.colorful {
+                    _theme = ColorfulTheme::default();
+                    builder = Select::with_theme(&_theme);
+                } else {
+                    builder = Select::new();
                 }
-            } else if let Some(def) = float_q.default {
-                builder = builder.default(def);
-            }
 
-            let result = builder.interact_text();
+                builder = builder.with_prompt(row).items(&matrix_q.columns);
 
-            match result {
-                Ok(value) => {
-                    // Check bounds
-                    if let Some(min) = float_q.min {
-                        if value < min {
-                            println!("Error: Value must be at least {min}");
-                            continue;
-                        }
-                    }
-                    if let Some(max) = float_q.max {
-                        if value > max {
-                            println!("Error: Value must be at most {max}");
-                            continue;
-                        }
-                    }
+                if let Some(&idx) = defaults.get(row_idx)
+                    && idx < matrix_q.columns.len()
+                {
+                    builder = builder.default(idx);
+                }
 
-                    let rv = ResponseValue::Float(value);
-                    if let Err(msg) = validate(&rv, responses, path) {
-                        println!("Error: {msg}");
-                        continue;
+                match builder.interact() {
+                    Ok(idx) => columns.push(idx),
+                    Err(e) if is_cancelled(&e) => {
+                        return Err(DialoguerError::cancelled());
                     }
-                    responses.insert(path.clone(), rv);
-                    return Ok(());
-                }
-                Err(e) if is_cancelled(&e) => {
-                    return Err(DialoguerError::Cancelled);
+                    Err(e) => return Err(DialoguerError::Dialoguer(e)),
                 }
-                Err(e) => return Err(DialoguerError::Dialoguer(e)),
             }
+
+            let rv = ResponseValue::ChosenVariants(columns);
+            if let Err(msg) = validate(&rv, responses, path) {
+                eprintln!("Error: {msg}");
+                continue;
+            }
+
+            responses.insert(path.clone(), rv);
+            return Ok(());
         }
     }
 
-    fn ask_confirm(
+    fn ask_select(
         &self,
         path: &ResponsePath,
         prompt: &str,
-        confirm_q: &elicitor::ConfirmQuestion,
+        select_q: &elicitor::SelectQuestion,
         default: &DefaultValue,
         responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), DialoguerError> {
-        let default_val = if let Some(ResponseValue::Bool(b)) = default.value() {
-            *b
-        } else {
-            confirm_q.default
-        };
-
-        let mut builder: Confirm;
-        let _theme;
-        if self.colorful {
-            _theme = ColorfulTheme::default();
-            builder = Confirm::with_theme(&_theme);
-        } else {
-            builder = Confirm::new();
+        if select_q.options.is_empty() {
+            return Err(DialoguerError::ValidationError(format!(
+                "No choices available for '{}'",
+                path.as_str()
+            )));
         }
 
-        builder = builder.with_prompt(prompt).default(default_val);
+        let items: Vec<&str> = select_q.options.iter().map(|o| o.label.as_str()).collect();
+        let default_idx = default
+            .value()
+            .and_then(|v| v.as_str())
+            .and_then(|v| select_q.position(v))
+            .or(select_q.default);
 
-        let result = builder.interact();
+        loop {
+            let selection = self.choose(prompt, &items, default_idx)?;
 
-        match result {
-            Ok(value) => {
-                responses.insert(path.clone(), ResponseValue::Bool(value));
-                Ok(())
+            let rv = ResponseValue::String(select_q.options[selection].value.clone());
+            if let Err(msg) = validate(&rv, responses, path) {
+                eprintln!("Error: {msg}");
+                continue;
             }
-            Err(e) if is_cancelled(&e) => Err(DialoguerError::Cancelled),
-            Err(e) => Err(DialoguerError::Dialoguer(e)),
+
+            responses.insert(path.clone(), rv);
+            return Ok(());
         }
     }
 
@@ -504,7 +1161,7 @@ impl DialoguerBackend {
                     match builder.interact_text() {
                         Ok(s) if s.is_empty() => break,
                         Ok(s) => Some(ResponseValue::String(s)),
-                        Err(e) if is_cancelled(&e) => return Err(DialoguerError::Cancelled),
+                        Err(e) if is_cancelled(&e) => return Err(DialoguerError::cancelled()),
                         Err(e) => return Err(DialoguerError::Dialoguer(e)),
                     }
                 }
@@ -518,32 +1175,28 @@ impl DialoguerBackend {
                         builder = Input::new();
                     }
 
-                    builder = builder.with_prompt(&item_prompt).allow_empty(true);
+                    let parse = |s: &str| {
+                        s.parse::<i64>()
+                            .map_err(|_| list_q.messages.invalid_integer(None))
+                            .and_then(|n| check_bounds(&list_q.messages, n, *min, *max).map(|()| n))
+                    };
+                    builder = builder
+                        .with_prompt(&item_prompt)
+                        .allow_empty(true)
+                        .validate_with(|s: &String| {
+                            if s.is_empty() {
+                                Ok(())
+                            } else {
+                                parse(s).map(|_| ())
+                            }
+                        });
 
                     match builder.interact_text() {
                         Ok(s) if s.is_empty() => break,
-                        Ok(s) => match s.parse::<i64>() {
-                            Ok(n) => {
-                                if let Some(min_val) = min {
-                                    if n < *min_val {
-                                        println!("    Error: Value must be at least {min_val}");
-                                        continue;
-                                    }
-                                }
-                                if let Some(max_val) = max {
-                                    if n > *max_val {
-                                        println!("    Error: Value must be at most {max_val}");
-                                        continue;
-                                    }
-                                }
-                                Some(ResponseValue::Int(n))
-                            }
-                            Err(_) => {
-                                println!("    Error: Please enter a valid integer");
-                                continue;
-                            }
-                        },
-                        Err(e) if is_cancelled(&e) => return Err(DialoguerError::Cancelled),
+                        Ok(s) => Some(ResponseValue::Int(
+                            parse(&s).map_err(DialoguerError::ValidationError)?,
+                        )),
+                        Err(e) if is_cancelled(&e) => return Err(DialoguerError::cancelled()),
                         Err(e) => return Err(DialoguerError::Dialoguer(e)),
                     }
                 }
@@ -557,32 +1210,28 @@ impl DialoguerBackend {
                         builder = Input::new();
                     }
 
-                    builder = builder.with_prompt(&item_prompt).allow_empty(true);
+                    let parse = |s: &str| {
+                        s.parse::<f64>()
+                            .map_err(|_| list_q.messages.invalid_number(None))
+                            .and_then(|n| check_bounds(&list_q.messages, n, *min, *max).map(|()| n))
+                    };
+                    builder = builder
+                        .with_prompt(&item_prompt)
+                        .allow_empty(true)
+                        .validate_with(|s: &String| {
+                            if s.is_empty() {
+                                Ok(())
+                            } else {
+                                parse(s).map(|_| ())
+                            }
+                        });
 
                     match builder.interact_text() {
                         Ok(s) if s.is_empty() => break,
-                        Ok(s) => match s.parse::<f64>() {
-                            Ok(n) => {
-                                if let Some(min_val) = min {
-                                    if n < *min_val {
-                                        println!("    Error: Value must be at least {min_val}");
-                                        continue;
-                                    }
-                                }
-                                if let Some(max_val) = max {
-                                    if n > *max_val {
-                                        println!("    Error: Value must be at most {max_val}");
-                                        continue;
-                                    }
-                                }
-                                Some(ResponseValue::Float(n))
-                            }
-                            Err(_) => {
-                                println!("    Error: Please enter a valid number");
-                                continue;
-                            }
-                        },
-                        Err(e) if is_cancelled(&e) => return Err(DialoguerError::Cancelled),
+                        Ok(s) => Some(ResponseValue::Float(
+                            parse(&s).map_err(DialoguerError::ValidationError)?,
+                        )),
+                        Err(e) if is_cancelled(&e) => return Err(DialoguerError::cancelled()),
                         Err(e) => return Err(DialoguerError::Dialoguer(e)),
                     }
                 }
@@ -654,33 +1303,10 @@ impl DialoguerBackend {
         one_of: &elicitor::OneOfQuestion,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
     ) -> Result<(), DialoguerError> {
         let items: Vec<&str> = one_of.variants.iter().map(|v| v.name.as_str()).collect();
-
-        let mut builder: Select;
-        let _theme;
-        if self.colorful {
-            _theme = ColorfulTheme::default();
-            builder = Select::with_theme(&_theme);
-        } else {
-            builder = Select::new();
-        }
-
-        builder = builder.with_prompt(prompt).items(&items);
-
-        if let Some(default_idx) = one_of.default {
-            builder = builder.default(default_idx);
-        }
-
-        let result = builder.interact();
-
-        let selection = match result {
-            Ok(idx) => idx,
-            Err(e) if is_cancelled(&e) => {
-                return Err(DialoguerError::Cancelled);
-            }
-            Err(e) => return Err(DialoguerError::Dialoguer(e)),
-        };
+        let selection = self.choose(prompt, &items, one_of.default)?;
 
         // Store the selected variant index
         let variant_path = path.child(SELECTED_VARIANT_KEY);
@@ -694,13 +1320,20 @@ impl DialoguerBackend {
             }
             QuestionKind::AllOf(all_of) => {
                 for nested_q in all_of.questions() {
-                    self.ask_question(nested_q, responses, validate, Some(path))?;
+                    self.ask_question(nested_q, responses, validate, observer, Some(path))?;
                 }
             }
             QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
             | QuestionKind::Int(_)
+            | QuestionKind::Currency(_)
+            | QuestionKind::Color(_)
+            | QuestionKind::Path(_)
+            | QuestionKind::Rating(_)
+            | QuestionKind::Matrix(_)
             | QuestionKind::Float(_)
             | QuestionKind::Confirm(_)
+            | QuestionKind::Select(_)
             | QuestionKind::Masked(_)
             | QuestionKind::Multiline(_)
             | QuestionKind::List(_) => {
@@ -709,7 +1342,7 @@ impl DialoguerBackend {
                     format!("Enter {} value:", selected_variant.name),
                     selected_variant.kind.clone(),
                 );
-                self.ask_question(&variant_q, responses, validate, Some(path))?;
+                self.ask_question(&variant_q, responses, validate, observer, Some(path))?;
             }
             QuestionKind::OneOf(nested_one_of) => {
                 let variant_q = Question::new(
@@ -717,7 +1350,7 @@ impl DialoguerBackend {
                     format!("Select {}:", selected_variant.name),
                     QuestionKind::OneOf(nested_one_of.clone()),
                 );
-                self.ask_question(&variant_q, responses, validate, Some(path))?;
+                self.ask_question(&variant_q, responses, validate, observer, Some(path))?;
             }
             QuestionKind::AnyOf(nested_any_of) => {
                 let variant_q = Question::new(
@@ -725,7 +1358,7 @@ impl DialoguerBackend {
                     format!("Select {} options:", selected_variant.name),
                     QuestionKind::AnyOf(nested_any_of.clone()),
                 );
-                self.ask_question(&variant_q, responses, validate, Some(path))?;
+                self.ask_question(&variant_q, responses, validate, observer, Some(path))?;
             }
         }
 
@@ -739,6 +1372,7 @@ impl DialoguerBackend {
         any_of: &elicitor::AnyOfQuestion,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
     ) -> Result<(), DialoguerError> {
         let selections = loop {
             let items: Vec<&str> = any_of.variants.iter().map(|v| v.name.as_str()).collect();
@@ -767,7 +1401,7 @@ impl DialoguerBackend {
             let selections = match result {
                 Ok(indices) => indices,
                 Err(e) if is_cancelled(&e) => {
-                    return Err(DialoguerError::Cancelled);
+                    return Err(DialoguerError::cancelled());
                 }
                 Err(e) => return Err(DialoguerError::Dialoguer(e)),
             };
@@ -804,7 +1438,13 @@ impl DialoguerBackend {
                 }
                 QuestionKind::AllOf(all_of) => {
                     for nested_q in all_of.questions() {
-                        self.ask_question(nested_q, responses, validate, Some(&item_path))?;
+                        self.ask_question(
+                            nested_q,
+                            responses,
+                            validate,
+                            observer,
+                            Some(&item_path),
+                        )?;
                     }
                 }
                 _ => {
@@ -824,6 +1464,15 @@ impl SurveyBackend for DialoguerBackend {
         &self,
         definition: &SurveyDefinition,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
     ) -> Result<Responses, Self::Error> {
         let mut responses = Responses::new();
 
@@ -833,9 +1482,17 @@ impl SurveyBackend for DialoguerBackend {
             println!();
         }
 
-        // Ask all questions
-        for question in definition.questions() {
-            self.ask_question(question, &mut responses, validate, None)?;
+        // Ask all questions, keeping the answers given so far on cancellation
+        let asked = definition.questions().iter().try_for_each(|question| {
+            self.ask_question(question, &mut responses, validate, observer, None)
+        });
+        if let Err(err) = asked {
+            return Err(match err {
+                DialoguerError::Cancelled { .. } => DialoguerError::Cancelled {
+                    partial: Box::new(responses),
+                },
+                err => err,
+            });
         }
 
         // Show epilogue if present
@@ -860,10 +1517,81 @@ mod tests {
 
     #[test]
     fn error_types() {
-        let err = DialoguerError::Cancelled;
+        let err = DialoguerError::Cancelled {
+            partial: Box::default(),
+        };
         assert_eq!(err.to_string(), "Survey cancelled by user");
 
         let err = DialoguerError::ValidationError("test error".to_string());
         assert_eq!(err.to_string(), "Validation error: test error");
     }
+
+    #[test]
+    fn cancellation_keeps_partial_answers() {
+        let mut partial = Responses::new();
+        partial.insert("name", "Alice");
+
+        let err = SurveyError::from(DialoguerError::Cancelled {
+            partial: Box::new(partial),
+        });
+        assert!(err.is_cancelled());
+        assert_eq!(
+            err.partial()
+                .unwrap()
+                .get_string(&ResponsePath::new("name"))
+                .unwrap(),
+            "Alice"
+        );
+
+        let err = SurveyError::from(DialoguerError::ValidationError("bad".to_string()));
+        assert!(err.partial().is_none());
+    }
+
+    #[test]
+    fn enum_fields_are_asked_with_their_prompt() {
+        use elicitor::{AllOfQuestion, OneOfQuestion, Variant};
+
+        // What the derive generates for a field of type `enum Carrier { Post, Courier }`
+        let choice = AllOfQuestion::new(vec![Question::new(
+            ResponsePath::empty(),
+            "",
+            QuestionKind::OneOf(OneOfQuestion::new(vec![
+                Variant::unit("Post"),
+                Variant::unit("Courier"),
+            ])),
+        )]);
+        assert_eq!(
+            enum_choice(&choice).map(|one_of| one_of.variants.len()),
+            Some(2)
+        );
+
+        let nested_struct = AllOfQuestion::new(vec![Question::new(
+            "street",
+            "Street:",
+            QuestionKind::OneOf(OneOfQuestion::new(vec![Variant::unit("Main")])),
+        )]);
+        assert!(enum_choice(&nested_struct).is_none());
+    }
+
+    #[test]
+    fn numbers_are_checked_against_their_bounds() {
+        let messages = Messages::default();
+        assert_eq!(check_bounds(&messages, 5, Some(1), Some(10)), Ok(()));
+        assert_eq!(
+            check_bounds(&messages, 0, Some(1), Some(10)),
+            Err("Value must be at least 1".to_string())
+        );
+        assert_eq!(
+            check_bounds(&messages, 2.5, None, Some(2.0)),
+            Err("Value must be at most 2".to_string())
+        );
+        assert_eq!(check_bounds(&messages, -3, None, None), Ok(()));
+    }
+
+    #[test]
+    fn typed_text_ends_at_an_empty_line() {
+        let input = std::io::Cursor::new("Dear team,\nthanks!\n\nignored\n");
+        assert_eq!(read_until_blank(input).unwrap(), "Dear team,\nthanks!");
+        assert_eq!(read_until_blank(std::io::Cursor::new("")).unwrap(), "");
+    }
 }
@@ -0,0 +1,20 @@
+//! Color question example
+//!
+//! Demonstrates:
+//! - elicitor::Color fields
+//! - Hex input with a live swatch
+//! - Suggesting colors with with_suggestions
+//!
+//! Run with: cargo run --example ratatui_color
+
+use elicitor_wizard_ratatui::RatatuiBackend;
+use example_surveys::EditorTheme;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RatatuiBackend::new();
+    let result = EditorTheme::builder()
+        .with_suggestions(&EditorTheme::dark())
+        .run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
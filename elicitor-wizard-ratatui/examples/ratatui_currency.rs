@@ -0,0 +1,17 @@
+//! Currency question example
+//!
+//! Demonstrates:
+//! - #[currency("USD")] on integer fields holding cents
+//! - A formatted preview next to the typed amount
+//!
+//! Run with: cargo run --example ratatui_currency
+
+use elicitor_wizard_ratatui::RatatuiBackend;
+use example_surveys::MortgageApplication;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RatatuiBackend::new();
+    let result = MortgageApplication::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! Matrix question example
+//!
+//! Demonstrates:
+//! - HashMap<RowEnum, ColumnEnum> shown as a table
+//! - ↑/↓ to pick a row, ←/→ to pick a column
+//!
+//! Run with: cargo run --example ratatui_matrix
+
+use elicitor_wizard_ratatui::RatatuiBackend;
+use example_surveys::CourseEvaluation;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RatatuiBackend::new();
+    let result = CourseEvaluation::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
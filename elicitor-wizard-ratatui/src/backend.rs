@@ -5,16 +5,91 @@
 
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
     },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use elicitor::{
-    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition,
+    AutocompleteQuestion, COMMENT_KEY, Checkpoint, ColorQuestion, CurrencyQuestion, DefaultValue,
+    ListElementKind, MatrixQuestion, Messages, NoObserver, NumberFormat, PathMode, PathQuestion,
+    Question, QuestionKind, RatingQuestion, ResponsePath, ResponseValue, Responses,
+    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition, SurveyError,
+    SurveyObserver, TextChecker, fuzzy_match,
 };
 
+/// Select lists longer than this show the filter box before anything is typed.
+const FILTER_BOX_MIN_OPTIONS: usize = 8;
+
+/// Options shown per page of a choice list unless changed with
+/// [`RatatuiBackend::with_page_size`].
+pub const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// Rows left for the prompt when it wraps over several lines.
+const MAX_PROMPT_LINES: u16 = 6;
+
+/// Index of `value` on the rating scale, if it lies on it.
+fn rating_index(question: &RatingQuestion, value: i64) -> Option<usize> {
+    question
+        .contains(value)
+        .then(|| (value - question.min) as usize)
+}
+
+/// Sections of the follow-up questions of a chosen variant: those of the
+/// choice question, then the variant itself.
+fn variant_sections(
+    parent: &[Section],
+    variant: &elicitor::Variant,
+    path: &ResponsePath,
+) -> Vec<Section> {
+    let mut sections = parent.to_vec();
+    sections.push(Section {
+        path: path.clone(),
+        name: variant.name.clone(),
+    });
+    sections
+}
+
+/// The limits `kind` puts on an answer, in words, e.g. "At most 10".
+fn constraints(kind: &QuestionKind) -> Vec<String> {
+    fn range<T: std::fmt::Display>(min: Option<T>, max: Option<T>, unit: &str) -> Option<String> {
+        match (min, max) {
+            (Some(min), Some(max)) => Some(format!("Between {min} and {max}{unit}")),
+            (Some(min), None) => Some(format!("At least {min}{unit}")),
+            (None, Some(max)) => Some(format!("At most {max}{unit}")),
+            (None, None) => None,
+        }
+    }
+
+    let limit = match kind {
+        QuestionKind::Int(q) => range(q.min.map(|m| q.format(m)), q.max.map(|m| q.format(m)), ""),
+        QuestionKind::Float(q) => range(q.min.map(|m| q.format(m)), q.max.map(|m| q.format(m)), ""),
+        QuestionKind::Currency(q) => {
+            range(q.min.map(|m| q.format(m)), q.max.map(|m| q.format(m)), "")
+        }
+        QuestionKind::List(q) => range(q.min_items, q.max_items, " items"),
+        QuestionKind::AnyOf(q) => q.max_selected.map(|max| format!("At most {max} selected")),
+        QuestionKind::Path(q) => match &q.mode {
+            PathMode::Any => None,
+            PathMode::File { extensions } if extensions.is_empty() => {
+                Some("An existing file".to_string())
+            }
+            PathMode::File { extensions } => Some(format!(
+                "An existing file ending in {}",
+                extensions
+                    .iter()
+                    .map(|ext| format!(".{ext}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            PathMode::Directory => Some("An existing directory".to_string()),
+        },
+        _ => None,
+    };
+    limit.into_iter().collect()
+}
+
 /// Helper function to get the parent path by stripping the last segment.
 fn parent_path(path: &ResponsePath) -> ResponsePath {
     let path_str = path.as_str();
@@ -24,22 +99,35 @@ fn parent_path(path: &ResponsePath) -> ResponsePath {
         ResponsePath::empty()
     }
 }
+use crate::bidi::{self, TextDirection};
 use ratatui::{
     Frame, Terminal,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     prelude::CrosstermBackend,
     style::{Color, Style, Stylize},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Table, TableState, Wrap,
+    },
 };
+use std::borrow::Cow;
 use std::io::{self, Stdout};
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::keymap::{Action, KeyMap, Resolved};
+use crate::theme::Theme;
 
 /// Error type for the Ratatui backend.
 #[derive(Debug, Error)]
 pub enum RatatuiError {
     /// User cancelled the survey (e.g., pressed Esc).
+    ///
+    /// Holds the answers given before cancelling.
     #[error("Survey cancelled by user")]
-    Cancelled,
+    Cancelled { partial: Box<Responses> },
 
     /// An I/O error occurred.
     #[error("I/O error: {0}")]
@@ -50,30 +138,21 @@ pub enum RatatuiError {
     Terminal(String),
 }
 
-/// Color theme for the TUI.
-#[derive(Debug, Clone)]
-pub struct Theme {
-    pub primary: Color,
-    pub secondary: Color,
-    pub background: Color,
-    pub text: Color,
-    pub highlight: Color,
-    pub error: Color,
-    pub success: Color,
-    pub border: Color,
+impl RatatuiError {
+    /// The answers given before the user cancelled, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            _ => None,
+        }
+    }
 }
 
-impl Default for Theme {
-    fn default() -> Self {
-        Self {
-            primary: Color::Cyan,
-            secondary: Color::Blue,
-            background: Color::Reset,
-            text: Color::White,
-            highlight: Color::Yellow,
-            error: Color::Red,
-            success: Color::Green,
-            border: Color::Gray,
+impl From<RatatuiError> for SurveyError {
+    fn from(err: RatatuiError) -> Self {
+        match err {
+            RatatuiError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
         }
     }
 }
@@ -88,6 +167,21 @@ pub struct RatatuiBackend {
     title: String,
     /// Color theme for the UI.
     theme: Theme,
+    /// Options shown per page of a choice list.
+    page_size: usize,
+    /// Key bindings.
+    keymap: KeyMap,
+    /// Where "Save draft" puts the answers when the user leaves early.
+    checkpoint: Option<Arc<dyn Checkpoint>>,
+    /// Direction prompts and answers are written in.
+    direction: TextDirection,
+    /// Whether the progress bar and help panels are mirrored for right-to-left readers.
+    mirrored: bool,
+    /// Checker run on multiline answers before they are accepted.
+    text_checker: Option<Arc<dyn TextChecker>>,
+    /// High-contrast mode as last switched with F2, shared by clones. Until then,
+    /// surveys start in the mode set with `elicitor::set_high_contrast`.
+    high_contrast: Arc<Mutex<Option<bool>>>,
 }
 
 impl Default for RatatuiBackend {
@@ -102,6 +196,13 @@ impl RatatuiBackend {
         Self {
             title: "Survey".to_string(),
             theme: Theme::default(),
+            page_size: DEFAULT_PAGE_SIZE,
+            keymap: KeyMap::default(),
+            checkpoint: None,
+            direction: TextDirection::Auto,
+            mirrored: false,
+            text_checker: None,
+            high_contrast: Arc::default(),
         }
     }
 
@@ -117,10 +218,58 @@ impl RatatuiBackend {
         self
     }
 
+    /// Show choice lists `page_size` options at a time (at least one). PageUp and
+    /// PageDown move through the pages.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Use other key bindings, e.g. [`KeyMap::vim`].
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Offer "Save draft" when the user leaves early, saving the answers so far
+    /// to `checkpoint`.
+    pub fn with_checkpoint(mut self, checkpoint: impl Checkpoint + 'static) -> Self {
+        self.checkpoint = Some(Arc::new(checkpoint));
+        self
+    }
+
+    /// Write prompts and answers in `direction`. By default a question whose prompt
+    /// starts with an Arabic or Hebrew letter is shown right to left.
+    pub fn with_text_direction(mut self, direction: TextDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Mirror the progress bar and help panels for right-to-left readers: the bar
+    /// fills from the right, key hints start at the right and help is right-aligned.
+    pub fn with_mirrored_layout(mut self, mirrored: bool) -> Self {
+        self.mirrored = mirrored;
+        self
+    }
+
+    /// Check multiline answers with `checker` before accepting them.
+    ///
+    /// Findings are shown in place of the answer's error; submitting the same text
+    /// again keeps it as it is.
+    pub fn with_text_checker(mut self, checker: impl TextChecker + 'static) -> Self {
+        self.text_checker = Some(Arc::new(checker));
+        self
+    }
+
     fn setup_terminal(&self) -> Result<Terminal<CrosstermBackend<Stdout>>, RatatuiError> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
         Ok(terminal)
@@ -134,7 +283,8 @@ impl RatatuiBackend {
         execute!(
             terminal.backend_mut(),
             LeaveAlternateScreen,
-            DisableMouseCapture
+            DisableMouseCapture,
+            DisableBracketedPaste
         )?;
         terminal.show_cursor()?;
         Ok(())
@@ -142,13 +292,13 @@ impl RatatuiBackend {
 }
 
 /// State for the entire wizard.
-struct WizardState {
+pub(crate) struct WizardState {
     /// All flattened questions.
     questions: Vec<FlatQuestion>,
     /// Current question index.
     current_index: usize,
     /// Collected responses.
-    responses: Responses,
+    pub(crate) responses: Responses,
     /// Current input buffer.
     input: String,
     /// Cursor position in input.
@@ -157,16 +307,46 @@ struct WizardState {
     selected_option: usize,
     /// For multi-select questions: which options are selected.
     multi_selected: Vec<bool>,
+    /// For select questions: the text typed to filter the options.
+    filter: String,
+    /// Options shown per page of a choice list.
+    pub(crate) page_size: usize,
+    /// Key bindings.
+    pub(crate) keymap: KeyMap,
+    /// Whether a modal keymap is in typing mode.
+    inserting: bool,
+    /// Where "Save draft" puts the answers when the user leaves early.
+    pub(crate) checkpoint: Option<Arc<dyn Checkpoint>>,
+    /// Direction prompts and answers are written in.
+    pub(crate) direction: TextDirection,
+    /// Whether the progress bar and help panels are mirrored.
+    pub(crate) mirrored: bool,
+    /// Checker run on multiline answers before they are accepted.
+    pub(crate) text_checker: Option<Arc<dyn TextChecker>>,
+    /// Multiline answer whose findings were shown, kept when submitted again.
+    reviewed_text: Option<String>,
+    /// The dialog asking whether to leave, while it is open.
+    leaving: Option<LeaveDialog>,
+    /// Whether the help overlay for the current question is open.
+    show_help: bool,
+    /// For matrix questions: the chosen column of each row.
+    matrix_choices: Vec<Option<usize>>,
     /// Current validation error message.
     error_message: Option<String>,
+    /// Comment being written for the current question, if the comment editor is open.
+    comment: Option<String>,
     /// Whether wizard is complete.
-    complete: bool,
+    pub(crate) complete: bool,
     /// Whether user cancelled.
-    cancelled: bool,
-    /// Theme.
+    pub(crate) cancelled: bool,
+    /// Theme in use.
     theme: Theme,
+    /// Theme configured on the backend, restored when high-contrast mode is switched off.
+    pub(crate) custom_theme: Theme,
+    /// Whether high-contrast mode with more spacing is on.
+    pub(crate) high_contrast: bool,
     /// Title.
-    title: String,
+    pub(crate) title: String,
     /// Epilogue text.
     epilogue: Option<String>,
 }
@@ -186,43 +366,139 @@ struct FlatQuestion {
     assumed: Option<ResponseValue>,
     /// Whether this field has custom validation.
     has_validation: bool,
+    /// Whether the user may attach a free-text comment.
+    allow_comment: bool,
+    /// Longer explanation from `#[help]`, shown in the help overlay.
+    help: Option<String>,
+    /// Limits on the answer in words, shown in the help overlay.
+    constraints: Vec<String>,
+    /// The nested structs and variants the question belongs to, outermost first.
+    sections: Vec<Section>,
+}
+
+/// A nested struct or chosen enum variant whose questions are asked together.
+#[derive(Clone, PartialEq)]
+struct Section {
+    /// Response path of the struct or variant, telling apart sections of the same name.
+    path: ResponsePath,
+    /// Name shown in the progress line and breadcrumb.
+    name: String,
+}
+
+impl Section {
+    /// Name a nested struct after its prompt, or its field name if it has none.
+    fn of(question: &Question, path: &ResponsePath) -> Self {
+        let prompt = question.ask().trim_end_matches([':', '?', ' ']);
+        let name = if prompt.is_empty() {
+            let field = question.path().as_str();
+            let field = field.rsplit('.').next().unwrap_or(field).replace('_', " ");
+            let mut chars = field.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        } else {
+            prompt.to_string()
+        };
+        Self {
+            path: path.clone(),
+            name,
+        }
+    }
 }
 
 #[derive(Clone)]
 enum FlatQuestionKind {
     Input,
+    Autocomplete {
+        question: AutocompleteQuestion,
+    },
     Multiline,
     Masked,
     Int {
         min: Option<i64>,
         max: Option<i64>,
+        format: NumberFormat,
+        messages: Messages,
     },
     Float {
         min: Option<f64>,
         max: Option<f64>,
+        format: NumberFormat,
+        messages: Messages,
+    },
+    Currency {
+        question: CurrencyQuestion,
+    },
+    Color {
+        question: ColorQuestion,
+    },
+    Path {
+        question: PathQuestion,
     },
     Confirm {
         default: bool,
     },
+    Rating {
+        question: RatingQuestion,
+        /// Index of the initially highlighted point on the scale.
+        default_idx: usize,
+    },
     List {
         element_kind: ListElementKind,
+        messages: Messages,
+    },
+    Matrix {
+        question: MatrixQuestion,
+        /// Suggested column of each row.
+        defaults: Vec<usize>,
     },
     Select {
         options: Vec<String>,
         default_idx: usize,
         /// For enum variants: the variants with their nested questions.
         variants: Option<Vec<elicitor::Variant>>,
+        /// For runtime choices: the string value stored for each option.
+        values: Option<Vec<String>>,
     },
     MultiSelect {
         options: Vec<String>,
         defaults: Vec<usize>,
+        /// Most options that may be selected at once.
+        max: Option<usize>,
         /// For AnyOf: the variants.
         variants: Option<Vec<elicitor::Variant>>,
     },
 }
 
+/// The choices of the dialog shown when the user wants to leave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LeaveChoice {
+    Discard,
+    SaveDraft,
+    Continue,
+}
+
+impl LeaveChoice {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Discard => "Discard",
+            Self::SaveDraft => "Save draft",
+            Self::Continue => "Continue",
+        }
+    }
+}
+
+/// State of the dialog shown when the user wants to leave.
+struct LeaveDialog {
+    /// Index of the highlighted choice.
+    highlighted: usize,
+    /// Why the draft could not be saved.
+    error: Option<String>,
+}
+
 impl WizardState {
-    fn new(definition: &SurveyDefinition, theme: Theme, title: String) -> Self {
+    pub(crate) fn new(definition: &SurveyDefinition, theme: Theme, title: String) -> Self {
         let questions = Self::flatten_questions(definition.questions(), &ResponsePath::empty());
 
         // If there's a prelude, include it in the title
@@ -248,6 +524,7 @@ impl WizardState {
                 }
                 FlatQuestionKind::Select { default_idx, .. } => (*default_idx, Vec::new()),
                 FlatQuestionKind::Confirm { default } => (if *default { 0 } else { 1 }, Vec::new()),
+                FlatQuestionKind::Rating { default_idx, .. } => (*default_idx, Vec::new()),
                 _ => (0, Vec::new()),
             }
         } else {
@@ -262,15 +539,40 @@ impl WizardState {
             cursor_pos: 0,
             selected_option,
             multi_selected,
+            filter: String::new(),
+            page_size: DEFAULT_PAGE_SIZE,
+            keymap: KeyMap::default(),
+            inserting: false,
+            checkpoint: None,
+            direction: TextDirection::Auto,
+            mirrored: false,
+            text_checker: None,
+            reviewed_text: None,
+            leaving: None,
+            show_help: false,
+            matrix_choices: Vec::new(),
             error_message: None,
+            comment: None,
             complete: false,
             cancelled: false,
-            theme,
+            theme: theme.clone(),
+            custom_theme: theme,
+            high_contrast: false,
             title: display_title,
             epilogue: definition.epilogue.clone(),
         }
     }
 
+    /// Switch to the high-contrast theme with more spacing, or back.
+    pub(crate) fn set_high_contrast(&mut self, enabled: bool) {
+        self.high_contrast = enabled;
+        self.theme = if enabled {
+            Theme::high_contrast()
+        } else {
+            self.custom_theme.clone()
+        };
+    }
+
     fn flatten_questions(questions: &[Question], prefix: &ResponsePath) -> Vec<FlatQuestion> {
         let mut flat = Vec::new();
 
@@ -304,6 +606,30 @@ impl WizardState {
                         default_value,
                         assumed,
                         has_validation: input_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
+                    });
+                }
+                QuestionKind::Autocomplete(autocomplete_q) => {
+                    let default_value = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::String(s)) => Some(s.clone()),
+                        _ => autocomplete_q.default.clone(),
+                    };
+                    flat.push(FlatQuestion {
+                        path,
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Autocomplete {
+                            question: autocomplete_q.clone(),
+                        },
+                        default_value,
+                        assumed,
+                        has_validation: autocomplete_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::Multiline(ml_q) => {
@@ -318,6 +644,10 @@ impl WizardState {
                         default_value,
                         assumed,
                         has_validation: ml_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::Masked(masked_q) => {
@@ -328,12 +658,16 @@ impl WizardState {
                         default_value: None,
                         assumed,
                         has_validation: masked_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::Int(int_q) => {
                     let default_value = match question.default() {
-                        DefaultValue::Suggested(ResponseValue::Int(i)) => Some(i.to_string()),
-                        _ => int_q.default.map(|d| d.to_string()),
+                        DefaultValue::Suggested(ResponseValue::Int(i)) => Some(int_q.format(*i)),
+                        _ => int_q.default.map(|d| int_q.format(d)),
                     };
                     flat.push(FlatQuestion {
                         path,
@@ -341,16 +675,108 @@ impl WizardState {
                         kind: FlatQuestionKind::Int {
                             min: int_q.min,
                             max: int_q.max,
+                            format: int_q.number_format(),
+                            messages: int_q.messages.clone(),
                         },
                         default_value,
                         assumed,
                         has_validation: int_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
+                    });
+                }
+                QuestionKind::Currency(currency_q) => {
+                    let default_value = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::Int(i)) => Some(*i),
+                        _ => currency_q.default,
+                    }
+                    .map(|minor| currency_q.format(minor));
+                    flat.push(FlatQuestion {
+                        path,
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Currency {
+                            question: currency_q.clone(),
+                        },
+                        default_value,
+                        assumed,
+                        has_validation: currency_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
+                    });
+                }
+                QuestionKind::Color(color_q) => {
+                    let default_value = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::String(hex)) => Some(hex.clone()),
+                        _ => color_q.default.map(|c| c.to_hex()),
+                    };
+                    flat.push(FlatQuestion {
+                        path,
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Color {
+                            question: color_q.clone(),
+                        },
+                        default_value,
+                        assumed,
+                        has_validation: color_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
+                    });
+                }
+                QuestionKind::Path(path_q) => {
+                    let default_value = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::String(s)) => Some(s.clone()),
+                        _ => path_q.default.clone(),
+                    };
+                    flat.push(FlatQuestion {
+                        path,
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Path {
+                            question: path_q.clone(),
+                        },
+                        default_value,
+                        assumed,
+                        has_validation: path_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
+                    });
+                }
+                QuestionKind::Rating(rating_q) => {
+                    let default = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::Int(i)) => Some(*i),
+                        _ => rating_q.default,
+                    };
+                    flat.push(FlatQuestion {
+                        path,
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Rating {
+                            question: rating_q.clone(),
+                            default_idx: default
+                                .and_then(|value| rating_index(rating_q, value))
+                                .unwrap_or(0),
+                        },
+                        default_value: None,
+                        assumed,
+                        has_validation: rating_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::Float(float_q) => {
                     let default_value = match question.default() {
-                        DefaultValue::Suggested(ResponseValue::Float(f)) => Some(f.to_string()),
-                        _ => float_q.default.map(|d| d.to_string()),
+                        DefaultValue::Suggested(ResponseValue::Float(f)) => {
+                            Some(float_q.format(*f))
+                        }
+                        _ => float_q.default.map(|d| float_q.format(d)),
                     };
                     flat.push(FlatQuestion {
                         path,
@@ -358,10 +784,16 @@ impl WizardState {
                         kind: FlatQuestionKind::Float {
                             min: float_q.min,
                             max: float_q.max,
+                            format: float_q.number_format(),
+                            messages: float_q.messages.clone(),
                         },
                         default_value,
                         assumed,
                         has_validation: float_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::Confirm(confirm_q) => {
@@ -376,6 +808,10 @@ impl WizardState {
                         default_value: Some(if default { "yes" } else { "no" }.to_string()),
                         assumed,
                         has_validation: false,
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::List(list_q) => {
@@ -384,10 +820,66 @@ impl WizardState {
                         prompt: question.ask().to_string(),
                         kind: FlatQuestionKind::List {
                             element_kind: list_q.element_kind.clone(),
+                            messages: list_q.messages.clone(),
                         },
                         default_value: None,
                         assumed,
                         has_validation: list_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
+                    });
+                }
+                QuestionKind::Matrix(matrix_q) => {
+                    let defaults = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::ChosenVariants(columns)) => {
+                            columns.clone()
+                        }
+                        _ => Vec::new(),
+                    };
+                    flat.push(FlatQuestion {
+                        path,
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Matrix {
+                            question: matrix_q.clone(),
+                            defaults,
+                        },
+                        default_value: None,
+                        assumed,
+                        has_validation: matrix_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
+                    });
+                }
+                QuestionKind::Select(select_q) => {
+                    let default_idx = match question.default() {
+                        DefaultValue::Suggested(ResponseValue::String(s)) => select_q.position(s),
+                        _ => None,
+                    }
+                    .or(select_q.default)
+                    .unwrap_or(0);
+
+                    flat.push(FlatQuestion {
+                        path,
+                        prompt: question.ask().to_string(),
+                        kind: FlatQuestionKind::Select {
+                            options: select_q.options.iter().map(|o| o.label.clone()).collect(),
+                            default_idx,
+                            variants: None,
+                            values: Some(
+                                select_q.options.iter().map(|o| o.value.clone()).collect(),
+                            ),
+                        },
+                        default_value: None,
+                        assumed,
+                        has_validation: select_q.validate.is_some(),
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::OneOf(one_of) => {
@@ -402,10 +894,15 @@ impl WizardState {
                             options,
                             default_idx,
                             variants: Some(one_of.variants.clone()),
+                            values: None,
                         },
                         default_value: None,
                         assumed,
                         has_validation: false,
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::AnyOf(any_of) => {
@@ -418,11 +915,16 @@ impl WizardState {
                         kind: FlatQuestionKind::MultiSelect {
                             options,
                             defaults: any_of.defaults.clone(),
+                            max: any_of.max_selected,
                             variants: Some(any_of.variants.clone()),
                         },
                         default_value: None,
                         assumed,
                         has_validation: false,
+                        allow_comment: question.allows_comment(),
+                        help: question.help().map(str::to_string),
+                        constraints: constraints(question.kind()),
+                        sections: Vec::new(),
                     });
                 }
                 QuestionKind::AllOf(all_of) => {
@@ -434,11 +936,16 @@ impl WizardState {
                     // This handles the case of enum fields where the #[ask(...)] is on the
                     // struct field but the enum generates a OneOf with an empty prompt.
                     let parent_prompt = question.ask();
-                    if !parent_prompt.is_empty() {
-                        if let Some(first) = nested.first_mut() {
-                            if first.prompt.is_empty() {
-                                first.prompt = parent_prompt.to_string();
-                            }
+                    if !parent_prompt.is_empty()
+                        && let Some(first) = nested.first_mut()
+                        && first.prompt.is_empty()
+                    {
+                        first.prompt = parent_prompt.to_string();
+                    } else if !question.path().as_str().is_empty() {
+                        // A nested struct, its questions form a section
+                        let section = Section::of(question, &path);
+                        for q in &mut nested {
+                            q.sections.insert(0, section.clone());
                         }
                     }
 
@@ -450,6 +957,121 @@ impl WizardState {
         flat
     }
 
+    /// Response path under which the current question's comment is stored.
+    fn comment_path(&self) -> Option<ResponsePath> {
+        self.current_question()
+            .filter(|q| q.allow_comment)
+            .map(|q| q.path.child(COMMENT_KEY))
+    }
+
+    /// Open the comment editor for the current question, prefilled with any earlier comment.
+    /// Choices of the leave dialog; saving a draft needs a checkpoint.
+    fn leave_choices(&self) -> Vec<LeaveChoice> {
+        if self.checkpoint.is_some() {
+            vec![
+                LeaveChoice::Discard,
+                LeaveChoice::SaveDraft,
+                LeaveChoice::Continue,
+            ]
+        } else {
+            vec![LeaveChoice::Discard, LeaveChoice::Continue]
+        }
+    }
+
+    /// Open the leave dialog with "Continue" highlighted, so a stray Esc and Enter
+    /// lose nothing.
+    fn open_leave_dialog(&mut self) {
+        self.leaving = Some(LeaveDialog {
+            highlighted: self.leave_choices().len() - 1,
+            error: None,
+        });
+    }
+
+    /// Handle a key in the leave dialog, given as the standard key and as pressed.
+    /// Returns whether the wizard is done.
+    fn handle_leave_key(&mut self, key: Option<KeyCode>, pressed: KeyCode) -> bool {
+        let choices = self.leave_choices();
+        let Some(dialog) = self.leaving.as_mut() else {
+            return false;
+        };
+        // First letters pick a choice directly
+        let shortcut = match pressed {
+            KeyCode::Char(c) => choices
+                .iter()
+                .copied()
+                .find(|choice| choice.label().starts_with(c.to_ascii_uppercase())),
+            _ => None,
+        };
+        let choice = match (shortcut, key) {
+            (Some(choice), _) => choice,
+            (None, Some(KeyCode::Left | KeyCode::Up)) => {
+                dialog.highlighted = dialog.highlighted.saturating_sub(1);
+                return false;
+            }
+            (None, Some(KeyCode::Right | KeyCode::Down | KeyCode::Tab)) => {
+                dialog.highlighted = (dialog.highlighted + 1).min(choices.len() - 1);
+                return false;
+            }
+            (None, Some(KeyCode::Enter)) => choices[dialog.highlighted],
+            (None, Some(KeyCode::Esc)) => LeaveChoice::Continue,
+            _ => return false,
+        };
+
+        match choice {
+            LeaveChoice::Discard => {
+                self.cancelled = true;
+                true
+            }
+            LeaveChoice::SaveDraft => {
+                let saved = match &self.checkpoint {
+                    Some(checkpoint) => checkpoint.save(&self.responses),
+                    None => Err("no checkpoint configured".to_string()),
+                };
+                match saved {
+                    Ok(()) => {
+                        self.cancelled = true;
+                        true
+                    }
+                    Err(err) => {
+                        dialog.error = Some(format!("Cannot save the draft: {err}"));
+                        false
+                    }
+                }
+            }
+            LeaveChoice::Continue => {
+                self.leaving = None;
+                false
+            }
+        }
+    }
+
+    /// Whether unbound keys are typed: always without a modal keymap, else in
+    /// typing mode and the comment editor.
+    fn typing(&self) -> bool {
+        !self.keymap.is_modal() || self.inserting || self.comment.is_some()
+    }
+
+    fn open_comment(&mut self) {
+        if let Some(q) = self.current_question().filter(|q| q.allow_comment) {
+            let existing = self.responses.comment(&q.path).unwrap_or_default();
+            self.comment = Some(existing.to_string());
+        }
+    }
+
+    /// Close the comment editor, storing the comment unless it is blank.
+    fn save_comment(&mut self) {
+        let (Some(comment), Some(path)) = (self.comment.take(), self.comment_path()) else {
+            return;
+        };
+        let comment = comment.trim();
+        if comment.is_empty() {
+            self.responses.remove(&path);
+        } else {
+            self.responses
+                .insert(path, ResponseValue::String(comment.to_string()));
+        }
+    }
+
     fn current_question(&self) -> Option<&FlatQuestion> {
         self.questions.get(self.current_index)
     }
@@ -458,35 +1080,198 @@ impl WizardState {
         (self.current_index + 1, self.questions.len())
     }
 
+    /// The innermost section of the current question with the position of the
+    /// question in it, e.g. `("Database configuration", 3, 7)`.
+    fn section_progress(&self) -> Option<(&str, usize, usize)> {
+        let current = self.current_question()?;
+        let section = current.sections.last()?;
+        let in_section = |q: &FlatQuestion| q.sections.contains(section);
+        let start = self.questions[..self.current_index]
+            .iter()
+            .rposition(|q| !in_section(q))
+            .map_or(0, |i| i + 1);
+        let len = self.questions[start..]
+            .iter()
+            .take_while(|q| in_section(q))
+            .count();
+        Some((&section.name, self.current_index - start + 1, len))
+    }
+
+    /// Options of the current select question that match the filter, best match first,
+    /// with the char indices of the matched characters. All options while nothing is typed.
+    fn visible_options(&self) -> Vec<(usize, Vec<usize>)> {
+        let Some(FlatQuestionKind::Select { options, .. }) =
+            self.current_question().map(|q| &q.kind)
+        else {
+            return Vec::new();
+        };
+        if self.filter.is_empty() {
+            return (0..options.len()).map(|i| (i, Vec::new())).collect();
+        }
+        let mut found: Vec<_> = options
+            .iter()
+            .enumerate()
+            .filter_map(|(i, option)| {
+                fuzzy_match(option, &self.filter).map(|found| (found.score, i, found.indices))
+            })
+            .collect();
+        // Stable sort keeps the original order for equal scores
+        found.sort_by_key(|(score, ..)| std::cmp::Reverse(*score));
+        found
+            .into_iter()
+            .map(|(_, i, indices)| (i, indices))
+            .collect()
+    }
+
+    /// Type into the filter of a select question and highlight the best match.
+    fn edit_filter(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char(c) => self.filter.push(c),
+            KeyCode::Backspace => {
+                self.filter.pop();
+            }
+            _ => return,
+        }
+        self.error_message = None;
+        if let Some((best, _)) = self.visible_options().first() {
+            self.selected_option = *best;
+        }
+    }
+
+    /// Move the highlight `offset` options down (or up, if negative) among the options
+    /// that match the filter, stopping at either end.
+    fn step_select(&mut self, offset: isize) {
+        let visible = self.visible_options();
+        let next = match visible.iter().position(|(i, _)| *i == self.selected_option) {
+            Some(pos) => visible.get(pos.saturating_add_signed(offset).min(visible.len() - 1)),
+            None => visible.first(),
+        };
+        if let Some((i, _)) = next {
+            self.selected_option = *i;
+        }
+    }
+
+    /// How many options of the current multi-select list are selected.
+    fn selected_count(&self) -> usize {
+        self.multi_selected
+            .iter()
+            .filter(|&&selected| selected)
+            .count()
+    }
+
+    /// Select all options (`a`), none (`A` or `n`) or invert the selection (`i`)
+    /// of a multi-select list. Selecting all stops at the list's maximum, inverting
+    /// may go over it and is then refused on submit.
+    fn select_in_bulk(&mut self, key: char) {
+        let Some(FlatQuestionKind::MultiSelect { options, max, .. }) =
+            self.current_question().map(|q| &q.kind)
+        else {
+            return;
+        };
+        let (len, max) = (options.len(), max.unwrap_or(usize::MAX));
+        self.multi_selected.resize(len, false);
+        match key {
+            'a' => {
+                let mut room = max.saturating_sub(self.selected_count());
+                for selected in self.multi_selected.iter_mut().filter(|s| !**s) {
+                    if room == 0 {
+                        break;
+                    }
+                    *selected = true;
+                    room -= 1;
+                }
+            }
+            'A' | 'n' => self.multi_selected.fill(false),
+            'i' => {
+                for selected in &mut self.multi_selected {
+                    *selected = !*selected;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Why the current multi-select selection cannot be submitted, checked while
+    /// it changes: too many options, or the field's validator rejects it.
+    fn selection_problem(
+        &self,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Option<String> {
+        let question = self.current_question()?;
+        let FlatQuestionKind::MultiSelect { max, .. } = &question.kind else {
+            return None;
+        };
+        let count = self.selected_count();
+        if let Some(max) = max.filter(|&max| count > max) {
+            return Some(format!("At most {max} can be selected, {count} are"));
+        }
+        let selected = self
+            .multi_selected
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &selected)| selected.then_some(i))
+            .collect();
+        validate(
+            &ResponseValue::ChosenVariants(selected),
+            &self.responses,
+            &parent_path(&question.path),
+        )
+        .err()
+    }
+
+    /// Move the highlight of a choice list one page down or up.
+    fn page_step(&mut self, down: bool) {
+        let offset = if down {
+            self.page_size as isize
+        } else {
+            -(self.page_size as isize)
+        };
+        match self.current_question().map(|q| &q.kind) {
+            Some(FlatQuestionKind::Select { .. }) => self.step_select(offset),
+            Some(FlatQuestionKind::MultiSelect { options, .. }) if !options.is_empty() => {
+                self.selected_option = self
+                    .selected_option
+                    .saturating_add_signed(offset)
+                    .min(options.len() - 1);
+            }
+            _ => {}
+        }
+    }
+
     fn handle_input(&mut self, key: KeyCode) {
+        // Left and right move through right-to-left answers the way they are shown
+        let key = match key {
+            KeyCode::Left if self.input_rtl() => KeyCode::Right,
+            KeyCode::Right if self.input_rtl() => KeyCode::Left,
+            key => key,
+        };
         match key {
+            // Editing the text resets the highlighted completion to the best match
             KeyCode::Char(c) => {
                 self.input.insert(self.cursor_pos, c);
-                self.cursor_pos += 1;
+                self.cursor_pos += c.len_utf8();
+                self.selected_option = 0;
                 self.error_message = None;
             }
-            KeyCode::Backspace => {
-                if self.cursor_pos > 0 {
-                    self.cursor_pos -= 1;
-                    self.input.remove(self.cursor_pos);
-                    self.error_message = None;
-                }
+            KeyCode::Backspace if self.cursor_pos > 0 => {
+                self.cursor_pos -= self.char_before_cursor();
+                self.input.remove(self.cursor_pos);
+                self.selected_option = 0;
+                self.error_message = None;
             }
-            KeyCode::Delete => {
-                if self.cursor_pos < self.input.len() {
-                    self.input.remove(self.cursor_pos);
-                    self.error_message = None;
-                }
+            KeyCode::Delete if self.cursor_pos < self.input.len() => {
+                self.input.remove(self.cursor_pos);
+                self.selected_option = 0;
+                self.error_message = None;
             }
-            KeyCode::Left => {
-                if self.cursor_pos > 0 {
-                    self.cursor_pos -= 1;
-                }
+            KeyCode::Left if self.cursor_pos > 0 => {
+                self.cursor_pos -= self.char_before_cursor();
             }
-            KeyCode::Right => {
-                if self.cursor_pos < self.input.len() {
-                    self.cursor_pos += 1;
-                }
+            KeyCode::Right if self.cursor_pos < self.input.len() => {
+                self.cursor_pos += self.input[self.cursor_pos..]
+                    .chars()
+                    .next()
+                    .map_or(0, char::len_utf8);
             }
             KeyCode::Home => {
                 self.cursor_pos = 0;
@@ -498,48 +1283,266 @@ impl WizardState {
         }
     }
 
-    fn validate_and_submit(
-        &mut self,
-        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
-    ) -> bool {
-        let Some(question) = self.current_question().cloned() else {
-            return false;
-        };
+    /// Byte length of the character before the cursor, which is a byte offset.
+    fn char_before_cursor(&self) -> usize {
+        self.input[..self.cursor_pos]
+            .chars()
+            .next_back()
+            .map_or(0, char::len_utf8)
+    }
 
-        // Use default if input is empty and default exists
-        let value = if self.input.is_empty() {
-            question.default_value.clone().unwrap_or_default()
-        } else {
-            self.input.clone()
-        };
+    /// Terminal column of the cursor within the input, counting wide characters
+    /// such as emoji and CJK as two, in the order the input is shown.
+    fn cursor_column(&self) -> u16 {
+        if !self.input_rtl() && !bidi::has_rtl(&self.input) {
+            return self.input[..self.cursor_pos].width() as u16;
+        }
+        let (start, line) = self.cursor_line();
+        bidi::cursor_column(line, self.cursor_pos - start, self.input_rtl())
+    }
 
-        // Remove the current field's old value before validation.
-        // This is important when going back and re-entering a value,
+    /// The line of the input holding the cursor, with the offset it starts at.
+    fn cursor_line(&self) -> (usize, &str) {
+        let start = self.input[..self.cursor_pos]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let end = self.input[self.cursor_pos..]
+            .find('\n')
+            .map_or(self.input.len(), |i| self.cursor_pos + i);
+        (start, &self.input[start..end])
+    }
+
+    /// Position of the cursor in an input box drawn in `area`. Right-to-left answers
+    /// are aligned to the right of the box.
+    fn input_cursor(&self, area: Rect) -> (u16, u16) {
+        let mut x = area.x + 1 + self.cursor_column();
+        if self.input_rtl() {
+            let (_, line) = self.cursor_line();
+            x += area
+                .width
+                .saturating_sub(2)
+                .saturating_sub(line.width() as u16);
+        }
+        (x, area.y + 1)
+    }
+
+    /// Whether the current question's prompt is written right to left.
+    fn rtl(&self) -> bool {
+        self.current_question()
+            .is_some_and(|q| self.direction.is_rtl(&q.prompt))
+    }
+
+    /// Whether the answer is typed right to left: in the direction of the prompt,
+    /// except for hex colors.
+    fn input_rtl(&self) -> bool {
+        self.rtl()
+            && !matches!(
+                self.current_question().map(|q| &q.kind),
+                Some(FlatQuestionKind::Color { .. })
+            )
+    }
+
+    /// Insert pasted text at once rather than key by key. Only multiline answers
+    /// keep line breaks; elsewhere they become spaces.
+    pub(crate) fn paste(&mut self, text: &str) {
+        if self.complete || self.leaving.is_some() {
+            return;
+        }
+        let multiline = matches!(
+            self.current_question().map(|q| &q.kind),
+            Some(FlatQuestionKind::Multiline)
+        ) && self.comment.is_none();
+        let text = text.trim_end_matches(['\r', '\n']).replace("\r\n", "\n");
+        let text = if multiline {
+            text
+        } else {
+            text.replace(['\r', '\n'], " ")
+        };
+
+        if let Some(comment) = self.comment.as_mut() {
+            comment.push_str(&text);
+            return;
+        }
+        match self.current_question().map(|q| &q.kind) {
+            Some(FlatQuestionKind::Select { .. }) => {
+                self.filter.push_str(&text);
+                self.error_message = None;
+                if let Some((best, _)) = self.visible_options().first() {
+                    self.selected_option = *best;
+                }
+            }
+            Some(
+                FlatQuestionKind::Confirm { .. }
+                | FlatQuestionKind::MultiSelect { .. }
+                | FlatQuestionKind::Rating { .. }
+                | FlatQuestionKind::Matrix { .. },
+            )
+            | None => {}
+            Some(_) => {
+                self.input.insert_str(self.cursor_pos, &text);
+                self.cursor_pos += text.len();
+                self.selected_option = 0;
+                self.error_message = None;
+            }
+        }
+    }
+
+    /// Load the row choices of a matrix question from its response or suggestion.
+    ///
+    /// The highlighted option is the current row.
+    fn load_matrix_choices(&mut self) {
+        let Some(FlatQuestion {
+            path,
+            kind: FlatQuestionKind::Matrix { question, defaults },
+            ..
+        }) = self.current_question()
+        else {
+            self.matrix_choices.clear();
+            return;
+        };
+
+        let answered = match self.responses.get(path) {
+            Some(ResponseValue::ChosenVariants(columns)) => columns.as_slice(),
+            _ => defaults.as_slice(),
+        };
+        let choices = (0..question.rows.len())
+            .map(|row| {
+                answered
+                    .get(row)
+                    .copied()
+                    .filter(|&column| column < question.columns.len())
+            })
+            .collect();
+        self.matrix_choices = choices;
+        self.selected_option = 0;
+    }
+
+    /// Move the choice of the highlighted matrix row one column left or right.
+    fn step_matrix_column(&mut self, forward: bool) {
+        let Some(FlatQuestionKind::Matrix { question, .. }) =
+            self.current_question().map(|q| &q.kind)
+        else {
+            return;
+        };
+        let last = question.columns.len().saturating_sub(1);
+        if let Some(choice) = self.matrix_choices.get_mut(self.selected_option) {
+            *choice = Some(match (*choice, forward) {
+                (None, _) => 0,
+                (Some(column), true) => (column + 1).min(last),
+                (Some(column), false) => column.saturating_sub(1),
+            });
+            self.error_message = None;
+        }
+    }
+
+    /// Replace the input with the highlighted completion of an autocomplete question.
+    fn accept_completion(&mut self) {
+        let Some(FlatQuestionKind::Autocomplete { question }) =
+            self.current_question().map(|q| &q.kind)
+        else {
+            return;
+        };
+        if let Some(completion) = question
+            .completions(&self.input)
+            .into_iter()
+            .nth(self.selected_option)
+        {
+            self.input = completion;
+            self.cursor_pos = self.input.len();
+            self.selected_option = 0;
+            self.error_message = None;
+        }
+    }
+
+    /// The findings of the text checker on `text`, unless they were shown for the same
+    /// text before and the user submitted it again.
+    fn review_text(&mut self, text: &str) -> Option<String> {
+        let checker = self.text_checker.as_ref()?;
+        if self.reviewed_text.as_deref() == Some(text) {
+            return None;
+        }
+        let issues = checker.check(text);
+        if issues.is_empty() {
+            return None;
+        }
+        self.reviewed_text = Some(text.to_string());
+        let findings: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        Some(format!(
+            "{} (submit again to keep the text)",
+            findings.join("; ")
+        ))
+    }
+
+    fn validate_and_submit(
+        &mut self,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> bool {
+        let Some(question) = self.current_question().cloned() else {
+            return false;
+        };
+
+        // A filtered select list can hide the highlighted option
+        if matches!(question.kind, FlatQuestionKind::Select { .. })
+            && !self
+                .visible_options()
+                .iter()
+                .any(|(i, _)| *i == self.selected_option)
+        {
+            self.error_message = Some("No option matches the filter".to_string());
+            return false;
+        }
+
+        // Use default if input is empty and default exists
+        let value = if self.input.is_empty() {
+            question.default_value.clone().unwrap_or_default()
+        } else {
+            self.input.clone()
+        };
+
+        // Remove the current field's old value before validation.
+        // This is important when going back and re-entering a value,
         // so cross-field validators don't count both old and new values.
         let old_value = self.responses.remove(&question.path);
 
         match &question.kind {
-            FlatQuestionKind::Input | FlatQuestionKind::Multiline | FlatQuestionKind::Masked => {
+            FlatQuestionKind::Input
+            | FlatQuestionKind::Autocomplete { .. }
+            | FlatQuestionKind::Multiline
+            | FlatQuestionKind::Masked => {
                 let rv = ResponseValue::String(value.clone());
                 // Run validation if field has it
-                if question.has_validation {
-                    if let Err(err) = validate(&rv, &self.responses, &question.path) {
-                        self.error_message = Some(err);
-                        // Restore old value on validation failure
-                        if let Some(old) = old_value {
-                            self.responses.insert(question.path.clone(), old);
-                        }
-                        return false;
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    // Restore old value on validation failure
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+                if matches!(question.kind, FlatQuestionKind::Multiline)
+                    && let Some(findings) = self.review_text(&value)
+                {
+                    self.error_message = Some(findings);
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
                     }
+                    return false;
                 }
                 self.responses.insert(question.path.clone(), rv);
             }
-            FlatQuestionKind::Int { min, max } => match value.parse::<i64>() {
+            FlatQuestionKind::Int {
+                min,
+                max,
+                format,
+                messages,
+            } => match format.parse_int(&value, messages) {
                 Ok(n) => {
                     if let Some(min_val) = min
                         && n < *min_val
                     {
-                        self.error_message = Some(format!("Value must be at least {}", min_val));
+                        self.error_message = Some(messages.at_least(&format.format_int(*min_val)));
                         // Restore old value on validation failure
                         if let Some(old) = old_value {
                             self.responses.insert(question.path.clone(), old);
@@ -549,7 +1552,7 @@ impl WizardState {
                     if let Some(max_val) = max
                         && n > *max_val
                     {
-                        self.error_message = Some(format!("Value must be at most {}", max_val));
+                        self.error_message = Some(messages.at_most(&format.format_int(*max_val)));
                         // Restore old value on validation failure
                         if let Some(old) = old_value {
                             self.responses.insert(question.path.clone(), old);
@@ -557,20 +1560,20 @@ impl WizardState {
                         return false;
                     }
                     let rv = ResponseValue::Int(n);
-                    if question.has_validation {
-                        if let Err(err) = validate(&rv, &self.responses, &question.path) {
-                            self.error_message = Some(err);
-                            // Restore old value on validation failure
-                            if let Some(old) = old_value {
-                                self.responses.insert(question.path.clone(), old);
-                            }
-                            return false;
+                    if question.has_validation
+                        && let Err(err) = validate(&rv, &self.responses, &question.path)
+                    {
+                        self.error_message = Some(err);
+                        // Restore old value on validation failure
+                        if let Some(old) = old_value {
+                            self.responses.insert(question.path.clone(), old);
                         }
+                        return false;
                     }
                     self.responses.insert(question.path.clone(), rv);
                 }
-                Err(_) => {
-                    self.error_message = Some("Please enter a valid integer".to_string());
+                Err(message) => {
+                    self.error_message = Some(message);
                     // Restore old value on validation failure
                     if let Some(old) = old_value {
                         self.responses.insert(question.path.clone(), old);
@@ -578,12 +1581,90 @@ impl WizardState {
                     return false;
                 }
             },
-            FlatQuestionKind::Float { min, max } => match value.parse::<f64>() {
+            FlatQuestionKind::Currency { question: currency } => {
+                let checked = currency
+                    .parse(&value)
+                    .and_then(|minor| currency.check_bounds(minor).map(|()| minor));
+                let minor = match checked {
+                    Ok(minor) => minor,
+                    Err(err) => {
+                        self.error_message = Some(err);
+                        // Restore old value on validation failure
+                        if let Some(old) = old_value {
+                            self.responses.insert(question.path.clone(), old);
+                        }
+                        return false;
+                    }
+                };
+                let rv = ResponseValue::Int(minor);
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    // Restore old value on validation failure
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+                self.responses.insert(question.path.clone(), rv);
+            }
+            FlatQuestionKind::Color { question: color } => {
+                let rv = match color.parse(&value) {
+                    Ok(parsed) => ResponseValue::String(parsed.to_hex()),
+                    Err(err) => {
+                        self.error_message = Some(err);
+                        // Restore old value on validation failure
+                        if let Some(old) = old_value {
+                            self.responses.insert(question.path.clone(), old);
+                        }
+                        return false;
+                    }
+                };
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    // Restore old value on validation failure
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+                self.responses.insert(question.path.clone(), rv);
+            }
+            FlatQuestionKind::Path { question: path_q } => {
+                let rv = ResponseValue::String(value.clone());
+                // Files and directories must exist before custom validation runs
+                let checked = path_q.check(&value).and_then(|()| {
+                    if question.has_validation {
+                        validate(&rv, &self.responses, &question.path)
+                    } else {
+                        Ok(())
+                    }
+                });
+                if let Err(err) = checked {
+                    self.error_message = Some(err);
+                    // Restore old value on validation failure
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+                self.responses.insert(question.path.clone(), rv);
+            }
+            FlatQuestionKind::Float {
+                min,
+                max,
+                format,
+                messages,
+            } => match format.parse_float(&value, messages) {
                 Ok(n) => {
                     if let Some(min_val) = min
                         && n < *min_val
                     {
-                        self.error_message = Some(format!("Value must be at least {}", min_val));
+                        self.error_message =
+                            Some(messages.at_least(&format.format_float(*min_val)));
                         // Restore old value on validation failure
                         if let Some(old) = old_value {
                             self.responses.insert(question.path.clone(), old);
@@ -593,7 +1674,7 @@ impl WizardState {
                     if let Some(max_val) = max
                         && n > *max_val
                     {
-                        self.error_message = Some(format!("Value must be at most {}", max_val));
+                        self.error_message = Some(messages.at_most(&format.format_float(*max_val)));
                         // Restore old value on validation failure
                         if let Some(old) = old_value {
                             self.responses.insert(question.path.clone(), old);
@@ -601,20 +1682,20 @@ impl WizardState {
                         return false;
                     }
                     let rv = ResponseValue::Float(n);
-                    if question.has_validation {
-                        if let Err(err) = validate(&rv, &self.responses, &question.path) {
-                            self.error_message = Some(err);
-                            // Restore old value on validation failure
-                            if let Some(old) = old_value {
-                                self.responses.insert(question.path.clone(), old);
-                            }
-                            return false;
+                    if question.has_validation
+                        && let Err(err) = validate(&rv, &self.responses, &question.path)
+                    {
+                        self.error_message = Some(err);
+                        // Restore old value on validation failure
+                        if let Some(old) = old_value {
+                            self.responses.insert(question.path.clone(), old);
                         }
+                        return false;
                     }
                     self.responses.insert(question.path.clone(), rv);
                 }
-                Err(_) => {
-                    self.error_message = Some("Please enter a valid number".to_string());
+                Err(message) => {
+                    self.error_message = Some(message);
                     // Restore old value on validation failure
                     if let Some(old) = old_value {
                         self.responses.insert(question.path.clone(), old);
@@ -627,115 +1708,180 @@ impl WizardState {
                 self.responses
                     .insert(question.path.clone(), ResponseValue::Bool(answer));
             }
-            FlatQuestionKind::List { element_kind } => {
+            FlatQuestionKind::Rating {
+                question: rating_q, ..
+            } => {
+                let rv = ResponseValue::Int(rating_q.min + self.selected_option as i64);
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    // Restore old value on validation failure
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+                self.responses.insert(question.path.clone(), rv);
+            }
+            FlatQuestionKind::Matrix { .. } => {
+                let Some(columns) = self
+                    .matrix_choices
+                    .iter()
+                    .copied()
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    self.error_message = Some("Please answer every row".to_string());
+                    // Restore old value on validation failure
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                };
+                let rv = ResponseValue::ChosenVariants(columns);
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    // Restore old value on validation failure
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+                self.responses.insert(question.path.clone(), rv);
+            }
+            FlatQuestionKind::List {
+                element_kind,
+                messages,
+            } => {
                 // Parse the input as a list (comma or newline separated)
                 let items: Vec<&str> = self
                     .input
-                    .split(|c| c == ',' || c == '\n')
+                    .split([',', '\n'])
                     .map(|s| s.trim())
                     .filter(|s| !s.is_empty())
                     .collect();
 
-                let rv = match element_kind {
-                    ListElementKind::String => {
-                        ResponseValue::StringList(items.iter().map(|s| s.to_string()).collect())
-                    }
-                    ListElementKind::Int { min, max } => {
-                        let mut ints = Vec::new();
-                        for item in &items {
-                            match item.parse::<i64>() {
-                                Ok(n) => {
-                                    if let Some(min_val) = min {
-                                        if n < *min_val {
-                                            self.error_message = Some(format!(
-                                                "Value {} must be at least {}",
-                                                n, min_val
+                let rv =
+                    match element_kind {
+                        ListElementKind::String => {
+                            ResponseValue::StringList(items.iter().map(|s| s.to_string()).collect())
+                        }
+                        ListElementKind::Int { min, max } => {
+                            let mut ints = Vec::new();
+                            for item in &items {
+                                match item.parse::<i64>() {
+                                    Ok(n) => {
+                                        if let Some(min_val) = min
+                                            && n < *min_val
+                                        {
+                                            self.error_message = Some(messages.item_at_least(
+                                                &n.to_string(),
+                                                &min_val.to_string(),
                                             ));
                                             if let Some(old) = old_value {
                                                 self.responses.insert(question.path.clone(), old);
                                             }
                                             return false;
                                         }
-                                    }
-                                    if let Some(max_val) = max {
-                                        if n > *max_val {
-                                            self.error_message = Some(format!(
-                                                "Value {} must be at most {}",
-                                                n, max_val
+                                        if let Some(max_val) = max
+                                            && n > *max_val
+                                        {
+                                            self.error_message = Some(messages.item_at_most(
+                                                &n.to_string(),
+                                                &max_val.to_string(),
                                             ));
                                             if let Some(old) = old_value {
                                                 self.responses.insert(question.path.clone(), old);
                                             }
                                             return false;
                                         }
+                                        ints.push(n);
                                     }
-                                    ints.push(n);
-                                }
-                                Err(_) => {
-                                    self.error_message =
-                                        Some(format!("'{}' is not a valid integer", item));
-                                    if let Some(old) = old_value {
-                                        self.responses.insert(question.path.clone(), old);
+                                    Err(_) => {
+                                        self.error_message =
+                                            Some(messages.item_not_integer(&format!("'{item}'")));
+                                        if let Some(old) = old_value {
+                                            self.responses.insert(question.path.clone(), old);
+                                        }
+                                        return false;
                                     }
-                                    return false;
                                 }
                             }
+                            ResponseValue::IntList(ints)
                         }
-                        ResponseValue::IntList(ints)
-                    }
-                    ListElementKind::Float { min, max } => {
-                        let mut floats = Vec::new();
-                        for item in &items {
-                            match item.parse::<f64>() {
-                                Ok(n) => {
-                                    if let Some(min_val) = min {
-                                        if n < *min_val {
-                                            self.error_message = Some(format!(
-                                                "Value {} must be at least {}",
-                                                n, min_val
+                        ListElementKind::Float { min, max } => {
+                            let mut floats = Vec::new();
+                            for item in &items {
+                                match item.parse::<f64>() {
+                                    Ok(n) => {
+                                        if let Some(min_val) = min
+                                            && n < *min_val
+                                        {
+                                            self.error_message = Some(messages.item_at_least(
+                                                &n.to_string(),
+                                                &min_val.to_string(),
                                             ));
                                             if let Some(old) = old_value {
                                                 self.responses.insert(question.path.clone(), old);
                                             }
                                             return false;
                                         }
-                                    }
-                                    if let Some(max_val) = max {
-                                        if n > *max_val {
-                                            self.error_message = Some(format!(
-                                                "Value {} must be at most {}",
-                                                n, max_val
+                                        if let Some(max_val) = max
+                                            && n > *max_val
+                                        {
+                                            self.error_message = Some(messages.item_at_most(
+                                                &n.to_string(),
+                                                &max_val.to_string(),
                                             ));
                                             if let Some(old) = old_value {
                                                 self.responses.insert(question.path.clone(), old);
                                             }
                                             return false;
                                         }
+                                        floats.push(n);
                                     }
-                                    floats.push(n);
-                                }
-                                Err(_) => {
-                                    self.error_message =
-                                        Some(format!("'{}' is not a valid number", item));
-                                    if let Some(old) = old_value {
-                                        self.responses.insert(question.path.clone(), old);
+                                    Err(_) => {
+                                        self.error_message =
+                                            Some(messages.item_not_number(&format!("'{item}'")));
+                                        if let Some(old) = old_value {
+                                            self.responses.insert(question.path.clone(), old);
+                                        }
+                                        return false;
                                     }
-                                    return false;
                                 }
                             }
+                            ResponseValue::FloatList(floats)
                         }
-                        ResponseValue::FloatList(floats)
-                    }
-                };
+                    };
 
-                if question.has_validation {
-                    if let Err(err) = validate(&rv, &self.responses, &question.path) {
-                        self.error_message = Some(err);
-                        if let Some(old) = old_value {
-                            self.responses.insert(question.path.clone(), old);
-                        }
-                        return false;
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
                     }
+                    return false;
+                }
+                self.responses.insert(question.path.clone(), rv);
+            }
+            FlatQuestionKind::Select {
+                values: Some(values),
+                ..
+            } => {
+                // Runtime choices: store the chosen option's value
+                let Some(value) = values.get(self.selected_option) else {
+                    self.error_message = Some("No choices available".to_string());
+                    return false;
+                };
+                let rv = ResponseValue::String(value.clone());
+                if question.has_validation
+                    && let Err(err) = validate(&rv, &self.responses, &question.path)
+                {
+                    self.error_message = Some(err);
+                    return false;
                 }
                 self.responses.insert(question.path.clone(), rv);
             }
@@ -755,14 +1901,11 @@ impl WizardState {
                     old_variant_idx.is_some() && old_variant_idx != Some(self.selected_option);
 
                 // Remove any existing dynamically-inserted questions for this enum
-                let base_path_str = base_path.as_str();
-                let current_path_str = question.path.as_str();
-
                 let i = self.current_index + 1;
                 while i < self.questions.len() {
-                    let q_path = self.questions[i].path.as_str();
-                    // Remove if path starts with base_path but is not the select question itself
-                    if q_path.starts_with(base_path_str) && q_path != current_path_str {
+                    let q_path = &self.questions[i].path;
+                    // Remove if path is below base_path but is not the select question itself
+                    if q_path.is_within(&base_path) && *q_path != question.path {
                         // Only remove responses if changing to a different variant
                         if changing_variant {
                             self.responses.remove(&self.questions[i].path);
@@ -789,9 +1932,12 @@ impl WizardState {
                         QuestionKind::AllOf(all_of) => {
                             let variant_questions =
                                 Self::flatten_questions(all_of.questions(), &base_path);
+                            let sections =
+                                variant_sections(&question.sections, selected_variant, &base_path);
                             if !variant_questions.is_empty() {
                                 let insert_pos = self.current_index + 1;
-                                for (i, q) in variant_questions.into_iter().enumerate() {
+                                for (i, mut q) in variant_questions.into_iter().enumerate() {
+                                    q.sections.splice(0..0, sections.iter().cloned());
                                     self.questions.insert(insert_pos + i, q);
                                 }
                             }
@@ -807,22 +1953,51 @@ impl WizardState {
                                     prompt: format!("Enter {} value:", selected_variant.name),
                                     kind: match &selected_variant.kind {
                                         QuestionKind::Input(_) => FlatQuestionKind::Input,
+                                        QuestionKind::Autocomplete(aq) => {
+                                            FlatQuestionKind::Autocomplete {
+                                                question: aq.clone(),
+                                            }
+                                        }
                                         QuestionKind::Int(iq) => FlatQuestionKind::Int {
                                             min: iq.min,
                                             max: iq.max,
+                                            format: iq.number_format(),
+                                            messages: iq.messages.clone(),
                                         },
                                         QuestionKind::Float(fq) => FlatQuestionKind::Float {
                                             min: fq.min,
                                             max: fq.max,
+                                            format: fq.number_format(),
+                                            messages: fq.messages.clone(),
+                                        },
+                                        QuestionKind::Currency(cq) => FlatQuestionKind::Currency {
+                                            question: cq.clone(),
+                                        },
+                                        QuestionKind::Color(cq) => FlatQuestionKind::Color {
+                                            question: cq.clone(),
+                                        },
+                                        QuestionKind::Path(pq) => FlatQuestionKind::Path {
+                                            question: pq.clone(),
                                         },
                                         QuestionKind::Confirm(cq) => FlatQuestionKind::Confirm {
                                             default: cq.default,
                                         },
+                                        QuestionKind::Rating(rq) => FlatQuestionKind::Rating {
+                                            question: rq.clone(),
+                                            default_idx: rq
+                                                .default
+                                                .and_then(|value| rating_index(rq, value))
+                                                .unwrap_or(0),
+                                        },
                                         _ => FlatQuestionKind::Input,
                                     },
                                     default_value: None,
                                     assumed: None,
                                     has_validation: false,
+                                    allow_comment: false,
+                                    help: None,
+                                    constraints: constraints(&selected_variant.kind),
+                                    sections: question.sections.clone(),
                                 };
                                 self.questions.insert(self.current_index + 1, variant_q);
                             }
@@ -831,6 +2006,14 @@ impl WizardState {
                 }
             }
             FlatQuestionKind::MultiSelect { variants, .. } => {
+                if let Some(problem) = self.selection_problem(validate) {
+                    self.error_message = Some(problem);
+                    if let Some(old) = old_value {
+                        self.responses.insert(question.path.clone(), old);
+                    }
+                    return false;
+                }
+
                 // Get base path (strip selected_variants suffix)
                 let base_path = parent_path(&question.path);
 
@@ -859,14 +2042,11 @@ impl WizardState {
                 let selections_changed = old_indices != selected_indices;
 
                 // Remove any existing dynamically-inserted questions for this multiselect
-                let base_path_str = base_path.as_str();
-                let current_path_str = question.path.as_str();
-
                 let i = self.current_index + 1;
                 while i < self.questions.len() {
-                    let q_path = self.questions[i].path.as_str();
-                    // Remove if path starts with base_path but is not the multiselect question itself
-                    if q_path.starts_with(base_path_str) && q_path != current_path_str {
+                    let q_path = &self.questions[i].path;
+                    // Remove if path is below base_path but is not the multiselect question itself
+                    if q_path.is_within(&base_path) && *q_path != question.path {
                         // Only remove responses if selections changed
                         if selections_changed {
                             self.responses.remove(&self.questions[i].path);
@@ -877,6 +2057,17 @@ impl WizardState {
                     }
                 }
 
+                // Forget which variant the items of the old selection were
+                if selections_changed {
+                    for item_idx in 0..old_indices.len() {
+                        self.responses.remove(
+                            &base_path
+                                .child(&item_idx.to_string())
+                                .child(SELECTED_VARIANT_KEY),
+                        );
+                    }
+                }
+
                 self.responses.insert(
                     question.path.clone(),
                     ResponseValue::ChosenVariants(selected_indices.clone()),
@@ -899,7 +2090,10 @@ impl WizardState {
                             if let QuestionKind::AllOf(all_of) = &variant.kind {
                                 let variant_questions =
                                     Self::flatten_questions(all_of.questions(), &item_path);
-                                for q in variant_questions {
+                                let sections =
+                                    variant_sections(&question.sections, variant, &item_path);
+                                for mut q in variant_questions {
+                                    q.sections.splice(0..0, sections.iter().cloned());
                                     self.questions.insert(self.current_index + insert_offset, q);
                                     insert_offset += 1;
                                 }
@@ -913,13 +2107,353 @@ impl WizardState {
         true
     }
 
-    fn next_question(
+    /// Move to the first question that is not assumed and load its defaults.
+    pub(crate) fn start(&mut self) {
+        while self.current_index < self.questions.len() {
+            if let Some(assumed) = &self.questions[self.current_index].assumed {
+                self.responses.insert(
+                    self.questions[self.current_index].path.clone(),
+                    assumed.clone(),
+                );
+                self.current_index += 1;
+            } else {
+                // Initialize first question's defaults
+                // Extract values first to avoid borrow issues
+                let init_data = self.current_question().map(|q| match &q.kind {
+                    FlatQuestionKind::Confirm { default } => {
+                        (Some(if *default { 0 } else { 1 }), None, None)
+                    }
+                    FlatQuestionKind::Select { default_idx, .. }
+                    | FlatQuestionKind::Rating { default_idx, .. } => {
+                        (Some(*default_idx), None, None)
+                    }
+                    FlatQuestionKind::MultiSelect {
+                        options, defaults, ..
+                    } => {
+                        let mut selected = vec![false; options.len()];
+                        for &idx in defaults {
+                            if idx < selected.len() {
+                                selected[idx] = true;
+                            }
+                        }
+                        (None, Some(selected), None)
+                    }
+                    _ => (None, None, q.default_value.clone()),
+                });
+
+                if let Some((selected_opt, multi_sel, default_val)) = init_data {
+                    if let Some(sel) = selected_opt {
+                        self.selected_option = sel;
+                    }
+                    if let Some(multi) = multi_sel {
+                        self.multi_selected = multi;
+                    }
+                    if let Some(def) = default_val {
+                        self.input = def;
+                        self.cursor_pos = self.input.len();
+                    }
+                }
+                self.load_matrix_choices();
+                break;
+            }
+        }
+
+        if self.current_index >= self.questions.len() {
+            self.complete = true;
+        }
+    }
+
+    /// Handle a key press. Returns whether the wizard is done: cancelled, or
+    /// confirmed on the completion screen.
+    pub(crate) fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> bool {
+        if key.kind != KeyEventKind::Press {
+            return false;
+        }
+
+        // Raw mode swallows the interrupt signal, so Ctrl+C cancels right away
+        if !self.complete
+            && key.code == KeyCode::Char('c')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+        {
+            self.cancelled = true;
+            return true;
+        }
+
+        // F2 switches high-contrast mode for this wizard only
+        if key.code == KeyCode::F(2) {
+            self.set_high_contrast(!self.high_contrast);
+            return false;
+        }
+
+        // F1 opens the help overlay, and any key closes it again
+        if self.show_help {
+            self.show_help = false;
+            return false;
+        }
+        if key.code == KeyCode::F(1) && !self.complete && self.leaving.is_none() {
+            self.show_help = true;
+            return false;
+        }
+
+        // Map the key through the keymap onto the standard keys handled below
+        let resolved = self.keymap.resolve(&key, self.typing());
+        if self.leaving.is_some() {
+            let standard = resolved.map(|resolved| resolved.key().code);
+            return self.handle_leave_key(standard, key.code);
+        }
+
+        // Multi-select lists take no text, so letters select in bulk there
+        if !self.complete
+            && self.comment.is_none()
+            && !key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            && let KeyCode::Char(c @ ('a' | 'A' | 'n' | 'i')) = key.code
+            && matches!(
+                self.current_question().map(|q| &q.kind),
+                Some(FlatQuestionKind::MultiSelect { .. })
+            )
+        {
+            self.select_in_bulk(c);
+            self.error_message = self.selection_problem(validate);
+            return false;
+        }
+
+        let key = match resolved {
+            Some(Resolved::Action(Action::Insert)) => {
+                self.inserting = true;
+                return false;
+            }
+            Some(Resolved::Action(Action::Normal)) if self.comment.is_none() => {
+                self.inserting = false;
+                return false;
+            }
+            Some(resolved) => resolved.key(),
+            None => return false,
+        };
+
+        if self.complete {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => return true,
+                _ => {}
+            }
+        } else if let Some(comment) = self.comment.as_mut() {
+            match key.code {
+                KeyCode::Enter => self.save_comment(),
+                KeyCode::Esc => self.comment = None,
+                KeyCode::Backspace => {
+                    comment.pop();
+                }
+                KeyCode::Char(c) => comment.push(c),
+                _ => {}
+            }
+        } else {
+            match key.code {
+                KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.open_comment();
+                }
+                KeyCode::Esc => {
+                    self.open_leave_dialog();
+                }
+                KeyCode::Enter => {
+                    self.next_question(validate);
+                }
+                KeyCode::Tab => {
+                    self.accept_completion();
+                }
+                KeyCode::Up | KeyCode::Down
+                    if matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Select { .. })
+                    ) =>
+                {
+                    self.step_select(if key.code == KeyCode::Down { 1 } else { -1 });
+                }
+                KeyCode::Char(_) | KeyCode::Backspace
+                    if !key.modifiers.contains(KeyModifiers::CONTROL)
+                        && matches!(
+                            self.current_question().map(|q| &q.kind),
+                            Some(FlatQuestionKind::Select { .. })
+                        ) =>
+                {
+                    // Typing filters the options
+                    self.edit_filter(key.code);
+                }
+                KeyCode::PageUp | KeyCode::PageDown => {
+                    self.page_step(key.code == KeyCode::PageDown);
+                }
+                KeyCode::Up => {
+                    if matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Confirm { .. })
+                            | Some(FlatQuestionKind::MultiSelect { .. })
+                            | Some(FlatQuestionKind::Matrix { .. })
+                            | Some(FlatQuestionKind::Autocomplete { .. })
+                    ) && self.selected_option > 0
+                    {
+                        self.selected_option -= 1;
+                    }
+                }
+                KeyCode::Down => {
+                    if let Some(q) = self.current_question() {
+                        match &q.kind {
+                            FlatQuestionKind::Confirm { .. } if self.selected_option < 1 => {
+                                self.selected_option += 1;
+                            }
+                            FlatQuestionKind::MultiSelect { options, .. }
+                                if self.selected_option < options.len() - 1 =>
+                            {
+                                self.selected_option += 1;
+                            }
+                            FlatQuestionKind::Matrix { question, .. }
+                                if self.selected_option + 1 < question.rows.len() =>
+                            {
+                                self.selected_option += 1;
+                            }
+                            FlatQuestionKind::Autocomplete { question }
+                                if self.selected_option + 1
+                                    < question.completions(&self.input).len() =>
+                            {
+                                self.selected_option += 1;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                KeyCode::Char(' ') => {
+                    // Space toggles selection in multi-select
+                    if let Some(FlatQuestionKind::MultiSelect { options, max, .. }) =
+                        self.current_question().map(|q| &q.kind)
+                    {
+                        let (len, max) = (options.len(), *max);
+                        // Ensure multi_selected is properly sized
+                        if self.multi_selected.len() != len {
+                            self.multi_selected = vec![false; len];
+                        }
+                        if self.selected_option < len {
+                            let selecting = !self.multi_selected[self.selected_option];
+                            if let Some(max) =
+                                max.filter(|&max| selecting && self.selected_count() >= max)
+                            {
+                                self.error_message = Some(format!("At most {max} can be selected"));
+                            } else {
+                                self.multi_selected[self.selected_option] = selecting;
+                                self.error_message = self.selection_problem(validate);
+                            }
+                        }
+                    } else {
+                        // For other question types, treat space as regular input
+                        self.handle_input(key.code);
+                    }
+                }
+                KeyCode::Backspace => {
+                    // For text input questions, backspace deletes or goes back when empty
+                    // For Select/MultiSelect/Confirm, backspace does nothing (use Left to go back)
+                    let is_selection_question = matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Confirm { .. })
+                            | Some(FlatQuestionKind::Select { .. })
+                            | Some(FlatQuestionKind::MultiSelect { .. })
+                            | Some(FlatQuestionKind::Rating { .. })
+                            | Some(FlatQuestionKind::Matrix { .. })
+                    );
+
+                    if !is_selection_question {
+                        if self.input.is_empty() && self.current_index > 0 {
+                            // For text input, backspace goes back only when empty
+                            self.prev_question();
+                        } else {
+                            // Otherwise, handle as normal backspace in text
+                            self.handle_input(key.code);
+                        }
+                    }
+                    // For selection questions, backspace does nothing
+                }
+                KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    // Ctrl+Left always goes back to previous question
+                    if self.current_index > 0 {
+                        self.prev_question();
+                    }
+                }
+                KeyCode::Left
+                    if matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Rating { .. })
+                    ) =>
+                {
+                    self.selected_option = self.selected_option.saturating_sub(1);
+                }
+                KeyCode::Left
+                    if matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Matrix { .. })
+                    ) =>
+                {
+                    self.step_matrix_column(false);
+                }
+                KeyCode::Right => {
+                    // Right arrow moves along a rating scale or matrix row, or the
+                    // cursor in text input
+                    if let Some(FlatQuestionKind::Rating { question, .. }) =
+                        self.current_question().map(|q| &q.kind)
+                    {
+                        if self.selected_option + 1 < question.points().count() {
+                            self.selected_option += 1;
+                        }
+                    } else if matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Matrix { .. })
+                    ) {
+                        self.step_matrix_column(true);
+                    } else {
+                        self.handle_input(key.code);
+                    }
+                }
+                KeyCode::Left => {
+                    // Left arrow moves cursor in text input, does nothing for selection
+                    let is_selection_question = matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Confirm { .. })
+                            | Some(FlatQuestionKind::Select { .. })
+                            | Some(FlatQuestionKind::MultiSelect { .. })
+                            | Some(FlatQuestionKind::Rating { .. })
+                            | Some(FlatQuestionKind::Matrix { .. })
+                    );
+
+                    if !is_selection_question {
+                        self.handle_input(key.code);
+                    }
+                }
+                _ => {
+                    if !matches!(
+                        self.current_question().map(|q| &q.kind),
+                        Some(FlatQuestionKind::Confirm { .. })
+                            | Some(FlatQuestionKind::Select { .. })
+                            | Some(FlatQuestionKind::MultiSelect { .. })
+                            | Some(FlatQuestionKind::Rating { .. })
+                            | Some(FlatQuestionKind::Matrix { .. })
+                    ) {
+                        self.handle_input(key.code);
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn next_question(
         &mut self,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) {
         if self.validate_and_submit(validate) {
             self.current_index += 1;
             self.input.clear();
+            self.filter.clear();
+            self.inserting = false;
             self.cursor_pos = 0;
             self.selected_option = 0;
             self.multi_selected.clear();
@@ -947,9 +2481,28 @@ impl WizardState {
                                     self.selected_option = if *default { 0 } else { 1 };
                                 }
                             }
-                            FlatQuestionKind::Select { default_idx, .. } => {
+                            FlatQuestionKind::Rating {
+                                question,
+                                default_idx,
+                            } => {
+                                self.selected_option = match existing_response {
+                                    Some(ResponseValue::Int(n)) => rating_index(question, n),
+                                    _ => None,
+                                }
+                                .unwrap_or(*default_idx);
+                            }
+                            FlatQuestionKind::Select {
+                                default_idx,
+                                values,
+                                ..
+                            } => {
                                 if let Some(ResponseValue::ChosenVariant(idx)) = existing_response {
                                     self.selected_option = idx;
+                                } else if let (Some(ResponseValue::String(s)), Some(values)) =
+                                    (&existing_response, values)
+                                    && let Some(idx) = values.iter().position(|v| v == s)
+                                {
+                                    self.selected_option = idx;
                                 } else {
                                     self.selected_option = *default_idx;
                                 }
@@ -1010,6 +2563,7 @@ impl WizardState {
                     break;
                 }
             }
+            self.load_matrix_choices();
 
             if self.current_index >= self.questions.len() {
                 self.complete = true;
@@ -1021,76 +2575,110 @@ impl WizardState {
         if self.current_index > 0 {
             self.current_index -= 1;
             self.input.clear();
+            self.filter.clear();
+            self.inserting = false;
             self.cursor_pos = 0;
             self.multi_selected.clear();
             self.error_message = None;
 
             // Restore previous response as input
-            if let Some(q) = self.current_question() {
-                if let Some(prev_response) = self.responses.get(&q.path) {
-                    match prev_response {
-                        ResponseValue::String(s) => {
+            if let Some(q) = self.current_question()
+                && let Some(prev_response) = self.responses.get(&q.path)
+            {
+                match prev_response {
+                    ResponseValue::String(s) => {
+                        if let FlatQuestionKind::Select {
+                            values: Some(values),
+                            ..
+                        } = &q.kind
+                        {
+                            self.selected_option =
+                                values.iter().position(|v| v == s).unwrap_or_default();
+                        } else {
                             self.input = s.clone();
                             self.cursor_pos = self.input.len();
                         }
-                        ResponseValue::Int(n) => {
-                            self.input = n.to_string();
-                            self.cursor_pos = self.input.len();
-                        }
-                        ResponseValue::Float(n) => {
+                    }
+                    ResponseValue::Int(n) => {
+                        if let FlatQuestionKind::Rating { question, .. } = &q.kind {
+                            self.selected_option = rating_index(question, *n).unwrap_or_default();
+                        } else {
                             self.input = n.to_string();
                             self.cursor_pos = self.input.len();
                         }
-                        ResponseValue::Bool(b) => {
-                            self.selected_option = if *b { 0 } else { 1 };
-                        }
-                        ResponseValue::ChosenVariant(idx) => {
-                            self.selected_option = *idx;
-                        }
-                        ResponseValue::ChosenVariants(indices) => {
-                            if let FlatQuestionKind::MultiSelect { options, .. } = &q.kind {
-                                self.multi_selected = vec![false; options.len()];
-                                for &idx in indices {
-                                    if idx < self.multi_selected.len() {
-                                        self.multi_selected[idx] = true;
-                                    }
+                    }
+                    ResponseValue::Float(n) => {
+                        self.input = n.to_string();
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::Bool(b) => {
+                        self.selected_option = if *b { 0 } else { 1 };
+                    }
+                    ResponseValue::ChosenVariant(idx) => {
+                        self.selected_option = *idx;
+                    }
+                    ResponseValue::ChosenVariants(indices) => {
+                        if let FlatQuestionKind::MultiSelect { options, .. } = &q.kind {
+                            self.multi_selected = vec![false; options.len()];
+                            for &idx in indices {
+                                if idx < self.multi_selected.len() {
+                                    self.multi_selected[idx] = true;
                                 }
                             }
                         }
-                        ResponseValue::StringList(list) => {
-                            self.input = list.join(", ");
-                            self.cursor_pos = self.input.len();
-                        }
-                        ResponseValue::IntList(list) => {
-                            self.input = list
-                                .iter()
-                                .map(|n| n.to_string())
-                                .collect::<Vec<_>>()
-                                .join(", ");
-                            self.cursor_pos = self.input.len();
-                        }
-                        ResponseValue::FloatList(list) => {
-                            self.input = list
-                                .iter()
-                                .map(|n| n.to_string())
-                                .collect::<Vec<_>>()
-                                .join(", ");
-                            self.cursor_pos = self.input.len();
-                        }
+                    }
+                    ResponseValue::StringList(list) => {
+                        self.input = list.join(", ");
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::IntList(list) => {
+                        self.input = list
+                            .iter()
+                            .map(|n| n.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        self.cursor_pos = self.input.len();
+                    }
+                    ResponseValue::FloatList(list) => {
+                        self.input = list
+                            .iter()
+                            .map(|n| n.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        self.cursor_pos = self.input.len();
                     }
                 }
             }
+            self.load_matrix_choices();
         }
     }
 }
 
-fn draw_ui(frame: &mut Frame, state: &WizardState) {
-    let area = frame.area();
+/// Fill the frame with the theme's background; high-contrast mode also makes all text bold.
+fn draw_background(frame: &mut Frame, state: &WizardState, area: Rect) {
+    let mut style = Style::default().bg(state.theme.background);
+    if state.high_contrast {
+        style = style.bold();
+    }
+    frame.render_widget(Block::default().style(style), area);
+}
+
+/// Draw the wizard, or the completion screen once all questions are answered.
+pub(crate) fn draw(frame: &mut Frame, state: &WizardState, area: Rect) {
+    if state.complete {
+        draw_completion(frame, state, area);
+    } else {
+        draw_ui(frame, state, area);
+    }
+}
+
+fn draw_ui(frame: &mut Frame, state: &WizardState, area: Rect) {
+    draw_background(frame, state, area);
 
     // Main layout
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .margin(1)
+        .margin(if state.high_contrast { 2 } else { 1 })
         .constraints([
             Constraint::Length(3), // Header
             Constraint::Length(2), // Progress bar
@@ -1112,12 +2700,19 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
 
     // Progress - thin bar with text
     let (current, total) = state.progress();
-    let progress_text = format!(" {} / {} ", current, total);
+    let progress_text = match state.section_progress() {
+        Some((section, position, len)) => {
+            format!(" {section} — {position}/{len}  ·  {current} / {total} ")
+        }
+        None => format!(" {} / {} ", current, total),
+    };
 
     // Create a horizontal layout for the progress area
     let progress_area = chunks[1];
     let bar_width = progress_area.width.saturating_sub(2); // Leave margin
-    let text_width = progress_text.len() as u16;
+    let progress_text = truncate_to_width(&progress_text, bar_width as usize);
+    let progress_text = bidi::display_order(&progress_text, false);
+    let text_width = progress_text.width() as u16;
 
     // Calculate the filled portion
     let ratio = current as f32 / total as f32;
@@ -1136,7 +2731,13 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
     if filled_width > 0 {
         let filled = "━".repeat(filled_width as usize);
         let filled_widget = Paragraph::new(filled).style(Style::default().fg(state.theme.primary));
-        frame.render_widget(filled_widget, Rect::new(bar_x, bar_y, filled_width, 1));
+        // A mirrored bar fills from the right
+        let filled_x = if state.mirrored {
+            bar_x + bar_width - filled_width
+        } else {
+            bar_x
+        };
+        frame.render_widget(filled_widget, Rect::new(filled_x, bar_y, filled_width, 1));
     }
 
     // Progress text centered below the bar
@@ -1145,28 +2746,53 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
         Paragraph::new(progress_text).style(Style::default().fg(state.theme.secondary));
     frame.render_widget(text_widget, Rect::new(text_x, bar_y + 1, text_width, 1));
 
-    // Content area
+    // Content area, the prompt grows with its wrapped lines
+    let prompt_width = chunks[2].width.saturating_sub(2);
+    let prompt_lines = state
+        .current_question()
+        .map(|q| match prompt_text(state, &q.prompt, prompt_width) {
+            Some(lines) => lines.len() as u16,
+            None => wrapped_lines(&q.prompt, prompt_width),
+        })
+        .unwrap_or(1)
+        .min(MAX_PROMPT_LINES);
     let content_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Question prompt
-            Constraint::Min(5),    // Input area
-            Constraint::Length(2), // Error message
+            Constraint::Length(prompt_lines + 2), // Question prompt
+            Constraint::Min(5),                   // Input area
+            Constraint::Length(2),                // Error message
         ])
         .split(chunks[2]);
 
     if let Some(question) = state.current_question() {
-        // Question prompt
-        let prompt = Paragraph::new(question.prompt.clone())
-            .style(Style::default().fg(state.theme.text))
-            .wrap(Wrap { trim: true })
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(state.theme.primary))
-                    .title(" Question ")
-                    .title_style(Style::default().fg(state.theme.highlight)),
+        // Question prompt, titled with the breadcrumb of nested sections
+        let title = if question.sections.is_empty() {
+            " Question ".to_string()
+        } else {
+            let breadcrumb = question
+                .sections
+                .iter()
+                .map(|section| section.name.as_str())
+                .collect::<Vec<_>>()
+                .join(" › ");
+            let fitted = truncate_to_width(
+                &breadcrumb,
+                content_chunks[0].width.saturating_sub(4) as usize,
             );
+            format!(" {fitted} ")
+        };
+        let prompt = match prompt_text(state, &question.prompt, prompt_width) {
+            Some(lines) => Paragraph::new(lines.join("\n")).alignment(bidi::alignment(state.rtl())),
+            None => Paragraph::new(question.prompt.clone()).wrap(Wrap { trim: true }),
+        };
+        let prompt = prompt.style(Style::default().fg(state.theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(state.theme.primary))
+                .title(title)
+                .title_style(Style::default().fg(state.theme.highlight)),
+        );
         frame.render_widget(prompt, content_chunks[0]);
 
         // Input area based on question type
@@ -1174,15 +2800,29 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
             FlatQuestionKind::Input
             | FlatQuestionKind::Multiline
             | FlatQuestionKind::Int { .. }
-            | FlatQuestionKind::Float { .. } => {
+            | FlatQuestionKind::Float { .. }
+            | FlatQuestionKind::Currency { .. }
+            | FlatQuestionKind::Path { .. } => {
                 let hint = match &question.kind {
-                    FlatQuestionKind::Int { min, max } => {
+                    FlatQuestionKind::Currency { question: currency } => {
+                        let mut hints = vec![currency.symbol().to_string()];
+                        if let Some(m) = currency.min {
+                            hints.push(format!("min: {}", currency.format(m)));
+                        }
+                        if let Some(m) = currency.max {
+                            hints.push(format!("max: {}", currency.format(m)));
+                        }
+                        format!(" ({})", hints.join(", "))
+                    }
+                    FlatQuestionKind::Int {
+                        min, max, format, ..
+                    } => {
                         let mut hints = vec![];
                         if let Some(m) = min {
-                            hints.push(format!("min: {}", m));
+                            hints.push(format!("min: {}", format.format_int(*m)));
                         }
                         if let Some(m) = max {
-                            hints.push(format!("max: {}", m));
+                            hints.push(format!("max: {}", format.format_int(*m)));
                         }
                         if hints.is_empty() {
                             "".to_string()
@@ -1190,13 +2830,15 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                             format!(" ({})", hints.join(", "))
                         }
                     }
-                    FlatQuestionKind::Float { min, max } => {
+                    FlatQuestionKind::Float {
+                        min, max, format, ..
+                    } => {
                         let mut hints = vec![];
                         if let Some(m) = min {
-                            hints.push(format!("min: {}", m));
+                            hints.push(format!("min: {}", format.format_float(*m)));
                         }
                         if let Some(m) = max {
-                            hints.push(format!("max: {}", m));
+                            hints.push(format!("max: {}", format.format_float(*m)));
                         }
                         if hints.is_empty() {
                             "".to_string()
@@ -1204,6 +2846,10 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                             format!(" ({})", hints.join(", "))
                         }
                     }
+                    FlatQuestionKind::Path { question: path_q } => path_q
+                        .hint()
+                        .map(|hint| format!(" ({})", hint))
+                        .unwrap_or_default(),
                     _ => "".to_string(),
                 };
 
@@ -1227,22 +2873,120 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                         .unwrap_or_default()
                         .dim()
                         .to_string()
+                } else if let FlatQuestionKind::Currency { question: currency } = &question.kind
+                    && let Ok(minor) = currency.parse(&state.input)
+                {
+                    format!(
+                        "{}{}",
+                        state.input,
+                        format!("  = {}", currency.format(minor)).dim()
+                    )
                 } else {
-                    state.input.clone()
+                    bidi::display_lines(&state.input, state.input_rtl())
                 };
 
                 let input_widget = Paragraph::new(display_text)
                     .style(Style::default().fg(state.theme.text))
+                    .alignment(bidi::alignment(state.input_rtl()))
                     .block(input_block);
                 frame.render_widget(input_widget, content_chunks[1]);
 
                 // Show cursor
-                let cursor_x = content_chunks[1].x + 1 + state.cursor_pos as u16;
-                let cursor_y = content_chunks[1].y + 1;
-                frame.set_cursor_position((cursor_x, cursor_y));
+                frame.set_cursor_position(state.input_cursor(content_chunks[1]));
+            }
+            FlatQuestionKind::Color { question: color } => {
+                let default_hint = question
+                    .default_value
+                    .as_ref()
+                    .map(|d| format!(" [default: {}]", d))
+                    .unwrap_or_default();
+
+                let input_block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(state.theme.border))
+                    .title(format!(" Hex color{} ", default_hint))
+                    .title_style(Style::default().fg(state.theme.secondary));
+
+                // Swatch of the typed (or default) color once it parses
+                let shown = if state.input.is_empty() {
+                    question.default_value.clone().unwrap_or_default()
+                } else {
+                    state.input.clone()
+                };
+                let mut spans = vec![Span::styled(
+                    state.input.clone(),
+                    Style::default().fg(state.theme.text),
+                )];
+                if let Ok(parsed) = color.parse(&shown) {
+                    spans.push(Span::raw("  "));
+                    spans.push(Span::styled(
+                        "    ",
+                        Style::default().bg(Color::Rgb(parsed.r, parsed.g, parsed.b)),
+                    ));
+                }
+
+                let input_widget = Paragraph::new(Line::from(spans)).block(input_block);
+                frame.render_widget(input_widget, content_chunks[1]);
+
+                frame.set_cursor_position(state.input_cursor(content_chunks[1]));
+            }
+            FlatQuestionKind::Autocomplete {
+                question: completer,
+            } => {
+                let area_chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(3), Constraint::Min(2)])
+                    .split(content_chunks[1]);
+
+                let default_hint = question
+                    .default_value
+                    .as_ref()
+                    .map(|d| format!(" [default: {}]", d))
+                    .unwrap_or_default();
+
+                let input_block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(state.theme.border))
+                    .title(format!(" Input{} ", default_hint))
+                    .title_style(Style::default().fg(state.theme.secondary));
+                let input_widget =
+                    Paragraph::new(bidi::display_order(&state.input, state.input_rtl()))
+                        .style(Style::default().fg(state.theme.text))
+                        .alignment(bidi::alignment(state.input_rtl()))
+                        .block(input_block);
+                frame.render_widget(input_widget, area_chunks[0]);
+
+                // Fuzzy-filtered completions for the current input
+                let completions = completer.completions(&state.input);
+                let items: Vec<ListItem> = completions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, completion)| {
+                        let style = if i == state.selected_option {
+                            Style::default().fg(state.theme.highlight).bold()
+                        } else {
+                            Style::default().fg(state.theme.text)
+                        };
+                        ListItem::new(format!("  {}", completion)).style(style)
+                    })
+                    .collect();
+                let list = List::new(items)
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(Style::default().fg(state.theme.border))
+                            .title(" Suggestions ")
+                            .title_style(Style::default().fg(state.theme.secondary)),
+                    )
+                    .highlight_symbol("► ");
+                let mut list_state = ListState::default();
+                list_state.select((!completions.is_empty()).then_some(state.selected_option));
+                frame.render_stateful_widget(list, area_chunks[1], &mut list_state);
+
+                frame.set_cursor_position(state.input_cursor(area_chunks[0]));
             }
             FlatQuestionKind::Masked => {
-                let masked_input = "●".repeat(state.input.len());
+                let masked_input = "●".repeat(state.input.chars().count());
                 let input_block = Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(state.theme.border))
@@ -1253,7 +2997,7 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                     .block(input_block);
                 frame.render_widget(input_widget, content_chunks[1]);
 
-                let cursor_x = content_chunks[1].x + 1 + state.input.len() as u16;
+                let cursor_x = content_chunks[1].x + 1 + state.input.chars().count() as u16;
                 let cursor_y = content_chunks[1].y + 1;
                 frame.set_cursor_position((cursor_x, cursor_y));
             }
@@ -1285,7 +3029,99 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                 list_state.select(Some(state.selected_option));
                 frame.render_stateful_widget(list, content_chunks[1], &mut list_state);
             }
-            FlatQuestionKind::List { element_kind } => {
+            FlatQuestionKind::Rating { question, .. } => {
+                let selected = question.min + state.selected_option as i64;
+                let mut spans = Vec::new();
+                if let Some((low, _)) = &question.anchors {
+                    spans.push(Span::styled(
+                        format!("{} ", low),
+                        Style::default().fg(state.theme.secondary),
+                    ));
+                }
+                for point in question.points() {
+                    let style = if point == selected {
+                        Style::default().fg(state.theme.highlight).bold()
+                    } else {
+                        Style::default().fg(state.theme.text)
+                    };
+                    let symbol = if !question.shows_stars() {
+                        format!(" {} ", point)
+                    } else if point <= selected {
+                        " ★ ".to_string()
+                    } else {
+                        " ☆ ".to_string()
+                    };
+                    spans.push(Span::styled(symbol, style));
+                }
+                if let Some((_, high)) = &question.anchors {
+                    spans.push(Span::styled(
+                        format!(" {}", high),
+                        Style::default().fg(state.theme.secondary),
+                    ));
+                }
+
+                let rating = Paragraph::new(vec![
+                    Line::from(spans),
+                    Line::from(Span::styled(
+                        question.label(selected),
+                        Style::default().fg(state.theme.highlight),
+                    )),
+                ])
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(state.theme.border))
+                        .title(" Rating ")
+                        .title_style(Style::default().fg(state.theme.secondary)),
+                );
+                frame.render_widget(rating, content_chunks[1]);
+            }
+            FlatQuestionKind::Matrix { question, .. } => {
+                let header = Row::new(
+                    std::iter::once(Cell::from(""))
+                        .chain(question.columns.iter().map(|c| Cell::from(c.clone()))),
+                )
+                .style(Style::default().fg(state.theme.secondary).bold());
+
+                let rows = question.rows.iter().enumerate().map(|(row_idx, row)| {
+                    let choice = state.matrix_choices.get(row_idx).copied().flatten();
+                    let cells = (0..question.columns.len()).map(|column| {
+                        if choice == Some(column) {
+                            Cell::from("(●)").style(Style::default().fg(state.theme.highlight))
+                        } else {
+                            Cell::from("( )")
+                        }
+                    });
+                    Row::new(std::iter::once(Cell::from(row.clone())).chain(cells))
+                        .style(Style::default().fg(state.theme.text))
+                });
+
+                let label_width = question.rows.iter().map(|r| r.len()).max().unwrap_or(0);
+                let widths = std::iter::once(Constraint::Length(label_width as u16 + 2)).chain(
+                    question
+                        .columns
+                        .iter()
+                        .map(|c| Constraint::Length(c.len().max(3) as u16 + 2)),
+                );
+
+                let table = Table::new(rows, widths)
+                    .header(header)
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(Style::default().fg(state.theme.border))
+                            .title(" Matrix ")
+                            .title_style(Style::default().fg(state.theme.secondary)),
+                    )
+                    .row_highlight_style(Style::default().bold())
+                    .highlight_symbol("► ");
+
+                let mut table_state = TableState::default();
+                table_state.select(Some(state.selected_option));
+                frame.render_stateful_widget(table, content_chunks[1], &mut table_state);
+            }
+            FlatQuestionKind::List { element_kind, .. } => {
                 let type_hint = match element_kind {
                     ListElementKind::String => "strings",
                     ListElementKind::Int { .. } => "integers",
@@ -1298,48 +3134,121 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                     .title(format!(" List of {} (comma-separated) ", type_hint))
                     .title_style(Style::default().fg(state.theme.secondary));
 
-                let input_widget = Paragraph::new(state.input.clone())
-                    .style(Style::default().fg(state.theme.text))
-                    .block(input_block);
+                let input_widget =
+                    Paragraph::new(bidi::display_lines(&state.input, state.input_rtl()))
+                        .style(Style::default().fg(state.theme.text))
+                        .alignment(bidi::alignment(state.input_rtl()))
+                        .block(input_block);
                 frame.render_widget(input_widget, content_chunks[1]);
 
                 // Show cursor
-                let cursor_x = content_chunks[1].x + 1 + state.cursor_pos as u16;
-                let cursor_y = content_chunks[1].y + 1;
-                frame.set_cursor_position((cursor_x, cursor_y));
+                frame.set_cursor_position(state.input_cursor(content_chunks[1]));
             }
-            FlatQuestionKind::Select { options, .. } => {
-                let items: Vec<ListItem> = options
+            FlatQuestionKind::Select {
+                options, variants, ..
+            } => {
+                // Long lists get a filter box above them, short ones once typing starts
+                let list_area =
+                    if options.len() >= FILTER_BOX_MIN_OPTIONS || !state.filter.is_empty() {
+                        let area_chunks = Layout::default()
+                            .direction(Direction::Vertical)
+                            .constraints([Constraint::Length(3), Constraint::Min(2)])
+                            .split(content_chunks[1]);
+                        let filter_block = Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(Style::default().fg(state.theme.border))
+                            .title(" Filter ")
+                            .title_style(Style::default().fg(state.theme.secondary));
+                        let filter_widget = Paragraph::new(state.filter.clone())
+                            .style(Style::default().fg(state.theme.text))
+                            .block(filter_block);
+                        frame.render_widget(filter_widget, area_chunks[0]);
+                        frame.set_cursor_position((
+                            area_chunks[0].x + 1 + state.filter.width() as u16,
+                            area_chunks[0].y + 1,
+                        ));
+                        area_chunks[1]
+                    } else {
+                        content_chunks[1]
+                    };
+
+                let visible = state.visible_options();
+                let highlighted = visible
                     .iter()
-                    .enumerate()
-                    .map(|(i, opt)| {
-                        let style = if i == state.selected_option {
+                    .position(|(i, _)| *i == state.selected_option);
+                let page = Page::of(visible.len(), highlighted.unwrap_or(0), state.page_size);
+                // Borders, highlight symbol and indent
+                let label_width = usize::from(list_area.width.saturating_sub(6));
+                let items: Vec<ListItem> = visible[page.start..page.end]
+                    .iter()
+                    .map(|(i, matched)| {
+                        let style = if *i == state.selected_option {
                             Style::default().fg(state.theme.highlight).bold()
                         } else {
                             Style::default().fg(state.theme.text)
                         };
-                        ListItem::new(format!("  {}", opt)).style(style)
+                        // Matched characters are underlined
+                        let shown = fitting_chars(&options[*i], label_width);
+                        let mut spans = vec![Span::raw("  ")];
+                        spans.extend(
+                            options[*i]
+                                .chars()
+                                .take(shown.unwrap_or(usize::MAX))
+                                .enumerate()
+                                .map(|(pos, c)| {
+                                    if matched.contains(&pos) {
+                                        Span::styled(
+                                            c.to_string(),
+                                            Style::default().fg(state.theme.secondary).underlined(),
+                                        )
+                                    } else {
+                                        Span::raw(c.to_string())
+                                    }
+                                }),
+                        );
+                        if shown.is_some() {
+                            spans.push(Span::raw("…"));
+                        } else if *i == state.selected_option
+                            && let Some(help) = option_help(variants, *i)
+                        {
+                            let room = label_width.saturating_sub(options[*i].width());
+                            spans.push(help_span(help, room, &state.theme));
+                        }
+                        ListItem::new(Line::from(spans)).style(style)
                     })
                     .collect();
 
                 let list = List::new(items)
                     .block(
-                        Block::default()
-                            .borders(Borders::ALL)
-                            .border_style(Style::default().fg(state.theme.border))
-                            .title(" Select Option ")
-                            .title_style(Style::default().fg(state.theme.secondary)),
+                        page.title_bottom(
+                            Block::default()
+                                .borders(Borders::ALL)
+                                .border_style(Style::default().fg(state.theme.border))
+                                .title(" Select Option ")
+                                .title_style(Style::default().fg(state.theme.secondary)),
+                            &state.theme,
+                        ),
                     )
                     .highlight_symbol("► ");
 
                 let mut list_state = ListState::default();
-                list_state.select(Some(state.selected_option));
-                frame.render_stateful_widget(list, content_chunks[1], &mut list_state);
+                list_state.select(highlighted.map(|pos| pos - page.start));
+                frame.render_stateful_widget(list, list_area, &mut list_state);
+                page.draw_scrollbar(frame, list_area, highlighted.unwrap_or(0));
             }
-            FlatQuestionKind::MultiSelect { options, .. } => {
-                let items: Vec<ListItem> = options
+            FlatQuestionKind::MultiSelect {
+                options,
+                max,
+                variants,
+                ..
+            } => {
+                let page = Page::of(options.len(), state.selected_option, state.page_size);
+                // Borders, highlight symbol, indent and checkbox
+                let label_width = usize::from(content_chunks[1].width.saturating_sub(10));
+                let items: Vec<ListItem> = options[page.start..page.end]
                     .iter()
                     .enumerate()
+                    .map(|(i, opt)| (page.start + i, opt))
                     .map(|(i, opt)| {
                         let is_selected = state.multi_selected.get(i).copied().unwrap_or(false);
                         let checkbox = if is_selected { "[✓]" } else { "[ ]" };
@@ -1350,24 +3259,40 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                         } else {
                             Style::default().fg(state.theme.text)
                         };
-                        ListItem::new(format!("  {} {}", checkbox, opt)).style(style)
+                        let label = truncate_to_width(opt, label_width);
+                        let mut spans = vec![Span::raw(format!("  {checkbox} {label}"))];
+                        if i == state.selected_option
+                            && let Some(help) = option_help(variants, i)
+                        {
+                            let room = label_width.saturating_sub(label.width());
+                            spans.push(help_span(help, room, &state.theme));
+                        }
+                        ListItem::new(Line::from(spans)).style(style)
                     })
                     .collect();
 
-                let selected_count = state.multi_selected.iter().filter(|&&x| x).count();
+                let mut count = format!("{} of {} selected", state.selected_count(), options.len());
+                if let Some(max) = max {
+                    count.push_str(&format!(", max {max}"));
+                }
                 let list = List::new(items)
                     .block(
-                        Block::default()
-                            .borders(Borders::ALL)
-                            .border_style(Style::default().fg(state.theme.border))
-                            .title(format!(" Multi-Select ({} selected) ", selected_count))
-                            .title_style(Style::default().fg(state.theme.secondary)),
+                        page.title_bottom(
+                            Block::default()
+                                .borders(Borders::ALL)
+                                .border_style(Style::default().fg(state.theme.border))
+                                .title(format!(" Multi-Select ({count}) "))
+                                .title_style(Style::default().fg(state.theme.secondary)),
+                            &state.theme,
+                        ),
                     )
                     .highlight_symbol("► ");
 
                 let mut list_state = ListState::default();
-                list_state.select(Some(state.selected_option));
+                list_state
+                    .select((!options.is_empty()).then(|| state.selected_option - page.start));
                 frame.render_stateful_widget(list, content_chunks[1], &mut list_state);
+                page.draw_scrollbar(frame, content_chunks[1], state.selected_option);
             }
         }
 
@@ -1378,20 +3303,82 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                 .alignment(Alignment::Center);
             frame.render_widget(error_widget, content_chunks[2]);
         }
-    }
 
-    // Help bar
-    let help_text = match state.current_question().map(|q| &q.kind) {
-        Some(FlatQuestionKind::Confirm { .. }) | Some(FlatQuestionKind::Select { .. }) => {
-            "↑/↓: Select  Enter: Confirm  Ctrl+←: Back  Esc: Cancel"
+        // Comment editor, drawn over the answer area
+        if let Some(comment) = &state.comment {
+            let editor = Paragraph::new(format!("{}█", comment))
+                .style(Style::default().fg(state.theme.text))
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(state.theme.highlight))
+                        .title(" Comment ")
+                        .title_style(Style::default().fg(state.theme.secondary)),
+                );
+            frame.render_widget(Clear, content_chunks[1]);
+            frame.render_widget(editor, content_chunks[1]);
         }
-        Some(FlatQuestionKind::MultiSelect { .. }) => {
-            "↑/↓: Navigate  Space: Toggle  Enter: Confirm  Ctrl+←: Back  Esc: Cancel"
+    }
+
+    // Help bar, naming the keys of the keymap in use
+    let help_text = if state.leaving.is_some() {
+        help_line(
+            state,
+            &[
+                (&[Action::Left, Action::Right], "Choose"),
+                (&[Action::Confirm], "Select"),
+                (&[Action::Cancel, Action::Normal], "Continue"),
+            ],
+        )
+    } else if state.comment.is_some() {
+        help_line(
+            state,
+            &[
+                (&[Action::Confirm], "Save comment"),
+                (&[Action::Cancel, Action::Normal], "Discard"),
+            ],
+        )
+    } else {
+        let mut entries = question_keys(state);
+        let takes_text = !matches!(
+            state.current_question().map(|q| &q.kind),
+            Some(FlatQuestionKind::Confirm { .. })
+                | Some(FlatQuestionKind::MultiSelect { .. })
+                | Some(FlatQuestionKind::Rating { .. })
+                | Some(FlatQuestionKind::Matrix { .. })
+        );
+        if state.keymap.is_modal() {
+            if state.inserting {
+                entries.insert(0, (&[Action::Normal], "Stop typing"));
+            } else if takes_text {
+                entries.insert(0, (&[Action::Insert], "Type"));
+            }
         }
-        Some(FlatQuestionKind::List { .. }) => {
-            "Enter values separated by commas  Enter: Submit  Ctrl+←: Back  Esc: Cancel"
+
+        let mut help_text = help_line(state, &entries);
+        let hint = match state.current_question().map(|q| &q.kind) {
+            Some(FlatQuestionKind::Select { .. }) if state.typing() => "Type: Filter  ",
+            Some(FlatQuestionKind::MultiSelect { .. }) => "a: All  n: None  i: Invert  ",
+            Some(FlatQuestionKind::List { .. }) => "Enter values separated by commas  ",
+            _ => "",
+        };
+        help_text.insert_str(0, hint);
+        if state.keymap.is_modal() {
+            let mode = if state.inserting {
+                "-- INSERT --  "
+            } else {
+                "-- NORMAL --  "
+            };
+            help_text.insert_str(0, mode);
         }
-        _ => "Enter: Submit  Ctrl+←: Back  Esc: Cancel",
+        format!("{help_text}  F1: Help  F2: Contrast")
+    };
+    // Mirrored, the first key hint is read first from the right
+    let help_text = if state.mirrored {
+        help_text.rsplit("  ").collect::<Vec<_>>().join("  ")
+    } else {
+        help_text
     };
     let help = Paragraph::new(help_text)
         .style(Style::default().fg(state.theme.border))
@@ -1402,10 +3389,345 @@ fn draw_ui(frame: &mut Frame, state: &WizardState) {
                 .border_style(Style::default().fg(state.theme.border)),
         );
     frame.render_widget(help, chunks[3]);
+
+    if let Some(dialog) = &state.leaving {
+        draw_leave_dialog(frame, state, dialog, area);
+    } else if state.show_help
+        && let Some(question) = state.current_question()
+    {
+        draw_help_overlay(frame, state, question, area);
+    }
+}
+
+/// Explain the current question: its help text, the limits on the answer and
+/// the keys it takes.
+fn draw_help_overlay(frame: &mut Frame, state: &WizardState, question: &FlatQuestion, area: Rect) {
+    let text = Style::default().fg(state.theme.text);
+    let heading = Style::default().fg(state.theme.secondary).bold();
+    let width = 60.min(area.width);
+    let inner_width = width.saturating_sub(2).max(1);
+    let title = Style::default().fg(state.theme.primary).bold();
+    let mut lines: Vec<Line> = match prompt_text(state, &question.prompt, inner_width) {
+        Some(prompt) => prompt
+            .into_iter()
+            .map(|line| Line::styled(line, title).alignment(bidi::alignment(state.rtl())))
+            .collect(),
+        None => vec![Line::from(Span::styled(question.prompt.clone(), title))],
+    };
+    lines.extend([
+        Line::default(),
+        Line::from(Span::styled(
+            question
+                .help
+                .clone()
+                .unwrap_or_else(|| "No further explanation.".to_string()),
+            text,
+        )),
+    ]);
+    if !question.constraints.is_empty() || question.has_validation {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled("Allowed answers", heading)));
+        for constraint in &question.constraints {
+            lines.push(Line::from(Span::styled(format!("• {constraint}"), text)));
+        }
+        if question.has_validation {
+            lines.push(Line::from(Span::styled(
+                "• Checked by the survey's own rules",
+                text,
+            )));
+        }
+    }
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("Keys", heading)));
+    for entry in question_keys(state) {
+        let keys = help_line(state, &[entry]);
+        if !keys.is_empty() {
+            lines.push(Line::from(Span::styled(keys, text)));
+        }
+    }
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(
+        "Press any key to close",
+        Style::default().fg(state.theme.border),
+    )));
+
+    let inner_width = usize::from(inner_width);
+    let rows: usize = lines
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(inner_width))
+        .sum();
+    let height = (rows as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    // Mirrored, the help reads from the right
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .alignment(bidi::alignment(state.mirrored))
+        .style(Style::default().bg(state.theme.background))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(state.theme.highlight))
+                .title(" Help ")
+                .title_style(Style::default().fg(state.theme.secondary)),
+        );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(paragraph, popup);
+}
+
+/// Ask whether to discard the answers, save them as a draft, or go on.
+fn draw_leave_dialog(frame: &mut Frame, state: &WizardState, dialog: &LeaveDialog, area: Rect) {
+    let answered = state.current_index.min(state.questions.len());
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Leave the survey?",
+            Style::default().fg(state.theme.text).bold(),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "{answered} of {} questions answered.",
+                state.questions.len()
+            ),
+            Style::default().fg(state.theme.text),
+        )),
+        Line::default(),
+    ];
+    if let Some(error) = &dialog.error {
+        lines.push(Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(state.theme.error),
+        )));
+        lines.push(Line::default());
+    }
+    let mut buttons = Vec::new();
+    for (i, choice) in state.leave_choices().into_iter().enumerate() {
+        if i > 0 {
+            buttons.push(Span::raw("   "));
+        }
+        let style = if i == dialog.highlighted {
+            Style::default()
+                .fg(state.theme.background)
+                .bg(state.theme.highlight)
+                .bold()
+        } else {
+            Style::default().fg(state.theme.primary)
+        };
+        buttons.push(Span::styled(format!(" {} ", choice.label()), style));
+    }
+    lines.push(Line::from(buttons));
+
+    let width = 50.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().bg(state.theme.background))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(state.theme.highlight))
+                .title(" Leave ")
+                .title_style(Style::default().fg(state.theme.secondary)),
+        );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(paragraph, popup);
+}
+
+/// The keys of the current question with what they do, for the help bar and
+/// the help overlay.
+fn question_keys(state: &WizardState) -> Vec<(&'static [Action], &'static str)> {
+    let mut entries: Vec<(&[Action], &str)> = match state.current_question().map(|q| &q.kind) {
+        Some(FlatQuestionKind::Confirm { .. }) => vec![
+            (&[Action::Up, Action::Down], "Select"),
+            (&[Action::Confirm], "Confirm"),
+        ],
+        Some(FlatQuestionKind::Select { .. }) => vec![
+            (&[Action::Up, Action::Down], "Select"),
+            (&[Action::PageUp, Action::PageDown], "Page"),
+            (&[Action::Confirm], "Confirm"),
+        ],
+        Some(FlatQuestionKind::Rating { .. }) => vec![
+            (&[Action::Left, Action::Right], "Rate"),
+            (&[Action::Confirm], "Confirm"),
+        ],
+        Some(FlatQuestionKind::Matrix { .. }) => vec![
+            (&[Action::Up, Action::Down], "Row"),
+            (&[Action::Left, Action::Right], "Choose"),
+            (&[Action::Confirm], "Confirm"),
+        ],
+        Some(FlatQuestionKind::MultiSelect { .. }) => vec![
+            (&[Action::Up, Action::Down], "Navigate"),
+            (&[Action::PageUp, Action::PageDown], "Page"),
+            (&[Action::Toggle], "Toggle"),
+            (&[Action::Confirm], "Confirm"),
+        ],
+        Some(FlatQuestionKind::Autocomplete { .. }) => vec![
+            (&[Action::Up, Action::Down], "Suggestion"),
+            (&[Action::Complete], "Complete"),
+            (&[Action::Confirm], "Submit"),
+        ],
+        _ => vec![(&[Action::Confirm], "Submit")],
+    };
+    entries.extend([(&[Action::Back][..], "Back"), (&[Action::Cancel], "Cancel")]);
+    if state.current_question().is_some_and(|q| q.allow_comment) {
+        entries.push((&[Action::Comment], "Comment"));
+    }
+    entries
+}
+
+/// Help entries like `↑/↓: Select`, naming the first key bound to each action in
+/// the current mode. Entries without a bound key are left out.
+fn help_line(state: &WizardState, entries: &[(&[Action], &str)]) -> String {
+    let typing = state.typing();
+    entries
+        .iter()
+        .filter_map(|(actions, label)| {
+            let keys: Vec<String> = actions
+                .iter()
+                .filter_map(|action| state.keymap.keys(*action, typing).next())
+                .map(|key| key.to_string())
+                .collect();
+            (!keys.is_empty()).then(|| format!("{}: {label}", keys.join("/")))
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// How many leading chars of `text` fit into `width` terminal columns with an
+/// ellipsis after them, or `None` if the whole text fits.
+fn fitting_chars(text: &str, width: usize) -> Option<usize> {
+    if text.width() <= width {
+        return None;
+    }
+    let budget = width.saturating_sub(1);
+    let mut used = 0;
+    Some(
+        text.chars()
+            .take_while(|c| {
+                used += c.width().unwrap_or(0);
+                used <= budget
+            })
+            .count(),
+    )
+}
+
+/// Shorten `text` to `width` terminal columns, ending in `…` when cut.
+fn truncate_to_width(text: &str, width: usize) -> Cow<'_, str> {
+    match fitting_chars(text, width) {
+        Some(shown) => Cow::Owned(text.chars().take(shown).chain(['…']).collect()),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// What choosing the enum variant at `index` means, if it says.
+fn option_help(variants: &Option<Vec<elicitor::Variant>>, index: usize) -> Option<&str> {
+    variants.as_ref()?.get(index)?.help.as_deref()
 }
 
-fn draw_completion(frame: &mut Frame, state: &WizardState) {
-    let area = frame.area();
+/// The help of the highlighted option, dimmed and cut to the `width` left beside its
+/// label.
+fn help_span(help: &str, width: usize, theme: &Theme) -> Span<'static> {
+    let text = truncate_to_width(help, width.saturating_sub(3));
+    let text = if width > 3 {
+        format!(" — {text}")
+    } else {
+        String::new()
+    };
+    Span::styled(text, Style::default().fg(theme.secondary).not_bold().dim())
+}
+
+/// The lines of a prompt holding right-to-left text, wrapped to `width` columns and
+/// in display order, or `None` to let ratatui wrap a left-to-right prompt.
+fn prompt_text(state: &WizardState, prompt: &str, width: u16) -> Option<Vec<String>> {
+    let rtl = state.direction.is_rtl(prompt);
+    (rtl || bidi::has_rtl(prompt)).then(|| bidi::wrap(prompt, width, rtl))
+}
+
+/// Number of lines `text` takes when wrapped to `width` columns.
+fn wrapped_lines(text: &str, width: u16) -> u16 {
+    let width = usize::from(width.max(1));
+    text.lines()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum::<usize>()
+        .max(1) as u16
+}
+
+/// The page of a choice list that holds the highlighted option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Page {
+    /// Index of the first option on the page.
+    start: usize,
+    /// Index after the last option on the page.
+    end: usize,
+    /// Zero-based number of the page.
+    number: usize,
+    /// Number of pages, at least one.
+    count: usize,
+    /// Number of options in the whole list.
+    len: usize,
+}
+
+impl Page {
+    fn of(len: usize, highlighted: usize, page_size: usize) -> Self {
+        let page_size = page_size.max(1);
+        let number = highlighted.min(len.saturating_sub(1)) / page_size;
+        let start = number * page_size;
+        Self {
+            start,
+            end: (start + page_size).min(len),
+            number,
+            count: len.div_ceil(page_size).max(1),
+            len,
+        }
+    }
+
+    /// Show "page/count" in the bottom border of lists with several pages.
+    fn title_bottom<'a>(&self, block: Block<'a>, theme: &Theme) -> Block<'a> {
+        if self.count > 1 {
+            block.title_bottom(
+                Line::styled(
+                    format!(" {}/{} ", self.number + 1, self.count),
+                    Style::default().fg(theme.secondary),
+                )
+                .right_aligned(),
+            )
+        } else {
+            block
+        }
+    }
+
+    /// Draw a scrollbar over the right border of lists with several pages.
+    fn draw_scrollbar(&self, frame: &mut Frame, area: Rect, highlighted: usize) {
+        if self.count < 2 {
+            return;
+        }
+        let mut scrollbar_state = ScrollbarState::new(self.len).position(highlighted);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None);
+        frame.render_stateful_widget(
+            scrollbar,
+            area.inner(Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+    }
+}
+
+fn draw_completion(frame: &mut Frame, state: &WizardState, area: Rect) {
+    draw_background(frame, state, area);
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -1446,200 +3768,63 @@ impl SurveyBackend for RatatuiBackend {
         &self,
         definition: &SurveyDefinition,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
     ) -> Result<Responses, Self::Error> {
         let mut terminal = self.setup_terminal()?;
         let mut state = WizardState::new(definition, self.theme.clone(), self.title.clone());
+        state.page_size = self.page_size;
+        state.keymap = self.keymap.clone();
+        state.checkpoint = self.checkpoint.clone();
+        state.direction = self.direction;
+        state.mirrored = self.mirrored;
+        state.text_checker = self.text_checker.clone();
+        let started_high_contrast = self
+            .high_contrast
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .unwrap_or_else(elicitor::high_contrast_enabled);
+        state.set_high_contrast(started_high_contrast);
 
-        // Skip initially assumed questions
-        while state.current_index < state.questions.len() {
-            if let Some(assumed) = &state.questions[state.current_index].assumed {
-                state.responses.insert(
-                    state.questions[state.current_index].path.clone(),
-                    assumed.clone(),
-                );
-                state.current_index += 1;
-            } else {
-                // Initialize first question's defaults
-                // Extract values first to avoid borrow issues
-                let init_data = state.current_question().map(|q| match &q.kind {
-                    FlatQuestionKind::Confirm { default } => {
-                        (Some(if *default { 0 } else { 1 }), None, None)
-                    }
-                    FlatQuestionKind::Select { default_idx, .. } => {
-                        (Some(*default_idx), None, None)
-                    }
-                    FlatQuestionKind::MultiSelect {
-                        options, defaults, ..
-                    } => {
-                        let mut selected = vec![false; options.len()];
-                        for &idx in defaults {
-                            if idx < selected.len() {
-                                selected[idx] = true;
-                            }
-                        }
-                        (None, Some(selected), None)
-                    }
-                    _ => (None, None, q.default_value.clone()),
-                });
-
-                if let Some((selected_opt, multi_sel, default_val)) = init_data {
-                    if let Some(sel) = selected_opt {
-                        state.selected_option = sel;
-                    }
-                    if let Some(multi) = multi_sel {
-                        state.multi_selected = multi;
-                    }
-                    if let Some(def) = default_val {
-                        state.input = def;
-                        state.cursor_pos = state.input.len();
-                    }
-                }
-                break;
-            }
-        }
-
-        if state.current_index >= state.questions.len() {
-            state.complete = true;
-        }
+        state.start();
 
+        let mut shown = None;
         loop {
-            terminal.draw(|frame| {
-                if state.complete {
-                    draw_completion(frame, &state);
-                } else {
-                    draw_ui(frame, &state);
+            if !state.complete && shown != Some(state.current_index) {
+                if let Some(q) = state.current_question() {
+                    observer.on_question_start(&q.path);
                 }
-            })?;
-
-            if let Event::Key(key) = event::read()? {
-                if key.kind != KeyEventKind::Press {
-                    continue;
-                }
-
-                if state.complete {
-                    match key.code {
-                        KeyCode::Enter | KeyCode::Esc => break,
-                        _ => {}
-                    }
-                } else {
-                    match key.code {
-                        KeyCode::Esc => {
-                            state.cancelled = true;
-                            break;
-                        }
-                        KeyCode::Enter => {
-                            state.next_question(validate);
-                        }
-                        KeyCode::Up => {
-                            if matches!(
-                                state.current_question().map(|q| &q.kind),
-                                Some(FlatQuestionKind::Confirm { .. })
-                                    | Some(FlatQuestionKind::Select { .. })
-                                    | Some(FlatQuestionKind::MultiSelect { .. })
-                            ) && state.selected_option > 0
-                            {
-                                state.selected_option -= 1;
-                            }
-                        }
-                        KeyCode::Down => {
-                            if let Some(q) = state.current_question() {
-                                match &q.kind {
-                                    FlatQuestionKind::Confirm { .. } => {
-                                        if state.selected_option < 1 {
-                                            state.selected_option += 1;
-                                        }
-                                    }
-                                    FlatQuestionKind::Select { options, .. } => {
-                                        if state.selected_option < options.len() - 1 {
-                                            state.selected_option += 1;
-                                        }
-                                    }
-                                    FlatQuestionKind::MultiSelect { options, .. } => {
-                                        if state.selected_option < options.len() - 1 {
-                                            state.selected_option += 1;
-                                        }
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-                        KeyCode::Char(' ') => {
-                            // Space toggles selection in multi-select
-                            if let Some(FlatQuestionKind::MultiSelect { options, .. }) =
-                                state.current_question().map(|q| &q.kind)
-                            {
-                                // Ensure multi_selected is properly sized
-                                if state.multi_selected.len() != options.len() {
-                                    state.multi_selected = vec![false; options.len()];
-                                }
-                                if state.selected_option < state.multi_selected.len() {
-                                    state.multi_selected[state.selected_option] =
-                                        !state.multi_selected[state.selected_option];
-                                }
-                            } else {
-                                // For other question types, treat space as regular input
-                                state.handle_input(key.code);
-                            }
-                        }
-                        KeyCode::Backspace => {
-                            // For text input questions, backspace deletes or goes back when empty
-                            // For Select/MultiSelect/Confirm, backspace does nothing (use Left to go back)
-                            let is_selection_question = matches!(
-                                state.current_question().map(|q| &q.kind),
-                                Some(FlatQuestionKind::Confirm { .. })
-                                    | Some(FlatQuestionKind::Select { .. })
-                                    | Some(FlatQuestionKind::MultiSelect { .. })
-                            );
+                shown = Some(state.current_index);
+            }
 
-                            if !is_selection_question {
-                                if state.input.is_empty() && state.current_index > 0 {
-                                    // For text input, backspace goes back only when empty
-                                    state.prev_question();
-                                } else {
-                                    // Otherwise, handle as normal backspace in text
-                                    state.handle_input(key.code);
-                                }
-                            }
-                            // For selection questions, backspace does nothing
-                        }
-                        KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            // Ctrl+Left always goes back to previous question
-                            if state.current_index > 0 {
-                                state.prev_question();
-                            }
-                        }
-                        KeyCode::Left => {
-                            // Left arrow moves cursor in text input, does nothing for selection
-                            let is_selection_question = matches!(
-                                state.current_question().map(|q| &q.kind),
-                                Some(FlatQuestionKind::Confirm { .. })
-                                    | Some(FlatQuestionKind::Select { .. })
-                                    | Some(FlatQuestionKind::MultiSelect { .. })
-                            );
+            terminal.draw(|frame| draw(frame, &state, frame.area()))?;
 
-                            if !is_selection_question {
-                                state.handle_input(key.code);
-                            }
-                        }
-                        _ => {
-                            if !matches!(
-                                state.current_question().map(|q| &q.kind),
-                                Some(FlatQuestionKind::Confirm { .. })
-                                    | Some(FlatQuestionKind::Select { .. })
-                                    | Some(FlatQuestionKind::MultiSelect { .. })
-                            ) {
-                                state.handle_input(key.code);
-                            }
-                        }
-                    }
-                }
+            match event::read()? {
+                Event::Key(key) if state.handle_key(key, validate) => break,
+                Event::Paste(text) => state.paste(&text),
+                _ => {}
             }
         }
 
         self.restore_terminal(&mut terminal)?;
 
+        // The next survey of this backend starts in the mode the user switched to
+        if state.high_contrast != started_high_contrast {
+            *self.high_contrast.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some(state.high_contrast);
+        }
+
         if state.cancelled {
-            return Err(RatatuiError::Cancelled);
+            return Err(RatatuiError::Cancelled {
+                partial: Box::new(state.responses),
+            });
         }
 
         Ok(state.responses)
@@ -1659,13 +3844,519 @@ mod tests {
 
     #[test]
     fn error_types() {
-        let err = RatatuiError::Cancelled;
+        let err = RatatuiError::Cancelled {
+            partial: Box::default(),
+        };
         assert_eq!(err.to_string(), "Survey cancelled by user");
 
         let err = RatatuiError::Terminal("test error".to_string());
         assert_eq!(err.to_string(), "Terminal error: test error");
     }
 
+    #[test]
+    fn high_contrast_restores_the_custom_theme() {
+        let definition = SurveyDefinition::new(Vec::new());
+        let custom = Theme {
+            primary: Color::Magenta,
+            ..Theme::default()
+        };
+        let mut state = WizardState::new(&definition, custom, "Survey".to_string());
+
+        state.set_high_contrast(true);
+        assert_eq!(state.theme.background, Color::Black);
+        state.set_high_contrast(false);
+        assert_eq!(state.theme.primary, Color::Magenta);
+    }
+
+    #[test]
+    fn f2_switches_high_contrast_of_this_wizard_only() {
+        let definition = SurveyDefinition::new(Vec::new());
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+        let before = elicitor::high_contrast_enabled();
+        state.set_high_contrast(before);
+
+        let f2 = KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE);
+        state.handle_key(f2, &|_, _, _| Ok(()));
+        assert_eq!(state.high_contrast, !before);
+        assert_eq!(elicitor::high_contrast_enabled(), before);
+    }
+
+    #[test]
+    fn help_names_the_keys_of_the_keymap() {
+        let definition = SurveyDefinition::new(Vec::new());
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+        let entries: &[(&[Action], &str)] = &[
+            (&[Action::Up, Action::Down], "Select"),
+            (&[Action::Cancel], "Cancel"),
+        ];
+        assert_eq!(help_line(&state, entries), "↑/↓: Select  Esc: Cancel");
+
+        state.keymap = KeyMap::vim();
+        assert_eq!(help_line(&state, entries), "k/j: Select  q: Cancel");
+        state.inserting = true;
+        assert_eq!(help_line(&state, entries), "↑/↓: Select");
+    }
+
+    #[test]
+    fn multi_select_keys_select_in_bulk_and_check_live() {
+        use elicitor::{AnyOfQuestion, Variant};
+
+        let items = ["Potion", "Scroll", "Shield", "Sword"];
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "items",
+            "Items:",
+            QuestionKind::AnyOf(
+                AnyOfQuestion::new(items.into_iter().map(Variant::unit).collect())
+                    .with_max_selected(2),
+            ),
+        )]);
+        let validate = |value: &ResponseValue, _: &Responses, path: &ResponsePath| match value {
+            ResponseValue::ChosenVariants(chosen) if path.as_str() == "items" => {
+                if chosen.contains(&3) {
+                    Err("No swords".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        };
+        let press = |state: &mut WizardState, code: KeyCode| {
+            state.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &validate)
+        };
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+
+        press(&mut state, KeyCode::Char('a'));
+        assert_eq!(state.multi_selected, [true, true, false, false]);
+        press(&mut state, KeyCode::Char('i'));
+        assert_eq!(state.multi_selected, [false, false, true, true]);
+        assert_eq!(state.error_message.as_deref(), Some("No swords"));
+        press(&mut state, KeyCode::Char('n'));
+        assert_eq!(state.selected_count(), 0);
+        assert_eq!(state.error_message, None);
+
+        press(&mut state, KeyCode::Char(' '));
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Char(' '));
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Char(' '));
+        assert_eq!(state.multi_selected, [true, true, false, false]);
+        assert_eq!(
+            state.error_message.as_deref(),
+            Some("At most 2 can be selected")
+        );
+        assert_eq!(state.questions[0].constraints, ["At most 2 selected"]);
+
+        press(&mut state, KeyCode::Char('A'));
+        press(&mut state, KeyCode::Char('i'));
+        press(&mut state, KeyCode::Enter);
+        assert_eq!(
+            state.error_message.as_deref(),
+            Some("At most 2 can be selected, 4 are")
+        );
+        assert!(!state.complete);
+    }
+
+    #[test]
+    fn progress_names_the_nested_section() {
+        use elicitor::{AllOfQuestion, InputQuestion};
+
+        let input = |path: &str, prompt: &str| {
+            Question::new(path, prompt, QuestionKind::Input(InputQuestion::new()))
+        };
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                "database",
+                "Database configuration:",
+                QuestionKind::AllOf(AllOfQuestion::new(vec![
+                    input("host", "Host:"),
+                    Question::new(
+                        "login_details",
+                        "",
+                        QuestionKind::AllOf(AllOfQuestion::new(vec![
+                            input("user", "User:"),
+                            input("password", "Password:"),
+                        ])),
+                    ),
+                ])),
+            ),
+            input("name", "Name:"),
+        ]);
+        let validate = |_: &ResponseValue, _: &Responses, _: &ResponsePath| Ok(());
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+
+        assert_eq!(
+            state.section_progress(),
+            Some(("Database configuration", 1, 3))
+        );
+        state.input = "localhost".to_string();
+        state.next_question(&validate);
+        assert_eq!(state.section_progress(), Some(("Login details", 1, 2)));
+        let breadcrumb: Vec<_> = state.questions[1]
+            .sections
+            .iter()
+            .map(|section| section.name.as_str())
+            .collect();
+        assert_eq!(breadcrumb, ["Database configuration", "Login details"]);
+        state.input = "admin".to_string();
+        state.next_question(&validate);
+        state.input = "secret".to_string();
+        state.next_question(&validate);
+        assert_eq!(state.section_progress(), None);
+    }
+
+    #[test]
+    fn multi_select_asks_the_fields_of_chosen_variants() {
+        use elicitor::{AllOfQuestion, AnyOfQuestion, InputQuestion, IntQuestion, Variant};
+
+        let gum = Variant::new(
+            "ChewingGum",
+            QuestionKind::AllOf(AllOfQuestion::new(vec![
+                Question::new(
+                    "flavor",
+                    "Flavor:",
+                    QuestionKind::Input(InputQuestion::new()),
+                ),
+                Question::new(
+                    "quantity",
+                    "Quantity:",
+                    QuestionKind::Int(IntQuestion::new()),
+                ),
+            ])),
+        );
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                "treats",
+                "Treats:",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![Variant::unit("Candy"), gum])),
+            ),
+            Question::new(
+                "treats_budget",
+                "Budget:",
+                QuestionKind::Int(IntQuestion::new()),
+            ),
+        ]);
+        let validate = |_: &ResponseValue, _: &Responses, _: &ResponsePath| Ok(());
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+
+        state.multi_selected = vec![true, true];
+        state.next_question(&validate);
+        assert_eq!(
+            state.current_question().map(|q| q.path.as_str()),
+            Some("treats.1.flavor")
+        );
+        assert_eq!(state.section_progress(), Some(("ChewingGum", 1, 2)));
+        state.input = "Mint".to_string();
+        state.next_question(&validate);
+        state.input = "3".to_string();
+        state.next_question(&validate);
+        assert_eq!(
+            state.current_question().map(|q| q.path.as_str()),
+            Some("treats_budget")
+        );
+
+        let responses = &state.responses;
+        assert_eq!(
+            responses.get(&ResponsePath::new("treats.1.selected_variant")),
+            Some(&ResponseValue::ChosenVariant(1))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("treats.1.flavor")),
+            Some(&ResponseValue::String("Mint".into()))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("treats.1.quantity")),
+            Some(&ResponseValue::Int(3))
+        );
+    }
+
+    #[test]
+    fn typing_filters_select_options() {
+        use elicitor::{OneOfQuestion, Variant};
+
+        let countries = ["Germany", "New Zealand", "Norway", "Netherlands"];
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "country",
+            "Country:",
+            QuestionKind::OneOf(OneOfQuestion::new(
+                countries.into_iter().map(Variant::unit).collect(),
+            )),
+        )]);
+        let validate = |_: &ResponseValue, _: &Responses, _: &ResponsePath| Ok(());
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+
+        for c in "nzl".chars() {
+            state.edit_filter(KeyCode::Char(c));
+        }
+        assert_eq!(state.visible_options(), vec![(1, vec![0, 4, 7])]);
+        assert_eq!(state.selected_option, 1);
+
+        state.edit_filter(KeyCode::Char('x'));
+        assert!(state.visible_options().is_empty());
+        assert!(!state.validate_and_submit(&validate));
+        assert_eq!(
+            state.error_message.as_deref(),
+            Some("No option matches the filter")
+        );
+
+        // Prefix matches come first, shorter names before longer ones
+        for _ in 0..3 {
+            state.edit_filter(KeyCode::Backspace);
+        }
+        assert_eq!(state.filter, "n");
+        assert_eq!(state.selected_option, 2);
+        state.step_select(1);
+        assert_eq!(state.selected_option, 1);
+        state.next_question(&validate);
+        assert_eq!(
+            state
+                .responses
+                .get(&ResponsePath::new("country.selected_variant")),
+            Some(&ResponseValue::ChosenVariant(1))
+        );
+        assert!(state.filter.is_empty());
+    }
+
+    #[test]
+    fn esc_asks_before_leaving_and_can_save_a_draft() {
+        use elicitor::InputQuestion;
+        use std::sync::Mutex;
+
+        let definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new())),
+            Question::new("city", "City?", QuestionKind::Input(InputQuestion::new())),
+        ]);
+        let validate = |_: &ResponseValue, _: &Responses, _: &ResponsePath| Ok(());
+        let press = |state: &mut WizardState, code: KeyCode| {
+            state.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &validate)
+        };
+        let drafts = Arc::new(Mutex::new(Vec::new()));
+        let fail = Arc::new(Mutex::new(true));
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+        state.checkpoint = Some(Arc::new({
+            let drafts = drafts.clone();
+            let fail = fail.clone();
+            move |draft: &Responses| {
+                if *fail.lock().unwrap() {
+                    return Err("disk full".to_string());
+                }
+                drafts.lock().unwrap().push(draft.len());
+                Ok(())
+            }
+        }));
+        state.start();
+
+        for c in "Ada".chars() {
+            press(&mut state, KeyCode::Char(c));
+        }
+        press(&mut state, KeyCode::Enter);
+
+        // A stray Esc and Enter continue the survey
+        assert!(!press(&mut state, KeyCode::Esc));
+        assert!(state.leaving.is_some());
+        assert!(!press(&mut state, KeyCode::Enter));
+        assert!(state.leaving.is_none());
+        assert!(!state.cancelled);
+
+        press(&mut state, KeyCode::Esc);
+        press(&mut state, KeyCode::Left);
+        assert!(!press(&mut state, KeyCode::Enter));
+        assert_eq!(
+            state.leaving.as_ref().and_then(|d| d.error.as_deref()),
+            Some("Cannot save the draft: disk full")
+        );
+
+        *fail.lock().unwrap() = false;
+        assert!(press(&mut state, KeyCode::Char('s')));
+        assert!(state.cancelled);
+        assert_eq!(*drafts.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn pasting_inserts_the_whole_text() {
+        use elicitor::{InputQuestion, MultilineQuestion, OneOfQuestion, Variant};
+
+        let definition = SurveyDefinition::new(vec![
+            Question::new("key", "API key:", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "notes",
+                "Notes:",
+                QuestionKind::Multiline(MultilineQuestion::new()),
+            ),
+            Question::new(
+                "country",
+                "Country:",
+                QuestionKind::OneOf(OneOfQuestion::new(
+                    ["Germany", "Norway"]
+                        .into_iter()
+                        .map(Variant::unit)
+                        .collect(),
+                )),
+            ),
+        ]);
+        let validate = |_: &ResponseValue, _: &Responses, _: &ResponsePath| Ok(());
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+        state.start();
+
+        state.handle_input(KeyCode::Char('['));
+        state.handle_input(KeyCode::Char(']'));
+        state.handle_input(KeyCode::Left);
+        state.paste("sk-123\n");
+        assert_eq!(state.input, "[sk-123]");
+        assert_eq!(state.cursor_pos, 7);
+        state.next_question(&validate);
+
+        state.paste("first line\r\nsecond line\n");
+        assert_eq!(state.input, "first line\nsecond line");
+        state.next_question(&validate);
+
+        state.paste("nor");
+        assert_eq!(state.filter, "nor");
+        assert_eq!(state.selected_option, 1);
+    }
+
+    #[test]
+    fn text_findings_are_shown_before_the_text_is_kept() {
+        use elicitor::{MultilineQuestion, TextIssue};
+
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "notes",
+            "Notes:",
+            QuestionKind::Multiline(MultilineQuestion::new()),
+        )]);
+        let validate = |_: &ResponseValue, _: &Responses, _: &ResponsePath| Ok(());
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+        state.text_checker = Some(Arc::new(|text: &str| {
+            if text.contains("TODO") {
+                vec![TextIssue::warning("Unfinished text")]
+            } else {
+                vec![]
+            }
+        }));
+        state.start();
+
+        state.paste("TODO");
+        state.next_question(&validate);
+        assert!(!state.complete);
+        assert_eq!(
+            state.error_message.as_deref(),
+            Some("Unfinished text (submit again to keep the text)")
+        );
+
+        state.next_question(&validate);
+        assert!(state.complete);
+        assert_eq!(
+            state.responses.get(&ResponsePath::new("notes")),
+            Some(&ResponseValue::String("TODO".to_string()))
+        );
+    }
+
+    #[test]
+    fn f1_shows_help_and_constraints_of_the_question() {
+        use elicitor::{IntQuestion, ListQuestion, PathQuestion};
+
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                "age",
+                "Age?",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(18), None)),
+            )
+            .with_help("Needed for the insurance"),
+            Question::new(
+                "tags",
+                "Tags?",
+                QuestionKind::List(ListQuestion::strings().with_item_bounds(Some(1), Some(3))),
+            ),
+            Question::new(
+                "photo",
+                "Photo?",
+                QuestionKind::Path(PathQuestion::file(["png", "jpg"])),
+            ),
+        ]);
+        let validate = |_: &ResponseValue, _: &Responses, _: &ResponsePath| Ok(());
+        let press = |state: &mut WizardState, code: KeyCode| {
+            state.handle_key(KeyEvent::new(code, KeyModifiers::NONE), &validate)
+        };
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+
+        let age = &state.questions[0];
+        assert_eq!(age.help.as_deref(), Some("Needed for the insurance"));
+        assert_eq!(age.constraints, ["At least 18"]);
+        assert_eq!(state.questions[1].constraints, ["Between 1 and 3 items"]);
+        assert_eq!(
+            state.questions[2].constraints,
+            ["An existing file ending in .png, .jpg"]
+        );
+
+        press(&mut state, KeyCode::F(1));
+        assert!(state.show_help);
+        // The key closing the overlay does nothing else
+        press(&mut state, KeyCode::Char('4'));
+        assert!(!state.show_help);
+        assert!(state.input.is_empty());
+    }
+
+    #[test]
+    fn wide_characters_move_the_cursor_by_their_width() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "topping",
+            "Topping:",
+            QuestionKind::Input(Default::default()),
+        )]);
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+
+        state.handle_input(KeyCode::Char('🍕'));
+        state.handle_input(KeyCode::Char('a'));
+        assert_eq!(state.cursor_column(), 3);
+        state.handle_input(KeyCode::Left);
+        state.handle_input(KeyCode::Left);
+        state.handle_input(KeyCode::Char('日'));
+        assert_eq!(state.input, "日🍕a");
+        assert_eq!(state.cursor_column(), 2);
+        state.handle_input(KeyCode::Right);
+        state.handle_input(KeyCode::Backspace);
+        assert_eq!(state.input, "日a");
+        assert_eq!(state.cursor_column(), 2);
+
+        assert_eq!(truncate_to_width("💼 Business", 20), "💼 Business");
+        assert_eq!(truncate_to_width("💼 Business", 6), "💼 Bu…");
+        assert_eq!(fitting_chars("日本語テキスト", 7), Some(3));
+        assert_eq!(wrapped_lines("🍕🍕🍕", 4), 2);
+    }
+
+    #[test]
+    fn long_choice_lists_are_paged() {
+        use elicitor::{AnyOfQuestion, Variant};
+
+        let zones: Vec<_> = (0..25).map(|i| Variant::unit(format!("UTC+{i}"))).collect();
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "zones",
+            "Time zones:",
+            QuestionKind::AnyOf(AnyOfQuestion::new(zones)),
+        )]);
+        let mut state = WizardState::new(&definition, Theme::default(), "Survey".to_string());
+
+        state.page_step(true);
+        state.page_step(true);
+        assert_eq!(state.selected_option, 20);
+        state.page_step(true);
+        assert_eq!(state.selected_option, 24);
+        assert_eq!(
+            Page::of(25, state.selected_option, state.page_size),
+            Page {
+                start: 20,
+                end: 25,
+                number: 2,
+                count: 3,
+                len: 25,
+            }
+        );
+        state.page_step(false);
+        assert_eq!(state.selected_option, 14);
+
+        assert_eq!(wrapped_lines("short", 40), 1);
+        assert_eq!(wrapped_lines(&"x".repeat(90), 40), 3);
+    }
+
     #[test]
     fn theme_default() {
         let theme = Theme::default();
@@ -0,0 +1,166 @@
+//! Right-to-left text such as Arabic and Hebrew.
+//!
+//! Terminals put the characters of a line into cells from left to right in the order
+//! they are written. Prompts and answers are therefore reordered into display order
+//! with the Unicode bidirectional algorithm, wrapped before reordering so the first
+//! words stay on the first line, and aligned to the right when written right to left.
+//! Joining the letters of Arabic script is left to the terminal.
+
+use ratatui::layout::Alignment;
+use unicode_bidi::{Direction, Level, ParagraphBidiInfo};
+use unicode_width::UnicodeWidthChar;
+
+/// Direction in which prompts and answers are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    /// Right to left if the prompt's first letter is written right to left, e.g. in
+    /// Arabic or Hebrew.
+    #[default]
+    Auto,
+    /// Always left to right.
+    LeftToRight,
+    /// Always right to left.
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Whether `text` is written right to left.
+    pub(crate) fn is_rtl(self, text: &str) -> bool {
+        match self {
+            Self::Auto => unicode_bidi::get_base_direction(text) == Direction::Rtl,
+            Self::LeftToRight => false,
+            Self::RightToLeft => true,
+        }
+    }
+}
+
+/// Whether `text` holds any letters written right to left.
+pub(crate) fn has_rtl(text: &str) -> bool {
+    ParagraphBidiInfo::new(text, None).has_rtl()
+}
+
+/// Alignment of a paragraph written in the given direction.
+pub(crate) fn alignment(rtl: bool) -> Alignment {
+    if rtl {
+        Alignment::Right
+    } else {
+        Alignment::Left
+    }
+}
+
+/// A line in the order its characters appear on screen.
+pub(crate) fn display_order(line: &str, rtl: bool) -> String {
+    let info = ParagraphBidiInfo::new(line, Some(base_level(rtl)));
+    if info.is_pure_ltr && !rtl {
+        return line.to_string();
+    }
+    info.reorder_line(0..line.len()).into_owned()
+}
+
+/// Each line of `text` in display order, `text` being one line per line break.
+pub(crate) fn display_lines(text: &str, rtl: bool) -> String {
+    text.split('\n')
+        .map(|line| display_order(line, rtl))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Wrap `text` to `width` columns at spaces, then put each line in display order.
+pub(crate) fn wrap(text: &str, width: u16, rtl: bool) -> Vec<String> {
+    let width = usize::from(width.max(1));
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut line_width = 0;
+        for word in paragraph.split_whitespace() {
+            let word_width: usize = word.chars().filter_map(|c| c.width()).sum();
+            if line_width > 0 && line_width + 1 + word_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            if line_width > 0 {
+                line.push(' ');
+                line_width += 1;
+            }
+            // Words longer than a line are split wherever the line is full
+            for c in word.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if line_width + char_width > width && line_width > 0 {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(c);
+                line_width += char_width;
+            }
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.iter().map(|line| display_order(line, rtl)).collect()
+}
+
+/// Terminal column of the cursor at byte offset `cursor` of `line`, counted from
+/// the left of the line in display order.
+///
+/// Within right-to-left letters the cursor sits right of the character after it, so
+/// it moves left while typing.
+pub(crate) fn cursor_column(line: &str, cursor: usize, rtl: bool) -> u16 {
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    if chars.is_empty() {
+        return 0;
+    }
+    let info = ParagraphBidiInfo::new(line, Some(base_level(rtl)));
+    let levels = info.reordered_levels_per_char(0..line.len());
+
+    // Left edge of each character in display order
+    let mut left = vec![0; chars.len()];
+    let mut x = 0;
+    for logical in ParagraphBidiInfo::reorder_visual(&levels) {
+        left[logical] = x;
+        x += chars[logical].1.width().unwrap_or(0);
+    }
+
+    let column = match chars.iter().position(|&(i, _)| i >= cursor) {
+        Some(next) if levels[next].is_rtl() => left[next] + chars[next].1.width().unwrap_or(0),
+        Some(next) => left[next],
+        None => {
+            let last = chars.len() - 1;
+            if levels[last].is_rtl() {
+                left[last]
+            } else {
+                left[last] + chars[last].1.width().unwrap_or(0)
+            }
+        }
+    };
+    column as u16
+}
+
+fn base_level(rtl: bool) -> Level {
+    if rtl { Level::rtl() } else { Level::ltr() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn right_to_left_text_is_shown_in_display_order() {
+        assert!(TextDirection::Auto.is_rtl("שלום, world"));
+        assert!(!TextDirection::Auto.is_rtl("Hello שלום"));
+        assert!(TextDirection::RightToLeft.is_rtl("Hello"));
+
+        assert_eq!(display_order("שלום", true), "םולש");
+        assert_eq!(display_order("שלום abc", true), "abc םולש");
+        assert_eq!(display_order("abc", false), "abc");
+        assert_eq!(wrap("אחת שתיים שלוש", 9, true), vec!["םייתש תחא", "שולש"]);
+
+        // Typing "שלום" leaves the cursor left of the word, before the first letter
+        // it sits at the right edge
+        assert_eq!(cursor_column("שלום", "שלום".len(), true), 0);
+        assert_eq!(cursor_column("שלום", 0, true), 4);
+        assert_eq!(cursor_column("ab", 1, false), 1);
+        assert_eq!(cursor_column("", 0, true), 0);
+    }
+}
@@ -0,0 +1,380 @@
+//! Key bindings of the wizard.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+
+/// Something the user can do with a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Highlight the previous option, row or suggestion.
+    Up,
+    /// Highlight the next option, row or suggestion.
+    Down,
+    /// Move left on a rating scale or matrix row, or the cursor in text.
+    Left,
+    /// Move right on a rating scale or matrix row, or the cursor in text.
+    Right,
+    /// Show the previous page of a choice list.
+    PageUp,
+    /// Show the next page of a choice list.
+    PageDown,
+    /// Toggle the highlighted option of a multi-select.
+    Toggle,
+    /// Accept the highlighted completion.
+    Complete,
+    /// Submit the answer.
+    Confirm,
+    /// Go back to the previous question.
+    Back,
+    /// Cancel the survey.
+    Cancel,
+    /// Open the comment editor.
+    Comment,
+    /// Start typing (modal keymaps only).
+    Insert,
+    /// Stop typing (modal keymaps only).
+    Normal,
+}
+
+impl Action {
+    /// The key of the standard keymap that does the same, fed to the event loop.
+    fn standard_key(self) -> KeyEvent {
+        let code = match self {
+            Self::Up => KeyCode::Up,
+            Self::Down => KeyCode::Down,
+            Self::Left => KeyCode::Left,
+            Self::Right => KeyCode::Right,
+            Self::PageUp => KeyCode::PageUp,
+            Self::PageDown => KeyCode::PageDown,
+            Self::Toggle => KeyCode::Char(' '),
+            Self::Complete => KeyCode::Tab,
+            Self::Confirm => KeyCode::Enter,
+            Self::Back => return KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL),
+            Self::Comment => return KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+            Self::Cancel | Self::Insert | Self::Normal => KeyCode::Esc,
+        };
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+}
+
+/// A key with its modifiers, e.g. `Key::ctrl('d')`.
+///
+/// Shift is part of the character for letter keys: `Key::from('G')` is Shift+G.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// A key pressed without modifiers.
+    pub fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// A key pressed with Ctrl.
+    pub fn ctrl(key: impl Into<Key>) -> Self {
+        let key = key.into();
+        Self {
+            code: key.code,
+            modifiers: key.modifiers | KeyModifiers::CONTROL,
+        }
+    }
+
+    fn of_event(event: &KeyEvent) -> Self {
+        let mut modifiers = event.modifiers;
+        if matches!(event.code, KeyCode::Char(_)) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+impl From<KeyCode> for Key {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code)
+    }
+}
+
+impl From<char> for Key {
+    fn from(c: char) -> Self {
+        Self::new(KeyCode::Char(c))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("Alt+")?;
+        }
+        match self.code {
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{c}"),
+            code => write!(f, "{code}"),
+        }
+    }
+}
+
+/// What a key press means in the current mode.
+pub(crate) enum Resolved {
+    /// A bound action.
+    Action(Action),
+    /// An unbound key that edits text.
+    Edit(KeyEvent),
+}
+
+impl Resolved {
+    /// The key the event loop handles, with actions mapped to their standard keys.
+    pub(crate) fn key(&self) -> KeyEvent {
+        match self {
+            Self::Action(action) => action.standard_key(),
+            Self::Edit(key) => *key,
+        }
+    }
+}
+
+/// Key bindings of the wizard.
+///
+/// The default keymap uses the arrow keys, Enter and Esc. [`KeyMap::vim`] is
+/// modal: in normal mode letters move and act, `i` starts typing and Esc
+/// stops. Keys bound to an action are not typed; Ctrl+C and F2 always cancel
+/// and switch high-contrast mode.
+///
+/// ```rust,ignore
+/// let keymap = KeyMap::default()
+///     .unbind(KeyCode::Esc)
+///     .bind(Key::ctrl('q'), Action::Cancel);
+/// let backend = RatatuiBackend::new().with_keymap(keymap);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMap {
+    /// Bindings while typing, the only ones of a keymap without modes.
+    typing: Vec<(Key, Action)>,
+    /// Bindings in normal mode, `None` for a keymap without modes.
+    normal: Option<Vec<(Key, Action)>>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            typing: vec![
+                (Key::new(KeyCode::Up), Action::Up),
+                (Key::new(KeyCode::Down), Action::Down),
+                (Key::new(KeyCode::Left), Action::Left),
+                (Key::new(KeyCode::Right), Action::Right),
+                (Key::new(KeyCode::PageUp), Action::PageUp),
+                (Key::new(KeyCode::PageDown), Action::PageDown),
+                (Key::from(' '), Action::Toggle),
+                (Key::new(KeyCode::Tab), Action::Complete),
+                (Key::new(KeyCode::Enter), Action::Confirm),
+                (Key::ctrl(KeyCode::Left), Action::Back),
+                (Key::new(KeyCode::Esc), Action::Cancel),
+                (Key::ctrl('n'), Action::Comment),
+            ],
+            normal: None,
+        }
+    }
+}
+
+impl KeyMap {
+    /// Vim-style bindings: `j`/`k` and `h`/`l` move, Ctrl+D/Ctrl+U page, Space
+    /// toggles, `i` or `a` start typing and Esc stops, Backspace goes back and `q`
+    /// cancels. While typing, Enter still submits and the arrow keys still move.
+    pub fn vim() -> Self {
+        let typing = vec![
+            (Key::new(KeyCode::Esc), Action::Normal),
+            (Key::new(KeyCode::Enter), Action::Confirm),
+            (Key::new(KeyCode::Up), Action::Up),
+            (Key::new(KeyCode::Down), Action::Down),
+            (Key::new(KeyCode::Left), Action::Left),
+            (Key::new(KeyCode::Right), Action::Right),
+            (Key::new(KeyCode::Tab), Action::Complete),
+            (Key::ctrl(KeyCode::Left), Action::Back),
+            (Key::ctrl('n'), Action::Comment),
+        ];
+        let normal = vec![
+            (Key::from('k'), Action::Up),
+            (Key::new(KeyCode::Up), Action::Up),
+            (Key::from('j'), Action::Down),
+            (Key::new(KeyCode::Down), Action::Down),
+            (Key::from('h'), Action::Left),
+            (Key::new(KeyCode::Left), Action::Left),
+            (Key::from('l'), Action::Right),
+            (Key::new(KeyCode::Right), Action::Right),
+            (Key::ctrl('u'), Action::PageUp),
+            (Key::new(KeyCode::PageUp), Action::PageUp),
+            (Key::ctrl('d'), Action::PageDown),
+            (Key::new(KeyCode::PageDown), Action::PageDown),
+            (Key::from(' '), Action::Toggle),
+            (Key::new(KeyCode::Tab), Action::Complete),
+            (Key::new(KeyCode::Enter), Action::Confirm),
+            (Key::new(KeyCode::Backspace), Action::Back),
+            (Key::ctrl(KeyCode::Left), Action::Back),
+            (Key::from('q'), Action::Cancel),
+            (Key::ctrl('n'), Action::Comment),
+            (Key::from('i'), Action::Insert),
+            (Key::from('a'), Action::Insert),
+        ];
+        Self {
+            typing,
+            normal: Some(normal),
+        }
+    }
+
+    /// Whether the keymap has a normal mode besides typing.
+    pub fn is_modal(&self) -> bool {
+        self.normal.is_some()
+    }
+
+    /// Bind `key` to `action`, replacing what it did before. In a modal keymap
+    /// this binds the key in normal mode, see [`KeyMap::bind_typing`].
+    pub fn bind(mut self, key: impl Into<Key>, action: Action) -> Self {
+        let layer = self.normal.as_mut().unwrap_or(&mut self.typing);
+        insert_binding(layer, key.into(), action);
+        self
+    }
+
+    /// Bind `key` to `action` while typing, replacing what it did before.
+    pub fn bind_typing(mut self, key: impl Into<Key>, action: Action) -> Self {
+        insert_binding(&mut self.typing, key.into(), action);
+        self
+    }
+
+    /// Remove the bindings of `key` in every mode.
+    pub fn unbind(mut self, key: impl Into<Key>) -> Self {
+        let key = key.into();
+        self.typing.retain(|(bound, _)| *bound != key);
+        if let Some(normal) = self.normal.as_mut() {
+            normal.retain(|(bound, _)| *bound != key);
+        }
+        self
+    }
+
+    /// Keys bound to `action`, while typing or not.
+    pub fn keys(&self, action: Action, typing: bool) -> impl Iterator<Item = Key> + '_ {
+        self.layer(typing)
+            .iter()
+            .filter(move |(_, bound)| *bound == action)
+            .map(|(key, _)| *key)
+    }
+
+    fn layer(&self, typing: bool) -> &[(Key, Action)] {
+        match &self.normal {
+            Some(normal) if !typing => normal,
+            _ => &self.typing,
+        }
+    }
+
+    /// What `event` means. Unbound keys edit text while typing and are
+    /// ignored otherwise.
+    pub(crate) fn resolve(&self, event: &KeyEvent, typing: bool) -> Option<Resolved> {
+        let key = Key::of_event(event);
+        if let Some((_, action)) = self.layer(typing).iter().find(|(bound, _)| *bound == key) {
+            return Some(Resolved::Action(*action));
+        }
+        let edits = match key.code {
+            KeyCode::Char(_) => key.modifiers.is_empty(),
+            KeyCode::Backspace | KeyCode::Delete | KeyCode::Home | KeyCode::End => true,
+            _ => false,
+        };
+        (edits && (typing || !self.is_modal())).then_some(Resolved::Edit(*event))
+    }
+}
+
+fn insert_binding(layer: &mut Vec<(Key, Action)>, key: Key, action: Action) {
+    layer.retain(|(bound, _)| *bound != key);
+    layer.push((key, action));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn action(keymap: &KeyMap, event: KeyEvent, typing: bool) -> Option<Action> {
+        match keymap.resolve(&event, typing) {
+            Some(Resolved::Action(action)) => Some(action),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn default_keymap_types_unbound_letters() {
+        let keymap = KeyMap::default();
+        assert_eq!(
+            action(&keymap, press(KeyCode::Esc), false),
+            Some(Action::Cancel)
+        );
+        assert!(matches!(
+            keymap.resolve(&press(KeyCode::Char('j')), false),
+            Some(Resolved::Edit(_))
+        ));
+        assert!(keymap.resolve(&press(KeyCode::F(5)), false).is_none());
+    }
+
+    #[test]
+    fn vim_keymap_has_modes() {
+        let keymap = KeyMap::vim();
+        assert_eq!(
+            action(&keymap, press(KeyCode::Char('j')), false),
+            Some(Action::Down)
+        );
+        assert!(keymap.resolve(&press(KeyCode::Char('x')), false).is_none());
+        assert_eq!(
+            action(&keymap, press(KeyCode::Char('i')), false),
+            Some(Action::Insert)
+        );
+        assert!(matches!(
+            keymap.resolve(&press(KeyCode::Char('j')), true),
+            Some(Resolved::Edit(_))
+        ));
+        assert_eq!(
+            action(&keymap, press(KeyCode::Esc), true),
+            Some(Action::Normal)
+        );
+        let shifted = KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT);
+        assert!(matches!(
+            keymap.resolve(&shifted, true),
+            Some(Resolved::Edit(_))
+        ));
+    }
+
+    #[test]
+    fn rebinding_replaces_the_old_action() {
+        let keymap = KeyMap::default()
+            .unbind(KeyCode::Esc)
+            .bind('q', Action::Cancel)
+            .bind(KeyCode::Enter, Action::Toggle);
+        assert!(keymap.resolve(&press(KeyCode::Esc), false).is_none());
+        assert_eq!(
+            action(&keymap, press(KeyCode::Char('q')), false),
+            Some(Action::Cancel)
+        );
+        assert_eq!(keymap.keys(Action::Confirm, false).count(), 0);
+        assert_eq!(
+            keymap.keys(Action::Back, false).next().unwrap().to_string(),
+            "Ctrl+←"
+        );
+    }
+}
@@ -10,10 +10,14 @@
 //!
 //! - Rich TUI with panels and borders
 //! - Progress indicator showing current question
-//! - Keyboard navigation (arrow keys, Enter, Esc)
+//! - Keyboard navigation (arrow keys, Enter, Esc), rebindable, with a vim keymap
+//! - Typing filters select lists, matched letters are underlined
 //! - Real-time validation with error display
-//! - Customizable color themes
+//! - Bracketed paste: pasted text is inserted at once, line breaks kept only in multiline answers
+//! - Customizable color themes, with named presets and TOML/JSON palette files
 //! - Support for all question types (input, select, multi-select, confirm, etc.)
+//! - Right-to-left prompts and answers, e.g. in Arabic or Hebrew, with an optional mirrored layout
+//! - [`WizardWidget`] to embed the wizard as a pane of an existing ratatui application
 //!
 //! ## Example
 //!
@@ -40,5 +44,14 @@
 //! ```
 
 mod backend;
+mod bidi;
+mod keymap;
+mod theme;
+mod widget;
 
-pub use backend::{RatatuiBackend, RatatuiError, Theme};
+pub use backend::{DEFAULT_PAGE_SIZE, RatatuiBackend, RatatuiError};
+pub use bidi::TextDirection;
+pub use crossterm::event::{KeyCode, KeyModifiers};
+pub use keymap::{Action, Key, KeyMap};
+pub use theme::{PRESETS, Theme, ThemeError};
+pub use widget::{WizardStatus, WizardWidget};
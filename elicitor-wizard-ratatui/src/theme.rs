@@ -0,0 +1,203 @@
+//! Color themes for the wizard, built in code, picked by name or loaded from a file.
+
+use elicitor::{Palette, parse_palette};
+use ratatui::style::Color;
+use std::{fs, path::Path, str::FromStr};
+
+pub use elicitor::ThemeError;
+
+/// Names of the built-in presets accepted by [`Theme::named`].
+pub use elicitor::THEME_PRESETS as PRESETS;
+
+/// Color theme for the TUI.
+///
+/// Besides building one in code, a theme can be picked by name with
+/// [`Theme::named`] or parsed from a TOML or JSON palette:
+///
+/// ```toml
+/// base = "solarized"     # optional preset to start from
+/// primary = "#268bd2"
+/// error = "light-red"
+/// ```
+///
+/// Colors are hex (`#rrggbb`), ANSI names or 256-color indices. Keys left out
+/// keep the color of the base preset (`dark` if none is given).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub primary: Color,
+    pub secondary: Color,
+    pub background: Color,
+    pub text: Color,
+    pub highlight: Color,
+    pub error: Color,
+    pub success: Color,
+    pub border: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            primary: Color::Cyan,
+            secondary: Color::Blue,
+            background: Color::Reset,
+            text: Color::White,
+            highlight: Color::Yellow,
+            error: Color::Red,
+            success: Color::Green,
+            border: Color::Gray,
+        }
+    }
+}
+
+impl Theme {
+    /// Bright colors on black, used while high-contrast mode is on (F2).
+    pub fn high_contrast() -> Self {
+        Self {
+            primary: Color::White,
+            secondary: Color::LightCyan,
+            background: Color::Black,
+            text: Color::White,
+            highlight: Color::LightYellow,
+            error: Color::LightRed,
+            success: Color::LightGreen,
+            border: Color::White,
+        }
+    }
+
+    /// Dark text on a white background.
+    pub fn light() -> Self {
+        Self {
+            primary: Color::Rgb(0x00, 0x5f, 0xaf),
+            secondary: Color::Rgb(0x5f, 0x00, 0xaf),
+            background: Color::Rgb(0xff, 0xff, 0xff),
+            text: Color::Rgb(0x1c, 0x1c, 0x1c),
+            highlight: Color::Rgb(0xaf, 0x5f, 0x00),
+            error: Color::Rgb(0xc0, 0x00, 0x00),
+            success: Color::Rgb(0x00, 0x87, 0x00),
+            border: Color::Rgb(0x80, 0x80, 0x80),
+        }
+    }
+
+    /// The dark Solarized palette.
+    pub fn solarized() -> Self {
+        Self {
+            primary: Color::Rgb(0x26, 0x8b, 0xd2),
+            secondary: Color::Rgb(0x2a, 0xa1, 0x98),
+            background: Color::Rgb(0x00, 0x2b, 0x36),
+            text: Color::Rgb(0x83, 0x94, 0x96),
+            highlight: Color::Rgb(0xb5, 0x89, 0x00),
+            error: Color::Rgb(0xdc, 0x32, 0x2f),
+            success: Color::Rgb(0x85, 0x99, 0x00),
+            border: Color::Rgb(0x58, 0x6e, 0x75),
+        }
+    }
+
+    /// Look up a built-in preset, see [`PRESETS`].
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::default()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Load a theme from a `.toml` or `.json` file.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let text = fs::read_to_string(path)?;
+        text.parse()
+    }
+}
+
+impl Palette for Theme {
+    type Color = Color;
+
+    fn preset(name: &str) -> Option<Self> {
+        Self::named(name)
+    }
+
+    fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        match key {
+            "primary" => Some(&mut self.primary),
+            "secondary" => Some(&mut self.secondary),
+            "background" => Some(&mut self.background),
+            "text" => Some(&mut self.text),
+            "highlight" => Some(&mut self.highlight),
+            "error" => Some(&mut self.error),
+            "success" => Some(&mut self.success),
+            "border" => Some(&mut self.border),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = ThemeError;
+
+    /// Parse a palette, as JSON if it starts with `{` and as TOML otherwise.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_palette(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_preset_has_a_name() {
+        for name in PRESETS {
+            assert!(Theme::named(name).is_some(), "{name}");
+        }
+        assert!(Theme::named("neon").is_none());
+        assert_eq!(Theme::named("dark"), Some(Theme::default()));
+    }
+
+    #[test]
+    fn toml_overrides_the_base_preset() {
+        let theme: Theme = "base = \"solarized\"\nprimary = \"#ff0000\"\nerror = \"light-red\"\n"
+            .parse()
+            .unwrap();
+        assert_eq!(theme.primary, Color::Rgb(0xff, 0, 0));
+        assert_eq!(theme.error, Color::LightRed);
+        assert_eq!(theme.background, Theme::solarized().background);
+    }
+
+    #[test]
+    fn json_starts_from_the_default_theme() {
+        let theme: Theme = r##"{ "highlight": "#00ff00", "selected_bg": "blue" }"##
+            .parse()
+            .unwrap();
+        assert_eq!(theme.highlight, Color::Rgb(0, 0xff, 0));
+        assert_eq!(theme.text, Theme::default().text);
+    }
+
+    #[test]
+    fn bad_palettes_are_rejected() {
+        assert!(matches!(
+            "primary = \"not a color\"".parse::<Theme>(),
+            Err(ThemeError::InvalidColor { key, .. }) if key == "primary"
+        ));
+        assert!(matches!(
+            "accent = \"red\"".parse::<Theme>(),
+            Err(ThemeError::UnknownKey(key)) if key == "accent"
+        ));
+        assert!(matches!(
+            "selected_bg = \"not a color\"".parse::<Theme>(),
+            Err(ThemeError::InvalidColor { key, .. }) if key == "selected_bg"
+        ));
+        assert!(matches!(
+            "base = \"neon\"".parse::<Theme>(),
+            Err(ThemeError::UnknownPreset(_))
+        ));
+        assert!(matches!(
+            "primary = 3".parse::<Theme>(),
+            Err(ThemeError::InvalidColor { .. })
+        ));
+        assert!(matches!(
+            "[1, 2]".parse::<Theme>(),
+            Err(ThemeError::Toml(_))
+        ));
+    }
+}
@@ -0,0 +1,342 @@
+//! The wizard as a pane of an existing ratatui application.
+
+use crossterm::event::{Event, KeyEvent};
+use elicitor::{
+    Checkpoint, ResponsePath, ResponseValue, Responses, Survey, SurveyDefinition, TextChecker,
+};
+use ratatui::{Frame, layout::Rect};
+use std::sync::Arc;
+
+use crate::backend::{WizardState, draw};
+use crate::bidi::TextDirection;
+use crate::keymap::KeyMap;
+use crate::theme::Theme;
+
+type Validator = Box<dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>>;
+
+/// Whether an embedded wizard is still asking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStatus {
+    /// Questions are left.
+    Running,
+    /// All questions are answered.
+    Completed,
+    /// The user cancelled.
+    Cancelled,
+}
+
+/// The wizard as a widget inside an application's own layout and event loop.
+///
+/// Unlike [`RatatuiBackend`](crate::RatatuiBackend), it leaves the terminal to
+/// the application: render it into any [`Rect`] and pass it the events of
+/// the pane.
+///
+/// ```rust,ignore
+/// let mut wizard = WizardWidget::for_survey::<Settings>().with_title("Settings");
+/// loop {
+///     terminal.draw(|frame| wizard.render(frame, panes[1]))?;
+///     match wizard.handle_event(&event::read()?) {
+///         WizardStatus::Running => {}
+///         WizardStatus::Completed => break Some(Settings::from_responses(wizard.responses())),
+///         WizardStatus::Cancelled => break None,
+///     }
+/// }
+/// ```
+pub struct WizardWidget {
+    state: WizardState,
+    prelude: Option<String>,
+    validate: Validator,
+}
+
+impl WizardWidget {
+    /// A wizard asking the questions of `definition`. Questions naming a validator
+    /// pass until one is given with [`with_validator`](Self::with_validator).
+    pub fn new(definition: &SurveyDefinition) -> Self {
+        let mut state = WizardState::new(definition, Theme::default(), "Survey".to_string());
+        state.set_high_contrast(elicitor::high_contrast_enabled());
+        state.start();
+        Self {
+            state,
+            prelude: definition.prelude.clone(),
+            validate: Box::new(|_, _, _| Ok(())),
+        }
+    }
+
+    /// A wizard for a `#[derive(Survey)]` type, checking answers with its validators.
+    pub fn for_survey<T: Survey + 'static>() -> Self {
+        Self::new(&T::survey()).with_validator(T::validate_field)
+    }
+
+    /// Check each answer with `validate` before moving on.
+    pub fn with_validator(
+        mut self,
+        validate: impl Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.validate = Box::new(validate);
+        self
+    }
+
+    /// Set the title shown at the top of the wizard.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        let title = title.into();
+        self.state.title = match &self.prelude {
+            Some(prelude) => format!("{title}\n{prelude}"),
+            None => title,
+        };
+        self
+    }
+
+    /// Set a custom color theme.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.state.custom_theme = theme;
+        self.state.set_high_contrast(self.state.high_contrast);
+        self
+    }
+
+    /// Show choice lists `page_size` options at a time (at least one).
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.state.page_size = page_size.max(1);
+        self
+    }
+
+    /// Use other key bindings, e.g. [`KeyMap::vim`].
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.state.keymap = keymap;
+        self
+    }
+
+    /// Offer "Save draft" when the user leaves early, saving the answers so far
+    /// to `checkpoint`.
+    pub fn with_checkpoint(mut self, checkpoint: impl Checkpoint + 'static) -> Self {
+        self.state.checkpoint = Some(Arc::new(checkpoint));
+        self
+    }
+
+    /// Write prompts and answers in `direction`, by default that of each prompt.
+    pub fn with_text_direction(mut self, direction: TextDirection) -> Self {
+        self.state.direction = direction;
+        self
+    }
+
+    /// Mirror the progress bar and help panels for right-to-left readers.
+    pub fn with_mirrored_layout(mut self, mirrored: bool) -> Self {
+        self.state.mirrored = mirrored;
+        self
+    }
+
+    /// Check multiline answers with `checker`; submitting the same text again keeps it.
+    pub fn with_text_checker(mut self, checker: impl TextChecker + 'static) -> Self {
+        self.state.text_checker = Some(Arc::new(checker));
+        self
+    }
+
+    /// Handle an event of the pane: key presses and pasted text.
+    pub fn handle_event(&mut self, event: &Event) -> WizardStatus {
+        match event {
+            Event::Key(key) => return self.handle_key(*key),
+            Event::Paste(text) => self.state.paste(text),
+            _ => {}
+        }
+        self.status()
+    }
+
+    /// Handle a key press.
+    pub fn handle_key(&mut self, key: KeyEvent) -> WizardStatus {
+        if self.status() == WizardStatus::Running {
+            self.state.handle_key(key, &*self.validate);
+        }
+        self.status()
+    }
+
+    /// Whether the wizard is still asking.
+    pub fn status(&self) -> WizardStatus {
+        if self.state.cancelled {
+            WizardStatus::Cancelled
+        } else if self.state.complete {
+            WizardStatus::Completed
+        } else {
+            WizardStatus::Running
+        }
+    }
+
+    /// The answers given so far.
+    pub fn responses(&self) -> &Responses {
+        &self.state.responses
+    }
+
+    /// Take the answers out of the wizard.
+    pub fn into_responses(self) -> Responses {
+        self.state.responses
+    }
+
+    /// Draw the wizard into `area`; once completed, the completion screen.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        draw(frame, &self.state, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use elicitor::{InputQuestion, IntQuestion, OneOfQuestion, Question, QuestionKind, Variant};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn press(wizard: &mut WizardWidget, code: KeyCode) -> WizardStatus {
+        wizard.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn type_text(wizard: &mut WizardWidget, text: &str) {
+        for c in text.chars() {
+            press(wizard, KeyCode::Char(c));
+        }
+    }
+
+    fn definition() -> SurveyDefinition {
+        SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "age",
+                "Age?",
+                QuestionKind::Int(IntQuestion::with_bounds_and_validator(
+                    None,
+                    None,
+                    Some("adult".to_string()),
+                )),
+            ),
+        ])
+    }
+
+    #[test]
+    fn widget_renders_into_a_pane_and_takes_keys() {
+        let mut wizard = WizardWidget::new(&definition()).with_validator(|value, _, path| {
+            match (path.as_str(), value) {
+                ("age", ResponseValue::Int(age)) if *age < 18 => Err("Too young".to_string()),
+                _ => Ok(()),
+            }
+        });
+
+        let pane = Rect::new(10, 2, 40, 16);
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal.draw(|frame| wizard.render(frame, pane)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut text = String::new();
+        for position in buffer.area.positions() {
+            let symbol = buffer[position].symbol();
+            if pane.contains(position) {
+                text.push_str(symbol);
+            } else {
+                assert_eq!(symbol, " ", "drawn outside the pane at {position:?}");
+            }
+        }
+        assert!(text.contains("Name?"));
+
+        type_text(&mut wizard, "Ada");
+        assert_eq!(press(&mut wizard, KeyCode::Enter), WizardStatus::Running);
+        type_text(&mut wizard, "12");
+        assert_eq!(press(&mut wizard, KeyCode::Enter), WizardStatus::Running);
+        press(&mut wizard, KeyCode::Backspace);
+        press(&mut wizard, KeyCode::Backspace);
+        type_text(&mut wizard, "36");
+        assert_eq!(press(&mut wizard, KeyCode::Enter), WizardStatus::Completed);
+
+        let responses = wizard.into_responses();
+        assert_eq!(
+            responses.get(&ResponsePath::new("name")),
+            Some(&ResponseValue::String("Ada".to_string()))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("age")),
+            Some(&ResponseValue::Int(36))
+        );
+    }
+
+    #[test]
+    fn right_to_left_prompts_and_answers_are_mirrored() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "name",
+            "מה שמך?",
+            QuestionKind::Input(InputQuestion::new()),
+        )]);
+        let mut wizard = WizardWidget::new(&definition).with_mirrored_layout(true);
+        type_text(&mut wizard, "דנה");
+        // Right moves back towards the start of the answer, shown on the right
+        press(&mut wizard, KeyCode::Right);
+        type_text(&mut wizard, "ו");
+
+        let pane = Rect::new(0, 0, 40, 20);
+        let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+        terminal.draw(|frame| wizard.render(frame, pane)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..pane.height)
+            .map(|y| (0..pane.width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+
+        // Shown in display order, aligned to the right of their boxes
+        let prompt = rows.iter().find(|row| row.contains("?ךמש המ")).unwrap();
+        assert!(prompt.trim_end().ends_with("?ךמש המ│"));
+        let input = rows.iter().position(|row| row.contains("הונד")).unwrap();
+        assert!(rows[input].trim_end().ends_with("הונד│"));
+        // The cursor sits after the inserted letter, left of the last one
+        let cursor = terminal.get_cursor_position().unwrap();
+        assert_eq!(usize::from(cursor.y), input);
+        let column = rows[input].chars().position(|c| c == 'ה').unwrap();
+        assert_eq!(usize::from(cursor.x), column + 1);
+
+        assert_eq!(press(&mut wizard, KeyCode::Enter), WizardStatus::Completed);
+        assert_eq!(
+            wizard.responses().get(&ResponsePath::new("name")),
+            Some(&ResponseValue::String("דנוה".to_string()))
+        );
+    }
+
+    #[test]
+    fn help_of_the_highlighted_option_is_shown_next_to_it() {
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "role",
+            "Role?",
+            QuestionKind::OneOf(OneOfQuestion::new(vec![
+                Variant::unit("Warrior").with_help("Fights up close"),
+                Variant::unit("Mage").with_help("Casts spells"),
+            ])),
+        )]);
+        let mut wizard = WizardWidget::new(&definition);
+        let rows = |wizard: &WizardWidget| {
+            let mut terminal = Terminal::new(TestBackend::new(50, 20)).unwrap();
+            terminal
+                .draw(|frame| wizard.render(frame, Rect::new(0, 0, 50, 20)))
+                .unwrap();
+            let buffer = terminal.backend().buffer().clone();
+            (0..20)
+                .map(|y| (0..50).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+
+        let shown = rows(&wizard);
+        assert!(
+            shown
+                .iter()
+                .any(|row| row.contains("Warrior — Fights up close"))
+        );
+        assert!(!shown.iter().any(|row| row.contains("Casts spells")));
+
+        press(&mut wizard, KeyCode::Down);
+        let shown = rows(&wizard);
+        assert!(shown.iter().any(|row| row.contains("Mage — Casts spells")));
+        assert!(!shown.iter().any(|row| row.contains("Fights up close")));
+    }
+
+    #[test]
+    fn widget_reports_cancelling() {
+        let mut wizard = WizardWidget::new(&definition());
+        assert_eq!(press(&mut wizard, KeyCode::Esc), WizardStatus::Running);
+        assert_eq!(
+            press(&mut wizard, KeyCode::Char('d')),
+            WizardStatus::Cancelled
+        );
+        assert_eq!(
+            press(&mut wizard, KeyCode::Char('x')),
+            WizardStatus::Cancelled
+        );
+    }
+}
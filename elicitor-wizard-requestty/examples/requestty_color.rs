@@ -0,0 +1,20 @@
+//! Color question example
+//!
+//! Demonstrates:
+//! - elicitor::Color fields
+//! - Hex input like #1e90ff or #fff, shown with a swatch
+//! - Suggesting colors with with_suggestions
+//!
+//! Run with: cargo run --example requestty_color
+
+use elicitor_wizard_requestty::RequesttyBackend;
+use example_surveys::EditorTheme;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RequesttyBackend::new();
+    let result = EditorTheme::builder()
+        .with_suggestions(&EditorTheme::dark())
+        .run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
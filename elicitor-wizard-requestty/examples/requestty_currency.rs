@@ -0,0 +1,17 @@
+//! Currency question example
+//!
+//! Demonstrates:
+//! - #[currency("USD")] on integer fields holding cents
+//! - Amounts like 1,250.50 are parsed and shown with the symbol
+//!
+//! Run with: cargo run --example requestty_currency
+
+use elicitor_wizard_requestty::RequesttyBackend;
+use example_surveys::MortgageApplication;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RequesttyBackend::new();
+    let result = MortgageApplication::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
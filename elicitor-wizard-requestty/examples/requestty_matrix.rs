@@ -0,0 +1,17 @@
+//! Matrix question example
+//!
+//! Demonstrates:
+//! - HashMap<RowEnum, ColumnEnum> for a grid question
+//! - One selection per row
+//!
+//! Run with: cargo run --example requestty_matrix
+
+use elicitor_wizard_requestty::RequesttyBackend;
+use example_surveys::CourseEvaluation;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RequesttyBackend::new();
+    let result = CourseEvaluation::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
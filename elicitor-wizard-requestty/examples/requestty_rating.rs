@@ -0,0 +1,22 @@
+//! Rating scale example
+//!
+//! Demonstrates:
+//! - #[rating(1..=5)] for a star rating
+//! - #[rating(0..=10)] for a numeric scale
+//! - #[likert("low".."high")] for a Likert item with anchor labels
+//! - #[allow_comment] for an optional remark on the overall rating
+//!
+//! Run with: cargo run --example requestty_rating
+
+use elicitor_wizard_requestty::RequesttyBackend;
+use example_surveys::CourseFeedback;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RequesttyBackend::new();
+    let (result, responses) = CourseFeedback::builder().run_with_responses(backend)?;
+    println!("{result:#?}");
+    for (path, comment) in responses.comments() {
+        println!("Comment on {path}: {comment}");
+    }
+    Ok(())
+}
@@ -0,0 +1,17 @@
+//! Runtime choices example
+//!
+//! Demonstrates:
+//! - #[choices_from] attribute for options that are only known at interview time
+//! - Choice functions returning plain values or (value, label) pairs
+//!
+//! Run with: cargo run --example requestty_runtime_choices
+
+use elicitor_wizard_requestty::RequesttyBackend;
+use example_surveys::FilePicker;
+
+fn main() -> anyhow::Result<()> {
+    let backend = RequesttyBackend::new();
+    let result = FilePicker::builder().run(backend)?;
+    println!("{result:#?}");
+    Ok(())
+}
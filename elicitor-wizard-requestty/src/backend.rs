@@ -1,17 +1,21 @@
 //! Requestty backend implementation for SurveyBackend trait.
 
 use elicitor::{
-    DefaultValue, ListElementKind, Question, QuestionKind, ResponsePath, ResponseValue, Responses,
-    SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend, SurveyDefinition,
+    COMMENT_KEY, DefaultValue, ListElementKind, NoObserver, Question, QuestionKind, ResponsePath,
+    ResponseValue, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY, SurveyBackend,
+    SurveyDefinition, SurveyError, SurveyObserver, TextChecker, TextIssueSeverity,
 };
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Error type for the Requestty backend.
 #[derive(Debug, Error)]
 pub enum RequesttyError {
     /// User cancelled the survey (e.g., pressed Ctrl+C).
+    ///
+    /// Holds the answers given before cancelling.
     #[error("Survey cancelled by user")]
-    Cancelled,
+    Cancelled { partial: Box<Responses> },
 
     /// An error occurred during prompting.
     #[error("Prompt error: {0}")]
@@ -22,10 +26,39 @@ pub enum RequesttyError {
     UnexpectedAnswerType { expected: String, got: String },
 }
 
+impl RequesttyError {
+    /// The answers given before the user cancelled, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            _ => None,
+        }
+    }
+
+    /// A cancellation whose partial answers are filled in by `collect`.
+    fn cancelled() -> Self {
+        Self::Cancelled {
+            partial: Box::default(),
+        }
+    }
+}
+
+impl From<RequesttyError> for SurveyError {
+    fn from(err: RequesttyError) -> Self {
+        match err {
+            RequesttyError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
+        }
+    }
+}
+
 impl From<requestty::ErrorKind> for RequesttyError {
     fn from(err: requestty::ErrorKind) -> Self {
         match err {
-            requestty::ErrorKind::Interrupted => Self::Cancelled,
+            // Ctrl+C, Esc, or input closed
+            requestty::ErrorKind::Interrupted
+            | requestty::ErrorKind::Aborted
+            | requestty::ErrorKind::Eof => Self::cancelled(),
             _ => Self::PromptError(err.to_string()),
         }
     }
@@ -36,12 +69,135 @@ impl From<requestty::ErrorKind> for RequesttyError {
 /// This backend uses the `requestty` library to present questions
 /// to the user in a command-line interface.
 #[derive(Debug, Default, Clone)]
-pub struct RequesttyBackend;
+pub struct RequesttyBackend {
+    /// Optional checker run on multiline answers before they are accepted.
+    text_checker: Option<Arc<dyn TextChecker>>,
+    /// Rows shown at once in select and multi-select lists.
+    page_size: Option<usize>,
+    /// Stop at the first and last item instead of wrapping around.
+    stop_at_ends: bool,
+    /// Append "(Use arrow keys)" to single-choice prompts.
+    arrow_hint: bool,
+}
+
+/// The smallest page size requestty accepts.
+const MIN_PAGE_SIZE: usize = 5;
 
 impl RequesttyBackend {
     /// Create a new Requestty backend.
     pub const fn new() -> Self {
-        Self
+        Self {
+            text_checker: None,
+            page_size: None,
+            stop_at_ends: false,
+            arrow_hint: false,
+        }
+    }
+
+    /// Check multiline answers with the given checker before accepting them.
+    ///
+    /// Findings are printed after the editor closes and the user can choose to
+    /// revise the text or submit it as-is.
+    pub fn with_text_checker(mut self, checker: impl TextChecker + 'static) -> Self {
+        self.text_checker = Some(Arc::new(checker));
+        self
+    }
+
+    /// Show at most `rows` items of a select or multi-select list at once; the
+    /// rest scroll into view. Requestty shows 15 by default and needs at least 5,
+    /// so smaller values are raised to 5.
+    pub fn with_page_size(mut self, rows: usize) -> Self {
+        self.page_size = Some(rows.max(MIN_PAGE_SIZE));
+        self
+    }
+
+    /// Whether moving past the last item of a list wraps around to the first,
+    /// and the other way round. Lists wrap by default.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.stop_at_ends = !looping;
+        self
+    }
+
+    /// Append "(Use arrow keys)" to the prompt of single-choice lists.
+    ///
+    /// Multi-select lists always show requestty's own key hint.
+    pub fn with_arrow_hint(mut self) -> Self {
+        self.arrow_hint = true;
+        self
+    }
+
+    /// A single-choice list with the configured page size, looping and hint.
+    fn select(
+        &self,
+        name: impl Into<String>,
+        prompt: &str,
+    ) -> requestty::question::SelectBuilder<'static> {
+        let message = if self.arrow_hint {
+            format!("{prompt} (Use arrow keys)")
+        } else {
+            prompt.to_string()
+        };
+        let mut q = requestty::Question::select(name)
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(message)
+            .should_loop(!self.stop_at_ends);
+        if let Some(rows) = self.page_size {
+            q = q.page_size(rows);
+        }
+        q
+    }
+
+    /// A multi-select list with the configured page size and looping.
+    fn multi_select(
+        &self,
+        name: impl Into<String>,
+        prompt: &str,
+    ) -> requestty::question::MultiSelectBuilder<'static> {
+        let mut q = requestty::Question::multi_select(name)
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(prompt.to_string())
+            .should_loop(!self.stop_at_ends);
+        if let Some(rows) = self.page_size {
+            q = q.page_size(rows);
+        }
+        q
+    }
+
+    /// Run the text checker (if any) and ask whether to keep the text.
+    ///
+    /// Returns `true` if the answer should be accepted.
+    fn review_text(&self, text: &str) -> Result<bool, RequesttyError> {
+        let Some(checker) = &self.text_checker else {
+            return Ok(true);
+        };
+        let issues = checker.check(text);
+        if issues.is_empty() {
+            return Ok(true);
+        }
+
+        for issue in &issues {
+            let label = match issue.severity {
+                TextIssueSeverity::Suggestion => "Suggestion",
+                TextIssueSeverity::Warning => "Warning",
+            };
+            println!("  {label}: {issue}");
+        }
+
+        // Only default to submitting when there is nothing more serious than suggestions
+        let default = !issues.iter().any(|issue| issue.is_warning());
+        let q = requestty::Question::confirm("submit_anyway")
+            .on_esc(requestty::OnEsc::Terminate)
+            .message("Submit anyway?")
+            .default(default)
+            .build();
+
+        match requestty::prompt_one(q)? {
+            requestty::Answer::Bool(keep) => Ok(keep),
+            other => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "Bool".to_string(),
+                got: format!("{other:?}"),
+            }),
+        }
     }
 
     /// Ask a single question and store the response.
@@ -50,6 +206,7 @@ impl RequesttyBackend {
         question: &Question,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
         path_prefix: Option<&ResponsePath>,
     ) -> Result<(), RequesttyError> {
         let path = match path_prefix {
@@ -62,7 +219,7 @@ impl RequesttyBackend {
             // Convert path like "role" or "user_name" to "Role" or "User Name"
             path.as_str()
                 .split('.')
-                .last()
+                .next_back()
                 .unwrap_or("")
                 .split('_')
                 .map(|word| {
@@ -84,7 +241,11 @@ impl RequesttyBackend {
             return Ok(());
         }
 
-        match question.kind() {
+        if !matches!(question.kind(), QuestionKind::Unit | QuestionKind::AllOf(_)) {
+            observer.on_question_start(&path);
+        }
+
+        let answered = match question.kind() {
             QuestionKind::Unit => {
                 // Nothing to collect for unit types
                 Ok(())
@@ -99,6 +260,15 @@ impl RequesttyBackend {
                 validate,
             ),
 
+            QuestionKind::Autocomplete(autocomplete_q) => self.ask_autocomplete(
+                &path,
+                &prompt,
+                autocomplete_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
             QuestionKind::Multiline(multiline_q) => self.ask_multiline(
                 &path,
                 &prompt,
@@ -126,6 +296,51 @@ impl RequesttyBackend {
                 validate,
             ),
 
+            QuestionKind::Currency(currency_q) => self.ask_currency(
+                &path,
+                &prompt,
+                currency_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Color(color_q) => self.ask_color(
+                &path,
+                &prompt,
+                color_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Path(path_q) => self.ask_path(
+                &path,
+                &prompt,
+                path_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Rating(rating_q) => self.ask_rating(
+                &path,
+                &prompt,
+                rating_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
+            QuestionKind::Matrix(matrix_q) => self.ask_matrix(
+                &path,
+                &prompt,
+                matrix_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
             QuestionKind::Float(float_q) => self.ask_float(
                 &path,
                 &prompt,
@@ -139,6 +354,15 @@ impl RequesttyBackend {
                 self.ask_confirm(&path, &prompt, confirm_q, question.default(), responses)
             }
 
+            QuestionKind::Select(select_q) => self.ask_select(
+                &path,
+                &prompt,
+                select_q,
+                question.default(),
+                responses,
+                validate,
+            ),
+
             QuestionKind::List(list_q) => self.ask_list(
                 &path,
                 &prompt,
@@ -149,21 +373,46 @@ impl RequesttyBackend {
             ),
 
             QuestionKind::OneOf(one_of) => {
-                self.ask_one_of(&path, &prompt, one_of, responses, validate)
+                self.ask_one_of(&path, &prompt, one_of, responses, validate, observer)
             }
 
             QuestionKind::AnyOf(any_of) => {
-                self.ask_any_of(&path, &prompt, any_of, responses, validate)
+                self.ask_any_of(&path, &prompt, any_of, responses, validate, observer)
             }
 
             QuestionKind::AllOf(all_of) => {
                 // Recursively ask all nested questions
                 for nested_q in all_of.questions() {
-                    self.ask_question(nested_q, responses, validate, Some(&path))?;
+                    self.ask_question(nested_q, responses, validate, observer, Some(&path))?;
                 }
                 Ok(())
             }
+        };
+        answered?;
+
+        if question.allows_comment() {
+            self.ask_comment(&path, responses)?;
         }
+        Ok(())
+    }
+
+    /// Offer an optional free-text comment on the answer at `path`.
+    fn ask_comment(
+        &self,
+        path: &ResponsePath,
+        responses: &mut Responses,
+    ) -> Result<(), RequesttyError> {
+        let question = requestty::Question::input(path.child(COMMENT_KEY).as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message("Comment (optional, Enter to skip):")
+            .build();
+
+        if let requestty::Answer::String(comment) = requestty::prompt_one(question)?
+            && !comment.trim().is_empty()
+        {
+            responses.insert(path.child(COMMENT_KEY), ResponseValue::String(comment));
+        }
+        Ok(())
     }
 
     fn ask_input(
@@ -175,48 +424,95 @@ impl RequesttyBackend {
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), RequesttyError> {
-        loop {
-            let mut q = requestty::Question::input(path.as_str()).message(prompt);
+        let mut q = requestty::Question::input(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(prompt);
+
+        // Apply default value
+        if let Some(default_val) = default.value() {
+            if let ResponseValue::String(s) = default_val {
+                q = q.default(s.clone());
+            }
+        } else if let Some(ref def) = input_q.default {
+            q = q.default(def.clone());
+        }
 
-            // Apply default value
-            if let Some(default_val) = default.value() {
-                if let ResponseValue::String(s) = default_val {
-                    q = q.default(s.clone());
-                }
-            } else if let Some(ref def) = input_q.default {
-                q = q.default(def.clone());
+        // Set up validation - pass the value directly
+        let responses_clone = responses.clone();
+        let path_clone = path.clone();
+        let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+            let rv = ResponseValue::String(value.to_string());
+            validate(&rv, &responses_clone, &path_clone)
+        };
+
+        let result = requestty::prompt_one(q.validate(validate_fn).build());
+
+        match result {
+            Ok(requestty::Answer::String(s)) => {
+                responses.insert(path.clone(), ResponseValue::String(s));
+                Ok(())
             }
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
 
-            // Set up validation - pass the value directly
-            let responses_clone = responses.clone();
-            let path_clone = path.clone();
-            let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
-                let rv = ResponseValue::String(value.to_string());
-                validate(&rv, &responses_clone, &path_clone)
-            };
+    fn ask_autocomplete(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        autocomplete_q: &elicitor::AutocompleteQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        let mut q = requestty::Question::input(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(prompt);
+
+        // Apply default value
+        if let Some(default_val) = default.value() {
+            if let ResponseValue::String(s) = default_val {
+                q = q.default(s.clone());
+            }
+        } else if let Some(ref def) = autocomplete_q.default {
+            q = q.default(def.clone());
+        }
 
-            let result = requestty::prompt_one(q.validate(validate_fn).build());
+        // Tab shows the matching completions; requestty needs at least one
+        let completer = autocomplete_q.clone();
+        let complete_fn = move |value: String, _: &requestty::Answers| {
+            let completions = completer.completions(&value);
+            if completions.is_empty() {
+                std::iter::once(value).collect()
+            } else {
+                completions.into_iter().collect()
+            }
+        };
 
-            match result {
-                Ok(requestty::Answer::String(s)) => {
-                    responses.insert(path.clone(), ResponseValue::String(s));
-                    return Ok(());
-                }
-                Ok(other) => {
-                    return Err(RequesttyError::UnexpectedAnswerType {
-                        expected: "String".to_string(),
-                        got: format!("{other:?}"),
-                    });
-                }
-                Err(e) => {
-                    if matches!(e, requestty::ErrorKind::Interrupted) {
-                        return Err(RequesttyError::Cancelled);
-                    }
-                    // For other errors, the validation message was shown, retry
-                    eprintln!("Error: {e}");
-                    continue;
-                }
+        let responses_clone = responses.clone();
+        let path_clone = path.clone();
+        let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+            let rv = ResponseValue::String(value.to_string());
+            validate(&rv, &responses_clone, &path_clone)
+        };
+
+        let result =
+            requestty::prompt_one(q.auto_complete(complete_fn).validate(validate_fn).build());
+
+        match result {
+            Ok(requestty::Answer::String(s)) => {
+                responses.insert(path.clone(), ResponseValue::String(s));
+                Ok(())
             }
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -229,10 +525,17 @@ impl RequesttyBackend {
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), RequesttyError> {
+        // Text from a rejected submission, offered again when revising
+        let mut draft: Option<String> = None;
+
         loop {
-            let mut q = requestty::Question::editor(path.as_str()).message(prompt);
+            let mut q = requestty::Question::editor(path.as_str())
+                .on_esc(requestty::OnEsc::Terminate)
+                .message(prompt);
 
-            if let Some(default_val) = default.value() {
+            if let Some(text) = &draft {
+                q = q.default(text.clone());
+            } else if let Some(default_val) = default.value() {
                 if let ResponseValue::String(s) = default_val {
                     q = q.default(s.clone());
                 }
@@ -251,6 +554,10 @@ impl RequesttyBackend {
 
             match result {
                 Ok(requestty::Answer::String(s)) => {
+                    if !self.review_text(&s)? {
+                        draft = Some(s);
+                        continue;
+                    }
                     responses.insert(path.clone(), ResponseValue::String(s));
                     return Ok(());
                 }
@@ -260,13 +567,7 @@ impl RequesttyBackend {
                         got: format!("{other:?}"),
                     });
                 }
-                Err(e) => {
-                    if matches!(e, requestty::ErrorKind::Interrupted) {
-                        return Err(RequesttyError::Cancelled);
-                    }
-                    eprintln!("Error: {e}");
-                    continue;
-                }
+                Err(e) => return Err(e.into()),
             }
         }
     }
@@ -283,41 +584,33 @@ impl RequesttyBackend {
         // Note: requestty password doesn't support default values for security
         let _ = default;
 
-        loop {
-            let mut q = requestty::Question::password(path.as_str()).message(prompt);
+        let mut q = requestty::Question::password(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(prompt);
 
-            if let Some(mask) = masked_q.mask {
-                q = q.mask(mask);
-            }
+        if let Some(mask) = masked_q.mask {
+            q = q.mask(mask);
+        }
 
-            let responses_clone = responses.clone();
-            let path_clone = path.clone();
-            let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
-                let rv = ResponseValue::String(value.to_string());
-                validate(&rv, &responses_clone, &path_clone)
-            };
+        let responses_clone = responses.clone();
+        let path_clone = path.clone();
+        let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+            let rv = ResponseValue::String(value.to_string());
+            validate(&rv, &responses_clone, &path_clone)
+        };
 
-            let result = requestty::prompt_one(q.validate(validate_fn).build());
+        let result = requestty::prompt_one(q.validate(validate_fn).build());
 
-            match result {
-                Ok(requestty::Answer::String(s)) => {
-                    responses.insert(path.clone(), ResponseValue::String(s));
-                    return Ok(());
-                }
-                Ok(other) => {
-                    return Err(RequesttyError::UnexpectedAnswerType {
-                        expected: "String".to_string(),
-                        got: format!("{other:?}"),
-                    });
-                }
-                Err(e) => {
-                    if matches!(e, requestty::ErrorKind::Interrupted) {
-                        return Err(RequesttyError::Cancelled);
-                    }
-                    eprintln!("Error: {e}");
-                    continue;
-                }
+        match result {
+            Ok(requestty::Answer::String(s)) => {
+                responses.insert(path.clone(), ResponseValue::String(s));
+                Ok(())
             }
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -330,61 +623,312 @@ impl RequesttyBackend {
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), RequesttyError> {
-        loop {
-            let mut q = requestty::Question::int(path.as_str()).message(prompt);
+        let mut q = requestty::Question::input(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(prompt);
+
+        // Typed and shown in the separators of the question's locale
+        if let Some(default_val) = default.value() {
+            if let ResponseValue::Int(value) = default_val {
+                q = q.default(int_q.format(*value));
+            }
+        } else if let Some(def) = int_q.default {
+            q = q.default(int_q.format(def));
+        }
 
-            if let Some(default_val) = default.value() {
-                if let ResponseValue::Int(i) = default_val {
-                    q = q.default(*i);
-                }
-            } else if let Some(def) = int_q.default {
-                q = q.default(def);
+        // Parse the number, then check bounds and run custom validation
+        let responses_clone = responses.clone();
+        let path_clone = path.clone();
+
+        let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+            let value = int_q.parse(value)?;
+            int_q.check_bounds(value)?;
+            validate(&ResponseValue::Int(value), &responses_clone, &path_clone)
+        };
+
+        let result = requestty::prompt_one(q.validate(validate_fn).build());
+
+        match result {
+            Ok(requestty::Answer::String(s)) => {
+                let value = int_q.parse(&s).map_err(RequesttyError::PromptError)?;
+                responses.insert(path.clone(), ResponseValue::Int(value));
+                Ok(())
             }
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
 
-            // Add min/max validation
-            let min = int_q.min;
-            let max = int_q.max;
-            let responses_clone = responses.clone();
-            let path_clone = path.clone();
+    fn ask_currency(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        currency_q: &elicitor::CurrencyQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        let mut q = requestty::Question::input(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(format!("{prompt} ({})", currency_q.symbol()));
 
-            let validate_fn = move |value: i64, _: &requestty::Answers| -> Result<(), String> {
-                // Check bounds first
-                if let Some(min_val) = min
-                    && value < min_val
-                {
-                    return Err(format!("Value must be at least {min_val}"));
-                }
-                if let Some(max_val) = max
-                    && value > max_val
-                {
-                    return Err(format!("Value must be at most {max_val}"));
-                }
-                // Then run custom validation
-                let rv = ResponseValue::Int(value);
-                validate(&rv, &responses_clone, &path_clone)
-            };
+        let default_minor = match default.value() {
+            Some(ResponseValue::Int(minor)) => Some(*minor),
+            _ => currency_q.default,
+        };
+        if let Some(minor) = default_minor {
+            q = q.default(currency_q.format(minor));
+        }
 
-            let result = requestty::prompt_one(q.validate(validate_fn).build());
+        // Parse the amount, then check bounds and run custom validation
+        let responses_clone = responses.clone();
+        let path_clone = path.clone();
+        let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+            let minor = currency_q.parse(value)?;
+            currency_q.check_bounds(minor)?;
+            validate(&ResponseValue::Int(minor), &responses_clone, &path_clone)
+        };
 
-            match result {
-                Ok(requestty::Answer::Int(i)) => {
-                    responses.insert(path.clone(), ResponseValue::Int(i));
-                    return Ok(());
+        // Show the answer formatted, e.g. "1250.5" as "$1,250.50"
+        let transform = move |value: &str,
+                              _: &requestty::Answers,
+                              backend: &mut dyn requestty::prompt::Backend|
+              -> std::io::Result<()> {
+            match currency_q.parse(value) {
+                Ok(minor) => write!(backend, "{}", currency_q.format(minor)),
+                Err(_) => write!(backend, "{value}"),
+            }
+        };
+
+        let result = requestty::prompt_one(q.validate(validate_fn).transform(transform).build());
+
+        match result {
+            Ok(requestty::Answer::String(s)) => {
+                let minor = currency_q.parse(&s).map_err(RequesttyError::PromptError)?;
+                responses.insert(path.clone(), ResponseValue::Int(minor));
+                Ok(())
+            }
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn ask_color(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        color_q: &elicitor::ColorQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        let mut q = requestty::Question::input(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(format!("{prompt} (hex, e.g. #1e90ff)"));
+
+        let default_color = match default.value() {
+            Some(ResponseValue::String(hex)) => hex.parse().ok(),
+            _ => color_q.default,
+        };
+        if let Some(color) = default_color {
+            q = q.default(color.to_hex());
+        }
+
+        // Parse the hex string, then run custom validation on the normalized form
+        let responses_clone = responses.clone();
+        let path_clone = path.clone();
+        let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+            let color = color_q.parse(value)?;
+            validate(
+                &ResponseValue::String(color.to_hex()),
+                &responses_clone,
+                &path_clone,
+            )
+        };
+
+        // Show the answer as a swatch next to its normalized hex string
+        let transform = move |value: &str,
+                              _: &requestty::Answers,
+                              backend: &mut dyn requestty::prompt::Backend|
+              -> std::io::Result<()> {
+            match color_q.parse(value) {
+                Ok(color) => {
+                    backend.set_bg(requestty::prompt::style::Color::Rgb(
+                        color.r, color.g, color.b,
+                    ))?;
+                    write!(backend, "  ")?;
+                    backend.set_bg(requestty::prompt::style::Color::Reset)?;
+                    write!(backend, " {color}")
                 }
-                Ok(other) => {
+                Err(_) => write!(backend, "{value}"),
+            }
+        };
+
+        let result = requestty::prompt_one(q.validate(validate_fn).transform(transform).build());
+
+        match result {
+            Ok(requestty::Answer::String(s)) => {
+                let color = color_q.parse(&s).map_err(RequesttyError::PromptError)?;
+                responses.insert(path.clone(), ResponseValue::String(color.to_hex()));
+                Ok(())
+            }
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn ask_path(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        path_q: &elicitor::PathQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        let message = match path_q.hint() {
+            Some(hint) => format!("{prompt} ({hint})"),
+            None => prompt.to_string(),
+        };
+        let mut q = requestty::Question::input(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(message);
+
+        if let Some(ResponseValue::String(s)) = default.value() {
+            q = q.default(s.clone());
+        } else if let Some(ref def) = path_q.default {
+            q = q.default(def.clone());
+        }
+
+        // Check existence and extension before custom validation
+        let responses_clone = responses.clone();
+        let path_clone = path.clone();
+        let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+            path_q.check(value)?;
+            let rv = ResponseValue::String(value.to_string());
+            validate(&rv, &responses_clone, &path_clone)
+        };
+
+        let result = requestty::prompt_one(q.validate(validate_fn).build());
+
+        match result {
+            Ok(requestty::Answer::String(s)) => {
+                responses.insert(path.clone(), ResponseValue::String(s));
+                Ok(())
+            }
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn ask_rating(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        rating_q: &elicitor::RatingQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        let points: Vec<i64> = rating_q.points().collect();
+        let default_idx = default
+            .value()
+            .and_then(|v| v.as_int())
+            .or(rating_q.default)
+            .and_then(|v| points.iter().position(|&p| p == v));
+
+        loop {
+            let choices: Vec<String> = points.iter().map(|&p| rating_q.label(p)).collect();
+            let mut q = self.select(path.as_str(), prompt).choices(choices);
+
+            if let Some(idx) = default_idx {
+                q = q.default(idx);
+            }
+
+            let result = requestty::prompt_one(q.build())?;
+
+            let selection = match result {
+                requestty::Answer::ListItem(item) => item.index,
+                other => {
                     return Err(RequesttyError::UnexpectedAnswerType {
-                        expected: "Int".to_string(),
+                        expected: "ListItem".to_string(),
                         got: format!("{other:?}"),
                     });
                 }
-                Err(e) => {
-                    if matches!(e, requestty::ErrorKind::Interrupted) {
-                        return Err(RequesttyError::Cancelled);
+            };
+
+            let rv = ResponseValue::Int(points[selection]);
+            if let Err(msg) = validate(&rv, responses, path) {
+                eprintln!("Error: {msg}");
+                continue;
+            }
+
+            responses.insert(path.clone(), rv);
+            return Ok(());
+        }
+    }
+
+    fn ask_matrix(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        matrix_q: &elicitor::MatrixQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        let defaults = match default.value() {
+            Some(ResponseValue::ChosenVariants(columns)) => columns.clone(),
+            _ => Vec::new(),
+        };
+
+        loop {
+            println!("{prompt}");
+
+            // One selection per row, all on the same scale
+            let mut columns = Vec::with_capacity(matrix_q.rows.len());
+            for (row_idx, row) in matrix_q.rows.iter().enumerate() {
+                let mut q = self
+                    .select(format!("{}.{row_idx}", path.as_str()), row.as_str())
+                    .choices(matrix_q.columns.clone());
+
+                if let Some(&idx) = defaults.get(row_idx)
+                    && idx < matrix_q.columns.len()
+                {
+                    q = q.default(idx);
+                }
+
+                match requestty::prompt_one(q.build())? {
+                    requestty::Answer::ListItem(item) => columns.push(item.index),
+                    other => {
+                        return Err(RequesttyError::UnexpectedAnswerType {
+                            expected: "ListItem".to_string(),
+                            got: format!("{other:?}"),
+                        });
                     }
-                    eprintln!("Error: {e}");
-                    continue;
                 }
             }
+
+            let rv = ResponseValue::ChosenVariants(columns);
+            if let Err(msg) = validate(&rv, responses, path) {
+                eprintln!("Error: {msg}");
+                continue;
+            }
+
+            responses.insert(path.clone(), rv);
+            return Ok(());
         }
     }
 
@@ -397,59 +941,42 @@ impl RequesttyBackend {
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<(), RequesttyError> {
-        loop {
-            let mut q = requestty::Question::float(path.as_str()).message(prompt);
-
-            if let Some(default_val) = default.value() {
-                if let ResponseValue::Float(f) = default_val {
-                    q = q.default(*f);
-                }
-            } else if let Some(def) = float_q.default {
-                q = q.default(def);
+        let mut q = requestty::Question::input(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
+            .message(prompt);
+
+        // Typed and shown in the separators of the question's locale
+        if let Some(default_val) = default.value() {
+            if let ResponseValue::Float(value) = default_val {
+                q = q.default(float_q.format(*value));
             }
+        } else if let Some(def) = float_q.default {
+            q = q.default(float_q.format(def));
+        }
 
-            // Add min/max validation
-            let min = float_q.min;
-            let max = float_q.max;
-            let responses_clone = responses.clone();
-            let path_clone = path.clone();
+        // Parse the number, then check bounds and run custom validation
+        let responses_clone = responses.clone();
+        let path_clone = path.clone();
 
-            let validate_fn = move |value: f64, _: &requestty::Answers| -> Result<(), String> {
-                if let Some(min_val) = min
-                    && value < min_val
-                {
-                    return Err(format!("Value must be at least {min_val}"));
-                }
-                if let Some(max_val) = max
-                    && value > max_val
-                {
-                    return Err(format!("Value must be at most {max_val}"));
-                }
-                let rv = ResponseValue::Float(value);
-                validate(&rv, &responses_clone, &path_clone)
-            };
+        let validate_fn = move |value: &str, _: &requestty::Answers| -> Result<(), String> {
+            let value = float_q.parse(value)?;
+            float_q.check_bounds(value)?;
+            validate(&ResponseValue::Float(value), &responses_clone, &path_clone)
+        };
 
-            let result = requestty::prompt_one(q.validate(validate_fn).build());
+        let result = requestty::prompt_one(q.validate(validate_fn).build());
 
-            match result {
-                Ok(requestty::Answer::Float(f)) => {
-                    responses.insert(path.clone(), ResponseValue::Float(f));
-                    return Ok(());
-                }
-                Ok(other) => {
-                    return Err(RequesttyError::UnexpectedAnswerType {
-                        expected: "Float".to_string(),
-                        got: format!("{other:?}"),
-                    });
-                }
-                Err(e) => {
-                    if matches!(e, requestty::ErrorKind::Interrupted) {
-                        return Err(RequesttyError::Cancelled);
-                    }
-                    eprintln!("Error: {e}");
-                    continue;
-                }
+        match result {
+            Ok(requestty::Answer::String(s)) => {
+                let value = float_q.parse(&s).map_err(RequesttyError::PromptError)?;
+                responses.insert(path.clone(), ResponseValue::Float(value));
+                Ok(())
             }
+            Ok(other) => Err(RequesttyError::UnexpectedAnswerType {
+                expected: "String".to_string(),
+                got: format!("{other:?}"),
+            }),
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -468,6 +995,7 @@ impl RequesttyBackend {
         };
 
         let q = requestty::Question::confirm(path.as_str())
+            .on_esc(requestty::OnEsc::Terminate)
             .message(prompt)
             .default(default_val)
             .build();
@@ -486,6 +1014,59 @@ impl RequesttyBackend {
         }
     }
 
+    fn ask_select(
+        &self,
+        path: &ResponsePath,
+        prompt: &str,
+        select_q: &elicitor::SelectQuestion,
+        default: &DefaultValue,
+        responses: &mut Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<(), RequesttyError> {
+        if select_q.options.is_empty() {
+            return Err(RequesttyError::PromptError(format!(
+                "No choices available for '{}'",
+                path.as_str()
+            )));
+        }
+
+        let default_idx = default
+            .value()
+            .and_then(|v| v.as_str())
+            .and_then(|v| select_q.position(v))
+            .or(select_q.default);
+
+        loop {
+            let choices: Vec<String> = select_q.options.iter().map(|o| o.label.clone()).collect();
+            let mut q = self.select(path.as_str(), prompt).choices(choices);
+
+            if let Some(idx) = default_idx {
+                q = q.default(idx);
+            }
+
+            let result = requestty::prompt_one(q.build())?;
+
+            let selection = match result {
+                requestty::Answer::ListItem(item) => item.index,
+                other => {
+                    return Err(RequesttyError::UnexpectedAnswerType {
+                        expected: "ListItem".to_string(),
+                        got: format!("{other:?}"),
+                    });
+                }
+            };
+
+            let rv = ResponseValue::String(select_q.options[selection].value.clone());
+            if let Err(msg) = validate(&rv, responses, path) {
+                eprintln!("Error: {msg}");
+                continue;
+            }
+
+            responses.insert(path.clone(), rv);
+            return Ok(());
+        }
+    }
+
     fn ask_list(
         &self,
         path: &ResponsePath,
@@ -504,6 +1085,7 @@ impl RequesttyBackend {
             let item_prompt = format!("[{}]", items.len() + 1);
 
             let q = requestty::Question::input(&item_prompt)
+                .on_esc(requestty::OnEsc::Terminate)
                 .message(&item_prompt)
                 .build();
 
@@ -512,47 +1094,52 @@ impl RequesttyBackend {
             match result {
                 requestty::Answer::String(s) if s.is_empty() => break,
                 requestty::Answer::String(s) => {
+                    let messages = &list_q.messages;
                     let value = match &list_q.element_kind {
                         ListElementKind::String => Some(ResponseValue::String(s)),
                         ListElementKind::Int { min, max } => match s.parse::<i64>() {
                             Ok(n) => {
-                                if let Some(min_val) = min {
-                                    if n < *min_val {
-                                        println!("    Error: Value must be at least {min_val}");
-                                        continue;
-                                    }
+                                if let Some(min_val) = min
+                                    && n < *min_val
+                                {
+                                    let message = messages.at_least(&min_val.to_string());
+                                    println!("    Error: {message}");
+                                    continue;
                                 }
-                                if let Some(max_val) = max {
-                                    if n > *max_val {
-                                        println!("    Error: Value must be at most {max_val}");
-                                        continue;
-                                    }
+                                if let Some(max_val) = max
+                                    && n > *max_val
+                                {
+                                    let message = messages.at_most(&max_val.to_string());
+                                    println!("    Error: {message}");
+                                    continue;
                                 }
                                 Some(ResponseValue::Int(n))
                             }
                             Err(_) => {
-                                println!("    Error: Please enter a valid integer");
+                                println!("    Error: {}", messages.invalid_integer(None));
                                 continue;
                             }
                         },
                         ListElementKind::Float { min, max } => match s.parse::<f64>() {
                             Ok(n) => {
-                                if let Some(min_val) = min {
-                                    if n < *min_val {
-                                        println!("    Error: Value must be at least {min_val}");
-                                        continue;
-                                    }
+                                if let Some(min_val) = min
+                                    && n < *min_val
+                                {
+                                    let message = messages.at_least(&min_val.to_string());
+                                    println!("    Error: {message}");
+                                    continue;
                                 }
-                                if let Some(max_val) = max {
-                                    if n > *max_val {
-                                        println!("    Error: Value must be at most {max_val}");
-                                        continue;
-                                    }
+                                if let Some(max_val) = max
+                                    && n > *max_val
+                                {
+                                    let message = messages.at_most(&max_val.to_string());
+                                    println!("    Error: {message}");
+                                    continue;
                                 }
                                 Some(ResponseValue::Float(n))
                             }
                             Err(_) => {
-                                println!("    Error: Please enter a valid number");
+                                println!("    Error: {}", messages.invalid_number(None));
                                 continue;
                             }
                         },
@@ -625,13 +1212,12 @@ impl RequesttyBackend {
         one_of: &elicitor::OneOfQuestion,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
     ) -> Result<(), RequesttyError> {
         // Build choices from variant names
         let choices: Vec<String> = one_of.variants.iter().map(|v| v.name.clone()).collect();
 
-        let mut q = requestty::Question::select(path.as_str())
-            .message(prompt)
-            .choices(choices);
+        let mut q = self.select(path.as_str(), prompt).choices(choices);
 
         if let Some(default_idx) = one_of.default {
             q = q.default(default_idx);
@@ -661,13 +1247,20 @@ impl RequesttyBackend {
             }
             QuestionKind::AllOf(all_of) => {
                 for nested_q in all_of.questions() {
-                    self.ask_question(nested_q, responses, validate, Some(path))?;
+                    self.ask_question(nested_q, responses, validate, observer, Some(path))?;
                 }
             }
             QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
             | QuestionKind::Int(_)
+            | QuestionKind::Currency(_)
+            | QuestionKind::Color(_)
+            | QuestionKind::Path(_)
+            | QuestionKind::Rating(_)
+            | QuestionKind::Matrix(_)
             | QuestionKind::Float(_)
             | QuestionKind::Confirm(_)
+            | QuestionKind::Select(_)
             | QuestionKind::Masked(_)
             | QuestionKind::Multiline(_)
             | QuestionKind::List(_) => {
@@ -677,7 +1270,7 @@ impl RequesttyBackend {
                     format!("Enter {} value:", selected_variant.name),
                     selected_variant.kind.clone(),
                 );
-                self.ask_question(&variant_q, responses, validate, Some(path))?;
+                self.ask_question(&variant_q, responses, validate, observer, Some(path))?;
             }
             QuestionKind::OneOf(nested_one_of) => {
                 // Nested enum
@@ -686,7 +1279,7 @@ impl RequesttyBackend {
                     format!("Select {}:", selected_variant.name),
                     QuestionKind::OneOf(nested_one_of.clone()),
                 );
-                self.ask_question(&variant_q, responses, validate, Some(path))?;
+                self.ask_question(&variant_q, responses, validate, observer, Some(path))?;
             }
             QuestionKind::AnyOf(nested_any_of) => {
                 let variant_q = Question::new(
@@ -694,7 +1287,7 @@ impl RequesttyBackend {
                     format!("Select {} options:", selected_variant.name),
                     QuestionKind::AnyOf(nested_any_of.clone()),
                 );
-                self.ask_question(&variant_q, responses, validate, Some(path))?;
+                self.ask_question(&variant_q, responses, validate, observer, Some(path))?;
             }
         }
 
@@ -708,6 +1301,7 @@ impl RequesttyBackend {
         any_of: &elicitor::AnyOfQuestion,
         responses: &mut Responses,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
     ) -> Result<(), RequesttyError> {
         // Loop until valid selection or user cancels
         let selections = loop {
@@ -722,8 +1316,8 @@ impl RequesttyBackend {
                 })
                 .collect();
 
-            let q = requestty::Question::multi_select(path.as_str())
-                .message(prompt)
+            let q = self
+                .multi_select(path.as_str(), prompt)
                 .choices_with_default(choices)
                 .build();
 
@@ -775,7 +1369,13 @@ impl RequesttyBackend {
                 }
                 QuestionKind::AllOf(all_of) => {
                     for nested_q in all_of.questions() {
-                        self.ask_question(nested_q, responses, validate, Some(&item_path))?;
+                        self.ask_question(
+                            nested_q,
+                            responses,
+                            validate,
+                            observer,
+                            Some(&item_path),
+                        )?;
                     }
                 }
                 _ => {
@@ -795,6 +1395,15 @@ impl SurveyBackend for RequesttyBackend {
         &self,
         definition: &SurveyDefinition,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
     ) -> Result<Responses, Self::Error> {
         let mut responses = Responses::new();
 
@@ -804,9 +1413,17 @@ impl SurveyBackend for RequesttyBackend {
             println!();
         }
 
-        // Ask all questions
-        for question in definition.questions() {
-            self.ask_question(question, &mut responses, validate, None)?;
+        // Ask all questions, keeping the answers given so far on cancellation
+        let asked = definition.questions().iter().try_for_each(|question| {
+            self.ask_question(question, &mut responses, validate, observer, None)
+        });
+        if let Err(err) = asked {
+            return Err(match err {
+                RequesttyError::Cancelled { .. } => RequesttyError::Cancelled {
+                    partial: Box::new(responses),
+                },
+                err => err,
+            });
         }
 
         // Show epilogue if present
@@ -830,7 +1447,9 @@ mod tests {
 
     #[test]
     fn error_types() {
-        let err = RequesttyError::Cancelled;
+        let err = RequesttyError::Cancelled {
+            partial: Box::default(),
+        };
         assert_eq!(err.to_string(), "Survey cancelled by user");
 
         let err = RequesttyError::PromptError("test error".to_string());
@@ -845,4 +1464,32 @@ mod tests {
             "Unexpected answer type: expected String, got Int"
         );
     }
+
+    #[test]
+    fn esc_and_closed_input_cancel_the_survey() {
+        for kind in [
+            requestty::ErrorKind::Interrupted,
+            requestty::ErrorKind::Aborted,
+            requestty::ErrorKind::Eof,
+        ] {
+            let err = RequesttyError::from(kind);
+            assert!(matches!(err, RequesttyError::Cancelled { .. }), "{err}");
+        }
+    }
+
+    #[test]
+    fn list_options() {
+        let backend = RequesttyBackend::new();
+        assert_eq!(backend.page_size, None);
+        assert!(!backend.stop_at_ends);
+        assert!(!backend.arrow_hint);
+
+        let backend = RequesttyBackend::new()
+            .with_page_size(2)
+            .with_looping(false)
+            .with_arrow_hint();
+        assert_eq!(backend.page_size, Some(MIN_PAGE_SIZE));
+        assert!(backend.stop_at_ends);
+        assert!(backend.arrow_hint);
+    }
 }
@@ -0,0 +1,472 @@
+//! Backend for screen readers and braille displays.
+//!
+//! `AccessibleBackend` reads answers line by line like `PlainBackend`, but writes
+//! everything as complete sentences on lines of their own: no colors, no cursor
+//! movement and nothing redrawn. Questions are numbered, every option is listed with
+//! its number, the expected input is spelled out ("Enter a number from 1 to 5."), and
+//! every accepted answer is confirmed:
+//!
+//! ```text
+//! Question 2: What is your age?
+//! Enter a whole number from 0 to 150.
+//! Answer:
+//! You answered 30.
+//! ```
+//!
+//! ```rust,ignore
+//! let config = SimpleConfig::builder().run(AccessibleBackend::new())?;
+//! ```
+
+use std::io::{Read, Write};
+use std::sync::Mutex;
+
+use crate::auto_answer::{check, kind_default};
+use crate::plain::{
+    Conversation, Notice, PlainError, PlainIo, choose, converse, parse, parse_list, read_block,
+    read_line,
+};
+use crate::{
+    ListElementKind, NoObserver, QuestionKind, QuestionStep, ResponsePath, ResponseValue,
+    Responses, SurveyBackend, SurveyDefinition, SurveyObserver,
+};
+
+/// A backend that asks one numbered question at a time in plain sentences, for
+/// screen readers and braille displays.
+pub struct AccessibleBackend {
+    io: Mutex<PlainIo>,
+}
+
+impl std::fmt::Debug for AccessibleBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessibleBackend").finish_non_exhaustive()
+    }
+}
+
+impl Default for AccessibleBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessibleBackend {
+    /// Read answers from stdin and write questions to stdout.
+    pub fn new() -> Self {
+        Self::from_reader(std::io::stdin())
+    }
+
+    /// Read answers from `input` and write questions to stdout.
+    pub fn from_reader(input: impl Read + Send + 'static) -> Self {
+        Self {
+            io: Mutex::new(PlainIo::new(input)),
+        }
+    }
+
+    /// Write questions, confirmations and errors to `output` instead of stdout.
+    pub fn with_output(self, output: impl Write + Send + 'static) -> Self {
+        let mut io = self.io.into_inner().unwrap_or_else(|e| e.into_inner());
+        io.output = Box::new(output);
+        Self { io: Mutex::new(io) }
+    }
+}
+
+impl SurveyBackend for AccessibleBackend {
+    type Error = PlainError;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        let mut io = self.io.lock().unwrap_or_else(|e| e.into_inner());
+        let mut sentences = Sentences {
+            io: &mut io,
+            number: 0,
+        };
+        let responses = converse(&mut sentences, definition, validate, observer)?;
+        writeln!(
+            sentences.io.output,
+            "All {} questions are answered.",
+            sentences.number
+        )?;
+        sentences.io.output.flush()?;
+        Ok(responses)
+    }
+}
+
+/// The conversation of an accessible survey, numbering the questions it asks.
+struct Sentences<'a> {
+    io: &'a mut PlainIo,
+    /// Number of the last question asked.
+    number: usize,
+}
+
+impl Conversation for Sentences<'_> {
+    type Error = PlainError;
+
+    fn show(&mut self, text: &str, notice: Notice) -> Result<(), PlainError> {
+        match notice {
+            Notice::Heading => writeln!(self.io.output, "Section: {text}")?,
+            Notice::Prelude | Notice::Epilogue => writeln!(self.io.output, "{text}")?,
+        }
+        Ok(())
+    }
+
+    fn ask(
+        &mut self,
+        step: &QuestionStep,
+        responses: &Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<ResponseValue, PlainError> {
+        self.number += 1;
+        let value = ask(self.io, self.number, step, responses, validate)?;
+        writeln!(self.io.output, "{}", confirmation(&step.kind, &value))?;
+        Ok(value)
+    }
+
+    fn interrupted(&mut self, error: PlainError, partial: Responses) -> PlainError {
+        match error {
+            PlainError::Cancelled { .. } => {
+                if let Err(error) = writeln!(self.io.output, "The survey was cancelled.") {
+                    return error.into();
+                }
+                PlainError::Cancelled {
+                    partial: Box::new(partial),
+                }
+            }
+            error => error,
+        }
+    }
+}
+
+/// Ask `step` until the answer passes validation.
+fn ask(
+    io: &mut PlainIo,
+    number: usize,
+    step: &QuestionStep,
+    responses: &Responses,
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+) -> Result<ResponseValue, PlainError> {
+    let default = step.suggestion.clone().or_else(|| kind_default(&step.kind));
+
+    writeln!(io.output, "Question {number}: {}", step.prompt)?;
+    loop {
+        write_instructions(io, step, default.as_ref())?;
+        let parsed = match &step.kind {
+            QuestionKind::Multiline(_) => {
+                let lines = read_block(io)?;
+                match &default {
+                    Some(value) if lines.is_empty() => Ok(value.clone()),
+                    _ => Ok(ResponseValue::String(lines.join("\n"))),
+                }
+            }
+            QuestionKind::List(list_q) => {
+                let lines = read_block(io)?;
+                parse_list(list_q, &lines).and_then(|value| {
+                    let count = lines.len();
+                    match (list_q.min_items, list_q.max_items) {
+                        (Some(min), _) if count < min => Err(format!("Enter at least {min} items")),
+                        (_, Some(max)) if count > max => Err(format!("Enter at most {max} items")),
+                        _ => Ok(value),
+                    }
+                })
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                let mut columns = Vec::with_capacity(matrix_q.rows.len());
+                let mut parsed = Ok(());
+                for (idx, row) in matrix_q.rows.iter().enumerate() {
+                    writeln!(
+                        io.output,
+                        "Row {} of {}: {row}",
+                        idx + 1,
+                        matrix_q.rows.len()
+                    )?;
+                    writeln!(io.output, "Answer:")?;
+                    io.output.flush()?;
+                    let line = read_line(io)?;
+                    match choose(&line, &matrix_q.columns) {
+                        Ok(column) => columns.push(column),
+                        Err(msg) => {
+                            parsed = Err(msg);
+                            break;
+                        }
+                    }
+                }
+                parsed.map(|()| ResponseValue::ChosenVariants(columns))
+            }
+            kind => {
+                writeln!(io.output, "Answer:")?;
+                io.output.flush()?;
+                let line = read_line(io)?;
+                match &default {
+                    Some(value) if line.is_empty() => Ok(value.clone()),
+                    _ => parse(kind, &line),
+                }
+            }
+        };
+
+        // Multi-selects are validated at the question, like in the other backends
+        let checked = parsed.and_then(|value| match step.kind {
+            QuestionKind::OneOf(_) => Ok(value),
+            QuestionKind::AnyOf(_) => {
+                validate(&value, responses, &step.path.parent()).map(|()| value)
+            }
+            _ => check(&step.kind, &value)
+                .and_then(|()| validate(&value, responses, &step.path))
+                .map(|()| value),
+        });
+        match checked {
+            Ok(value) => return Ok(value),
+            Err(msg) => writeln!(
+                io.output,
+                "Error: {}. Please answer question {number} again.",
+                msg.trim_end_matches('.')
+            )?,
+        }
+    }
+}
+
+/// List the options of `step` with their numbers and spell out the expected input.
+fn write_instructions(
+    io: &mut PlainIo,
+    step: &QuestionStep,
+    default: Option<&ResponseValue>,
+) -> Result<(), PlainError> {
+    let choices = choices(&step.kind);
+    if !choices.is_empty() {
+        writeln!(io.output, "There are {} options:", choices.len())?;
+        for (idx, choice) in choices.iter().enumerate() {
+            writeln!(io.output, "Option {}: {choice}.", idx + 1)?;
+        }
+    }
+
+    let count = choices.len();
+    let expected = match &step.kind {
+        QuestionKind::Select(_) | QuestionKind::OneOf(_) => {
+            format!("Enter a number from 1 to {count}.")
+        }
+        QuestionKind::AnyOf(_) => format!(
+            "Enter the numbers from 1 to {count} of all options that apply, separated by commas. Leave the line empty to select none."
+        ),
+        QuestionKind::Matrix(q) => format!(
+            "Each of the {} rows is asked on its own. Enter a number from 1 to {count} for each row.",
+            q.rows.len()
+        ),
+        QuestionKind::Confirm(_) => "Enter yes or no.".to_string(),
+        QuestionKind::Rating(q) => format!("Enter a number from {} to {}.", q.min, q.max),
+        QuestionKind::Int(q) => format!(
+            "Enter a whole number{}.",
+            range(q.min.map(|m| q.format(m)), q.max.map(|m| q.format(m)))
+        ),
+        QuestionKind::Float(q) => format!(
+            "Enter a number{}.",
+            range(q.min.map(|m| q.format(m)), q.max.map(|m| q.format(m)))
+        ),
+        QuestionKind::Currency(q) => {
+            format!(
+                "Enter an amount{}.",
+                range(q.min.map(|m| q.format(m)), q.max.map(|m| q.format(m)))
+            )
+        }
+        QuestionKind::Color(_) => "Enter a color as a hex code, for example #ff8800.".to_string(),
+        QuestionKind::Path(_) => "Enter a path.".to_string(),
+        QuestionKind::Masked(_) => "Enter the secret. It is not shown or repeated.".to_string(),
+        QuestionKind::Multiline(_) => {
+            "Enter your text over as many lines as needed. End with an empty line.".to_string()
+        }
+        QuestionKind::List(q) => {
+            let kind = match q.element_kind {
+                ListElementKind::String => "item",
+                ListElementKind::Int { .. } => "whole number",
+                ListElementKind::Float { .. } => "number",
+            };
+            format!("Enter one {kind} per line. End the list with an empty line.")
+        }
+        _ => "Enter your answer.".to_string(),
+    };
+    writeln!(io.output, "{expected}")?;
+
+    let kept = match (&step.kind, default) {
+        (QuestionKind::Masked(_) | QuestionKind::List(_) | QuestionKind::Matrix(_), _) => None,
+        (QuestionKind::AnyOf(_), Some(ResponseValue::ChosenVariants(indices)))
+            if indices.is_empty() =>
+        {
+            None
+        }
+        (kind, Some(value)) => Some(described(kind, value)),
+        (_, None) => None,
+    };
+    if let Some(kept) = kept {
+        writeln!(io.output, "Press Enter without typing to keep {kept}.")?;
+    }
+    io.output.flush()?;
+    Ok(())
+}
+
+/// The names of the options of a question of `kind`, if it offers any.
+fn choices(kind: &QuestionKind) -> Vec<&str> {
+    match kind {
+        QuestionKind::Select(q) => q.options.iter().map(|o| o.label.as_str()).collect(),
+        QuestionKind::OneOf(q) => q.variants.iter().map(|v| v.name.as_str()).collect(),
+        QuestionKind::AnyOf(q) => q.variants.iter().map(|v| v.name.as_str()).collect(),
+        QuestionKind::Matrix(q) => q.columns.iter().map(String::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The bounds of a number, spelled out.
+fn range(min: Option<impl std::fmt::Display>, max: Option<impl std::fmt::Display>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!(" from {min} to {max}"),
+        (Some(min), None) => format!(" of at least {min}"),
+        (None, Some(max)) => format!(" of at most {max}"),
+        (None, None) => String::new(),
+    }
+}
+
+/// The accepted answer, repeated back in words.
+fn confirmation(kind: &QuestionKind, value: &ResponseValue) -> String {
+    match (kind, value) {
+        (QuestionKind::Masked(_), _) => "Your answer is recorded.".to_string(),
+        (QuestionKind::List(_), _) => format!("You entered {}.", described(kind, value)),
+        _ => format!("You answered {}.", described(kind, value)),
+    }
+}
+
+/// An answer to a question of `kind` in words, with choices by name.
+fn described(kind: &QuestionKind, value: &ResponseValue) -> String {
+    let names = choices(kind);
+    let listed = |items: Vec<String>| match items.len() {
+        0 => "nothing".to_string(),
+        1 => items[0].clone(),
+        _ => format!("{} items: {}", items.len(), items.join(", ")),
+    };
+    match (kind, value) {
+        (QuestionKind::Currency(q), ResponseValue::Int(minor)) => q.format(*minor),
+        (QuestionKind::Int(q), ResponseValue::Int(n)) => q.format(*n),
+        (QuestionKind::Float(q), ResponseValue::Float(f)) => q.format(*f),
+        (QuestionKind::Select(q), ResponseValue::String(v)) => q
+            .position(v)
+            .map_or_else(|| v.clone(), |idx| names[idx].to_string()),
+        (QuestionKind::Matrix(q), ResponseValue::ChosenVariants(columns)) => q
+            .rows
+            .iter()
+            .zip(columns)
+            .map(|(row, &column)| format!("{row}: {}", names[column]))
+            .collect::<Vec<_>>()
+            .join(", "),
+        (_, ResponseValue::ChosenVariant(idx)) => names
+            .get(*idx)
+            .map_or_else(|| format!("option {}", idx + 1), |name| name.to_string()),
+        (_, ResponseValue::ChosenVariants(indices)) => listed(
+            indices
+                .iter()
+                .map(|&idx| {
+                    names
+                        .get(idx)
+                        .map_or_else(|| format!("option {}", idx + 1), |n| n.to_string())
+                })
+                .collect(),
+        ),
+        (_, ResponseValue::String(s)) if s.is_empty() => "nothing".to_string(),
+        (_, ResponseValue::String(s)) => s.clone(),
+        (_, ResponseValue::Int(n)) => n.to_string(),
+        (_, ResponseValue::Float(f)) => f.to_string(),
+        (_, ResponseValue::Bool(b)) => if *b { "yes" } else { "no" }.to_string(),
+        (_, ResponseValue::StringList(items)) => listed(items.clone()),
+        (_, ResponseValue::IntList(items)) => listed(items.iter().map(i64::to_string).collect()),
+        (_, ResponseValue::FloatList(items)) => listed(items.iter().map(f64::to_string).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnyOfQuestion, ConfirmQuestion, InputQuestion, IntQuestion, Question, Variant};
+    use std::sync::Arc;
+
+    /// Output shared with the test, to read what the backend wrote.
+    #[derive(Clone, Default)]
+    struct Written(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Written {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Written {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[test]
+    fn questions_are_numbered_and_answers_confirmed() {
+        let definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "age",
+                "Age?",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new(
+                "extras",
+                "Extras?",
+                QuestionKind::AnyOf(AnyOfQuestion::new(vec![
+                    Variant::unit("Cheese"),
+                    Variant::unit("Ham"),
+                ])),
+            ),
+            Question::new(
+                "ok",
+                "Ok?",
+                QuestionKind::Confirm(ConfirmQuestion::with_default(true)),
+            ),
+        ]);
+        let written = Written::default();
+        let backend = AccessibleBackend::from_reader("Ada\n200\n36\n1,2\n\n".as_bytes())
+            .with_output(written.clone());
+
+        let responses = backend.collect(&definition, &|_, _, _| Ok(())).unwrap();
+        assert_eq!(
+            responses.get(&ResponsePath::new("age")),
+            Some(&ResponseValue::Int(36))
+        );
+
+        let text = written.text();
+        for line in [
+            "Question 1: Name?",
+            "You answered Ada.",
+            "Question 2: Age?",
+            "Enter a whole number from 0 to 150.",
+            "Error: 200 is out of bounds. Please answer question 2 again.",
+            "You answered 36.",
+            "Option 2: Ham.",
+            "You answered 2 items: Cheese, Ham.",
+            "Press Enter without typing to keep yes.",
+            "You answered yes.",
+            "All 4 questions are answered.",
+        ] {
+            assert!(
+                text.lines().any(|l| l == line),
+                "missing {line:?} in:\n{text}"
+            );
+        }
+        // Nothing but plain text reaches the screen reader
+        assert!(!text.contains('\x1b'));
+    }
+}
@@ -0,0 +1,139 @@
+//! Checking assumed answers before a survey starts.
+//!
+//! Assumed answers skip their questions, so no backend ever validates them. The
+//! builders check them up front instead: a value of the wrong type, out of bounds or
+//! rejected by the field's validator fails the run before anything is shown, with all
+//! invalid assumptions listed at once.
+
+use crate::auto_answer::check;
+use crate::{
+    QuestionKind, RenderStep, ResponsePath, ResponseValue, Responses, SurveyDefinition,
+    apply_assumed, render_plan,
+};
+
+/// Assumed answers that the questions they answer would not accept.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub struct InvalidAssumptions {
+    /// Each invalid assumption with the reason it is rejected, in question order.
+    pub invalid: Vec<(ResponsePath, String)>,
+}
+
+impl std::fmt::Display for InvalidAssumptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} invalid assumption(s):", self.invalid.len())?;
+        for (path, message) in &self.invalid {
+            write!(f, "\n  {path}: {message}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Check the assumed answers of `definition` against the type, bounds and validator of
+/// their questions.
+///
+/// The validator sees all assumed answers, like it would see earlier answers in a
+/// backend. An `Option` assumed to be `None` is not checked.
+pub fn check_assumptions(
+    definition: &SurveyDefinition,
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+) -> Result<(), InvalidAssumptions> {
+    let mut responses = Responses::new();
+    apply_assumed(definition, &mut responses);
+
+    let mut invalid = Vec::new();
+    for step in render_plan(definition, &responses) {
+        let RenderStep::Question(step) = step else {
+            continue;
+        };
+        let Some(value) = step.answer.as_ref().filter(|_| step.assumed) else {
+            continue;
+        };
+        let none_marker = matches!(value, ResponseValue::Bool(false))
+            && !matches!(step.kind, QuestionKind::Confirm(_));
+        if none_marker {
+            continue;
+        }
+
+        let checked = check_type(&step.kind, value)
+            .and_then(|()| check(&step.kind, value))
+            .and_then(|()| match step.kind {
+                QuestionKind::OneOf(_) => Ok(()),
+                QuestionKind::AnyOf(_) => validate(value, &responses, &step.path.parent()),
+                _ => validate(value, &responses, &step.path),
+            });
+        if let Err(message) = checked {
+            invalid.push((step.path, message));
+        }
+    }
+
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(InvalidAssumptions { invalid })
+    }
+}
+
+/// Check that `value` is of the type a question of `kind` answers with, and that
+/// chosen variants exist.
+fn check_type(kind: &QuestionKind, value: &ResponseValue) -> Result<(), String> {
+    let expected = kind.answer_type();
+    if value.type_name() != expected {
+        return Err(format!("expected {expected}, got {}", value.type_name()));
+    }
+    let variants = match kind {
+        QuestionKind::OneOf(q) => q.variants.len(),
+        QuestionKind::AnyOf(q) => q.variants.len(),
+        _ => return Ok(()),
+    };
+    let chosen = match value {
+        ResponseValue::ChosenVariant(idx) => std::slice::from_ref(idx),
+        ResponseValue::ChosenVariants(indices) => indices.as_slice(),
+        _ => &[],
+    };
+    match chosen.iter().find(|&&idx| idx >= variants) {
+        Some(idx) => Err(format!("there is no variant {idx}")),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputQuestion, IntQuestion, Question};
+
+    #[test]
+    fn every_invalid_assumption_is_listed() {
+        let mut age = Question::new(
+            "age",
+            "Age?",
+            QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+        );
+        age.set_assumption(ResponseValue::Int(200));
+        let mut name = Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new()));
+        name.set_assumption(ResponseValue::Int(7));
+        let mut city = Question::new("city", "City?", QuestionKind::Input(InputQuestion::new()));
+        city.set_assumption(ResponseValue::String("x".to_string()));
+        let definition = SurveyDefinition::new(vec![age, name, city]);
+
+        let err = check_assumptions(&definition, &|value, _, _| match value {
+            ResponseValue::String(s) if s.len() < 2 => Err("too short".to_string()),
+            _ => Ok(()),
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.invalid,
+            vec![
+                (ResponsePath::new("age"), "200 is out of bounds".to_string()),
+                (
+                    ResponsePath::new("name"),
+                    "expected String, got Int".to_string()
+                ),
+                (ResponsePath::new("city"), "too short".to_string()),
+            ]
+        );
+        assert!(
+            err.to_string()
+                .starts_with("3 invalid assumption(s):\n  age: ")
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Backend that answers every question on its own, for smoke tests.
+//!
+//! `AutoAnswerBackend` answers each question with its assumed value, its suggestion or
+//! default, or a placeholder derived from the question type (empty text, zero clamped
+//! to the bounds, the first option). Questions where none of these pass validation have
+//! no safe value and fail the run, so large example forms can be checked to still
+//! construct after refactors:
+//!
+//! ```rust,ignore
+//! use elicitor::AutoAnswerBackend;
+//!
+//! let backend = AutoAnswerBackend::new();
+//! let config = Config::builder().run(backend.clone())?;
+//!
+//! for answer in backend.report().placeholders() {
+//!     println!("{} has no default", answer.path);
+//! }
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    ListElementKind, NoObserver, QuestionKind, QuestionStep, ResponsePath, ResponseValue,
+    Responses, SurveyBackend, SurveyDefinition, SurveyObserver, apply_assumed, next_step,
+    record_answer,
+};
+
+/// Where an automatic answer came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnswerSource {
+    /// The question's assumed value.
+    Assumed,
+    /// The question's suggestion or default.
+    Suggested,
+    /// A placeholder derived from the question type.
+    Placeholder,
+    /// No candidate passed validation. The placeholder was recorded to continue.
+    Unsafe {
+        /// Why the last candidate was rejected.
+        reason: String,
+    },
+}
+
+/// One automatically answered question.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoAnswer {
+    /// Where the answer is stored.
+    pub path: ResponsePath,
+    /// The recorded answer.
+    pub value: ResponseValue,
+    /// Where the answer came from.
+    pub source: AnswerSource,
+}
+
+/// The answers of the last run of an [`AutoAnswerBackend`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AutoAnswerReport {
+    answers: Vec<AutoAnswer>,
+}
+
+impl AutoAnswerReport {
+    /// All answers, in the order they were given.
+    pub fn answers(&self) -> &[AutoAnswer] {
+        &self.answers
+    }
+
+    /// Answers made up from the question type.
+    pub fn placeholders(&self) -> impl Iterator<Item = &AutoAnswer> {
+        self.answers
+            .iter()
+            .filter(|answer| answer.source == AnswerSource::Placeholder)
+    }
+
+    /// Answers where no candidate passed validation.
+    pub fn unsafe_answers(&self) -> impl Iterator<Item = &AutoAnswer> {
+        self.answers
+            .iter()
+            .filter(|answer| matches!(answer.source, AnswerSource::Unsafe { .. }))
+    }
+
+    /// Whether every question had a safe value.
+    pub fn is_safe(&self) -> bool {
+        self.unsafe_answers().next().is_none()
+    }
+}
+
+/// Error type for AutoAnswerBackend.
+#[derive(Debug, thiserror::Error)]
+pub enum AutoAnswerError {
+    #[error("No safe answer for: {}", .0.join(", "))]
+    NoSafeValue(Vec<String>),
+}
+
+/// A backend that answers every question without user interaction.
+///
+/// Clones share the report, so keep a clone to inspect it after the run.
+#[derive(Debug, Clone, Default)]
+pub struct AutoAnswerBackend {
+    report: Arc<Mutex<AutoAnswerReport>>,
+}
+
+impl AutoAnswerBackend {
+    /// Create a new auto-answer backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The answers of the last run.
+    pub fn report(&self) -> AutoAnswerReport {
+        self.report
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl SurveyBackend for AutoAnswerBackend {
+    type Error = AutoAnswerError;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        let mut responses = Responses::new();
+        apply_assumed(definition, &mut responses);
+        let mut report = AutoAnswerReport {
+            answers: responses
+                .iter()
+                .map(|(path, value)| AutoAnswer {
+                    path: path.clone(),
+                    value: value.clone(),
+                    source: AnswerSource::Assumed,
+                })
+                .collect(),
+        };
+
+        while let Some(step) = next_step(definition, &responses) {
+            let question_path = match step.kind {
+                QuestionKind::OneOf(_) | QuestionKind::AnyOf(_) => step.path.parent(),
+                _ => step.path.clone(),
+            };
+            observer.on_question_start(&question_path);
+
+            let (value, source) = answer(&step, &responses, validate);
+            record_answer(&mut responses, &step, value.clone());
+            report.answers.push(AutoAnswer {
+                path: step.path,
+                value,
+                source,
+            });
+        }
+
+        let unsafe_paths: Vec<String> = report
+            .unsafe_answers()
+            .map(|answer| answer.path.as_str().to_string())
+            .collect();
+        *self.report.lock().unwrap_or_else(|e| e.into_inner()) = report;
+
+        if unsafe_paths.is_empty() {
+            Ok(responses)
+        } else {
+            Err(AutoAnswerError::NoSafeValue(unsafe_paths))
+        }
+    }
+}
+
+/// The first candidate for `step` that passes validation.
+fn answer(
+    step: &QuestionStep,
+    responses: &Responses,
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+) -> (ResponseValue, AnswerSource) {
+    let candidates = step
+        .suggestion
+        .iter()
+        .cloned()
+        .chain(kind_default(&step.kind))
+        .map(|value| (value, AnswerSource::Suggested))
+        .chain(placeholder(&step.kind).map(|value| (value, AnswerSource::Placeholder)));
+
+    let mut rejected = None;
+    for (value, source) in candidates {
+        // Backends do not validate confirmations and choices
+        let checked = match step.kind {
+            QuestionKind::Confirm(_) | QuestionKind::OneOf(_) | QuestionKind::AnyOf(_) => Ok(()),
+            _ => check(&step.kind, &value).and_then(|()| validate(&value, responses, &step.path)),
+        };
+        match checked {
+            Ok(()) => return (value, source),
+            Err(reason) => rejected = Some((value, reason)),
+        }
+    }
+
+    let (value, reason) = rejected.unwrap_or_else(|| {
+        (
+            ResponseValue::String(String::new()),
+            "The question offers nothing to choose".to_string(),
+        )
+    });
+    (value, AnswerSource::Unsafe { reason })
+}
+
+/// The default configured on the question kind, if any.
+pub(crate) fn kind_default(kind: &QuestionKind) -> Option<ResponseValue> {
+    match kind {
+        QuestionKind::Input(q) => q.default.clone().map(ResponseValue::String),
+        QuestionKind::Autocomplete(q) => q.default.clone().map(ResponseValue::String),
+        QuestionKind::Multiline(q) => q.default.clone().map(ResponseValue::String),
+        QuestionKind::Path(q) => q.default.clone().map(ResponseValue::String),
+        QuestionKind::Int(q) => q.default.map(ResponseValue::Int),
+        QuestionKind::Rating(q) => q.default.map(ResponseValue::Int),
+        QuestionKind::Currency(q) => q.default.map(ResponseValue::Int),
+        QuestionKind::Float(q) => q.default.map(ResponseValue::Float),
+        QuestionKind::Color(q) => q.default.map(|color| ResponseValue::String(color.to_hex())),
+        QuestionKind::Confirm(q) => Some(ResponseValue::Bool(q.default)),
+        QuestionKind::Select(q) => q
+            .default
+            .and_then(|idx| q.options.get(idx))
+            .map(|option| ResponseValue::String(option.value.clone())),
+        _ => None,
+    }
+}
+
+/// A value of the right type for `kind`, within its bounds.
+fn placeholder(kind: &QuestionKind) -> Option<ResponseValue> {
+    let value = match kind {
+        QuestionKind::Unit | QuestionKind::AllOf(_) => return None,
+        QuestionKind::Input(_)
+        | QuestionKind::Autocomplete(_)
+        | QuestionKind::Multiline(_)
+        | QuestionKind::Masked(_) => ResponseValue::String(String::new()),
+        QuestionKind::Path(_) => ResponseValue::String(".".to_string()),
+        QuestionKind::Int(q) => ResponseValue::Int(clamp_int(q.min, q.max)),
+        QuestionKind::Currency(q) => ResponseValue::Int(clamp_int(q.min, q.max)),
+        QuestionKind::Rating(q) => ResponseValue::Int(q.min),
+        QuestionKind::Float(q) => ResponseValue::Float(clamp_float(q.min, q.max)),
+        QuestionKind::Color(_) => ResponseValue::String("#000000".to_string()),
+        QuestionKind::Confirm(_) => ResponseValue::Bool(false),
+        QuestionKind::Select(q) => ResponseValue::String(q.options.first()?.value.clone()),
+        QuestionKind::Matrix(q) if !q.columns.is_empty() => {
+            ResponseValue::ChosenVariants(vec![0; q.rows.len()])
+        }
+        QuestionKind::Matrix(_) => return None,
+        QuestionKind::List(q) => {
+            let count = q.min_items.unwrap_or(0);
+            match q.element_kind {
+                ListElementKind::String => ResponseValue::StringList(vec![String::new(); count]),
+                ListElementKind::Int { min, max } => {
+                    ResponseValue::IntList(vec![clamp_int(min, max); count])
+                }
+                ListElementKind::Float { min, max } => {
+                    ResponseValue::FloatList(vec![clamp_float(min, max); count])
+                }
+            }
+        }
+        QuestionKind::OneOf(q) if !q.variants.is_empty() => ResponseValue::ChosenVariant(0),
+        QuestionKind::OneOf(_) => return None,
+        QuestionKind::AnyOf(_) => ResponseValue::ChosenVariants(Vec::new()),
+    };
+    Some(value)
+}
+
+fn clamp_int(min: Option<i64>, max: Option<i64>) -> i64 {
+    0.max(min.unwrap_or(i64::MIN)).min(max.unwrap_or(i64::MAX))
+}
+
+fn clamp_float(min: Option<f64>, max: Option<f64>) -> f64 {
+    0f64.max(min.unwrap_or(f64::MIN))
+        .min(max.unwrap_or(f64::MAX))
+}
+
+/// Check the constraints that interactive backends enforce while prompting.
+pub(crate) fn check(kind: &QuestionKind, value: &ResponseValue) -> Result<(), String> {
+    let outside = |value: f64, min: Option<f64>, max: Option<f64>| {
+        min.is_some_and(|min| value < min) || max.is_some_and(|max| value > max)
+    };
+    match (kind, value) {
+        (QuestionKind::Int(q), ResponseValue::Int(v))
+            if outside(
+                *v as f64,
+                q.min.map(|min| min as f64),
+                q.max.map(|max| max as f64),
+            ) =>
+        {
+            Err(format!("{} is out of bounds", q.format(*v)))
+        }
+        (QuestionKind::Float(q), ResponseValue::Float(v)) if outside(*v, q.min, q.max) => {
+            Err(format!("{} is out of bounds", q.format(*v)))
+        }
+        (QuestionKind::Currency(q), ResponseValue::Int(v)) => q.check_bounds(*v),
+        (QuestionKind::Rating(q), ResponseValue::Int(v)) if !q.contains(*v) => {
+            Err(format!("{v} is not a point of the rating scale"))
+        }
+        (QuestionKind::Color(q), ResponseValue::String(hex)) => q.parse(hex).map(|_| ()),
+        (QuestionKind::Path(q), ResponseValue::String(path)) => q.check(path),
+        (QuestionKind::Select(q), ResponseValue::String(v)) if q.position(v).is_none() => {
+            Err(format!("{v:?} is not one of the available choices"))
+        }
+        (QuestionKind::Matrix(q), ResponseValue::ChosenVariants(columns))
+            if !q.is_complete(columns) =>
+        {
+            Err("Not every row of the matrix is answered".to_string())
+        }
+        _ => Ok(()),
+    }
+}
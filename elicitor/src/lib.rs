@@ -8,4 +8,30 @@ pub use elicitor_macro::Survey;
 
 // Test backend for testing surveys without user interaction
 mod test_backend;
-pub use test_backend::TestBackend;
+pub use test_backend::{TestBackend, TestBackendError};
+
+// Checking assumed answers before a survey starts
+mod assumptions;
+pub use assumptions::{InvalidAssumptions, check_assumptions};
+
+// Backend answering with defaults and placeholders, for smoke tests
+mod auto_answer;
+pub use auto_answer::{
+    AnswerSource, AutoAnswer, AutoAnswerBackend, AutoAnswerError, AutoAnswerReport,
+};
+
+// Backend answering with random values, for load tests
+mod random_answer;
+pub use random_answer::{Distribution, RandomAnswerBackend, RandomAnswerError};
+
+// Backend reading answers line by line, for pipes and CI
+mod plain;
+pub use plain::{Conversation, Notice, PlainBackend, PlainError, converse, pending};
+
+// Backend writing plain sentences, for screen readers
+mod accessible;
+pub use accessible::AccessibleBackend;
+
+// Recording sessions of interactive backends and replaying them
+mod replay;
+pub use replay::{RecordingBackend, ReplayBackend, ReplayError, Session, SessionOutcome};
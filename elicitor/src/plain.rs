@@ -0,0 +1,656 @@
+//! Backend reading answers line by line, for pipes, CI and tests.
+//!
+//! `PlainBackend` prints each prompt and reads the answer from one line of input, so
+//! it needs no terminal. An empty line takes the suggestion or default. Choices are
+//! answered with their number or their name, multi-selects with a comma-separated
+//! list, and lists and multiline text end at an empty line. Invalid answers print an
+//! error and the question is asked again, and input ending early cancels the survey:
+//!
+//! ```text
+//! echo -e "Alice\n30\ny" | my-app
+//! ```
+//!
+//! ```rust,ignore
+//! let backend = PlainBackend::from_reader(std::io::Cursor::new("Alice\n30\ny\n"))
+//!     .with_output(std::io::sink());
+//! let config = SimpleConfig::builder().run(backend)?;
+//! ```
+//!
+//! Backends that ask one question at a time in another way, e.g. in a chat, implement
+//! [`Conversation`] and are run by [`converse`] like this one.
+
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::Mutex;
+
+use crate::auto_answer::{check, kind_default};
+use crate::{
+    ListElementKind, ListQuestion, NoObserver, QuestionKind, QuestionStep, RenderStep,
+    ResponsePath, ResponseValue, Responses, SurveyBackend, SurveyDefinition, SurveyError,
+    SurveyObserver, apply_assumed, record_answer, render_plan,
+};
+
+/// Error type for PlainBackend and AccessibleBackend.
+#[derive(Debug, thiserror::Error)]
+pub enum PlainError {
+    /// The input ended before every question was answered.
+    ///
+    /// Holds the answers given before that.
+    #[error("Survey cancelled by user")]
+    Cancelled { partial: Box<Responses> },
+
+    /// Reading the input or writing the prompts failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl PlainError {
+    /// The answers given before the input ended, if this is a cancellation.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            Self::Io(_) => None,
+        }
+    }
+}
+
+impl From<PlainError> for SurveyError {
+    fn from(err: PlainError) -> Self {
+        match err {
+            PlainError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
+        }
+    }
+}
+
+/// Where answers come from and prompts go to.
+pub(crate) struct PlainIo {
+    pub(crate) input: Box<dyn BufRead + Send>,
+    pub(crate) output: Box<dyn Write + Send>,
+}
+
+impl PlainIo {
+    /// Read from `input` and write to stdout.
+    pub(crate) fn new(input: impl Read + Send + 'static) -> Self {
+        Self {
+            input: Box::new(BufReader::new(input)),
+            output: Box::new(std::io::stdout()),
+        }
+    }
+}
+
+/// A backend that prints prompts and reads one answer per line, without a terminal.
+pub struct PlainBackend {
+    io: Mutex<PlainIo>,
+}
+
+impl std::fmt::Debug for PlainBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlainBackend").finish_non_exhaustive()
+    }
+}
+
+impl Default for PlainBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlainBackend {
+    /// Read answers from stdin and print prompts to stdout.
+    pub fn new() -> Self {
+        Self::from_reader(std::io::stdin())
+    }
+
+    /// Read answers from `input`, e.g. a file or a byte slice, and print prompts to stdout.
+    pub fn from_reader(input: impl Read + Send + 'static) -> Self {
+        Self {
+            io: Mutex::new(PlainIo::new(input)),
+        }
+    }
+
+    /// Print prompts and errors to `output` instead of stdout.
+    pub fn with_output(self, output: impl Write + Send + 'static) -> Self {
+        let mut io = self.io.into_inner().unwrap_or_else(|e| e.into_inner());
+        io.output = Box::new(output);
+        Self { io: Mutex::new(io) }
+    }
+}
+
+impl SurveyBackend for PlainBackend {
+    type Error = PlainError;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        let mut io = self.io.lock().unwrap_or_else(|e| e.into_inner());
+        let responses = converse(&mut *io, definition, validate, observer)?;
+        io.output.flush()?;
+        Ok(responses)
+    }
+}
+
+impl Conversation for PlainIo {
+    type Error = PlainError;
+
+    fn show(&mut self, text: &str, notice: Notice) -> Result<(), PlainError> {
+        match notice {
+            Notice::Prelude => writeln!(self.output, "{text}\n")?,
+            Notice::Heading | Notice::Epilogue => writeln!(self.output, "\n{text}")?,
+        }
+        Ok(())
+    }
+
+    fn ask(
+        &mut self,
+        step: &QuestionStep,
+        responses: &Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<ResponseValue, PlainError> {
+        ask(self, step, responses, validate)
+    }
+
+    fn interrupted(&mut self, error: PlainError, partial: Responses) -> PlainError {
+        match error {
+            PlainError::Cancelled { .. } => PlainError::Cancelled {
+                partial: Box::new(partial),
+            },
+            error => error,
+        }
+    }
+}
+
+/// Text of a survey that a [`Conversation`] shows around the questions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notice {
+    /// The prelude, before the first question.
+    Prelude,
+    /// The heading of a group of questions, before the first question of the group.
+    Heading,
+    /// The epilogue, after the last answer.
+    Epilogue,
+}
+
+/// A backend asking one question at a time, run by [`converse`].
+///
+/// The line-based backends and the chat backend share the order of the questions,
+/// the headings and the handling of cancellation this way, and only differ in how
+/// they show text and ask a single question.
+pub trait Conversation {
+    /// Error of showing text or asking a question.
+    type Error;
+
+    /// Show `text`, the prelude, a heading or the epilogue.
+    fn show(&mut self, text: &str, notice: Notice) -> Result<(), Self::Error>;
+
+    /// Ask `step` until the answer passes `validate`.
+    fn ask(
+        &mut self,
+        step: &QuestionStep,
+        responses: &Responses,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<ResponseValue, Self::Error>;
+
+    /// The error the survey ends with after asking failed with `error`. `partial`
+    /// holds the answers given before, for a cancellation to keep.
+    fn interrupted(&mut self, error: Self::Error, partial: Responses) -> Self::Error;
+}
+
+/// Ask the questions of `definition` one at a time through `conversation`.
+///
+/// Assumed answers are filled in without asking, each heading is shown once before
+/// the first question of its group, and an enum field is asked with the prompt of
+/// the field itself.
+pub fn converse<C: Conversation + ?Sized>(
+    conversation: &mut C,
+    definition: &SurveyDefinition,
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    observer: &dyn SurveyObserver,
+) -> Result<Responses, C::Error> {
+    let mut responses = Responses::new();
+    apply_assumed(definition, &mut responses);
+
+    if let Some(prelude) = &definition.prelude {
+        conversation.show(prelude, Notice::Prelude)?;
+    }
+
+    let mut shown_headings = HashSet::new();
+    while let Some((mut step, headings)) = pending(definition, &responses) {
+        let question_path = match step.kind {
+            QuestionKind::OneOf(_) | QuestionKind::AnyOf(_) => step.path.parent(),
+            _ => step.path.clone(),
+        };
+        for (path, text) in headings {
+            if !shown_headings.insert(path.clone()) {
+                continue;
+            }
+            // An enum field is asked with the prompt of the field itself
+            if path == question_path {
+                step.prompt = text;
+            } else {
+                conversation.show(&text, Notice::Heading)?;
+            }
+        }
+
+        observer.on_question_start(&question_path);
+        match conversation.ask(&step, &responses, validate) {
+            Ok(value) => record_answer(&mut responses, &step, value),
+            Err(error) => return Err(conversation.interrupted(error, responses)),
+        }
+    }
+
+    if let Some(epilogue) = &definition.epilogue {
+        conversation.show(epilogue, Notice::Epilogue)?;
+    }
+    Ok(responses)
+}
+
+/// The next question to ask, with the headings of the groups it is in.
+pub fn pending(
+    definition: &SurveyDefinition,
+    responses: &Responses,
+) -> Option<(QuestionStep, Vec<(ResponsePath, String)>)> {
+    let mut headings = Vec::new();
+    for step in render_plan(definition, responses) {
+        match step {
+            RenderStep::Heading { path, text } => headings.push((path, text)),
+            RenderStep::Question(question) if question.is_pending() => {
+                headings.retain(|(path, _)| question.path.is_within(path));
+                return Some((question, headings));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Ask `step` until the answer passes validation.
+fn ask(
+    io: &mut PlainIo,
+    step: &QuestionStep,
+    responses: &Responses,
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+) -> Result<ResponseValue, PlainError> {
+    let default = step.suggestion.clone().or_else(|| kind_default(&step.kind));
+
+    loop {
+        write_prompt(io, step, default.as_ref())?;
+        let parsed = match &step.kind {
+            QuestionKind::Multiline(_) => {
+                let lines = read_block(io)?;
+                match &default {
+                    Some(value) if lines.is_empty() => Ok(value.clone()),
+                    _ => Ok(ResponseValue::String(lines.join("\n"))),
+                }
+            }
+            QuestionKind::List(list_q) => {
+                let lines = read_block(io)?;
+                parse_list(list_q, &lines).and_then(|value| {
+                    let count = lines.len();
+                    match (list_q.min_items, list_q.max_items) {
+                        (Some(min), _) if count < min => Err(format!("Enter at least {min} items")),
+                        (_, Some(max)) if count > max => Err(format!("Enter at most {max} items")),
+                        _ => Ok(value),
+                    }
+                })
+            }
+            QuestionKind::Matrix(matrix_q) => {
+                let mut columns = Vec::with_capacity(matrix_q.rows.len());
+                let mut parsed = Ok(());
+                for row in &matrix_q.rows {
+                    write!(io.output, "  {row}: ")?;
+                    io.output.flush()?;
+                    let line = read_line(io)?;
+                    match choose(&line, &matrix_q.columns) {
+                        Ok(column) => columns.push(column),
+                        Err(msg) => {
+                            parsed = Err(msg);
+                            break;
+                        }
+                    }
+                }
+                parsed.map(|()| ResponseValue::ChosenVariants(columns))
+            }
+            kind => {
+                let line = read_line(io)?;
+                match &default {
+                    Some(value) if line.is_empty() => Ok(value.clone()),
+                    _ => parse(kind, &line),
+                }
+            }
+        };
+
+        // Multi-selects are validated at the question, like in the other backends
+        let checked = parsed.and_then(|value| match step.kind {
+            QuestionKind::OneOf(_) => Ok(value),
+            QuestionKind::AnyOf(_) => {
+                validate(&value, responses, &step.path.parent()).map(|()| value)
+            }
+            _ => check(&step.kind, &value)
+                .and_then(|()| validate(&value, responses, &step.path))
+                .map(|()| value),
+        });
+        match checked {
+            Ok(value) => return Ok(value),
+            Err(msg) => writeln!(io.output, "Error: {msg}")?,
+        }
+    }
+}
+
+/// Print the prompt of `step`, its choices and the answer an empty line gives.
+fn write_prompt(
+    io: &mut PlainIo,
+    step: &QuestionStep,
+    default: Option<&ResponseValue>,
+) -> Result<(), PlainError> {
+    let choices: Vec<&str> = match &step.kind {
+        QuestionKind::Select(q) => q.options.iter().map(|o| o.label.as_str()).collect(),
+        QuestionKind::OneOf(q) => q.variants.iter().map(|v| v.name.as_str()).collect(),
+        QuestionKind::AnyOf(q) => q.variants.iter().map(|v| v.name.as_str()).collect(),
+        QuestionKind::Matrix(q) => q.columns.iter().map(String::as_str).collect(),
+        _ => Vec::new(),
+    };
+
+    let hint = match &step.kind {
+        QuestionKind::Confirm(_) => match default {
+            Some(ResponseValue::Bool(false)) => " [y/N]".to_string(),
+            _ => " [Y/n]".to_string(),
+        },
+        QuestionKind::AnyOf(_) => match default {
+            Some(ResponseValue::ChosenVariants(indices)) if !indices.is_empty() => {
+                let numbers: Vec<String> = indices.iter().map(|i| (i + 1).to_string()).collect();
+                format!(" (comma-separated) [{}]", numbers.join(","))
+            }
+            _ => " (comma-separated)".to_string(),
+        },
+        QuestionKind::Multiline(_) | QuestionKind::List(_) => {
+            " (end with an empty line)".to_string()
+        }
+        QuestionKind::Masked(_) | QuestionKind::Matrix(_) => String::new(),
+        kind => default
+            .and_then(|value| shown(kind, value))
+            .map(|value| format!(" [{value}]"))
+            .unwrap_or_default(),
+    };
+
+    let block = matches!(
+        step.kind,
+        QuestionKind::Multiline(_) | QuestionKind::List(_) | QuestionKind::Matrix(_)
+    ) || !choices.is_empty();
+    if block {
+        writeln!(io.output, "{}{hint}", step.prompt)?;
+        for (idx, choice) in choices.iter().enumerate() {
+            writeln!(io.output, "  {}) {choice}", idx + 1)?;
+        }
+        if !matches!(
+            step.kind,
+            QuestionKind::Multiline(_) | QuestionKind::List(_) | QuestionKind::Matrix(_)
+        ) {
+            write!(io.output, "> ")?;
+        }
+    } else {
+        write!(io.output, "{}{hint}: ", step.prompt)?;
+    }
+    io.output.flush()?;
+    Ok(())
+}
+
+/// How a default is shown next to the prompt.
+fn shown(kind: &QuestionKind, value: &ResponseValue) -> Option<String> {
+    match (kind, value) {
+        (QuestionKind::Currency(q), ResponseValue::Int(minor)) => Some(q.format(*minor)),
+        (QuestionKind::Int(q), ResponseValue::Int(n)) => Some(q.format(*n)),
+        (QuestionKind::Float(q), ResponseValue::Float(f)) => Some(q.format(*f)),
+        (QuestionKind::Select(q), ResponseValue::String(v)) => {
+            q.position(v).map(|idx| (idx + 1).to_string())
+        }
+        (_, ResponseValue::ChosenVariant(idx)) => Some((idx + 1).to_string()),
+        (_, ResponseValue::String(s)) => Some(s.clone()),
+        (_, ResponseValue::Int(n)) => Some(n.to_string()),
+        (_, ResponseValue::Float(f)) => Some(f.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a one-line answer to a question of `kind`.
+pub(crate) fn parse(kind: &QuestionKind, line: &str) -> Result<ResponseValue, String> {
+    match kind {
+        QuestionKind::Int(q) => q.parse(line).map(ResponseValue::Int),
+        QuestionKind::Rating(_) => line
+            .parse()
+            .map(ResponseValue::Int)
+            .map_err(|_| "Please enter a valid integer".to_string()),
+        QuestionKind::Float(q) => q.parse(line).map(ResponseValue::Float),
+        QuestionKind::Currency(q) => q.parse(line).map(ResponseValue::Int),
+        QuestionKind::Color(q) => q
+            .parse(line)
+            .map(|color| ResponseValue::String(color.to_hex())),
+        QuestionKind::Confirm(_) => match line.to_lowercase().as_str() {
+            "y" | "yes" | "true" => Ok(ResponseValue::Bool(true)),
+            "n" | "no" | "false" => Ok(ResponseValue::Bool(false)),
+            _ => Err("Please answer y or n".to_string()),
+        },
+        QuestionKind::Select(q) => {
+            let labels: Vec<&str> = q.options.iter().map(|o| o.label.as_str()).collect();
+            let idx = q.position(line).map_or_else(|| choose(line, &labels), Ok)?;
+            Ok(ResponseValue::String(q.options[idx].value.clone()))
+        }
+        QuestionKind::OneOf(q) => {
+            let names: Vec<&str> = q.variants.iter().map(|v| v.name.as_str()).collect();
+            choose(line, &names).map(ResponseValue::ChosenVariant)
+        }
+        QuestionKind::AnyOf(q) => {
+            let names: Vec<&str> = q.variants.iter().map(|v| v.name.as_str()).collect();
+            let chosen = line
+                .split(',')
+                .map(str::trim)
+                .filter(|part| !part.is_empty())
+                .map(|part| choose(part, &names))
+                .collect::<Result<Vec<_>, _>>()?;
+            match q.max_selected {
+                Some(max) if chosen.len() > max => Err(format!("Select at most {max} options")),
+                _ => Ok(ResponseValue::ChosenVariants(chosen)),
+            }
+        }
+        _ => Ok(ResponseValue::String(line.to_string())),
+    }
+}
+
+/// Parse the items of a list question, one per line.
+pub(crate) fn parse_list(list_q: &ListQuestion, lines: &[String]) -> Result<ResponseValue, String> {
+    let messages = &list_q.messages;
+    let in_bounds = |value: f64, min: Option<f64>, max: Option<f64>| {
+        if let Some(min) = min.filter(|&min| value < min) {
+            Err(messages.at_least(&min.to_string()))
+        } else if let Some(max) = max.filter(|&max| value > max) {
+            Err(messages.at_most(&max.to_string()))
+        } else {
+            Ok(())
+        }
+    };
+    match &list_q.element_kind {
+        ListElementKind::String => Ok(ResponseValue::StringList(lines.to_vec())),
+        ListElementKind::Int { min, max } => lines
+            .iter()
+            .map(|line| {
+                let n: i64 = line
+                    .parse()
+                    .map_err(|_| messages.item_not_integer(&format!("{line:?}")))?;
+                in_bounds(n as f64, min.map(|m| m as f64), max.map(|m| m as f64)).map(|()| n)
+            })
+            .collect::<Result<_, _>>()
+            .map(ResponseValue::IntList),
+        ListElementKind::Float { min, max } => lines
+            .iter()
+            .map(|line| {
+                let f: f64 = line
+                    .parse()
+                    .map_err(|_| messages.item_not_number(&format!("{line:?}")))?;
+                in_bounds(f, *min, *max).map(|()| f)
+            })
+            .collect::<Result<_, _>>()
+            .map(ResponseValue::FloatList),
+    }
+}
+
+/// The index of the choice named by `answer`, either its number or its name.
+pub(crate) fn choose(answer: &str, choices: &[impl AsRef<str>]) -> Result<usize, String> {
+    if let Ok(number) = answer.parse::<usize>() {
+        return number
+            .checked_sub(1)
+            .filter(|&idx| idx < choices.len())
+            .ok_or_else(|| format!("Enter a number from 1 to {}", choices.len()));
+    }
+    choices
+        .iter()
+        .position(|choice| choice.as_ref().eq_ignore_ascii_case(answer))
+        .ok_or_else(|| format!("{answer:?} is not one of the choices"))
+}
+
+/// One line of input without its line ending. The end of input cancels the survey.
+pub(crate) fn read_line(io: &mut PlainIo) -> Result<String, PlainError> {
+    let mut line = String::new();
+    if io.input.read_line(&mut line)? == 0 {
+        return Err(PlainError::Cancelled {
+            partial: Box::default(),
+        });
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Lines of input up to the next empty line.
+pub(crate) fn read_block(io: &mut PlainIo) -> Result<Vec<String>, PlainError> {
+    let mut lines = Vec::new();
+    loop {
+        let line = read_line(io)?;
+        if line.is_empty() {
+            return Ok(lines);
+        }
+        lines.push(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AllOfQuestion, AnyOfQuestion, ConfirmQuestion, InputQuestion, IntQuestion, ListQuestion,
+        OneOfQuestion, Question, Variant,
+    };
+
+    fn no_validation(_: &ResponseValue, _: &Responses, _: &ResponsePath) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn run(definition: &SurveyDefinition, input: &'static str) -> Result<Responses, PlainError> {
+        PlainBackend::from_reader(input.as_bytes())
+            .with_output(std::io::sink())
+            .collect(definition, &no_validation)
+    }
+
+    #[test]
+    fn answers_are_read_line_by_line() {
+        let definition = SurveyDefinition::new(vec![
+            Question::new("name", "Name?", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "age",
+                "Age?",
+                QuestionKind::Int(IntQuestion::with_bounds(Some(0), Some(150))),
+            ),
+            Question::new("tags", "Tags?", QuestionKind::List(ListQuestion::strings())),
+            Question::new(
+                "ok",
+                "Ok?",
+                QuestionKind::Confirm(ConfirmQuestion::with_default(true)),
+            ),
+        ]);
+
+        // An age out of bounds is asked again, the empty line takes the default
+        let responses = run(&definition, "Alice\n200\n30\na\nb\n\n\n").unwrap();
+        assert_eq!(
+            responses.get(&ResponsePath::new("name")),
+            Some(&ResponseValue::String("Alice".to_string()))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("age")),
+            Some(&ResponseValue::Int(30))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("tags")),
+            Some(&ResponseValue::StringList(vec![
+                "a".to_string(),
+                "b".to_string()
+            ]))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("ok")),
+            Some(&ResponseValue::Bool(true))
+        );
+    }
+
+    #[test]
+    fn choices_are_answered_by_number_or_name() {
+        let variants = vec![
+            Variant::unit("Red"),
+            Variant::unit("Green"),
+            Variant::unit("Blue"),
+        ];
+        let definition = SurveyDefinition::new(vec![
+            Question::new(
+                "color",
+                "Color",
+                QuestionKind::AllOf(AllOfQuestion::new(vec![Question::new(
+                    "",
+                    "",
+                    QuestionKind::OneOf(OneOfQuestion::new(variants.clone())),
+                )])),
+            ),
+            Question::new(
+                "extras",
+                "Extras?",
+                QuestionKind::AnyOf(AnyOfQuestion::new(variants)),
+            ),
+        ]);
+
+        let responses = run(&definition, "green\n1, blue\n").unwrap();
+        assert_eq!(
+            responses.get(&ResponsePath::new("color.selected_variant")),
+            Some(&ResponseValue::ChosenVariant(1))
+        );
+        assert_eq!(
+            responses.get(&ResponsePath::new("extras.selected_variants")),
+            Some(&ResponseValue::ChosenVariants(vec![0, 2]))
+        );
+    }
+
+    #[test]
+    fn input_ending_early_cancels_with_partial_answers() {
+        let definition = SurveyDefinition::new(vec![
+            Question::new("first", "First?", QuestionKind::Input(InputQuestion::new())),
+            Question::new(
+                "second",
+                "Second?",
+                QuestionKind::Input(InputQuestion::new()),
+            ),
+        ]);
+
+        let err = run(&definition, "one\n").unwrap_err();
+        let partial = err.partial().unwrap();
+        assert_eq!(
+            partial.get(&ResponsePath::new("first")),
+            Some(&ResponseValue::String("one".to_string()))
+        );
+        assert!(!partial.contains(&ResponsePath::new("second")));
+    }
+}
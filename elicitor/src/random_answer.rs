@@ -0,0 +1,386 @@
+//! Backend that answers with random values, for load tests.
+//!
+//! `RandomAnswerBackend` generates synthetic submissions that respect bounds, options
+//! and validators, so storage sinks and analytics pipelines can be exercised with
+//! realistic data. Questions without a configured distribution get uniform answers:
+//!
+//! ```rust,ignore
+//! use elicitor::{Distribution, RandomAnswerBackend};
+//!
+//! let backend = RandomAnswerBackend::new()
+//!     .with_seed(7)
+//!     .with_weights("plan", [("free", 8), ("pro", 2)])
+//!     .with_distribution("age", Distribution::IntRange(18..=65))
+//!     .with_distribution("newsletter", Distribution::Probability(0.3));
+//!
+//! for _ in 0..1000 {
+//!     let signup = Signup::builder().run(backend.clone())?;
+//!     store.save(&signup)?;
+//! }
+//! ```
+//!
+//! Clones share the random state, so every run yields a new submission. The same seed
+//! yields the same sequence of submissions.
+
+use std::collections::HashMap;
+use std::ops::{Range, RangeInclusive};
+use std::sync::{Arc, Mutex};
+
+use crate::auto_answer::check;
+use crate::{
+    ListElementKind, NoObserver, PathMode, QuestionKind, QuestionStep, ResponsePath, ResponseValue,
+    Responses, SurveyBackend, SurveyDefinition, SurveyObserver, apply_assumed, next_step,
+    record_answer,
+};
+
+/// Attempts per question before giving up on finding a valid answer.
+const MAX_ATTEMPTS: usize = 64;
+
+/// Words for generated text answers.
+const WORDS: &[&str] = &[
+    "amber", "birch", "cobalt", "delta", "ember", "fjord", "granite", "harbor", "indigo",
+    "juniper", "kestrel", "lumen", "meadow", "nimbus", "orchid", "pebble", "quartz", "river",
+    "summit", "tundra", "umber", "violet", "willow", "zephyr",
+];
+
+/// How answers to one question are distributed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Distribution {
+    /// One of the values, picked in proportion to its weight.
+    Weighted(Vec<(ResponseValue, u32)>),
+    /// An integer, uniformly from the range.
+    IntRange(RangeInclusive<i64>),
+    /// A float, uniformly from the range.
+    FloatRange(Range<f64>),
+    /// For yes/no questions the chance of yes, for multi-selects the chance of each
+    /// variant being picked.
+    Probability(f64),
+    /// For enums, selections, ratings and matrix rows: the weight of each option by index.
+    ChoiceWeights(Vec<u32>),
+}
+
+/// Error type for RandomAnswerBackend.
+#[derive(Debug, thiserror::Error)]
+pub enum RandomAnswerError {
+    #[error("No valid answer for '{path}' after {MAX_ATTEMPTS} attempts: {message}")]
+    NoValidAnswer { path: String, message: String },
+}
+
+/// A backend that answers every question with random, valid values.
+#[derive(Debug, Clone)]
+pub struct RandomAnswerBackend {
+    rng: Arc<Mutex<Rng>>,
+    distributions: HashMap<ResponsePath, Distribution>,
+}
+
+impl Default for RandomAnswerBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RandomAnswerBackend {
+    /// Create a backend seeded from the current time, or with a fixed seed in the browser.
+    pub fn new() -> Self {
+        // The browser has no clock without JavaScript bindings
+        let seed = if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+            0
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_nanos() as u64)
+                .unwrap_or_default()
+        };
+        Self {
+            rng: Arc::new(Mutex::new(Rng(seed))),
+            distributions: HashMap::new(),
+        }
+    }
+
+    /// Use a fixed seed for reproducible submissions.
+    pub fn with_seed(self, seed: u64) -> Self {
+        *self.rng.lock().unwrap_or_else(|e| e.into_inner()) = Rng(seed);
+        self
+    }
+
+    /// Set the distribution of the answers at `path`.
+    ///
+    /// For enums and multi-selects, `path` is the path of the question.
+    pub fn with_distribution(
+        mut self,
+        path: impl Into<ResponsePath>,
+        distribution: Distribution,
+    ) -> Self {
+        self.distributions.insert(path.into(), distribution);
+        self
+    }
+
+    /// Pick the answer at `path` from `values` in proportion to their weights.
+    pub fn with_weights<V: Into<ResponseValue>>(
+        self,
+        path: impl Into<ResponsePath>,
+        values: impl IntoIterator<Item = (V, u32)>,
+    ) -> Self {
+        let values = values
+            .into_iter()
+            .map(|(value, weight)| (value.into(), weight))
+            .collect();
+        self.with_distribution(path, Distribution::Weighted(values))
+    }
+}
+
+impl SurveyBackend for RandomAnswerBackend {
+    type Error = RandomAnswerError;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        let mut responses = Responses::new();
+        apply_assumed(definition, &mut responses);
+
+        while let Some(step) = next_step(definition, &responses) {
+            let question_path = match step.kind {
+                QuestionKind::OneOf(_) | QuestionKind::AnyOf(_) => step.path.parent(),
+                _ => step.path.clone(),
+            };
+            observer.on_question_start(&question_path);
+
+            let distribution = self
+                .distributions
+                .get(&step.path)
+                .or_else(|| self.distributions.get(&question_path));
+            let value = answer(&mut rng, &step, distribution, &responses, validate)?;
+            record_answer(&mut responses, &step, value);
+        }
+
+        Ok(responses)
+    }
+}
+
+/// Draw answers for `step` until one passes validation.
+fn answer(
+    rng: &mut Rng,
+    step: &QuestionStep,
+    distribution: Option<&Distribution>,
+    responses: &Responses,
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+) -> Result<ResponseValue, RandomAnswerError> {
+    let mut message = String::from("The question offers nothing to choose");
+    for _ in 0..MAX_ATTEMPTS {
+        let Some(value) = draw(rng, &step.kind, distribution) else {
+            break;
+        };
+        // Backends do not validate confirmations and choices
+        let checked = match step.kind {
+            QuestionKind::Confirm(_) | QuestionKind::OneOf(_) | QuestionKind::AnyOf(_) => Ok(()),
+            _ => check(&step.kind, &value).and_then(|()| validate(&value, responses, &step.path)),
+        };
+        match checked {
+            Ok(()) => return Ok(value),
+            Err(reason) => message = reason,
+        }
+    }
+    Err(RandomAnswerError::NoValidAnswer {
+        path: step.path.as_str().to_string(),
+        message,
+    })
+}
+
+/// A random answer to a question of `kind`.
+fn draw(
+    rng: &mut Rng,
+    kind: &QuestionKind,
+    distribution: Option<&Distribution>,
+) -> Option<ResponseValue> {
+    match distribution {
+        Some(Distribution::Weighted(values)) => {
+            let weights: Vec<u32> = values.iter().map(|(_, weight)| *weight).collect();
+            return Some(values[rng.weighted(&weights)?].0.clone());
+        }
+        Some(Distribution::IntRange(range)) => {
+            return Some(ResponseValue::Int(rng.int(*range.start(), *range.end())));
+        }
+        Some(Distribution::FloatRange(range)) => {
+            return Some(ResponseValue::Float(rng.float(range.start, range.end)));
+        }
+        _ => {}
+    }
+    let weights = match distribution {
+        Some(Distribution::ChoiceWeights(weights)) => Some(weights.as_slice()),
+        _ => None,
+    };
+    let probability = match distribution {
+        Some(Distribution::Probability(p)) => *p,
+        _ => 0.5,
+    };
+    let pick = |rng: &mut Rng, count: usize| match weights {
+        Some(weights) => rng.weighted(&weights[..weights.len().min(count)]),
+        None => (count > 0).then(|| rng.below(count as u64) as usize),
+    };
+
+    let value = match kind {
+        QuestionKind::Unit | QuestionKind::AllOf(_) => return None,
+        QuestionKind::Input(_) | QuestionKind::Multiline(_) => ResponseValue::String(rng.words()),
+        QuestionKind::Autocomplete(autocomplete_q) => {
+            let suggestions = autocomplete_q.completions("");
+            match pick(rng, suggestions.len()) {
+                Some(idx) => ResponseValue::String(suggestions[idx].clone()),
+                None => ResponseValue::String(rng.words()),
+            }
+        }
+        QuestionKind::Masked(_) => ResponseValue::String(rng.token(16)),
+        QuestionKind::Int(int_q) => {
+            let (min, max) = bounds(int_q.min, int_q.max, 1000);
+            ResponseValue::Int(rng.int(min, max))
+        }
+        QuestionKind::Currency(currency_q) => {
+            let (min, max) = bounds(currency_q.min, currency_q.max, 100_000);
+            ResponseValue::Int(rng.int(min, max))
+        }
+        QuestionKind::Float(float_q) => {
+            let min = float_q
+                .min
+                .unwrap_or(float_q.max.map_or(0.0, |max| max - 1000.0));
+            let max = float_q.max.unwrap_or(min + 1000.0);
+            ResponseValue::Float(rng.float(min, max))
+        }
+        QuestionKind::Rating(rating_q) => {
+            let points: Vec<i64> = rating_q.points().collect();
+            ResponseValue::Int(points[pick(rng, points.len())?])
+        }
+        QuestionKind::Color(_) => ResponseValue::String(format!("#{:06x}", rng.below(0x100_0000))),
+        QuestionKind::Path(path_q) => match (&path_q.mode, &path_q.default) {
+            (PathMode::Any, _) => ResponseValue::String(format!("{}.txt", rng.token(8))),
+            // Existing paths cannot be made up
+            (_, Some(default)) => ResponseValue::String(default.clone()),
+            (_, None) => ResponseValue::String(".".to_string()),
+        },
+        QuestionKind::Confirm(_) => ResponseValue::Bool(rng.chance(probability)),
+        QuestionKind::Select(select_q) => {
+            let idx = pick(rng, select_q.options.len())?;
+            ResponseValue::String(select_q.options[idx].value.clone())
+        }
+        QuestionKind::Matrix(matrix_q) => ResponseValue::ChosenVariants(
+            (0..matrix_q.rows.len())
+                .map(|_| pick(rng, matrix_q.columns.len()))
+                .collect::<Option<_>>()?,
+        ),
+        QuestionKind::List(list_q) => {
+            let min = list_q.min_items.unwrap_or(0);
+            let max = list_q.max_items.unwrap_or(min + 3).max(min);
+            let count = rng.int(min as i64, max as i64) as usize;
+            match list_q.element_kind {
+                ListElementKind::String => {
+                    ResponseValue::StringList((0..count).map(|_| rng.words()).collect())
+                }
+                ListElementKind::Int { min, max } => {
+                    let (min, max) = bounds(min, max, 1000);
+                    ResponseValue::IntList((0..count).map(|_| rng.int(min, max)).collect())
+                }
+                ListElementKind::Float { min, max } => {
+                    let min = min.unwrap_or(max.map_or(0.0, |max| max - 1000.0));
+                    let max = max.unwrap_or(min + 1000.0);
+                    ResponseValue::FloatList((0..count).map(|_| rng.float(min, max)).collect())
+                }
+            }
+        }
+        QuestionKind::OneOf(one_of) => {
+            ResponseValue::ChosenVariant(pick(rng, one_of.variants.len())?)
+        }
+        QuestionKind::AnyOf(any_of) => ResponseValue::ChosenVariants(
+            (0..any_of.variants.len())
+                .filter(|_| rng.chance(probability))
+                .collect(),
+        ),
+    };
+    Some(value)
+}
+
+/// Inclusive bounds, `span` wide on the open side.
+fn bounds(min: Option<i64>, max: Option<i64>, span: i64) -> (i64, i64) {
+    match (min, max) {
+        (Some(min), Some(max)) => (min, max),
+        (Some(min), None) => (min, min.saturating_add(span)),
+        (None, Some(max)) => (max.saturating_sub(span).min(0), max),
+        (None, None) => (0, span),
+    }
+}
+
+/// SplitMix64, small and good enough for synthetic data.
+#[derive(Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`, `n` must not be zero.
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn int(&mut self, min: i64, max: i64) -> i64 {
+        if max <= min {
+            return min;
+        }
+        let span = (max as i128 - min as i128 + 1) as u128;
+        (min as i128 + (self.next() as u128 % span) as i128) as i64
+    }
+
+    fn float(&mut self, min: f64, max: f64) -> f64 {
+        let unit = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
+        min + (max - min) * unit
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        self.float(0.0, 1.0) < probability
+    }
+
+    /// An index picked in proportion to `weights`, `None` if all are zero.
+    fn weighted(&mut self, weights: &[u32]) -> Option<usize> {
+        let total: u64 = weights.iter().map(|&w| w as u64).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut target = self.below(total);
+        for (idx, &weight) in weights.iter().enumerate() {
+            if target < weight as u64 {
+                return Some(idx);
+            }
+            target -= weight as u64;
+        }
+        None
+    }
+
+    fn words(&mut self) -> String {
+        let count = 1 + self.below(3) as usize;
+        (0..count)
+            .map(|_| WORDS[self.below(WORDS.len() as u64) as usize])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn token(&mut self, len: usize) -> String {
+        const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        (0..len)
+            .map(|_| ALPHABET[self.below(ALPHABET.len() as u64) as usize] as char)
+            .collect()
+    }
+}
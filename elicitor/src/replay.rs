@@ -0,0 +1,522 @@
+//! Recording interactive sessions and replaying them.
+//!
+//! `RecordingBackend` wraps an interactive backend and writes every answer of a run
+//! to a session file, also when the user cancels or the backend fails half-way.
+//! `ReplayBackend` answers the same survey from that file without user interaction,
+//! so a session from a bug report becomes a regression test:
+//!
+//! ```rust,ignore
+//! use elicitor::{RecordingBackend, ReplayBackend};
+//!
+//! // While reproducing the bug
+//! let backend = RecordingBackend::new(RequesttyBackend::new(), "signup.session");
+//! let signup = Signup::builder().run(backend)?;
+//!
+//! // In the test
+//! let signup = Signup::builder().run(ReplayBackend::from_file("signup.session")?)?;
+//! ```
+//!
+//! Session files are plain text with one answer per line, preceded by the prompt as a
+//! `#` comment, so they can be read and edited by hand. Masked answers are written like
+//! any other, so keep sessions of real users private.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::auto_answer::check;
+use crate::{
+    FieldHandling, NoObserver, QuestionKind, RenderStep, ResponsePath, ResponseValue, Responses,
+    SensitiveField, SensitiveSink, SurveyBackend, SurveyDefinition, SurveyError, SurveyObserver,
+    apply_assumed, next_step, record_answer, render_plan,
+};
+
+/// First line of every session file.
+const SESSION_HEADER: &str = "# elicitor session";
+
+/// Line marking a session the user cancelled.
+const CANCELLED_MARKER: &str = "!cancelled";
+
+/// Prefix of the line holding the error a session ended with.
+const ERROR_MARKER: &str = "!error\t";
+
+/// How a recorded session ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionOutcome {
+    /// Every question was answered.
+    Completed,
+    /// The user cancelled.
+    Cancelled,
+    /// The backend failed with this message.
+    Failed(String),
+}
+
+/// The answers of one run, in the order they were given.
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// The recorded answers.
+    pub responses: Responses,
+    /// How the run ended.
+    pub outcome: SessionOutcome,
+}
+
+impl Session {
+    /// The session file for `responses`, with the prompts of `definition` as comments.
+    pub fn to_text(&self, definition: &SurveyDefinition) -> String {
+        let prompts: HashMap<ResponsePath, String> = render_plan(definition, &self.responses)
+            .into_iter()
+            .filter_map(|step| match step {
+                RenderStep::Question(question) => Some((question.path, question.prompt)),
+                _ => None,
+            })
+            .collect();
+
+        let mut text = format!("{SESSION_HEADER}\n");
+        for (path, value) in self.responses.iter_exposed() {
+            if let Some(prompt) = prompts.get(path) {
+                text.push_str(&format!("# {}\n", escape(prompt)));
+            }
+            text.push_str(&format!(
+                "{}\t{}\t{}\n",
+                path.as_str(),
+                value.type_name(),
+                encode(value)
+            ));
+        }
+        match &self.outcome {
+            SessionOutcome::Completed => {}
+            SessionOutcome::Cancelled => text.push_str(&format!("{CANCELLED_MARKER}\n")),
+            SessionOutcome::Failed(message) => {
+                text.push_str(&format!("{ERROR_MARKER}{}\n", escape(message)));
+            }
+        }
+        text
+    }
+
+    /// Read a session file written by [`to_text`](Self::to_text).
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        let mut responses = Responses::new();
+        let mut outcome = SessionOutcome::Completed;
+
+        for (idx, line) in text.lines().enumerate() {
+            let invalid = |message: String| ReplayError::InvalidSession {
+                line: idx + 1,
+                message,
+            };
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line == CANCELLED_MARKER {
+                outcome = SessionOutcome::Cancelled;
+                continue;
+            }
+            if let Some(message) = line.strip_prefix(ERROR_MARKER) {
+                outcome = SessionOutcome::Failed(unescape(message));
+                continue;
+            }
+
+            let mut fields = line.splitn(3, '\t');
+            let (Some(path), Some(type_name), Some(payload)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid("expected path, type and value".to_string()));
+            };
+            let value = decode(type_name, payload).map_err(invalid)?;
+            responses.insert(path, value);
+        }
+
+        Ok(Self { responses, outcome })
+    }
+}
+
+/// Error type for RecordingBackend and ReplayBackend.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("Session file line {line}: {message}")]
+    InvalidSession { line: usize, message: String },
+
+    #[error("The session has no answer for '{0}'")]
+    MissingAnswer(String),
+
+    #[error("Recorded answer for '{path}' is rejected: {message}")]
+    ValidationFailed { path: String, message: String },
+
+    /// The recorded session was cancelled at this point.
+    ///
+    /// Holds the answers replayed until then.
+    #[error("Survey cancelled by user")]
+    Cancelled { partial: Box<Responses> },
+
+    #[error("The recorded session failed: {0}")]
+    Failed(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl ReplayError {
+    /// The answers replayed before the recorded cancellation, if this is one.
+    pub fn partial(&self) -> Option<&Responses> {
+        match self {
+            Self::Cancelled { partial } => Some(partial),
+            _ => None,
+        }
+    }
+}
+
+impl From<ReplayError> for SurveyError {
+    fn from(err: ReplayError) -> Self {
+        match err {
+            ReplayError::Cancelled { partial } => SurveyError::Cancelled { partial },
+            err => SurveyError::backend(err),
+        }
+    }
+}
+
+/// A backend that writes the answers of `inner` to a session file.
+///
+/// The file is written after every run, also when it is cancelled or fails. For
+/// failures, the answers that passed validation before the error are written.
+#[derive(Debug, Clone)]
+pub struct RecordingBackend<B> {
+    inner: B,
+    path: PathBuf,
+}
+
+impl<B> RecordingBackend<B> {
+    /// Record the runs of `inner` to the file at `path`.
+    pub fn new(inner: B, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+        }
+    }
+
+    /// The wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+}
+
+/// Session files hold every answer as it was given, secrets included.
+impl<B> SensitiveSink for RecordingBackend<B> {
+    fn sink_name(&self) -> String {
+        format!("session file {}", self.path.display())
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        FieldHandling::Plaintext
+    }
+}
+
+impl<B> SurveyBackend for RecordingBackend<B>
+where
+    B: SurveyBackend,
+    B::Error: Into<SurveyError>,
+{
+    type Error = SurveyError;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        // Answers the inner backend accepted, in case it fails without handing them back
+        let validated = Mutex::new(Responses::new());
+        let recording_validate =
+            |value: &ResponseValue, responses: &Responses, path: &ResponsePath| {
+                let result = validate(value, responses, path);
+                if result.is_ok() {
+                    validated
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .insert(path.clone(), value.clone());
+                }
+                result
+            };
+
+        let result = self
+            .inner
+            .collect_observed(definition, &recording_validate, observer)
+            .map_err(Into::into);
+
+        let session = match &result {
+            Ok(responses) => Session {
+                responses: responses.clone(),
+                outcome: SessionOutcome::Completed,
+            },
+            Err(SurveyError::Cancelled { partial }) => Session {
+                responses: (**partial).clone(),
+                outcome: SessionOutcome::Cancelled,
+            },
+            Err(err) => Session {
+                responses: validated.into_inner().unwrap_or_else(|e| e.into_inner()),
+                outcome: SessionOutcome::Failed(err.to_string()),
+            },
+        };
+        std::fs::write(&self.path, session.to_text(definition))
+            .map_err(|e| SurveyError::backend(ReplayError::Io(e)))?;
+
+        result
+    }
+}
+
+/// A backend that answers from a recorded session.
+///
+/// Every answer is validated again, so a session recorded before a change to the
+/// survey shows where the change breaks it. A session that was cancelled or failed
+/// ends the same way once its answers run out.
+#[derive(Debug, Clone)]
+pub struct ReplayBackend {
+    session: Session,
+}
+
+impl ReplayBackend {
+    /// Replay `session`.
+    pub fn new(session: Session) -> Self {
+        Self { session }
+    }
+
+    /// Replay the session file at `path`.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let text = std::fs::read_to_string(path)?;
+        Session::parse(&text).map(Self::new)
+    }
+
+    /// The session being replayed.
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+}
+
+impl SurveyBackend for ReplayBackend {
+    type Error = ReplayError;
+
+    fn collect(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        let recorded = &self.session.responses;
+        let mut responses = Responses::new();
+        apply_assumed(definition, &mut responses);
+
+        while let Some(step) = next_step(definition, &responses) {
+            let question_path = match step.kind {
+                QuestionKind::OneOf(_) | QuestionKind::AnyOf(_) => step.path.parent(),
+                _ => step.path.clone(),
+            };
+
+            let Some(value) = recorded.get(&step.path) else {
+                return Err(match &self.session.outcome {
+                    SessionOutcome::Completed => {
+                        ReplayError::MissingAnswer(step.path.as_str().to_string())
+                    }
+                    SessionOutcome::Cancelled => ReplayError::Cancelled {
+                        partial: Box::new(responses),
+                    },
+                    SessionOutcome::Failed(message) => ReplayError::Failed(message.clone()),
+                });
+            };
+            observer.on_question_start(&question_path);
+
+            // Choices are validated at the question, like in the interactive backends
+            let checked = match step.kind {
+                QuestionKind::OneOf(_) => Ok(()),
+                QuestionKind::AnyOf(_) => validate(value, &responses, &question_path),
+                _ => {
+                    check(&step.kind, value).and_then(|()| validate(value, &responses, &step.path))
+                }
+            };
+            if let Err(message) = checked {
+                return Err(ReplayError::ValidationFailed {
+                    path: step.path.as_str().to_string(),
+                    message,
+                });
+            }
+
+            record_answer(&mut responses, &step, value.clone());
+        }
+
+        // Comments are no questions of their own
+        for (path, value) in recorded.iter() {
+            if path.last() == Some(crate::COMMENT_KEY) && !responses.contains(path) {
+                responses.insert(path.clone(), value.clone());
+            }
+        }
+
+        Ok(responses)
+    }
+}
+
+/// The payload of a session line for `value`; list items are separated by tabs.
+fn encode(value: &ResponseValue) -> String {
+    fn join<T: ToString>(items: &[T]) -> String {
+        items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
+    match value {
+        ResponseValue::String(s) => escape(s),
+        ResponseValue::Int(n) => n.to_string(),
+        ResponseValue::Float(f) => f.to_string(),
+        ResponseValue::Bool(b) => b.to_string(),
+        ResponseValue::ChosenVariant(idx) => idx.to_string(),
+        ResponseValue::ChosenVariants(indices) => join(indices),
+        ResponseValue::StringList(items) => items
+            .iter()
+            .map(|item| escape(item))
+            .collect::<Vec<_>>()
+            .join("\t"),
+        ResponseValue::IntList(items) => join(items),
+        ResponseValue::FloatList(items) => join(items),
+    }
+}
+
+/// The value of type `type_name` written as `payload` by [`encode`].
+fn decode(type_name: &str, payload: &str) -> Result<ResponseValue, String> {
+    fn items<T: std::str::FromStr>(payload: &str) -> Result<Vec<T>, String> {
+        if payload.is_empty() {
+            return Ok(Vec::new());
+        }
+        payload
+            .split('\t')
+            .map(|item| item.parse().map_err(|_| format!("invalid item {item:?}")))
+            .collect()
+    }
+    fn one<T: std::str::FromStr>(payload: &str) -> Result<T, String> {
+        payload
+            .parse()
+            .map_err(|_| format!("invalid value {payload:?}"))
+    }
+    let value = match type_name {
+        "String" => ResponseValue::String(unescape(payload)),
+        "Int" => ResponseValue::Int(one(payload)?),
+        "Float" => ResponseValue::Float(one(payload)?),
+        "Bool" => ResponseValue::Bool(one(payload)?),
+        "ChosenVariant" => ResponseValue::ChosenVariant(one(payload)?),
+        "ChosenVariants" => ResponseValue::ChosenVariants(items(payload)?),
+        "StringList" if payload.is_empty() => ResponseValue::StringList(Vec::new()),
+        "StringList" => ResponseValue::StringList(payload.split('\t').map(unescape).collect()),
+        "IntList" => ResponseValue::IntList(items(payload)?),
+        "FloatList" => ResponseValue::FloatList(items(payload)?),
+        other => return Err(format!("unknown type {other:?}")),
+    };
+    Ok(value)
+}
+
+/// `text` with backslashes, tabs and line breaks escaped, to fit in one field.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The text [`escape`] was given.
+fn unescape(field: &str) -> String {
+    let mut text = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => text.push('\t'),
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            Some(other) => text.push(other),
+            None => text.push('\\'),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_text_round_trips() {
+        let mut responses = Responses::new();
+        responses.insert("name", "Ada\tLovelace\nLondon \\ UK");
+        responses.insert("age", 36i64);
+        responses.insert("height", 1.65);
+        responses.insert("developer", true);
+        responses.insert("role.selected_variant", ResponseValue::ChosenVariant(1));
+        responses.insert(
+            "langs.selected_variants",
+            ResponseValue::ChosenVariants(vec![0, 2]),
+        );
+        responses.insert(
+            "tags",
+            ResponseValue::StringList(vec!["a b".to_string(), String::new()]),
+        );
+        responses.insert("scores", ResponseValue::IntList(Vec::new()));
+        let session = Session {
+            responses,
+            outcome: SessionOutcome::Failed("disk\nfull".to_string()),
+        };
+
+        let text = session.to_text(&SurveyDefinition::new(Vec::new()));
+        assert!(text.starts_with(SESSION_HEADER));
+        let parsed = Session::parse(&text).unwrap();
+        assert_eq!(parsed.outcome, session.outcome);
+        assert!(
+            parsed.responses.iter().eq(session.responses.iter()),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_reported() {
+        let err =
+            Session::parse("# elicitor session\nname\tString\tAda\nage\tInt\tmany\n").unwrap_err();
+        assert!(matches!(err, ReplayError::InvalidSession { line: 3, .. }));
+    }
+
+    #[test]
+    fn recordings_are_plaintext_in_privacy_reports() {
+        use crate::{MaskedQuestion, PrivacyReport, Question};
+
+        let definition = SurveyDefinition::new(vec![Question::new(
+            "pin",
+            "PIN:",
+            QuestionKind::Masked(MaskedQuestion::new()),
+        )]);
+        let recording = RecordingBackend::new((), "login.session");
+        let report = PrivacyReport::new(&definition).with_sink(&recording);
+        assert_eq!(
+            report.handling(&ResponsePath::new("pin"), "session file login.session"),
+            Some(FieldHandling::Plaintext)
+        );
+    }
+}
@@ -27,10 +27,31 @@
 //! assert_eq!(config.host, "localhost");
 //! assert_eq!(config.port, 8080);
 //! ```
+//!
+//! To also check which questions are asked and in which order, script the interview
+//! with [`TestBackend::expect`]. The run fails on a question the script does not
+//! expect next, on scripted questions that are never asked, and on answers of the
+//! wrong type:
+//!
+//! ```rust,ignore
+//! let order: Order = Order::builder()
+//!     .run(
+//!         TestBackend::new()
+//!             .expect("customer.name", "Ada")
+//!             .expect("delivery", ResponseValue::ChosenVariant(1)) // Courier
+//!             .expect("delivery.address", "1 Main St"),
+//!     )
+//!     .unwrap();
+//! ```
 
 use std::collections::HashMap;
 
-use crate::{ResponsePath, ResponseValue, Responses, SurveyBackend, SurveyDefinition};
+use crate::auto_answer::check;
+use crate::{
+    COMMENT_KEY, Color, DefaultValue, NoObserver, QuestionKind, ResponsePath, ResponseValue,
+    Responses, SurveyBackend, SurveyDefinition, SurveyObserver, apply_assumed, next_step,
+    record_answer,
+};
 
 /// A test backend that returns pre-configured responses.
 ///
@@ -39,6 +60,8 @@ use crate::{ResponsePath, ResponseValue, Responses, SurveyBackend, SurveyDefinit
 #[derive(Debug, Clone, Default)]
 pub struct TestBackend {
     responses: HashMap<String, ResponseValue>,
+    /// Questions expected in order, with their answers. Replaces `responses` when set.
+    script: Vec<(String, ResponseValue)>,
 }
 
 /// Error type for TestBackend.
@@ -49,6 +72,22 @@ pub enum TestBackendError {
 
     #[error("Validation failed for '{path}': {message}")]
     ValidationFailed { path: String, message: String },
+
+    #[error("Asked '{asked}' but the script expected '{expected}'")]
+    UnexpectedQuestion { asked: String, expected: String },
+
+    #[error("Asked '{0}' after the script ended")]
+    UnscriptedQuestion(String),
+
+    #[error("Scripted questions were never asked: {}", .0.join(", "))]
+    SkippedQuestions(Vec<String>),
+
+    #[error("Answer for '{path}' should be {expected}, got {got}")]
+    TypeMismatch {
+        path: String,
+        expected: &'static str,
+        got: &'static str,
+    },
 }
 
 impl TestBackend {
@@ -56,9 +95,21 @@ impl TestBackend {
     pub fn new() -> Self {
         Self {
             responses: HashMap::new(),
+            script: Vec::new(),
         }
     }
 
+    /// Expect `path` to be the next question asked and answer it with `value`.
+    ///
+    /// Once a step is scripted, only the script answers questions. Enum and
+    /// multi-select questions can be named by their field path or by their
+    /// `selected_variant(s)` key. Follow-up questions of a variant are scripted
+    /// at their full path, e.g. `"delivery.address"`.
+    pub fn expect(mut self, path: impl Into<String>, value: impl Into<ResponseValue>) -> Self {
+        self.script.push((path.into(), value.into()));
+        self
+    }
+
     /// Add a response for a given path.
     ///
     /// The path should match the field name or dot-separated path for nested fields.
@@ -91,6 +142,17 @@ impl TestBackend {
         self.with_response(path, ResponseValue::Bool(value))
     }
 
+    /// Add a color response, stored as a `#rrggbb` string.
+    pub fn with_color(self, path: impl Into<String>, color: Color) -> Self {
+        self.with_response(path, ResponseValue::String(color.to_hex()))
+    }
+
+    /// Add a comment on the answer at `path`, for questions with `#[allow_comment]`.
+    pub fn with_comment(self, path: impl Into<String>, comment: impl Into<String>) -> Self {
+        let path = format!("{}.{COMMENT_KEY}", path.into());
+        self.with_response(path, ResponseValue::String(comment.into()))
+    }
+
     /// Add a chosen variant response (for OneOf questions).
     pub fn with_variant(self, path: impl Into<String>, index: usize) -> Self {
         self.with_response(path, ResponseValue::ChosenVariant(index))
@@ -110,6 +172,19 @@ impl SurveyBackend for TestBackend {
         definition: &SurveyDefinition,
         validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
     ) -> Result<Responses, Self::Error> {
+        self.collect_observed(definition, validate, &NoObserver)
+    }
+
+    fn collect_observed(
+        &self,
+        definition: &SurveyDefinition,
+        validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+        observer: &dyn SurveyObserver,
+    ) -> Result<Responses, Self::Error> {
+        if !self.script.is_empty() {
+            return collect_scripted(definition, &self.script, validate, observer);
+        }
+
         let mut responses = Responses::new();
 
         // Recursively collect responses for all questions
@@ -119,21 +194,97 @@ impl SurveyBackend for TestBackend {
             &self.responses,
             &mut responses,
             validate,
+            observer,
         )?;
 
         Ok(responses)
     }
 }
 
+/// Answer the questions of `definition` in the order they are asked, from `script`.
+fn collect_scripted(
+    definition: &SurveyDefinition,
+    script: &[(String, ResponseValue)],
+    validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    observer: &dyn SurveyObserver,
+) -> Result<Responses, TestBackendError> {
+    let mut responses = Responses::new();
+    apply_assumed(definition, &mut responses);
+
+    let mut script = script.iter();
+    while let Some(step) = next_step(definition, &responses) {
+        let question_path = match step.kind {
+            QuestionKind::OneOf(_) | QuestionKind::AnyOf(_) => step.path.parent(),
+            _ => step.path.clone(),
+        };
+        let asked = step.path.as_str().to_string();
+
+        let Some((expected, value)) = script.next() else {
+            return Err(TestBackendError::UnscriptedQuestion(asked));
+        };
+        if *expected != asked && expected != question_path.as_str() {
+            return Err(TestBackendError::UnexpectedQuestion {
+                asked,
+                expected: expected.clone(),
+            });
+        }
+        observer.on_question_start(&question_path);
+
+        let expected_type = step.kind.answer_type();
+        if value.type_name() != expected_type {
+            return Err(TestBackendError::TypeMismatch {
+                path: asked,
+                expected: expected_type,
+                got: value.type_name(),
+            });
+        }
+
+        // Choices are checked against the offered variants, multi-selects validated
+        // at the question like in the interactive backends
+        let checked = match &step.kind {
+            QuestionKind::OneOf(one_of) => match value {
+                ResponseValue::ChosenVariant(idx) if *idx >= one_of.variants.len() => {
+                    Err(format!("There is no variant {idx}"))
+                }
+                _ => Ok(()),
+            },
+            QuestionKind::AnyOf(any_of) => match value {
+                ResponseValue::ChosenVariants(indices)
+                    if indices.iter().any(|&idx| idx >= any_of.variants.len()) =>
+                {
+                    Err(format!("{indices:?} names a variant that does not exist"))
+                }
+                _ => validate(value, &responses, &question_path),
+            },
+            QuestionKind::Confirm(_) => Ok(()),
+            kind => check(kind, value).and_then(|()| validate(value, &responses, &step.path)),
+        };
+        if let Err(message) = checked {
+            return Err(TestBackendError::ValidationFailed {
+                path: asked,
+                message,
+            });
+        }
+
+        record_answer(&mut responses, &step, value.clone());
+    }
+
+    let skipped: Vec<String> = script.map(|(path, _)| path.clone()).collect();
+    if skipped.is_empty() {
+        Ok(responses)
+    } else {
+        Err(TestBackendError::SkippedQuestions(skipped))
+    }
+}
+
 fn collect_question_responses(
     questions: &[crate::Question],
     prefix: &ResponsePath,
     test_responses: &HashMap<String, ResponseValue>,
     responses: &mut Responses,
     validate: &dyn Fn(&ResponseValue, &Responses, &ResponsePath) -> Result<(), String>,
+    observer: &dyn SurveyObserver,
 ) -> Result<(), TestBackendError> {
-    use crate::QuestionKind;
-
     for question in questions {
         let full_path = if prefix.is_empty() {
             question.path().clone()
@@ -143,11 +294,43 @@ fn collect_question_responses(
 
         let path_str = full_path.as_str().to_string();
 
+        if question.allows_comment()
+            && let Some(comment) = test_responses.get(full_path.child(COMMENT_KEY).as_str())
+        {
+            responses.insert(full_path.child(COMMENT_KEY), comment.clone());
+        }
+
+        // Assumed answers are used as given unless the test overrides them. A `false`
+        // assumed for anything but a confirmation marks an `Option` assumed to be `None`.
+        // Assumed choices are followed into the fields of the chosen variants below.
+        let choice = matches!(
+            question.kind(),
+            QuestionKind::OneOf(_) | QuestionKind::AnyOf(_)
+        );
+        if let DefaultValue::Assumed(value) = question.default()
+            && !choice
+            && !test_responses.contains_key(&path_str)
+        {
+            let none_marker = matches!(value, ResponseValue::Bool(false))
+                && !matches!(question.kind(), QuestionKind::Confirm(_));
+            if !none_marker {
+                responses.insert(full_path.clone(), value.clone());
+            }
+            continue;
+        }
+
+        if !matches!(question.kind(), QuestionKind::Unit | QuestionKind::AllOf(_)) {
+            observer.on_question_start(&full_path);
+        }
+
         match question.kind() {
             QuestionKind::Unit => {
                 // No response needed for unit types
             }
-            QuestionKind::Input(_) | QuestionKind::Multiline(_) | QuestionKind::Masked(_) => {
+            QuestionKind::Input(_)
+            | QuestionKind::Autocomplete(_)
+            | QuestionKind::Multiline(_)
+            | QuestionKind::Masked(_) => {
                 if let Some(value) = test_responses.get(&path_str) {
                     // Validate before inserting
                     if let Err(msg) = validate(value, responses, &full_path) {
@@ -189,6 +372,72 @@ fn collect_question_responses(
                     return Err(TestBackendError::MissingResponse(path_str));
                 }
             }
+            QuestionKind::Currency(currency) => {
+                if let Some(value) = test_responses.get(&path_str) {
+                    // Amounts are whole minor units within the bounds
+                    let in_bounds = match value.as_int() {
+                        Some(minor) => currency.check_bounds(minor),
+                        None => Err(format!("{:?} is not an amount in minor units", value)),
+                    };
+                    if let Err(msg) =
+                        in_bounds.and_then(|()| validate(value, responses, &full_path))
+                    {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: msg,
+                        });
+                    }
+                    responses.insert(full_path.clone(), value.clone());
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(path_str));
+                }
+            }
+            QuestionKind::Color(color) => {
+                if let Some(value) = test_responses.get(&path_str) {
+                    // Colors are hex strings, stored in their normalized `#rrggbb` form
+                    let parsed = match value.as_str() {
+                        Some(hex) => color.parse(hex),
+                        None => Err(format!("{:?} is not a hex color", value)),
+                    };
+                    let value = match parsed {
+                        Ok(parsed) => ResponseValue::String(parsed.to_hex()),
+                        Err(message) => {
+                            return Err(TestBackendError::ValidationFailed {
+                                path: path_str,
+                                message,
+                            });
+                        }
+                    };
+                    if let Err(msg) = validate(&value, responses, &full_path) {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: msg,
+                        });
+                    }
+                    responses.insert(full_path.clone(), value);
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(path_str));
+                }
+            }
+            QuestionKind::Path(path_q) => {
+                if let Some(value) = test_responses.get(&path_str) {
+                    // Files and directories must exist with the right extension
+                    let checked = match value.as_str() {
+                        Some(path) => path_q.check(path),
+                        None => Err(format!("{:?} is not a path", value)),
+                    };
+                    if let Err(msg) = checked.and_then(|()| validate(value, responses, &full_path))
+                    {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: msg,
+                        });
+                    }
+                    responses.insert(full_path.clone(), value.clone());
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(path_str));
+                }
+            }
             QuestionKind::Confirm(_) => {
                 if let Some(value) = test_responses.get(&path_str) {
                     responses.insert(full_path, value.clone());
@@ -196,6 +445,72 @@ fn collect_question_responses(
                     return Err(TestBackendError::MissingResponse(path_str));
                 }
             }
+            QuestionKind::Rating(rating) => {
+                if let Some(value) = test_responses.get(&path_str) {
+                    // The answer must be a point of the scale
+                    let on_scale = value.as_int().is_some_and(|v| rating.contains(v));
+                    if !on_scale {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: format!("{:?} is not a point of the rating scale", value),
+                        });
+                    }
+                    if let Err(msg) = validate(value, responses, &full_path) {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: msg,
+                        });
+                    }
+                    responses.insert(full_path.clone(), value.clone());
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(path_str));
+                }
+            }
+            QuestionKind::Matrix(matrix) => {
+                if let Some(value) = test_responses.get(&path_str) {
+                    // Every row needs one of the columns
+                    let complete = match value {
+                        ResponseValue::ChosenVariants(columns) => matrix.is_complete(columns),
+                        _ => false,
+                    };
+                    if !complete {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: format!("{:?} does not answer every row of the matrix", value),
+                        });
+                    }
+                    if let Err(msg) = validate(value, responses, &full_path) {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: msg,
+                        });
+                    }
+                    responses.insert(full_path.clone(), value.clone());
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(path_str));
+                }
+            }
+            QuestionKind::Select(select) => {
+                if let Some(value) = test_responses.get(&path_str) {
+                    // The answer must be one of the offered options
+                    let offered = value.as_str().and_then(|s| select.position(s));
+                    if offered.is_none() {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: format!("{:?} is not one of the available choices", value),
+                        });
+                    }
+                    if let Err(msg) = validate(value, responses, &full_path) {
+                        return Err(TestBackendError::ValidationFailed {
+                            path: path_str,
+                            message: msg,
+                        });
+                    }
+                    responses.insert(full_path.clone(), value.clone());
+                } else if !question.is_assumed() {
+                    return Err(TestBackendError::MissingResponse(path_str));
+                }
+            }
             QuestionKind::List(_) => {
                 if let Some(value) = test_responses.get(&path_str) {
                     // Validate before inserting
@@ -212,7 +527,10 @@ fn collect_question_responses(
             }
             QuestionKind::OneOf(one_of) => {
                 let variant_key = format!("{}.{}", path_str, crate::SELECTED_VARIANT_KEY);
-                if let Some(ResponseValue::ChosenVariant(idx)) = test_responses.get(&variant_key) {
+                if let Some(ResponseValue::ChosenVariant(idx)) = test_responses
+                    .get(&variant_key)
+                    .or_else(|| assumed_value(question))
+                {
                     responses.insert(
                         full_path.child(crate::SELECTED_VARIANT_KEY),
                         ResponseValue::ChosenVariant(*idx),
@@ -228,6 +546,7 @@ fn collect_question_responses(
                             test_responses,
                             responses,
                             validate,
+                            observer,
                         )?;
                     }
                 } else if !question.is_assumed() {
@@ -236,8 +555,9 @@ fn collect_question_responses(
             }
             QuestionKind::AnyOf(any_of) => {
                 let variants_key = format!("{}.{}", path_str, crate::SELECTED_VARIANTS_KEY);
-                if let Some(ResponseValue::ChosenVariants(indices)) =
-                    test_responses.get(&variants_key)
+                if let Some(ResponseValue::ChosenVariants(indices)) = test_responses
+                    .get(&variants_key)
+                    .or_else(|| assumed_value(question))
                 {
                     responses.insert(
                         full_path.child(crate::SELECTED_VARIANTS_KEY),
@@ -256,6 +576,7 @@ fn collect_question_responses(
                                 test_responses,
                                 responses,
                                 validate,
+                                observer,
                             )?;
                         }
                     }
@@ -270,6 +591,7 @@ fn collect_question_responses(
                     test_responses,
                     responses,
                     validate,
+                    observer,
                 )?;
             }
         }
@@ -277,3 +599,11 @@ fn collect_question_responses(
 
     Ok(())
 }
+
+/// The value assumed for `question`, if any.
+fn assumed_value(question: &crate::Question) -> Option<&ResponseValue> {
+    match question.default() {
+        DefaultValue::Assumed(value) => Some(value),
+        _ => None,
+    }
+}
@@ -40,6 +40,150 @@ fn simple_survey_with_test_backend() {
     assert!(config.developer);
 }
 
+#[test]
+fn simple_survey_from_piped_input() {
+    let backend = elicitor::PlainBackend::from_reader("Alice\n30\ny\n".as_bytes())
+        .with_output(std::io::sink());
+    let config: SimpleConfig = SimpleConfig::builder().run(backend).unwrap();
+
+    assert_eq!(
+        config,
+        SimpleConfig {
+            name: "Alice".to_string(),
+            age: 30,
+            developer: true,
+        }
+    );
+}
+
+#[test]
+fn hooks_see_each_question_and_can_replace_or_reask() {
+    let shown = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let log = shown.clone();
+    let backend = elicitor::PlainBackend::from_reader("root\n  Alice  \n30\ny\n".as_bytes())
+        .with_output(std::io::sink());
+    let config = SimpleConfig::builder()
+        .before_each(move |path| log.lock().unwrap().push(path.to_string()))
+        .after_each(|_, answer| match answer {
+            elicitor::ResponseValue::String(name) if name == "root" => {
+                elicitor::AnswerAction::Reask("This name is reserved".to_string())
+            }
+            elicitor::ResponseValue::String(name) => {
+                elicitor::AnswerAction::Replace(name.trim().into())
+            }
+            _ => elicitor::AnswerAction::Keep,
+        })
+        .run(backend)
+        .unwrap();
+
+    assert_eq!(config.name, "Alice");
+    assert_eq!(*shown.lock().unwrap(), vec!["name", "age", "developer"]);
+}
+
+fn adult(
+    value: &elicitor::ResponseValue,
+    _: &elicitor::Responses,
+    _: &elicitor::ResponsePath,
+) -> Result<(), elicitor::ValidationError> {
+    match value {
+        elicitor::ResponseValue::Int(age) if *age < 18 => Err(elicitor::ValidationError::keyed(
+            "error.too-young",
+            "You must be at least 18.",
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Greeting {
+    #[ask("Name:", key = "greeting.name")]
+    name: String,
+
+    #[ask(key = "greeting.age")]
+    #[validate(adult)]
+    age: u32,
+
+    #[ask("Language:")]
+    language: Language,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Language {
+    #[ask("English", key = "language.english")]
+    English,
+    #[ask("German", key = "language.german")]
+    German,
+}
+
+#[test]
+fn keyed_prompts_and_validator_messages_are_translated() {
+    let german = |key: &str| {
+        let text = match key {
+            "greeting.name" => "Vorname:",
+            "greeting.age" => "Alter:",
+            "language.german" => "Deutsch",
+            "error.too-young" => "Du musst mindestens 18 sein.",
+            _ => return None,
+        };
+        Some(text.to_string())
+    };
+
+    let plan = Greeting::builder()
+        .with_translator(german)
+        .plan()
+        .to_string();
+    assert!(plan.contains("Vorname: (name)"));
+    assert!(plan.contains("Alter: (age)"));
+    assert!(plan.contains("├── English (if chosen)"));
+    assert!(plan.contains("└── Deutsch (if chosen)"));
+
+    let errors = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    struct Errors(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+    impl elicitor::SurveyObserver for Errors {
+        fn on_validation_error(&self, _: &elicitor::ResponsePath, message: &str) {
+            self.0.lock().unwrap().push(message.to_string());
+        }
+    }
+    let backend = elicitor::PlainBackend::from_reader("Ada\n12\n30\n2\n".as_bytes())
+        .with_output(std::io::sink());
+    let greeting = Greeting::builder()
+        .with_translator(german)
+        .with_observer(Errors(errors.clone()))
+        .run(backend)
+        .unwrap();
+
+    assert_eq!(greeting.language, Language::German);
+    assert_eq!(
+        *errors.lock().unwrap(),
+        vec!["Du musst mindestens 18 sein."]
+    );
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Order {
+    #[ask("Price:")]
+    price: f64,
+
+    #[ask("Quantity:")]
+    quantity: u32,
+}
+
+#[test]
+fn numbers_are_typed_in_the_separators_of_the_locale() {
+    // "1.5" is not a number in German, where "." only groups thousands
+    let backend = elicitor::PlainBackend::from_reader("1.5\n1.234,5\n2.000\n".as_bytes())
+        .with_output(std::io::sink());
+    let order = Order::builder().with_locale("de").run(backend).unwrap();
+
+    assert_eq!(
+        order,
+        Order {
+            price: 1234.5,
+            quantity: 2000,
+        }
+    );
+}
+
 #[test]
 fn builder_with_suggestions() {
     // Just verify the builder methods exist and compile
@@ -124,128 +268,1235 @@ fn mask_and_multiline() {
     ));
 }
 
-// ============================================================================
-// Nested Builder Tests
-// ============================================================================
+#[test]
+fn masked_answers_are_redacted() {
+    use elicitor::{ResponseError, ResponsePath};
 
-#[derive(Survey, Debug, PartialEq)]
-struct Address {
-    #[ask("Street address:")]
-    street: String,
+    let (form, responses) = PasswordForm::builder()
+        .run_with_responses(
+            TestBackend::new()
+                .with_string("password", "hunter2")
+                .with_string("bio", "Gardener"),
+        )
+        .unwrap();
 
-    #[ask("City:")]
-    city: String,
+    assert_eq!(form.password, "hunter2");
 
-    #[ask("Zip code:")]
-    zip: String,
+    let password = ResponsePath::new("password");
+    assert!(responses.is_redacted(&password));
+    assert!(matches!(
+        responses.get_string(&password),
+        Err(ResponseError::Redacted(_))
+    ));
+    assert_eq!(responses.expose(&password).unwrap(), "hunter2");
+
+    let debug = format!("{responses:?}");
+    assert!(!debug.contains("hunter2"));
+    assert!(debug.contains("Gardener"));
+}
+
+#[test]
+fn privacy_report_covers_masked_fields() {
+    use elicitor::{FieldHandling, PrivacyReport, RESPONSES_SINK, ResponsePath};
+
+    let report = PrivacyReport::new(&PasswordForm::survey());
+    let paths: Vec<_> = report.fields().iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, vec!["password"]);
+    assert_eq!(
+        report.handling(&ResponsePath::new("password"), RESPONSES_SINK),
+        Some(FieldHandling::Redacted)
+    );
+    assert_eq!(report.plaintext_fields().count(), 0);
+}
+
+fn available_branches() -> Vec<String> {
+    vec!["main".to_string(), "develop".to_string()]
+}
+
+fn available_interfaces() -> Vec<(&'static str, &'static str)> {
+    vec![("lo", "Loopback"), ("eth0", "Ethernet")]
 }
 
 #[derive(Survey, Debug, PartialEq)]
-enum PaymentMethod {
-    #[ask("Cash payment")]
-    Cash,
+struct DeployForm {
+    #[ask("Branch to deploy:")]
+    #[choices_from(available_branches)]
+    branch: String,
 
-    #[ask("Credit card")]
-    CreditCard {
-        #[ask("Card number:")]
-        number: String,
-        #[ask("CVV:")]
-        cvv: String,
-    },
+    #[ask("Network interface:")]
+    #[choices_from("available_interfaces")]
+    interface: String,
+}
 
-    #[ask("Bank transfer")]
-    BankTransfer {
-        #[ask("IBAN:")]
-        iban: String,
-    },
+#[test]
+fn choices_from_survey_definition() {
+    use elicitor::{QuestionKind, SelectOption};
+
+    let survey = DeployForm::survey();
+
+    match survey.questions[0].kind() {
+        QuestionKind::Select(select_q) => {
+            assert_eq!(
+                select_q.options,
+                vec![SelectOption::from("main"), SelectOption::from("develop")]
+            );
+        }
+        _ => panic!("Expected Select question kind"),
+    }
+    match survey.questions[1].kind() {
+        QuestionKind::Select(select_q) => {
+            assert_eq!(select_q.options[1], SelectOption::new("eth0", "Ethernet"));
+        }
+        _ => panic!("Expected Select question kind"),
+    }
+}
+
+#[test]
+fn choices_from_with_test_backend() {
+    let form: DeployForm = DeployForm::builder()
+        .run(
+            TestBackend::new()
+                .with_string("branch", "develop")
+                .with_string("interface", "eth0"),
+        )
+        .unwrap();
+
+    assert_eq!(form.branch, "develop");
+    assert_eq!(form.interface, "eth0");
+}
+
+#[test]
+fn choices_from_rejects_unknown_choice() {
+    let result = DeployForm::builder().run(
+        TestBackend::new()
+            .with_string("branch", "feature")
+            .with_string("interface", "lo"),
+    );
+
+    assert!(result.is_err());
+}
+
+fn known_hosts(input: &str) -> Vec<String> {
+    vec![format!("{input}.local")]
 }
 
 #[derive(Survey, Debug, PartialEq)]
-struct OrderForm {
-    #[ask("Customer name:")]
-    customer_name: String,
+struct ConnectForm {
+    #[ask("Editor:")]
+    #[autocomplete(["vim", "emacs", "helix"])]
+    editor: String,
 
-    #[ask("Shipping address:")]
-    shipping_address: Address,
+    #[ask("Host:")]
+    #[autocomplete(known_hosts)]
+    host: String,
+}
 
-    #[ask("Payment method:")]
-    payment: PaymentMethod,
+#[test]
+fn autocomplete_survey_definition() {
+    use elicitor::QuestionKind;
 
-    #[ask("Nickname (optional):")]
-    nickname: Option<String>,
+    let survey = ConnectForm::survey();
+
+    match survey.questions[0].kind() {
+        QuestionKind::Autocomplete(autocomplete_q) => {
+            assert_eq!(autocomplete_q.completions("em"), vec!["emacs".to_string()]);
+        }
+        _ => panic!("Expected Autocomplete question kind"),
+    }
+    match survey.questions[1].kind() {
+        QuestionKind::Autocomplete(autocomplete_q) => {
+            assert_eq!(
+                autocomplete_q.completions("db"),
+                vec!["db.local".to_string()]
+            );
+        }
+        _ => panic!("Expected Autocomplete question kind"),
+    }
 }
 
 #[test]
-fn nested_struct_suggest_builder() {
-    // Test that closure-based suggest methods work for nested structs
-    let _builder = OrderForm::builder()
-        .suggest_customer_name("John Doe")
-        .suggest_shipping_address(|addr| {
-            addr.street("123 Main St").city("Springfield").zip("12345")
-        });
+fn autocomplete_accepts_free_text() {
+    let form: ConnectForm = ConnectForm::builder()
+        .run(
+            TestBackend::new()
+                .with_string("editor", "kakoune")
+                .with_string("host", "db.local"),
+        )
+        .unwrap();
+
+    assert_eq!(form.editor, "kakoune");
+    assert_eq!(form.host, "db.local");
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Feedback {
+    #[ask("Overall rating:")]
+    #[rating(1..=5)]
+    overall: u8,
+
+    #[ask("The documentation was helpful.")]
+    #[likert("Strongly disagree".."Strongly agree", 7)]
+    docs: i32,
 }
 
 #[test]
-fn enum_suggest_builder_suggest_variant() {
-    // Test that suggest_* methods work for enums
-    let _builder = OrderForm::builder().suggest_payment(|p| p.suggest_cash());
+fn rating_survey_definition() {
+    use elicitor::QuestionKind;
 
-    let _builder2 = OrderForm::builder().suggest_payment(|p| p.suggest_credit_card());
+    let survey = Feedback::survey();
 
-    let _builder3 = OrderForm::builder().suggest_payment(|p| p.suggest_bank_transfer());
+    match survey.questions[0].kind() {
+        QuestionKind::Rating(rating_q) => {
+            assert_eq!(rating_q.points(), 1..=5);
+            assert!(rating_q.shows_stars());
+        }
+        _ => panic!("Expected Rating question kind"),
+    }
+    match survey.questions[1].kind() {
+        QuestionKind::Rating(rating_q) => {
+            assert_eq!(rating_q.points(), 1..=7);
+            assert_eq!(rating_q.label(7), "7 - Strongly agree");
+        }
+        _ => panic!("Expected Rating question kind"),
+    }
 }
 
 #[test]
-fn enum_suggest_builder_variant_fields() {
-    // Test that variant field methods work
-    let _builder = OrderForm::builder().suggest_payment(|p| {
-        p.suggest_credit_card()
-            .credit_card(|cc| cc.number("4111111111111111").cvv("123"))
-    });
+fn rating_with_test_backend() {
+    let feedback: Feedback = Feedback::builder()
+        .run(
+            TestBackend::new()
+                .with_int("overall", 4)
+                .with_int("docs", 6),
+        )
+        .unwrap();
+
+    assert_eq!(
+        feedback,
+        Feedback {
+            overall: 4,
+            docs: 6
+        }
+    );
+
+    let result = Feedback::builder().run(
+        TestBackend::new()
+            .with_int("overall", 6)
+            .with_int("docs", 6),
+    );
+    assert!(result.is_err());
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Budget {
+    #[ask("Monthly budget:")]
+    #[currency("USD")]
+    #[min(1_000)]
+    #[max(1_000_000)]
+    monthly: u32,
+
+    #[ask("Savings:")]
+    #[currency("EUR", "de")]
+    savings: i64,
 }
 
 #[test]
-fn enum_suggest_multiple_variants() {
-    // Test that we can suggest values for multiple variants
-    // (only the selected one will be used, but all can have suggestions)
-    let _builder = OrderForm::builder().suggest_payment(|p| {
-        p.suggest_credit_card()
-            .credit_card(|cc| cc.number("4111111111111111").cvv("123"))
-            .bank_transfer(|bt| bt.iban("DE89370400440532013000"))
-    });
+fn currency_survey_definition() {
+    use elicitor::QuestionKind;
+
+    let survey = Budget::survey();
+
+    match survey.questions[0].kind() {
+        QuestionKind::Currency(currency_q) => {
+            assert_eq!(currency_q.currency, "USD");
+            assert_eq!(currency_q.min, Some(1_000));
+            assert_eq!(currency_q.parse("1,250.50"), Ok(125_050));
+        }
+        _ => panic!("Expected Currency question kind"),
+    }
+    match survey.questions[1].kind() {
+        QuestionKind::Currency(currency_q) => {
+            assert_eq!(currency_q.format(123_456), "1.234,56 €");
+        }
+        _ => panic!("Expected Currency question kind"),
+    }
 }
 
 #[test]
-fn option_suggest_some() {
-    // Test that Option fields can be suggested with some()
-    let _builder = OrderForm::builder().suggest_nickname(|opt| opt.some("Johnny"));
+fn currency_with_test_backend() {
+    let budget: Budget = Budget::builder()
+        .run(
+            TestBackend::new()
+                .with_int("monthly", 250_000)
+                .with_int("savings", 123_456),
+        )
+        .unwrap();
+
+    assert_eq!(
+        budget,
+        Budget {
+            monthly: 250_000,
+            savings: 123_456
+        }
+    );
+
+    let result = Budget::builder().run(
+        TestBackend::new()
+            .with_int("monthly", 500)
+            .with_int("savings", 0),
+    );
+    assert!(result.is_err());
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Palette {
+    #[ask("Accent color:")]
+    accent: elicitor::Color,
+
+    #[ask("Highlight color:")]
+    highlight: Option<elicitor::Color>,
 }
 
 #[test]
-fn option_suggest_none() {
-    // Test that Option fields can be suggested with none()
-    let _builder = OrderForm::builder().suggest_nickname(|opt| opt.none());
+fn color_with_test_backend() {
+    use elicitor::Color;
+
+    let palette: Palette = Palette::builder()
+        .run(
+            TestBackend::new()
+                .with_string("accent", "#1E90FF")
+                .with_color("highlight", Color::rgb(255, 170, 0)),
+        )
+        .unwrap();
+
+    assert_eq!(
+        palette,
+        Palette {
+            accent: Color::rgb(0x1e, 0x90, 0xff),
+            highlight: Some(Color::rgb(255, 170, 0)),
+        }
+    );
+
+    let result = Palette::builder().run(TestBackend::new().with_string("accent", "blue"));
+    assert!(result.is_err());
 }
 
 #[test]
-fn assume_nested_struct() {
-    // Test that assume works the same as suggest for nested structs
-    let _builder = OrderForm::builder().assume_shipping_address(|addr| {
-        addr.street("456 Oak Ave").city("Shelbyville").zip("67890")
-    });
+fn color_suggest_builder() {
+    use elicitor::Color;
+
+    // Colors are suggested as values, like other primitives
+    let _builder = Palette::builder()
+        .suggest_accent(Color::rgb(0x1e, 0x90, 0xff))
+        .suggest_highlight(|opt| opt.some(Color::rgb(255, 255, 255)));
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Upload {
+    #[ask("Manifest:")]
+    #[file(extensions = "toml")]
+    manifest: std::path::PathBuf,
+
+    #[ask("Sources:")]
+    #[directory]
+    sources: std::path::PathBuf,
+
+    #[ask("Output:")]
+    output: std::path::PathBuf,
 }
 
 #[test]
-fn assume_enum_with_fields() {
-    // Test that assume works for enums with variant fields
-    let _builder = OrderForm::builder().assume_payment(|p| {
-        p.suggest_bank_transfer()
-            .bank_transfer(|bt| bt.iban("DE89370400440532013000"))
-    });
+fn paths_with_test_backend() {
+    let root = env!("CARGO_MANIFEST_DIR");
+    let manifest = format!("{root}/Cargo.toml");
+    let sources = format!("{root}/src");
+
+    let upload = Upload::builder()
+        .run(
+            TestBackend::new()
+                .with_string("manifest", manifest.as_str())
+                .with_string("sources", sources.as_str())
+                .with_string("output", "does/not/exist.txt"),
+        )
+        .unwrap();
+    assert_eq!(upload.manifest, std::path::PathBuf::from(&manifest));
+    assert_eq!(
+        upload.output,
+        std::path::PathBuf::from("does/not/exist.txt")
+    );
+
+    // A directory where a file is expected
+    let result = Upload::builder().run(
+        TestBackend::new()
+            .with_string("manifest", sources.as_str())
+            .with_string("sources", sources.as_str())
+            .with_string("output", ""),
+    );
+    assert!(result.is_err());
+
+    // A file with the wrong extension
+    let result = Upload::builder().run(
+        TestBackend::new()
+            .with_string("manifest", format!("{root}/README.md"))
+            .with_string("sources", sources.as_str())
+            .with_string("output", ""),
+    );
+    assert!(result.is_err());
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Review {
+    #[ask("Score:")]
+    #[rating(1..=5)]
+    #[allow_comment]
+    score: u8,
+
+    #[ask("Would you come back?")]
+    again: bool,
 }
 
 #[test]
-fn combined_suggest_and_assume() {
+fn comment_survey_definition() {
+    let def = Review::survey();
+    assert!(def.questions[0].allows_comment());
+    assert!(!def.questions[1].allows_comment());
+}
+
+#[test]
+fn comments_with_test_backend() {
+    use elicitor::{COMMENT_KEY, ResponsePath};
+
+    let (review, responses) = Review::builder()
+        .run_with_responses(
+            TestBackend::new()
+                .with_int("score", 4)
+                .with_comment("score", "Loved the food")
+                .with_comment("again", "Ignored: no comments allowed here")
+                .with_bool("again", true),
+        )
+        .unwrap();
+
+    assert_eq!(
+        review,
+        Review {
+            score: 4,
+            again: true
+        }
+    );
+    assert_eq!(
+        responses.comment(&ResponsePath::new("score")),
+        Some("Loved the food")
+    );
+    assert_eq!(responses.comment(&ResponsePath::new("again")), None);
+    assert!(responses.contains(&ResponsePath::new("score").child(COMMENT_KEY)));
+    assert_eq!(responses.comments().count(), 1);
+}
+
+#[cfg(feature = "secrecy")]
+#[derive(Survey, Debug)]
+struct Credentials {
+    #[ask("User:")]
+    user: String,
+
+    #[ask("Token:")]
+    #[mask]
+    token: elicitor::SecretString,
+}
+
+#[cfg(feature = "secrecy")]
+#[test]
+fn secrets_are_sealed() {
+    use elicitor::ResponsePath;
+    use elicitor::secrecy::ExposeSecret;
+
+    let (credentials, responses) = Credentials::builder()
+        .run_with_responses(
+            TestBackend::new()
+                .with_string("user", "alice")
+                .with_string("token", "hunter2"),
+        )
+        .unwrap();
+
+    assert_eq!(credentials.token.expose_secret(), "hunter2");
+    assert!(!format!("{credentials:?}").contains("hunter2"));
+
+    let token = ResponsePath::new("token");
+    assert!(responses.get(&token).is_none());
+    assert_eq!(
+        responses.get_secret(&token).unwrap().expose_secret(),
+        "hunter2"
+    );
+    assert!(!format!("{responses:?}").contains("hunter2"));
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Decision {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Application {
+    #[ask("Your name:")]
+    #[editable_by("applicant")]
+    name: String,
+
+    #[ask("Decision:")]
+    #[editable_by("reviewer")]
+    decision: Decision,
+}
+
+#[test]
+fn roles_lock_fields_of_other_roles() {
+    use elicitor::PermissionError;
+
+    let application = Application::builder()
+        .suggest_decision(|d| d.suggest_pending())
+        .with_role("applicant")
+        .run(
+            TestBackend::new()
+                .with_string("name", "Alice")
+                .with_variant("decision.selected_variant", 0),
+        )
+        .unwrap();
+    assert_eq!(application.decision, Decision::Pending);
+
+    // The reviewer decides, the applicant's name is kept as given
+    let reviewed = Application::builder()
+        .with_suggestions(&application)
+        .with_role("reviewer")
+        .run(TestBackend::new().with_variant("decision.selected_variant", 1))
+        .unwrap();
+    assert_eq!(
+        reviewed,
+        Application {
+            name: "Alice".to_string(),
+            decision: Decision::Accepted,
+        }
+    );
+
+    // The applicant may not pick a decision
+    let err = Application::builder()
+        .suggest_decision(|d| d.suggest_pending())
+        .with_role("applicant")
+        .run(
+            TestBackend::new()
+                .with_string("name", "Alice")
+                .with_variant("decision.selected_variant", 1),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<PermissionError>(),
+        Some(PermissionError::Changed { .. })
+    ));
+}
+
+#[test]
+fn submission_round_trips_through_responses() {
+    use elicitor::{ResponsePath, Submission, SubmissionState};
+
+    let (config, responses) = SimpleConfig::builder()
+        .run_with_responses(
+            TestBackend::new()
+                .with_string("name", "Alice")
+                .with_int("age", 30)
+                .with_bool("developer", true),
+        )
+        .unwrap();
+
+    let mut submission = Submission::draft(config, responses);
+    submission.submit().unwrap();
+    submission.add_review_comment("age", "Please double-check");
+    submission.reject().unwrap();
+
+    let loaded = Submission::<SimpleConfig>::load(submission.to_responses()).unwrap();
+    assert_eq!(loaded.state(), SubmissionState::Rejected);
+    assert_eq!(loaded.value().name, "Alice");
+    assert_eq!(
+        loaded.review_comment(&ResponsePath::new("age")),
+        Some("Please double-check")
+    );
+    assert!(
+        loaded
+            .responses()
+            .metadata_iter()
+            .all(|(key, _)| !key.starts_with("workflow"))
+    );
+
+    // Plain responses were never part of a workflow
+    let plain = submission.responses().clone();
+    assert!(Submission::<SimpleConfig>::load(plain).is_err());
+}
+
+#[test]
+fn answer_origins_explain_skipped_questions() {
+    use elicitor::{AnswerOrigin, ResponsePath, Submission};
+
+    let (config, responses) = SimpleConfig::builder()
+        .assume_name("Alice")
+        .assume_developer(true)
+        .with_origin("developer", AnswerOrigin::Environment("IS_DEV".into()))
+        .run_with_responses(TestBackend::new().with_int("age", 30))
+        .unwrap();
+    assert_eq!(config.name, "Alice");
+
+    // The origins survive the review workflow
+    let submission = Submission::draft(config, responses);
+    let loaded = Submission::<SimpleConfig>::load(submission.to_responses()).unwrap();
+    let origin = |path| loaded.responses().origin(&ResponsePath::new(path));
+    assert_eq!(origin("name"), Some(AnswerOrigin::Assumed));
+    assert_eq!(
+        origin("developer")
+            .map(|origin| origin.to_string())
+            .as_deref(),
+        Some("from environment (IS_DEV)")
+    );
+    assert_eq!(origin("age"), None);
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Onboarding {
+    #[ask("Your name:")]
+    name: String,
+
+    #[ask("Expected salary:")]
+    #[flag("collect_salary")]
+    salary: Option<i64>,
+}
+
+#[test]
+fn feature_flags_switch_questions_off() {
+    use elicitor::StaticFlags;
+
+    let backend = || {
+        TestBackend::new()
+            .with_string("name", "Alice")
+            .with_int("salary", 50_000)
+    };
+
+    let collected = Onboarding::builder()
+        .with_flags(StaticFlags::new().with_flag("collect_salary", true))
+        .run(backend())
+        .unwrap();
+    assert_eq!(collected.salary, Some(50_000));
+
+    let (skipped, responses) = Onboarding::builder()
+        .with_flags(|_: &str| false)
+        .run_with_responses(backend())
+        .unwrap();
+    assert_eq!(skipped.salary, None);
+    assert!(!responses.contains(&elicitor::ResponsePath::new("salary")));
+
+    // Without a provider every question is asked
+    let unflagged = Onboarding::builder().run(backend()).unwrap();
+    assert_eq!(unflagged.salary, Some(50_000));
+}
+
+#[derive(Survey, Debug)]
+struct Shipping {
+    #[ask("Street:")]
+    #[help("Only used to deliver the parcel")]
+    street: String,
+
+    #[ask("Floor:")]
+    floor: i64,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Topping {
+    Cheese,
+    Olives,
+    Basil,
+}
+
+#[derive(Survey, Debug)]
+struct PizzaOrder {
+    #[ask("Toppings:")]
+    #[multiselect]
+    #[max(2)]
+    toppings: Vec<Topping>,
+}
+
+#[derive(Survey, Debug)]
+struct Delivery {
+    #[ask("Topping to skip:")]
+    #[dropdown]
+    skipped: Topping,
+
+    #[ask("Extra topping:")]
+    extra: Topping,
+}
+
+#[test]
+fn dropdown_attribute_marks_the_question() {
+    let survey = Delivery::survey();
+    assert!(survey.questions()[0].is_dropdown());
+    assert!(!survey.questions()[1].is_dropdown());
+}
+
+#[test]
+fn max_on_multiselect_limits_the_selections() {
+    let survey = PizzaOrder::survey();
+    let elicitor::QuestionKind::AnyOf(any_of) = survey.questions()[0].kind() else {
+        panic!("expected a multi-select question");
+    };
+    assert_eq!(any_of.max_selected, Some(2));
+}
+
+/// Answers every multi-select with the items it preselects.
+struct AcceptPreselection;
+
+impl elicitor::SurveyBackend for AcceptPreselection {
+    type Error = std::convert::Infallible;
+
+    fn collect(
+        &self,
+        definition: &elicitor::SurveyDefinition,
+        _validate: &dyn Fn(
+            &elicitor::ResponseValue,
+            &elicitor::Responses,
+            &elicitor::ResponsePath,
+        ) -> Result<(), String>,
+    ) -> Result<elicitor::Responses, Self::Error> {
+        let mut responses = elicitor::Responses::new();
+        for question in definition.questions() {
+            if let elicitor::QuestionKind::AnyOf(any_of) = question.kind() {
+                let path = question.path();
+                for (item, &variant) in any_of.defaults.iter().enumerate() {
+                    responses.insert(
+                        path.child(&item.to_string())
+                            .child(elicitor::SELECTED_VARIANT_KEY),
+                        elicitor::ResponseValue::ChosenVariant(variant),
+                    );
+                }
+                responses.insert(
+                    path.child(elicitor::SELECTED_VARIANTS_KEY),
+                    elicitor::ResponseValue::ChosenVariants(any_of.defaults.clone()),
+                );
+            }
+        }
+        Ok(responses)
+    }
+}
+
+#[test]
+fn suggested_multiselect_items_are_preselected() {
+    let previous = PizzaOrder {
+        toppings: vec![Topping::Basil, Topping::Cheese],
+    };
+    let order = PizzaOrder::builder()
+        .with_suggestions(&previous)
+        .run(AcceptPreselection)
+        .unwrap();
+    assert_eq!(order.toppings, previous.toppings);
+}
+
+#[test]
+fn help_attribute_is_carried_to_the_question() {
+    let survey = Shipping::survey();
+    assert_eq!(
+        survey.questions()[0].help(),
+        Some("Only used to deliver the parcel")
+    );
+    assert_eq!(survey.questions()[1].help(), None);
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Contact {
+    #[ask("Email:")]
+    email: String,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Member {
+    #[ask("Name:")]
+    name: String,
+
+    #[ask("Contact details:")]
+    contact: Contact,
+
+    #[replaced_by("contact.email")]
+    email: String,
+}
+
+#[test]
+fn replaced_fields_read_legacy_answers() {
+    let definition = Member::survey();
+    let paths: Vec<_> = definition
+        .questions()
+        .iter()
+        .map(|q| q.path().as_str())
+        .collect();
+    assert_eq!(paths, ["name", "contact"]);
+
+    let member = Member::builder()
+        .run(
+            TestBackend::new()
+                .with_string("name", "Alice")
+                .with_string("contact.email", "alice@example.com"),
+        )
+        .unwrap();
+    assert_eq!(member.email, "alice@example.com");
+
+    // An answer file saved before the email moved into the contact details
+    let mut saved = elicitor::Responses::new();
+    saved.insert("name", "Bob");
+    saved.insert("email", "bob@example.com");
+    let member = Member::from_responses(&saved);
+    assert_eq!(member.contact.email, "bob@example.com");
+    assert_eq!(member.email, "bob@example.com");
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Smoking {
+    Never,
+    Occasionally,
+    Daily,
+}
+
+#[derive(Debug, PartialEq)]
+enum Risk {
+    Low,
+    High,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+#[score(assess_risk -> Risk)]
+struct Declarations {
+    #[ask("Do you smoke?")]
+    #[score(0, 20, 50)]
+    smoking: Smoking,
+
+    #[ask("Any surgery in the last five years?")]
+    #[score(15)]
+    surgery: bool,
+
+    #[ask("Name:")]
+    name: String,
+}
+
+fn assess_risk(_: &Declarations, points: &elicitor::Points) -> Risk {
+    if points.total() >= 50 {
+        Risk::High
+    } else {
+        Risk::Low
+    }
+}
+
+#[test]
+fn scoring_with_test_backend() {
+    let (declarations, risk) = Declarations::builder()
+        .run_scored(
+            TestBackend::new()
+                .with_variant("smoking.selected_variant", 2)
+                .with_bool("surgery", false)
+                .with_string("name", "Alex"),
+        )
+        .unwrap();
+
+    assert_eq!(declarations.smoking, Smoking::Daily);
+    assert_eq!(risk, Risk::High);
+
+    let (_, risk) = Declarations::builder()
+        .run_scored(
+            TestBackend::new()
+                .with_variant("smoking.selected_variant", 0)
+                .with_bool("surgery", true)
+                .with_string("name", "Sam"),
+        )
+        .unwrap();
+    assert_eq!(risk, Risk::Low);
+}
+
+#[test]
+fn scoring_points_per_question() {
+    use elicitor::{Points, ResponsePath};
+
+    let (_, responses) = Declarations::builder()
+        .run_with_responses(
+            TestBackend::new()
+                .with_variant("smoking.selected_variant", 2)
+                .with_bool("surgery", true)
+                .with_string("name", "Alex"),
+        )
+        .unwrap();
+
+    let points = Points::tally(&Declarations::survey(), &responses);
+    assert_eq!(points.get(&ResponsePath::new("smoking")), Some(50));
+    assert_eq!(points.get(&ResponsePath::new("surgery")), Some(15));
+    assert_eq!(points.get(&ResponsePath::new("name")), None);
+    assert_eq!(points.total(), 65);
+}
+
+#[derive(Survey, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Aspect {
+    Content,
+    Pace,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Agreement {
+    Disagree,
+    Neutral,
+    Agree,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Evaluation {
+    #[ask("How do you feel about each aspect?")]
+    aspects: std::collections::BTreeMap<Aspect, Agreement>,
+}
+
+#[test]
+fn matrix_survey_definition() {
+    use elicitor::QuestionKind;
+
+    let survey = Evaluation::survey();
+
+    match survey.questions[0].kind() {
+        QuestionKind::Matrix(matrix_q) => {
+            assert_eq!(matrix_q.rows, vec!["Content", "Pace"]);
+            assert_eq!(matrix_q.columns, vec!["Disagree", "Neutral", "Agree"]);
+            assert!(matrix_q.is_complete(&[0, 2]));
+            assert!(!matrix_q.is_complete(&[0]));
+            assert!(!matrix_q.is_complete(&[0, 3]));
+        }
+        _ => panic!("Expected Matrix question kind"),
+    }
+}
+
+#[test]
+fn matrix_with_test_backend() {
+    let evaluation: Evaluation = Evaluation::builder()
+        .run(TestBackend::new().with_variants("aspects", vec![2, 0]))
+        .unwrap();
+
+    assert_eq!(
+        evaluation.aspects,
+        [
+            (Aspect::Content, Agreement::Agree),
+            (Aspect::Pace, Agreement::Disagree)
+        ]
+        .into_iter()
+        .collect()
+    );
+
+    let result = Evaluation::builder().run(TestBackend::new().with_variants("aspects", vec![2]));
+    assert!(result.is_err());
+}
+
+fn validate_pin(
+    value: &elicitor::ResponseValue,
+    _responses: &elicitor::Responses,
+    _path: &elicitor::ResponsePath,
+) -> Result<(), String> {
+    match value.as_str() {
+        Some(pin) if pin.len() == 4 && pin.chars().all(|c| c.is_ascii_digit()) => Ok(()),
+        _ => Err("PIN must be 4 digits".to_string()),
+    }
+}
+
+#[derive(Survey, Debug)]
+struct Unlock {
+    #[use_question("test.pin")]
+    pin: String,
+}
+
+#[test]
+fn use_question_from_question_bank() {
+    use elicitor::question_bank::{self, BankQuestion};
+    use elicitor::{MaskedQuestion, QuestionKind};
+
+    question_bank::register(
+        BankQuestion::new("test.pin", "PIN:")
+            .with_kind(QuestionKind::Masked(MaskedQuestion::new()))
+            .with_validator(validate_pin),
+    );
+
+    let survey = Unlock::survey();
+    assert_eq!(survey.questions[0].ask(), "PIN:");
+    assert!(matches!(
+        survey.questions[0].kind(),
+        QuestionKind::Masked(_)
+    ));
+
+    let unlock = Unlock::builder()
+        .run(TestBackend::new().with_string("pin", "1234"))
+        .unwrap();
+    assert_eq!(unlock.pin, "1234");
+
+    let result = Unlock::builder().run(TestBackend::new().with_string("pin", "12"));
+    assert!(result.is_err());
+}
+
+#[derive(Survey, Debug)]
+struct Mailing {
+    #[use_question("test.never_registered")]
+    email: String,
+}
+
+#[derive(Survey, Debug)]
+struct Door {
+    #[use_question("test.door_code")]
+    code: String,
+}
+
+#[test]
+fn unusable_bank_questions_fail_the_run() {
+    use elicitor::question_bank::{self, BankError, BankQuestion};
+    use elicitor::{IntQuestion, QuestionKind, SurveyError};
+
+    // Building the definition does not panic, the prompt falls back to the id
+    assert_eq!(
+        Mailing::survey().questions[0].ask(),
+        "test.never_registered"
+    );
+
+    let err = Mailing::builder()
+        .run(TestBackend::new().with_string("email", "a@b.c"))
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<SurveyError>(),
+        Some(SurveyError::QuestionBank(BankError::Unregistered(id))) if id == "test.never_registered"
+    ));
+
+    question_bank::register(
+        BankQuestion::new("test.door_code", "Code:")
+            .with_kind(QuestionKind::Int(IntQuestion::new())),
+    );
+    assert!(matches!(
+        question_bank::checked(Door::survey),
+        Err(SurveyError::QuestionBank(BankError::KindMismatch {
+            banked: "Int",
+            derived: "String",
+            ..
+        }))
+    ));
+}
+
+#[test]
+fn prompt_experiment_records_shown_variant() {
+    use elicitor::{PromptExperiment, ResponsePath, VariantStrategy};
+
+    let experiment = PromptExperiment::new(VariantStrategy::Bucket(42))
+        .with_variants("name", ["What should we call you?"]);
+
+    let (form, responses) = experiment
+        .run::<SimpleConfig, _>(
+            TestBackend::new()
+                .with_string("name", "Alice")
+                .with_int("age", 30)
+                .with_bool("developer", true),
+        )
+        .unwrap();
+
+    assert_eq!(form.name, "Alice");
+    let shown = PromptExperiment::shown_variant(&responses, &ResponsePath::new("name"));
+    assert_eq!(
+        shown,
+        Some(experiment.variant_for(&ResponsePath::new("name"), 2))
+    );
+    assert_eq!(
+        PromptExperiment::shown_variant(&responses, &ResponsePath::new("age")),
+        None
+    );
+}
+
+// ============================================================================
+// Nested Builder Tests
+// ============================================================================
+
+#[derive(Survey, Debug, PartialEq)]
+struct Address {
+    #[ask("Street address:")]
+    street: String,
+
+    #[ask("City:")]
+    city: String,
+
+    #[ask("Zip code:")]
+    zip: String,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum PaymentMethod {
+    #[ask("Cash payment")]
+    Cash,
+
+    #[ask("Credit card")]
+    CreditCard {
+        #[ask("Card number:")]
+        number: String,
+        #[ask("CVV:")]
+        cvv: String,
+    },
+
+    #[ask("Bank transfer")]
+    BankTransfer {
+        #[ask("IBAN:")]
+        iban: String,
+    },
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct OrderForm {
+    #[ask("Customer name:")]
+    customer_name: String,
+
+    #[ask("Shipping address:")]
+    shipping_address: Address,
+
+    #[ask("Payment method:")]
+    payment: PaymentMethod,
+
+    #[ask("Nickname (optional):")]
+    nickname: Option<String>,
+}
+
+#[test]
+fn nested_struct_suggest_builder() {
+    // Test that closure-based suggest methods work for nested structs
+    let _builder = OrderForm::builder()
+        .suggest_customer_name("John Doe")
+        .suggest_shipping_address(|addr| {
+            addr.street("123 Main St").city("Springfield").zip("12345")
+        });
+}
+
+#[test]
+fn enum_suggest_builder_suggest_variant() {
+    // Test that suggest_* methods work for enums
+    let _builder = OrderForm::builder().suggest_payment(|p| p.suggest_cash());
+
+    let _builder2 = OrderForm::builder().suggest_payment(|p| p.suggest_credit_card());
+
+    let _builder3 = OrderForm::builder().suggest_payment(|p| p.suggest_bank_transfer());
+}
+
+#[test]
+fn enum_suggest_builder_variant_fields() {
+    // Test that variant field methods work
+    let _builder = OrderForm::builder().suggest_payment(|p| {
+        p.suggest_credit_card()
+            .credit_card(|cc| cc.number("4111111111111111").cvv("123"))
+    });
+}
+
+#[test]
+fn enum_suggest_multiple_variants() {
+    // Test that we can suggest values for multiple variants
+    // (only the selected one will be used, but all can have suggestions)
+    let _builder = OrderForm::builder().suggest_payment(|p| {
+        p.suggest_credit_card()
+            .credit_card(|cc| cc.number("4111111111111111").cvv("123"))
+            .bank_transfer(|bt| bt.iban("DE89370400440532013000"))
+    });
+}
+
+#[test]
+fn option_suggest_some() {
+    // Test that Option fields can be suggested with some()
+    let _builder = OrderForm::builder().suggest_nickname(|opt| opt.some("Johnny"));
+}
+
+#[test]
+fn option_suggest_none() {
+    // Test that Option fields can be suggested with none()
+    let _builder = OrderForm::builder().suggest_nickname(|opt| opt.none());
+}
+
+#[test]
+fn assume_nested_struct() {
+    // Test that assume works the same as suggest for nested structs
+    let _builder = OrderForm::builder().assume_shipping_address(|addr| {
+        addr.street("456 Oak Ave").city("Shelbyville").zip("67890")
+    });
+}
+
+#[test]
+fn assume_enum_with_fields() {
+    // Test that assume works for enums with variant fields
+    let _builder = OrderForm::builder().assume_payment(|p| {
+        p.suggest_bank_transfer()
+            .bank_transfer(|bt| bt.iban("DE89370400440532013000"))
+    });
+}
+
+#[test]
+fn assumed_enum_variant_and_fields_are_not_asked() {
+    let builder = || {
+        OrderForm::builder()
+            .assume_customer_name("Ada")
+            .assume_shipping_address(|addr| {
+                addr.street("1 Main St").city("Springfield").zip("12345")
+            })
+            .assume_payment(|p| {
+                p.suggest_bank_transfer()
+                    .bank_transfer(|bt| bt.iban("DE89370400440532013000"))
+            })
+            .assume_nickname(|opt| opt.none())
+    };
+    let expected = OrderForm {
+        customer_name: "Ada".to_string(),
+        shipping_address: Address {
+            street: "1 Main St".to_string(),
+            city: "Springfield".to_string(),
+            zip: "12345".to_string(),
+        },
+        payment: PaymentMethod::BankTransfer {
+            iban: "DE89370400440532013000".to_string(),
+        },
+        nickname: None,
+    };
+
+    assert_eq!(builder().run(TestBackend::new()).unwrap(), expected);
+
+    // Without any input, a single question asked would cancel the survey
+    let plain = elicitor::PlainBackend::from_reader(std::io::empty()).with_output(std::io::sink());
+    assert_eq!(builder().run(plain).unwrap(), expected);
+}
+
+#[test]
+fn plan_lists_asked_and_skipped_questions() {
+    let plan = OrderForm::builder()
+        .suggest_customer_name("Ada")
+        .assume_payment(|p| p.suggest_cash())
+        .assume_nickname(|opt| opt.none())
+        .plan();
+
+    assert_eq!(
+        plan.asked()
+            .into_iter()
+            .map(|path| path.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "customer_name",
+            "shipping_address.street",
+            "shipping_address.city",
+            "shipping_address.zip"
+        ]
+    );
+    assert_eq!(
+        plan.skipped()
+            .into_iter()
+            .map(|(path, reason)| format!("{path}: {reason}"))
+            .collect::<Vec<_>>(),
+        vec![
+            "payment.selected_variant: assumed variant 0",
+            "payment: not chosen",
+            "payment: not chosen",
+            "nickname: assumed none",
+        ]
+    );
+    assert!(
+        plan.to_string()
+            .starts_with("Customer name: (customer_name) [suggested: \"Ada\"]\n")
+    );
+}
+
+#[test]
+fn combined_suggest_and_assume() {
     // Test combining suggest and assume in one builder
     let _builder = OrderForm::builder()
         .suggest_customer_name("John Doe")
@@ -253,3 +1504,958 @@ fn combined_suggest_and_assume() {
         .suggest_payment(|p| p.suggest_cash())
         .assume_nickname(|opt| opt.none());
 }
+
+#[test]
+fn diverged_responses_are_merged() {
+    use elicitor::{MergeStrategy, ResponsePath};
+
+    let run = |name: &str, age: i64| {
+        SimpleConfig::builder()
+            .run_with_responses(
+                TestBackend::new()
+                    .with_string("name", name)
+                    .with_int("age", age)
+                    .with_bool("developer", true),
+            )
+            .unwrap()
+            .1
+    };
+    let mut laptop = run("Alice", 30);
+    let phone = run("Alicia", 31);
+
+    let definition = SimpleConfig::survey();
+    let backend = TestBackend::new()
+        .with_variant("conflict_0.selected_variant", 0)
+        .with_variant("conflict_1.selected_variant", 1);
+    let conflicts = laptop
+        .merge_resolving(phone, MergeStrategy::interactive(&definition, &backend))
+        .unwrap();
+
+    assert_eq!(
+        conflicts,
+        vec![ResponsePath::new("name"), ResponsePath::new("age")]
+    );
+    let merged = SimpleConfig::from_responses(&laptop);
+    assert_eq!(merged.age, 31);
+    assert_eq!(merged.name, "Alice");
+    assert!(laptop.updated_at().is_some());
+}
+
+#[test]
+fn observers_see_question_progress() {
+    use elicitor::{ResponsePath, ResponseValue, SurveyObserver};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl SurveyObserver for Recorder {
+        fn on_question_start(&self, path: &ResponsePath) {
+            self.0.lock().unwrap().push(format!("start {path}"));
+        }
+
+        fn on_answer(&self, path: &ResponsePath, _value: &ResponseValue) {
+            self.0.lock().unwrap().push(format!("answer {path}"));
+        }
+    }
+
+    let recorder = Recorder::default();
+    SimpleConfig::builder()
+        .assume_developer(true)
+        .with_observer(recorder.clone())
+        .run(
+            TestBackend::new()
+                .with_string("name", "Alice")
+                .with_int("age", 30),
+        )
+        .unwrap();
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec!["start name", "answer name", "start age", "answer age"]
+    );
+}
+
+#[test]
+fn dynamic_survey_collects_responses() {
+    use elicitor::{DynamicSurvey, ResponsePath, ResponseValue, SELECTED_VARIANT_KEY, Variant};
+
+    let survey = DynamicSurvey::builder()
+        .input("name", "Your name?")
+        .int("age", "Your age?")
+        .validate("age", |value, _| match value {
+            ResponseValue::Int(age) if *age < 18 => Err("Must be an adult".to_string()),
+            _ => Ok(()),
+        })
+        .select("plan", "Plan?", ["free", "pro"])
+        .one_of(
+            "contact",
+            "Contact by?",
+            ["Email", "Phone"].map(Variant::unit),
+        )
+        .group("address", |group| group.input("city", "City?"))
+        .build();
+
+    let backend = TestBackend::new()
+        .with_string("name", "Ada")
+        .with_int("age", 36)
+        .with_string("plan", "pro")
+        .with_variant("contact.selected_variant", 1)
+        .with_string("address.city", "London");
+    let responses = survey.run(backend).unwrap();
+
+    assert_eq!(
+        responses.get_string(&ResponsePath::new("name")).unwrap(),
+        "Ada"
+    );
+    assert_eq!(
+        responses.get_string(&ResponsePath::new("plan")).unwrap(),
+        "pro"
+    );
+    assert_eq!(
+        responses
+            .get_chosen_variant(&ResponsePath::new("contact").child(SELECTED_VARIANT_KEY))
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        responses
+            .get_string(&ResponsePath::new("address.city"))
+            .unwrap(),
+        "London"
+    );
+
+    let underage = TestBackend::new()
+        .with_string("name", "Tim")
+        .with_int("age", 12);
+    assert!(survey.run(underage).is_err());
+}
+
+#[test]
+fn backends_are_picked_by_name() {
+    elicitor::register_backend("canned", || {
+        elicitor::boxed_backend(
+            TestBackend::new()
+                .with_string("name", "Ada")
+                .with_int("age", 36)
+                .with_bool("developer", true),
+        )
+    });
+
+    let config = SimpleConfig::builder().run_named("canned").unwrap();
+    assert_eq!(config.name, "Ada");
+
+    let err = SimpleConfig::builder().run_named("missing").unwrap_err();
+    assert!(err.to_string().contains("canned"));
+}
+
+#[test]
+fn responses_follow_question_order() {
+    let (_, responses) = SimpleConfig::builder()
+        .run_with_responses(
+            TestBackend::new()
+                .with_bool("developer", true)
+                .with_int("age", 30)
+                .with_string("name", "Alice"),
+        )
+        .unwrap();
+
+    let paths: Vec<_> = responses.paths().map(|path| path.as_str()).collect();
+    assert_eq!(paths, ["name", "age", "developer"]);
+
+    let debug = format!("{responses:?}");
+    let position = |needle: &str| debug.find(needle).unwrap();
+    assert!(position("\"name\"") < position("\"age\""));
+    assert!(position("\"age\"") < position("\"developer\""));
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Signup {
+    #[ask("Name:")]
+    #[tags("pii")]
+    name: String,
+
+    #[ask("Address:")]
+    #[tags("pii", "shipping")]
+    address: Address,
+
+    #[ask("How did you hear about us?")]
+    channel: String,
+}
+
+#[test]
+fn tagged_answers_can_be_stripped() {
+    use elicitor::ResponsePath;
+
+    let definition = Signup::survey();
+    assert_eq!(
+        definition.tagged_paths("pii"),
+        vec![ResponsePath::new("name"), ResponsePath::new("address")]
+    );
+
+    let (_, responses) = Signup::builder()
+        .run_with_responses(
+            TestBackend::new()
+                .with_string("name", "Ada")
+                .with_string("address.street", "1 Main St")
+                .with_string("address.city", "London")
+                .with_string("address.zip", "N1")
+                .with_string("channel", "Podcast"),
+        )
+        .unwrap();
+
+    let analytics = responses.strip_tagged(&definition, "pii");
+    let paths: Vec<_> = analytics.paths().map(|path| path.as_str()).collect();
+    assert_eq!(paths, ["channel"]);
+    assert!(analytics.updated_at().is_some());
+
+    let shipping = responses.filter_tagged(&definition, "shipping");
+    assert_eq!(shipping.all_under("address").count(), 3);
+    assert_eq!(shipping.len(), 3);
+}
+
+#[test]
+fn render_plan_drives_a_custom_frontend() {
+    use elicitor::{ResponseValue, Responses, next_step, record_answer};
+
+    let definition = OrderForm::survey();
+    let mut responses = Responses::new();
+    let mut asked = Vec::new();
+
+    while let Some(step) = next_step(&definition, &responses) {
+        let value = match step.path.as_str() {
+            "payment.selected_variant" => ResponseValue::ChosenVariant(2),
+            "payment.iban" => ResponseValue::String("DE00 1234".into()),
+            path => ResponseValue::String(format!("<{path}>")),
+        };
+        OrderForm::validate_field(&value, &responses, &step.path).unwrap();
+        asked.push(step.path.as_str().to_string());
+        record_answer(&mut responses, &step, value);
+    }
+
+    assert_eq!(
+        asked,
+        [
+            "customer_name",
+            "shipping_address.street",
+            "shipping_address.city",
+            "shipping_address.zip",
+            "payment.selected_variant",
+            "payment.iban",
+            "nickname",
+        ]
+    );
+    let order = OrderForm::from_responses(&responses);
+    assert_eq!(
+        order.payment,
+        PaymentMethod::BankTransfer {
+            iban: "DE00 1234".into()
+        }
+    );
+    assert_eq!(order.shipping_address.city, "<shipping_address.city>");
+}
+
+fn needs_at_sign(
+    value: &elicitor::ResponseValue,
+    _responses: &elicitor::Responses,
+    _path: &elicitor::ResponsePath,
+) -> Result<(), String> {
+    match value.as_str() {
+        Some(email) if email.contains('@') => Ok(()),
+        _ => Err("Invalid email address".into()),
+    }
+}
+
+#[derive(Survey, Debug)]
+struct Newsletter {
+    #[ask("Email:")]
+    #[validate("needs_at_sign")]
+    email: String,
+
+    #[ask("Issues per month:")]
+    #[min(1)]
+    #[max(4)]
+    frequency: u8,
+}
+
+#[test]
+fn invalid_assumptions_fail_before_anything_is_asked() {
+    use elicitor::{InvalidAssumptions, ResponsePath};
+
+    let builder = Newsletter::builder()
+        .assume_email("nobody")
+        .assume_frequency(9);
+    let expected = InvalidAssumptions {
+        invalid: vec![
+            (
+                ResponsePath::new("email"),
+                "Invalid email address".to_string(),
+            ),
+            (
+                ResponsePath::new("frequency"),
+                "9 is out of bounds".to_string(),
+            ),
+        ],
+    };
+    assert_eq!(builder.check_assumptions(), Err(expected.clone()));
+
+    // The backend has no answers scripted, it is never asked
+    let err = builder
+        .run(TestBackend::new())
+        .unwrap_err()
+        .downcast::<InvalidAssumptions>()
+        .unwrap();
+    assert_eq!(err, expected);
+
+    let valid = Newsletter::builder()
+        .assume_email("ada@example.com")
+        .assume_frequency(2);
+    assert_eq!(valid.check_assumptions(), Ok(()));
+}
+
+#[test]
+fn invalid_assumptions_are_reported_in_the_translators_language() {
+    let german = |key: &str| (key == "error.too-young").then(|| "Zu jung.".to_string());
+    let builder = Greeting::builder().with_translator(german).assume_age(12);
+
+    let invalid = builder.check_assumptions().unwrap_err().invalid;
+    assert_eq!(invalid[0].1, "Zu jung.");
+
+    let err = builder
+        .run(TestBackend::new())
+        .unwrap_err()
+        .downcast::<elicitor::InvalidAssumptions>()
+        .unwrap();
+    assert_eq!(err.invalid[0].1, "Zu jung.");
+}
+
+#[test]
+fn auto_answer_backend_fills_every_question() {
+    use elicitor::{AnswerSource, AutoAnswerBackend, AutoAnswerError, ResponsePath};
+
+    let backend = AutoAnswerBackend::new();
+    let order = OrderForm::builder()
+        .suggest_customer_name("Ada")
+        .run(backend.clone())
+        .unwrap();
+    assert_eq!(order.customer_name, "Ada");
+    assert_eq!(order.payment, PaymentMethod::Cash);
+
+    let report = backend.report();
+    assert!(report.is_safe());
+    assert_eq!(report.answers()[0].source, AnswerSource::Suggested);
+    assert_eq!(report.placeholders().count(), 5);
+
+    let err = Newsletter::builder()
+        .run(backend.clone())
+        .unwrap_err()
+        .downcast::<AutoAnswerError>()
+        .unwrap();
+    assert_eq!(err.to_string(), "No safe answer for: email");
+    let report = backend.report();
+    let frequency = &report.answers()[1];
+    assert_eq!(frequency.path, ResponsePath::new("frequency"));
+    assert_eq!(frequency.value, elicitor::ResponseValue::Int(1));
+}
+
+#[test]
+fn random_answers_respect_constraints_and_weights() {
+    use elicitor::{Distribution, RandomAnswerBackend};
+
+    let backend = RandomAnswerBackend::new()
+        .with_seed(42)
+        .with_weights("email", [("ada@example.com", 3), ("not an email", 1)])
+        .with_distribution("frequency", Distribution::IntRange(0..=10));
+    for _ in 0..50 {
+        let newsletter = Newsletter::builder().run(backend.clone()).unwrap();
+        assert_eq!(newsletter.email, "ada@example.com");
+        assert!((1..=4).contains(&newsletter.frequency));
+    }
+
+    let backend = RandomAnswerBackend::new()
+        .with_seed(7)
+        .with_distribution("payment", Distribution::ChoiceWeights(vec![0, 1, 0]));
+    let order = OrderForm::builder().run(backend.clone()).unwrap();
+    assert!(matches!(order.payment, PaymentMethod::CreditCard { .. }));
+
+    let replay = RandomAnswerBackend::new()
+        .with_seed(7)
+        .with_distribution("payment", Distribution::ChoiceWeights(vec![0, 1, 0]));
+    assert_eq!(OrderForm::builder().run(replay).unwrap(), order);
+}
+
+#[test]
+fn spreadsheets_import_row_by_row() {
+    use elicitor::{CsvFieldError, ResponsePath, csv_header, from_csv_reader};
+
+    assert_eq!(csv_header(&Newsletter::survey()), "email,frequency\n");
+
+    let csv = "email,frequency\nada@example.com,2\n,\nbob.example.com,3\ncy@example.com,9\n";
+    let import = from_csv_reader::<Newsletter>(csv.as_bytes()).unwrap();
+    assert_eq!(import.records.len(), 1);
+    assert_eq!(import.records[0].email, "ada@example.com");
+
+    let errors: Vec<String> = import.errors.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        errors,
+        [
+            "Row 4: Invalid answer for email: Invalid email address",
+            "Row 5: Invalid answer for frequency: 9 is above 4",
+        ]
+    );
+    assert_eq!(
+        import.errors[1].error,
+        CsvFieldError::InvalidValue {
+            path: ResponsePath::new("frequency"),
+            reason: "9 is above 4".to_string(),
+        }
+    );
+
+    let csv = format!(
+        "{}Ada,1 Main St,Springfield,12345,Credit Card,4111,123,,\n",
+        csv_header(&OrderForm::survey())
+    );
+    let import = from_csv_reader::<OrderForm>(csv.as_bytes()).unwrap();
+    assert!(import.errors.is_empty(), "{:?}", import.errors);
+    let order = &import.records[0];
+    assert_eq!(order.shipping_address.city, "Springfield");
+    assert_eq!(
+        order.payment,
+        PaymentMethod::CreditCard {
+            number: "4111".to_string(),
+            cvv: "123".to_string(),
+        }
+    );
+}
+
+#[test]
+fn statistics_summarize_many_runs() {
+    use elicitor::{AnswerSummary, ResponsePath, ResponseValue, SurveyStats, TestBackend};
+
+    let runs: Vec<_> = [("Ada Lovelace", 0), ("Bob", 1), ("Cy", 0)]
+        .into_iter()
+        .map(|(name, payment)| {
+            let mut backend = TestBackend::new()
+                .with_string("customer_name", name)
+                .with_string("shipping_address.street", "1 Main St")
+                .with_string("shipping_address.city", "Springfield")
+                .with_string("shipping_address.zip", "12345")
+                .with_response(
+                    "payment.selected_variant",
+                    ResponseValue::ChosenVariant(payment),
+                )
+                .with_string("nickname", "");
+            if payment == 1 {
+                backend = backend
+                    .with_string("payment.number", "4111")
+                    .with_string("payment.cvv", "123");
+            }
+            let (_, responses) = OrderForm::builder().run_with_responses(backend).unwrap();
+            responses
+        })
+        .collect();
+
+    let stats = SurveyStats::collect(&OrderForm::survey(), &runs);
+    assert_eq!(stats.runs(), 3);
+
+    let payment = stats.get(&ResponsePath::new("payment")).unwrap();
+    assert_eq!(payment.prompt, "Payment method:");
+    let AnswerSummary::Counts(counts) = &payment.summary else {
+        panic!("counts expected");
+    };
+    assert_eq!(counts[0], ("Cash payment".to_string(), 2));
+    assert_eq!(counts[1], ("Credit card".to_string(), 1));
+    assert_eq!(
+        stats
+            .get(&ResponsePath::new("payment.number"))
+            .unwrap()
+            .answered,
+        1
+    );
+
+    let AnswerSummary::Text(names) = stats
+        .get(&ResponsePath::new("customer_name"))
+        .unwrap()
+        .summary
+    else {
+        panic!("text expected");
+    };
+    assert_eq!(
+        (names.min_words, names.max_words, names.total_words),
+        (1, 2, 4)
+    );
+    assert!(
+        stats
+            .to_text()
+            .contains("Customer name: (customer_name)\n  answered: 3\n")
+    );
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Extra {
+    Breakfast,
+    Parking {
+        #[ask("License plate:")]
+        plate: String,
+    },
+    Tour(#[ask("Group size:")] u8),
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Booking {
+    #[ask("Guests:")]
+    guests: Vec<String>,
+
+    #[ask("Extras:")]
+    #[multiselect]
+    extras: Vec<Extra>,
+}
+
+#[test]
+fn instances_convert_back_to_responses() {
+    use elicitor::{Color, ResponsePath, ResponseValue};
+
+    fn round_trip<T: Survey + PartialEq + std::fmt::Debug>(value: T) {
+        assert_eq!(T::from_responses(&value.to_responses()), value);
+    }
+
+    round_trip(Feedback {
+        overall: 4,
+        docs: 6,
+    });
+    round_trip(Budget {
+        monthly: 250_000,
+        savings: -1_050,
+    });
+    round_trip(Palette {
+        accent: Color::rgb(0x1e, 0x90, 0xff),
+        highlight: None,
+    });
+    round_trip(Upload {
+        manifest: "Cargo.toml".into(),
+        sources: "src".into(),
+        output: "target/out".into(),
+    });
+    round_trip(Evaluation {
+        aspects: [
+            (Aspect::Pace, Agreement::Agree),
+            (Aspect::Content, Agreement::Disagree),
+        ]
+        .into_iter()
+        .collect(),
+    });
+    round_trip(Booking {
+        guests: vec!["Ada".into(), "Bob".into()],
+        extras: vec![
+            Extra::Parking {
+                plate: "B-AD 42".into(),
+            },
+            Extra::Tour(3),
+        ],
+    });
+
+    let order = OrderForm {
+        customer_name: "Ada".into(),
+        shipping_address: Address {
+            street: "1 Main St".into(),
+            city: "Springfield".into(),
+            zip: "12345".into(),
+        },
+        payment: PaymentMethod::CreditCard {
+            number: "4111".into(),
+            cvv: "123".into(),
+        },
+        nickname: Some("Countess".into()),
+    };
+    let responses = order.to_responses();
+    assert_eq!(
+        responses.get(&ResponsePath::new("payment.selected_variant")),
+        Some(&ResponseValue::ChosenVariant(1))
+    );
+    assert_eq!(
+        responses
+            .get_string(&ResponsePath::new("shipping_address.city"))
+            .unwrap(),
+        "Springfield"
+    );
+    assert_eq!(OrderForm::from_responses(&responses), order);
+
+    let matrix = Evaluation {
+        aspects: [
+            (Aspect::Pace, Agreement::Agree),
+            (Aspect::Content, Agreement::Neutral),
+        ]
+        .into_iter()
+        .collect(),
+    }
+    .to_responses();
+    assert_eq!(
+        matrix.get(&ResponsePath::new("aspects")),
+        Some(&ResponseValue::ChosenVariants(vec![1, 2]))
+    );
+}
+
+#[derive(Survey, Debug, PartialEq)]
+#[partial(Debug, PartialEq)]
+struct Trip {
+    #[ask("Traveller:")]
+    traveller: String,
+
+    #[ask("Nights:")]
+    nights: u8,
+
+    #[ask("Address:")]
+    address: Address,
+
+    #[ask("Extras:")]
+    #[multiselect]
+    extras: Vec<Extra>,
+
+    #[ask("Budget per night:")]
+    budget: Option<u16>,
+}
+
+#[test]
+fn partial_results_keep_the_answers_given() {
+    use elicitor::{ResponseValue, Responses, SurveyError};
+
+    let mut partial = Responses::new();
+    partial.insert("traveller", "Ada");
+    partial.insert("address.street", "1 Main St");
+    partial.insert(
+        "extras.selected_variants",
+        ResponseValue::ChosenVariants(vec![0]),
+    );
+    partial.insert("extras.0.selected_variant", ResponseValue::ChosenVariant(0));
+    let error = SurveyError::Cancelled {
+        partial: Box::new(partial),
+    };
+
+    let trip = Trip::from_partial_responses(error.partial().unwrap());
+    assert_eq!(
+        trip,
+        PartialTrip {
+            traveller: Some("Ada".to_string()),
+            nights: None,
+            address: None,
+            extras: Some(vec![Extra::Breakfast]),
+            budget: None,
+        }
+    );
+    assert_eq!(trip.complete(), None);
+
+    let complete = Trip {
+        traveller: "Ada".into(),
+        nights: 3,
+        address: Address {
+            street: "1 Main St".into(),
+            city: "Springfield".into(),
+            zip: "12345".into(),
+        },
+        extras: vec![Extra::Tour(2)],
+        budget: Some(120),
+    };
+    let partial = Trip::from_partial_responses(&complete.to_responses());
+    assert_eq!(
+        partial.address.as_ref().map(|a| a.city.as_str()),
+        Some("Springfield")
+    );
+    assert_eq!(partial.complete(), Some(complete));
+}
+
+#[test]
+fn editing_a_field_asks_only_that_question() {
+    let trip = Trip {
+        traveller: "Ada".into(),
+        nights: 3,
+        address: Address {
+            street: "1 Main St".into(),
+            city: "Springfield".into(),
+            zip: "12345".into(),
+        },
+        extras: vec![Extra::Tour(2)],
+        budget: Some(120),
+    };
+
+    // Only the edited questions have answers, any other question would fail the run
+    let moved = Trip::builder()
+        .edit_field(&trip, "address.city")
+        .edit_nights(&trip)
+        .run(
+            TestBackend::new()
+                .with_string("address.city", "Shelbyville")
+                .with_int("nights", 5),
+        )
+        .unwrap();
+    assert_eq!(moved.nights, 5);
+    assert_eq!(moved.address.city, "Shelbyville");
+    assert_eq!(moved.address.street, trip.address.street);
+    assert_eq!(moved.extras, trip.extras);
+    assert_eq!(moved.budget, Some(120));
+
+    let error = Trip::builder()
+        .edit_field(&moved, "address.country")
+        .run(TestBackend::new())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "no question to edit at `address.country`"
+    );
+}
+
+#[test]
+fn asking_missing_fields_keeps_the_valid_answers() {
+    use elicitor::Responses;
+
+    // A stored trip from before `budget` and `address.zip` were added
+    let mut stored = Responses::new();
+    stored.insert("traveller", "Ada");
+    stored.insert("nights", 3i64);
+    stored.insert("address.street", "1 Main St");
+    stored.insert("address.city", "Springfield");
+    stored.insert(
+        "extras.selected_variants",
+        elicitor::ResponseValue::ChosenVariants(vec![0]),
+    );
+    stored.insert(
+        "extras.0.selected_variant",
+        elicitor::ResponseValue::ChosenVariant(0),
+    );
+
+    let trip = Trip::builder()
+        .ask_missing(stored)
+        .run(
+            TestBackend::new()
+                .with_string("address.zip", "12345")
+                .with_int("budget", 90),
+        )
+        .unwrap();
+    assert_eq!(trip.traveller, "Ada");
+    assert_eq!(trip.address.zip, "12345");
+    assert_eq!(trip.extras, vec![Extra::Breakfast]);
+    assert_eq!(trip.budget, Some(90));
+
+    // Answers that fail validation are asked again
+    let mut stored = Responses::new();
+    stored.insert("email", "ada.example.com");
+    stored.insert("frequency", 2i64);
+    let newsletter = Newsletter::builder()
+        .ask_missing(stored)
+        .run(TestBackend::new().with_string("email", "ada@example.com"))
+        .unwrap();
+    assert_eq!(newsletter.email, "ada@example.com");
+    assert_eq!(newsletter.frequency, 2);
+}
+
+#[test]
+fn scripted_interview_follows_nested_sections_and_variants() {
+    let order: OrderForm = OrderForm::builder()
+        .run(
+            TestBackend::new()
+                .expect("customer_name", "Ada")
+                .expect("shipping_address.street", "1 Main St")
+                .expect("shipping_address.city", "Springfield")
+                .expect("shipping_address.zip", "12345")
+                .expect("payment", elicitor::ResponseValue::ChosenVariant(2))
+                .expect("payment.iban", "DE02100100109307118603")
+                .expect("nickname", "X"),
+        )
+        .unwrap();
+
+    assert_eq!(
+        order.payment,
+        PaymentMethod::BankTransfer {
+            iban: "DE02100100109307118603".to_string()
+        }
+    );
+    assert_eq!(order.shipping_address.city, "Springfield");
+}
+
+#[test]
+fn scripted_interview_rejects_a_different_sequence() {
+    use elicitor::{ResponseValue, TestBackendError};
+
+    let script = || {
+        TestBackend::new()
+            .expect("customer_name", "Ada")
+            .expect("shipping_address.street", "1 Main St")
+            .expect("shipping_address.city", "Springfield")
+            .expect("shipping_address.zip", "12345")
+    };
+
+    // Cash asks no follow-up, so the card number is never asked
+    let err = OrderForm::builder()
+        .run(
+            script()
+                .expect("payment", ResponseValue::ChosenVariant(0))
+                .expect("payment.number", "4111"),
+        )
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Asked 'nickname'"),
+        "unexpected error: {err}"
+    );
+
+    // The script ends before the survey does
+    let err = OrderForm::builder().run(script()).unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<TestBackendError>(),
+        Some(TestBackendError::UnscriptedQuestion(path)) if path == "payment.selected_variant"
+    ));
+
+    // An answer of the wrong type
+    let err = OrderForm::builder()
+        .run(TestBackend::new().expect("customer_name", 42))
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<TestBackendError>(),
+        Some(TestBackendError::TypeMismatch {
+            expected: "String",
+            got: "Int",
+            ..
+        })
+    ));
+
+    // Questions left over in the script
+    let err = OrderForm::builder()
+        .run(
+            script()
+                .expect("payment", ResponseValue::ChosenVariant(0))
+                .expect("nickname", "X")
+                .expect("newsletter", true),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<TestBackendError>(),
+        Some(TestBackendError::SkippedQuestions(paths)) if paths == &["newsletter".to_string()]
+    ));
+}
+
+#[test]
+fn recorded_session_replays_the_same_answers() {
+    use elicitor::{PlainBackend, RecordingBackend, ReplayBackend, SurveyError};
+
+    let dir = std::env::temp_dir().join(format!("elicitor-replay-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let session = dir.join("order.session");
+
+    let typed = "Ada\n1 Main St\nSpringfield\n12345\nBank transfer\nDE02100100109307118603\nX\n";
+    let plain = PlainBackend::from_reader(typed.as_bytes()).with_output(std::io::sink());
+    let recorded: OrderForm = OrderForm::builder()
+        .run(RecordingBackend::new(plain, &session))
+        .unwrap();
+
+    let replayed: OrderForm = OrderForm::builder()
+        .run(ReplayBackend::from_file(&session).unwrap())
+        .unwrap();
+    assert_eq!(replayed, recorded);
+    assert!(
+        std::fs::read_to_string(&session)
+            .unwrap()
+            .contains("# IBAN:")
+    );
+
+    // A session that ended early replays up to the same point
+    let plain = PlainBackend::from_reader("Ada\n".as_bytes()).with_output(std::io::sink());
+    let err = OrderForm::builder()
+        .run(RecordingBackend::new(plain, &session))
+        .unwrap_err();
+    assert!(err.downcast_ref::<SurveyError>().unwrap().is_cancelled());
+
+    let err = OrderForm::builder()
+        .run(ReplayBackend::from_file(&session).unwrap())
+        .unwrap_err();
+    let partial = err
+        .downcast_ref::<elicitor::ReplayError>()
+        .and_then(|err| err.partial())
+        .unwrap();
+    assert_eq!(
+        partial
+            .get_string(&elicitor::ResponsePath::new("customer_name"))
+            .unwrap(),
+        "Ada"
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn order_form_snapshot() {
+    assert_eq!(
+        OrderForm::survey().snapshot(),
+        r#"customer_name: Input "Customer name:"
+shipping_address: AllOf "Shipping address:"
+  street: Input "Street address:"
+  city: Input "City:"
+  zip: Input "Zip code:"
+payment: AllOf "Payment method:"
+  OneOf
+    Cash payment: Unit
+    Credit card: AllOf
+      number: Input "Card number:"
+      cvv: Input "CVV:"
+    Bank transfer: AllOf
+      iban: Input "IBAN:"
+nickname: Input "Nickname (optional):"
+"#
+    );
+}
+
+#[derive(Survey, Debug, PartialEq)]
+enum Class {
+    /// A fierce warrior
+    ///
+    /// Fights up close and takes the most hits.
+    Warrior,
+    /// A wise
+    /// mage
+    Mage,
+    #[ask("Rogue")]
+    /// Strikes from the shadows.
+    Rogue,
+    /// Hidden healer
+    #[help("Keeps the party alive.")]
+    Cleric,
+    Bard,
+}
+
+#[derive(Survey, Debug, PartialEq)]
+struct Hero {
+    #[ask("Class:")]
+    class: Class,
+}
+
+#[test]
+fn variants_are_labelled_and_explained_by_their_doc_comments() {
+    use elicitor::QuestionKind;
+
+    let survey = Class::survey();
+    let QuestionKind::OneOf(one_of) = survey.questions[0].kind() else {
+        panic!("Expected OneOf question kind");
+    };
+    let variants: Vec<(&str, Option<&str>)> = one_of
+        .variants
+        .iter()
+        .map(|v| (v.name.as_str(), v.help.as_deref()))
+        .collect();
+    assert_eq!(
+        variants,
+        vec![
+            (
+                "A fierce warrior",
+                Some("Fights up close and takes the most hits.")
+            ),
+            ("A wise mage", None),
+            ("Rogue", Some("Strikes from the shadows.")),
+            ("Hidden healer", Some("Keeps the party alive.")),
+            ("Bard", None),
+        ]
+    );
+
+    let backend = TestBackend::new().with_response(
+        "class.selected_variant",
+        elicitor::ResponseValue::ChosenVariant(2),
+    );
+    let hero: Hero = Hero::builder().run(backend).unwrap();
+    assert_eq!(hero.class, Class::Rogue);
+}
@@ -0,0 +1,30 @@
+//! Autocomplete example types
+//!
+//! Demonstrates:
+//! - #[autocomplete] with a fixed list of suggestions
+//! - #[autocomplete] with a completion function that sees the current input
+
+use elicitor::Survey;
+
+/// Completes against the login shells listed in `/etc/shells`.
+pub fn installed_shells(input: &str) -> Vec<String> {
+    std::fs::read_to_string("/etc/shells")
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| line.contains(input))
+        .map(String::from)
+        .collect()
+}
+
+#[derive(Survey, Debug)]
+pub struct EditorSetup {
+    #[ask("Which editor do you use?")]
+    #[autocomplete(["vim", "neovim", "emacs", "helix", "nano", "vscode", "zed"])]
+    pub editor: String,
+
+    #[ask("Which shell should be started?")]
+    #[autocomplete(installed_shells)]
+    pub shell: String,
+}
@@ -0,0 +1,34 @@
+//! Color question example types
+//!
+//! Demonstrates:
+//! - `elicitor::Color` fields, answered with a color picker or as a hex string
+//! - Suggesting a color as the default
+
+use elicitor::{Color, Survey};
+
+#[derive(Survey, Debug)]
+pub struct EditorTheme {
+    #[ask("Theme name:")]
+    pub name: String,
+
+    #[ask("Accent color:")]
+    pub accent: Color,
+
+    #[ask("Background color:")]
+    pub background: Color,
+
+    #[ask("Use the accent color for the cursor?")]
+    pub accent_cursor: bool,
+}
+
+impl EditorTheme {
+    /// A dark default theme, used as suggestions in the examples.
+    pub fn dark() -> Self {
+        Self {
+            name: "Midnight".to_string(),
+            accent: Color::rgb(0x1e, 0x90, 0xff),
+            background: Color::rgb(0x10, 0x10, 0x18),
+            accent_cursor: true,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Currency question example types
+//!
+//! Demonstrates:
+//! - `#[currency("USD")]` on integer fields holding cents
+//! - `#[currency("EUR", "de")]` for German formatting like `1.234,56 €`
+//! - Bounds given in minor units
+
+use elicitor::Survey;
+
+/// A mortgage enquiry with amounts in two currencies.
+#[derive(Survey, Debug)]
+pub struct MortgageApplication {
+    #[ask("Purchase price of the property:")]
+    #[currency("USD")]
+    #[min(5_000_000)]
+    pub purchase_price: u64,
+
+    #[ask("Down payment:")]
+    #[currency("USD")]
+    #[min(0)]
+    pub down_payment: u64,
+
+    #[ask("Gross monthly income:")]
+    #[currency("USD")]
+    #[min(0)]
+    pub monthly_income: u64,
+
+    #[ask("Savings held in euro:")]
+    #[currency("EUR", "de")]
+    #[min(0)]
+    pub savings_eur: u64,
+}
@@ -1,4 +1,4 @@
-use elicitor::{ResponseValue, Responses, Survey};
+use elicitor::{CurrencyQuestion, ResponseValue, Responses, SecretString, Survey};
 use std::path::PathBuf;
 
 pub fn validate_email(
@@ -9,7 +9,12 @@ pub fn validate_email(
     let ResponseValue::String(email) = value else {
         return Ok(());
     };
-    if !email.contains('@') || !email.split('@').last().is_some_and(|d| d.contains('.')) {
+    if !email.contains('@')
+        || !email
+            .split('@')
+            .next_back()
+            .is_some_and(|d| d.contains('.'))
+    {
         return Err("Enter a valid email (e.g., you@example.com)".into());
     }
     Ok(())
@@ -62,8 +67,8 @@ pub fn validate_skills(
     Ok(())
 }
 
-/// Salary expectations must total <= $250k (base + bonus)
-pub const MAX_TOTAL_COMP: i64 = 250_000;
+/// Salary expectations must total <= $250,000 (base + bonus), in cents
+pub const MAX_TOTAL_COMP: i64 = 25_000_000;
 
 pub fn validate_salary(
     value: &ResponseValue,
@@ -78,8 +83,11 @@ pub fn validate_salary(
     let total = base + bonus + current;
 
     if total > MAX_TOTAL_COMP {
+        let usd = CurrencyQuestion::new("USD");
         return Err(format!(
-            "Total comp ${total}k exceeds ${MAX_TOTAL_COMP}k limit"
+            "Total comp {} exceeds {} limit",
+            usd.format(total),
+            usd.format(MAX_TOTAL_COMP)
         ));
     }
     Ok(())
@@ -89,14 +97,16 @@ pub fn validate_salary(
 #[derive(Survey, Debug)]
 #[validate_fields(validate_salary)]
 pub struct Salary {
-    #[ask("Base salary ($k/year):")]
-    #[min(30)]
-    #[max(200)]
+    #[ask("Base salary per year:")]
+    #[currency("USD")]
+    #[min(3_000_000)]
+    #[max(20_000_000)]
     pub base: u32,
 
-    #[ask("Expected bonus ($k/year):")]
+    #[ask("Expected bonus per year:")]
+    #[currency("USD")]
     #[min(0)]
-    #[max(100)]
+    #[max(10_000_000)]
     pub bonus: u32,
 }
 
@@ -204,7 +214,7 @@ pub struct JobApplication {
     #[ask("Create a portal password:")]
     #[mask]
     #[validate(validate_password)]
-    pub password: String,
+    pub password: SecretString,
 
     // Enum selections
     #[ask("Position applying for:")]
@@ -242,6 +252,7 @@ pub struct JobApplication {
 
     // PathBuf
     #[ask("Resume file path:")]
+    #[file(extensions = "pdf,docx")]
     pub resume: PathBuf,
 
     // Simple bool
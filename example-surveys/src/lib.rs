@@ -1,8 +1,12 @@
 pub mod app_settings;
+pub mod autocomplete;
 pub mod basic_fields;
+pub mod color;
+pub mod currency;
 pub mod enum_oneof;
 pub mod job_application;
 pub mod masked_input;
+pub mod matrix;
 pub mod min_max_bounds;
 pub mod multiline_text;
 pub mod multiselect;
@@ -10,6 +14,8 @@ pub mod nested_struct;
 pub mod optional_fields;
 pub mod order_form;
 pub mod prelude_epilogue;
+pub mod rating;
+pub mod runtime_choices;
 pub mod sandwich;
 pub mod simple_spooky_forest;
 pub mod spooky_forest;
@@ -20,9 +26,18 @@ pub mod vec_lists;
 // Re-export app_settings types
 pub use app_settings::AppSettings;
 
+// Re-export autocomplete types
+pub use autocomplete::{EditorSetup, installed_shells};
+
 // Re-export basic_fields types
 pub use basic_fields::BasicFields;
 
+// Re-export color types
+pub use color::EditorTheme;
+
+// Re-export currency types
+pub use currency::MortgageApplication;
+
 // Re-export enum_oneof types
 pub use enum_oneof::{Checkout, PaymentMethod, ShippingMethod};
 
@@ -41,6 +56,9 @@ pub use spooky_forest::{
 // Re-export masked_input types
 pub use masked_input::{Login, Passwords, passwords_match};
 
+// Re-export matrix types
+pub use matrix::{Agreement, Aspect, CourseEvaluation};
+
 // Re-export min_max_bounds types
 pub use min_max_bounds::GameSettings;
 
@@ -62,6 +80,12 @@ pub use order_form::{OrderForm, PaymentMethod2, ShippingAddress, ShippingSpeed};
 // Re-export prelude_epilogue types
 pub use prelude_epilogue::FitnessProfile;
 
+// Re-export rating types
+pub use rating::CourseFeedback;
+
+// Re-export runtime_choices types
+pub use runtime_choices::{FilePicker, environment_variables, files_in_current_dir};
+
 // Re-export sandwich types
 pub use sandwich::{
     Bread, Cheese, Filling, FillingType, Nutrition, SandwichOrder, Sauce, Size, Topping,
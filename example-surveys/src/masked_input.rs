@@ -2,9 +2,11 @@
 //!
 //! Demonstrates:
 //! - #[mask] attribute for hiding sensitive input like passwords
+//! - `SecretString` fields, redacted in debug output and zeroized on drop
 //! - Cross-field validation for password confirmation
 
-use elicitor::{ResponsePath, ResponseValue, Responses, Survey};
+use elicitor::secrecy::ExposeSecret;
+use elicitor::{ResponsePath, ResponseValue, Responses, SecretString, Survey};
 
 /// Validates that the password confirmation matches the original password
 pub fn passwords_match(
@@ -28,7 +30,7 @@ pub fn passwords_match(
     // Get the original password using the typed accessor
     let password = ctx.get_password().unwrap_or_default();
 
-    if password != *password_confirm {
+    if password.expose_secret() != password_confirm {
         return Err("Passwords do not match".to_string());
     }
 
@@ -39,12 +41,12 @@ pub fn passwords_match(
 pub struct Passwords {
     #[ask("Enter your password:")]
     #[mask]
-    pub password: String,
+    pub password: SecretString,
 
     #[ask("Confirm your password:")]
     #[mask]
     #[validate(passwords_match)]
-    pub password_confirm: String,
+    pub password_confirm: SecretString,
 }
 
 #[derive(Survey, Debug)]
@@ -0,0 +1,37 @@
+//! Matrix question example types
+//!
+//! Demonstrates:
+//! - HashMap<RowEnum, ColumnEnum> for a grid question
+//! - Every variant of the key enum as a row, every variant of the value enum as a column
+
+use std::collections::HashMap;
+
+use elicitor::Survey;
+
+#[derive(Survey, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aspect {
+    Content,
+    Pace,
+    Exercises,
+    Materials,
+}
+
+#[derive(Survey, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Agreement {
+    #[ask("Strongly disagree")]
+    StronglyDisagree,
+    Disagree,
+    Neutral,
+    Agree,
+    #[ask("Strongly agree")]
+    StronglyAgree,
+}
+
+#[derive(Survey, Debug)]
+pub struct CourseEvaluation {
+    #[ask("Which course did you attend?")]
+    pub course: String,
+
+    #[ask("The following parts of the course were good:")]
+    pub aspects: HashMap<Aspect, Agreement>,
+}
@@ -5,7 +5,7 @@
 //! - AllOf (nested structs)
 //! - Variants with data
 
-use elicitor::Survey;
+use elicitor::{SecretString, Survey};
 
 /// Payment method selection (OneOf example).
 #[derive(Debug, Survey)]
@@ -18,7 +18,7 @@ pub enum PaymentMethod2 {
         expiry: String,
         #[ask("CVV:")]
         #[mask]
-        cvv: String,
+        cvv: SecretString,
     },
 
     #[ask("PayPal")]
@@ -0,0 +1,29 @@
+//! Rating scale example types
+//!
+//! Demonstrates:
+//! - #[rating(1..=5)] for a star rating
+//! - #[rating(0..=10)] for a numeric scale
+//! - #[likert("low".."high")] for a Likert item with anchor labels
+//! - #[allow_comment] for an optional remark on the overall rating
+
+use elicitor::Survey;
+
+#[derive(Survey, Debug)]
+pub struct CourseFeedback {
+    #[ask("How would you rate the course overall?")]
+    #[rating(1..=5)]
+    #[allow_comment]
+    pub overall: u8,
+
+    #[ask("How likely are you to recommend it to a colleague?")]
+    #[rating(0..=10)]
+    pub recommend: u8,
+
+    #[ask("The material was easy to follow.")]
+    #[likert("Strongly disagree".."Strongly agree")]
+    pub clarity: i32,
+
+    #[ask("The pace of the course was...")]
+    #[likert("Far too slow".."Far too fast", 7)]
+    pub pace: i32,
+}
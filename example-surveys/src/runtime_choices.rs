@@ -0,0 +1,45 @@
+//! Runtime choices example types
+//!
+//! Demonstrates:
+//! - #[choices_from] attribute for options that are only known at interview time
+//! - Choice functions returning plain values or (value, label) pairs
+
+use elicitor::Survey;
+
+/// Lists the files in the current directory.
+pub fn files_in_current_dir() -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(".")
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_file())
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Lists the environment variables, labelled with their current value.
+pub fn environment_variables() -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = std::env::vars()
+        .map(|(key, value)| {
+            let label = format!("{key} = {value}");
+            (key, label)
+        })
+        .collect();
+    vars.sort();
+    vars
+}
+
+#[derive(Survey, Debug)]
+pub struct FilePicker {
+    #[ask("Which file should be processed?")]
+    #[choices_from(files_in_current_dir)]
+    pub file: String,
+
+    #[ask("Which environment variable should be exported?")]
+    #[choices_from(environment_variables)]
+    pub variable: String,
+}
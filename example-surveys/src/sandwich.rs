@@ -1,4 +1,4 @@
-use elicitor::{ResponseValue, Responses, Survey};
+use elicitor::{ResponseValue, Responses, SecretString, Survey};
 use std::path::PathBuf;
 
 /// Single validator: toppings budget (each topping = $0.50, max $3 = 6 toppings)
@@ -147,7 +147,7 @@ pub struct SandwichOrder {
 
     #[ask("Rewards PIN (4 digits):")]
     #[mask]
-    pub pin: String,
+    pub pin: SecretString,
 
     #[ask("Choose your bread:")]
     pub bread: Bread,
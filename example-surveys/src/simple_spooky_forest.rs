@@ -8,7 +8,7 @@
 //! - Enum selection (OneOf)
 //! - Multi-select (AnyOf) with budget validation
 
-use elicitor::{ResponsePath, ResponseValue, Responses, Survey};
+use elicitor::{ResponsePath, ResponseValue, Responses, SecretString, Survey};
 
 pub fn is_valid_name(
     value: &ResponseValue,
@@ -103,7 +103,7 @@ pub struct SimpleSpookyForest {
 
     #[ask("What's the secret passphrase?")]
     #[mask]
-    pub passphrase: String,
+    pub passphrase: SecretString,
 
     #[ask("How old are you?")]
     #[min(18)]
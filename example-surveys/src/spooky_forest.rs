@@ -1,4 +1,4 @@
-use elicitor::{ResponseValue, Responses, Survey};
+use elicitor::{ResponseValue, Responses, SecretString, Survey};
 use std::path::PathBuf;
 
 /// Validates that a name is between 3 and 50 characters
@@ -423,7 +423,7 @@ pub struct SpookyForest {
     #[ask("Create a secret passphrase (8+ chars, uppercase & number required):")]
     #[mask]
     #[validate(validate_passphrase)]
-    pub passphrase: String,
+    pub passphrase: SecretString,
 
     #[ask("Tell us your backstory:")]
     #[multiline]
@@ -460,6 +460,7 @@ pub struct SpookyForest {
     pub inventory: Vec<Item>,
 
     #[ask("Your character portrait file:")]
+    #[file(extensions = "png,jpg")]
     pub portrait_path: PathBuf,
 
     #[ask("Enable hardcore mode? (permadeath)")]
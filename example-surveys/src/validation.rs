@@ -5,7 +5,7 @@
 //! - Custom validator functions
 //! - Using ResponseValue and Responses for validation
 
-use elicitor::{ResponsePath, ResponseValue, Responses, Survey};
+use elicitor::{ResponsePath, ResponseValue, Responses, SecretString, Survey};
 
 pub fn validate_email(
     value: &ResponseValue,
@@ -77,7 +77,7 @@ pub struct AccountCreation {
     #[ask("Create a password:")]
     #[mask]
     #[validate(validate_password)]
-    pub password: String,
+    pub password: SecretString,
 
     #[ask("Your age:")]
     #[min(13)]
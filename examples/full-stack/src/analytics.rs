@@ -0,0 +1,162 @@
+//! Exports of the stored submissions for spreadsheets and dashboards.
+
+use std::collections::{BTreeMap, HashMap};
+
+use elicitor::{
+    FieldHandling, Question, QuestionKind, ResponsePath, ResponseValue, SELECTED_VARIANT_KEY,
+    SELECTED_VARIANTS_KEY, SensitiveField, SensitiveSink, SurveyDefinition,
+};
+
+use crate::Submission;
+
+/// The export of [`to_csv`], for privacy reports.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvExport;
+
+/// Every stored answer gets a column, masked or not.
+impl SensitiveSink for CsvExport {
+    fn sink_name(&self) -> String {
+        "csv export".to_string()
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        FieldHandling::Plaintext
+    }
+}
+
+/// One row per submission and one column per answer, with choices written as the
+/// variant names.
+pub fn to_csv(definition: &SurveyDefinition, submissions: &[Submission]) -> String {
+    let choices = choice_names(definition);
+    let mut columns: Vec<&ResponsePath> = Vec::new();
+    for submission in submissions {
+        for path in submission.answers.keys() {
+            if !columns.contains(&path) {
+                columns.push(path);
+            }
+        }
+    }
+
+    let mut csv = String::from("id");
+    for path in &columns {
+        csv.push(',');
+        csv.push_str(&csv_field(path.as_str()));
+    }
+    csv.push('\n');
+
+    for submission in submissions {
+        csv.push_str(&submission.id.to_string());
+        for path in &columns {
+            csv.push(',');
+            if let Some(value) = submission.answers.get(*path) {
+                csv.push_str(&csv_field(&cell(value, choices.get(*path))));
+            }
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// How often each answer was given to the question at `path`, e.g. tickets per type.
+pub fn tally(
+    definition: &SurveyDefinition,
+    submissions: &[Submission],
+    path: &ResponsePath,
+) -> BTreeMap<String, usize> {
+    let choices = choice_names(definition);
+    let mut counts = BTreeMap::new();
+    for submission in submissions {
+        if let Some(value) = submission.answers.get(path) {
+            *counts.entry(cell(value, choices.get(path))).or_default() += 1;
+        }
+    }
+    counts
+}
+
+fn cell(value: &ResponseValue, choices: Option<&Vec<String>>) -> String {
+    let name = |idx: usize| {
+        choices
+            .and_then(|names| names.get(idx))
+            .cloned()
+            .unwrap_or_else(|| idx.to_string())
+    };
+    match value {
+        ResponseValue::String(s) => s.clone(),
+        ResponseValue::Int(i) => i.to_string(),
+        ResponseValue::Float(f) => f.to_string(),
+        ResponseValue::Bool(b) => b.to_string(),
+        ResponseValue::ChosenVariant(idx) => name(*idx),
+        ResponseValue::ChosenVariants(indices) => indices
+            .iter()
+            .map(|idx| name(*idx))
+            .collect::<Vec<_>>()
+            .join(";"),
+        ResponseValue::StringList(items) => items.join(";"),
+        ResponseValue::IntList(items) => join(items),
+        ResponseValue::FloatList(items) => join(items),
+    }
+}
+
+fn join<T: ToString>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Variant names of every choice question, by the path its answer is stored at.
+fn choice_names(definition: &SurveyDefinition) -> HashMap<ResponsePath, Vec<String>> {
+    let mut names = HashMap::new();
+    collect_choices(definition.questions(), &ResponsePath::empty(), &mut names);
+    names
+}
+
+fn collect_choices(
+    questions: &[Question],
+    prefix: &ResponsePath,
+    names: &mut HashMap<ResponsePath, Vec<String>>,
+) {
+    for question in questions {
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+        let variant_names = |variants: &[elicitor::Variant]| {
+            variants
+                .iter()
+                .map(|variant| variant.name.clone())
+                .collect()
+        };
+        match question.kind() {
+            QuestionKind::AllOf(all_of) => collect_choices(all_of.questions(), &path, names),
+            QuestionKind::OneOf(one_of) => {
+                names.insert(
+                    path.child(SELECTED_VARIANT_KEY),
+                    variant_names(&one_of.variants),
+                );
+                for variant in &one_of.variants {
+                    if let QuestionKind::AllOf(all_of) = &variant.kind {
+                        collect_choices(all_of.questions(), &path, names);
+                    }
+                }
+            }
+            QuestionKind::AnyOf(any_of) => {
+                names.insert(
+                    path.child(SELECTED_VARIANTS_KEY),
+                    variant_names(&any_of.variants),
+                );
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Errors of the app.
+
+use elicitor_doc_html::FormDataError;
+use elicitor_doc_pdf::ImportError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("Invalid submission: {0}")]
+    Submission(#[from] FormDataError),
+
+    #[error("Storage error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Corrupt submission record: {0}")]
+    Record(#[from] serde_json::Error),
+
+    #[error("No submission with id {0}")]
+    NotFound(u64),
+
+    #[error("Could not read the PDF form: {0}")]
+    Pdf(#[from] ImportError),
+}
@@ -0,0 +1,26 @@
+//! # elicitor-full-stack
+//!
+//! One survey taken through every stage an application needs:
+//!
+//! 1. [`Registration`] is a `#[derive(Survey)]` type.
+//! 2. [`server::App`] serves it as an HTML form and parses the posted answers with
+//!    `elicitor-doc-html`.
+//! 3. [`FileStore`] appends each submission to a JSON lines file.
+//! 4. [`analytics`] exports the submissions as CSV and counts answers.
+//! 5. [`pdf::filled_pdf`] renders a submission as a filled PDF form with
+//!    `elicitor-doc-pdf`, which reads back into the same answers.
+//!
+//! Run the server with `cargo run -p elicitor-full-stack` and open
+//! <http://127.0.0.1:8080>. The tests in `tests/pipeline.rs` drive the same routes
+//! without a socket.
+
+pub mod analytics;
+mod error;
+pub mod pdf;
+pub mod server;
+mod store;
+mod survey;
+
+pub use error::AppError;
+pub use store::{FileStore, Submission};
+pub use survey::{Registration, Ticket, validate_email};
@@ -0,0 +1,21 @@
+//! Serve the registration form.
+//!
+//! Run with: cargo run -p elicitor-full-stack [-- <address> [<store>]]
+
+use std::net::TcpListener;
+
+use elicitor_full_stack::FileStore;
+use elicitor_full_stack::server::{App, serve};
+
+fn main() -> std::io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    let store = args
+        .next()
+        .unwrap_or_else(|| "submissions.jsonl".to_string());
+
+    let listener = TcpListener::bind(&address)?;
+    println!("Serving the registration form on http://{address}");
+    println!("Submissions: {store}, export: http://{address}/export.csv");
+    serve(listener, &App::new(FileStore::new(store)))
+}
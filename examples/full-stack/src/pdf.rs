@@ -0,0 +1,50 @@
+//! Filled PDF copies of stored submissions.
+
+use elicitor::{
+    Question, QuestionKind, ResponsePath, Responses, SELECTED_VARIANT_KEY, SELECTED_VARIANTS_KEY,
+    SurveyDefinition,
+};
+use elicitor_doc_pdf::{PdfOptions, definition_to_pdf_form};
+
+/// A PDF form of `definition` with the fields filled in from `responses`.
+///
+/// The form stays editable, and [`elicitor_doc_pdf::from_pdf_form`] reads it back.
+pub fn filled_pdf(definition: &SurveyDefinition, responses: &Responses, title: &str) -> Vec<u8> {
+    let mut definition = definition.clone();
+    fill(
+        definition.questions_mut(),
+        &ResponsePath::empty(),
+        responses,
+    );
+    definition_to_pdf_form(&definition, &PdfOptions::new().with_title(title))
+}
+
+/// Suggest the stored answers, which the PDF generator writes as field values.
+fn fill(questions: &mut [Question], prefix: &ResponsePath, responses: &Responses) {
+    for question in questions {
+        let path = if question.path().is_empty() {
+            prefix.clone()
+        } else {
+            prefix.child(question.path().as_str())
+        };
+        let answer = match question.kind_mut() {
+            QuestionKind::AllOf(all_of) => {
+                fill(all_of.questions_mut(), &path, responses);
+                None
+            }
+            QuestionKind::OneOf(one_of) => {
+                for variant in one_of.variants_mut() {
+                    if let QuestionKind::AllOf(all_of) = &mut variant.kind {
+                        fill(all_of.questions_mut(), &path, responses);
+                    }
+                }
+                responses.get(&path.child(SELECTED_VARIANT_KEY))
+            }
+            QuestionKind::AnyOf(_) => responses.get(&path.child(SELECTED_VARIANTS_KEY)),
+            _ => responses.get(&path),
+        };
+        if let Some(value) = answer {
+            question.set_suggestion(value.clone());
+        }
+    }
+}
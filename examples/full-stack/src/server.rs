@@ -0,0 +1,233 @@
+//! A minimal HTTP server over the pipeline, built on `std::net` only.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+use elicitor::Survey;
+use elicitor_doc_html::{HtmlOptions, form_data_to_responses, parse_urlencoded};
+
+use crate::{AppError, FileStore, Registration, analytics, pdf};
+
+const TITLE: &str = "Meetup Registration";
+
+/// A parsed HTTP request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+impl Request {
+    pub fn get(path: impl Into<String>) -> Self {
+        Self {
+            method: "GET".to_string(),
+            path: path.into(),
+            body: String::new(),
+        }
+    }
+
+    pub fn post(path: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            method: "POST".to_string(),
+            path: path.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// An HTTP response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    fn html(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self::new(status, "text/html; charset=utf-8", body)
+    }
+
+    fn error(err: &AppError) -> Self {
+        let status = match err {
+            AppError::Submission(_) => 422,
+            AppError::NotFound(_) => 404,
+            _ => 500,
+        };
+        Self::new(status, "text/plain; charset=utf-8", err.to_string())
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            404 => "Not Found",
+            422 => "Unprocessable Entity",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Routes requests to the form, the store, the CSV export and the PDF copies.
+#[derive(Debug, Clone)]
+pub struct App {
+    store: FileStore,
+}
+
+impl App {
+    pub fn new(store: FileStore) -> Self {
+        Self { store }
+    }
+
+    pub fn handle(&self, request: &Request) -> Response {
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/") => Ok(self.form()),
+            ("POST", "/submit") => self.submit(&request.body),
+            ("GET", "/export.csv") => self.export(),
+            ("GET", path) => match submission_pdf_id(path) {
+                Some(id) => self.pdf(id),
+                None => Ok(not_found()),
+            },
+            _ => Ok(not_found()),
+        };
+        result.unwrap_or_else(|err| Response::error(&err))
+    }
+
+    fn form(&self) -> Response {
+        let options = HtmlOptions::new()
+            .with_title(TITLE)
+            .with_action("/submit")
+            .with_client_validation(true);
+        Response::html(
+            200,
+            elicitor_doc_html::to_html_with_options::<Registration>(options),
+        )
+    }
+
+    fn submit(&self, body: &str) -> Result<Response, AppError> {
+        let definition = Registration::survey();
+        let responses = form_data_to_responses(&definition, &parse_urlencoded(body))?;
+        for (path, value) in responses.iter() {
+            Registration::validate_field(value, &responses, path).map_err(|message| {
+                elicitor_doc_html::FormDataError::InvalidValue {
+                    path: path.clone(),
+                    message,
+                }
+            })?;
+        }
+
+        let id = self.store.insert(&responses)?;
+        let registration = Registration::from_responses(&responses);
+        Ok(Response::html(
+            201,
+            format!(
+                "<p>Thanks, {}! Download your <a href=\"/submissions/{id}.pdf\">registration</a>.</p>\n",
+                escape(&registration.name)
+            ),
+        ))
+    }
+
+    fn export(&self) -> Result<Response, AppError> {
+        let csv = analytics::to_csv(&Registration::survey(), &self.store.all()?);
+        Ok(Response::new(200, "text/csv; charset=utf-8", csv))
+    }
+
+    fn pdf(&self, id: u64) -> Result<Response, AppError> {
+        let submission = self.store.get(id)?;
+        let pdf = pdf::filled_pdf(&Registration::survey(), &submission.responses(), TITLE);
+        Ok(Response::new(200, "application/pdf", pdf))
+    }
+}
+
+fn not_found() -> Response {
+    Response::new(404, "text/plain; charset=utf-8", "Not found")
+}
+
+/// The id in `/submissions/{id}.pdf`.
+fn submission_pdf_id(path: &str) -> Option<u64> {
+    path.strip_prefix("/submissions/")?
+        .strip_suffix(".pdf")?
+        .parse()
+        .ok()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Answer requests on `listener` one at a time, until it fails.
+pub fn serve(listener: TcpListener, app: &App) -> std::io::Result<()> {
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let response = match read_request(&mut stream) {
+            Ok(request) => app.handle(&request),
+            Err(err) => Response::new(400, "text/plain; charset=utf-8", err.to_string()),
+        };
+        write_response(&mut stream, &response)?;
+    }
+    Ok(())
+}
+
+/// Read a request line, the headers and a `Content-Length` body.
+pub fn read_request(stream: &mut TcpStream) -> std::io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "malformed request line",
+        ));
+    };
+    let method = method.to_string();
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request {
+        method,
+        path,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn write_response(stream: &mut TcpStream, response: &Response) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
@@ -0,0 +1,93 @@
+//! Submissions stored as JSON lines, one record per submission.
+
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use elicitor::{
+    FieldHandling, ResponsePath, ResponseValue, Responses, SensitiveField, SensitiveSink,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::AppError;
+
+/// A stored submission.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub id: u64,
+    pub answers: BTreeMap<ResponsePath, ResponseValue>,
+}
+
+impl Submission {
+    /// The answers as responses, e.g. for `T::from_responses`.
+    pub fn responses(&self) -> Responses {
+        let mut responses = Responses::new();
+        for (path, value) in &self.answers {
+            responses.insert(path.clone(), value.clone());
+        }
+        responses
+    }
+}
+
+/// Append-only store of submissions in a JSON lines file.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    path: PathBuf,
+}
+
+impl FileStore {
+    /// Store submissions in the file at `path`, created on the first submission.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Append a submission and return its id.
+    pub fn insert(&self, responses: &Responses) -> Result<u64, AppError> {
+        let id = self.all()?.last().map_or(1, |last| last.id + 1);
+        let answers = responses
+            .iter()
+            .map(|(path, value)| (path.clone(), value.clone()))
+            .collect();
+        let mut line = serde_json::to_string(&Submission { id, answers })?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        Ok(id)
+    }
+
+    /// All submissions, oldest first.
+    pub fn all(&self) -> Result<Vec<Submission>, AppError> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    /// The submission with `id`.
+    pub fn get(&self, id: u64) -> Result<Submission, AppError> {
+        self.all()?
+            .into_iter()
+            .find(|submission| submission.id == id)
+            .ok_or(AppError::NotFound(id))
+    }
+}
+
+impl SensitiveSink for FileStore {
+    fn sink_name(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn handling(&self, _field: &SensitiveField) -> FieldHandling {
+        FieldHandling::Plaintext
+    }
+}
@@ -0,0 +1,51 @@
+//! The survey the app collects.
+
+use elicitor::{ResponsePath, ResponseValue, Responses, Survey};
+
+pub fn validate_email(
+    value: &ResponseValue,
+    _responses: &Responses,
+    _path: &ResponsePath,
+) -> Result<(), String> {
+    let ResponseValue::String(email) = value else {
+        return Ok(());
+    };
+    match email.split_once('@') {
+        Some((user, domain)) if !user.is_empty() && domain.contains('.') => Ok(()),
+        _ => Err("Not an email address".to_string()),
+    }
+}
+
+#[derive(Survey, Debug, Clone, PartialEq)]
+pub enum Ticket {
+    #[ask("Standard")]
+    Standard,
+
+    #[ask("Student")]
+    Student,
+
+    #[ask("Speaker")]
+    Speaker,
+}
+
+#[derive(Survey, Debug, Clone, PartialEq)]
+#[prelude("Register for the Rust meetup.")]
+pub struct Registration {
+    #[ask("Your name:")]
+    pub name: String,
+
+    #[ask("Email address:")]
+    #[validate(validate_email)]
+    pub email: String,
+
+    #[ask("Ticket:")]
+    pub ticket: Ticket,
+
+    #[ask("Guests you bring along:")]
+    #[min(0)]
+    #[max(3)]
+    pub guests: u32,
+
+    #[ask("Subscribe to the newsletter?")]
+    pub newsletter: bool,
+}
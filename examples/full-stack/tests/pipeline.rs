@@ -0,0 +1,135 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+
+use elicitor::{ResponsePath, Survey};
+use elicitor_full_stack::server::{App, Request, serve};
+use elicitor_full_stack::{FileStore, Registration, Ticket, analytics};
+
+const ADA: &str = "name=Ada+Lovelace&email=ada%40example.org&ticket=1&guests=2&newsletter=on";
+
+fn store(name: &str) -> (FileStore, PathBuf) {
+    let path = std::env::temp_dir().join(format!(
+        "elicitor-full-stack-{}-{name}.jsonl",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    (FileStore::new(&path), path)
+}
+
+#[test]
+fn form_posts_to_the_submit_route() {
+    let (store, _) = store("form");
+    let response = App::new(store).handle(&Request::get("/"));
+    assert_eq!(response.status, 200);
+
+    let html = String::from_utf8(response.body).unwrap();
+    assert!(html.contains("method=\"post\" action=\"/submit\""));
+    assert!(html.contains("name=\"email\""));
+    assert!(html.contains("<script>"));
+}
+
+#[test]
+fn submissions_are_stored_and_exported() {
+    let (store, path) = store("export");
+    let app = App::new(store.clone());
+
+    assert_eq!(app.handle(&Request::post("/submit", ADA)).status, 201);
+    let bob = "name=Bob&email=bob%40example.org&ticket=0&guests=0";
+    assert_eq!(app.handle(&Request::post("/submit", bob)).status, 201);
+
+    let submissions = store.all().unwrap();
+    assert_eq!(submissions.len(), 2);
+    assert_eq!(
+        Registration::from_responses(&submissions[0].responses()),
+        Registration {
+            name: "Ada Lovelace".to_string(),
+            email: "ada@example.org".to_string(),
+            ticket: Ticket::Student,
+            guests: 2,
+            newsletter: true,
+        }
+    );
+
+    let csv = String::from_utf8(app.handle(&Request::get("/export.csv")).body).unwrap();
+    let mut lines = csv.lines();
+    assert!(lines.next().unwrap().starts_with("id,"));
+    assert!(lines.next().unwrap().contains("Ada Lovelace"));
+    assert!(csv.contains("Student"));
+
+    let tickets = analytics::tally(
+        &Registration::survey(),
+        &submissions,
+        &ResponsePath::new("ticket.selected_variant"),
+    );
+    assert_eq!(tickets.get("Student"), Some(&1));
+    assert_eq!(tickets.get("Standard"), Some(&1));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn invalid_submissions_are_rejected() {
+    let (store, _) = store("invalid");
+    let app = App::new(store.clone());
+
+    let response = app.handle(&Request::post(
+        "/submit",
+        "name=Eve&email=nope&ticket=0&guests=1",
+    ));
+    assert_eq!(response.status, 422);
+    assert!(
+        String::from_utf8(response.body)
+            .unwrap()
+            .contains("Not an email address")
+    );
+
+    let response = app.handle(&Request::post(
+        "/submit",
+        "name=Eve&email=eve%40example.org&ticket=0&guests=9",
+    ));
+    assert_eq!(response.status, 422);
+    assert!(store.all().unwrap().is_empty());
+}
+
+#[test]
+fn filled_pdf_reads_back_into_the_submission() {
+    let (store, path) = store("pdf");
+    let app = App::new(store);
+    app.handle(&Request::post("/submit", ADA));
+
+    let response = app.handle(&Request::get("/submissions/1.pdf"));
+    assert_eq!(response.status, 200);
+    assert_eq!(response.content_type, "application/pdf");
+
+    let registration: Registration = elicitor_doc_pdf::from_pdf_form(&response.body).unwrap();
+    assert_eq!(registration.name, "Ada Lovelace");
+    assert_eq!(registration.ticket, Ticket::Student);
+    assert_eq!(registration.guests, 2);
+    assert!(registration.newsletter);
+
+    assert_eq!(app.handle(&Request::get("/submissions/7.pdf")).status, 404);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn serves_requests_over_tcp() {
+    let (store, path) = store("tcp");
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || serve(listener, &App::new(store)));
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "POST /submit HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{ADA}",
+        ADA.len()
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 201 Created\r\n"));
+    assert!(response.contains("Thanks, Ada Lovelace!"));
+
+    std::fs::remove_file(path).unwrap();
+}